@@ -0,0 +1,45 @@
+#![no_main]
+
+use game::utils::{
+    calculate_curve_rate, calculate_earnings_per_ore_increment, calculate_prize_split,
+    calculate_proportion, calculate_prorated_interest, calculate_rank_rewards,
+    calculate_utilization,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u64, u64, u32, [u8; 4], [u8; 10])| {
+    let (amount, duration, ores, [rate, utilization, target, min_rate], payout_curve) = input;
+
+    // Proportions of at most 100% never exceed the amount
+    if let Ok(proportion) = calculate_proportion(amount, rate) {
+        assert!(rate > 100 || proportion <= amount);
+    } else {
+        assert!(rate > 100);
+    }
+
+    // Interest over at most a year at up to 100% never exceeds the principal
+    if let Ok(interest) = calculate_prorated_interest(amount, duration, rate) {
+        assert!(rate > 100 || duration > game::constants::SECONDS_PER_YEAR || interest <= amount);
+    }
+
+    assert!(calculate_utilization(amount, duration).unwrap() <= 100);
+
+    if let Ok(curve_rate) = calculate_curve_rate(utilization, target, min_rate, rate) {
+        assert!(curve_rate <= rate);
+    } else {
+        assert!(min_rate > rate);
+    }
+
+    if let Ok((first_prize, shared_prize)) = calculate_prize_split(amount, ores as u64) {
+        assert!(first_prize as u128 + shared_prize as u128 * (ores as u128 - 1) <= amount as u128);
+    }
+
+    if let Ok(increment) = calculate_earnings_per_ore_increment(amount, ores) {
+        assert!(increment as u128 * ores as u128 <= amount as u128);
+    }
+
+    // Rank rewards always add back up to the rewards
+    if let Some(rank_rewards) = calculate_rank_rewards(amount, &payout_curve).unwrap() {
+        assert_eq!(rank_rewards.iter().map(|&r| r as u128).sum::<u128>(), amount as u128);
+    }
+});
@@ -3,9 +3,6 @@ use anchor_lang::prelude::*;
 /// The super admin public key used by the game.
 pub const SUPER_ADMIN: Pubkey = pubkey!("3aKZLDP9qQWN1iSUUsvxV5eFsjnG7K162aw1suAiUWyW");
 
-/// The main token mint public key used by the game.
-pub const TOKEN_MINT: Pubkey = pubkey!("6mLHbFNMZDFzb3dVEnAthfuwgNuCyAqhGoSzpHtDB5vf");
-
 /// The default player public key used as a baseline or placeholder in the game logic.
 pub const DEFAULT_PLAYER: Pubkey = pubkey!("11111111111111111111111111111111");
 
@@ -24,9 +21,15 @@ pub const PRICE_PER_ORE: u64 = 1_000;
 /// Lamports per token, representing the smallest token unit.
 pub const LAMPORTS_PER_TOKEN: u64 = 1_000_000;
 
+/// Decimals of the main token mint, matching `LAMPORTS_PER_TOKEN`.
+pub const TOKEN_DECIMALS: u8 = 6;
+
 /// Lamports per ORE, computed as `PRICE_PER_ORE * LAMPORTS_PER_TOKEN`.
 pub const LAMPORTS_PER_ORE: u64 = PRICE_PER_ORE * LAMPORTS_PER_TOKEN;
 
+/// Fixed-point scale of `earnings_per_ore`, so rewards shared by many ORE keep their remainders.
+pub const EARNINGS_PER_ORE_PRECISION: u128 = 1_000_000_000_000;
+
 /// Number of seconds in a minute.
 pub const SECONDS_PER_MINUTE: u64 = 60;
 
@@ -34,14 +37,18 @@ pub const SECONDS_PER_MINUTE: u64 = 60;
 pub const SECONDS_PER_HOUR: u64 = SECONDS_PER_MINUTE * 60;
 
 /// Number of seconds in a day (24 * SECONDS_PER_HOUR).
+#[cfg(not(feature = "localnet-defaults"))]
 pub const SECONDS_PER_DAY: u64 = SECONDS_PER_HOUR * 24;
-/// For testing or faster rounds, a "day" is reduced to 5 minutes instead of 24 hours.
-// pub const SECONDS_PER_DAY: u64 = SECONDS_PER_MINUTE * 5;
+/// For localnet tests, a "day" is reduced to 5 minutes instead of 24 hours.
+#[cfg(feature = "localnet-defaults")]
+pub const SECONDS_PER_DAY: u64 = SECONDS_PER_MINUTE * 5;
 
 /// Number of seconds in a year (365 * SECONDS_PER_DAY).
+#[cfg(not(feature = "localnet-defaults"))]
 pub const SECONDS_PER_YEAR: u64 = SECONDS_PER_DAY * 365;
-/// For testing or demonstration, a "year" is shortened to 10 minutes.
-// pub const SECONDS_PER_YEAR: u64 = SECONDS_PER_MINUTE * 10;
+/// For localnet tests, a "year" is shortened to 10 minutes.
+#[cfg(feature = "localnet-defaults")]
+pub const SECONDS_PER_YEAR: u64 = SECONDS_PER_MINUTE * 10;
 
 /// Time extension in seconds for each action performed during the round.
 pub const ACTION_TIME_EXTENSION: u8 = SECONDS_PER_MINUTE as u8;
@@ -49,16 +56,98 @@ pub const ACTION_TIME_EXTENSION: u8 = SECONDS_PER_MINUTE as u8;
 /// Maximum countdown time in seconds (e.g., 1 hour).
 pub const MAX_COUNTDOWN_SECONDS: u16 = SECONDS_PER_HOUR as u16;
 
+/// Default of how far ahead of now, in seconds, a round or period may be scheduled to start (e.g., 7 days).
+pub const MAX_START_TIME_DELAY: u64 = SECONDS_PER_DAY * 7;
+
+/// Longest validity of a session key, in seconds (e.g., 7 days).
+pub const MAX_SESSION_KEY_DURATION: u64 = SECONDS_PER_DAY * 7;
+
+/// Default of how far ahead of now, in seconds, a round or period must at least be scheduled to start.
+pub const MIN_START_LEAD_TIME: u64 = 0;
+
+/// Minimum initial countdown of a round in seconds (e.g., 1 minute).
+pub const MIN_ROUND_COUNTDOWN_DURATION: u64 = SECONDS_PER_MINUTE;
+
+/// Maximum initial countdown of a round in seconds (e.g., 7 days).
+pub const MAX_ROUND_COUNTDOWN_DURATION: u64 = SECONDS_PER_DAY * 7;
+
+/// Minimum leaderboard duration of a period in seconds (e.g., 1 hour).
+pub const MIN_LEADERBOARD_DURATION: u64 = SECONDS_PER_HOUR;
+
+/// Maximum leaderboard duration of a period in seconds (e.g., 90 days).
+pub const MAX_LEADERBOARD_DURATION: u64 = SECONDS_PER_DAY * 90;
+
+/// Remaining countdown in seconds below which a round with ORE held enters its climax (e.g., 5 minutes).
+pub const ROUND_CLIMAX_SECONDS: u64 = SECONDS_PER_MINUTE * 5;
+
+/// Remaining countdowns, in seconds and descending, whose crossing is announced by a `CountdownMilestone` event
+/// (e.g., 1 hour, 10 minutes and 1 minute).
+pub const COUNTDOWN_MILESTONES: [u64; 3] = [
+    SECONDS_PER_HOUR,
+    SECONDS_PER_MINUTE * 10,
+    SECONDS_PER_MINUTE,
+];
+
+/// Maximum number of days over which a round's first-place grand prize can be streamed (e.g., 365 days).
+pub const MAX_GRAND_PRIZE_VESTING_DAYS: u16 = 365;
+
 /// The default exit rewards per second, used as a baseline for exit incentives.
 pub const EXIT_REWARDS_PER_SECOND: u64 = 1 * LAMPORTS_PER_TOKEN;
 
 /// The default sugar rush rewards, used as a baseline for sugar rush incentives.
 pub const SUGAR_RUSH_REWARDS_PER_SECOND: u64 = 10 * LAMPORTS_PER_TOKEN;
 
+/// Default of how long, in seconds, no one must tap candy before the sugar rush rewards start escalating.
+pub const SUGAR_RUSH_ESCALATION_DELAY_SECONDS: u64 = SECONDS_PER_MINUTE;
+
+/// Default growth of the sugar rush multiplier per idle minute, in basis points (e.g., +10%).
+pub const SUGAR_RUSH_ESCALATION_RATE: u16 = 10;
+
+/// Default cap of the sugar rush multiplier, in basis points (e.g., 3x).
+pub const SUGAR_RUSH_MAX_MULTIPLIER: u16 = 300;
+
+/// Default fee to re-enter a round after exiting it, paid into the grand prize pool (the price of one ORE).
+pub const REENTRY_FEE: u64 = LAMPORTS_PER_ORE;
+
 /// The cooldown time in seconds for joining a team, defined as one "day" here.
 pub const TEAM_JOIN_COOLDOWN_SECONDS: u64 = SECONDS_PER_DAY * 1;
 
-/// Fixed reward amount for new player registration: 1500 FGC
+/// Default of the team applications a player may submit per day.
+pub const TEAM_APPLICATIONS_PER_DAY: u8 = 5;
+
+/// Default of the cooldown, in seconds, before a player may apply again to a team that rejected them.
+pub const TEAM_REAPPLICATION_COOLDOWN_SECONDS: u64 = SECONDS_PER_DAY * 7;
+
+/// Default of the inactivity, in seconds, after which a team's captaincy may be claimed: by the longest-tenured
+/// manager after one window, by any member after two.
+pub const CAPTAIN_INACTIVITY_SECONDS: u64 = SECONDS_PER_DAY * 30;
+
+/// Default of the team vault balance above which team reward distributions must be approved by a manager
+/// or wait out the veto period (e.g., 100,000 tokens).
+pub const TEAM_DISTRIBUTION_APPROVAL_THRESHOLD: u64 = 100_000 * LAMPORTS_PER_TOKEN;
+
+/// Default of the veto period, in seconds, a proposed team reward distribution waits for without a manager's approval.
+pub const TEAM_DISTRIBUTION_VETO_SECONDS: u64 = SECONDS_PER_DAY * 2;
+
+/// Default of how long, in seconds, a compliance attestation stays valid after being issued (e.g., 1 hour).
+pub const COMPLIANCE_ATTESTATION_MAX_AGE_SECONDS: u64 = SECONDS_PER_HOUR;
+
+/// Default of how long, in seconds, a player must wait between two of their candy taps.
+pub const CANDY_TAP_COOLDOWN_SECONDS: u64 = SECONDS_PER_MINUTE;
+
+/// How long, in seconds, a player waits before loosening their spend limits or shortening their self-exclusion takes effect.
+pub const RESPONSIBLE_PLAY_COOLDOWN_SECONDS: u64 = SECONDS_PER_DAY * 7;
+
+/// Default of the minimum interval, in seconds, between two candy taps of any players in a round.
+pub const CANDY_TAP_MIN_INTERVAL_SECONDS: u64 = 5;
+
+/// Default of how long, in seconds, the bot may miss its heartbeat before the backup authorities take over.
+pub const BOT_HEARTBEAT_TIMEOUT_SECONDS: u64 = SECONDS_PER_MINUTE * 30;
+
+/// Maximum number of backup authorities standing in for the bot authority.
+pub const MAX_BACKUP_AUTHORITIES: usize = 3;
+
+/// Default initial reward amount for new player registration: 1500 FGC, decayed by the game's registration reward schedule.
 /// Each FGC is represented in lamports, so `REGISTRATION_REWARD` = 1500 * LAMPORTS_PER_TOKEN.
 pub const REGISTRATION_REWARD: u64 = 1_500 * LAMPORTS_PER_TOKEN;
 
@@ -66,10 +155,44 @@ pub const REGISTRATION_REWARD: u64 = 1_500 * LAMPORTS_PER_TOKEN;
 /// This value limits the total airdrop distribution per day to maintain game economy stability.
 pub const DAILY_AIRDROP_REWARDS_CAP: u64 = 500_000 * LAMPORTS_PER_TOKEN;
 
+/// The number of purchase volume tiers configurable on the `Game`, each raising the consumption rewards rate.
+pub const CONSUMPTION_REWARD_TIER_COUNT: usize = 4;
+
+/// The length, in days, of one rolling window of a player's purchase volume.
+pub const PURCHASE_VOLUME_WINDOW_DAYS: u32 = 5;
+
+/// The number of rolling windows making up a player's purchase volume: six windows of five days cover 30 days.
+pub const PURCHASE_VOLUME_WINDOW_COUNT: usize = 6;
+
+/// Cost in vouchers of a streak shield, preserving a player's purchase streak over one missed day (500 FGV).
+pub const STREAK_SHIELD_VOUCHER_COST: u64 = 500 * LAMPORTS_PER_TOKEN;
+
+/// How long, in seconds, a streak shield can be used after it is bought (e.g., 30 days).
+pub const STREAK_SHIELD_DURATION: u64 = SECONDS_PER_DAY * 30;
+
+/// How long, in seconds, credited referral rewards can be collected before they expire back to the referral pool (e.g., 90 days).
+pub const REFERRAL_REWARDS_EXPIRY_SECONDS: u64 = SECONDS_PER_DAY * 90;
+
+/// How long, in seconds, a governance proposal is open for votes after it is created (e.g., 3 days).
+pub const GOVERNANCE_VOTING_SECONDS: u64 = SECONDS_PER_DAY * 3;
+
+/// How long, in seconds, a passed governance proposal waits after its vote before it can be executed (e.g., 2 days).
+pub const GOVERNANCE_TIMELOCK_SECONDS: u64 = SECONDS_PER_DAY * 2;
+
+/// The voting power, in game tokens, that must vote on a governance proposal for it to pass (e.g., 1,000,000 tokens).
+pub const GOVERNANCE_QUORUM: u64 = 1_000_000 * LAMPORTS_PER_TOKEN;
+
 /// The duration (in seconds) for which funds remain locked under normal conditions.
 /// Set to one year (`SECONDS_PER_YEAR`) for a long-term staking scenario.
 pub const LOCK_DURATION: u64 = SECONDS_PER_YEAR;
 
+/// The highest boost, in basis points, a player's stake grants to the construction rewards settled by their purchases (e.g., 25%).
+pub const MAX_STAKE_BOOST_BPS: u16 = 2_500;
+
+/// The vote-escrowed stake granting the highest boost, i.e. the tokens staked for a full `LOCK_DURATION` (e.g., 100,000 tokens).
+/// Each stake counts its amount times its remaining lock over `LOCK_DURATION`, so shorter remaining locks need larger stakes.
+pub const STAKE_BOOST_FULL_BALANCE: u64 = 100_000 * LAMPORTS_PER_TOKEN;
+
 /// The duration (in seconds) during which an early unlock can be requested.
 /// Set to one day (`SECONDS_PER_DAY`) to allow short-term early exits with reduced rewards.
 pub const EARLY_UNLOCK_DURATION: u64 = SECONDS_PER_DAY;
@@ -82,6 +205,22 @@ pub const ANNUAL_RATE: u8 = 100; // 100% APR
 /// `EARLY_UNLOCK_APR = 20` means a 20% annual rate for early unlocking.
 pub const EARLY_UNLOCK_APR: u8 = 20; // 20% APR
 
+/// The lowest APR the utilization curve can assign to new stake orders, reached at full utilization.
+pub const MIN_ANNUAL_RATE: u8 = 20; // 20% APR
+
+/// The pool utilization (in basis points) above which the utilization curve starts lowering the APR.
+pub const TARGET_UTILIZATION: u8 = 80; // 80%
+
+/// Tip paid from the maintenance pool to whoever rolls an ended period over to the next one.
+pub const PERIOD_ROLLOVER_TIP: u64 = 100 * LAMPORTS_PER_TOKEN;
+
+/// Tip paid from the maintenance pool for each counted round end call and each completed matured stake order.
+pub const CRANK_TIP: u64 = 10 * LAMPORTS_PER_TOKEN;
+
+/// Compute units a purchase must stay within, matching the default per-instruction limit so clients
+/// do not need to request a larger compute budget.
+pub const PURCHASE_COMPUTE_UNIT_BUDGET: u32 = 200_000;
+
 /// One million constant for calculations and scaling.
 pub const ONE_MILLION: u64 = 1_000_000;
 
@@ -109,6 +248,9 @@ pub const PLAYER_DATA_SEED: &[u8] = b"player_data";
 /// Seed used to derive the stake order's Program Derived Address (PDA).
 pub const STAKE_ORDER_SEED: &[u8] = b"stake_order";
 
+/// Seed used to derive the stake order index's Program Derived Address (PDA).
+pub const STAKE_ORDER_INDEX_SEED: &[u8] = b"stake_order_index";
+
 /// Seed used to derive the pool's Program Derived Address (PDA).
 pub const STAKE_POOL_SEED: &[u8] = b"stake_pool";
 
@@ -118,6 +260,101 @@ pub const TEAM_NAME_SEED: &[u8] = b"team_name";
 /// Seed used to derive the deposit's Program Derived Address (PDA).
 pub const VAULT_SEED: &[u8] = b"vault";
 
+/// Seed used to derive the deposit receipt's Program Derived Address (PDA).
+pub const DEPOSIT_RECEIPT_SEED: &[u8] = b"deposit_receipt";
+
+/// Seed used to derive the vault ledger's Program Derived Address (PDA).
+pub const VAULT_LEDGER_SEED: &[u8] = b"vault_ledger";
+
+/// Seed used to derive the exchange config's Program Derived Address (PDA).
+pub const EXCHANGE_CONFIG_SEED: &[u8] = b"exchange_config";
+
+/// Seed used to derive the game config's Program Derived Address (PDA).
+pub const GAME_CONFIG_SEED: &[u8] = b"game_config";
+
+/// Seed used to derive the event ring buffer's Program Derived Address (PDA).
+pub const EVENT_RING_BUFFER_SEED: &[u8] = b"event_ring_buffer";
+
+/// Seed used to derive the leaderboard page's Program Derived Address (PDA).
+pub const LEADERBOARD_PAGE_SEED: &[u8] = b"leaderboard_page";
+
+/// Seed used to derive the marketplace listing's Program Derived Address (PDA).
+pub const LISTING_SEED: &[u8] = b"listing";
+
+/// Seed used to derive the round position's Program Derived Address (PDA).
+pub const ROUND_POSITION_SEED: &[u8] = b"round_position";
+
+/// Seed used to derive the round position mint's Program Derived Address (PDA).
+pub const ROUND_POSITION_MINT_SEED: &[u8] = b"round_position_mint";
+
+/// Seed used to derive the session key's Program Derived Address (PDA).
+pub const SESSION_KEY_SEED: &[u8] = b"session_key";
+
+/// Seed used to derive the custodial claim code's Program Derived Address (PDA).
+pub const CLAIM_CODE_SEED: &[u8] = b"claim_code";
+
+/// Seed used to derive the lottery table's Program Derived Address (PDA).
+pub const LOTTERY_TABLE_SEED: &[u8] = b"lottery_table";
+
+/// Seed used to derive the daily stats' Program Derived Address (PDA).
+pub const DAILY_STATS_SEED: &[u8] = b"daily_stats";
+
+/// Seed used to derive a voucher kind's Program Derived Address (PDA).
+pub const VOUCHER_KIND_SEED: &[u8] = b"voucher_kind";
+
+/// Seed used to derive a voucher kind mint's Program Derived Address (PDA).
+pub const VOUCHER_KIND_MINT_SEED: &[u8] = b"voucher_kind_mint";
+
+/// Seed used to derive an airdrop campaign's Program Derived Address (PDA).
+pub const AIRDROP_CAMPAIGN_SEED: &[u8] = b"airdrop_campaign";
+
+/// Seed used to derive a player's airdrop claim record's Program Derived Address (PDA).
+pub const AIRDROP_CLAIM_SEED: &[u8] = b"airdrop_claim";
+
+/// Seed used to derive a merkle airdrop's Program Derived Address (PDA).
+pub const MERKLE_AIRDROP_SEED: &[u8] = b"merkle_airdrop";
+
+/// Seed used to derive a merkle airdrop's claimed bitmap's Program Derived Address (PDA).
+pub const MERKLE_AIRDROP_BITMAP_SEED: &[u8] = b"merkle_airdrop_bitmap";
+
+/// Seed used to derive a period's team quest's Program Derived Address (PDA).
+pub const TEAM_QUEST_SEED: &[u8] = b"team_quest";
+
+/// Seed used to derive a team's team quest completion record's Program Derived Address (PDA).
+pub const TEAM_QUEST_COMPLETION_SEED: &[u8] = b"team_quest_completion";
+
+/// Seed used to derive a round's prediction market's Program Derived Address (PDA).
+pub const PREDICTION_MARKET_SEED: &[u8] = b"prediction_market";
+
+/// Seed used to derive a predictor's prediction's Program Derived Address (PDA).
+pub const PREDICTION_SEED: &[u8] = b"prediction";
+
+/// Seed used to derive a predicted winner's prediction outcome's Program Derived Address (PDA).
+pub const PREDICTION_OUTCOME_SEED: &[u8] = b"prediction_outcome";
+
+/// Seed used to derive a grand prize winner's prize vesting's Program Derived Address (PDA).
+pub const PRIZE_VESTING_SEED: &[u8] = b"prize_vesting";
+
+/// Seed used to derive a team member's pending distribution's Program Derived Address (PDA).
+pub const PENDING_DISTRIBUTION_SEED: &[u8] = b"pending_distribution";
+
+/// Seed used to derive a team's ledger's Program Derived Address (PDA).
+pub const TEAM_LEDGER_SEED: &[u8] = b"team_ledger";
+
+/// Seed used to derive an absorbed team's merge record's Program Derived Address (PDA).
+pub const TEAM_MERGE_SEED: &[u8] = b"team_merge";
+
+/// Seed used to derive a banned wallet's ban entry's Program Derived Address (PDA).
+pub const BAN_ENTRY_SEED: &[u8] = b"ban_entry";
+
+/// Seed used to derive a governance proposal's Program Derived Address (PDA).
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+
+/// Seed used to derive a voter's vote record's Program Derived Address (PDA).
+pub const VOTE_RECORD_SEED: &[u8] = b"vote_record";
+
+/// Defaults of the pool shares and costs, stored in the `GameConfig` account at `initialize`.
+///
 /// Percentage of total purchase allocated to construction worker rewards (25%).
 pub const CONSTRUCTION_POOL_SHARE: u8 = 25;
 
@@ -127,20 +364,85 @@ pub const LOTTERY_POOL_SHARE: u8 = 10;
 /// Percentage of total purchase allocated to referrer rewards (10%).
 pub const REFERRAL_POOL_SHARE: u8 = 10;
 
-/// Percentage of total purchase allocated to grand prizes (30%).
-pub const GRAND_PRIZES_POOL_SHARE: u8 = 30;
+/// Percentage of total purchase allocated to the insurance pool covering payout shortfalls (1%).
+pub const INSURANCE_POOL_SHARE: u8 = 1;
+
+/// Percentage of total purchase allocated to grand prizes (29%).
+/// The grand prize pool is the dust sink: its share is the remainder of the purchase once the other shares are taken,
+/// so it also receives the lamports lost when rounding the other shares down.
+pub const GRAND_PRIZES_POOL_SHARE: u8 = 29;
 
 /// Percentage of total purchase allocated to consumption rewards (10%).
 pub const CONSUMPTION_POOL_SHARE: u8 = 10;
 
-/// Exchange collateral rate used in collateral exchange calculations.
-pub const EXCHANGE_COLLATERAL_RATE: u8 = 100;
-
 /// Redeem voucher rate used when converting vouchers back into tokens.
 pub const REDEEM_VOUCHER_RATE: u8 = 10;
 
-/// Cost in vouchers for one lottery draw (1000 FGV).
+/// Percentage of the price of a filled marketplace listing credited to the developer rewards pool.
+pub const MARKET_FEE_RATE: u8 = 2;
+
+/// Percentage of referral rewards added from the bonus pool when a player collects them as vouchers.
+pub const REFERRAL_VOUCHER_BONUS_RATE: u8 = 10;
+
+/// Highest value of a voucher kind, as a percentage of its face value (a premium credit worth 2x).
+pub const MAX_VOUCHER_KIND_VALUE_RATE: u16 = 200;
+
+/// Cost in vouchers for one draw on the classic lottery table (1000 FGV).
 pub const ONCE_DRAW_LOTTERY_VOUCHER_COST: u64 = 1000 * LAMPORTS_PER_TOKEN;
 
-/// Minimum required lottery pool balance for allowing draws.
+/// Number of stops on each reel of a lottery table; a random byte lands on each stop equally often.
+pub const LOTTERY_REEL_STOPS: usize = 32;
+
+/// Number of distinct symbols a lottery table can show, each with its own multipliers.
+pub const LOTTERY_TABLE_SYMBOLS: usize = 8;
+
+/// Symbols of the classic lottery table shown at each reel stop: one 7 (0), two cherries (1),
+/// three bells (2), four lemons (3) and blanks (4) on the other stops.
+pub const CLASSIC_LOTTERY_REEL_SYMBOLS: [u8; LOTTERY_REEL_STOPS] = [
+    0, 1, 1, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4,
+];
+
+/// Multipliers of the classic lottery table for three 7s, cherries, bells and lemons.
+pub const CLASSIC_LOTTERY_TRIPLE_MULTIPLIERS: [u16; LOTTERY_TABLE_SYMBOLS] =
+    [1000, 100, 50, 20, 0, 0, 0, 0];
+
+/// Multipliers of the classic lottery table for two cherries, bells or lemons.
+pub const CLASSIC_LOTTERY_PAIR_MULTIPLIERS: [u16; LOTTERY_TABLE_SYMBOLS] = [0, 6, 6, 3, 0, 0, 0, 0];
+
+/// Multipliers of the classic lottery table for a single cherry.
+pub const CLASSIC_LOTTERY_SINGLE_MULTIPLIERS: [u16; LOTTERY_TABLE_SYMBOLS] =
+    [0, 3, 0, 0, 0, 0, 0, 0];
+
+/// Default return to player ceiling of a lottery table, as a percentage of its bets.
+/// A table paying out more is paused, protecting the lottery pool from a mistaken multiplier table.
+pub const LOTTERY_MAX_RETURN_TO_PLAYER_RATE: u16 = 120;
+
+/// Default bets a lottery table takes before its return to player ceiling is enforced (10,000 classic draws).
+pub const LOTTERY_RETURN_TO_PLAYER_MIN_BETS: u64 = 10_000 * ONCE_DRAW_LOTTERY_VOUCHER_COST;
+
+/// Default number of slots after its seed slot a lottery randomness seed can still be committed to by a draw.
+pub const RANDOMNESS_SLOT_WINDOW: u64 = 3;
+
+/// Default minimum required lottery pool balance for allowing draws.
+#[cfg(not(feature = "localnet-defaults"))]
 pub const MIN_LOTTERY_REWARDS_POOL_BALANCE: u64 = 100_0000 * LAMPORTS_PER_TOKEN;
+/// For localnet tests, the lottery can be drawn once the pool covers the top prize (1000x) of a single draw.
+#[cfg(feature = "localnet-defaults")]
+pub const MIN_LOTTERY_REWARDS_POOL_BALANCE: u64 = 1000 * ONCE_DRAW_LOTTERY_VOUCHER_COST;
+
+/// The Metaplex Bubblegum program minting the compressed round badges.
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+
+/// The SPL account compression program storing the round badge merkle tree.
+pub const SPL_ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+/// The SPL noop program logging the compressed round badge leaves for indexers.
+pub const SPL_NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+/// The symbol of the round badges.
+pub const ROUND_BADGE_SYMBOL: &str = "ARK";
+
+/// The metadata URI of the round badges, completed with the round number and entry day.
+pub const ROUND_BADGE_METADATA_URI: &str =
+    "https://www.thedoomsdayark.com/meta/badge/metadata.json";
@@ -1,3 +1,4 @@
+use crate::utils::MathError;
 use anchor_lang::prelude::*;
 
 /// Represents the set of possible errors emitted by the program.
@@ -86,10 +87,18 @@ pub enum ErrorCode {
     #[msg("Insufficient funds.")]
     InsufficientFunds,
 
+    /// Emitted when the token mint supplied at initialization does not use the expected decimals.
+    #[msg("Invalid token mint.")]
+    InvalidTokenMint,
+
     /// Emitted when a provided timestamp is outside the acceptable range or format.
     #[msg("Invalid timestamp.")]
     InvalidTimestamp,
 
+    /// Emitted when the remaining accounts passed to a batch instruction are missing or malformed.
+    #[msg("Invalid remaining accounts.")]
+    InvalidRemainingAccounts,
+
     //-------------------------------------------------------------------------
     // Time-related Errors
     //-------------------------------------------------------------------------
@@ -151,6 +160,211 @@ pub enum ErrorCode {
     #[msg("Insufficient number of players eligible for auto-reinvest.")]
     InsufficientAutoReinvestPlayers,
 
+    //-------------------------------------------------------------------------
+    // Round Re-entry Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a player who exited the current round adds ORE to it without re-entering it first.
+    #[msg("The player must re-enter the round after exiting it.")]
+    NeedToReenterRound,
+
+    /// Emitted when a player re-enters a round they have not exited.
+    #[msg("The player has not exited this round.")]
+    PlayerNotExitedFromRound,
+
+    //-------------------------------------------------------------------------
+    // Insurance Pool Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a shortfall exceeds the insurance pool balance.
+    #[msg("Insufficient insurance pool balance.")]
+    InsufficientInsuranceBalance,
+
+    //-------------------------------------------------------------------------
+    // Game Config Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when the game config shares or rates exceed 100%, or the ORE cost or the randomness slot window is zero.
+    #[msg("Invalid game config.")]
+    InvalidGameConfig,
+
+    //-------------------------------------------------------------------------
+    // Bot Failover Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when setting more than `MAX_BACKUP_AUTHORITIES` backup authorities, or a zero bot heartbeat timeout.
+    #[msg("Invalid backup authorities.")]
+    InvalidBackupAuthorities,
+
+    //-------------------------------------------------------------------------
+    // Lottery Table Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a lottery table costs nothing, has no return to player ceiling, or shows a symbol it has no multipliers for.
+    #[msg("Invalid lottery table.")]
+    InvalidLotteryTable,
+
+    /// Emitted when drawing the lottery on a disabled table.
+    #[msg("Lottery table is disabled.")]
+    LotteryTableDisabled,
+
+    //-------------------------------------------------------------------------
+    // Responsible Play Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a self-excluded player tries to purchase ORE or draw the lottery.
+    #[msg("Player is self-excluded.")]
+    PlayerSelfExcluded,
+
+    /// Emitted when a purchase exceeds the player's daily ORE limit.
+    #[msg("Exceeds daily ORE limit.")]
+    ExceedsDailyOreLimit,
+
+    /// Emitted when a lottery draw exceeds the player's daily draw limit.
+    #[msg("Exceeds daily lottery draw limit.")]
+    ExceedsDailyDrawLimit,
+
+    //-------------------------------------------------------------------------
+    // Daily Stats Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when creating the stats of a past day, or passing the stats of another day than today.
+    #[msg("Daily stats day mismatch.")]
+    DailyStatsDayMismatch,
+
+    //-------------------------------------------------------------------------
+    // Voucher Kind Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a voucher kind is worth nothing or more than `MAX_VOUCHER_KIND_VALUE_RATE`.
+    #[msg("Invalid voucher kind value rate.")]
+    InvalidVoucherKindValueRate,
+
+    /// Emitted when paying with or issuing a voucher kind that is disabled.
+    #[msg("Voucher kind is disabled.")]
+    VoucherKindDisabled,
+
+    /// Emitted when only some of the voucher kind accounts of a purchase are passed,
+    /// or they do not belong to the voucher kind.
+    #[msg("Invalid voucher kind accounts.")]
+    InvalidVoucherKindAccounts,
+
+    //-------------------------------------------------------------------------
+    // Round Badge Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when only some of the round badge accounts of a purchase are passed,
+    /// or the merkle tree is not the game's badge tree.
+    #[msg("Invalid round badge accounts.")]
+    InvalidRoundBadgeAccounts,
+
+    //-------------------------------------------------------------------------
+    // Registration Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a registration gate is set without the minimum deposit or attestor it requires.
+    #[msg("Invalid registration gate.")]
+    InvalidRegistrationGate,
+
+    /// Emitted when a registration reward schedule decays below a floor above its initial rewards.
+    #[msg("Invalid registration reward schedule.")]
+    InvalidRegistrationRewardSchedule,
+
+    //-------------------------------------------------------------------------
+    // Airdrop Campaign Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when an airdrop campaign is created without a daily cap, rewards or a valid window.
+    #[msg("Invalid airdrop campaign.")]
+    InvalidAirdropCampaign,
+
+    /// Emitted when an airdrop campaign's rewards are collected while it is disabled or outside its window.
+    #[msg("Airdrop campaign is not active.")]
+    AirdropCampaignNotActive,
+
+    /// Emitted when only some of the airdrop campaign accounts of a collection are passed.
+    #[msg("Invalid airdrop campaign accounts.")]
+    InvalidAirdropCampaignAccounts,
+
+    //-------------------------------------------------------------------------
+    // Merkle Airdrop Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a merkle airdrop is created without tokens or with a claim count out of range.
+    #[msg("Invalid merkle airdrop.")]
+    InvalidMerkleAirdrop,
+
+    /// Emitted when a merkle airdrop claim does not belong to the airdrop's merkle tree.
+    #[msg("Invalid merkle proof.")]
+    InvalidMerkleProof,
+
+    /// Emitted when a merkle airdrop claim was already collected.
+    #[msg("Merkle airdrop already claimed.")]
+    MerkleAirdropAlreadyClaimed,
+
+    /// Emitted when the claims of a merkle airdrop exceed its total amount.
+    #[msg("Merkle airdrop claims exceed its total amount.")]
+    ExceedsMerkleAirdropTotal,
+
+    //-------------------------------------------------------------------------
+    // Consumption Reward Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when the consumption reward tiers are not ascending, exceed 100% or leave gaps.
+    #[msg("Invalid consumption reward tiers.")]
+    InvalidConsumptionRewardTiers,
+
+    //-------------------------------------------------------------------------
+    // Team Quest Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a team quest is created for an ended period, without a goal or bonus, or funded below one bonus.
+    #[msg("Invalid team quest.")]
+    InvalidTeamQuest,
+
+    /// Emitted when a team quest is finalized for a team that did not reach its goal.
+    #[msg("Team quest not completed.")]
+    TeamQuestNotCompleted,
+
+    /// Emitted when a team quest's budget cannot cover another team's bonus.
+    #[msg("Insufficient team quest budget.")]
+    InsufficientTeamQuestBudget,
+
+    //-------------------------------------------------------------------------
+    // Prediction Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a prediction locks no vouchers, or adds to a prediction on another winner.
+    #[msg("Invalid prediction.")]
+    InvalidPrediction,
+
+    /// Emitted when a prediction is placed after the round entered its climax or ended.
+    #[msg("Predictions are closed for this round.")]
+    PredictionsClosed,
+
+    /// Emitted when a prediction market is settled or predicted on after its settlement.
+    #[msg("Prediction market already settled.")]
+    PredictionMarketSettled,
+
+    /// Emitted when a prediction is claimed before its market is settled.
+    #[msg("Prediction market not settled.")]
+    PredictionMarketNotSettled,
+
+    /// Emitted when a prediction is claimed twice.
+    #[msg("Prediction already claimed.")]
+    PredictionAlreadyClaimed,
+
+    /// Emitted when the outcome provided on settlement is not the one of the round's winner.
+    #[msg("Invalid prediction outcome.")]
+    InvalidPredictionOutcome,
+
+    //-------------------------------------------------------------------------
+    // Grand Prize Vesting Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a round's grand prize is set to stream over more than `MAX_GRAND_PRIZE_VESTING_DAYS`.
+    #[msg("Invalid grand prize vesting.")]
+    InvalidGrandPrizeVesting,
+
+    /// Emitted when a streamed grand prize is distributed without its prize vesting account.
+    #[msg("The prize vesting account is required to stream this grand prize.")]
+    PrizeVestingRequired,
+
+    /// Emitted when a streamed grand prize has nothing vested left to claim.
+    #[msg("No vested prize to claim.")]
+    NoVestedPrizeToClaim,
+
+    //-------------------------------------------------------------------------
+    // Streak Shield Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a player buys a streak shield while still holding an unexpired one.
+    #[msg("The player already holds an active streak shield.")]
+    StreakShieldActive,
+
     //-------------------------------------------------------------------------
     // Candy Tap Errors
     //-------------------------------------------------------------------------
@@ -169,7 +383,7 @@ pub enum ErrorCode {
     #[msg("You need to reveal the last result before participating in this lottery.")]
     BeforeThisLotteryNeedToRevealLastResult,
 
-    /// Emitted if the lottery pool is found empty or insufficient for a draw.
+    /// Emitted if the lottery pool is found empty or insufficient for a draw, or to pay a revealed win.
     #[msg("Lottery pool is empty.")]
     LotteryPoolIsEmpty,
 
@@ -187,6 +401,18 @@ pub enum ErrorCode {
     #[msg("Purchase quantity must be greater than 0.")]
     PurchaseQuantityMustGreaterThanZero,
 
+    /// Emitted when purchasing with SOL while no swap pool is whitelisted.
+    #[msg("Swap pool is not configured.")]
+    SwapPoolNotConfigured,
+
+    /// Emitted when the swap accounts do not include the whitelisted swap pool.
+    #[msg("Swap pool mismatch.")]
+    SwapPoolMismatch,
+
+    /// Emitted when the swap returns fewer tokens than the player's minimum.
+    #[msg("Swap output is below the minimum token amount.")]
+    SwapSlippageExceeded,
+
     //-------------------------------------------------------------------------
     // Set Auto Reinvesting Errors
     //-------------------------------------------------------------------------
@@ -284,6 +510,45 @@ pub enum ErrorCode {
     #[msg("Rewards have already been distributed.")]
     AlreadyDistributed,
 
+    /// Emitted when leaderboard rewards are claimed before the period has ended.
+    #[msg("Period has not ended.")]
+    PeriodNotEnded,
+
+    /// Emitted when a leaderboard rank is out of range.
+    #[msg("Invalid leaderboard rank.")]
+    InvalidLeaderboardRank,
+
+    /// Emitted when the claimant does not hold the leaderboard rank in the final standings.
+    #[msg("Not the leaderboard rank holder.")]
+    NotLeaderboardRankHolder,
+
+    /// Emitted when a leaderboard payout curve does not sum to 100%.
+    #[msg("Invalid payout curve.")]
+    InvalidPayoutCurve,
+
+    //-------------------------------------------------------------------------
+    // Leaderboard Page Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a new leaderboard page would not fit below the existing pages.
+    #[msg("Invalid leaderboard page range.")]
+    InvalidLeaderboardPageRange,
+
+    /// Emitted when a leaderboard page has no room for another player.
+    #[msg("Leaderboard page is full.")]
+    LeaderboardPageFull,
+
+    /// Emitted when a leaderboard page or player does not belong to the expected period.
+    #[msg("Leaderboard page mismatch.")]
+    LeaderboardPageMismatch,
+
+    /// Emitted when the higher leaderboard pages are missing or out of order in a rank proof.
+    #[msg("Invalid leaderboard page order.")]
+    InvalidLeaderboardPageOrder,
+
+    /// Emitted when the player is not listed with their current score on the leaderboard page.
+    #[msg("Player not on leaderboard page.")]
+    PlayerNotOnLeaderboardPage,
+
     //-------------------------------------------------------------------------
     // Player Data Errors
     //-------------------------------------------------------------------------
@@ -358,6 +623,96 @@ pub enum ErrorCode {
     #[msg("Stake order is already early unstaked.")]
     StakeOrderAlreadyEarlyUnstaked,
 
+    /// Emitted when the player already holds `MAX_ACTIVE_STAKE_ORDERS` (32) active stake orders.
+    #[msg("Stake order index is full: a player may hold at most 32 active stake orders.")]
+    StakeOrderIndexFull,
+
+    /// Emitted when the stake order is already listed in the player's stake order index.
+    #[msg("Stake order is already indexed.")]
+    StakeOrderAlreadyIndexed,
+
+    /// Emitted when a stake order or one of its accounts does not belong to the expected player.
+    #[msg("Stake order owner mismatch.")]
+    StakeOrderOwnerMismatch,
+
+    /// Emitted when the stake rate curve parameters are inconsistent.
+    #[msg("Invalid stake rate curve.")]
+    InvalidStakeRateCurve,
+
+    /// Emitted when a new stake is attempted while the stake pool is being wound down.
+    #[msg("Stake pool is winding down.")]
+    StakePoolWindingDown,
+
+    /// Emitted when an emergency unstake is attempted while the stake pool operates normally.
+    #[msg("Stake pool is not winding down.")]
+    StakePoolNotWindingDown,
+
+    /// Emitted when a new stake is attempted on a decommissioned stake pool.
+    #[msg("Stake pool is decommissioned.")]
+    StakePoolDecommissioned,
+
+    /// Emitted when the stake pool still has active orders that must mature first.
+    #[msg("Stake pool still has active orders.")]
+    StakePoolHasActiveOrders,
+
+    /// Emitted when a token stake instruction is used on a voucher stake order, or vice versa.
+    #[msg("Stake order asset mismatch.")]
+    StakeOrderAssetMismatch,
+
+    /// Emitted when claiming an early unstake for an order that never requested one.
+    #[msg("Stake order has not requested an early unstake.")]
+    StakeOrderNotEarlyUnstaked,
+
+    /// Emitted when claiming interest on a stake order that has nothing claimable.
+    #[msg("No stake interest to claim.")]
+    NoStakeInterestToClaim,
+
+    //-------------------------------------------------------------------------
+    // Voucher Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when freezing a voucher account while voucher transfers are not restricted.
+    #[msg("Voucher transfers are not restricted.")]
+    VoucherTransfersNotRestricted,
+
+    /// Emitted when the voucher account is already in the requested frozen or thawed state.
+    #[msg("Voucher account is already in the requested state.")]
+    VoucherAccountStateUnchanged,
+
+    /// Emitted when the voucher redemption fee rate exceeds 100%.
+    #[msg("Invalid voucher redemption fee rate.")]
+    InvalidVoucherRedemptionFeeRate,
+
+    //-------------------------------------------------------------------------
+    // Exchange Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when the collateral exchange rate is zero or above `MAX_EXCHANGE_RATE`.
+    #[msg("Invalid exchange rate.")]
+    InvalidExchangeRate,
+
+    /// Emitted when a collateral exchange exceeds the player's daily exchange limit.
+    #[msg("Exceeds daily exchange limit.")]
+    ExceedsDailyExchangeLimit,
+
+    /// Emitted when a collateral exchange exceeds the global exchange cap.
+    #[msg("Exceeds global exchange cap.")]
+    ExceedsExchangeGlobalCap,
+
+    /// Emitted when the oracle exchange rate bounds are zero, inverted or above `MAX_EXCHANGE_RATE`.
+    #[msg("Invalid exchange rate bounds.")]
+    InvalidExchangeRateBounds,
+
+    /// Emitted when the exchange is oracle-priced but the price feed account is missing.
+    #[msg("Price feed account is required.")]
+    PriceFeedRequired,
+
+    /// Emitted when the price feed account does not match the configured feed.
+    #[msg("Price feed mismatch.")]
+    PriceFeedMismatch,
+
+    /// Emitted when the price feed account cannot be parsed.
+    #[msg("Invalid price feed.")]
+    InvalidPriceFeed,
+
     //-------------------------------------------------------------------------
     // Team Errors
     //-------------------------------------------------------------------------
@@ -388,4 +743,244 @@ pub enum ErrorCode {
     /// Emitted when the captain cannot leave the team.
     #[msg("Captain cannot leave the team.")]
     TeamCaptainCannotLeave,
+
+    //-------------------------------------------------------------------------
+    // Invariant Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when an instruction leaves the game state inconsistent, with the `strict-invariants` feature enabled.
+    #[msg("Game invariant violated.")]
+    InvariantViolation,
+
+    //-------------------------------------------------------------------------
+    // Math Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when the reward math overflows, underflows or divides by zero.
+    #[msg("Math overflow.")]
+    MathOverflow,
+
+    //-------------------------------------------------------------------------
+    // Schedule Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a round or period starts outside the game's start time window.
+    #[msg("Start time is outside the allowed window.")]
+    StartTimeOutOfWindow,
+
+    /// Emitted when a round countdown is outside `MIN_ROUND_COUNTDOWN_DURATION..=MAX_ROUND_COUNTDOWN_DURATION`.
+    #[msg("Invalid round countdown duration.")]
+    InvalidRoundDuration,
+
+    /// Emitted when a period leaderboard duration is outside `MIN_LEADERBOARD_DURATION..=MAX_LEADERBOARD_DURATION`.
+    #[msg("Invalid period leaderboard duration.")]
+    InvalidPeriodDuration,
+
+    /// Emitted when the rewards allocated to a round or period exceed the pool funding them.
+    #[msg("Rewards exceed the pool balance.")]
+    RewardsExceedPoolBalance,
+
+    /// Emitted when the minimum lead time of the start time window exceeds its maximum.
+    #[msg("Invalid start time window.")]
+    InvalidStartTimeWindow,
+
+    //-------------------------------------------------------------------------
+    // Marketplace Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a listing is created without vouchers, a position token or a price, or handled by the instructions of the other listing kind.
+    #[msg("Invalid listing.")]
+    InvalidListing,
+
+    /// Emitted when a seller attempts to fill their own listing.
+    #[msg("Cannot fill your own listing.")]
+    CannotFillOwnListing,
+
+    //-------------------------------------------------------------------------
+    // Round Position Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a position is tokenized without ORE or with more ORE than the player holds.
+    #[msg("Invalid position ORE amount.")]
+    InvalidPositionOres,
+
+    //-------------------------------------------------------------------------
+    // Session Key Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a session key is created with no or unknown scopes, or an expiry outside `MAX_SESSION_KEY_DURATION`.
+    #[msg("Invalid session key.")]
+    InvalidSessionKey,
+
+    /// Emitted when a transaction is signed by neither the player nor the session signer of their session key.
+    #[msg("Signer is not authorized for this player.")]
+    SessionSignerMismatch,
+
+    /// Emitted when a session signer uses an expired session key.
+    #[msg("Session key expired.")]
+    SessionKeyExpired,
+
+    /// Emitted when a session signer calls an instruction outside the session's scopes.
+    #[msg("Session key scope not authorized.")]
+    SessionScopeNotAuthorized,
+
+    /// Emitted when purchases through a session exceed its daily ORE limit.
+    #[msg("Session daily ORE limit exceeded.")]
+    SessionDailyOresExceeded,
+
+    //-------------------------------------------------------------------------
+    // Custodial Account Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when the claim code presented does not match the custodial account's claim code hash.
+    #[msg("Invalid claim code.")]
+    InvalidClaimCode,
+
+    /// Emitted when a custodial account is upgraded while still tied to a team, referees, a round, stake orders or a vesting prize.
+    #[msg("Custodial account cannot be upgraded in its current state.")]
+    CustodialAccountNotUpgradable,
+
+    //-------------------------------------------------------------------------
+    // Candy Tap Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a candy tap comes before the player's cooldown or the round's minimum interval has passed.
+    #[msg("Candy tap throttled, try again later.")]
+    CandyTapThrottled,
+
+    /// Emitted when the sugar rush escalation curve caps the multiplier below 1x.
+    #[msg("Invalid sugar rush escalation.")]
+    InvalidSugarRushEscalation,
+
+    /// Emitted when a proportional sugar rush emission has no horizon, or a lowest rate above its highest.
+    #[msg("Invalid sugar rush emission.")]
+    InvalidSugarRushEmission,
+
+    /// Emitted when candy is tapped while the sugar rush rewards pool is empty, leaving nothing to pay.
+    #[msg("The sugar rush rewards pool is empty.")]
+    SugarRushPoolEmpty,
+
+    //-------------------------------------------------------------------------
+    // Team Application Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when team application limits allow no application per day.
+    #[msg("Invalid team application limits.")]
+    InvalidTeamApplicationLimits,
+
+    /// Emitted when a player applies to more teams in a day than the game allows.
+    #[msg("Daily team application cap reached. Please try again tomorrow.")]
+    TeamApplicationDailyCapReached,
+
+    /// Emitted when a player applies again to a team that rejected them before the reapplication cooldown expired.
+    #[msg("Reapplying to this team is on cooldown. Please try again later.")]
+    TeamReapplicationCooldown,
+
+    //-------------------------------------------------------------------------
+    // Team Merge Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a team is merged into itself or the default team, or the absorbed team has pending applications.
+    #[msg("Invalid team merge.")]
+    InvalidTeamMerge,
+
+    /// Emitted when the absorbed team purchased ores in the current period, where it could still be ranked.
+    #[msg("The absorbed team is on the current period's leaderboard.")]
+    AbsorbedTeamRanked,
+
+    //-------------------------------------------------------------------------
+    // Captain Succession Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when the captain inactivity window is set to zero.
+    #[msg("Invalid captain inactivity window.")]
+    InvalidCaptainInactivityWindow,
+
+    /// Emitted when the captaincy is claimed before the captain has been inactive long enough.
+    #[msg("The captain is still active.")]
+    CaptainStillActive,
+
+    /// Emitted when a member other than the longest-tenured manager claims the captaincy before any member may.
+    #[msg("Only the longest-tenured manager may claim the captaincy yet.")]
+    NotNextInSuccession,
+
+    //-------------------------------------------------------------------------
+    // Team Distribution Approval Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when the veto period of team reward distributions is set to zero.
+    #[msg("Invalid team distribution approval settings.")]
+    InvalidTeamDistributionApproval,
+
+    /// Emitted when team rewards are distributed directly out of a team vault above the approval threshold.
+    #[msg("The team vault requires team reward distributions to be proposed and approved.")]
+    TeamDistributionRequiresApproval,
+
+    /// Emitted when an unapproved team reward distribution is executed before its veto period ends.
+    #[msg("The team reward distribution is still in its veto period.")]
+    TeamDistributionInVetoPeriod,
+
+    /// Emitted when a team reward distribution is vetoed after its veto period ended.
+    #[msg("The veto period of the team reward distribution has ended.")]
+    TeamDistributionVetoPeriodEnded,
+
+    //-------------------------------------------------------------------------
+    // Player Ban Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a banned wallet plays.
+    #[msg("The player is banned.")]
+    PlayerBanned,
+
+    //-------------------------------------------------------------------------
+    // Compliance Attestation Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when the compliance attestation settings are invalid.
+    #[msg("Invalid compliance attestation settings.")]
+    InvalidComplianceAttestation,
+
+    /// Emitted when a gated instruction is not preceded by a compliance attestation of the player.
+    #[msg("A compliance attestation of the player is required.")]
+    ComplianceAttestationRequired,
+
+    /// Emitted when the compliance attestation was issued too long ago, or in the future.
+    #[msg("The compliance attestation has expired.")]
+    ComplianceAttestationExpired,
+
+    //-------------------------------------------------------------------------
+    // Governance Errors
+    //-------------------------------------------------------------------------
+    /// Emitted when a vote is cast on a proposal whose voting period has ended.
+    #[msg("The voting period of the proposal has ended.")]
+    ProposalVotingEnded,
+
+    /// Emitted when a proposal is executed before its voting period has ended.
+    #[msg("The proposal is still being voted on.")]
+    ProposalVotingInProgress,
+
+    /// Emitted when a proposal that did not reach the quorum or a majority is executed.
+    #[msg("The proposal did not pass.")]
+    ProposalNotPassed,
+
+    /// Emitted when a passed proposal is executed before its timelock has ended.
+    #[msg("The proposal is still timelocked.")]
+    ProposalTimelocked,
+
+    /// Emitted when a proposal is executed twice.
+    #[msg("The proposal has already been executed.")]
+    ProposalAlreadyExecuted,
+
+    /// Emitted when a voter has no voting power at the proposal's snapshot.
+    #[msg("No voting power at the proposal's snapshot.")]
+    NoVotingPower,
+
+    /// Emitted when an account passed to a migration matches neither a known legacy layout nor the current one.
+    #[msg("Unknown account layout.")]
+    UnknownAccountLayout,
+
+    /// Emitted when the soulbound mode is turned on while the voucher is not the freeze authority of the voucher mint.
+    #[msg("The voucher mint can not be frozen by the voucher.")]
+    VoucherMintNotFreezable,
+
+    /// Emitted when the absorbed team of a merge still has team reward distributions awaiting execution or veto.
+    #[msg("The absorbed team has pending distributions.")]
+    AbsorbedTeamHasPendingDistributions,
+
+    /// Emitted when the token mint charges a transfer fee, which would leave the pools credited with more than
+    /// their vaults receive.
+    #[msg("The token mint charges a transfer fee.")]
+    MintHasTransferFee,
+}
+
+impl From<MathError> for anchor_lang::error::Error {
+    fn from(_: MathError) -> Self {
+        ErrorCode::MathOverflow.into()
+    }
 }
@@ -1,9 +1,43 @@
+use crate::interface::EVENT_AUTHORITY_SEED;
+use crate::state::{
+    AirdropCampaignParams, BurnReason, ConsumptionRewardTier, DefaultWinnerPolicy, DepositPurpose,
+    ExhaustedPool, Game, GameConfigParams, GovernanceAction, LotteryTableParams, MaintenanceCrank,
+    PayoutAsset, ReferralPayout, RegistrationGate, RoundPhase, ShortfallPool,
+    SugarRushEmissionMode, VaultReconciliation,
+};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+
+/// The version of the `TransferEvent` layout, bumped whenever existing `EventType` or `EventData`
+/// variants are reordered or changed. Appending new variants keeps the version unchanged.
+pub const EVENT_SCHEMA_VERSION: u8 = 10;
+
+/// Records `event` in the instruction's `event_ring_buffer` and emits it through a self-CPI, as `emit_cpi!` does.
+/// Every instruction emitting events takes both accounts, so each event is recorded through this macro.
+///
+/// # Arguments
+/// - `ctx`: The instruction context, holding the `event_ring_buffer` and `event_authority` accounts.
+/// - `event`: The `TransferEvent` to record.
+macro_rules! record_event {
+    ($ctx:ident, $event:expr) => {{
+        let event = $event;
+        $ctx.accounts.event_ring_buffer.load_mut()?.push(&event)?;
+        event.emit_cpi(
+            $ctx.accounts.event_authority.to_account_info(),
+            $ctx.bumps.event_authority,
+        )?;
+    }};
+}
 
 #[event]
 /// Represents a generic event emitted by the program, capturing various types of actions, their contexts, and origins.
 /// This event standardizes how different game-related operations are logged on-chain.
 pub struct TransferEvent {
+    /// The `EVENT_SCHEMA_VERSION` the event was emitted with, telling indexers which layout to decode.
+    pub event_schema_version: u8,
+    /// The stable discriminant of `event_type`, unaffected by the position of the variant.
+    pub event_tag: u16,
     /// The category of the event, indicating what kind of action occurred.
     pub event_type: EventType,
     /// The nonce of the event, used to ensure unique event IDs.
@@ -48,7 +82,8 @@ pub enum EventData {
         index: u8,
         grand_prizes: u64,
     },
-    /// Emitted when leaderboard rewards are distributed, indicating which teams and players won.
+    /// Emitted when leaderboard rewards were distributed to the top three teams and the top player.
+    /// Superseded by `DistributeLeaderboardRankRewards`.
     DistributeLeaderboardRewards {
         period: Pubkey,
         team_first: Pubkey,
@@ -87,6 +122,9 @@ pub enum EventData {
         round: Pubkey,
         player: Pubkey,
         last_active_participant: Pubkey,
+        sugar_rush_rewards: u64,
+        idle_seconds: u64,
+        sugar_rush_multiplier: u16,
     },
     /// Emitted when collateral tokens are exchanged for another token or voucher.
     CollateralExchange {
@@ -94,6 +132,8 @@ pub enum EventData {
         voucher: Pubkey,
         exchange_token_amount: u64,
         voucher_amount: u64,
+        exchange_rate: u8,
+        is_oracle_priced: bool,
     },
     /// Emitted when a player collects airdrop rewards allocated to them.
     CollectAirdropReward {
@@ -101,6 +141,7 @@ pub enum EventData {
         player: Pubkey,
         airdrop_rewards: u64,
         voucher: Pubkey,
+        airdrop_campaign: Pubkey,
     },
     /// Emitted when a player collects rewards gained from consumption or spending activities.
     CollectConsumptionRewards {
@@ -109,19 +150,22 @@ pub enum EventData {
         consumption_rewards: u64,
         voucher: Pubkey,
     },
-    /// Emitted when a player collects referral rewards for inviting new participants.
+    /// Emitted when a player collects referral rewards for inviting new participants, in tokens or in vouchers with a bonus.
     CollectReferralReward {
         game: Pubkey,
         player: Pubkey,
         referral_rewards: u64,
+        payout: ReferralPayout,
+        voucher_bonus: u64,
     },
-    /// Emitted when the lottery is drawn, indicating the involved player, randomness provider, and bet amount.
+    /// Emitted when the lottery is drawn, indicating the involved player, randomness provider, bet amount and lottery table.
     DrawLottery {
         game: Pubkey,
         player: Pubkey,
         randomness_provider: Pubkey,
         bet_amount: u64,
         voucher: Pubkey,
+        lottery_table: Pubkey,
     },
     /// Emitted when a player exits the game or round, possibly collecting accrued rewards.
     Exit {
@@ -182,6 +226,7 @@ pub enum EventData {
         player: Pubkey,
         available_ores: u32,
         construction_rewards: u64,
+        carried_over: bool,
     },
     /// Emitted when a player requests an early unstake of their staked tokens.
     RequestEarlyUnstake {
@@ -226,6 +271,7 @@ pub enum EventData {
         team: Pubkey,
         member: Pubkey,
         team_rewards: u64,
+        payout_asset: PayoutAsset,
     },
     /// Emitted when a member is granted managerial privileges within a team.
     GrantManagerPrivileges { team: Pubkey, member: Pubkey },
@@ -243,55 +289,970 @@ pub enum EventData {
         captain: Pubkey,
         new_captain: Pubkey,
     },
+    /// Emitted when the parameters of the stake rate utilization curve are changed.
+    SetStakeRateCurve {
+        stake_pool: Pubkey,
+        min_annual_rate: u8,
+        max_annual_rate: u8,
+        target_utilization: u8,
+    },
+    /// Emitted when the stake pool APR is recomputed from the utilization curve.
+    UpdateStakeRates {
+        stake_pool: Pubkey,
+        utilization: u8,
+        previous_annual_rate: u8,
+        annual_rate: u8,
+    },
+    /// Emitted when the stake pool wind-down flag is toggled.
+    SetStakePoolWindingDown {
+        stake_pool: Pubkey,
+        is_winding_down: bool,
+    },
+    /// Emitted when a player withdraws their principal from a winding-down pool, forfeiting rewards.
+    EmergencyUnstake {
+        player: Pubkey,
+        stake_order: Pubkey,
+        stake_amount: u64,
+        forfeited_token_rewards: u64,
+        stake_pool: Pubkey,
+    },
+    /// Emitted when the stake pool is decommissioned and stops accepting new orders.
+    DecommissionStakePool {
+        stake_pool: Pubkey,
+        active_orders: u32,
+    },
+    /// Emitted when the residual balances of a decommissioned stake pool are swept back to the game vault.
+    SweepStakePool {
+        stake_pool: Pubkey,
+        token_amount: u64,
+        voucher_amount: u64,
+    },
+    /// Emitted when a player stakes vouchers to earn token rewards.
+    StakeVouchers {
+        player: Pubkey,
+        stake_amount: u64,
+        stake_order: Pubkey,
+        stake_pool: Pubkey,
+        annual_rate: u8,
+        lock_duration: u64,
+        token_rewards: u64,
+    },
+    /// Emitted when a player withdraws staked vouchers, with the token rewards paid (zero if forfeited).
+    UnstakeVouchers {
+        player: Pubkey,
+        stake_order: Pubkey,
+        stake_amount: u64,
+        token_rewards: u64,
+        stake_pool: Pubkey,
+    },
+    /// Emitted when a player claims an early-unstaked order, including the rewards forfeited by unlocking early.
+    ClaimEarlyUnstake {
+        player: Pubkey,
+        stake_order: Pubkey,
+        stake_amount: u64,
+        token_rewards: u64,
+        forfeited_token_rewards: u64,
+        forfeited_voucher_rewards: u64,
+        stake_pool: Pubkey,
+    },
+    /// Emitted when a player claims the interest accrued by a stake order, with the total claimed so far.
+    ClaimStakeInterest {
+        player: Pubkey,
+        stake_order: Pubkey,
+        token_rewards: u64,
+        total_token_rewards: u64,
+        stake_pool: Pubkey,
+    },
+    /// Emitted when the authority toggles the voucher soulbound mode.
+    SetVoucherTransferRestriction {
+        voucher: Pubkey,
+        is_transfer_restricted: bool,
+    },
+    /// Emitted when a voucher account is frozen, preventing vouchers from being traded out of the game.
+    FreezeVoucherAccount {
+        voucher: Pubkey,
+        voucher_account: Pubkey,
+    },
+    /// Emitted when a voucher account is thawed.
+    ThawVoucherAccount {
+        voucher: Pubkey,
+        voucher_account: Pubkey,
+    },
+    /// Emitted when the authority updates the voucher redemption fee.
+    SetVoucherRedemptionFee {
+        voucher: Pubkey,
+        redemption_fee_rate: u8,
+    },
+    /// Emitted when a player redeems vouchers for the underlying tokens, net of the redemption fee.
+    RedeemVouchers {
+        player: Pubkey,
+        voucher: Pubkey,
+        voucher_amount: u64,
+        token_amount: u64,
+        fee_amount: u64,
+    },
+    /// Emitted when the authority initializes the collateral exchange configuration.
+    InitializeExchangeConfig {
+        exchange_config: Pubkey,
+        exchange_rate: u8,
+        daily_player_limit: u64,
+        global_cap: u64,
+    },
+    /// Emitted when the authority updates the collateral exchange rate or limits.
+    UpdateExchangeConfig {
+        exchange_config: Pubkey,
+        exchange_rate: u8,
+        daily_player_limit: u64,
+        global_cap: u64,
+    },
+    /// Emitted when the authority configures the oracle price feed of the collateral exchange.
+    SetExchangePriceFeed {
+        exchange_config: Pubkey,
+        price_feed: Pubkey,
+        min_exchange_rate: u8,
+        max_exchange_rate: u8,
+        max_staleness_slots: u64,
+        max_confidence_rate: u8,
+    },
+    /// Emitted when the authority whitelists the AMM pool used for SOL purchases.
+    SetSwapPool {
+        game: Pubkey,
+        swap_program: Pubkey,
+        swap_pool: Pubkey,
+    },
+    /// Emitted when a player swaps SOL for game tokens ahead of a purchase.
+    PurchaseWithSol {
+        player: Pubkey,
+        swap_pool: Pubkey,
+        sol_amount: u64,
+        token_amount: u64,
+    },
+    /// Emitted when a player settles a round, snapshotting their activity counters for analytics.
+    PlayerSnapshot {
+        player: Pubkey,
+        round: Pubkey,
+        purchased_ores: u32,
+        purchase_count: u32,
+        tap_count: u32,
+        draw_count: u32,
+        stake_count: u32,
+    },
+    /// Emitted when a leaderboard page is created for a period.
+    InitializeLeaderboardPage {
+        period: Pubkey,
+        leaderboard_page: Pubkey,
+        page_index: u16,
+        min_score: u32,
+        max_score: u32,
+    },
+    /// Emitted when the crank records a player's score on a leaderboard page.
+    UpdateLeaderboardPage {
+        leaderboard_page: Pubkey,
+        player: Pubkey,
+        score: u32,
+        position: u32,
+    },
+    /// Emitted when a player's rank in a period is proven on-chain.
+    VerifyLeaderboardRank {
+        period: Pubkey,
+        player: Pubkey,
+        score: u32,
+        rank: u32,
+    },
+    /// Emitted when the team or player leaderboard rewards of a period were distributed across all ranks.
+    /// Superseded by `ClaimLeaderboardReward`.
+    /// Ranks held by the default team or player appear with their rewards, which are burned.
+    DistributeLeaderboardRankRewards {
+        period: Pubkey,
+        is_team_leaderboard: bool,
+        recipients: Vec<Pubkey>,
+        rank_rewards: Vec<u64>,
+        burned_rewards: u64,
+    },
+    /// Emitted when a team or player pulls the rewards of its final leaderboard rank.
+    ClaimLeaderboardReward {
+        period: Pubkey,
+        is_team_leaderboard: bool,
+        recipient: Pubkey,
+        rank: u8,
+        rewards: u64,
+        payout_asset: PayoutAsset,
+    },
+    /// Emitted when an ended period is rolled over to the next one, snapshotting its final standings.
+    RolloverPeriod {
+        previous_period: Pubkey,
+        period: Pubkey,
+        top_teams: Vec<Pubkey>,
+        top_players: Vec<Pubkey>,
+        tip: u64,
+        carried_over_rewards: u64,
+    },
+    /// Emitted when the end of a round truncates a period configured to end with it.
+    PeriodTruncated {
+        period: Pubkey,
+        round: Pubkey,
+        original_start_time: u64,
+        original_end_time: u64,
+        start_time: u64,
+        end_time: u64,
+    },
+    /// Emitted when an action moves a round to another `RoundPhase`.
+    RoundPhaseChanged {
+        round: Pubkey,
+        previous_phase: RoundPhase,
+        phase: RoundPhase,
+        available_ores: u32,
+        end_time: u64,
+    },
+    /// Emitted when the game authority changes the window in which rounds and periods may start.
+    SetStartTimeWindow {
+        game: Pubkey,
+        min_start_lead_seconds: u64,
+        max_future_start_seconds: u64,
+    },
+    /// Emitted when a vault's token balance differs from the pool balances it backs.
+    VaultDiscrepancy {
+        vault: Pubkey,
+        ledger: Pubkey,
+        backed_balance: u64,
+        vault_balance: u64,
+    },
+    /// Emitted when a contributor funds a reward pool, recording the receipt created for it.
+    DepositToPool {
+        contributor: Pubkey,
+        receipt: Pubkey,
+        purpose: DepositPurpose,
+        pool: Pubkey,
+        vault: Pubkey,
+        amount: u64,
+    },
+    /// Emitted when a seller escrows vouchers into a new marketplace listing.
+    CreateListing {
+        listing: Pubkey,
+        seller: Pubkey,
+        voucher_amount: u64,
+        price: u64,
+    },
+    /// Emitted when a buyer fills a marketplace listing, paying the seller and the protocol fee.
+    FillListing {
+        listing: Pubkey,
+        seller: Pubkey,
+        buyer: Pubkey,
+        voucher_amount: u64,
+        price: u64,
+        fee: u64,
+    },
+    /// Emitted when a seller cancels a marketplace listing and recovers the escrowed vouchers.
+    CancelListing {
+        listing: Pubkey,
+        seller: Pubkey,
+        voucher_amount: u64,
+    },
+    /// Emitted when a player detaches ORE from their holdings into a tokenized round position.
+    TokenizePosition {
+        round: Pubkey,
+        position: Pubkey,
+        position_mint: Pubkey,
+        player: Pubkey,
+        ores: u32,
+    },
+    /// Emitted when the holder of a round position token redeems it. While the round is ongoing the ORE is
+    /// merged into the holder's holdings; once it is over the position is settled and `construction_rewards` paid out.
+    RedeemPosition {
+        round: Pubkey,
+        position: Pubkey,
+        holder: Pubkey,
+        ores: u32,
+        construction_rewards: u64,
+        is_settled: bool,
+    },
+    /// Emitted when the authority sets the name, symbol and URI of the voucher mint's metadata.
+    SetVoucherMetadata {
+        voucher_mint: Pubkey,
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    /// Emitted when a player authorizes a session signer to act on their behalf.
+    CreateSessionKey {
+        player: Pubkey,
+        session_key: Pubkey,
+        session_signer: Pubkey,
+        scopes: u8,
+        max_daily_ores: u32,
+        token_allowance: u64,
+        expires_at: u64,
+    },
+    /// Emitted when a player revokes their session key.
+    RevokeSessionKey { player: Pubkey, session_key: Pubkey },
+    /// Emitted after `Register` when a sponsor paid the rent of the new player's accounts.
+    SponsorRegistration { player: Pubkey, sponsor: Pubkey },
+    /// Emitted when the bot registers a custodial player account on behalf of a user without a wallet.
+    RegisterCustodial {
+        player: Pubkey,
+        referrer: Pubkey,
+        claim_code: Pubkey,
+    },
+    /// Emitted when a custodial player account is re-keyed to the user's own wallet.
+    UpgradeToSelfCustody {
+        custodial_player: Pubkey,
+        player: Pubkey,
+        token_amount: u64,
+        voucher_amount: u64,
+    },
+    /// Emitted when the game authority changes how often candy taps may happen.
+    SetCandyTapThrottle {
+        game: Pubkey,
+        candy_tap_cooldown_seconds: u64,
+        candy_tap_min_interval_seconds: u64,
+    },
+    /// Emitted when the game authority changes the curve escalating the sugar rush rewards.
+    SetSugarRushEscalation {
+        game: Pubkey,
+        sugar_rush_escalation_delay_seconds: u64,
+        sugar_rush_escalation_rate: u16,
+        sugar_rush_max_multiplier: u16,
+    },
+    /// Emitted when a player re-enters a round they exited, paying the re-entry fee.
+    ReenterRound {
+        round: Pubkey,
+        player: Pubkey,
+        reentry_fee: u64,
+    },
+    /// Emitted when the game authority changes the fee to re-enter a round.
+    SetReentryFee { game: Pubkey, reentry_fee: u64 },
+    /// Emitted when a payout shortfall is covered from the insurance pool.
+    CoverShortfall {
+        pool: ShortfallPool,
+        round: Pubkey,
+        amount: u64,
+        insurance_pool_balance: u64,
+    },
+    /// Emitted whenever game tokens or vouchers are burned, with the running total burned of the mint.
+    Burn {
+        mint: Pubkey,
+        from: Pubkey,
+        amount: u64,
+        reason: BurnReason,
+        total_burned: u64,
+    },
+    /// Emitted when the policy for the rewards won by the default player or team was set.
+    SetDefaultWinnerPolicy {
+        game: Pubkey,
+        policy: DefaultWinnerPolicy,
+    },
+    /// Emitted when the pool shares and costs of the game config were updated.
+    UpdateGameConfig {
+        game_config: Pubkey,
+        params: GameConfigParams,
+    },
+    /// Emitted when the game authority creates a lottery table.
+    CreateLotteryTable {
+        lottery_table: Pubkey,
+        table_id: u16,
+        params: LotteryTableParams,
+    },
+    /// Emitted when the game authority enables or disables a lottery table.
+    SetLotteryTableEnabled {
+        lottery_table: Pubkey,
+        is_enabled: bool,
+    },
+    /// Emitted when a lottery table paid out more than its return to player ceiling and was paused.
+    LotteryTablePaused {
+        lottery_table: Pubkey,
+        total_bets: u64,
+        total_payouts: u64,
+        max_return_to_player_rate: u16,
+    },
+    /// Emitted when a player sets their daily spend limits; looser limits take effect at `unlock_timestamp`, `0` if immediately.
+    SetSpendLimit {
+        player: Pubkey,
+        daily_ore_limit: u32,
+        daily_draw_limit: u32,
+        unlock_timestamp: u64,
+    },
+    /// Emitted when a player excludes themselves from purchases and lottery draws.
+    SelfExclude {
+        player: Pubkey,
+        self_excluded_until: u64,
+    },
+    /// Emitted when the bot authority records its heartbeat.
+    RecordBotHeartbeat {
+        game: Pubkey,
+        last_bot_heartbeat: u64,
+    },
+    /// Emitted when the game authority sets the backup authorities standing in for a stale bot.
+    SetBackupAuthorities {
+        game: Pubkey,
+        backup_authorities: Vec<Pubkey>,
+        bot_heartbeat_timeout_seconds: u64,
+    },
+    /// Emitted when the caller of a permissionless maintenance crank is paid a tip from the maintenance pool.
+    CrankTip {
+        crank: MaintenanceCrank,
+        caller: Pubkey,
+        tip_account: Pubkey,
+        tasks: u32,
+        tip: u64,
+        maintenance_pool_balance: u64,
+    },
+    /// Emitted when the stats account of a day is created.
+    InitializeDailyStats { daily_stats: Pubkey, day: u32 },
+    /// Emitted when the game authority registers a voucher kind.
+    RegisterVoucherKind {
+        voucher_kind: Pubkey,
+        kind_id: u16,
+        voucher_mint: Pubkey,
+        value_rate: u16,
+    },
+    /// Emitted when the game authority changes the value rate of a voucher kind or enables or disables it.
+    UpdateVoucherKind {
+        voucher_kind: Pubkey,
+        value_rate: u16,
+        is_enabled: bool,
+    },
+    /// Emitted when the game authority issues vouchers of a kind, depositing the tokens backing them.
+    IssueVoucherKind {
+        voucher_kind: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        backing_amount: u64,
+        total_supply: u64,
+    },
+    /// Emitted when a player collects their referral, consumption and airdrop rewards in one instruction.
+    /// Categories with nothing to collect are zero.
+    CollectAllRewards {
+        game: Pubkey,
+        player: Pubkey,
+        referral_rewards: u64,
+        referral_payout: ReferralPayout,
+        voucher_bonus: u64,
+        consumption_rewards: u64,
+        airdrop_rewards: u64,
+        voucher: Pubkey,
+        airdrop_campaign: Option<Pubkey>,
+    },
+    /// Emitted when the game authority sets the round badge tree or toggles the badges.
+    SetRoundBadge {
+        game: Pubkey,
+        badge_merkle_tree: Pubkey,
+        is_badge_enabled: bool,
+    },
+    /// Emitted when a player's first purchase in a round mints them a compressed NFT badge.
+    MintRoundBadge {
+        player: Pubkey,
+        round: Pubkey,
+        round_number: u16,
+        entry_day: u32,
+        badge_merkle_tree: Pubkey,
+    },
+    /// Emitted when the game authority sets the gate of the registration rewards.
+    SetRegistrationGate {
+        game: Pubkey,
+        gate: RegistrationGate,
+        min_deposit: u64,
+        attestor: Pubkey,
+    },
+    /// Emitted when the game authority sets the decay schedule of the registration rewards.
+    SetRegistrationRewardSchedule {
+        game: Pubkey,
+        initial_rewards: u64,
+        decay_interval: u64,
+        decay_amount: u64,
+        floor_rewards: u64,
+    },
+    /// Emitted when the game authority creates an airdrop campaign.
+    CreateAirdropCampaign {
+        airdrop_campaign: Pubkey,
+        campaign_id: u16,
+        params: AirdropCampaignParams,
+        game_pool_budget: u64,
+    },
+    /// Emitted when the game authority deposits tokens into an airdrop campaign's budget.
+    FundAirdropCampaign {
+        airdrop_campaign: Pubkey,
+        amount: u64,
+        funded_amount: u64,
+    },
+    /// Emitted when the game authority enables or disables an airdrop campaign.
+    SetAirdropCampaignEnabled {
+        airdrop_campaign: Pubkey,
+        is_enabled: bool,
+    },
+    /// Emitted when the game authority creates a merkle airdrop, depositing its tokens.
+    CreateMerkleAirdrop {
+        merkle_airdrop: Pubkey,
+        airdrop_id: u16,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        claim_count: u32,
+    },
+    /// Emitted when a player claims their share of a merkle airdrop.
+    ClaimMerkleAirdrop {
+        merkle_airdrop: Pubkey,
+        player: Pubkey,
+        index: u32,
+        amount: u64,
+        claimed_amount: u64,
+    },
+    /// Emitted when the game authority sets the consumption reward tiers by purchase volume.
+    SetConsumptionRewardTiers {
+        game: Pubkey,
+        tiers: Vec<ConsumptionRewardTier>,
+    },
+    /// Emitted when the game authority creates a period's team quest, depositing its budget.
+    CreateTeamQuest {
+        team_quest: Pubkey,
+        period: Pubkey,
+        goal_ores: u32,
+        bonus_rewards: u64,
+        funded_amount: u64,
+    },
+    /// Emitted when a team's completion of a team quest is finalized, paying its bonus.
+    FinalizeTeamQuest {
+        team_quest: Pubkey,
+        team: Pubkey,
+        purchased_ores: u32,
+        bonus_rewards: u64,
+    },
+    /// Emitted when a predictor locks vouchers on a round's winner.
+    PlacePrediction {
+        prediction_market: Pubkey,
+        predictor: Pubkey,
+        predicted_winner: Pubkey,
+        amount: u64,
+        total_staked: u64,
+    },
+    /// Emitted when a round's prediction market is settled on its winner.
+    SettlePredictions {
+        prediction_market: Pubkey,
+        round: Pubkey,
+        winner: Pubkey,
+        winning_staked: u64,
+        total_staked: u64,
+    },
+    /// Emitted when a predictor claims the payout of their prediction.
+    ClaimPrediction {
+        prediction_market: Pubkey,
+        predictor: Pubkey,
+        amount: u64,
+        payout: u64,
+    },
+    /// Emitted when the game authority sets the days over which a round's first-place grand prize is streamed.
+    SetGrandPrizeVesting {
+        round: Pubkey,
+        grand_prize_vesting_days: u16,
+    },
+    /// Emitted when a round's first-place grand prize starts streaming to its winner.
+    VestGrandPrize {
+        round: Pubkey,
+        player: Pubkey,
+        prize_vesting: Pubkey,
+        total_amount: u64,
+        end_time: u64,
+    },
+    /// Emitted when a winner claims the vested part of their streamed grand prize.
+    ClaimVestedPrize {
+        prize_vesting: Pubkey,
+        player: Pubkey,
+        amount: u64,
+        claimed_amount: u64,
+    },
+    /// Emitted when a player spends vouchers on a streak shield.
+    BuyStreakShield {
+        player: Pubkey,
+        voucher_cost: u64,
+        streak_shield_expiry: u64,
+    },
+    /// Emitted when the game authority sets the controller emitting the sugar rush rewards.
+    SetSugarRushEmission {
+        game: Pubkey,
+        mode: SugarRushEmissionMode,
+        rewards_per_second: u64,
+        horizon_seconds: u64,
+        min_rewards_per_second: u64,
+        effective_rewards_per_second: u64,
+    },
+    /// Emitted with every candy tap, recording the sugar rush rewards per second it was paid at.
+    SugarRushEmission {
+        round: Pubkey,
+        mode: SugarRushEmissionMode,
+        rewards_per_second: u64,
+        sugar_rush_rewards_pool_balance: u64,
+    },
+    /// Emitted when a reward pool cannot pay a reward in full, the payout being capped at its remaining balance.
+    PoolExhausted {
+        pool: ExhaustedPool,
+        requested_amount: u64,
+        paid_amount: u64,
+        pool_balance: u64,
+    },
+    /// Emitted when the game authority changes the limits on team applications.
+    SetTeamApplicationLimits {
+        game: Pubkey,
+        max_team_applications_per_day: u8,
+        team_reapplication_cooldown_seconds: u64,
+    },
+    /// Emitted when two captains merge their teams, the absorbed team being closed.
+    MergeTeams {
+        surviving_team: Pubkey,
+        absorbed_team: Pubkey,
+        moved_members: u16,
+        moved_tokens: u64,
+    },
+    /// Emitted when the game authority changes the captain inactivity after which a captaincy may be claimed.
+    SetCaptainInactivityWindow {
+        game: Pubkey,
+        captain_inactivity_seconds: u64,
+    },
+    /// Emitted when a member claims the captaincy of a team whose captain went inactive.
+    ClaimCaptaincy {
+        team: Pubkey,
+        previous_captain: Pubkey,
+        new_captain: Pubkey,
+        captain_inactive_seconds: u64,
+    },
+    /// Emitted when the game authority changes the approval required by team reward distributions out of large team vaults.
+    SetTeamDistributionApproval {
+        game: Pubkey,
+        team_distribution_approval_threshold: u64,
+        team_distribution_veto_seconds: u64,
+    },
+    /// Emitted when a captain proposes a team reward distribution, executable once approved or after its veto period.
+    ProposeTeamDistribution {
+        team: Pubkey,
+        member: Pubkey,
+        team_rewards: u64,
+        executable_timestamp: u64,
+    },
+    /// Emitted when a manager vetoes a proposed team reward distribution.
+    VetoTeamDistribution {
+        team: Pubkey,
+        member: Pubkey,
+        team_rewards: u64,
+        manager: Pubkey,
+    },
+    /// Emitted when the game authority bans a wallet from the game.
+    BanPlayer { player: Pubkey, reason_code: u16 },
+    /// Emitted when the game authority lifts the ban of a wallet.
+    UnbanPlayer { player: Pubkey },
+    /// Emitted when the game authority changes the compliance attestation gating selected instructions.
+    SetComplianceAttestation {
+        game: Pubkey,
+        compliance_signer: Pubkey,
+        compliance_gated_instructions: u8,
+        compliance_attestation_max_age_seconds: u64,
+    },
+    /// Emitted when a crank call finds nothing to do, instead of failing the transaction.
+    /// `reason_code` is the discriminant of `reason`, stable across schema versions.
+    SoftFailure {
+        reason: SoftFailureReason,
+        reason_code: u16,
+    },
+    /// Emitted when the countdown of a round reaches one of the `COUNTDOWN_MILESTONES`, as a side effect of the call
+    /// that observed it, so that clients can push notifications without polling `end_time`.
+    CountdownMilestone {
+        round: Pubkey,
+        milestone_seconds: u64,
+        end_time: u64,
+    },
+    /// Emitted when the referral rewards a referrer left uncollected past their expiry are swept back to the referral pool.
+    SweepExpiredReferralRewards {
+        referrer: Pubkey,
+        expired_referral_rewards: u64,
+        remaining_referral_rewards: u64,
+        total_expired_referral_rewards: u64,
+    },
+    /// Emitted by every `reconcile_game` call, comparing the game, round and period vaults with the balances
+    /// recomputed from their pool fields.
+    ReconciliationReport {
+        game_vault: VaultReconciliation,
+        round_vault: VaultReconciliation,
+        period_vault: VaultReconciliation,
+        is_balanced: bool,
+    },
+    /// Emitted when a player creates a governance proposal.
+    CreateProposal {
+        proposal: Pubkey,
+        proposal_number: u32,
+        proposer: Pubkey,
+        action: GovernanceAction,
+        snapshot_period: Pubkey,
+        voting_end_timestamp: u64,
+        executable_timestamp: u64,
+    },
+    /// Emitted when a player votes on a governance proposal.
+    CastVote {
+        proposal: Pubkey,
+        voter: Pubkey,
+        is_in_favor: bool,
+        voting_power: u64,
+        votes_for: u64,
+        votes_against: u64,
+    },
+    /// Emitted when a passed governance proposal is executed, applying its parameter change.
+    ExecuteProposal {
+        proposal: Pubkey,
+        action: GovernanceAction,
+        votes_for: u64,
+        votes_against: u64,
+    },
+    /// Emitted when a purchase boosts the construction rewards it settles by the player's stake boost.
+    ConstructionRewardsBoosted {
+        player: Pubkey,
+        construction_rewards: u64,
+        boost_factor_bps: u16,
+        boost: u64,
+    },
+    /// Emitted when a holder offers a tokenized round position on the marketplace.
+    CreatePositionListing {
+        listing: Pubkey,
+        seller: Pubkey,
+        round_position: Pubkey,
+        ores: u32,
+        price: u64,
+    },
+    /// Emitted when a buyer fills a marketplace listing of a round position.
+    FillPositionListing {
+        listing: Pubkey,
+        seller: Pubkey,
+        buyer: Pubkey,
+        round_position: Pubkey,
+        price: u64,
+        fee: u64,
+    },
+    /// Emitted when a seller cancels a marketplace listing of a round position and recovers the position token.
+    CancelPositionListing {
+        listing: Pubkey,
+        seller: Pubkey,
+        round_position: Pubkey,
+    },
 }
 
 /// Classifies event types into a known set of categories, mirroring variants of `EventData`.
 /// This enum helps in quickly identifying the nature of the event without parsing the entire payload.
+///
+/// Each variant carries an explicit discriminant emitted as the stable `event_tag` of `TransferEvent`.
+/// Existing discriminants never change; new variants are appended with the next free value.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[repr(u16)]
 pub enum EventType {
-    AutoReinvest,
-    CollectDeveloperRewards,
-    CreatePeriod,
-    CreateRound,
-    DistributeGrandPrizes,
-    DistributeLeaderboardRewards,
-    InitializeDefaultPlayer,
-    InitializeDefaultTeam,
-    InitializeStakeTokenPool,
-    InitializeStakeVoucherPool,
-    InitializeVault,
-    InitializeVoucher,
-    Initialize,
-    CancelIsAutoReinvesting,
-    CandyTap,
-    CollateralExchange,
-    CollectAirdropReward,
-    CollectConsumptionRewards,
-    CollectReferralReward,
-    DrawLottery,
-    Exit,
-    Purchase,
-    RoundEnd,
-    Register,
-    Reinvest,
-    RevealDrawLotteryResult,
-    SetIsAutoReinvesting,
-    SetReferrer,
-    SettlePreviousRound,
-    RequestEarlyUnstake,
-    Stake,
-    Unstake,
-    Deposit,
-    AcceptTeamApplication,
-    ApplyToJoinTeam,
-    CreateTeam,
-    DistributeTeamRewards,
-    GrantManagerPrivileges,
-    LeaveTeam,
-    RejectTeamApplication,
-    RemoveMemberFromTeam,
-    RevokeManagerPrivileges,
-    TransferTeamCaptaincy,
+    AutoReinvest = 0,
+    CollectDeveloperRewards = 1,
+    CreatePeriod = 2,
+    CreateRound = 3,
+    DistributeGrandPrizes = 4,
+    DistributeLeaderboardRewards = 5,
+    InitializeDefaultPlayer = 6,
+    InitializeDefaultTeam = 7,
+    InitializeStakeTokenPool = 8,
+    InitializeStakeVoucherPool = 9,
+    InitializeVault = 10,
+    InitializeVoucher = 11,
+    Initialize = 12,
+    CancelIsAutoReinvesting = 13,
+    CandyTap = 14,
+    CollateralExchange = 15,
+    CollectAirdropReward = 16,
+    CollectConsumptionRewards = 17,
+    CollectReferralReward = 18,
+    DrawLottery = 19,
+    Exit = 20,
+    Purchase = 21,
+    RoundEnd = 22,
+    Register = 23,
+    Reinvest = 24,
+    RevealDrawLotteryResult = 25,
+    SetIsAutoReinvesting = 26,
+    SetReferrer = 27,
+    SettlePreviousRound = 28,
+    RequestEarlyUnstake = 29,
+    Stake = 30,
+    Unstake = 31,
+    Deposit = 32,
+    AcceptTeamApplication = 33,
+    ApplyToJoinTeam = 34,
+    CreateTeam = 35,
+    DistributeTeamRewards = 36,
+    GrantManagerPrivileges = 37,
+    LeaveTeam = 38,
+    RejectTeamApplication = 39,
+    RemoveMemberFromTeam = 40,
+    RevokeManagerPrivileges = 41,
+    TransferTeamCaptaincy = 42,
+    SetStakeRateCurve = 43,
+    UpdateStakeRates = 44,
+    SetStakePoolWindingDown = 45,
+    EmergencyUnstake = 46,
+    DecommissionStakePool = 47,
+    SweepStakePool = 48,
+    StakeVouchers = 49,
+    UnstakeVouchers = 50,
+    ClaimEarlyUnstake = 51,
+    ClaimStakeInterest = 52,
+    SetVoucherTransferRestriction = 53,
+    FreezeVoucherAccount = 54,
+    ThawVoucherAccount = 55,
+    SetVoucherRedemptionFee = 56,
+    RedeemVouchers = 57,
+    InitializeExchangeConfig = 58,
+    UpdateExchangeConfig = 59,
+    SetExchangePriceFeed = 60,
+    SetSwapPool = 61,
+    PurchaseWithSol = 62,
+    PlayerSnapshot = 63,
+    InitializeLeaderboardPage = 64,
+    UpdateLeaderboardPage = 65,
+    VerifyLeaderboardRank = 66,
+    DistributeLeaderboardRankRewards = 67,
+    ClaimLeaderboardReward = 68,
+    RolloverPeriod = 69,
+    PeriodTruncated = 70,
+    RoundPhaseChanged = 71,
+    SetStartTimeWindow = 72,
+    VaultDiscrepancy = 73,
+    DepositToPool = 74,
+    CreateListing = 75,
+    FillListing = 76,
+    CancelListing = 77,
+    TokenizePosition = 78,
+    RedeemPosition = 79,
+    SetVoucherMetadata = 80,
+    CreateSessionKey = 81,
+    RevokeSessionKey = 82,
+    SponsorRegistration = 83,
+    RegisterCustodial = 84,
+    UpgradeToSelfCustody = 85,
+    SetCandyTapThrottle = 86,
+    SetSugarRushEscalation = 87,
+    ReenterRound = 88,
+    SetReentryFee = 89,
+    CoverShortfall = 90,
+    Burn = 91,
+    SetDefaultWinnerPolicy = 92,
+    UpdateGameConfig = 93,
+    CreateLotteryTable = 94,
+    SetLotteryTableEnabled = 95,
+    LotteryTablePaused = 96,
+    SetSpendLimit = 97,
+    SelfExclude = 98,
+    RecordBotHeartbeat = 99,
+    SetBackupAuthorities = 100,
+    CrankTip = 101,
+    InitializeDailyStats = 102,
+    RegisterVoucherKind = 103,
+    UpdateVoucherKind = 104,
+    IssueVoucherKind = 105,
+    CollectAllRewards = 106,
+    SetRoundBadge = 107,
+    MintRoundBadge = 108,
+    SetRegistrationGate = 109,
+    SetRegistrationRewardSchedule = 110,
+    CreateAirdropCampaign = 111,
+    FundAirdropCampaign = 112,
+    SetAirdropCampaignEnabled = 113,
+    CreateMerkleAirdrop = 114,
+    ClaimMerkleAirdrop = 115,
+    SetConsumptionRewardTiers = 116,
+    CreateTeamQuest = 117,
+    FinalizeTeamQuest = 118,
+    PlacePrediction = 119,
+    SettlePredictions = 120,
+    ClaimPrediction = 121,
+    SetGrandPrizeVesting = 122,
+    VestGrandPrize = 123,
+    ClaimVestedPrize = 124,
+    BuyStreakShield = 125,
+    SetSugarRushEmission = 126,
+    SugarRushEmission = 127,
+    PoolExhausted = 128,
+    SetTeamApplicationLimits = 129,
+    MergeTeams = 130,
+    SetCaptainInactivityWindow = 131,
+    ClaimCaptaincy = 132,
+    SetTeamDistributionApproval = 133,
+    ProposeTeamDistribution = 134,
+    VetoTeamDistribution = 135,
+    BanPlayer = 136,
+    UnbanPlayer = 137,
+    SetComplianceAttestation = 138,
+    SoftFailure = 139,
+    CountdownMilestone = 140,
+    SweepExpiredReferralRewards = 141,
+    ReconciliationReport = 142,
+    CreateProposal = 143,
+    CastVote = 144,
+    ExecuteProposal = 145,
+    ConstructionRewardsBoosted = 146,
+    CreatePositionListing = 147,
+    FillPositionListing = 148,
+    CancelPositionListing = 149,
+}
+
+/// Why a crank call found nothing to do. Such calls succeed and emit a `SoftFailure` event rather than failing,
+/// so that indexers can tell "nothing to do" apart from hard errors. The discriminants are the machine-readable
+/// `reason_code`s of the event, and, like `EventType`, are append-only.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u16)]
+pub enum SoftFailureReason {
+    /// A round-end call of `purchase` came within 150 slots of the previous one, and was not counted.
+    RoundEndCallTooSoon = 0,
+    /// `unstake_matured_batch` found no matured token stake order to complete.
+    NoMaturedStakeOrders = 1,
+    /// `decommission_stake_pool` could not sweep the pool yet, stake orders being still active.
+    StakeOrdersOutstanding = 2,
+    /// `sweep_expired_referral_rewards` found no expired referral rewards to sweep.
+    NoExpiredReferralRewards = 3,
+}
+
+impl TransferEvent {
+    /// Emits the event through a self-CPI signed by the event authority, the expansion of `emit_cpi!`
+    /// for callers that do not hold the whole instruction context.
+    ///
+    /// # Arguments
+    /// - `event_authority`: The program's event authority account.
+    /// - `event_authority_bump`: The bump of the event authority PDA.
+    pub fn emit_cpi(&self, event_authority: AccountInfo, event_authority_bump: u8) -> Result<()> {
+        let ix_data: Vec<u8> = anchor_lang::event::EVENT_IX_TAG_LE
+            .into_iter()
+            .chain(anchor_lang::Event::data(self))
+            .collect();
+        let ix = Instruction::new_with_bytes(
+            crate::ID,
+            &ix_data,
+            vec![AccountMeta::new_readonly(*event_authority.key, true)],
+        );
+        invoke_signed(
+            &ix,
+            &[event_authority],
+            &[&[EVENT_AUTHORITY_SEED, &[event_authority_bump]]],
+        )
+        .map_err(Into::into)
+    }
+
+    /// Builds the `SoftFailure` event of a crank call that found nothing to do, taking the next event nonce of `game`.
+    /// Like any event, it is then recorded with `record_event!`.
+    pub fn soft_failure(
+        game: &mut Game,
+        reason: SoftFailureReason,
+        initiator: Pubkey,
+        timestamp: u64,
+    ) -> Result<Self> {
+        game.increment_event_nonce()?;
+        Ok(TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::SoftFailure as u16,
+            event_type: EventType::SoftFailure,
+            event_nonce: game.event_nonce,
+            data: EventData::SoftFailure {
+                reason,
+                reason_code: reason as u16,
+            },
+            initiator_type: InitiatorType::SYSTEM,
+            initiator,
+            timestamp,
+        })
+    }
 }
 
 /// Identifies the nature of the entity initiating the event.
@@ -0,0 +1,155 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_SEED,
+    VOTE_RECORD_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use solana_program::sysvar::clock::Clock;
+
+/// The `CastVote` instruction lets a player vote on a governance proposal, once, with their voting power at its snapshot:
+/// the tokens and vouchers staked by their orders created before the proposal and not completed,
+/// plus the ORE they purchased in the proposal's snapshot period, valued at the game config's `lamports_per_ore`.
+/// The voter's stake orders are passed through `remaining_accounts`, by ascending order number.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    /// The player voting, paying the rent of the vote record.
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    /// The voter's player data, holding the ORE purchased in the snapshot period.
+    #[account(seeds = [PLAYER_DATA_SEED, voter.key().as_ref()], bump)]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game config valuing the ORE purchased.
+    #[account(seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// The proposal voted on.
+    #[account(mut)]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// The vote record, whose creation prevents the voter from voting twice.
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteRecord::INIT_SPACE,
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
+    )]
+    pub vote_record: Box<Account<'info, VoteRecord>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Votes on the proposal in favor or against it.
+///
+/// Steps:
+/// 1. Sum the stake of the voter's orders in `remaining_accounts`, verifying each is derived from the voter and its own
+///    order number, and counting only the orders created before the proposal and not completed.
+/// 2. Add the ORE the voter purchased in the snapshot period, valued at `lamports_per_ore`.
+/// 3. Cast the voting power on the proposal while the vote is open, and record the vote.
+/// 4. Emit a `CastVote` event to log the vote on-chain.
+pub fn cast_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CastVote<'info>>,
+    is_in_favor: bool,
+) -> Result<()> {
+    // Fetch the current UNIX timestamp to check the vote is open
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let program_id = ctx.program_id;
+    let remaining_accounts = ctx.remaining_accounts;
+
+    // Extract references for clarity
+    let CastVote {
+        voter,
+        player_data,
+        game,
+        game_config,
+        proposal,
+        vote_record,
+        ..
+    } = ctx.accounts;
+
+    // Stake orders are listed by ascending order number, so none is counted twice
+    let mut staked_amount: u64 = 0;
+    let mut last_order_number: Option<u16> = None;
+    for account in remaining_accounts {
+        let stake_order: Account<'info, StakeOrder> = Account::try_from(account)?;
+        let order_number = stake_order.stake_number;
+        let stake_order_address = Pubkey::create_program_address(
+            &[
+                STAKE_ORDER_SEED,
+                voter.key().as_ref(),
+                order_number.to_le_bytes().as_ref(),
+                &[stake_order.bump],
+            ],
+            program_id,
+        )
+        .map_err(|_| ErrorCode::StakeOrderOwnerMismatch)?;
+        require_keys_eq!(
+            stake_order.key(),
+            stake_order_address,
+            ErrorCode::StakeOrderOwnerMismatch
+        );
+        require!(
+            last_order_number.map_or(true, |last| order_number > last),
+            ErrorCode::InvalidRemainingAccounts
+        );
+        last_order_number = Some(order_number);
+
+        if !stake_order.is_completed && stake_order.created_timestamp <= proposal.snapshot_timestamp
+        {
+            staked_amount = staked_amount.safe_add(stake_order.stake_amount)?;
+        }
+    }
+
+    let purchased_ores = player_data.period_purchased_ores(proposal.snapshot_period);
+    let voting_power =
+        staked_amount.safe_add((purchased_ores as u64).safe_mul(game_config.lamports_per_ore)?)?;
+
+    proposal.cast_vote(is_in_favor, voting_power, timestamp)?;
+    vote_record.initialize(
+        proposal.key(),
+        voter.key(),
+        is_in_favor,
+        voting_power,
+        ctx.bumps.vote_record,
+    );
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the vote
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CastVote as u16,
+        event_type: EventType::CastVote,
+        event_nonce: game.event_nonce,
+        data: EventData::CastVote {
+            proposal: proposal.key(),
+            voter: voter.key(),
+            is_in_favor,
+            voting_power,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: voter.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, GOVERNANCE_TIMELOCK_SECONDS, GOVERNANCE_VOTING_SECONDS,
+    PLAYER_DATA_SEED, PROPOSAL_SEED,
+};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `CreateProposal` instruction lets a player put a game parameter change to the vote of the community.
+/// The proposal snapshots the game's previous period, whose purchased ORE counts as voting power, and its creation time,
+/// before which stake orders must have been created to count. It is open for `GOVERNANCE_VOTING_SECONDS`, and a passed
+/// proposal is executed through `execute_proposal` once `GOVERNANCE_TIMELOCK_SECONDS` have passed after the vote.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    /// The player creating the proposal, paying its rent.
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    /// The proposer's player data, ensuring they are registered.
+    #[account(seeds = [PLAYER_DATA_SEED, proposer.key().as_ref()], bump)]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The global game account, numbering the proposals.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The new proposal, derived from the game's `proposal_nonce`.
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [PROPOSAL_SEED, game.proposal_nonce.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a proposal applying `action`.
+///
+/// Steps:
+/// 1. Record the proposal with its snapshot, voting period and timelock, and increment the game's `proposal_nonce`.
+/// 2. Emit a `CreateProposal` event to log the proposal on-chain.
+pub fn create_proposal(ctx: Context<CreateProposal>, action: GovernanceAction) -> Result<()> {
+    // Fetch the current UNIX timestamp to take the snapshot
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references for clarity
+    let CreateProposal {
+        proposer,
+        game,
+        proposal,
+        ..
+    } = ctx.accounts;
+
+    let proposal_number = game.proposal_nonce;
+    proposal.initialize(
+        proposal_number,
+        proposer.key(),
+        ProposalParams {
+            action: action.clone(),
+            snapshot_period: game.previous_period,
+            voting_seconds: GOVERNANCE_VOTING_SECONDS,
+            timelock_seconds: GOVERNANCE_TIMELOCK_SECONDS,
+        },
+        timestamp,
+        ctx.bumps.proposal,
+    )?;
+    game.increment_proposal_nonce()?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the proposal
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CreateProposal as u16,
+        event_type: EventType::CreateProposal,
+        event_nonce: game.event_nonce,
+        data: EventData::CreateProposal {
+            proposal: proposal.key(),
+            proposal_number,
+            proposer: proposer.key(),
+            action,
+            snapshot_period: proposal.snapshot_period,
+            voting_end_timestamp: proposal.voting_end_timestamp,
+            executable_timestamp: proposal.executable_timestamp,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: proposer.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
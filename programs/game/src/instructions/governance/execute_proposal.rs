@@ -0,0 +1,93 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, GOVERNANCE_QUORUM, STAKE_POOL_SEED,
+};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `ExecuteProposal` instruction lets anyone apply the parameter change of a governance proposal that reached
+/// `GOVERNANCE_QUORUM` with a majority in favor, once its timelock has ended.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    /// The caller executing the proposal.
+    pub caller: Signer<'info>,
+
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game config updated by an `UpdateGameConfig` action.
+    #[account(mut, seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// The stake pool updated by a `SetStakeRateCurve` action.
+    #[account(mut, seeds = [STAKE_POOL_SEED], bump)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The proposal executed.
+    #[account(mut)]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the proposal.
+///
+/// Steps:
+/// 1. Check the vote has ended, passed, and the timelock has ended, and mark the proposal as executed.
+/// 2. Apply its action to the game config or the stake pool, validated as `update_game_config` and `set_stake_rate_curve` do.
+/// 3. Emit an `ExecuteProposal` event to log the change on-chain.
+pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+    // Fetch the current UNIX timestamp to check the timelock
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references for clarity
+    let ExecuteProposal {
+        caller,
+        game,
+        game_config,
+        stake_pool,
+        proposal,
+        ..
+    } = ctx.accounts;
+
+    proposal.execute(GOVERNANCE_QUORUM, timestamp)?;
+
+    match &proposal.action {
+        GovernanceAction::UpdateGameConfig { params } => game_config.update(params)?,
+        GovernanceAction::SetStakeRateCurve {
+            min_annual_rate,
+            max_annual_rate,
+            target_utilization,
+        } => {
+            stake_pool.update_rate_curve(*min_annual_rate, *max_annual_rate, *target_utilization)?
+        }
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the change
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::ExecuteProposal as u16,
+        event_type: EventType::ExecuteProposal,
+        event_nonce: game.event_nonce,
+        data: EventData::ExecuteProposal {
+            proposal: proposal.key(),
+            action: proposal.action.clone(),
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: caller.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
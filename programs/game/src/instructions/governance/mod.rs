@@ -0,0 +1,6 @@
+pub mod cast_vote;
+pub mod create_proposal;
+pub mod execute_proposal;
+pub use cast_vote::*;
+pub use create_proposal::*;
+pub use execute_proposal::*;
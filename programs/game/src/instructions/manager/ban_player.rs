@@ -0,0 +1,77 @@
+use crate::constants::{BAN_ENTRY_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `BanPlayer` instruction lets the game authority ban a wallet abusing an exploit while a fix ships.
+/// Banned wallets are rejected by `purchase`, its variants, `register`, `register_sponsored`, `draw_lottery`,
+/// and team joins, with `PlayerBanned`.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct BanPlayer<'info> {
+    /// The authority (signer) authorized to manage the game, paying the rent of the ban entry.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The global game account.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The ban entry of the wallet, whose existence bans it.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BanEntry::INIT_SPACE,
+        seeds = [BAN_ENTRY_SEED, wallet.as_ref()],
+        bump,
+    )]
+    pub ban_entry: Box<Account<'info, BanEntry>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Executes the `BanPlayer` instruction:
+///
+/// - Creates the ban entry of `wallet`, recording `reason_code`.
+/// - Emits a `BanPlayer` event to record the ban on-chain.
+pub fn ban_player(ctx: Context<BanPlayer>, wallet: Pubkey, reason_code: u16) -> Result<()> {
+    // Obtain the current UNIX timestamp for the ban and event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let BanPlayer {
+        authority,
+        game,
+        ban_entry,
+        ..
+    } = ctx.accounts;
+
+    ban_entry.initialize(wallet, reason_code, timestamp, ctx.bumps.ban_entry);
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the ban
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::BanPlayer as u16,
+        event_type: EventType::BanPlayer,
+        event_nonce: game.event_nonce,
+        data: EventData::BanPlayer {
+            player: wallet,
+            reason_code,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -1,14 +1,15 @@
-use crate::constants::{GAME_SEED, TOKEN_MINT};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
-use crate::state::Game;
-use crate::utils::{to_timestamp_u64, transfer_from_token_vault_to_token_account};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::{EventRingBuffer, Game};
+use crate::utils::{to_timestamp_u64, transfer_from_vault};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CollectDeveloperRewards<'info> {
     /// The authority account that must sign the transaction.
@@ -27,11 +28,11 @@ pub struct CollectDeveloperRewards<'info> {
 
     /// The game vault token account holding tokens for developer rewards and other pools.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The token mint representing the in-game currency.
-    #[account(address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The authority's token account where developer rewards will be transferred.
     /// Created if needed, ensuring the authority can receive tokens directly.
@@ -39,19 +40,23 @@ pub struct CollectDeveloperRewards<'info> {
         init_if_needed,
         payer = authority,
         associated_token::mint = token_mint,
-        associated_token::authority = authority
+        associated_token::authority = authority,
+        associated_token::token_program = token_program
     )]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The SPL token program, enabling token transfers and related operations.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The associated token program used to create associated token accounts.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The system program for Solana account creation and basic operations.
     pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// The `collect_developer_rewards` instruction allows the authorized entity to withdraw accumulated developer rewards from the game vault.
@@ -70,6 +75,7 @@ pub fn collect_developer_rewards(ctx: Context<CollectDeveloperRewards>) -> Resul
 
     // Extract references to accounts for clarity.
     let CollectDeveloperRewards {
+        token_mint,
         authority,
         game,
         game_vault,
@@ -96,19 +102,22 @@ pub fn collect_developer_rewards(ctx: Context<CollectDeveloperRewards>) -> Resul
         .safe_add(developer_rewards)?;
 
     // Transfer the developer rewards from the game vault to the authority's token account.
-    transfer_from_token_vault_to_token_account(
+    transfer_from_vault(
         game,
+        ctx.bumps.game,
         &game_vault,
         &token_account,
+        token_mint,
         &token_program,
         developer_rewards,
-        &[GAME_SEED, &[ctx.bumps.game]],
     )?;
 
     game.increment_event_nonce()?;
 
     // Emit an event logging the developer reward collection.
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CollectDeveloperRewards as u16,
         event_type: EventType::CollectDeveloperRewards,
         event_nonce: game.event_nonce,
         data: EventData::CollectDeveloperRewards {
@@ -118,7 +127,8 @@ pub fn collect_developer_rewards(ctx: Context<CollectDeveloperRewards>) -> Resul
         initiator_type: InitiatorType::SYSTEM,
         initiator: authority.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
@@ -0,0 +1,114 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::transfer_from_vault;
+use crate::utils::{cover_shortfall as cover_shortfall_from_insurance, to_timestamp_u64};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `CoverShortfall` instruction lets the bot authority top up a pool that cannot cover a payout
+/// (a round's grand prizes, construction rewards or exit rewards) from the insurance pool.
+/// Every draw is logged with a `CoverShortfall` event.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CoverShortfall<'info> {
+    /// The bot authority cranking the cover, must sign the transaction.
+    #[account(mut)]
+    pub bot_authority: Signer<'info>,
+
+    /// The global game account, holding the insurance pool.
+    #[account(mut,
+        seeds = [GAME_SEED], bump,
+        has_one = game_vault,
+        has_one = bot_authority @ ErrorCode::AuthorityMismatch,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game vault holding the insurance pool.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The round whose grand prize pool is covered, referencing its `round_vault`.
+    /// Unused for the pools held by the game.
+    #[account(mut, has_one = round_vault)]
+    pub round: Box<Account<'info, Round>>,
+
+    /// The round vault receiving the tokens covering a grand prize shortfall.
+    #[account(mut)]
+    pub round_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program used for the transfer.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `CoverShortfall` instruction:
+///
+/// - Moves `amount` from the insurance pool to the pool selected by `pool`.
+/// - For grand prizes, transfers the tokens from the game vault to the round vault.
+/// - Emits a `CoverShortfall` event to record the draw on-chain.
+pub fn cover_shortfall(
+    ctx: Context<CoverShortfall>,
+    pool: ShortfallPool,
+    amount: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let CoverShortfall {
+        bot_authority,
+        game,
+        game_vault,
+        round,
+        round_vault,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    cover_shortfall_from_insurance(game, round, pool, amount)?;
+
+    // The grand prizes are paid from the round vault
+    if pool == ShortfallPool::GrandPrize {
+        transfer_from_vault(
+            game,
+            ctx.bumps.game,
+            game_vault,
+            round_vault,
+            token_mint,
+            token_program,
+            amount,
+        )?;
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the draw from the insurance pool
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CoverShortfall as u16,
+        event_type: EventType::CoverShortfall,
+        event_nonce: game.event_nonce,
+        data: EventData::CoverShortfall {
+            pool,
+            round: round.key(),
+            amount,
+            insurance_pool_balance: game.insurance_pool_balance,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: bot_authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
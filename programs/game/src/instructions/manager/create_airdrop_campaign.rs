@@ -0,0 +1,150 @@
+use crate::constants::{AIRDROP_CAMPAIGN_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_vault};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `CreateAirdropCampaign` instruction lets the game authority start an airdrop with its own vault,
+/// eligibility rule, daily cap and time window, next to any other running campaign.
+/// The campaign's budget may be seeded from the game's airdrop pool, and topped up with `fund_airdrop_campaign`.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(campaign_id: u16)]
+pub struct CreateAirdropCampaign<'info> {
+    /// The authority (signer) authorized to manage the game, paying for the campaign's rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority and holding the airdrop pool.
+    #[account(
+        mut,
+        seeds = [GAME_SEED],
+        bump,
+        has_one = authority @ ErrorCode::AuthorityMismatch,
+        has_one = game_vault,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game vault, holding the tokens of the game's airdrop pool.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The airdrop campaign to create, indexed by `campaign_id`.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AirdropCampaign::INIT_SPACE,
+        seeds = [AIRDROP_CAMPAIGN_SEED, campaign_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub airdrop_campaign: Box<Account<'info, AirdropCampaign>>,
+
+    /// The vault holding the campaign's budget.
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = airdrop_campaign,
+        associated_token::token_program = token_program
+    )]
+    pub campaign_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The main token mint account.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program for creating the vault and moving the budget.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program for creating the vault.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program, required to create the campaign.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `CreateAirdropCampaign` instruction:
+///
+/// - Creates the campaign and its vault, enabled with `params`.
+/// - Moves `game_pool_budget` from the game's airdrop pool into the campaign's vault.
+/// - Emits a `CreateAirdropCampaign` event to record the new campaign on-chain.
+pub fn create_airdrop_campaign(
+    ctx: Context<CreateAirdropCampaign>,
+    campaign_id: u16,
+    params: AirdropCampaignParams,
+    game_pool_budget: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let CreateAirdropCampaign {
+        authority,
+        game,
+        game_vault,
+        airdrop_campaign,
+        campaign_vault,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    airdrop_campaign.initialize(
+        campaign_id,
+        campaign_vault.key(),
+        &params,
+        ctx.bumps.airdrop_campaign,
+    )?;
+
+    // Seed the campaign's budget from the game's airdrop pool
+    if game_pool_budget > 0 {
+        require!(
+            game.airdrop_rewards_pool_balance >= game_pool_budget,
+            ErrorCode::InsufficientAirdropRewardBalance
+        );
+        game.airdrop_rewards_pool_balance = game
+            .airdrop_rewards_pool_balance
+            .safe_sub(game_pool_budget)?;
+        airdrop_campaign.fund(game_pool_budget)?;
+
+        transfer_from_vault(
+            game,
+            ctx.bumps.game,
+            game_vault,
+            campaign_vault,
+            token_mint,
+            token_program,
+            game_pool_budget,
+        )?;
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new airdrop campaign
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CreateAirdropCampaign as u16,
+        event_type: EventType::CreateAirdropCampaign,
+        event_nonce: game.event_nonce,
+        data: EventData::CreateAirdropCampaign {
+            airdrop_campaign: airdrop_campaign.key(),
+            campaign_id,
+            params,
+            game_pool_budget,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
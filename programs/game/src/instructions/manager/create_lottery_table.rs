@@ -0,0 +1,82 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, LOTTERY_TABLE_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `CreateLotteryTable` instruction lets the game authority add a themed slot machine to the lottery,
+/// with its own draw cost, reel symbols and multipliers.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(table_id: u16)]
+pub struct CreateLotteryTable<'info> {
+    /// The authority (signer) authorized to manage the game, paying for the table's rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The lottery table to create, indexed by `table_id`.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LotteryTable::INIT_SPACE,
+        seeds = [LOTTERY_TABLE_SEED, table_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub lottery_table: Box<Account<'info, LotteryTable>>,
+
+    /// The system program, required to create the table.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `CreateLotteryTable` instruction:
+///
+/// - Validates and stores the cost, symbols and multipliers of the new table, enabled for draws.
+/// - Emits a `CreateLotteryTable` event to record the new table on-chain.
+pub fn create_lottery_table(
+    ctx: Context<CreateLotteryTable>,
+    table_id: u16,
+    params: LotteryTableParams,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let CreateLotteryTable {
+        authority,
+        game,
+        lottery_table,
+        ..
+    } = ctx.accounts;
+
+    lottery_table.initialize(table_id, &params, ctx.bumps.lottery_table)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new lottery table
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CreateLotteryTable as u16,
+        event_type: EventType::CreateLotteryTable,
+        event_nonce: game.event_nonce,
+        data: EventData::CreateLotteryTable {
+            lottery_table: lottery_table.key(),
+            table_id,
+            params,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
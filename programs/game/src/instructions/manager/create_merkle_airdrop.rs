@@ -0,0 +1,157 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, MERKLE_AIRDROP_BITMAP_SEED, MERKLE_AIRDROP_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_player_to_vault};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `CreateMerkleAirdrop` instruction lets the game authority distribute off-chain computed retroactive
+/// rewards, e.g. a compensation for an incident, trustlessly: it commits to the merkle root of the claims
+/// and deposits the tokens covering them into a dedicated vault, from which each player claims with a proof.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(airdrop_id: u16, merkle_root: [u8; 32], total_amount: u64, claim_count: u32)]
+pub struct CreateMerkleAirdrop<'info> {
+    /// The authority (signer) authorized to manage the game, paying the deposit and the accounts' rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The merkle airdrop to create, indexed by `airdrop_id`.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MerkleAirdrop::INIT_SPACE,
+        seeds = [MERKLE_AIRDROP_SEED, airdrop_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub merkle_airdrop: Box<Account<'info, MerkleAirdrop>>,
+
+    /// The bitmap recording the airdrop's collected claims.
+    #[account(
+        init,
+        payer = authority,
+        space = MerkleAirdropBitmap::space(claim_count),
+        seeds = [MERKLE_AIRDROP_BITMAP_SEED, merkle_airdrop.key().as_ref()],
+        bump,
+    )]
+    pub merkle_airdrop_bitmap: Box<Account<'info, MerkleAirdropBitmap>>,
+
+    /// The vault holding the airdrop's tokens.
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = merkle_airdrop,
+        associated_token::token_program = token_program
+    )]
+    pub airdrop_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The authority's token account, paying the deposit.
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The main token mint account.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program for creating the vault and the deposit.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program for creating the vault.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program, required to create the airdrop and its bitmap.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `CreateMerkleAirdrop` instruction:
+///
+/// - Creates the airdrop, its empty claimed bitmap and its vault.
+/// - Deposits `total_amount` tokens from the authority into the airdrop's vault.
+/// - Emits a `CreateMerkleAirdrop` event to record the new airdrop on-chain.
+pub fn create_merkle_airdrop(
+    ctx: Context<CreateMerkleAirdrop>,
+    airdrop_id: u16,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    claim_count: u32,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let CreateMerkleAirdrop {
+        authority,
+        game,
+        merkle_airdrop,
+        merkle_airdrop_bitmap,
+        airdrop_vault,
+        authority_token_account,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    merkle_airdrop.initialize(
+        airdrop_id,
+        merkle_root,
+        airdrop_vault.key(),
+        total_amount,
+        claim_count,
+        ctx.bumps.merkle_airdrop,
+    )?;
+    merkle_airdrop_bitmap.initialize(
+        merkle_airdrop.key(),
+        claim_count,
+        ctx.bumps.merkle_airdrop_bitmap,
+    );
+
+    transfer_from_player_to_vault(
+        authority,
+        authority_token_account,
+        airdrop_vault,
+        token_mint,
+        token_program,
+        total_amount,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new merkle airdrop
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CreateMerkleAirdrop as u16,
+        event_type: EventType::CreateMerkleAirdrop,
+        event_nonce: game.event_nonce,
+        data: EventData::CreateMerkleAirdrop {
+            merkle_airdrop: merkle_airdrop.key(),
+            airdrop_id,
+            merkle_root,
+            total_amount,
+            claim_count,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -1,14 +1,18 @@
-use crate::constants::{GAME_SEED, PERIOD_SEED, TOKEN_MINT};
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, MAX_LEADERBOARD_DURATION, MIN_LEADERBOARD_DURATION,
+    PERIOD_SEED,
+};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
-use crate::state::{Game, Period};
-use crate::utils::{to_timestamp_u64, transfer_from_token_vault_to_token_account};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::{EventRingBuffer, Game, Period, PeriodConfig};
+use crate::utils::{to_timestamp_u64, transfer_from_vault, validate_start_time};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreatePeriod<'info> {
     /// The authority initializing the period, must sign the transaction.
@@ -31,38 +35,42 @@ pub struct CreatePeriod<'info> {
     #[account(
         init,
         payer = bot_authority,
-        space = 8 + Period::INIT_SPACE,
+        space = 8 + std::mem::size_of::<Period>(),
         seeds = [PERIOD_SEED, game.period_nonce.to_le_bytes().as_ref()],
         bump,
     )]
-    pub period: Box<Account<'info, Period>>,
+    pub period: AccountLoader<'info, Period>,
 
     /// The main game vault token account holding tokens for various distributions.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The period's associated token vault, created to hold tokens allocated for this period's rewards.
     #[account(
         init,
         payer = bot_authority,
         associated_token::mint = token_mint,
-        associated_token::authority = period
+        associated_token::authority = period,
+        associated_token::token_program = token_program
     )]
-    pub period_vault: Box<Account<'info, TokenAccount>>,
+    pub period_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The token mint representing the in-game currency.
-    #[account(address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The associated token program used to create the period_vault account.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The SPL token program, enabling token transfers and related operations.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The system program for basic Solana operations, required for account initializations.
     pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// The `create_period` instruction sets up a new leaderboard period within the game.
@@ -71,9 +79,11 @@ pub struct CreatePeriod<'info> {
 ///
 /// Steps:
 /// 1. Validate that the authority is authorized and that the game has sufficient reward balances.
-/// 2. Ensure start_time is valid and that requested `team_rewards` and `individual_rewards` are non-zero.
+/// 2. Ensure `start_time` is within the game's start time window, `leaderboard_duration` is within
+///    `MIN_LEADERBOARD_DURATION..=MAX_LEADERBOARD_DURATION`, and requested `team_rewards` and `individual_rewards` are non-zero.
 /// 3. Subtract the total allocated rewards from the game's `period_rewards_pool_balance`.
-/// 4. Initialize the `Period` account with the provided parameters and increment `period_nonce` in the `game`.
+/// 4. Initialize and configure the `Period` account with the provided parameters, splitting the rewards across the ranks
+///    following the payout curves, recording whether they are paid in tokens or vouchers, and increment `period_nonce` in the `game`.
 /// 5. Transfer the allocated rewards from `game_vault` to `period_vault`.
 /// 6. Emit a `CreatePeriod` event to log the new period creation.
 
@@ -83,12 +93,14 @@ pub fn create_period(
     leaderboard_duration: u64,
     team_rewards: u64,
     individual_rewards: u64,
+    config: PeriodConfig,
 ) -> Result<()> {
     // Fetch current UNIX timestamp for logical checks and event timestamping.
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
     let CreatePeriod {
+        token_mint,
         bot_authority,
         game,
         period,
@@ -101,7 +113,16 @@ pub fn create_period(
     // Validate input parameters and ensure sufficient game resources.
     require!(team_rewards > 0, ErrorCode::InvalidAmount);
     require!(individual_rewards > 0, ErrorCode::InvalidAmount);
-    require!(start_time >= timestamp, ErrorCode::InvalidAmount);
+    validate_start_time(
+        start_time,
+        timestamp,
+        game.min_start_lead_seconds,
+        game.max_future_start_seconds,
+    )?;
+    require!(
+        (MIN_LEADERBOARD_DURATION..=MAX_LEADERBOARD_DURATION).contains(&leaderboard_duration),
+        ErrorCode::InvalidPeriodDuration
+    );
 
     let total_rewards = team_rewards.safe_add(individual_rewards)?;
     require!(
@@ -110,15 +131,17 @@ pub fn create_period(
     );
     require!(
         total_rewards <= game.period_rewards_pool_balance,
-        ErrorCode::InsufficientFunds
+        ErrorCode::RewardsExceedPoolBalance
     );
 
     // Update game state: set current_period and deduct from initial leaderboard reward pool.
+    game.previous_period = game.current_period;
     game.current_period = period.key();
     game.period_rewards_pool_balance = game.period_rewards_pool_balance.safe_sub(total_rewards)?;
 
     // Initialize the period account with provided arguments.
-    period.initialize(
+    let mut period_data = period.load_init()?;
+    period_data.initialize(
         game.period_nonce,
         period_vault.key(),
         start_time,
@@ -129,24 +152,29 @@ pub fn create_period(
         game.default_team,
         ctx.bumps.period,
     )?;
+    period_data.configure(&config)?;
+    drop(period_data);
 
     // Increment period_nonce for future period derivations.
     game.increment_period_nonce()?;
 
     // Transfer the allocated rewards from game_vault to period_vault.
-    transfer_from_token_vault_to_token_account(
+    transfer_from_vault(
         game,
+        ctx.bumps.game,
         game_vault,
         period_vault,
+        token_mint,
         token_program,
         total_rewards,
-        &[GAME_SEED, &[ctx.bumps.game]],
     )?;
 
     game.increment_event_nonce()?;
 
     // Emit the event logging period creation.
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CreatePeriod as u16,
         event_type: EventType::CreatePeriod,
         event_nonce: game.event_nonce,
         data: EventData::CreatePeriod {
@@ -156,7 +184,8 @@ pub fn create_period(
         initiator_type: InitiatorType::SYSTEM,
         initiator: bot_authority.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
@@ -1,14 +1,18 @@
-use crate::constants::{GAME_SEED, ROUND_SEED, TOKEN_MINT};
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, MAX_ROUND_COUNTDOWN_DURATION, MIN_ROUND_COUNTDOWN_DURATION,
+    ROUND_SEED,
+};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
-use crate::utils::{to_timestamp_u64, transfer_from_token_vault_to_token_account};
+use crate::utils::{to_timestamp_u64, transfer_from_vault, validate_start_time};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateRound<'info> {
     /// The authority (signer) who initiates the creation of a new round.
@@ -28,7 +32,7 @@ pub struct CreateRound<'info> {
 
     /// The main game vault holding tokens allocated for different in-game pools.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The round account to be created. Each round is uniquely derived using `game.round_nonce`.
     #[account(
@@ -41,8 +45,8 @@ pub struct CreateRound<'info> {
     pub round: Box<Account<'info, Round>>,
 
     /// The token mint representing the in-game currency.
-    #[account(address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The associated token account for the round, serving as the `round_vault`.
     /// Stores tokens specifically allocated to this round.
@@ -50,19 +54,23 @@ pub struct CreateRound<'info> {
         init,
         payer = bot_authority,
         associated_token::mint = token_mint,
-        associated_token::authority = round
+        associated_token::authority = round,
+        associated_token::token_program = token_program
     )]
-    pub round_vault: Box<Account<'info, TokenAccount>>,
+    pub round_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The associated token program used for creating the `round_vault`.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The SPL token program enabling token transfers.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The system program required for account creations and other operations.
     pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// The `create_round` instruction sets up a new game round in the platform.
@@ -70,7 +78,8 @@ pub struct CreateRound<'info> {
 /// This enables players to participate in a fresh round with a defined start time and countdown duration.
 ///
 /// Steps:
-/// 1. Validate inputs (e.g., `start_time` >= current time, `countdown_duration` > 0) and ensure the game has sufficient funds.
+/// 1. Validate inputs (`start_time` within the game's start time window, `countdown_duration` within
+///    `MIN_ROUND_COUNTDOWN_DURATION..=MAX_ROUND_COUNTDOWN_DURATION`) and ensure the game has sufficient funds.
 /// 2. Deduct the `initial_grand_prizes` from the `round_rewards_pool_balance`.
 /// 3. Initialize the `Round` account with the provided parameters and increment `round_nonce` in the game account.
 /// 4. Transfer the allocated grand prize tokens from `game_vault` to the `round_vault`.
@@ -86,6 +95,7 @@ pub fn create_round(
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
     let CreateRound {
+        token_mint,
         game,
         game_vault,
         round,
@@ -96,15 +106,23 @@ pub fn create_round(
     } = ctx.accounts;
 
     // Validate input parameters and ensure the game has enough resources.
-    require!(start_time >= timestamp, ErrorCode::InvalidAmount);
-    require!(countdown_duration > 0, ErrorCode::InvalidAmount);
+    validate_start_time(
+        start_time,
+        timestamp,
+        game.min_start_lead_seconds,
+        game.max_future_start_seconds,
+    )?;
+    require!(
+        (MIN_ROUND_COUNTDOWN_DURATION..=MAX_ROUND_COUNTDOWN_DURATION).contains(&countdown_duration),
+        ErrorCode::InvalidRoundDuration
+    );
     require!(
         initial_grand_prizes <= game_vault.amount,
         ErrorCode::InsufficientBalance
     );
     require!(
         initial_grand_prizes <= game.round_rewards_pool_balance,
-        ErrorCode::InsufficientBalance
+        ErrorCode::RewardsExceedPoolBalance
     );
 
     let grand_prizes = initial_grand_prizes.safe_add(game.bonus_rewards_pool_balance)?;
@@ -116,6 +134,7 @@ pub fn create_round(
         grand_prizes,
         start_time,
         countdown_duration,
+        game.exit_rewards_per_second,
         game.default_player,
         ctx.bumps.round,
     )?;
@@ -128,13 +147,14 @@ pub fn create_round(
     game.bonus_rewards_pool_balance = 0;
 
     // Transfer the initial grand prize amount from game_vault to round_vault.
-    transfer_from_token_vault_to_token_account(
+    transfer_from_vault(
         game,
+        ctx.bumps.game,
         &game_vault,
         &round_vault,
+        token_mint,
         &token_program,
         grand_prizes,
-        &[GAME_SEED, &[ctx.bumps.game]],
     )?;
 
     // Increment the round_nonce for future round derivations.
@@ -143,7 +163,9 @@ pub fn create_round(
     game.increment_event_nonce()?;
 
     // Emit event logging the creation of a new round.
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CreateRound as u16,
         event_type: EventType::CreateRound,
         event_nonce: game.event_nonce,
         data: EventData::CreateRound {
@@ -153,7 +175,8 @@ pub fn create_round(
         initiator_type: InitiatorType::SYSTEM,
         initiator: bot_authority.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
@@ -0,0 +1,150 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, TEAM_QUEST_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_player_to_vault};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `CreateTeamQuest` instruction lets the game authority set a collective goal for the teams in a period,
+/// depositing the budget paying the bonus of every team reaching it.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateTeamQuest<'info> {
+    /// The authority (signer) authorized to manage the game, paying the budget and the quest's rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The period the quest runs in, which must not have ended.
+    pub period: AccountLoader<'info, Period>,
+
+    /// The team quest to create, one per period.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TeamQuest::INIT_SPACE,
+        seeds = [TEAM_QUEST_SEED, period.key().as_ref()],
+        bump,
+    )]
+    pub team_quest: Box<Account<'info, TeamQuest>>,
+
+    /// The vault holding the quest's budget.
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = team_quest,
+        associated_token::token_program = token_program
+    )]
+    pub quest_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The authority's token account, paying the budget.
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The main token mint account.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program for creating the vault and the deposit.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program for creating the vault.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program, required to create the quest.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `CreateTeamQuest` instruction:
+///
+/// - Requires the period not to have ended.
+/// - Creates the quest and its vault, and deposits `budget` tokens from the authority into it.
+/// - Emits a `CreateTeamQuest` event to record the new quest on-chain.
+///
+/// # Arguments
+/// - `goal_ores`: The ORE a team must purchase during the period to complete the quest.
+/// - `bonus_rewards`: The rewards of every team completing the quest.
+/// - `budget`: The tokens deposited to pay the bonuses, at least one bonus.
+pub fn create_team_quest(
+    ctx: Context<CreateTeamQuest>,
+    goal_ores: u32,
+    bonus_rewards: u64,
+    budget: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for the period check and event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let CreateTeamQuest {
+        authority,
+        game,
+        period,
+        team_quest,
+        quest_vault,
+        authority_token_account,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require!(
+        !period.load()?.is_ended(timestamp),
+        ErrorCode::InvalidTeamQuest
+    );
+
+    team_quest.initialize(
+        period.key(),
+        quest_vault.key(),
+        goal_ores,
+        bonus_rewards,
+        budget,
+        ctx.bumps.team_quest,
+    )?;
+
+    transfer_from_player_to_vault(
+        authority,
+        authority_token_account,
+        quest_vault,
+        token_mint,
+        token_program,
+        budget,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new team quest
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CreateTeamQuest as u16,
+        event_type: EventType::CreateTeamQuest,
+        event_nonce: game.event_nonce,
+        data: EventData::CreateTeamQuest {
+            team_quest: team_quest.key(),
+            period: period.key(),
+            goal_ores,
+            bonus_rewards,
+            funded_amount: budget,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,250 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, STAKE_POOL_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{
+    EventData, EventType, InitiatorType, SoftFailureReason, TransferEvent, EVENT_SCHEMA_VERSION,
+};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_vault};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{
+    burn, close_account, Burn, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+use solana_program::sysvar::clock::Clock;
+
+/// The `DecommissionStakePool` instruction retires the stake pool in two phases:
+///
+/// 1. The first call marks the pool as decommissioned, so `stake` refuses new orders while existing orders keep running to maturity.
+/// 2. Once `active_orders == 0`, a subsequent call sweeps the residual balances back to the game vault and closes both pool vaults.
+///    Calls made while orders are still active emit a `SoftFailure` event.
+///
+/// Residual reward tokens are credited to the developer rewards pool, since the stake pool was funded by the authority.
+/// Residual vouchers are burned and their backing tokens are redeemed from the voucher vault into the game vault.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DecommissionStakePool<'info> {
+    /// The authority (signer) authorized to decommission the stake pool. Receives the rent of the closed vaults.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority and the game vault.
+    #[account(mut,
+        seeds = [GAME_SEED], bump,
+        has_one = game_vault,
+        has_one = authority @ ErrorCode::AuthorityMismatch,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game vault receiving the swept tokens.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The stake pool being decommissioned.
+    #[account(mut,
+        seeds = [STAKE_POOL_SEED],
+        bump,
+        has_one = stake_pool_token_vault,
+        has_one = stake_pool_voucher_vault,
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The stake pool's token vault, holding the residual reward tokens.
+    #[account(mut)]
+    pub stake_pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The stake pool's voucher vault, holding the residual voucher rewards.
+    #[account(mut)]
+    pub stake_pool_voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global voucher account tracking voucher supply.
+    #[account(
+        mut,
+        seeds = [VOUCHER_SEED],
+        bump,
+        has_one = voucher_vault,
+    )]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher vault holding the tokens that back the voucher supply.
+    #[account(mut)]
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The voucher mint, used to burn the residual vouchers.
+    #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The SPL token program for token operations.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `DecommissionStakePool` instruction:
+///
+/// - Marks the stake pool as decommissioned and emits a `DecommissionStakePool` event.
+/// - If no orders remain active, sweeps the residual tokens and vouchers, closes the pool vaults
+///   and emits a `SweepStakePool` event.
+pub fn decommission_stake_pool(ctx: Context<DecommissionStakePool>) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let DecommissionStakePool {
+        token_mint,
+        authority,
+        game,
+        game_vault,
+        stake_pool,
+        stake_pool_token_vault,
+        stake_pool_voucher_vault,
+        voucher,
+        voucher_vault,
+        voucher_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    if !stake_pool.is_decommissioned {
+        stake_pool.is_decommissioned = true;
+
+        game.increment_event_nonce()?;
+
+        // Emit an event logging that the pool stopped accepting orders
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::DecommissionStakePool as u16,
+            event_type: EventType::DecommissionStakePool,
+            event_nonce: game.event_nonce,
+            data: EventData::DecommissionStakePool {
+                stake_pool: stake_pool.key(),
+                active_orders: stake_pool.active_orders,
+            },
+            initiator_type: InitiatorType::STAKE,
+            initiator: authority.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    // Existing orders must mature before the residual balances can be swept
+    if stake_pool.active_orders > 0 {
+        let event = TransferEvent::soft_failure(
+            game,
+            SoftFailureReason::StakeOrdersOutstanding,
+            authority.key(),
+            timestamp,
+        )?;
+        record_event!(ctx, event);
+        return Ok(());
+    }
+
+    stake_pool.sweep()?;
+
+    let stake_pool_signer_seeds: &[&[u8]] = &[STAKE_POOL_SEED, &[ctx.bumps.stake_pool]];
+
+    // Sweep the residual reward tokens into the game vault
+    let token_amount = stake_pool_token_vault.amount;
+    transfer_from_vault(
+        stake_pool,
+        ctx.bumps.stake_pool,
+        stake_pool_token_vault,
+        game_vault,
+        token_mint,
+        token_program,
+        token_amount,
+    )?;
+    game.developer_rewards_pool_balance =
+        game.developer_rewards_pool_balance.safe_add(token_amount)?;
+
+    // Burn the residual vouchers and redeem their backing tokens into the game vault
+    let voucher_amount = stake_pool_voucher_vault.amount;
+    voucher.burn(voucher_amount)?;
+
+    burn(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Burn {
+                mint: voucher_mint.to_account_info(),
+                from: stake_pool_voucher_vault.to_account_info(),
+                authority: stake_pool.to_account_info(),
+            },
+            &[stake_pool_signer_seeds],
+        ),
+        voucher_amount,
+    )?;
+
+    game.record_burned_vouchers(voucher_amount)?;
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the burn
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Burn as u16,
+        event_type: EventType::Burn,
+        event_nonce: game.event_nonce,
+        data: EventData::Burn {
+            mint: voucher_mint.key(),
+            from: stake_pool_voucher_vault.key(),
+            amount: voucher_amount,
+            reason: BurnReason::StakePoolDecommission,
+            total_burned: game.total_burned_vouchers,
+        },
+        initiator_type: InitiatorType::STAKE,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    transfer_from_vault(
+        voucher,
+        ctx.bumps.voucher,
+        voucher_vault,
+        game_vault,
+        token_mint,
+        token_program,
+        voucher_amount,
+    )?;
+    game.developer_rewards_pool_balance = game
+        .developer_rewards_pool_balance
+        .safe_add(voucher_amount)?;
+
+    // Close both pool vaults, returning their rent to the authority
+    for vault in [stake_pool_token_vault, stake_pool_voucher_vault] {
+        close_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            CloseAccount {
+                account: vault.to_account_info(),
+                destination: authority.to_account_info(),
+                authority: stake_pool.to_account_info(),
+            },
+            &[stake_pool_signer_seeds],
+        ))?;
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the swept balances
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SweepStakePool as u16,
+        event_type: EventType::SweepStakePool,
+        event_nonce: game.event_nonce,
+        data: EventData::SweepStakePool {
+            stake_pool: stake_pool.key(),
+            token_amount,
+            voucher_amount,
+        },
+        initiator_type: InitiatorType::STAKE,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -1,23 +1,27 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED, ROUND_SEED, TOKEN_MINT};
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, PRIZE_VESTING_SEED, ROUND_SEED,
+};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
-use crate::utils::{to_timestamp_u64, transfer_from_token_vault_to_token_account};
+use crate::utils::{to_timestamp_u64, transfer_from_vault};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
-use anchor_spl::token::{self, burn, Burn, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(index: u8, player: Pubkey)]
 pub struct DistributeGrandPrizes<'info> {
     /// The authority executing the distribution of grand prizes. Must sign the transaction.
+    /// A backup authority may stand in while the bot's heartbeat is stale.
     #[account(mut)]
     pub bot_authority: Signer<'info>,
 
     /// The global game account, linked to round and ensuring authorized access.
     #[account(mut, seeds = [GAME_SEED], bump,
-        has_one = bot_authority @ ErrorCode::AuthorityMismatch,
+        has_one = game_vault,
     )]
     pub game: Box<Account<'info, Game>>,
 
@@ -45,30 +49,55 @@ pub struct DistributeGrandPrizes<'info> {
 
     /// The round vault token account holding the grand prize tokens.
     #[account(mut)]
-    pub round_vault: Box<Account<'info, TokenAccount>>,
+    pub round_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The game vault receiving the grand prizes won by the default player, unless the default winner policy burns them.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The player's token account where grand prizes will be transferred.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vesting streaming the first-place grand prize to the player, paid for by the bot authority.
+    /// Required when the round streams its first-place grand prize, and only then.
+    #[account(
+        init,
+        payer = bot_authority,
+        space = 8 + PrizeVesting::INIT_SPACE,
+        seeds = [PRIZE_VESTING_SEED, round.key().as_ref(), player.as_ref()],
+        bump,
+    )]
+    pub prize_vesting: Option<Box<Account<'info, PrizeVesting>>>,
 
     /// The token mint representing the in-game currency.
-    #[account(mut, address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(mut, address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The token program used for token transfers.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program, required to create the prize vesting.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// The `distribute_grand_prizes` instruction awards one of the last 10 active participants in the round with their portion of the grand prize.
 /// This action is typically executed by an authorized entity after the round has ended, distributing prizes in sequence (index 0 to 9).
 ///
 /// Steps:
-/// 1. Ensure the round has ended and grand prize distribution is still ongoing (not all 10 winners distributed).
+/// 1. Check the signer is the bot authority, or a backup authority while the bot's heartbeat is stale.
+///    Ensure the round has ended and grand prize distribution is still ongoing (not all 10 winners distributed).
 /// 2. Confirm that the `index` and `player` match the next expected winner in `round.last_active_participant_list`.
 /// 3. Call `distribute_grand_prizes()` on `round` to determine the reward amount for this winner.
 /// 4. Update the `player_data` to record the collected grand prizes.
 /// 5. Transfer the grand prize amount from `round_vault` to the player's `token_account`.
+///    Grand prizes won by the default player follow the game's `DefaultWinnerPolicy` instead,
+///    and a first-place grand prize the round streams is kept in the round vault and vested to the player
+///    through `prize_vesting`, to be claimed with `claim_vested_prize`.
 /// 6. Emit a `DistributeGrandPrizes` event to record this distribution on-chain.
 
 pub fn distribute_grand_prizes(
@@ -85,6 +114,7 @@ pub fn distribute_grand_prizes(
         game,
         round,
         round_vault,
+        game_vault,
         token_mint,
         token_account,
         token_program,
@@ -92,6 +122,9 @@ pub fn distribute_grand_prizes(
         ..
     } = ctx.accounts;
 
+    // The signer must be the bot authority, or a backup authority while the bot's heartbeat is stale
+    game.require_bot_authority(bot_authority.key(), timestamp)?;
+
     require!(
         !round.is_grand_prize_distribution_completed,
         ErrorCode::GrandPrizeDistributionAlreadyCompleted,
@@ -109,7 +142,9 @@ pub fn distribute_grand_prizes(
     // Determine the grand_prizes amount to be distributed from the round's logic.
     let grand_prizes = round.distribute_grand_prizes()?;
 
-    if player_data.player == game.default_player {
+    let is_default_winner = player_data.player == game.default_player;
+    let default_winner_policy = game.default_winner_policy();
+    if is_default_winner && default_winner_policy == DefaultWinnerPolicy::Burn {
         burn(
             CpiContext::new_with_signer(
                 token_program.to_account_info(),
@@ -126,6 +161,84 @@ pub fn distribute_grand_prizes(
             ),
             grand_prizes,
         )?;
+
+        game.record_burned_tokens(grand_prizes)?;
+        game.increment_event_nonce()?;
+
+        // Emit an event recording the burn
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::Burn as u16,
+            event_type: EventType::Burn,
+            event_nonce: game.event_nonce,
+            data: EventData::Burn {
+                mint: token_mint.key(),
+                from: round_vault.key(),
+                amount: grand_prizes,
+                reason: BurnReason::GrandPrizes,
+                total_burned: game.total_burned_tokens,
+            },
+            initiator_type: InitiatorType::SYSTEM,
+            initiator: bot_authority.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    } else if is_default_winner {
+        // Nobody can claim the default player's grand prizes, move them to the pool selected by the policy
+        game.credit_default_winner_rewards(default_winner_policy, grand_prizes)?;
+
+        transfer_from_vault(
+            round,
+            round.bump,
+            round_vault,
+            game_vault,
+            token_mint,
+            token_program,
+            grand_prizes,
+        )?;
+    } else if round.is_grand_prize_vested(index) {
+        game.distributed_grand_prizes = game.distributed_grand_prizes.safe_add(grand_prizes)?;
+        player_data.collect_grand_prizes(grand_prizes)?;
+        player_data.vest_grand_prizes(grand_prizes)?;
+
+        // Keep the prize in the round vault and stream it to the player
+        let prize_vesting = ctx
+            .accounts
+            .prize_vesting
+            .as_mut()
+            .ok_or(ErrorCode::PrizeVestingRequired)?;
+        prize_vesting.initialize(
+            round.key(),
+            player,
+            grand_prizes,
+            timestamp,
+            round.grand_prize_vesting_days,
+            ctx.bumps
+                .prize_vesting
+                .ok_or(ErrorCode::PrizeVestingRequired)?,
+        )?;
+        round.vest_grand_prizes(grand_prizes)?;
+
+        game.increment_event_nonce()?;
+
+        // Emit an event recording the stream
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::VestGrandPrize as u16,
+            event_type: EventType::VestGrandPrize,
+            event_nonce: game.event_nonce,
+            data: EventData::VestGrandPrize {
+                round: round.key(),
+                player,
+                prize_vesting: prize_vesting.key(),
+                total_amount: grand_prizes,
+                end_time: prize_vesting.end_time,
+            },
+            initiator_type: InitiatorType::SYSTEM,
+            initiator: bot_authority.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
     } else {
         game.distributed_grand_prizes = game.distributed_grand_prizes.safe_add(grand_prizes)?;
 
@@ -133,36 +246,36 @@ pub fn distribute_grand_prizes(
         player_data.collect_grand_prizes(grand_prizes)?;
 
         // Transfer the grand prize tokens from the round vault to the player's token account.
-        transfer_from_token_vault_to_token_account(
+        transfer_from_vault(
             round,
+            round.bump,
             &round_vault,
             &token_account,
+            token_mint,
             &token_program,
             grand_prizes,
-            &[
-                ROUND_SEED,
-                round.round_number.to_le_bytes().as_ref(),
-                &[round.bump],
-            ],
         )?;
     }
 
     game.increment_event_nonce()?;
 
     // Emit a transfer event capturing this grand prize distribution.
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::DistributeGrandPrizes as u16,
         event_type: EventType::DistributeGrandPrizes,
         event_nonce: game.event_nonce,
         data: EventData::DistributeGrandPrizes {
             round: round.key(),
             player,
             index,
-            grand_prizes
+            grand_prizes,
         },
         initiator_type: InitiatorType::SYSTEM,
         initiator: bot_authority.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
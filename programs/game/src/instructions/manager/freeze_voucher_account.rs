@@ -0,0 +1,112 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    freeze_account, FreezeAccount, Mint, TokenAccount, TokenInterface,
+};
+use solana_program::sysvar::clock::Clock;
+
+/// The `FreezeVoucherAccount` instruction lets the game authority freeze a voucher token account while the
+/// soulbound mode is on. A frozen account can not transfer vouchers to other wallets; game instructions
+/// thaw it for the duration of their own voucher movements and freeze it again afterwards.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FreezeVoucherAccount<'info> {
+    /// The authority (signer) authorized to manage the voucher.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The voucher account, acting as the freeze authority of the voucher mint.
+    #[account(
+        seeds = [VOUCHER_SEED],
+        bump,
+        has_one = voucher_mint,
+    )]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher token mint account.
+    #[account(seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The voucher token account to be frozen.
+    #[account(
+        mut,
+        token::mint = voucher_mint,
+        token::token_program = token_program,
+    )]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The SPL token program.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `FreezeVoucherAccount` instruction:
+///
+/// - Requires the soulbound mode to be on and the account not to be frozen yet.
+/// - Freezes the voucher account with the `voucher` PDA as freeze authority.
+/// - Emits a `FreezeVoucherAccount` event to record the change on-chain.
+pub fn freeze_voucher_account(ctx: Context<FreezeVoucherAccount>) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let FreezeVoucherAccount {
+        authority,
+        game,
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    // Accounts may only be frozen while voucher transfers are restricted
+    require!(
+        voucher.is_transfer_restricted,
+        ErrorCode::VoucherTransfersNotRestricted
+    );
+    require!(
+        !voucher_account.is_frozen(),
+        ErrorCode::VoucherAccountStateUnchanged
+    );
+
+    freeze_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        FreezeAccount {
+            account: voucher_account.to_account_info(),
+            mint: voucher_mint.to_account_info(),
+            authority: voucher.to_account_info(),
+        },
+        &[&[VOUCHER_SEED, &[ctx.bumps.voucher]]],
+    ))?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the freeze action
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::FreezeVoucherAccount as u16,
+        event_type: EventType::FreezeVoucherAccount,
+        event_nonce: game.event_nonce,
+        data: EventData::FreezeVoucherAccount {
+            voucher: voucher.key(),
+            voucher_account: voucher_account.key(),
+        },
+        initiator_type: InitiatorType::VOUCHER,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use crate::constants::{AIRDROP_CAMPAIGN_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_player_to_vault};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `FundAirdropCampaign` instruction lets the game authority deposit tokens into an airdrop campaign's budget.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FundAirdropCampaign<'info> {
+    /// The authority (signer) authorized to manage the game, paying the deposit.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The airdrop campaign being funded.
+    #[account(
+        mut,
+        seeds = [AIRDROP_CAMPAIGN_SEED, airdrop_campaign.campaign_id.to_le_bytes().as_ref()],
+        bump = airdrop_campaign.bump,
+        has_one = campaign_vault,
+    )]
+    pub airdrop_campaign: Box<Account<'info, AirdropCampaign>>,
+
+    /// The vault holding the campaign's budget.
+    #[account(mut)]
+    pub campaign_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The authority's token account, paying the deposit.
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The main token mint account.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program for the deposit.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `FundAirdropCampaign` instruction:
+///
+/// - Deposits `amount` tokens from the authority into the campaign's vault.
+/// - Emits a `FundAirdropCampaign` event to record the deposit on-chain.
+pub fn fund_airdrop_campaign(ctx: Context<FundAirdropCampaign>, amount: u64) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let FundAirdropCampaign {
+        authority,
+        game,
+        airdrop_campaign,
+        campaign_vault,
+        authority_token_account,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require!(amount > 0, ErrorCode::InvalidAirdropCampaign);
+    airdrop_campaign.fund(amount)?;
+
+    transfer_from_player_to_vault(
+        authority,
+        authority_token_account,
+        campaign_vault,
+        token_mint,
+        token_program,
+        amount,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the deposit
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::FundAirdropCampaign as u16,
+        event_type: EventType::FundAirdropCampaign,
+        event_nonce: game.event_nonce,
+        data: EventData::FundAirdropCampaign {
+            airdrop_campaign: airdrop_campaign.key(),
+            amount,
+            funded_amount: airdrop_campaign.funded_amount,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
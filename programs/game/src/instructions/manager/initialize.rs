@@ -1,12 +1,14 @@
-use crate::constants::{GAME_SEED, SUPER_ADMIN, TOKEN_MINT};
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, SUPER_ADMIN, TOKEN_DECIMALS,
+};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
-use crate::state::Game;
-use crate::utils::{to_timestamp_u64, transfer_from_player_to_vault};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::{EventRingBuffer, Game, GameConfig};
+use crate::utils::{require_no_transfer_fee, to_timestamp_u64, transfer_from_player_to_vault};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
 /// The `Initialize` instruction sets up the initial game state and its main token vault.
@@ -14,11 +16,13 @@ use solana_program::sysvar::clock::Clock;
 /// and establishes a vault for holding the main token that will be used throughout the game's operations.
 ///
 /// # Steps
-/// 1. Create and initialize the `game` account using `GAME_SEED`.
+/// 1. Create and initialize the `game` account using `GAME_SEED`, and the `game_config` account holding the pool shares and costs.
 /// 2. Set up the `game_vault` as an associated token account for storing in-game tokens.
 /// 3. Link the `authority` and `token_mint` to the `game`.
 /// 4. Call `game.initialize` to record initial configuration within the `game` state.
-/// 5. Emit an `Initialize` event to log the successful initialization.
+/// 5. Create the `event_ring_buffer` recording the most recent events.
+/// 6. Emit an `Initialize` event to log the successful initialization.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     /// The admin (signer) authorized to initialize the game.
@@ -35,43 +39,68 @@ pub struct Initialize<'info> {
     )]
     pub game: Box<Account<'info, Game>>,
 
+    /// The game config account to be created, holding the pool shares and costs.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GameConfig::INIT_SPACE,
+        seeds = [GAME_CONFIG_SEED],
+        bump,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
     /// The game's token vault, initialized if needed, for holding tokens utilized by the game.
     #[account(
         init_if_needed,
         payer = authority,
         associated_token::mint = token_mint,
-        associated_token::authority = game
+        associated_token::authority = game,
+        associated_token::token_program = token_program
     )]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// The main token mint for the in-game currency.
-    #[account(address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    /// The main token mint for the in-game currency, stored in `game.token_mint` and
+    /// required by every other instruction through `address = game.token_mint`.
+    /// Rejected if it carries the Token-2022 transfer fee extension.
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The authority's associated token account from which tokens will be deposited.
     #[account(
         mut,
         associated_token::mint = token_mint,
-        associated_token::authority = authority
+        associated_token::authority = authority,
+        associated_token::token_program = token_program
     )]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The SPL token program enabling token operations.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The associated token program used for creating the `game_vault`.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The system program required for account creations and system-level operations.
     pub system_program: Program<'info, System>,
+
+    /// The event ring buffer to be created, recording the most recent events.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<EventRingBuffer>(),
+        seeds = [EVENT_RING_BUFFER_SEED],
+        bump,
+    )]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Executes the `Initialize` instruction:
 ///
 /// - Creates and configures the `game` account.
 /// - Sets up `game_vault` as the associated token account for the `game`.
+/// - Rejects a token mint without `TOKEN_DECIMALS` decimals or charging a transfer fee.
 /// - Calls `game.initialize` to record the authority and token mint.
+/// - Initializes the `game_config` with the default pool shares and costs, adjustable with `update_game_config`.
 /// - Emits an `Initialize` event, providing an on-chain record of the initialization.
 pub fn initialize(
     ctx: Context<Initialize>,
@@ -90,6 +119,7 @@ pub fn initialize(
 
     let Initialize {
         game,
+        game_config,
         authority,
         game_vault,
         token_mint,
@@ -107,6 +137,15 @@ pub fn initialize(
         .safe_add(consumption_rewards)?
         .safe_add(sugar_rush_rewards)?;
 
+    // The reward constants assume the token uses `TOKEN_DECIMALS` decimals
+    require!(
+        token_mint.decimals == TOKEN_DECIMALS,
+        ErrorCode::InvalidTokenMint
+    );
+
+    // The pools are credited with the amounts transferred, so the token must not charge a transfer fee
+    require_no_transfer_fee(token_mint)?;
+
     require!(
         increase_amount <= token_account.amount,
         ErrorCode::InsufficientFunds
@@ -127,12 +166,14 @@ pub fn initialize(
         consumption_rewards,
         sugar_rush_rewards,
     )?;
+    game_config.initialize()?;
 
     // Transfer tokens from the authority's token account to the game vault.
     transfer_from_player_to_vault(
         authority,
         token_account,
         game_vault,
+        token_mint,
         token_program,
         increase_amount,
     )?;
@@ -140,14 +181,18 @@ pub fn initialize(
     game.increment_event_nonce()?;
 
     // Emit an initialization event to log the game setup.
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Initialize as u16,
         event_type: EventType::Initialize,
         event_nonce: game.event_nonce,
         data: EventData::Initialize { game: game.key() },
         initiator_type: InitiatorType::SYSTEM,
         initiator: authority.key(),
         timestamp,
-    });
+    };
+    ctx.accounts.event_ring_buffer.load_init()?.push(&event)?;
+    emit_cpi!(event);
 
     Ok(())
 }
@@ -0,0 +1,83 @@
+use crate::constants::{DAILY_STATS_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{timestamp_to_days, to_timestamp_u64};
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `InitializeDailyStats` instruction lets anyone create the stats account of today or a later day,
+/// typically the bot ahead of midnight, so the hot paths can record the day's activity from its first minute.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(day: u32)]
+pub struct InitializeDailyStats<'info> {
+    /// The payer of the stats account's rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The global game account, tracking the event nonce.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The stats account of the day to create.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DailyStats::INIT_SPACE,
+        seeds = [DAILY_STATS_SEED, day.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub daily_stats: Box<Account<'info, DailyStats>>,
+
+    /// The system program, required to create the stats account.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `InitializeDailyStats` instruction:
+///
+/// - Validates the day is not in the past.
+/// - Initializes the day's stats with zero activity.
+/// - Emits an `InitializeDailyStats` event to record the new account on-chain.
+pub fn initialize_daily_stats(ctx: Context<InitializeDailyStats>, day: u32) -> Result<()> {
+    // Obtain the current UNIX timestamp for the day check and event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let InitializeDailyStats {
+        payer,
+        game,
+        daily_stats,
+        ..
+    } = ctx.accounts;
+
+    require!(
+        day >= timestamp_to_days(timestamp)?,
+        ErrorCode::DailyStatsDayMismatch
+    );
+
+    daily_stats.initialize(day, ctx.bumps.daily_stats)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new stats account
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::InitializeDailyStats as u16,
+        event_type: EventType::InitializeDailyStats,
+        event_nonce: game.event_nonce,
+        data: EventData::InitializeDailyStats {
+            daily_stats: daily_stats.key(),
+            day,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: payer.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
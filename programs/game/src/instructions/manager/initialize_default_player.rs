@@ -1,15 +1,16 @@
 use crate::constants::{
-    DEFAULT_PLAYER, GAME_SEED, PLAYER_DATA_SEED, TOKEN_MINT, VOUCHER_MINT_SEED,
+    DEFAULT_PLAYER, EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, VOUCHER_MINT_SEED,
 };
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeDefaultPlayer<'info> {
     /// The authority (signer) who is authorized to initialize the default player.
@@ -40,8 +41,8 @@ pub struct InitializeDefaultPlayer<'info> {
     pub default_player_data: Box<Account<'info, PlayerData>>,
 
     /// The token mint representing the in-game currency.
-    #[account(address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The voucher mint account for creating vouchers.
     /// Linked to VOUCHER_MINT_SEED for derivation.
@@ -49,35 +50,40 @@ pub struct InitializeDefaultPlayer<'info> {
         seeds = [VOUCHER_MINT_SEED],
         bump,
     )]
-    pub voucher_mint: Box<Account<'info, Mint>>,
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The default player's token account, created if needed to hold the in-game tokens.
     #[account(
         init_if_needed,
         payer = authority,
         associated_token::mint = token_mint,
-        associated_token::authority = default_player
+        associated_token::authority = default_player,
+        associated_token::token_program = token_program
     )]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The default player's voucher account, created if needed to hold vouchers.
     #[account(
         init_if_needed,
         payer = authority,
         associated_token::mint = voucher_mint,
-        associated_token::authority = default_player
+        associated_token::authority = default_player,
+        associated_token::token_program = token_program
     )]
-    pub voucher_account: Box<Account<'info, TokenAccount>>,
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The SPL token program enabling token operations.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The Associated Token program used for creating associated token accounts.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The System program for basic Solana operations.
     pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// The `initialize_default_player` instruction sets up a default player and its associated data account.
@@ -114,7 +120,9 @@ pub fn initialize_default_player(ctx: Context<InitializeDefaultPlayer>) -> Resul
     game.increment_event_nonce()?;
 
     // Emit an event to record the initialization of the default player.
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::InitializeDefaultPlayer as u16,
         event_type: EventType::InitializeDefaultPlayer,
         event_nonce: game.event_nonce,
         data: EventData::InitializeDefaultPlayer {
@@ -123,7 +131,8 @@ pub fn initialize_default_player(ctx: Context<InitializeDefaultPlayer>) -> Resul
         initiator_type: InitiatorType::SYSTEM,
         initiator: authority.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
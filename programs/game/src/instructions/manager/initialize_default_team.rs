@@ -1,13 +1,14 @@
-use crate::constants::{GAME_SEED, TEAM_SEED, TOKEN_MINT};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, TEAM_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeDefaultTeam<'info> {
     /// The authority (signer) who is authorized to initialize the default team.
@@ -38,23 +39,27 @@ pub struct InitializeDefaultTeam<'info> {
         init,
         payer = authority,
         associated_token::mint = token_mint,
-        associated_token::authority = default_team
+        associated_token::authority = default_team,
+        associated_token::token_program = token_program
     )]
-    pub default_team_vault: Box<Account<'info, TokenAccount>>,
+    pub default_team_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The token mint representing the in-game currency.
-    #[account(address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The SPL token program used for token transfers and related operations.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The associated token program used for creating the default_team_vault.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The system program required for account creations and other operations.
     pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// The `initialize_default_team` instruction sets up a default team within the game ecosystem.
@@ -97,7 +102,9 @@ pub fn initialize_default_team(ctx: Context<InitializeDefaultTeam>) -> Result<()
     game.increment_event_nonce()?;
 
     // Emit an event logging the creation of the default team.
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::InitializeDefaultTeam as u16,
         event_type: EventType::InitializeDefaultTeam,
         event_nonce: game.event_nonce,
         data: EventData::InitializeDefaultTeam {
@@ -107,7 +114,8 @@ pub fn initialize_default_team(ctx: Context<InitializeDefaultTeam>) -> Result<()
         initiator_type: InitiatorType::SYSTEM,
         initiator: authority.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
@@ -0,0 +1,83 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, EXCHANGE_CONFIG_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `InitializeExchangeConfig` instruction creates the `ExchangeConfig` account holding the
+/// collateral exchange rate, the per-player daily exchange limit and the global exchange cap.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeExchangeConfig<'info> {
+    /// The authority (signer) authorized to manage the collateral exchange.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The exchange config account to be created.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ExchangeConfig::INIT_SPACE,
+        seeds = [EXCHANGE_CONFIG_SEED],
+        bump,
+    )]
+    pub exchange_config: Box<Account<'info, ExchangeConfig>>,
+
+    /// The system program required for account creation.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `InitializeExchangeConfig` instruction:
+///
+/// - Initializes the exchange config with the provided rate and limits.
+/// - Emits an `InitializeExchangeConfig` event to record the configuration on-chain.
+pub fn initialize_exchange_config(
+    ctx: Context<InitializeExchangeConfig>,
+    exchange_rate: u8,
+    daily_player_limit: u64,
+    global_cap: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let InitializeExchangeConfig {
+        authority,
+        game,
+        exchange_config,
+        ..
+    } = ctx.accounts;
+
+    exchange_config.initialize(exchange_rate, daily_player_limit, global_cap)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the exchange configuration
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::InitializeExchangeConfig as u16,
+        event_type: EventType::InitializeExchangeConfig,
+        event_nonce: game.event_nonce,
+        data: EventData::InitializeExchangeConfig {
+            exchange_config: exchange_config.key(),
+            exchange_rate,
+            daily_player_limit,
+            global_cap,
+        },
+        initiator_type: InitiatorType::VOUCHER,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, LEADERBOARD_PAGE_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `InitializeLeaderboardPage` instruction lets the bot authority create the next leaderboard page of a period.
+/// Pages are created from the highest scores down, each covering the scores right below the previous page.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeLeaderboardPage<'info> {
+    /// The bot authority creating the page, paying for its rent.
+    #[account(mut)]
+    pub bot_authority: Signer<'info>,
+
+    /// The global game account, verifying the bot authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = bot_authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The period the page is created for, tracking its existing pages.
+    #[account(mut)]
+    pub period: AccountLoader<'info, Period>,
+
+    /// The leaderboard page to create, indexed by the period's current page count.
+    #[account(
+        init,
+        payer = bot_authority,
+        space = 8 + std::mem::size_of::<LeaderboardPage>(),
+        seeds = [
+            LEADERBOARD_PAGE_SEED,
+            period.key().as_ref(),
+            period.load()?.leaderboard_page_count.to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub leaderboard_page: AccountLoader<'info, LeaderboardPage>,
+
+    /// The system program, required to create the page.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `InitializeLeaderboardPage` instruction:
+///
+/// - Registers the page on the period, deriving its upper bound from the previous page.
+/// - Initializes the page as an empty bucket covering `min_score` up to that bound.
+/// - Emits an `InitializeLeaderboardPage` event to record the new page on-chain.
+pub fn initialize_leaderboard_page(
+    ctx: Context<InitializeLeaderboardPage>,
+    min_score: u32,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let InitializeLeaderboardPage {
+        bot_authority,
+        game,
+        period,
+        leaderboard_page,
+        ..
+    } = ctx.accounts;
+
+    let (page_index, max_score) = period.load_mut()?.add_leaderboard_page(min_score)?;
+
+    leaderboard_page.load_init()?.initialize(
+        period.key(),
+        page_index,
+        min_score,
+        max_score,
+        ctx.bumps.leaderboard_page,
+    );
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new leaderboard page
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::InitializeLeaderboardPage as u16,
+        event_type: EventType::InitializeLeaderboardPage,
+        event_nonce: game.event_nonce,
+        data: EventData::InitializeLeaderboardPage {
+            period: period.key(),
+            leaderboard_page: leaderboard_page.key(),
+            page_index,
+            min_score,
+            max_score,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: bot_authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
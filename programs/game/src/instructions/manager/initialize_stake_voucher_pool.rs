@@ -1,11 +1,13 @@
-use crate::constants::{GAME_SEED, STAKE_POOL_SEED, TOKEN_MINT, VOUCHER_MINT_SEED, VOUCHER_SEED};
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, STAKE_POOL_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
+};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::{to_timestamp_u64, transfer_from_player_to_vault};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, mint_to, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
 ///
@@ -18,6 +20,7 @@ use solana_program::sysvar::clock::Clock;
 /// 3. Set up the `stake_pool_token_vault` associated token account as the stake pool's token store.
 /// 4. Call `stake_pool.initialize` to record the token mint and vault references in the stake pool.
 /// 5. Emit an `InitializeStakeVoucherPool` event to log this initialization on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeStakeVoucherPool<'info> {
     /// The authority (signer) authorized to initialize the stake pool.
@@ -41,17 +44,19 @@ pub struct InitializeStakeVoucherPool<'info> {
         init,
         payer = authority,
         associated_token::mint = voucher_mint,
-        associated_token::authority = stake_pool
+        associated_token::authority = stake_pool,
+        associated_token::token_program = token_program
     )]
-    pub stake_pool_voucher_vault: Box<Account<'info, TokenAccount>>,
+    pub stake_pool_voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The authority's associated token account from which tokens will be deposited.
     #[account(
         mut,
         associated_token::mint = token_mint,
-        associated_token::authority = authority
+        associated_token::authority = authority,
+        associated_token::token_program = token_program
     )]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The global voucher state, maintaining minted vouchers and total supply.
     /// Verified by `seeds` ensuring uniqueness.
@@ -65,25 +70,28 @@ pub struct InitializeStakeVoucherPool<'info> {
 
     /// The voucher vault account holding voucher tokens or related assets.
     #[account(mut)]
-    pub voucher_vault: Box<Account<'info, TokenAccount>>,
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The voucher mint account, from which vouchers are minted and sent to the player's voucher_account.
     #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
-    pub voucher_mint: Box<Account<'info, Mint>>,
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The token mint representing the stakeable token.
-    #[account(address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The SPL token program for token operations.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The associated token program for creating the stake_pool_token_vault.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The system program for account creations and system-level operations.
     pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Executes the `InitializeStakeVoucherPool` instruction:
@@ -100,6 +108,7 @@ pub fn initialize_stake_voucher_pool(
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
     let InitializeStakeVoucherPool {
+        token_mint,
         game,
         authority,
         stake_pool,
@@ -125,6 +134,7 @@ pub fn initialize_stake_voucher_pool(
         authority,
         token_account,
         voucher_vault,
+        token_mint,
         token_program,
         voucher_rewards,
     )?;
@@ -149,7 +159,9 @@ pub fn initialize_stake_voucher_pool(
     game.increment_event_nonce()?;
 
     // Emit an event logging the stake pool initialization
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::InitializeStakeVoucherPool as u16,
         event_type: EventType::InitializeStakeVoucherPool,
         event_nonce: game.event_nonce,
         data: EventData::InitializeStakeVoucherPool {
@@ -159,7 +171,8 @@ pub fn initialize_stake_voucher_pool(
         initiator_type: InitiatorType::STAKE,
         initiator: authority.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
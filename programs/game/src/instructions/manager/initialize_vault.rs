@@ -1,11 +1,11 @@
-use crate::constants::{GAME_SEED, TOKEN_MINT, VAULT_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, VAULT_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::{to_timestamp_u64, transfer_from_player_to_vault};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
 /// The `InitializeDeposit` instruction sets up a new deposit within the game ecosystem.
@@ -17,6 +17,7 @@ use solana_program::sysvar::clock::Clock;
 /// 3. Set up the `deposit_token_vault` associated token account as the deposit's token store.
 /// 4. Call `deposit.initialize` to record the token mint and vault references in the deposit.
 /// 5. Emit an `InitializeDeposit` event to log this initialization on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeVault<'info> {
     /// The authority (signer) authorized to initialize the deposit.
@@ -27,9 +28,10 @@ pub struct InitializeVault<'info> {
     #[account(
         mut,
         associated_token::mint = token_mint,
-        associated_token::authority = authority
+        associated_token::authority = authority,
+        associated_token::token_program = token_program
     )]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The game account.
     #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
@@ -50,23 +52,27 @@ pub struct InitializeVault<'info> {
         init_if_needed,
         payer = authority,
         associated_token::mint = token_mint,
-        associated_token::authority = vault
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
     )]
-    pub token_vault: Box<Account<'info, TokenAccount>>,
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The token mint representing the stakeable token.
-    #[account(address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The SPL token program for token operations.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The associated token program for creating the deposit_token_vault.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The system program for account creations and system-level operations.
     pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Executes the `InitializeDeposit` instruction:
@@ -89,6 +95,7 @@ pub fn initialize_vault(
         vault,
         token_account,
         token_vault,
+        token_mint: token_mint_account,
         token_program,
         ..
     } = ctx.accounts;
@@ -106,6 +113,7 @@ pub fn initialize_vault(
         authority,
         token_account,
         token_vault,
+        token_mint_account,
         token_program,
         token_amount,
     )?;
@@ -113,7 +121,9 @@ pub fn initialize_vault(
     game.increment_event_nonce()?;
 
     // Emit an event logging the stake pool initialization
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::InitializeVault as u16,
         event_type: EventType::InitializeVault,
         event_nonce: game.event_nonce,
         data: EventData::InitializeVault {
@@ -125,7 +135,8 @@ pub fn initialize_vault(
         initiator_type: InitiatorType::DEPOSIT,
         initiator: authority.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
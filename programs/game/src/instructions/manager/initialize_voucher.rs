@@ -1,13 +1,13 @@
-use crate::constants::{GAME_SEED, TOKEN_MINT, VOUCHER_MINT_SEED, VOUCHER_SEED};
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
-use crate::utils::to_timestamp_u64;
+use crate::utils::{require_no_transfer_fee, to_timestamp_u64};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::metadata::{
     self, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3, Metadata,
 };
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
 const VOUCHER_METADATA_URI: &str = "https://www.thedoomsdayark.com/meta/av/metadata.json";
@@ -18,11 +18,13 @@ const VOUCHER_SYMBOL: &str = "AV";
 /// This voucher can be used within the game to represent and distribute certain in-game assets or rewards.
 ///
 /// # Steps
-/// 1. Create and initialize the `voucher` account.
-/// 2. Create the `voucher_mint` (with `VOUCHER_MINT_SEED`) and set `voucher` as its authority.
+/// 1. Create and initialize the `voucher` account, rejecting a token mint charging a transfer fee, as the
+///    voucher vault backs the vouchers with the amounts transferred to it.
+/// 2. Create the `voucher_mint` (with `VOUCHER_MINT_SEED`) and set `voucher` as its mint and freeze authority.
 /// 3. Create the `voucher_vault` associated token account for holding tokens related to the voucher.
 /// 4. Use `create_voucher_token_metadata` to associate metadata with the `voucher_mint`.
 /// 5. Emit an `InitializeVoucher` event to record the initialization on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeVoucher<'info> {
     /// The global game account, ensuring the authority and token_mint constraints.
@@ -48,12 +50,12 @@ pub struct InitializeVoucher<'info> {
     )]
     pub voucher: Box<Account<'info, Voucher>>,
 
-    /// The main token mint account.
-    #[account(address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    /// The main token mint account, rejected if it charges a transfer fee.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The voucher token mint account.
-    /// Created with `VOUCHER_MINT_SEED` and `voucher` as authority.
+    /// Created with `VOUCHER_MINT_SEED` and `voucher` as mint and freeze authority.
     #[account(
         init,
         payer = authority,
@@ -61,17 +63,20 @@ pub struct InitializeVoucher<'info> {
         bump,
         mint::decimals = 6,
         mint::authority = voucher,
+        mint::freeze_authority = voucher,
+        mint::token_program = token_program,
     )]
-    pub voucher_mint: Box<Account<'info, Mint>>,
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The voucher token vault associated token account, holding tokens backing the voucher.
     #[account(
         init,
         payer = authority,
         associated_token::mint = token_mint,
-        associated_token::authority = voucher
+        associated_token::authority = voucher,
+        associated_token::token_program = token_program
     )]
-    pub voucher_vault: Box<Account<'info, TokenAccount>>,
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The token metadata account, checked in CPI calls.
     /// CHECK: Validated via CPI to token metadata program.
@@ -82,8 +87,7 @@ pub struct InitializeVoucher<'info> {
     pub token_metadata_program: Program<'info, Metadata>,
 
     /// The SPL token program for token minting, burning, and transfers.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The associated token program for creating token accounts.
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -93,6 +97,10 @@ pub struct InitializeVoucher<'info> {
 
     /// Rent sysvar to fetch rent exemption data.
     pub rent: Sysvar<'info, Rent>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Executes the `InitializeVoucher` instruction:
@@ -104,6 +112,9 @@ pub fn initialize_voucher(ctx: Context<InitializeVoucher>) -> Result<()> {
     // Obtain current UNIX timestamp for event logging.
     let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
 
+    // The voucher vault backs the vouchers with the amounts transferred, so the token must not charge a fee
+    require_no_transfer_fee(&ctx.accounts.token_mint)?;
+
     // Create metadata for the voucher token
     create_voucher_token_metadata(&ctx)?;
 
@@ -122,16 +133,19 @@ pub fn initialize_voucher(ctx: Context<InitializeVoucher>) -> Result<()> {
     game.increment_event_nonce()?;
 
     // Emit initialization event
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::InitializeVoucher as u16,
         event_type: EventType::InitializeVoucher,
         event_nonce: game.event_nonce,
         data: EventData::InitializeVoucher {
-            voucher: voucher.key()
+            voucher: voucher.key(),
         },
         initiator_type: InitiatorType::VOUCHER,
         initiator: authority.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
@@ -0,0 +1,159 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, VOUCHER_KIND_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_player_to_vault};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `IssueVoucherKind` instruction lets the game authority hand out vouchers of a kind to a recipient,
+/// depositing the tokens backing them at the kind's value rate into the kind's vault.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct IssueVoucherKind<'info> {
+    /// The authority (signer) authorized to manage the game, funding the backing tokens.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The voucher kind being issued.
+    #[account(
+        mut,
+        seeds = [VOUCHER_KIND_SEED, voucher_kind.kind_id.to_le_bytes().as_ref()],
+        bump = voucher_kind.bump,
+        has_one = voucher_mint,
+        has_one = voucher_vault,
+    )]
+    pub voucher_kind: Box<Account<'info, VoucherKind>>,
+
+    /// The mint of the kind's vouchers.
+    #[account(mut)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The vault holding the tokens backing the kind's vouchers.
+    #[account(mut)]
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The authority's token account, paying the backing tokens.
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The wallet receiving the vouchers.
+    /// CHECK: Only used as the owner of `recipient_voucher_account`.
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The recipient's token account of the kind's vouchers, created if needed.
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = voucher_mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The main token mint account.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program for the backing transfer and the voucher minting.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program for creating the recipient's voucher account.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program, required to create the recipient's voucher account.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `IssueVoucherKind` instruction:
+///
+/// - Requires the kind to be enabled and deposits the tokens backing `amount` vouchers into its vault.
+/// - Mints `amount` vouchers of the kind to the recipient.
+/// - Emits an `IssueVoucherKind` event to record the issuance on-chain.
+pub fn issue_voucher_kind(ctx: Context<IssueVoucherKind>, amount: u64) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let IssueVoucherKind {
+        authority,
+        game,
+        voucher_kind,
+        voucher_mint,
+        voucher_vault,
+        authority_token_account,
+        recipient,
+        recipient_voucher_account,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    voucher_kind.require_enabled()?;
+    let backing_amount = voucher_kind.value_of(amount)?;
+    voucher_kind.mint(amount)?;
+
+    // Deposit the tokens backing the new vouchers
+    transfer_from_player_to_vault(
+        authority,
+        authority_token_account,
+        voucher_vault,
+        token_mint,
+        token_program,
+        backing_amount,
+    )?;
+
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: voucher_mint.to_account_info(),
+                to: recipient_voucher_account.to_account_info(),
+                authority: voucher_kind.to_account_info(),
+            },
+            &[&[
+                VOUCHER_KIND_SEED,
+                voucher_kind.kind_id.to_le_bytes().as_ref(),
+                &[voucher_kind.bump],
+            ]],
+        ),
+        amount,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the issued vouchers
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::IssueVoucherKind as u16,
+        event_type: EventType::IssueVoucherKind,
+        event_nonce: game.event_nonce,
+        data: EventData::IssueVoucherKind {
+            voucher_kind: voucher_kind.key(),
+            recipient: recipient.key(),
+            amount,
+            backing_amount,
+            total_supply: voucher_kind.total_supply,
+        },
+        initiator_type: InitiatorType::VOUCHER,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,280 @@
+use crate::constants::{EARNINGS_PER_ORE_PRECISION, GAME_SEED, LOTTERY_TABLE_SEED};
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::{load_legacy_account, store_migrated_account};
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_safe_math::SafeMath;
+
+/// Size of a `Round` account created while `earnings_per_ore` was an unscaled `u64`.
+const LEGACY_ROUND_LEN: usize = 8 + 453;
+
+/// Size of a `PlayerData` account created while `earnings_per_ore` was an unscaled `u64`.
+const LEGACY_PLAYER_DATA_LEN: usize = 8 + 510;
+
+/// The lottery table the draws committed before lottery tables existed are revealed on.
+const LEGACY_LOTTERY_TABLE_ID: u16 = 0;
+
+/// The frozen `Round` layout of `LEGACY_ROUND_LEN` accounts. Must never change.
+#[derive(AnchorDeserialize)]
+struct LegacyRound {
+    round_number: u16,
+    round_vault: Pubkey,
+    start_time: u64,
+    end_time: u64,
+    last_call_slot: u64,
+    call_count: u8,
+    earnings_per_ore: u64,
+    sold_ores: u32,
+    available_ores: u32,
+    grand_prize_pool_balance: u64,
+    first_grand_prizes: u64,
+    second_grand_prizes: u64,
+    distributed_grand_prizes: u64,
+    grand_prize_distribution_index: u8,
+    last_active_participant_list: Vec<Pubkey>,
+    auto_reinvesting_players: u16,
+    is_over: bool,
+    is_grand_prize_distribution_completed: bool,
+    last_collected_exit_reward_timestamp: u64,
+    last_collected_sugar_rush_reward_timestamp: u64,
+    bump: u8,
+}
+
+/// The frozen `PlayerData` layout of `LEGACY_PLAYER_DATA_LEN` accounts. Must never change.
+#[derive(AnchorDeserialize)]
+struct LegacyPlayerData {
+    player: Pubkey,
+    token_account: Pubkey,
+    voucher_account: Pubkey,
+    nonce: u16,
+    team: Pubkey,
+    team_applications: Vec<Pubkey>,
+    can_apply_to_team_timestamp: u64,
+    referrer: Pubkey,
+    referral_count: u16,
+    collectable_referral_rewards: u64,
+    collected_referral_rewards: u64,
+    current_round: Pubkey,
+    current_period: Pubkey,
+    current_period_purchased_ores: u32,
+    is_exited: bool,
+    earnings_per_ore: u64,
+    collectable_construction_rewards: u64,
+    available_ores: u32,
+    purchased_ores: u32,
+    is_auto_reinvesting: bool,
+    consecutive_purchased_days: u16,
+    last_purchased_day: u32,
+    last_collected_airdrop_reward_day: u32,
+    collected_airdrop_rewards: u64,
+    randomness_provider: Pubkey,
+    commit_slot: u64,
+    spin_symbols: [u8; 3],
+    result_multiplier: u16,
+    result_revealed: bool,
+    collected_construction_rewards: u64,
+    collected_grand_prizes: u64,
+    collectable_consumption_rewards: u64,
+    collected_consumption_rewards: u64,
+    collected_exit_rewards: u64,
+    collected_lottery_rewards: u64,
+    collected_individual_rewards: u64,
+    collected_team_rewards: u64,
+}
+
+/// The `MigrateEarningsPerOre` instruction lets the bot upgrade `Round` and `PlayerData` accounts created
+/// while `earnings_per_ore` was an unscaled `u64` to their current layout, scaling `earnings_per_ore`
+/// by `EARNINGS_PER_ORE_PRECISION`.
+///
+/// Legacy accounts are recognized by their frozen size and read with their frozen layout. The fields added since
+/// start at zero, except that a round accrues exit rewards at the game's `exit_rewards_per_second` from its last
+/// collection, and a lottery draw left unrevealed is revealed on lottery table `LEGACY_LOTTERY_TABLE_ID`.
+///
+/// The accounts to migrate are passed through `remaining_accounts`. Accounts already on the current layout
+/// are skipped, so the instruction can be re-run safely; accounts of any other size are rejected.
+#[derive(Accounts)]
+pub struct MigrateEarningsPerOre<'info> {
+    /// The bot authority running the migration. Pays the rent for the grown accounts.
+    #[account(mut)]
+    pub bot_authority: Signer<'info>,
+
+    /// The global game account, verifying the bot authority.
+    #[account(
+        seeds = [GAME_SEED], bump,
+        has_one = bot_authority @ ErrorCode::AuthorityMismatch,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The system program, required to fund the grown accounts.
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrates every legacy `Round` and `PlayerData` account supplied in `remaining_accounts`.
+///
+/// Steps:
+/// 1. Verify each account is owned by the program and is a `Round` or a `PlayerData`.
+/// 2. Skip accounts already on the current layout and fail on any size other than the legacy one.
+/// 3. Read the account with its legacy layout and convert it, scaling `earnings_per_ore` by `EARNINGS_PER_ORE_PRECISION`.
+/// 4. Fund the extra rent from the bot authority, grow the account and rewrite it on the current layout.
+pub fn migrate_earnings_per_ore<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MigrateEarningsPerOre<'info>>,
+) -> Result<()> {
+    let program_id = ctx.program_id;
+    let remaining_accounts = ctx.remaining_accounts;
+
+    let MigrateEarningsPerOre {
+        bot_authority,
+        game,
+        system_program,
+    } = ctx.accounts;
+
+    require!(
+        !remaining_accounts.is_empty(),
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    let bot_authority = bot_authority.to_account_info();
+    let system_program = system_program.to_account_info();
+
+    let mut legacy_lottery_table = None;
+    let mut migrated_accounts: u32 = 0;
+
+    for account in remaining_accounts {
+        let discriminator = {
+            let data = account.try_borrow_data()?;
+            require!(data.len() >= 8, ErrorCode::InvalidRemainingAccounts);
+            <[u8; 8]>::try_from(&data[..8]).unwrap()
+        };
+
+        if discriminator == Round::DISCRIMINATOR {
+            let Some(legacy) =
+                load_legacy_account::<LegacyRound, Round>(account, program_id, LEGACY_ROUND_LEN)?
+            else {
+                continue;
+            };
+
+            let round = migrate_round(legacy, game.exit_rewards_per_second)?;
+            store_migrated_account(account, &round, &bot_authority, &system_program)?;
+        } else if discriminator == PlayerData::DISCRIMINATOR {
+            let Some(legacy) = load_legacy_account::<LegacyPlayerData, PlayerData>(
+                account,
+                program_id,
+                LEGACY_PLAYER_DATA_LEN,
+            )?
+            else {
+                continue;
+            };
+
+            // Only draws left unrevealed need a table, so it is derived once and only if one is found
+            let lottery_table = if legacy.result_revealed {
+                Pubkey::default()
+            } else {
+                *legacy_lottery_table.get_or_insert_with(|| {
+                    Pubkey::find_program_address(
+                        &[
+                            LOTTERY_TABLE_SEED,
+                            LEGACY_LOTTERY_TABLE_ID.to_le_bytes().as_ref(),
+                        ],
+                        program_id,
+                    )
+                    .0
+                })
+            };
+
+            let player_data = migrate_player_data(legacy, lottery_table)?;
+            store_migrated_account(account, &player_data, &bot_authority, &system_program)?;
+        } else {
+            return err!(ErrorCode::InvalidRemainingAccounts);
+        }
+
+        migrated_accounts = migrated_accounts.safe_add(1)?;
+    }
+
+    msg!("Migrated earnings per ORE accounts: {}", migrated_accounts);
+
+    Ok(())
+}
+
+/// Converts a legacy round to the current layout.
+///
+/// # Arguments
+/// - `legacy`: The round on the legacy layout.
+/// - `exit_rewards_per_second`: The game's exit reward rate, which the round accrues from now on.
+fn migrate_round(legacy: LegacyRound, exit_rewards_per_second: u64) -> Result<Round> {
+    Ok(Round {
+        round_number: legacy.round_number,
+        round_vault: legacy.round_vault,
+        start_time: legacy.start_time,
+        end_time: legacy.end_time,
+        last_call_slot: legacy.last_call_slot,
+        call_count: legacy.call_count,
+        earnings_per_ore: (legacy.earnings_per_ore as u128).safe_mul(EARNINGS_PER_ORE_PRECISION)?,
+        sold_ores: legacy.sold_ores,
+        available_ores: legacy.available_ores,
+        grand_prize_pool_balance: legacy.grand_prize_pool_balance,
+        first_grand_prizes: legacy.first_grand_prizes,
+        second_grand_prizes: legacy.second_grand_prizes,
+        distributed_grand_prizes: legacy.distributed_grand_prizes,
+        grand_prize_distribution_index: legacy.grand_prize_distribution_index,
+        last_active_participant_list: legacy.last_active_participant_list,
+        auto_reinvesting_players: legacy.auto_reinvesting_players,
+        is_over: legacy.is_over,
+        is_grand_prize_distribution_completed: legacy.is_grand_prize_distribution_completed,
+        exit_rewards_per_second,
+        last_collected_exit_reward_timestamp: legacy.last_collected_exit_reward_timestamp,
+        last_collected_sugar_rush_reward_timestamp: legacy
+            .last_collected_sugar_rush_reward_timestamp,
+        bump: legacy.bump,
+        ..Default::default()
+    })
+}
+
+/// Converts a legacy player data account to the current layout.
+///
+/// # Arguments
+/// - `legacy`: The player data on the legacy layout.
+/// - `lottery_table`: The lottery table an unrevealed draw is revealed on.
+fn migrate_player_data(legacy: LegacyPlayerData, lottery_table: Pubkey) -> Result<PlayerData> {
+    Ok(PlayerData {
+        player: legacy.player,
+        token_account: legacy.token_account,
+        voucher_account: legacy.voucher_account,
+        nonce: legacy.nonce,
+        team: legacy.team,
+        team_applications: legacy.team_applications,
+        can_apply_to_team_timestamp: legacy.can_apply_to_team_timestamp,
+        referrer: legacy.referrer,
+        referral_count: legacy.referral_count,
+        collectable_referral_rewards: legacy.collectable_referral_rewards,
+        collected_referral_rewards: legacy.collected_referral_rewards,
+        current_round: legacy.current_round,
+        current_period: legacy.current_period,
+        current_period_purchased_ores: legacy.current_period_purchased_ores,
+        is_exited: legacy.is_exited,
+        earnings_per_ore: (legacy.earnings_per_ore as u128).safe_mul(EARNINGS_PER_ORE_PRECISION)?,
+        collectable_construction_rewards: legacy.collectable_construction_rewards,
+        available_ores: legacy.available_ores,
+        purchased_ores: legacy.purchased_ores,
+        is_auto_reinvesting: legacy.is_auto_reinvesting,
+        consecutive_purchased_days: legacy.consecutive_purchased_days,
+        last_purchased_day: legacy.last_purchased_day,
+        last_collected_airdrop_reward_day: legacy.last_collected_airdrop_reward_day,
+        collected_airdrop_rewards: legacy.collected_airdrop_rewards,
+        randomness_provider: legacy.randomness_provider,
+        lottery_table,
+        commit_slot: legacy.commit_slot,
+        spin_symbols: legacy.spin_symbols,
+        result_multiplier: legacy.result_multiplier,
+        result_revealed: legacy.result_revealed,
+        collected_construction_rewards: legacy.collected_construction_rewards,
+        collected_grand_prizes: legacy.collected_grand_prizes,
+        collectable_consumption_rewards: legacy.collectable_consumption_rewards,
+        collected_consumption_rewards: legacy.collected_consumption_rewards,
+        collected_exit_rewards: legacy.collected_exit_rewards,
+        collected_lottery_rewards: legacy.collected_lottery_rewards,
+        collected_individual_rewards: legacy.collected_individual_rewards,
+        collected_team_rewards: legacy.collected_team_rewards,
+        ..Default::default()
+    })
+}
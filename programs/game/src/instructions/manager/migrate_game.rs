@@ -0,0 +1,229 @@
+use crate::constants::{
+    BOT_HEARTBEAT_TIMEOUT_SECONDS, CANDY_TAP_COOLDOWN_SECONDS, CANDY_TAP_MIN_INTERVAL_SECONDS,
+    CAPTAIN_INACTIVITY_SECONDS, COMPLIANCE_ATTESTATION_MAX_AGE_SECONDS, EVENT_RING_BUFFER_SEED,
+    GAME_CONFIG_SEED, GAME_SEED, MAX_START_TIME_DELAY, MIN_START_LEAD_TIME, REENTRY_FEE,
+    SUGAR_RUSH_ESCALATION_DELAY_SECONDS, SUGAR_RUSH_ESCALATION_RATE, SUGAR_RUSH_MAX_MULTIPLIER,
+    TEAM_APPLICATIONS_PER_DAY, TEAM_DISTRIBUTION_APPROVAL_THRESHOLD,
+    TEAM_DISTRIBUTION_VETO_SECONDS, TEAM_REAPPLICATION_COOLDOWN_SECONDS,
+};
+use crate::errors::ErrorCode;
+use crate::state::{EventRingBuffer, Game, GameConfig};
+use crate::utils::{load_legacy_account, store_migrated_account};
+use anchor_lang::prelude::*;
+
+/// Size of the `Game` account created by the first deployment, before any field was appended.
+const LEGACY_GAME_LEN: usize = 8 + 528;
+
+/// The frozen `Game` layout of `LEGACY_GAME_LEN` accounts. Must never change.
+#[derive(AnchorDeserialize)]
+struct LegacyGame {
+    authority: Pubkey,
+    bot_authority: Pubkey,
+    token_mint: Pubkey,
+    game_vault: Pubkey,
+    default_team: Pubkey,
+    default_player: Pubkey,
+    current_round: Pubkey,
+    current_period: Pubkey,
+    construction_rewards_pool_balance: u64,
+    bonus_rewards_pool_balance: u64,
+    lottery_rewards_pool_balance: u64,
+    developer_rewards_pool_balance: u64,
+    referral_rewards_pool_balance: u64,
+    round_rewards_pool_balance: u64,
+    period_rewards_pool_balance: u64,
+    registration_rewards_pool_balance: u64,
+    airdrop_rewards_pool_balance: u64,
+    consumption_rewards_pool_balance: u64,
+    exit_rewards_pool_balance: u64,
+    sugar_rush_rewards_pool_balance: u64,
+    distributable_consumption_rewards: u64,
+    distributed_registration_rewards: u64,
+    distributed_airdrop_rewards: u64,
+    distributed_consumption_rewards: u64,
+    distributed_exit_rewards: u64,
+    distributed_stake_rewards: u64,
+    distributed_construction_rewards: u64,
+    distributed_bonus_rewards: u64,
+    distributed_lottery_rewards: u64,
+    distributed_developer_rewards: u64,
+    distributed_referral_rewards: u64,
+    distributed_grand_prizes: u64,
+    distributed_individual_rewards: u64,
+    distributed_team_rewards: u64,
+    current_day_distributed_airdrop_rewards: u64,
+    current_day_cap_airdrop_rewards: u64,
+    registration_rewards: u64,
+    sugar_rush_rewards_per_second: u64,
+    exit_rewards_per_second: u64,
+    team_join_cooldown_seconds: u64,
+    team_nonce: u32,
+    event_nonce: u32,
+    round_nonce: u16,
+    period_nonce: u16,
+    current_day: u32,
+}
+
+impl From<LegacyGame> for Game {
+    fn from(legacy: LegacyGame) -> Self {
+        Game {
+            authority: legacy.authority,
+            bot_authority: legacy.bot_authority,
+            token_mint: legacy.token_mint,
+            game_vault: legacy.game_vault,
+            default_team: legacy.default_team,
+            default_player: legacy.default_player,
+            current_round: legacy.current_round,
+            current_period: legacy.current_period,
+            construction_rewards_pool_balance: legacy.construction_rewards_pool_balance,
+            bonus_rewards_pool_balance: legacy.bonus_rewards_pool_balance,
+            lottery_rewards_pool_balance: legacy.lottery_rewards_pool_balance,
+            developer_rewards_pool_balance: legacy.developer_rewards_pool_balance,
+            referral_rewards_pool_balance: legacy.referral_rewards_pool_balance,
+            round_rewards_pool_balance: legacy.round_rewards_pool_balance,
+            period_rewards_pool_balance: legacy.period_rewards_pool_balance,
+            registration_rewards_pool_balance: legacy.registration_rewards_pool_balance,
+            airdrop_rewards_pool_balance: legacy.airdrop_rewards_pool_balance,
+            consumption_rewards_pool_balance: legacy.consumption_rewards_pool_balance,
+            exit_rewards_pool_balance: legacy.exit_rewards_pool_balance,
+            sugar_rush_rewards_pool_balance: legacy.sugar_rush_rewards_pool_balance,
+            distributable_consumption_rewards: legacy.distributable_consumption_rewards,
+            distributed_registration_rewards: legacy.distributed_registration_rewards,
+            distributed_airdrop_rewards: legacy.distributed_airdrop_rewards,
+            distributed_consumption_rewards: legacy.distributed_consumption_rewards,
+            distributed_exit_rewards: legacy.distributed_exit_rewards,
+            distributed_stake_rewards: legacy.distributed_stake_rewards,
+            distributed_construction_rewards: legacy.distributed_construction_rewards,
+            distributed_bonus_rewards: legacy.distributed_bonus_rewards,
+            distributed_lottery_rewards: legacy.distributed_lottery_rewards,
+            distributed_developer_rewards: legacy.distributed_developer_rewards,
+            distributed_referral_rewards: legacy.distributed_referral_rewards,
+            distributed_grand_prizes: legacy.distributed_grand_prizes,
+            distributed_individual_rewards: legacy.distributed_individual_rewards,
+            distributed_team_rewards: legacy.distributed_team_rewards,
+            current_day_distributed_airdrop_rewards: legacy.current_day_distributed_airdrop_rewards,
+            current_day_cap_airdrop_rewards: legacy.current_day_cap_airdrop_rewards,
+            registration_rewards: legacy.registration_rewards,
+            sugar_rush_rewards_per_second: legacy.sugar_rush_rewards_per_second,
+            exit_rewards_per_second: legacy.exit_rewards_per_second,
+            team_join_cooldown_seconds: legacy.team_join_cooldown_seconds,
+            team_nonce: legacy.team_nonce,
+            event_nonce: legacy.event_nonce,
+            round_nonce: legacy.round_nonce,
+            period_nonce: legacy.period_nonce,
+            current_day: legacy.current_day,
+
+            // The appended settings take the defaults `initialize` gives a new game
+            sugar_rush_escalation_delay_seconds: SUGAR_RUSH_ESCALATION_DELAY_SECONDS,
+            sugar_rush_escalation_rate: SUGAR_RUSH_ESCALATION_RATE,
+            sugar_rush_max_multiplier: SUGAR_RUSH_MAX_MULTIPLIER,
+            max_team_applications_per_day: TEAM_APPLICATIONS_PER_DAY,
+            team_reapplication_cooldown_seconds: TEAM_REAPPLICATION_COOLDOWN_SECONDS,
+            captain_inactivity_seconds: CAPTAIN_INACTIVITY_SECONDS,
+            team_distribution_approval_threshold: TEAM_DISTRIBUTION_APPROVAL_THRESHOLD,
+            team_distribution_veto_seconds: TEAM_DISTRIBUTION_VETO_SECONDS,
+            compliance_attestation_max_age_seconds: COMPLIANCE_ATTESTATION_MAX_AGE_SECONDS,
+            reentry_fee: REENTRY_FEE,
+            max_future_start_seconds: MAX_START_TIME_DELAY,
+            min_start_lead_seconds: MIN_START_LEAD_TIME,
+            candy_tap_cooldown_seconds: CANDY_TAP_COOLDOWN_SECONDS,
+            candy_tap_min_interval_seconds: CANDY_TAP_MIN_INTERVAL_SECONDS,
+            bot_heartbeat_timeout_seconds: BOT_HEARTBEAT_TIMEOUT_SECONDS,
+
+            ..Default::default()
+        }
+    }
+}
+
+/// The `MigrateGame` instruction lets the game authority upgrade the `Game` account created by the first
+/// deployment to its current layout, and create the `GameConfig` and `EventRingBuffer` accounts that
+/// `initialize` now creates alongside it. It must run right after the program upgrade, as every other
+/// instruction fails to deserialize the legacy `Game`.
+///
+/// The legacy account is recognized by its frozen size and read with its frozen layout. The appended pool
+/// balances and counters start at zero, and the appended settings take the defaults of a new game.
+///
+/// Accounts already on the current layout are skipped, so the instruction can be re-run safely.
+#[derive(Accounts)]
+pub struct MigrateGame<'info> {
+    /// The game authority running the migration. Pays the rent for the grown and the created accounts.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The global game account, migrated if it is still on the legacy layout.
+    /// CHECK: Derived from its seeds; its owner, discriminator, size and authority are checked by the handler.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: UncheckedAccount<'info>,
+
+    /// The game config, created with the default pool shares and costs if it does not exist yet.
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + GameConfig::INIT_SPACE,
+        seeds = [GAME_CONFIG_SEED],
+        bump,
+    )]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// The event ring buffer, created empty if it does not exist yet.
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<EventRingBuffer>(),
+        seeds = [EVENT_RING_BUFFER_SEED],
+        bump,
+    )]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+
+    /// The system program, required to fund the grown and the created accounts.
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrates the legacy `Game` account and creates the accounts introduced alongside it.
+///
+/// Steps:
+/// 1. Read the game with its legacy layout if it still has the legacy size, and with its current layout otherwise.
+/// 2. Verify the signer is the game authority.
+/// 3. Fund the extra rent from the authority, grow the legacy game and rewrite it on the current layout.
+/// 4. Initialize the game config with its defaults if it was just created.
+pub fn migrate_game(ctx: Context<MigrateGame>) -> Result<()> {
+    let program_id = ctx.program_id;
+
+    let MigrateGame {
+        authority,
+        game,
+        game_config,
+        system_program,
+        ..
+    } = ctx.accounts;
+
+    let game = game.to_account_info();
+    let legacy = load_legacy_account::<LegacyGame, Game>(&game, program_id, LEGACY_GAME_LEN)?;
+
+    let game_authority = match &legacy {
+        Some(legacy) => legacy.authority,
+        None => Game::try_deserialize(&mut &game.try_borrow_data()?[..])?.authority,
+    };
+    require_keys_eq!(
+        authority.key(),
+        game_authority,
+        ErrorCode::AuthorityMismatch
+    );
+
+    if let Some(legacy) = legacy {
+        store_migrated_account(
+            &game,
+            &Game::from(legacy),
+            &authority.to_account_info(),
+            &system_program.to_account_info(),
+        )?;
+        msg!("Migrated the game account");
+    }
+
+    // A game config just created is zeroed, and a valid one never costs zero lamports per ORE
+    if game_config.lamports_per_ore == 0 {
+        game_config.initialize()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,178 @@
+use crate::constants::GAME_SEED;
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::{load_legacy_zero_copy_account, store_migrated_zero_copy_account};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// Size of a `Period` account created while it was a borsh account paying the top three teams and the top player.
+const LEGACY_PERIOD_LEN: usize = 8 + 836;
+
+/// The legacy split of the team rewards across the top three teams, as a payout curve.
+const LEGACY_TEAM_PAYOUT_CURVE: [u8; TEAM_WINNERS_COUNT] = [50, 30, 20, 0, 0, 0, 0, 0, 0, 0];
+
+/// The legacy split of the individual rewards, all paid to the top player, as a payout curve.
+const LEGACY_PLAYER_PAYOUT_CURVE: [u8; PLAYER_WINNERS_COUNT] = [100, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+/// The frozen `Period` layout of `LEGACY_PERIOD_LEN` accounts. Must never change.
+#[derive(AnchorDeserialize)]
+struct LegacyPeriod {
+    period_number: u16,
+    period_vault: Pubkey,
+    team_reward_pool_balance: u64,
+    individual_reward_pool_balance: u64,
+    start_time: u64,
+    end_time: u64,
+    top_player_list: Vec<LegacyTopEntry>,
+    top_team_list: Vec<LegacyTopEntry>,
+    team_rewards: u64,
+    team_first_place_rewards: u64,
+    team_second_place_rewards: u64,
+    team_third_place_rewards: u64,
+    individual_rewards: u64,
+    is_distribution_completed: bool,
+    bump: u8,
+}
+
+/// The frozen layout of the legacy `TopPlayerAccount` and `TopTeamAccount` entries. Must never change.
+#[derive(AnchorDeserialize)]
+struct LegacyTopEntry {
+    key: Pubkey,
+    purchased_ores: u32,
+}
+
+/// The `MigratePeriods` instruction lets the bot convert the `Period` accounts created while periods were
+/// borsh accounts to the zero-copy layout. Every instruction loading a period through its `AccountLoader`,
+/// `purchase` included, rejects a legacy period, so the current period must be migrated right after the
+/// program upgrade, and any ended period whose rewards are still unclaimed before they are claimed.
+///
+/// A legacy period keeps its leaderboards and reward pools. Its team rewards stay split 50/30/20 across the
+/// top three teams and its individual rewards stay paid to the top player, now as rank rewards claimed through
+/// `claim_team_leaderboard_reward` and `claim_leaderboard_reward`. A period already distributed has every rank
+/// marked as claimed.
+/// The team leaderboard stays scored on the total ores purchased, without participation thresholds.
+///
+/// The periods to migrate are passed through `remaining_accounts`. Accounts already on the current layout
+/// are skipped, so the instruction can be re-run safely; accounts of any other size are rejected.
+#[derive(Accounts)]
+pub struct MigratePeriods<'info> {
+    /// The bot authority running the migration. Pays the rent for the grown accounts.
+    #[account(mut)]
+    pub bot_authority: Signer<'info>,
+
+    /// The global game account, verifying the bot authority and providing the default player and team
+    /// padding the leaderboards.
+    #[account(
+        seeds = [GAME_SEED], bump,
+        has_one = bot_authority @ ErrorCode::AuthorityMismatch,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The system program, required to fund the grown accounts.
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrates every legacy `Period` supplied in `remaining_accounts`.
+///
+/// Steps:
+/// 1. Verify each account is owned by the program and is a `Period`.
+/// 2. Skip periods already on the current layout and fail on any unknown size.
+/// 3. Convert the period, turning its legacy reward split into rank rewards.
+/// 4. Fund the extra rent from the bot authority, grow the period and rewrite it on the zero-copy layout.
+pub fn migrate_periods<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MigratePeriods<'info>>,
+) -> Result<()> {
+    let program_id = ctx.program_id;
+    let remaining_accounts = ctx.remaining_accounts;
+
+    let MigratePeriods {
+        bot_authority,
+        game,
+        system_program,
+    } = ctx.accounts;
+
+    require!(
+        !remaining_accounts.is_empty(),
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    let bot_authority = bot_authority.to_account_info();
+    let system_program = system_program.to_account_info();
+
+    let mut migrated_accounts: u32 = 0;
+
+    for account in remaining_accounts {
+        let Some(legacy) = load_legacy_zero_copy_account::<LegacyPeriod, Period>(
+            account,
+            program_id,
+            LEGACY_PERIOD_LEN,
+        )?
+        else {
+            continue;
+        };
+
+        let period = migrate_period(legacy, game.default_player, game.default_team)?;
+        store_migrated_zero_copy_account(account, &period, &bot_authority, &system_program)?;
+        migrated_accounts = migrated_accounts.safe_add(1)?;
+    }
+
+    msg!("Migrated periods: {}", migrated_accounts);
+
+    Ok(())
+}
+
+/// Converts a legacy period to the zero-copy layout.
+///
+/// # Arguments
+/// - `legacy`: The period on the legacy layout.
+/// - `default_player`: The default player padding `top_player_list`.
+/// - `default_team`: The default team padding `top_team_list`.
+fn migrate_period(
+    legacy: LegacyPeriod,
+    default_player: Pubkey,
+    default_team: Pubkey,
+) -> Result<Period> {
+    let mut period: Period = bytemuck::Zeroable::zeroed();
+    period.initialize(
+        legacy.period_number,
+        legacy.period_vault,
+        legacy.start_time,
+        legacy.end_time.safe_sub(legacy.start_time)?,
+        legacy.team_rewards,
+        legacy.individual_rewards,
+        default_player,
+        default_team,
+        legacy.bump,
+    )?;
+
+    period.team_reward_pool_balance = legacy.team_reward_pool_balance;
+    period.individual_reward_pool_balance = legacy.individual_reward_pool_balance;
+    period.team_rank_rewards[..3].copy_from_slice(&[
+        legacy.team_first_place_rewards,
+        legacy.team_second_place_rewards,
+        legacy.team_third_place_rewards,
+    ]);
+    period.player_rank_rewards[0] = legacy.individual_rewards;
+    period.team_payout_curve = LEGACY_TEAM_PAYOUT_CURVE;
+    period.player_payout_curve = LEGACY_PLAYER_PAYOUT_CURVE;
+
+    for (entry, legacy_entry) in period
+        .top_player_list
+        .iter_mut()
+        .zip(legacy.top_player_list)
+    {
+        entry.player = legacy_entry.key;
+        entry.purchased_ores = legacy_entry.purchased_ores;
+    }
+    for (entry, legacy_entry) in period.top_team_list.iter_mut().zip(legacy.top_team_list) {
+        entry.team = legacy_entry.key;
+        entry.purchased_ores = legacy_entry.purchased_ores;
+    }
+
+    if legacy.is_distribution_completed {
+        period.team_claimed_ranks = (1u16 << TEAM_WINNERS_COUNT) - 1;
+        period.player_claimed_ranks = (1u16 << PLAYER_WINNERS_COUNT) - 1;
+    }
+
+    Ok(period)
+}
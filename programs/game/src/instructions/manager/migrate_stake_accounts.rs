@@ -0,0 +1,220 @@
+use crate::constants::{
+    ANNUAL_RATE, GAME_SEED, MIN_ANNUAL_RATE, STAKE_POOL_SEED, TARGET_UTILIZATION,
+};
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::{load_legacy_account, store_migrated_account, write_account};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// Size of a `StakePool` account created before voucher staking, the rate curve and the wind-down flags.
+const LEGACY_STAKE_POOL_LEN: usize = 8 + 158;
+
+/// Size of a `StakeOrder` account created before voucher staking, interest claims and the stake boost.
+const LEGACY_STAKE_ORDER_LEN: usize = 8 + 86;
+
+/// The frozen `StakePool` layout of `LEGACY_STAKE_POOL_LEN` accounts. Must never change.
+#[derive(AnchorDeserialize)]
+struct LegacyStakePool {
+    stake_pool_token_vault: Pubkey,
+    stake_pool_voucher_vault: Pubkey,
+    staked_amount: u64,
+    distributed_token_rewards: u64,
+    burned_token_rewards: u64,
+    distributed_voucher_rewards: u64,
+    burned_voucher_rewards: u64,
+    token_rewards_pool_balance: u64,
+    voucher_rewards_pool_balance: u64,
+    distributable_token_rewards: u64,
+    one_shard: u64,
+    annual_rate: u8,
+    early_unlock_rate: u8,
+    lock_duration: u64,
+    early_unlock_duration: u64,
+    active_orders: u32,
+}
+
+impl From<LegacyStakePool> for StakePool {
+    fn from(legacy: LegacyStakePool) -> Self {
+        StakePool {
+            stake_pool_token_vault: legacy.stake_pool_token_vault,
+            stake_pool_voucher_vault: legacy.stake_pool_voucher_vault,
+            staked_amount: legacy.staked_amount,
+            distributed_token_rewards: legacy.distributed_token_rewards,
+            burned_token_rewards: legacy.burned_token_rewards,
+            distributed_voucher_rewards: legacy.distributed_voucher_rewards,
+            burned_voucher_rewards: legacy.burned_voucher_rewards,
+            token_rewards_pool_balance: legacy.token_rewards_pool_balance,
+            voucher_rewards_pool_balance: legacy.voucher_rewards_pool_balance,
+            distributable_token_rewards: legacy.distributable_token_rewards,
+            one_shard: legacy.one_shard,
+            annual_rate: legacy.annual_rate,
+            early_unlock_rate: legacy.early_unlock_rate,
+            lock_duration: legacy.lock_duration,
+            early_unlock_duration: legacy.early_unlock_duration,
+            active_orders: legacy.active_orders,
+            staked_voucher_amount: 0,
+            min_annual_rate: MIN_ANNUAL_RATE,
+            max_annual_rate: ANNUAL_RATE,
+            target_utilization: TARGET_UTILIZATION,
+            is_winding_down: false,
+            is_decommissioned: false,
+        }
+    }
+}
+
+/// The frozen `StakeOrder` layout of `LEGACY_STAKE_ORDER_LEN` accounts. Must never change.
+#[derive(AnchorDeserialize)]
+struct LegacyStakeOrder {
+    stake_number: u16,
+    stake_amount: u64,
+    token_rewards: u64,
+    voucher_rewards: u64,
+    stake_order_vault: Pubkey,
+    created_timestamp: u64,
+    unstaked_timestamp: u64,
+    annual_rate: u8,
+    lock_duration: u64,
+    is_early_unstaked: bool,
+    is_completed: bool,
+    bump: u8,
+}
+
+impl From<LegacyStakeOrder> for StakeOrder {
+    fn from(legacy: LegacyStakeOrder) -> Self {
+        StakeOrder {
+            stake_number: legacy.stake_number,
+            stake_amount: legacy.stake_amount,
+            token_rewards: legacy.token_rewards,
+            voucher_rewards: legacy.voucher_rewards,
+            stake_order_vault: legacy.stake_order_vault,
+            created_timestamp: legacy.created_timestamp,
+            unstaked_timestamp: legacy.unstaked_timestamp,
+            annual_rate: legacy.annual_rate,
+            lock_duration: legacy.lock_duration,
+            is_early_unstaked: legacy.is_early_unstaked,
+            is_completed: legacy.is_completed,
+            bump: legacy.bump,
+            is_voucher_stake: false,
+            early_claimable_timestamp: if legacy.is_early_unstaked {
+                legacy.unstaked_timestamp
+            } else {
+                0
+            },
+            forfeited_token_rewards: 0,
+            forfeited_voucher_rewards: 0,
+            last_claimed_timestamp: legacy.created_timestamp,
+            boost_lock_end: 0,
+            unpaid_token_rewards: 0,
+        }
+    }
+}
+
+/// The `MigrateStakeAccounts` instruction lets the bot upgrade the `StakePool` and the `StakeOrder` accounts
+/// created before the staking fields were appended to their layouts.
+///
+/// Legacy orders reserved their whole interest from `distributable_token_rewards` at creation and kept it in
+/// `token_rewards`, which now holds the interest paid out so far. The reservation of every legacy order that is
+/// still running is released back to the pool, and the order then accrues its interest like any other one.
+///
+/// The stake orders to migrate are passed through `remaining_accounts`. Accounts already on the current
+/// layout are skipped, so the instruction can be re-run safely.
+#[derive(Accounts)]
+pub struct MigrateStakeAccounts<'info> {
+    /// The bot authority running the migration. Pays the rent for the grown accounts.
+    #[account(mut)]
+    pub bot_authority: Signer<'info>,
+
+    /// The global game account, verifying the bot authority.
+    #[account(
+        seeds = [GAME_SEED], bump,
+        has_one = bot_authority @ ErrorCode::AuthorityMismatch,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The stake pool, migrated first if it is still on the legacy layout, receiving the released reservations.
+    /// CHECK: Derived from its seeds; its owner, discriminator and size are checked by the handler.
+    #[account(mut, seeds = [STAKE_POOL_SEED], bump)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// The system program, required to fund the grown accounts.
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrates the stake pool and every legacy `StakeOrder` supplied in `remaining_accounts`.
+///
+/// Steps:
+/// 1. Migrate the stake pool if it is still on the legacy layout.
+/// 2. Verify each stake order is owned by the program and is a `StakeOrder`.
+/// 3. Skip orders already on the current layout and fail on any unknown size.
+/// 4. Release the interest reserved by running orders back to `distributable_token_rewards`.
+/// 5. Fund the extra rent from the bot authority, grow the order and rewrite it on the current layout.
+pub fn migrate_stake_accounts<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MigrateStakeAccounts<'info>>,
+) -> Result<()> {
+    let program_id = ctx.program_id;
+    let remaining_accounts = ctx.remaining_accounts;
+
+    let MigrateStakeAccounts {
+        bot_authority,
+        stake_pool,
+        system_program,
+        ..
+    } = ctx.accounts;
+
+    let bot_authority = bot_authority.to_account_info();
+    let system_program = system_program.to_account_info();
+    let stake_pool = stake_pool.to_account_info();
+
+    let mut migrated_accounts: u32 = 0;
+
+    if let Some(legacy) = load_legacy_account::<LegacyStakePool, StakePool>(
+        &stake_pool,
+        program_id,
+        LEGACY_STAKE_POOL_LEN,
+    )? {
+        store_migrated_account(
+            &stake_pool,
+            &StakePool::from(legacy),
+            &bot_authority,
+            &system_program,
+        )?;
+        migrated_accounts = migrated_accounts.safe_add(1)?;
+    }
+
+    let mut pool = StakePool::try_deserialize(&mut &stake_pool.try_borrow_data()?[..])?;
+    let mut released_token_rewards: u64 = 0;
+
+    for account in remaining_accounts {
+        let Some(legacy) = load_legacy_account::<LegacyStakeOrder, StakeOrder>(
+            account,
+            program_id,
+            LEGACY_STAKE_ORDER_LEN,
+        )?
+        else {
+            continue;
+        };
+
+        let mut stake_order = StakeOrder::from(legacy);
+        if !stake_order.is_completed {
+            released_token_rewards = released_token_rewards.safe_add(stake_order.token_rewards)?;
+            stake_order.token_rewards = 0;
+        }
+
+        store_migrated_account(account, &stake_order, &bot_authority, &system_program)?;
+        migrated_accounts = migrated_accounts.safe_add(1)?;
+    }
+
+    pool.distributable_token_rewards = pool
+        .distributable_token_rewards
+        .safe_add(released_token_rewards)?;
+    write_account(&stake_pool, &pool)?;
+
+    msg!(
+        "Migrated stake accounts: {}, released token rewards: {}",
+        migrated_accounts,
+        released_token_rewards
+    );
+
+    Ok(())
+}
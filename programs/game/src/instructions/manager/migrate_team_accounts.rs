@@ -0,0 +1,128 @@
+use crate::constants::GAME_SEED;
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::{load_legacy_account, store_migrated_account};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// Size of a `Team` account created before the period scoring, previous period and voucher reward fields.
+const LEGACY_TEAM_LEN: usize = 8 + 1521;
+
+/// The frozen `Team` layout of `LEGACY_TEAM_LEN` accounts. Must never change.
+#[derive(AnchorDeserialize)]
+struct LegacyTeam {
+    team_number: u32,
+    team_vault: Pubkey,
+    captain: Pubkey,
+    manager_list: Vec<Pubkey>,
+    member_list: Vec<Pubkey>,
+    application_list: Vec<Pubkey>,
+    current_period: Pubkey,
+    purchased_ores: u32,
+    current_period_purchased_ores: u32,
+    distributable_team_rewards: u64,
+    distributed_team_rewards: u64,
+    last_updated_timestamp: u64,
+    bump: u8,
+}
+
+impl From<LegacyTeam> for Team {
+    fn from(legacy: LegacyTeam) -> Self {
+        Team {
+            team_number: legacy.team_number,
+            team_vault: legacy.team_vault,
+            captain: legacy.captain,
+            manager_list: legacy.manager_list,
+            member_list: legacy.member_list,
+            application_list: legacy.application_list,
+            current_period: legacy.current_period,
+            purchased_ores: legacy.purchased_ores,
+            current_period_purchased_ores: legacy.current_period_purchased_ores,
+            distributable_team_rewards: legacy.distributable_team_rewards,
+            distributed_team_rewards: legacy.distributed_team_rewards,
+            last_updated_timestamp: legacy.last_updated_timestamp,
+            bump: legacy.bump,
+            current_period_top_members: Vec::new(),
+            current_period_active_members: 0,
+            previous_period: Pubkey::default(),
+            previous_period_purchased_ores: 0,
+            distributable_team_voucher_rewards: 0,
+            pending_distributions: 0,
+        }
+    }
+}
+
+/// The `MigrateTeamAccounts` instruction lets the bot upgrade the `Team` accounts created before the
+/// team fields were appended to their layout.
+///
+/// The appended fields start empty. Among them, only the period scoring fields rank teams, under the `TopMembers`
+/// mode and the `min_team_active_members` threshold, which the periods migrated from the legacy layout do not use:
+/// the period running during the upgrade keeps ranking migrated teams on their `current_period_purchased_ores`.
+///
+/// The teams to migrate are passed through `remaining_accounts`. Accounts already on the current layout
+/// are skipped, so the instruction can be re-run safely; accounts of any other size are rejected.
+#[derive(Accounts)]
+pub struct MigrateTeamAccounts<'info> {
+    /// The bot authority running the migration. Pays the rent for the grown accounts.
+    #[account(mut)]
+    pub bot_authority: Signer<'info>,
+
+    /// The global game account, verifying the bot authority.
+    #[account(
+        seeds = [GAME_SEED], bump,
+        has_one = bot_authority @ ErrorCode::AuthorityMismatch,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The system program, required to fund the grown accounts.
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrates every legacy `Team` supplied in `remaining_accounts`.
+///
+/// Steps:
+/// 1. Verify each account is owned by the program and is a `Team`.
+/// 2. Skip teams already on the current layout and fail on any unknown size.
+/// 3. Fund the extra rent from the bot authority, grow the team and rewrite it on the current layout.
+pub fn migrate_team_accounts<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MigrateTeamAccounts<'info>>,
+) -> Result<()> {
+    let program_id = ctx.program_id;
+    let remaining_accounts = ctx.remaining_accounts;
+
+    let MigrateTeamAccounts {
+        bot_authority,
+        system_program,
+        ..
+    } = ctx.accounts;
+
+    require!(
+        !remaining_accounts.is_empty(),
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    let bot_authority = bot_authority.to_account_info();
+    let system_program = system_program.to_account_info();
+
+    let mut migrated_accounts: u32 = 0;
+
+    for account in remaining_accounts {
+        let Some(legacy) =
+            load_legacy_account::<LegacyTeam, Team>(account, program_id, LEGACY_TEAM_LEN)?
+        else {
+            continue;
+        };
+
+        store_migrated_account(
+            account,
+            &Team::from(legacy),
+            &bot_authority,
+            &system_program,
+        )?;
+        migrated_accounts = migrated_accounts.safe_add(1)?;
+    }
+
+    msg!("Migrated team accounts: {}", migrated_accounts);
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use crate::constants::{GAME_SEED, VOUCHER_SEED};
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::{load_legacy_account, store_migrated_account};
+use anchor_lang::prelude::*;
+
+/// Size of the `Voucher` account created before the soulbound mode and the redemption fee.
+const LEGACY_VOUCHER_LEN: usize = 8 + 80;
+
+/// The frozen `Voucher` layout of `LEGACY_VOUCHER_LEN` accounts. Must never change.
+#[derive(AnchorDeserialize)]
+struct LegacyVoucher {
+    voucher_mint: Pubkey,
+    voucher_vault: Pubkey,
+    minted_amount: u64,
+    total_supply: u64,
+}
+
+impl From<LegacyVoucher> for Voucher {
+    fn from(legacy: LegacyVoucher) -> Self {
+        Voucher {
+            voucher_mint: legacy.voucher_mint,
+            voucher_vault: legacy.voucher_vault,
+            minted_amount: legacy.minted_amount,
+            total_supply: legacy.total_supply,
+            is_transfer_restricted: false,
+            redemption_fee_rate: 0,
+        }
+    }
+}
+
+/// The `MigrateVoucher` instruction lets the bot upgrade the `Voucher` account created before the soulbound
+/// mode and the redemption fee were appended to its layout. Every instruction taking the voucher fails to
+/// deserialize the legacy account, so it must run right after the program upgrade.
+///
+/// The migrated voucher starts with transfers unrestricted and no redemption fee. Its mint was created
+/// without a freeze authority, so `set_voucher_transfer_restriction` keeps rejecting the soulbound mode on it.
+///
+/// A voucher already on the current layout is skipped, so the instruction can be re-run safely.
+#[derive(Accounts)]
+pub struct MigrateVoucher<'info> {
+    /// The bot authority running the migration. Pays the rent for the grown account.
+    #[account(mut)]
+    pub bot_authority: Signer<'info>,
+
+    /// The global game account, verifying the bot authority.
+    #[account(
+        seeds = [GAME_SEED], bump,
+        has_one = bot_authority @ ErrorCode::AuthorityMismatch,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The voucher account, migrated if it is still on the legacy layout.
+    /// CHECK: Derived from its seeds; its owner, discriminator and size are checked by the handler.
+    #[account(mut, seeds = [VOUCHER_SEED], bump)]
+    pub voucher: UncheckedAccount<'info>,
+
+    /// The system program, required to fund the grown account.
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrates the legacy `Voucher` account.
+///
+/// Steps:
+/// 1. Verify the voucher is owned by the program and skip it if it is already on the current layout.
+/// 2. Fund the extra rent from the bot authority, grow the voucher and rewrite it on the current layout.
+pub fn migrate_voucher(ctx: Context<MigrateVoucher>) -> Result<()> {
+    let program_id = ctx.program_id;
+
+    let MigrateVoucher {
+        bot_authority,
+        voucher,
+        system_program,
+        ..
+    } = ctx.accounts;
+
+    let voucher = voucher.to_account_info();
+
+    if let Some(legacy) =
+        load_legacy_account::<LegacyVoucher, Voucher>(&voucher, program_id, LEGACY_VOUCHER_LEN)?
+    {
+        store_migrated_account(
+            &voucher,
+            &Voucher::from(legacy),
+            &bot_authority.to_account_info(),
+            &system_program.to_account_info(),
+        )?;
+        msg!("Migrated the voucher account");
+    }
+
+    Ok(())
+}
@@ -3,7 +3,6 @@ pub mod collect_developer_rewards;
 pub mod create_period;
 pub mod create_round;
 pub mod distribute_grand_prizes;
-pub mod distribute_leaderboard_rewards;
 pub mod initialize;
 pub mod initialize_default_player;
 pub mod initialize_default_team;
@@ -11,12 +10,68 @@ pub mod initialize_vault;
 pub mod initialize_stake_token_pool;
 pub mod initialize_stake_voucher_pool;
 pub mod initialize_voucher;
+pub mod set_stake_rate_curve;
+pub mod set_stake_pool_winding_down;
+pub mod decommission_stake_pool;
+pub mod set_voucher_transfer_restriction;
+pub mod freeze_voucher_account;
+pub mod thaw_voucher_account;
+pub mod set_voucher_redemption_fee;
+pub mod initialize_exchange_config;
+pub mod update_exchange_config;
+pub mod set_exchange_price_feed;
+pub mod set_swap_pool;
+pub mod initialize_leaderboard_page;
+pub mod update_leaderboard_page;
+pub mod rollover_period;
+pub mod migrate_earnings_per_ore;
+pub mod migrate_stake_accounts;
+pub mod migrate_game;
+pub mod migrate_team_accounts;
+pub mod migrate_periods;
+pub mod migrate_voucher;
+pub mod set_start_time_window;
+pub mod reconcile_vault;
+pub mod set_voucher_metadata;
+pub mod register_custodial;
+pub mod set_candy_tap_throttle;
+pub mod set_sugar_rush_escalation;
+pub mod set_reentry_fee;
+pub mod cover_shortfall;
+pub mod set_default_winner_policy;
+pub mod update_game_config;
+pub mod create_lottery_table;
+pub mod set_lottery_table_enabled;
+pub mod record_bot_heartbeat;
+pub mod set_backup_authorities;
+pub mod initialize_daily_stats;
+pub mod register_voucher_kind;
+pub mod update_voucher_kind;
+pub mod issue_voucher_kind;
+pub mod set_round_badge;
+pub mod set_registration_gate;
+pub mod set_registration_reward_schedule;
+pub mod create_airdrop_campaign;
+pub mod fund_airdrop_campaign;
+pub mod set_airdrop_campaign_enabled;
+pub mod create_merkle_airdrop;
+pub mod set_consumption_reward_tiers;
+pub mod create_team_quest;
+pub mod set_grand_prize_vesting;
+pub mod set_sugar_rush_emission;
+pub mod set_team_application_limits;
+pub mod set_captain_inactivity_window;
+pub mod set_team_distribution_approval;
+pub mod ban_player;
+pub mod unban_player;
+pub mod set_compliance_attestation;
+pub mod sweep_expired_referral_rewards;
+pub mod reconcile_game;
 pub use auto_reinvest::*;
 pub use collect_developer_rewards::*;
 pub use create_period::*;
 pub use create_round::*;
 pub use distribute_grand_prizes::*;
-pub use distribute_leaderboard_rewards::*;
 pub use initialize::*;
 pub use initialize_default_player::*;
 pub use initialize_default_team::*;
@@ -24,3 +79,60 @@ pub use initialize_vault::*;
 pub use initialize_stake_token_pool::*;
 pub use initialize_stake_voucher_pool::*;
 pub use initialize_voucher::*;
+pub use set_stake_rate_curve::*;
+pub use set_stake_pool_winding_down::*;
+pub use decommission_stake_pool::*;
+pub use set_voucher_transfer_restriction::*;
+pub use freeze_voucher_account::*;
+pub use thaw_voucher_account::*;
+pub use set_voucher_redemption_fee::*;
+pub use initialize_exchange_config::*;
+pub use update_exchange_config::*;
+pub use set_exchange_price_feed::*;
+pub use set_swap_pool::*;
+pub use initialize_leaderboard_page::*;
+pub use update_leaderboard_page::*;
+pub use rollover_period::*;
+pub use migrate_earnings_per_ore::*;
+pub use migrate_stake_accounts::*;
+pub use migrate_game::*;
+pub use migrate_team_accounts::*;
+pub use migrate_periods::*;
+pub use migrate_voucher::*;
+pub use set_start_time_window::*;
+pub use reconcile_vault::*;
+pub use set_voucher_metadata::*;
+pub use register_custodial::*;
+pub use set_candy_tap_throttle::*;
+pub use set_sugar_rush_escalation::*;
+pub use set_reentry_fee::*;
+pub use cover_shortfall::*;
+pub use set_default_winner_policy::*;
+pub use update_game_config::*;
+pub use create_lottery_table::*;
+pub use set_lottery_table_enabled::*;
+pub use record_bot_heartbeat::*;
+pub use set_backup_authorities::*;
+pub use initialize_daily_stats::*;
+pub use register_voucher_kind::*;
+pub use update_voucher_kind::*;
+pub use issue_voucher_kind::*;
+pub use set_round_badge::*;
+pub use set_registration_gate::*;
+pub use set_registration_reward_schedule::*;
+pub use create_airdrop_campaign::*;
+pub use fund_airdrop_campaign::*;
+pub use set_airdrop_campaign_enabled::*;
+pub use create_merkle_airdrop::*;
+pub use set_consumption_reward_tiers::*;
+pub use create_team_quest::*;
+pub use set_grand_prize_vesting::*;
+pub use set_sugar_rush_emission::*;
+pub use set_team_application_limits::*;
+pub use set_captain_inactivity_window::*;
+pub use set_team_distribution_approval::*;
+pub use ban_player::*;
+pub use unban_player::*;
+pub use set_compliance_attestation::*;
+pub use sweep_expired_referral_rewards::*;
+pub use reconcile_game::*;
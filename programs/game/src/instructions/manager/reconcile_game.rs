@@ -0,0 +1,116 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::TokenAccount;
+use solana_program::sysvar::clock::Clock;
+
+/// The `ReconcileGame` instruction lets anyone audit the game vault, the current round vault and the current period vault.
+/// The balance each vault should hold is recomputed from the pool fields it backs, and a `ReconciliationReport` event
+/// logs the deltas, so that a drift left by any of the balance mutations is detected on-chain.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReconcileGame<'info> {
+    /// The caller running the audit.
+    pub caller: Signer<'info>,
+
+    /// The global game account, holding the pool balances backed by the game vault.
+    #[account(
+        mut,
+        seeds = [GAME_SEED],
+        bump,
+        has_one = game_vault,
+        has_one = current_round,
+        has_one = current_period,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game vault holding the game's reward pools.
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The current round, holding the grand prize pool backed by the round vault.
+    #[account(has_one = round_vault)]
+    pub current_round: Box<Account<'info, Round>>,
+
+    /// The current round's vault holding the grand prize pool.
+    pub round_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The current period, holding the rank rewards backed by the period vault.
+    #[account(constraint = current_period.load()?.period_vault == period_vault.key())]
+    pub current_period: AccountLoader<'info, Period>,
+
+    /// The current period's vault holding the rank rewards.
+    pub period_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Reconciles the game, round and period vaults with their pool fields.
+///
+/// Steps:
+/// 1. Recompute the balance of each vault: all game pools for the game vault, the grand prize pool and the streamed
+///    grand prizes for the round vault, the rank rewards not yet claimed for the period vault.
+/// 2. Compare each with the vault's token balance.
+/// 3. Emit a `ReconciliationReport` event with the deltas.
+pub fn reconcile_game(ctx: Context<ReconcileGame>) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let ReconcileGame {
+        caller,
+        game,
+        game_vault,
+        current_round,
+        round_vault,
+        current_period,
+        period_vault,
+        ..
+    } = ctx.accounts;
+
+    let game_vault_reconciliation = VaultReconciliation::new(
+        game_vault.key(),
+        game.total_pool_balance()?,
+        game_vault.amount,
+    );
+    let round_vault_reconciliation = VaultReconciliation::new(
+        round_vault.key(),
+        current_round
+            .grand_prize_pool_balance
+            .safe_add(current_round.vesting_grand_prizes)?,
+        round_vault.amount,
+    );
+    let period_vault_reconciliation = VaultReconciliation::new(
+        period_vault.key(),
+        current_period.load()?.unclaimed_rewards()?,
+        period_vault.amount,
+    );
+    let is_balanced = game_vault_reconciliation.is_balanced()
+        && round_vault_reconciliation.is_balanced()
+        && period_vault_reconciliation.is_balanced();
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the deltas
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::ReconciliationReport as u16,
+        event_type: EventType::ReconciliationReport,
+        event_nonce: game.event_nonce,
+        data: EventData::ReconciliationReport {
+            game_vault: game_vault_reconciliation,
+            round_vault: round_vault_reconciliation,
+            period_vault: period_vault_reconciliation,
+            is_balanced,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: caller.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
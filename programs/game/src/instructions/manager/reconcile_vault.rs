@@ -0,0 +1,137 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, VAULT_LEDGER_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::TokenAccount;
+use solana_program::sysvar::clock::Clock;
+
+/// The `ReconcileVault` instruction lets the bot audit the game vault and the current round vault.
+/// Each vault's token balance is compared with the sum of the pool balances it backs, the result is
+/// recorded on the vault's `VaultLedger`, and a `VaultDiscrepancy` event is emitted when they differ.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReconcileVault<'info> {
+    /// The bot authority running the audit. Pays for ledgers created on first use.
+    #[account(mut)]
+    pub bot_authority: Signer<'info>,
+
+    /// The global game account, holding the pool balances backed by the game vault.
+    #[account(
+        mut,
+        seeds = [GAME_SEED],
+        bump,
+        has_one = game_vault,
+        has_one = current_round,
+        has_one = bot_authority @ ErrorCode::AuthorityMismatch,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game vault holding the game's reward pools.
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The current round, holding the grand prize pool backed by the round vault.
+    #[account(has_one = round_vault)]
+    pub current_round: Box<Account<'info, Round>>,
+
+    /// The current round's vault holding the grand prize pool.
+    pub round_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The ledger auditing the game vault, created on first use.
+    #[account(
+        init_if_needed,
+        payer = bot_authority,
+        space = 8 + VaultLedger::INIT_SPACE,
+        seeds = [VAULT_LEDGER_SEED, game_vault.key().as_ref()],
+        bump,
+    )]
+    pub game_vault_ledger: Box<Account<'info, VaultLedger>>,
+
+    /// The ledger auditing the round vault, created on first use.
+    #[account(
+        init_if_needed,
+        payer = bot_authority,
+        space = 8 + VaultLedger::INIT_SPACE,
+        seeds = [VAULT_LEDGER_SEED, round_vault.key().as_ref()],
+        bump,
+    )]
+    pub round_vault_ledger: Box<Account<'info, VaultLedger>>,
+
+    /// The system program, required to create the ledgers.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Reconciles the game vault and the current round vault with their ledgers.
+///
+/// Steps:
+/// 1. Create the ledgers on first use and bind them to their vaults.
+/// 2. Sum the pool balances backed by each vault: all game pools for the game vault, the grand prize pool and the streamed grand prizes for the round vault.
+/// 3. Record the backed and actual balances on each ledger.
+/// 4. Emit a `VaultDiscrepancy` event for each vault whose balances differ.
+pub fn reconcile_vault(ctx: Context<ReconcileVault>) -> Result<()> {
+    // Obtain the current UNIX timestamp for the ledgers and event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let ReconcileVault {
+        bot_authority,
+        game,
+        game_vault,
+        current_round,
+        round_vault,
+        game_vault_ledger,
+        round_vault_ledger,
+        ..
+    } = ctx.accounts;
+
+    game_vault_ledger.initialize_if_needed(game_vault.key(), ctx.bumps.game_vault_ledger);
+    round_vault_ledger.initialize_if_needed(round_vault.key(), ctx.bumps.round_vault_ledger);
+
+    let audits = [
+        (
+            &mut *game_vault_ledger,
+            game.total_pool_balance()?,
+            game_vault.amount,
+        ),
+        (
+            &mut *round_vault_ledger,
+            current_round
+                .grand_prize_pool_balance
+                .safe_add(current_round.vesting_grand_prizes)?,
+            round_vault.amount,
+        ),
+    ];
+
+    for (ledger, backed_balance, vault_balance) in audits {
+        if !ledger.reconcile(backed_balance, vault_balance, timestamp)? {
+            continue;
+        }
+
+        game.increment_event_nonce()?;
+
+        // Emit an event logging the accounting drift
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::VaultDiscrepancy as u16,
+            event_type: EventType::VaultDiscrepancy,
+            event_nonce: game.event_nonce,
+            data: EventData::VaultDiscrepancy {
+                vault: ledger.vault,
+                ledger: ledger.key(),
+                backed_balance,
+                vault_balance,
+            },
+            initiator_type: InitiatorType::SYSTEM,
+            initiator: bot_authority.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `RecordBotHeartbeat` instruction lets the bot authority prove it is alive.
+/// While its heartbeats keep coming, the backup authorities stay locked out of the bot-only instructions.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RecordBotHeartbeat<'info> {
+    /// The bot authority sending its heartbeat.
+    #[account(mut)]
+    pub bot_authority: Signer<'info>,
+
+    /// The global game account, verifying the bot authority and storing its last heartbeat.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = bot_authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `RecordBotHeartbeat` instruction:
+///
+/// - Stores the current timestamp as the bot's last heartbeat.
+/// - Emits a `RecordBotHeartbeat` event to record the heartbeat on-chain.
+pub fn record_bot_heartbeat(ctx: Context<RecordBotHeartbeat>) -> Result<()> {
+    // Obtain the current UNIX timestamp for the heartbeat and event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let RecordBotHeartbeat {
+        bot_authority,
+        game,
+        ..
+    } = ctx.accounts;
+
+    game.record_bot_heartbeat(timestamp)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the heartbeat
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::RecordBotHeartbeat as u16,
+        event_type: EventType::RecordBotHeartbeat,
+        event_nonce: game.event_nonce,
+        data: EventData::RecordBotHeartbeat {
+            game: game.key(),
+            last_bot_heartbeat: timestamp,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: bot_authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use crate::constants::{
+    CLAIM_CODE_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, VOUCHER_MINT_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `RegisterCustodial` instruction lets the bot onboard a user who has no wallet yet.
+/// The player account is registered for a custodial key held by the operator, and a `ClaimCode`
+/// records the hash of a claim code handed to the user, so they can later move the account to their
+/// own wallet with `upgrade_to_self_custody`. Registration rewards are not distributed to custodial accounts.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(player_pubkey: Pubkey, referrer: Pubkey)]
+pub struct RegisterCustodial<'info> {
+    /// The bot authority registering the account. Pays for the created accounts.
+    #[account(mut)]
+    pub bot_authority: Signer<'info>,
+
+    /// The global game account, referencing the default team.
+    #[account(
+        mut,
+        seeds = [GAME_SEED],
+        bump,
+        has_one = bot_authority @ ErrorCode::AuthorityMismatch,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The custodial key the player account is registered for.
+    /// CHECK: Any key held by the operator; only its address is used.
+    #[account(
+        address = player_pubkey,
+        constraint = referrer != player.key() @ ErrorCode::CannotReferSelf
+    )]
+    pub player: UncheckedAccount<'info>,
+
+    /// The custodial player's data account.
+    #[account(
+        init,
+        payer = bot_authority,
+        space = 8 + PlayerData::INIT_SPACE,
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The referrer's data account, from which we increment the referral count.
+    #[account(
+        mut,
+        seeds = [PLAYER_DATA_SEED, referrer.as_ref()],
+        bump
+    )]
+    pub referrer_data: Box<Account<'info, PlayerData>>,
+
+    /// The claim code unlocking the upgrade of the account to self-custody.
+    #[account(
+        init,
+        payer = bot_authority,
+        space = 8 + ClaimCode::INIT_SPACE,
+        seeds = [CLAIM_CODE_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub claim_code: Box<Account<'info, ClaimCode>>,
+
+    /// The token mint representing the in-game token currency.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The voucher mint.
+    #[account(seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The custodial key's associated token account for the in-game token, created if needed.
+    #[account(
+        init_if_needed,
+        payer = bot_authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The custodial key's associated voucher account, created if needed.
+    #[account(
+        init_if_needed,
+        payer = bot_authority,
+        associated_token::mint = voucher_mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program
+    )]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The SPL token program.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Associated Token program used to create the custodial token accounts.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The System program for account creation.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Registers a custodial player account:
+///
+/// Steps:
+/// 1. Initialize the `PlayerData` of the custodial key, linked to the default team and the referrer.
+/// 2. Increment the referrer's referral count.
+/// 3. Record the claim code hash.
+/// 4. Emit a `RegisterCustodial` event.
+pub fn register_custodial(
+    ctx: Context<RegisterCustodial>,
+    player_pubkey: Pubkey,
+    referrer: Pubkey,
+    claim_code_hash: [u8; 32],
+) -> Result<()> {
+    // Get current UNIX timestamp for event logging
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let RegisterCustodial {
+        bot_authority,
+        game,
+        player_data,
+        referrer_data,
+        claim_code,
+        token_account,
+        voucher_account,
+        ..
+    } = ctx.accounts;
+
+    player_data.initialize(
+        player_pubkey,
+        referrer,
+        game.default_team,
+        token_account.key(),
+        voucher_account.key(),
+    )?;
+
+    referrer_data.increment_referral_count()?;
+
+    claim_code.set_inner(ClaimCode {
+        player: player_pubkey,
+        claim_code_hash,
+        bump: ctx.bumps.claim_code,
+    });
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the custodial registration
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::RegisterCustodial as u16,
+        event_type: EventType::RegisterCustodial,
+        event_nonce: game.event_nonce,
+        data: EventData::RegisterCustodial {
+            player: player_pubkey,
+            referrer,
+            claim_code: claim_code.key(),
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: bot_authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
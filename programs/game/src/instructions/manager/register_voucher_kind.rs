@@ -0,0 +1,128 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, VOUCHER_KIND_MINT_SEED, VOUCHER_KIND_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `RegisterVoucherKind` instruction lets the game authority add a promotional voucher currency,
+/// with its own mint and a value rate setting what one of its vouchers is worth on a purchase.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(kind_id: u16)]
+pub struct RegisterVoucherKind<'info> {
+    /// The authority (signer) authorized to manage the game, paying for the kind's rent.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The voucher kind to register, indexed by `kind_id`.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VoucherKind::INIT_SPACE,
+        seeds = [VOUCHER_KIND_SEED, kind_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub voucher_kind: Box<Account<'info, VoucherKind>>,
+
+    /// The mint of the kind's vouchers, with the kind as mint and freeze authority.
+    #[account(
+        init,
+        payer = authority,
+        seeds = [VOUCHER_KIND_MINT_SEED, kind_id.to_le_bytes().as_ref()],
+        bump,
+        mint::decimals = 6,
+        mint::authority = voucher_kind,
+        mint::freeze_authority = voucher_kind,
+        mint::token_program = token_program,
+    )]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The vault holding the tokens backing the kind's vouchers.
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = voucher_kind,
+        associated_token::token_program = token_program
+    )]
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The main token mint account.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program for creating the mint and the vault.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program for creating the vault.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program, required to create the kind.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `RegisterVoucherKind` instruction:
+///
+/// - Creates the voucher kind, its mint and its vault, enabled at `value_rate`.
+/// - Emits a `RegisterVoucherKind` event to record the new kind on-chain.
+pub fn register_voucher_kind(
+    ctx: Context<RegisterVoucherKind>,
+    kind_id: u16,
+    value_rate: u16,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let RegisterVoucherKind {
+        authority,
+        game,
+        voucher_kind,
+        voucher_mint,
+        voucher_vault,
+        ..
+    } = ctx.accounts;
+
+    voucher_kind.initialize(
+        kind_id,
+        voucher_mint.key(),
+        voucher_vault.key(),
+        value_rate,
+        ctx.bumps.voucher_kind,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new voucher kind
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::RegisterVoucherKind as u16,
+        event_type: EventType::RegisterVoucherKind,
+        event_nonce: game.event_nonce,
+        data: EventData::RegisterVoucherKind {
+            voucher_kind: voucher_kind.key(),
+            kind_id,
+            voucher_mint: voucher_mint.key(),
+            value_rate,
+        },
+        initiator_type: InitiatorType::VOUCHER,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
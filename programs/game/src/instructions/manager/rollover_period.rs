@@ -0,0 +1,341 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, PERIOD_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::{
+    BurnReason, DefaultWinnerPolicy, EventRingBuffer, Game, GameConfig, MaintenanceCrank, Period,
+};
+use crate::utils::{to_timestamp_u64, transfer_from_vault, transfer_from_zero_copy_vault};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `RolloverPeriod` instruction lets anyone roll the current period over to the next one once it has ended.
+/// The next period repeats the duration, rewards, payout curves, round coupling and team scoring mode of the ended one, is funded from the
+/// `period_rewards_pool_balance`, and the caller is paid the game config's `period_rollover_tip` from the maintenance pool
+/// for running the crank.
+/// The rewards of the ranks held by the default team or player follow the game's `DefaultWinnerPolicy`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RolloverPeriod<'info> {
+    /// The caller running the crank, paying for the new period accounts.
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// The global game account, pointing at the ended period.
+    #[account(
+        mut,
+        seeds = [GAME_SEED],
+        bump,
+        has_one = game_vault,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game config holding the rollover tip.
+    #[account(seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// The ended period whose final standings are snapshotted.
+    #[account(mut, address = game.current_period)]
+    pub previous_period: AccountLoader<'info, Period>,
+
+    /// The ended period's vault, from which the rewards of ranks no team or player qualified for are taken.
+    #[account(
+        mut,
+        constraint = previous_period.load()?.period_vault == previous_period_vault.key(),
+    )]
+    pub previous_period_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The next period account to be created.
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + std::mem::size_of::<Period>(),
+        seeds = [PERIOD_SEED, game.period_nonce.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub period: AccountLoader<'info, Period>,
+
+    /// The main game vault token account holding the period rewards pool.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The next period's associated token vault, holding its rewards.
+    #[account(
+        init,
+        payer = caller,
+        associated_token::mint = token_mint,
+        associated_token::authority = period,
+        associated_token::token_program = token_program
+    )]
+    pub period_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The caller's token account, receiving the rollover tip.
+    #[account(mut, token::mint = token_mint)]
+    pub tip_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint representing the in-game currency, mutable to burn unawarded rewards.
+    #[account(mut, address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The associated token program used to create the period_vault account.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The SPL token program, enabling token transfers and related operations.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program for basic Solana operations, required for account initializations.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the period rollover:
+///
+/// Steps:
+/// 1. Require the current period to have ended.
+/// 2. Require the period rewards pool to cover the next period's rewards, and take the tip from the maintenance pool.
+/// 3. Create the next period starting now, with the ended period's configuration,
+///    and make it the game's current period.
+/// 4. Take the rewards of the ended period's ranks still held by the default team or player, carrying them over to
+///    the next period, burning them, or moving them to the game vault, as the `DefaultWinnerPolicy` selects.
+/// 5. Fund the next period's vault and pay the tip to the caller.
+/// 6. Emit a `RolloverPeriod` event snapshotting the final standings of the ended period, and a `CrankTip` event if a tip was paid.
+pub fn rollover_period(ctx: Context<RolloverPeriod>) -> Result<()> {
+    // Fetch current UNIX timestamp for logical checks and event timestamping.
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let RolloverPeriod {
+        caller,
+        game,
+        game_config,
+        previous_period,
+        previous_period_vault,
+        period,
+        game_vault,
+        period_vault,
+        tip_account,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    let previous_period_data = *previous_period.load()?;
+    require!(
+        previous_period_data.is_ended(timestamp),
+        ErrorCode::PeriodNotEnded
+    );
+
+    // Ranks no team or player qualified for are taken instead of being left in the vault,
+    // and carried over to the next period only if the default winner policy rolls them
+    let (team_unawarded, individual_unawarded) = previous_period
+        .load_mut()?
+        .take_unawarded_rewards(game.default_team, game.default_player)?;
+    let unawarded_rewards = team_unawarded.safe_add(individual_unawarded)?;
+    let default_winner_policy = game.default_winner_policy();
+    let (team_carry, individual_carry) =
+        if default_winner_policy == DefaultWinnerPolicy::RollToNextPeriod {
+            (team_unawarded, individual_unawarded)
+        } else {
+            (0, 0)
+        };
+    let carried_over_rewards = team_carry.safe_add(individual_carry)?;
+
+    // Repeat the ended period's configuration
+    let leaderboard_duration = previous_period_data
+        .end_time
+        .safe_sub(previous_period_data.start_time)?;
+    let team_rewards = previous_period_data.team_rewards;
+    let individual_rewards = previous_period_data.individual_rewards;
+
+    let total_rewards = team_rewards.safe_add(individual_rewards)?;
+    require!(
+        total_rewards <= game.period_rewards_pool_balance,
+        ErrorCode::InsufficientFunds
+    );
+
+    // Take the tip from the maintenance pool, paying less once it runs low
+    let tip = game.take_crank_tip(game_config.period_rollover_tip, 1)?;
+    require!(
+        total_rewards.safe_add(tip)? <= game_vault.amount,
+        ErrorCode::InsufficientFunds
+    );
+
+    // Update game state: set current_period and deduct from the period rewards pool.
+    game.previous_period = game.current_period;
+    game.current_period = period.key();
+    game.period_rewards_pool_balance = game.period_rewards_pool_balance.safe_sub(total_rewards)?;
+
+    let mut period_data = period.load_init()?;
+    period_data.initialize(
+        game.period_nonce,
+        period_vault.key(),
+        timestamp,
+        leaderboard_duration,
+        team_rewards,
+        individual_rewards,
+        game.default_player,
+        game.default_team,
+        ctx.bumps.period,
+    )?;
+    period_data.add_carried_over_rewards(team_carry, individual_carry)?;
+    period_data.configure(&previous_period_data.config())?;
+    drop(period_data);
+
+    // Increment period_nonce for future period derivations.
+    game.increment_period_nonce()?;
+
+    // Carry the unawarded rewards over, fund the next period and tip the caller.
+    let previous_period_signer_seeds: &[&[u8]] = &[
+        PERIOD_SEED,
+        &previous_period_data.period_number.to_le_bytes(),
+        &[previous_period_data.bump],
+    ];
+    if carried_over_rewards > 0 {
+        transfer_from_zero_copy_vault(
+            previous_period,
+            &previous_period_data,
+            previous_period_data.bump,
+            previous_period_vault,
+            period_vault,
+            token_mint,
+            token_program,
+            carried_over_rewards,
+        )?;
+    }
+    // Rewards not rolled over are burned or moved to the game pool selected by the policy
+    if unawarded_rewards > 0 {
+        match default_winner_policy {
+            DefaultWinnerPolicy::RollToNextPeriod => {}
+            DefaultWinnerPolicy::Burn => {
+                burn(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        Burn {
+                            mint: token_mint.to_account_info(),
+                            from: previous_period_vault.to_account_info(),
+                            authority: previous_period.to_account_info(),
+                        },
+                        &[previous_period_signer_seeds],
+                    ),
+                    unawarded_rewards,
+                )?;
+
+                game.record_burned_tokens(unawarded_rewards)?;
+                game.increment_event_nonce()?;
+
+                // Emit an event recording the burn
+                let event = TransferEvent {
+                    event_schema_version: EVENT_SCHEMA_VERSION,
+                    event_tag: EventType::Burn as u16,
+                    event_type: EventType::Burn,
+                    event_nonce: game.event_nonce,
+                    data: EventData::Burn {
+                        mint: token_mint.key(),
+                        from: previous_period_vault.key(),
+                        amount: unawarded_rewards,
+                        reason: BurnReason::LeaderboardRewards,
+                        total_burned: game.total_burned_tokens,
+                    },
+                    initiator_type: InitiatorType::SYSTEM,
+                    initiator: caller.key(),
+                    timestamp,
+                };
+                record_event!(ctx, event);
+            }
+            DefaultWinnerPolicy::GrandPrize | DefaultWinnerPolicy::Insurance => {
+                game.credit_default_winner_rewards(default_winner_policy, unawarded_rewards)?;
+                transfer_from_zero_copy_vault(
+                    previous_period,
+                    &previous_period_data,
+                    previous_period_data.bump,
+                    previous_period_vault,
+                    game_vault,
+                    token_mint,
+                    token_program,
+                    unawarded_rewards,
+                )?;
+            }
+        }
+    }
+    transfer_from_vault(
+        game,
+        ctx.bumps.game,
+        game_vault,
+        period_vault,
+        token_mint,
+        token_program,
+        total_rewards,
+    )?;
+    if tip > 0 {
+        transfer_from_vault(
+            game,
+            ctx.bumps.game,
+            game_vault,
+            tip_account,
+            token_mint,
+            token_program,
+            tip,
+        )?;
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit the event logging the rollover and the final standings.
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::RolloverPeriod as u16,
+        event_type: EventType::RolloverPeriod,
+        event_nonce: game.event_nonce,
+        data: EventData::RolloverPeriod {
+            previous_period: previous_period.key(),
+            period: period.key(),
+            top_teams: previous_period_data
+                .top_team_list
+                .iter()
+                .map(|entry| entry.team)
+                .collect(),
+            top_players: previous_period_data
+                .top_player_list
+                .iter()
+                .map(|entry| entry.player)
+                .collect(),
+            tip,
+            carried_over_rewards,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: caller.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    if tip > 0 {
+        game.increment_event_nonce()?;
+
+        // Emit an event logging the tip paid for the crank
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::CrankTip as u16,
+            event_type: EventType::CrankTip,
+            event_nonce: game.event_nonce,
+            data: EventData::CrankTip {
+                crank: MaintenanceCrank::PeriodRollover,
+                caller: caller.key(),
+                tip_account: tip_account.key(),
+                tasks: 1,
+                tip,
+                maintenance_pool_balance: game.maintenance_pool_balance,
+            },
+            initiator_type: InitiatorType::SYSTEM,
+            initiator: caller.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    Ok(())
+}
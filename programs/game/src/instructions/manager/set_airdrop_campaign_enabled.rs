@@ -0,0 +1,74 @@
+use crate::constants::{AIRDROP_CAMPAIGN_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetAirdropCampaignEnabled` instruction lets the game authority pause or resume the collection of an
+/// airdrop campaign's rewards within its window.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetAirdropCampaignEnabled<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The airdrop campaign being enabled or disabled.
+    #[account(
+        mut,
+        seeds = [AIRDROP_CAMPAIGN_SEED, airdrop_campaign.campaign_id.to_le_bytes().as_ref()],
+        bump = airdrop_campaign.bump,
+    )]
+    pub airdrop_campaign: Box<Account<'info, AirdropCampaign>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetAirdropCampaignEnabled` instruction:
+///
+/// - Enables or disables the collection of the campaign's rewards.
+/// - Emits a `SetAirdropCampaignEnabled` event to record the change on-chain.
+pub fn set_airdrop_campaign_enabled(
+    ctx: Context<SetAirdropCampaignEnabled>,
+    is_enabled: bool,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetAirdropCampaignEnabled {
+        authority,
+        game,
+        airdrop_campaign,
+        ..
+    } = ctx.accounts;
+
+    airdrop_campaign.is_enabled = is_enabled;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the campaign's new state
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetAirdropCampaignEnabled as u16,
+        event_type: EventType::SetAirdropCampaignEnabled,
+        event_nonce: game.event_nonce,
+        data: EventData::SetAirdropCampaignEnabled {
+            airdrop_campaign: airdrop_campaign.key(),
+            is_enabled,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetBackupAuthorities` instruction lets the game authority designate the keys standing in for
+/// the bot authority on the failover-enabled bot-only instructions once the bot's heartbeat goes stale.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetBackupAuthorities<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the backup authorities.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetBackupAuthorities` instruction:
+///
+/// - Validates there are at most `MAX_BACKUP_AUTHORITIES` backup authorities and the heartbeat timeout is positive.
+/// - Stores the backup authorities and the heartbeat timeout on the game account.
+/// - Emits a `SetBackupAuthorities` event to record the change on-chain.
+pub fn set_backup_authorities(
+    ctx: Context<SetBackupAuthorities>,
+    backup_authorities: Vec<Pubkey>,
+    bot_heartbeat_timeout_seconds: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetBackupAuthorities {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_backup_authorities(&backup_authorities, bot_heartbeat_timeout_seconds)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new backup authorities
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetBackupAuthorities as u16,
+        event_type: EventType::SetBackupAuthorities,
+        event_nonce: game.event_nonce,
+        data: EventData::SetBackupAuthorities {
+            game: game.key(),
+            backup_authorities,
+            bot_heartbeat_timeout_seconds,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetCandyTapThrottle` instruction lets the game authority configure how often candy taps may
+/// happen, bounding how fast bots can tap for sugar rush rewards.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCandyTapThrottle<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the candy tap throttle.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetCandyTapThrottle` instruction:
+///
+/// - Stores the per-player cooldown and the per-round minimum interval of candy taps on the game account.
+/// - Emits a `SetCandyTapThrottle` event to record the change on-chain.
+pub fn set_candy_tap_throttle(
+    ctx: Context<SetCandyTapThrottle>,
+    candy_tap_cooldown_seconds: u64,
+    candy_tap_min_interval_seconds: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetCandyTapThrottle {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_candy_tap_throttle(candy_tap_cooldown_seconds, candy_tap_min_interval_seconds)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new candy tap throttle
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetCandyTapThrottle as u16,
+        event_type: EventType::SetCandyTapThrottle,
+        event_nonce: game.event_nonce,
+        data: EventData::SetCandyTapThrottle {
+            game: game.key(),
+            candy_tap_cooldown_seconds,
+            candy_tap_min_interval_seconds,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetCaptainInactivityWindow` instruction lets the game authority configure how long a captain must be
+/// inactive before the captaincy of their team may be claimed through `claim_captaincy`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetCaptainInactivityWindow<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the captain inactivity window.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetCaptainInactivityWindow` instruction:
+///
+/// - Stores the captain inactivity window, which must be non-zero, on the game account.
+/// - Emits a `SetCaptainInactivityWindow` event to record the change on-chain.
+pub fn set_captain_inactivity_window(
+    ctx: Context<SetCaptainInactivityWindow>,
+    captain_inactivity_seconds: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetCaptainInactivityWindow {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_captain_inactivity_window(captain_inactivity_seconds)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new captain inactivity window
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetCaptainInactivityWindow as u16,
+        event_type: EventType::SetCaptainInactivityWindow,
+        event_nonce: game.event_nonce,
+        data: EventData::SetCaptainInactivityWindow {
+            game: game.key(),
+            captain_inactivity_seconds,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetComplianceAttestation` instruction lets the game authority gate selected instructions, `purchase` and
+/// `draw_lottery`, behind a recent eligibility attestation signed by a compliance signer, enabling geo-gated
+/// deployments without off-chain custody of funds. Setting the default key as signer disables the gating.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetComplianceAttestation<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the compliance attestation settings.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetComplianceAttestation` instruction:
+///
+/// - Stores the compliance signer, the gated instructions and the attestation validity, which must be non-zero,
+///   on the game account.
+/// - Emits a `SetComplianceAttestation` event to record the change on-chain.
+pub fn set_compliance_attestation(
+    ctx: Context<SetComplianceAttestation>,
+    compliance_signer: Pubkey,
+    compliance_gated_instructions: u8,
+    compliance_attestation_max_age_seconds: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetComplianceAttestation {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_compliance_attestation(
+        compliance_signer,
+        compliance_gated_instructions,
+        compliance_attestation_max_age_seconds,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new compliance attestation settings
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetComplianceAttestation as u16,
+        event_type: EventType::SetComplianceAttestation,
+        event_nonce: game.event_nonce,
+        data: EventData::SetComplianceAttestation {
+            game: game.key(),
+            compliance_signer,
+            compliance_gated_instructions,
+            compliance_attestation_max_age_seconds,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetConsumptionRewardTiers` instruction lets the game authority raise the consumption rewards rate
+/// of players with a larger purchase volume over the last 30 days, rewarding its most active players.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetConsumptionRewardTiers<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the consumption reward tiers.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetConsumptionRewardTiers` instruction:
+///
+/// - Stores the consumption reward tiers on the game account.
+/// - Emits a `SetConsumptionRewardTiers` event to record the change on-chain.
+pub fn set_consumption_reward_tiers(
+    ctx: Context<SetConsumptionRewardTiers>,
+    tiers: Vec<ConsumptionRewardTier>,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetConsumptionRewardTiers {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_consumption_reward_tiers(&tiers)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new consumption reward tiers
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetConsumptionRewardTiers as u16,
+        event_type: EventType::SetConsumptionRewardTiers,
+        event_nonce: game.event_nonce,
+        data: EventData::SetConsumptionRewardTiers {
+            game: game.key(),
+            tiers,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
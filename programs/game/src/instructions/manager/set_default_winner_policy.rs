@@ -0,0 +1,64 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetDefaultWinnerPolicy` instruction lets the game authority configure what happens to the grand prizes
+/// and leaderboard rewards won by the default player or team: burned, rolled into the next period,
+/// sent to the grand prizes of the next rounds, or sent to the insurance pool.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetDefaultWinnerPolicy<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the default winner policy.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetDefaultWinnerPolicy` instruction:
+///
+/// - Stores the default winner policy on the game account.
+/// - Emits a `SetDefaultWinnerPolicy` event to record the change on-chain.
+pub fn set_default_winner_policy(
+    ctx: Context<SetDefaultWinnerPolicy>,
+    policy: DefaultWinnerPolicy,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetDefaultWinnerPolicy {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_default_winner_policy(policy)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new default winner policy
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetDefaultWinnerPolicy as u16,
+        event_type: EventType::SetDefaultWinnerPolicy,
+        event_nonce: game.event_nonce,
+        data: EventData::SetDefaultWinnerPolicy {
+            game: game.key(),
+            policy,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, EXCHANGE_CONFIG_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetExchangePriceFeed` instruction lets the game authority configure the oracle price feed the
+/// collateral exchange rate tracks, along with the rate bounds and the staleness and confidence limits.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetExchangePriceFeed<'info> {
+    /// The authority (signer) authorized to manage the collateral exchange.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The exchange config account being updated.
+    #[account(mut, seeds = [EXCHANGE_CONFIG_SEED], bump)]
+    pub exchange_config: Box<Account<'info, ExchangeConfig>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetExchangePriceFeed` instruction:
+///
+/// - Validates and stores the price feed and its bounds on the exchange config.
+/// - Emits a `SetExchangePriceFeed` event to record the change on-chain.
+pub fn set_exchange_price_feed(
+    ctx: Context<SetExchangePriceFeed>,
+    price_feed: Pubkey,
+    min_exchange_rate: u8,
+    max_exchange_rate: u8,
+    max_staleness_slots: u64,
+    max_confidence_rate: u8,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetExchangePriceFeed {
+        authority,
+        game,
+        exchange_config,
+        ..
+    } = ctx.accounts;
+
+    exchange_config.set_price_feed(
+        price_feed,
+        min_exchange_rate,
+        max_exchange_rate,
+        max_staleness_slots,
+        max_confidence_rate,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new price feed configuration
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetExchangePriceFeed as u16,
+        event_type: EventType::SetExchangePriceFeed,
+        event_nonce: game.event_nonce,
+        data: EventData::SetExchangePriceFeed {
+            exchange_config: exchange_config.key(),
+            price_feed,
+            min_exchange_rate,
+            max_exchange_rate,
+            max_staleness_slots,
+            max_confidence_rate,
+        },
+        initiator_type: InitiatorType::VOUCHER,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetGrandPrizeVesting` instruction lets the game authority stream the current round's first-place grand prize
+/// over a number of days instead of paying it at once, so that a large prize does not create a sell shock.
+/// The smaller grand prizes are always paid instantly.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetGrandPrizeVesting<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority and referencing the current round.
+    #[account(
+        mut,
+        seeds = [GAME_SEED],
+        bump,
+        has_one = authority @ ErrorCode::AuthorityMismatch,
+        has_one = current_round,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The current round, which must not be over.
+    #[account(mut)]
+    pub current_round: Box<Account<'info, Round>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetGrandPrizeVesting` instruction:
+///
+/// - Stores the days over which the current round's first-place grand prize is streamed, 0 paying it instantly.
+/// - Emits a `SetGrandPrizeVesting` event to record the change on-chain.
+///
+/// # Arguments
+/// - `grand_prize_vesting_days`: The streaming duration in days, at most `MAX_GRAND_PRIZE_VESTING_DAYS`.
+pub fn set_grand_prize_vesting(
+    ctx: Context<SetGrandPrizeVesting>,
+    grand_prize_vesting_days: u16,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetGrandPrizeVesting {
+        authority,
+        game,
+        current_round,
+        ..
+    } = ctx.accounts;
+
+    current_round.set_grand_prize_vesting_days(grand_prize_vesting_days)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new grand prize streaming
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetGrandPrizeVesting as u16,
+        event_type: EventType::SetGrandPrizeVesting,
+        event_nonce: game.event_nonce,
+        data: EventData::SetGrandPrizeVesting {
+            round: current_round.key(),
+            grand_prize_vesting_days,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, LOTTERY_TABLE_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetLotteryTableEnabled` instruction lets the game authority open or close a lottery table to new draws,
+/// including reopening a table paused by its return to player ceiling.
+/// Draws already committed to a closed table can still be revealed.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetLotteryTableEnabled<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The lottery table being enabled or disabled.
+    #[account(
+        mut,
+        seeds = [LOTTERY_TABLE_SEED, lottery_table.table_id.to_le_bytes().as_ref()],
+        bump = lottery_table.bump,
+    )]
+    pub lottery_table: Box<Account<'info, LotteryTable>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetLotteryTableEnabled` instruction:
+///
+/// - Enables or disables new draws on the lottery table.
+/// - Emits a `SetLotteryTableEnabled` event to record the change on-chain.
+pub fn set_lottery_table_enabled(
+    ctx: Context<SetLotteryTableEnabled>,
+    is_enabled: bool,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetLotteryTableEnabled {
+        authority,
+        game,
+        lottery_table,
+        ..
+    } = ctx.accounts;
+
+    lottery_table.is_enabled = is_enabled;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the table's new state
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetLotteryTableEnabled as u16,
+        event_type: EventType::SetLotteryTableEnabled,
+        event_nonce: game.event_nonce,
+        data: EventData::SetLotteryTableEnabled {
+            lottery_table: lottery_table.key(),
+            is_enabled,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetReentryFee` instruction lets the game authority configure the fee players pay into the
+/// grand prize pool to re-enter a round after exiting it.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetReentryFee<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the re-entry fee.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetReentryFee` instruction:
+///
+/// - Stores the re-entry fee on the game account.
+/// - Emits a `SetReentryFee` event to record the change on-chain.
+pub fn set_reentry_fee(ctx: Context<SetReentryFee>, reentry_fee: u64) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetReentryFee {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_reentry_fee(reentry_fee)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new re-entry fee
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetReentryFee as u16,
+        event_type: EventType::SetReentryFee,
+        event_nonce: game.event_nonce,
+        data: EventData::SetReentryFee {
+            game: game.key(),
+            reentry_fee,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
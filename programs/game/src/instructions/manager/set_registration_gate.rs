@@ -0,0 +1,68 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetRegistrationGate` instruction lets the game authority configure the proof a new player must give
+/// to receive the registration rewards: none, a minimum token balance, a referrer who purchased ORE this period,
+/// or an attestation signed by an off-chain verification service.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetRegistrationGate<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the registration gate.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetRegistrationGate` instruction:
+///
+/// - Stores the registration gate, its minimum deposit and its attestor on the game account.
+/// - Emits a `SetRegistrationGate` event to record the change on-chain.
+pub fn set_registration_gate(
+    ctx: Context<SetRegistrationGate>,
+    gate: RegistrationGate,
+    min_deposit: u64,
+    attestor: Pubkey,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetRegistrationGate {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_registration_gate(gate, min_deposit, attestor)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new registration gate
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetRegistrationGate as u16,
+        event_type: EventType::SetRegistrationGate,
+        event_nonce: game.event_nonce,
+        data: EventData::SetRegistrationGate {
+            game: game.key(),
+            gate,
+            min_deposit,
+            attestor,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetRegistrationRewardSchedule` instruction lets the game authority tune how the registration rewards decay
+/// as more of them are distributed, rewarding early adopters without a redeploy.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetRegistrationRewardSchedule<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the registration reward schedule.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetRegistrationRewardSchedule` instruction:
+///
+/// - Stores the initial rewards, decay and floor of the registration rewards on the game account.
+/// - Emits a `SetRegistrationRewardSchedule` event to record the change on-chain.
+pub fn set_registration_reward_schedule(
+    ctx: Context<SetRegistrationRewardSchedule>,
+    initial_rewards: u64,
+    decay_interval: u64,
+    decay_amount: u64,
+    floor_rewards: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetRegistrationRewardSchedule {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_registration_reward_schedule(
+        initial_rewards,
+        decay_interval,
+        decay_amount,
+        floor_rewards,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new registration reward schedule
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetRegistrationRewardSchedule as u16,
+        event_type: EventType::SetRegistrationRewardSchedule,
+        event_nonce: game.event_nonce,
+        data: EventData::SetRegistrationRewardSchedule {
+            game: game.key(),
+            initial_rewards,
+            decay_interval,
+            decay_amount,
+            floor_rewards,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
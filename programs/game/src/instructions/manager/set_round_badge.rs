@@ -0,0 +1,68 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetRoundBadge` instruction lets the game authority set the Bubblegum merkle tree into which
+/// `purchase` mints a compressed NFT badge on a player's first purchase in a round, and toggle the badges.
+///
+/// The tree is created with Bubblegum's `create_tree` and its tree delegate set to the game account,
+/// which signs the badge mints.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetRoundBadge<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the badge tree and toggle.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetRoundBadge` instruction:
+///
+/// - Stores the badge merkle tree and toggle on the game account.
+/// - Emits a `SetRoundBadge` event to record the change on-chain.
+pub fn set_round_badge(
+    ctx: Context<SetRoundBadge>,
+    badge_merkle_tree: Pubkey,
+    is_badge_enabled: bool,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetRoundBadge {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_round_badge(badge_merkle_tree, is_badge_enabled)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the badge settings
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetRoundBadge as u16,
+        event_type: EventType::SetRoundBadge,
+        event_nonce: game.event_nonce,
+        data: EventData::SetRoundBadge {
+            game: game.key(),
+            badge_merkle_tree,
+            is_badge_enabled,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, STAKE_POOL_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetStakePoolWindingDown` instruction lets the game authority, which holds the treasury role,
+/// start or stop winding down the stake pool. While winding down, new stakes are refused and players
+/// can leave immediately through `emergency_unstake`. The game has no pause state, so winding down is also
+/// how staking is paused.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetStakePoolWindingDown<'info> {
+    /// The authority (signer) authorized to manage the stake pool.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The stake pool whose wind-down flag is updated.
+    #[account(mut, seeds = [STAKE_POOL_SEED], bump)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetStakePoolWindingDown` instruction:
+///
+/// - Stores the new `is_winding_down` flag on the stake pool.
+/// - Emits a `SetStakePoolWindingDown` event to record the change on-chain.
+pub fn set_stake_pool_winding_down(
+    ctx: Context<SetStakePoolWindingDown>,
+    is_winding_down: bool,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetStakePoolWindingDown {
+        authority,
+        game,
+        stake_pool,
+        ..
+    } = ctx.accounts;
+
+    stake_pool.is_winding_down = is_winding_down;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new wind-down state
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetStakePoolWindingDown as u16,
+        event_type: EventType::SetStakePoolWindingDown,
+        event_nonce: game.event_nonce,
+        data: EventData::SetStakePoolWindingDown {
+            stake_pool: stake_pool.key(),
+            is_winding_down,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, STAKE_POOL_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetStakeRateCurve` instruction lets the game authority tune the utilization curve
+/// that `update_stake_rates` uses to derive the stake pool APR.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetStakeRateCurve<'info> {
+    /// The authority (signer) authorized to configure the stake pool.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The stake pool whose curve parameters are updated.
+    #[account(mut, seeds = [STAKE_POOL_SEED], bump)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetStakeRateCurve` instruction:
+///
+/// - Validates and stores the new curve parameters on the stake pool.
+/// - Emits a `SetStakeRateCurve` event to record the change on-chain.
+pub fn set_stake_rate_curve(
+    ctx: Context<SetStakeRateCurve>,
+    min_annual_rate: u8,
+    max_annual_rate: u8,
+    target_utilization: u8,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetStakeRateCurve {
+        authority,
+        game,
+        stake_pool,
+        ..
+    } = ctx.accounts;
+
+    stake_pool.update_rate_curve(min_annual_rate, max_annual_rate, target_utilization)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new curve parameters
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetStakeRateCurve as u16,
+        event_type: EventType::SetStakeRateCurve,
+        event_nonce: game.event_nonce,
+        data: EventData::SetStakeRateCurve {
+            stake_pool: stake_pool.key(),
+            min_annual_rate,
+            max_annual_rate,
+            target_utilization,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetStartTimeWindow` instruction lets the game authority configure how far ahead of now
+/// rounds and periods may be scheduled to start, guarding lifecycle instructions against stale or
+/// far-future start times.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetStartTimeWindow<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the start time window.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetStartTimeWindow` instruction:
+///
+/// - Stores the minimum lead time and maximum future delay of start times on the game account.
+/// - Emits a `SetStartTimeWindow` event to record the change on-chain.
+pub fn set_start_time_window(
+    ctx: Context<SetStartTimeWindow>,
+    min_start_lead_seconds: u64,
+    max_future_start_seconds: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetStartTimeWindow {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_start_time_window(min_start_lead_seconds, max_future_start_seconds)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new start time window
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetStartTimeWindow as u16,
+        event_type: EventType::SetStartTimeWindow,
+        event_nonce: game.event_nonce,
+        data: EventData::SetStartTimeWindow {
+            game: game.key(),
+            min_start_lead_seconds,
+            max_future_start_seconds,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
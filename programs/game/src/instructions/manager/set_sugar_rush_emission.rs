@@ -0,0 +1,77 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetSugarRushEmission` instruction lets the game authority configure the controller emitting the sugar rush
+/// rewards: a fixed rate, or a rate emitting the remaining sugar rush pool over a horizon, so that the pool
+/// neither drains early nor sits unused.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetSugarRushEmission<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the sugar rush emission controller.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetSugarRushEmission` instruction:
+///
+/// - Validates and stores the sugar rush emission controller on the game account.
+/// - Emits a `SetSugarRushEmission` event to record the change and the rate now in effect on-chain.
+pub fn set_sugar_rush_emission(
+    ctx: Context<SetSugarRushEmission>,
+    mode: SugarRushEmissionMode,
+    rewards_per_second: u64,
+    horizon_seconds: u64,
+    min_rewards_per_second: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetSugarRushEmission {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_sugar_rush_emission(
+        mode,
+        rewards_per_second,
+        horizon_seconds,
+        min_rewards_per_second,
+    )?;
+    let effective_rewards_per_second = game.sugar_rush_emission_rate()?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new emission controller
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetSugarRushEmission as u16,
+        event_type: EventType::SetSugarRushEmission,
+        event_nonce: game.event_nonce,
+        data: EventData::SetSugarRushEmission {
+            game: game.key(),
+            mode,
+            rewards_per_second,
+            horizon_seconds,
+            min_rewards_per_second,
+            effective_rewards_per_second,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
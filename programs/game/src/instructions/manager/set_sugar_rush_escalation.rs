@@ -0,0 +1,71 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetSugarRushEscalation` instruction lets the game authority configure the curve escalating
+/// the sugar rush rewards the longer no one taps candy.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetSugarRushEscalation<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the sugar rush escalation curve.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetSugarRushEscalation` instruction:
+///
+/// - Validates and stores the sugar rush escalation curve on the game account.
+/// - Emits a `SetSugarRushEscalation` event to record the change on-chain.
+pub fn set_sugar_rush_escalation(
+    ctx: Context<SetSugarRushEscalation>,
+    sugar_rush_escalation_delay_seconds: u64,
+    sugar_rush_escalation_rate: u16,
+    sugar_rush_max_multiplier: u16,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetSugarRushEscalation {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_sugar_rush_escalation(
+        sugar_rush_escalation_delay_seconds,
+        sugar_rush_escalation_rate,
+        sugar_rush_max_multiplier,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new escalation curve
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetSugarRushEscalation as u16,
+        event_type: EventType::SetSugarRushEscalation,
+        event_nonce: game.event_nonce,
+        data: EventData::SetSugarRushEscalation {
+            game: game.key(),
+            sugar_rush_escalation_delay_seconds,
+            sugar_rush_escalation_rate,
+            sugar_rush_max_multiplier,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetSwapPool` instruction lets the game authority whitelist the AMM program and pool
+/// through which `purchase_with_sol` swaps SOL for game tokens.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetSwapPool<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the whitelisted swap pool.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetSwapPool` instruction:
+///
+/// - Stores the AMM program and pool on the game account.
+/// - Emits a `SetSwapPool` event to record the change on-chain.
+pub fn set_swap_pool(
+    ctx: Context<SetSwapPool>,
+    swap_program: Pubkey,
+    swap_pool: Pubkey,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetSwapPool {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_swap_pool(swap_program, swap_pool)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the whitelisted swap pool
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetSwapPool as u16,
+        event_type: EventType::SetSwapPool,
+        event_nonce: game.event_nonce,
+        data: EventData::SetSwapPool {
+            game: game.key(),
+            swap_program,
+            swap_pool,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
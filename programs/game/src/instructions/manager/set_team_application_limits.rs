@@ -0,0 +1,68 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetTeamApplicationLimits` instruction lets the game authority configure how many team applications
+/// a player may submit per day, and how long a player must wait to apply again to a team that rejected them.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetTeamApplicationLimits<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the team application limits.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetTeamApplicationLimits` instruction:
+///
+/// - Stores the daily cap on team applications and the reapplication cooldown on the game account.
+/// - Emits a `SetTeamApplicationLimits` event to record the change on-chain.
+pub fn set_team_application_limits(
+    ctx: Context<SetTeamApplicationLimits>,
+    max_team_applications_per_day: u8,
+    team_reapplication_cooldown_seconds: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetTeamApplicationLimits {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_team_application_limits(
+        max_team_applications_per_day,
+        team_reapplication_cooldown_seconds,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new team application limits
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetTeamApplicationLimits as u16,
+        event_type: EventType::SetTeamApplicationLimits,
+        event_nonce: game.event_nonce,
+        data: EventData::SetTeamApplicationLimits {
+            game: game.key(),
+            max_team_applications_per_day,
+            team_reapplication_cooldown_seconds,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
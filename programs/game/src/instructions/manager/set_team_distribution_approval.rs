@@ -0,0 +1,68 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetTeamDistributionApproval` instruction lets the game authority configure the team vault balance above which
+/// team reward distributions must be proposed, and the veto period a proposal waits for unless the game authority approves it.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetTeamDistributionApproval<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account storing the team distribution approval settings.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetTeamDistributionApproval` instruction:
+///
+/// - Stores the approval threshold and the veto period, which must be non-zero, on the game account.
+/// - Emits a `SetTeamDistributionApproval` event to record the change on-chain.
+pub fn set_team_distribution_approval(
+    ctx: Context<SetTeamDistributionApproval>,
+    team_distribution_approval_threshold: u64,
+    team_distribution_veto_seconds: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetTeamDistributionApproval {
+        authority, game, ..
+    } = ctx.accounts;
+
+    game.set_team_distribution_approval(
+        team_distribution_approval_threshold,
+        team_distribution_veto_seconds,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new team distribution approval settings
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetTeamDistributionApproval as u16,
+        event_type: EventType::SetTeamDistributionApproval,
+        event_nonce: game.event_nonce,
+        data: EventData::SetTeamDistributionApproval {
+            game: game.key(),
+            team_distribution_approval_threshold,
+            team_distribution_veto_seconds,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{
+    self, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3, Metadata,
+    UpdateMetadataAccountsV2,
+};
+use anchor_spl::token_interface::Mint;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetVoucherMetadata` instruction lets the authority set the name, symbol and URI shown by wallets
+/// for the voucher mint. The metadata account is created if the voucher was initialized without one,
+/// and updated otherwise, with the `voucher` PDA signing as mint and update authority.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetVoucherMetadata<'info> {
+    /// The authority (signer) authorized to manage the voucher. Pays for the metadata account if created.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The global game account, ensuring the authority constraint.
+    #[account(
+        mut,
+        seeds = [GAME_SEED],
+        bump,
+        has_one = authority @ ErrorCode::AuthorityMismatch,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The voucher account, mint and update authority of the voucher metadata.
+    #[account(seeds = [VOUCHER_SEED], bump, has_one = voucher_mint)]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher token mint account.
+    #[account(seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The voucher mint's metadata account, derived by the token metadata program.
+    /// CHECK: Address checked against the metadata PDA; contents validated via CPI to token metadata program.
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), voucher_mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    pub token_metadata: UncheckedAccount<'info>,
+
+    /// The token metadata program used to create and update the voucher metadata.
+    pub token_metadata_program: Program<'info, Metadata>,
+
+    /// The system program required to create the metadata account.
+    pub system_program: Program<'info, System>,
+
+    /// Rent sysvar to fetch rent exemption data.
+    pub rent: Sysvar<'info, Rent>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Sets the voucher mint's metadata:
+///
+/// Steps:
+/// 1. Create the metadata account if it does not exist yet, otherwise update its data.
+/// 2. Emit a `SetVoucherMetadata` event.
+pub fn set_voucher_metadata(
+    ctx: Context<SetVoucherMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    // Obtain current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetVoucherMetadata {
+        authority,
+        game,
+        voucher,
+        voucher_mint,
+        token_metadata,
+        token_metadata_program,
+        system_program,
+        rent,
+        ..
+    } = ctx.accounts;
+
+    let data = DataV2 {
+        uri: uri.clone(),
+        name: name.clone(),
+        symbol: symbol.clone(),
+        creators: None,
+        seller_fee_basis_points: 0,
+        collection: None,
+        uses: None,
+    };
+    let voucher_signer_seeds: &[&[u8]] = &[VOUCHER_SEED, &[ctx.bumps.voucher]];
+
+    if token_metadata.data_is_empty() {
+        metadata::create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: token_metadata.to_account_info(),
+                    mint: voucher_mint.to_account_info(),
+                    mint_authority: voucher.to_account_info(),
+                    update_authority: voucher.to_account_info(),
+                    payer: authority.to_account_info(),
+                    rent: rent.to_account_info(),
+                    system_program: system_program.to_account_info(),
+                },
+                &[voucher_signer_seeds],
+            ),
+            data,
+            false,
+            true,
+            None,
+        )?;
+    } else {
+        metadata::update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                token_metadata_program.to_account_info(),
+                UpdateMetadataAccountsV2 {
+                    metadata: token_metadata.to_account_info(),
+                    update_authority: voucher.to_account_info(),
+                },
+                &[voucher_signer_seeds],
+            ),
+            None,
+            Some(data),
+            None,
+            None,
+        )?;
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the new metadata
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetVoucherMetadata as u16,
+        event_type: EventType::SetVoucherMetadata,
+        event_nonce: game.event_nonce,
+        data: EventData::SetVoucherMetadata {
+            voucher_mint: voucher_mint.key(),
+            name,
+            symbol,
+            uri,
+        },
+        initiator_type: InitiatorType::VOUCHER,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
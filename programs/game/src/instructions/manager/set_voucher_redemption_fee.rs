@@ -0,0 +1,70 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, VOUCHER_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetVoucherRedemptionFee` instruction lets the game authority set the percentage of the underlying
+/// tokens withheld when players redeem vouchers through `redeem_vouchers`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetVoucherRedemptionFee<'info> {
+    /// The authority (signer) authorized to manage the voucher.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The voucher account whose redemption fee is updated.
+    #[account(mut, seeds = [VOUCHER_SEED], bump)]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetVoucherRedemptionFee` instruction:
+///
+/// - Validates and stores the new `redemption_fee_rate` on the voucher.
+/// - Emits a `SetVoucherRedemptionFee` event to record the change on-chain.
+pub fn set_voucher_redemption_fee(
+    ctx: Context<SetVoucherRedemptionFee>,
+    redemption_fee_rate: u8,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetVoucherRedemptionFee {
+        authority,
+        game,
+        voucher,
+        ..
+    } = ctx.accounts;
+
+    voucher.set_redemption_fee_rate(redemption_fee_rate)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new redemption fee
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetVoucherRedemptionFee as u16,
+        event_type: EventType::SetVoucherRedemptionFee,
+        event_nonce: game.event_nonce,
+        data: EventData::SetVoucherRedemptionFee {
+            voucher: voucher.key(),
+            redemption_fee_rate,
+        },
+        initiator_type: InitiatorType::VOUCHER,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
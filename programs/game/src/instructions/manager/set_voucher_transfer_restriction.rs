@@ -0,0 +1,88 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use solana_program::program_option::COption;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SetVoucherTransferRestriction` instruction lets the game authority toggle the voucher soulbound mode.
+/// While the mode is on, player voucher accounts can be frozen through `freeze_voucher_account`
+/// so that vouchers remain in-game credit instead of a tradable token.
+///
+/// The mode can only be turned on if the `voucher` PDA is the freeze authority of the voucher mint. The voucher
+/// mint of the first deployment was created without a freeze authority, so the mode stays off on that deployment.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetVoucherTransferRestriction<'info> {
+    /// The authority (signer) authorized to manage the voucher.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The voucher account whose transfer restriction is updated.
+    #[account(mut, seeds = [VOUCHER_SEED], bump, has_one = voucher_mint)]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher token mint account, whose freeze authority must be the `voucher` to turn the mode on.
+    #[account(seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `SetVoucherTransferRestriction` instruction:
+///
+/// - Requires the `voucher` to be the freeze authority of the voucher mint when turning the mode on.
+/// - Stores the new `is_transfer_restricted` flag on the voucher.
+/// - Emits a `SetVoucherTransferRestriction` event to record the change on-chain.
+pub fn set_voucher_transfer_restriction(
+    ctx: Context<SetVoucherTransferRestriction>,
+    is_transfer_restricted: bool,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetVoucherTransferRestriction {
+        authority,
+        game,
+        voucher,
+        voucher_mint,
+        ..
+    } = ctx.accounts;
+
+    // Soulbound accounts are frozen by the voucher, which must hold the mint's freeze authority
+    require!(
+        !is_transfer_restricted || voucher_mint.freeze_authority == COption::Some(voucher.key()),
+        ErrorCode::VoucherMintNotFreezable
+    );
+
+    voucher.set_transfer_restricted(is_transfer_restricted)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new soulbound mode
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetVoucherTransferRestriction as u16,
+        event_type: EventType::SetVoucherTransferRestriction,
+        event_nonce: game.event_nonce,
+        data: EventData::SetVoucherTransferRestriction {
+            voucher: voucher.key(),
+            is_transfer_restricted,
+        },
+        initiator_type: InitiatorType::VOUCHER,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
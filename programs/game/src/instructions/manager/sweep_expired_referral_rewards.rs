@@ -0,0 +1,94 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
+use crate::events::{
+    EventData, EventType, InitiatorType, SoftFailureReason, TransferEvent, EVENT_SCHEMA_VERSION,
+};
+use crate::state::{EventRingBuffer, Game, PlayerData};
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SweepExpiredReferralRewards` instruction lets anyone sweep the referral rewards a referrer left uncollected
+/// for `REFERRAL_REWARDS_EXPIRY_SECONDS` back to the referral pool, so that the pool no longer owes them.
+/// The swept tokens stay in the `referral_rewards_pool_balance`, counted by the game's `expired_referral_rewards`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepExpiredReferralRewards<'info> {
+    /// The caller running the crank.
+    pub caller: Signer<'info>,
+
+    /// The global game account, holding the referral pool.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The referrer whose expired referral rewards are swept.
+    /// CHECK: Only used to derive the referrer's player data.
+    pub referrer: UncheckedAccount<'info>,
+
+    /// The referrer's player data, holding the referral reward credits.
+    #[account(
+        mut,
+        seeds = [PLAYER_DATA_SEED, referrer.key().as_ref()],
+        bump,
+    )]
+    pub referrer_data: Box<Account<'info, PlayerData>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Sweeps the expired referral rewards of a referrer:
+///
+/// Steps:
+/// 1. Remove the referral reward credits older than `REFERRAL_REWARDS_EXPIRY_SECONDS` from the referrer's collectable rewards.
+/// 2. If none expired, emit a `SoftFailure` event and return.
+/// 3. Add the expired rewards to the game's `expired_referral_rewards`.
+/// 4. Emit a `SweepExpiredReferralRewards` event.
+pub fn sweep_expired_referral_rewards(ctx: Context<SweepExpiredReferralRewards>) -> Result<()> {
+    // Fetch current UNIX timestamp for the expiry and event timestamping.
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let SweepExpiredReferralRewards {
+        caller,
+        game,
+        referrer,
+        referrer_data,
+        ..
+    } = ctx.accounts;
+
+    let expired_referral_rewards = referrer_data.expire_referral_rewards(timestamp)?;
+    if expired_referral_rewards == 0 {
+        let event = TransferEvent::soft_failure(
+            game,
+            SoftFailureReason::NoExpiredReferralRewards,
+            caller.key(),
+            timestamp,
+        )?;
+        record_event!(ctx, event);
+        return Ok(());
+    }
+
+    game.record_expired_referral_rewards(expired_referral_rewards)?;
+
+    game.increment_event_nonce()?;
+
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SweepExpiredReferralRewards as u16,
+        event_type: EventType::SweepExpiredReferralRewards,
+        event_nonce: game.event_nonce,
+        data: EventData::SweepExpiredReferralRewards {
+            referrer: referrer.key(),
+            expired_referral_rewards,
+            remaining_referral_rewards: referrer_data.collectable_referral_rewards,
+            total_expired_referral_rewards: game.expired_referral_rewards,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: caller.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::constants::{BAN_ENTRY_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `UnbanPlayer` instruction lets the game authority lift the ban of a wallet, closing its ban entry.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnbanPlayer<'info> {
+    /// The authority (signer) authorized to manage the game, refunded the rent of the ban entry.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The global game account.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The ban entry of the wallet, closed on exit.
+    #[account(
+        mut,
+        close = authority,
+        seeds = [BAN_ENTRY_SEED, ban_entry.wallet.as_ref()],
+        bump = ban_entry.bump,
+    )]
+    pub ban_entry: Box<Account<'info, BanEntry>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `UnbanPlayer` instruction:
+///
+/// - Emits an `UnbanPlayer` event to record the lifted ban on-chain; the ban entry is closed on exit.
+pub fn unban_player(ctx: Context<UnbanPlayer>) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let UnbanPlayer {
+        authority,
+        game,
+        ban_entry,
+        ..
+    } = ctx.accounts;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the lifted ban
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::UnbanPlayer as u16,
+        event_type: EventType::UnbanPlayer,
+        event_nonce: game.event_nonce,
+        data: EventData::UnbanPlayer {
+            player: ban_entry.wallet,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `UpdateGameConfig` instruction lets the game authority adjust the pool shares and costs of the game config,
+/// so each cluster runs the same program with its own tunables.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateGameConfig<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game config account being updated.
+    #[account(mut, seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `UpdateGameConfig` instruction:
+///
+/// - Validates and stores the new tunables on the game config.
+/// - Emits an `UpdateGameConfig` event to record the change on-chain.
+pub fn update_game_config(ctx: Context<UpdateGameConfig>, params: GameConfigParams) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let UpdateGameConfig {
+        authority,
+        game,
+        game_config,
+        ..
+    } = ctx.accounts;
+
+    game_config.update(&params)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the new game configuration
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::UpdateGameConfig as u16,
+        event_type: EventType::UpdateGameConfig,
+        event_nonce: game.event_nonce,
+        data: EventData::UpdateGameConfig {
+            game_config: game_config.key(),
+            params,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `UpdateLeaderboardPage` instruction is the crank the bot authority runs on `Purchase` events
+/// to keep a player's entry on the leaderboard pages in sync with their score.
+/// The score is read from the player's data account, so the crank cannot misreport it.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct UpdateLeaderboardPage<'info> {
+    /// The bot authority running the crank, or a backup authority while the bot's heartbeat is stale.
+    #[account(mut)]
+    pub bot_authority: Signer<'info>,
+
+    /// The global game account, holding the bot and backup authorities the signer is checked against.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The leaderboard page the player is added to, moved within or removed from.
+    #[account(mut)]
+    pub leaderboard_page: AccountLoader<'info, LeaderboardPage>,
+
+    /// The player's data account, holding their score for the page's period.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, player.as_ref()],
+        bump,
+        constraint = player_data.current_period == leaderboard_page.load()?.period @ ErrorCode::LeaderboardPageMismatch,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `UpdateLeaderboardPage` instruction:
+///
+/// - Checks the signer is the bot authority, or a backup authority while the bot's heartbeat is stale.
+/// - Lists the player on the page with their current score if it falls within the page's bucket,
+///   otherwise removes them from the page.
+/// - Emits an `UpdateLeaderboardPage` event with the player's position on the page, `0` if not listed.
+pub fn update_leaderboard_page(ctx: Context<UpdateLeaderboardPage>, player: Pubkey) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let UpdateLeaderboardPage {
+        bot_authority,
+        game,
+        leaderboard_page,
+        player_data,
+        ..
+    } = ctx.accounts;
+
+    // The signer must be the bot authority, or a backup authority while the bot's heartbeat is stale
+    game.require_bot_authority(bot_authority.key(), timestamp)?;
+
+    let score = player_data.current_period_purchased_ores;
+    let position = leaderboard_page.load_mut()?.update_entry(player, score)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the player's position on the page
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::UpdateLeaderboardPage as u16,
+        event_type: EventType::UpdateLeaderboardPage,
+        event_nonce: game.event_nonce,
+        data: EventData::UpdateLeaderboardPage {
+            leaderboard_page: leaderboard_page.key(),
+            player,
+            score,
+            position,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: bot_authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
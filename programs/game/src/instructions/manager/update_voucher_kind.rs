@@ -0,0 +1,80 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, VOUCHER_KIND_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `UpdateVoucherKind` instruction lets the game authority change what a voucher of a kind is worth,
+/// or stop the kind from being issued and spent.
+///
+/// Lowering the value rate leaves the excess backing in the kind's vault; raising it requires the vault
+/// to be topped up with `issue_voucher_kind` before the outstanding vouchers are fully backed again.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateVoucherKind<'info> {
+    /// The authority (signer) authorized to manage the game.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The game account, verifying the authority.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = authority @ ErrorCode::AuthorityMismatch)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The voucher kind being updated.
+    #[account(
+        mut,
+        seeds = [VOUCHER_KIND_SEED, voucher_kind.kind_id.to_le_bytes().as_ref()],
+        bump = voucher_kind.bump,
+    )]
+    pub voucher_kind: Box<Account<'info, VoucherKind>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `UpdateVoucherKind` instruction:
+///
+/// - Sets the value rate of the voucher kind and whether it is enabled.
+/// - Emits an `UpdateVoucherKind` event to record the change on-chain.
+pub fn update_voucher_kind(
+    ctx: Context<UpdateVoucherKind>,
+    value_rate: u16,
+    is_enabled: bool,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let UpdateVoucherKind {
+        authority,
+        game,
+        voucher_kind,
+        ..
+    } = ctx.accounts;
+
+    voucher_kind.set_value_rate(value_rate)?;
+    voucher_kind.is_enabled = is_enabled;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the kind's new state
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::UpdateVoucherKind as u16,
+        event_type: EventType::UpdateVoucherKind,
+        event_nonce: game.event_nonce,
+        data: EventData::UpdateVoucherKind {
+            voucher_kind: voucher_kind.key(),
+            value_rate,
+            is_enabled,
+        },
+        initiator_type: InitiatorType::VOUCHER,
+        initiator: authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, LISTING_SEED, PLAYER_DATA_SEED, VOUCHER_MINT_SEED,
+    VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    refreeze_voucher_account, thaw_voucher_account_if_frozen, to_timestamp_u64, transfer_from_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+use solana_program::sysvar::clock::Clock;
+
+/// The `CancelListing` instruction lets a seller withdraw an unfilled marketplace listing.
+/// The escrowed vouchers are refunded to the seller and the listing is closed.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    /// The seller cancelling the listing. Must sign the transaction and receives the rent back.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// The seller's data account, linking the seller to their voucher account.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, seller.key().as_ref()],
+        bump,
+        has_one = voucher_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The seller's voucher account receiving the refunded vouchers.
+    #[account(mut)]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The listing being cancelled, closed to the seller.
+    /// Position listings go through `cancel_position_listing`.
+    #[account(
+        mut,
+        seeds = [LISTING_SEED, seller.key().as_ref(), listing.listing_number.to_le_bytes().as_ref()],
+        bump = listing.bump,
+        has_one = seller,
+        has_one = escrow_vault,
+        constraint = !listing.is_position_listing() @ ErrorCode::InvalidListing,
+        close = seller,
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+
+    /// The escrow vault holding the listed vouchers.
+    #[account(mut)]
+    pub escrow_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global game account, used for the event nonce.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The voucher state account, freeze authority of the voucher mint.
+    #[account(seeds = [VOUCHER_SEED], bump)]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher mint account.
+    #[account(seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program, required for the refund.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Cancels a marketplace listing:
+///
+/// Steps:
+/// 1. Refund the escrowed vouchers to the seller, thawing the seller's voucher account if it is soulbound-frozen.
+/// 2. Close the escrow vault and the listing, returning their rent to the seller.
+/// 3. Emit a `CancelListing` event.
+pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+    // Fetch the current UNIX timestamp for the event
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let CancelListing {
+        seller,
+        voucher_account,
+        listing,
+        escrow_vault,
+        game,
+        voucher,
+        voucher_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    let seller_key = seller.key();
+    let listing_number = listing.listing_number.to_le_bytes();
+    let listing_signer_seeds: &[&[u8]] = &[
+        LISTING_SEED,
+        seller_key.as_ref(),
+        listing_number.as_ref(),
+        &[listing.bump],
+    ];
+
+    // Refund the vouchers to the seller
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+    transfer_from_vault(
+        listing,
+        listing.bump,
+        escrow_vault,
+        voucher_account,
+        voucher_mint,
+        token_program,
+        listing.voucher_amount,
+    )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    // Close the escrow vault, returning its rent to the seller
+    close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow_vault.to_account_info(),
+            destination: seller.to_account_info(),
+            authority: listing.to_account_info(),
+        },
+        &[listing_signer_seeds],
+    ))?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the cancellation
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CancelListing as u16,
+        event_type: EventType::CancelListing,
+        event_nonce: game.event_nonce,
+        data: EventData::CancelListing {
+            listing: listing.key(),
+            seller: seller.key(),
+            voucher_amount: listing.voucher_amount,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: seller.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
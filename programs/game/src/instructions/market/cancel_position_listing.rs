@@ -0,0 +1,137 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, LISTING_SEED};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_vault};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+use solana_program::sysvar::clock::Clock;
+
+/// The `CancelPositionListing` instruction lets a seller withdraw an unfilled marketplace listing of a round position.
+/// The escrowed position token is refunded to the seller and the listing is closed.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelPositionListing<'info> {
+    /// The seller cancelling the listing. Must sign the transaction and receives the rent back.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// The seller's token account receiving the refunded position token.
+    #[account(mut,
+        associated_token::mint = position_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The listing being cancelled, closed to the seller.
+    #[account(
+        mut,
+        seeds = [LISTING_SEED, seller.key().as_ref(), listing.listing_number.to_le_bytes().as_ref()],
+        bump = listing.bump,
+        has_one = seller,
+        has_one = escrow_vault,
+        has_one = round_position,
+        close = seller,
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+
+    /// The escrow vault holding the position token.
+    #[account(mut)]
+    pub escrow_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The position offered by the listing.
+    #[account(has_one = position_mint)]
+    pub round_position: Box<Account<'info, RoundPosition>>,
+
+    /// The mint of the position token.
+    pub position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The global game account, used for the event nonce.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The SPL token program, required for the refund.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Cancels a marketplace listing of a round position:
+///
+/// Steps:
+/// 1. Refund the escrowed position token to the seller.
+/// 2. Close the escrow vault and the listing, returning their rent to the seller.
+/// 3. Emit a `CancelPositionListing` event.
+pub fn cancel_position_listing(ctx: Context<CancelPositionListing>) -> Result<()> {
+    // Fetch the current UNIX timestamp for the event
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let CancelPositionListing {
+        seller,
+        position_token_account,
+        listing,
+        escrow_vault,
+        round_position,
+        position_mint,
+        game,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    let seller_key = seller.key();
+    let listing_number = listing.listing_number.to_le_bytes();
+    let listing_signer_seeds: &[&[u8]] = &[
+        LISTING_SEED,
+        seller_key.as_ref(),
+        listing_number.as_ref(),
+        &[listing.bump],
+    ];
+
+    // Refund the position token to the seller
+    transfer_from_vault(
+        listing,
+        listing.bump,
+        escrow_vault,
+        position_token_account,
+        position_mint,
+        token_program,
+        1,
+    )?;
+
+    // Close the escrow vault, returning its rent to the seller
+    close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow_vault.to_account_info(),
+            destination: seller.to_account_info(),
+            authority: listing.to_account_info(),
+        },
+        &[listing_signer_seeds],
+    ))?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the cancellation
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CancelPositionListing as u16,
+        event_type: EventType::CancelPositionListing,
+        event_nonce: game.event_nonce,
+        data: EventData::CancelPositionListing {
+            listing: listing.key(),
+            seller: seller.key(),
+            round_position: round_position.key(),
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: seller.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, LISTING_SEED, PLAYER_DATA_SEED, VOUCHER_MINT_SEED,
+    VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    refreeze_voucher_account, thaw_voucher_account_if_frozen, to_timestamp_u64,
+    transfer_from_player_to_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `CreateListing` instruction lets a player offer vouchers for sale on the marketplace.
+/// The vouchers are escrowed into a token account owned by a new `Listing` PDA until the listing is
+/// filled by a buyer or cancelled by the seller.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(listing_number: u32)]
+pub struct CreateListing<'info> {
+    /// The player selling the vouchers. Must sign the transaction and pays for the listing.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// The seller's data account, linking the seller to their voucher account.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, seller.key().as_ref()],
+        bump,
+        has_one = voucher_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The seller's voucher account from which the vouchers are escrowed.
+    #[account(mut)]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The new listing, derived from the seller and a listing number of their choice.
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + Listing::INIT_SPACE,
+        seeds = [LISTING_SEED, seller.key().as_ref(), listing_number.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+
+    /// The escrow vault, an associated token account holding the listed vouchers.
+    /// Initialized with the `listing` as its authority.
+    #[account(
+        init,
+        payer = seller,
+        associated_token::mint = voucher_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global game account, used for the event nonce.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The voucher state account, freeze authority of the voucher mint.
+    #[account(seeds = [VOUCHER_SEED], bump)]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher mint account.
+    #[account(seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program, required for the transfer.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program, used to create the `escrow_vault`.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program, required to create the listing.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Creates a marketplace listing:
+///
+/// Steps:
+/// 1. Validate the amount, the price and the seller's voucher balance.
+/// 2. Escrow the vouchers into the listing's vault, thawing the seller's voucher account if it is soulbound-frozen.
+/// 3. Record the listing.
+/// 4. Emit a `CreateListing` event.
+pub fn create_listing(
+    ctx: Context<CreateListing>,
+    listing_number: u32,
+    voucher_amount: u64,
+    price: u64,
+) -> Result<()> {
+    // Fetch the current UNIX timestamp for the listing and the event
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let CreateListing {
+        seller,
+        voucher_account,
+        listing,
+        escrow_vault,
+        game,
+        voucher,
+        voucher_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require!(voucher_amount > 0 && price > 0, ErrorCode::InvalidListing);
+    require!(
+        voucher_account.amount >= voucher_amount,
+        ErrorCode::InsufficientVoucherBalance
+    );
+
+    // Thaw the voucher account while the vouchers leave it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+    transfer_from_player_to_vault(
+        seller,
+        voucher_account,
+        escrow_vault,
+        voucher_mint,
+        token_program,
+        voucher_amount,
+    )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    listing.set_inner(Listing {
+        seller: seller.key(),
+        listing_number,
+        escrow_vault: escrow_vault.key(),
+        voucher_amount,
+        price,
+        created_timestamp: timestamp,
+        bump: ctx.bumps.listing,
+        round_position: Pubkey::default(),
+    });
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the new listing
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CreateListing as u16,
+        event_type: EventType::CreateListing,
+        event_nonce: game.event_nonce,
+        data: EventData::CreateListing {
+            listing: listing.key(),
+            seller: seller.key(),
+            voucher_amount,
+            price,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: seller.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, LISTING_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_player_to_vault};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `CreatePositionListing` instruction lets the holder of a tokenized round position offer it for sale
+/// on the marketplace. The position token is escrowed into a token account owned by a new `Listing` PDA
+/// until the listing is filled by a buyer or cancelled by the seller.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(listing_number: u32)]
+pub struct CreatePositionListing<'info> {
+    /// The holder selling the position. Must sign the transaction and pays for the listing.
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// The position offered for sale.
+    #[account(has_one = position_mint)]
+    pub round_position: Box<Account<'info, RoundPosition>>,
+
+    /// The mint of the position token.
+    pub position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The seller's token account holding the position token.
+    #[account(mut,
+        associated_token::mint = position_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The new listing, derived from the seller and a listing number of their choice.
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + Listing::INIT_SPACE,
+        seeds = [LISTING_SEED, seller.key().as_ref(), listing_number.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+
+    /// The escrow vault, an associated token account holding the position token.
+    /// Initialized with the `listing` as its authority.
+    #[account(
+        init,
+        payer = seller,
+        associated_token::mint = position_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global game account, used for the event nonce.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The SPL token program, required for the transfer.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program, used to create the `escrow_vault`.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program, required to create the listing.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Creates a marketplace listing of a round position:
+///
+/// Steps:
+/// 1. Validate the price and that the seller holds the position token.
+/// 2. Escrow the position token into the listing's vault.
+/// 3. Record the listing.
+/// 4. Emit a `CreatePositionListing` event.
+pub fn create_position_listing(
+    ctx: Context<CreatePositionListing>,
+    listing_number: u32,
+    price: u64,
+) -> Result<()> {
+    // Fetch the current UNIX timestamp for the listing and the event
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let CreatePositionListing {
+        seller,
+        round_position,
+        position_mint,
+        position_token_account,
+        listing,
+        escrow_vault,
+        game,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require!(price > 0, ErrorCode::InvalidListing);
+    require!(
+        position_token_account.amount == 1,
+        ErrorCode::InvalidListing
+    );
+
+    transfer_from_player_to_vault(
+        seller,
+        position_token_account,
+        escrow_vault,
+        position_mint,
+        token_program,
+        1,
+    )?;
+
+    listing.set_inner(Listing {
+        seller: seller.key(),
+        listing_number,
+        escrow_vault: escrow_vault.key(),
+        voucher_amount: 0,
+        price,
+        created_timestamp: timestamp,
+        bump: ctx.bumps.listing,
+        round_position: round_position.key(),
+    });
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the new listing
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CreatePositionListing as u16,
+        event_type: EventType::CreatePositionListing,
+        event_nonce: game.event_nonce,
+        data: EventData::CreatePositionListing {
+            listing: listing.key(),
+            seller: seller.key(),
+            round_position: round_position.key(),
+            ores: round_position.ores,
+            price,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: seller.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
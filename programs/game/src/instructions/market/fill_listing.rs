@@ -0,0 +1,234 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, LISTING_SEED, PLAYER_DATA_SEED,
+    VOUCHER_MINT_SEED, VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    calculate_proportion, refreeze_voucher_account, thaw_voucher_account_if_frozen,
+    to_timestamp_u64, transfer_from_player_to_vault, transfer_from_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+use solana_program::sysvar::clock::Clock;
+
+/// The `FillListing` instruction lets a player buy the vouchers escrowed in a marketplace listing.
+/// The buyer pays the listing price in game tokens: the game config's `market_fee_rate` percent goes to the game vault as
+/// developer rewards and the rest to the seller. The vouchers are released to the buyer and the listing is closed.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FillListing<'info> {
+    /// The player buying the vouchers. Must sign the transaction.
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// The buyer's data account, linking the buyer to their token and voucher accounts.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, buyer.key().as_ref()],
+        bump,
+        has_one = token_account,
+        has_one = voucher_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The buyer's token account paying the price.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The buyer's voucher account receiving the vouchers.
+    #[account(mut)]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The seller, receiving the rent of the closed listing and escrow vault.
+    #[account(mut)]
+    pub seller: SystemAccount<'info>,
+
+    /// The seller's token account receiving the price net of the fee.
+    #[account(mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program
+    )]
+    pub seller_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The listing being filled, closed to the seller once filled.
+    /// Position listings go through `fill_position_listing`.
+    #[account(
+        mut,
+        seeds = [LISTING_SEED, seller.key().as_ref(), listing.listing_number.to_le_bytes().as_ref()],
+        bump = listing.bump,
+        has_one = seller,
+        has_one = escrow_vault,
+        constraint = !listing.is_position_listing() @ ErrorCode::InvalidListing,
+        close = seller,
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+
+    /// The escrow vault holding the listed vouchers.
+    #[account(mut)]
+    pub escrow_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global game account, holding the developer rewards pool credited with the fee.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = game_vault)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game config holding the market fee rate.
+    #[account(seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// The main game vault receiving the fee.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The voucher state account, freeze authority of the voucher mint.
+    #[account(seeds = [VOUCHER_SEED], bump)]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher mint account.
+    #[account(seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program, required for the transfers.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Fills a marketplace listing:
+///
+/// Steps:
+/// 1. Verify the buyer is not the seller.
+/// 2. Pay the seller the price net of the fee, and credit the fee to the developer rewards pool.
+/// 3. Release the escrowed vouchers to the buyer, thawing the buyer's voucher account if it is soulbound-frozen.
+/// 4. Close the escrow vault and the listing, returning their rent to the seller.
+/// 5. Emit a `FillListing` event.
+pub fn fill_listing(ctx: Context<FillListing>) -> Result<()> {
+    // Fetch the current UNIX timestamp for the event
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let FillListing {
+        buyer,
+        token_account,
+        voucher_account,
+        seller,
+        seller_token_account,
+        listing,
+        escrow_vault,
+        game,
+        game_config,
+        game_vault,
+        voucher,
+        voucher_mint,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require!(buyer.key() != seller.key(), ErrorCode::CannotFillOwnListing);
+
+    // Pay the seller and the protocol fee
+    let fee = calculate_proportion(listing.price, game_config.market_fee_rate)?;
+    let seller_amount = listing.price.safe_sub(fee)?;
+
+    transfer_from_player_to_vault(
+        buyer,
+        token_account,
+        seller_token_account,
+        token_mint,
+        token_program,
+        seller_amount,
+    )?;
+
+    if fee > 0 {
+        transfer_from_player_to_vault(
+            buyer,
+            token_account,
+            game_vault,
+            token_mint,
+            token_program,
+            fee,
+        )?;
+        game.developer_rewards_pool_balance = game.developer_rewards_pool_balance.safe_add(fee)?;
+    }
+
+    // Release the vouchers to the buyer
+    let seller_key = seller.key();
+    let listing_number = listing.listing_number.to_le_bytes();
+    let listing_signer_seeds: &[&[u8]] = &[
+        LISTING_SEED,
+        seller_key.as_ref(),
+        listing_number.as_ref(),
+        &[listing.bump],
+    ];
+
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+    transfer_from_vault(
+        listing,
+        listing.bump,
+        escrow_vault,
+        voucher_account,
+        voucher_mint,
+        token_program,
+        listing.voucher_amount,
+    )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    // Close the escrow vault, returning its rent to the seller
+    close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow_vault.to_account_info(),
+            destination: seller.to_account_info(),
+            authority: listing.to_account_info(),
+        },
+        &[listing_signer_seeds],
+    ))?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the sale
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::FillListing as u16,
+        event_type: EventType::FillListing,
+        event_nonce: game.event_nonce,
+        data: EventData::FillListing {
+            listing: listing.key(),
+            seller: seller.key(),
+            buyer: buyer.key(),
+            voucher_amount: listing.voucher_amount,
+            price: listing.price,
+            fee,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: buyer.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
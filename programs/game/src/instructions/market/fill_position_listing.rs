@@ -0,0 +1,227 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, LISTING_SEED, PLAYER_DATA_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    calculate_proportion, to_timestamp_u64, transfer_from_player_to_vault, transfer_from_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+use solana_program::sysvar::clock::Clock;
+
+/// The `FillPositionListing` instruction lets a player buy the round position escrowed in a marketplace listing.
+/// The buyer pays the listing price in game tokens, split between the seller and the developer rewards like
+/// `fill_listing`. The position token is released to the buyer, who may then redeem it, and the listing is closed.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FillPositionListing<'info> {
+    /// The player buying the position. Must sign the transaction and pays for their position token account.
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// The buyer's data account, linking the buyer to their token account.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, buyer.key().as_ref()],
+        bump,
+        has_one = token_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The buyer's token account paying the price.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The buyer's token account receiving the position token.
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = position_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The seller, receiving the rent of the closed listing and escrow vault.
+    #[account(mut)]
+    pub seller: SystemAccount<'info>,
+
+    /// The seller's token account receiving the price net of the fee.
+    #[account(mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program
+    )]
+    pub seller_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The listing being filled, closed to the seller once filled.
+    #[account(
+        mut,
+        seeds = [LISTING_SEED, seller.key().as_ref(), listing.listing_number.to_le_bytes().as_ref()],
+        bump = listing.bump,
+        has_one = seller,
+        has_one = escrow_vault,
+        has_one = round_position,
+        close = seller,
+    )]
+    pub listing: Box<Account<'info, Listing>>,
+
+    /// The escrow vault holding the position token.
+    #[account(mut)]
+    pub escrow_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The position being sold.
+    #[account(has_one = position_mint)]
+    pub round_position: Box<Account<'info, RoundPosition>>,
+
+    /// The mint of the position token.
+    pub position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The global game account, holding the developer rewards pool credited with the fee.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = game_vault)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game config holding the market fee rate.
+    #[account(seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// The main game vault receiving the fee.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program, required for the transfers.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program, used to create the `position_token_account`.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program, required to create the `position_token_account`.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Fills a marketplace listing of a round position:
+///
+/// Steps:
+/// 1. Verify the buyer is not the seller.
+/// 2. Pay the seller the price net of the fee, and credit the fee to the developer rewards pool.
+/// 3. Release the escrowed position token to the buyer.
+/// 4. Close the escrow vault and the listing, returning their rent to the seller.
+/// 5. Emit a `FillPositionListing` event.
+pub fn fill_position_listing(ctx: Context<FillPositionListing>) -> Result<()> {
+    // Fetch the current UNIX timestamp for the event
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let FillPositionListing {
+        buyer,
+        token_account,
+        position_token_account,
+        seller,
+        seller_token_account,
+        listing,
+        escrow_vault,
+        round_position,
+        position_mint,
+        game,
+        game_config,
+        game_vault,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require!(buyer.key() != seller.key(), ErrorCode::CannotFillOwnListing);
+
+    // Pay the seller and the protocol fee
+    let fee = calculate_proportion(listing.price, game_config.market_fee_rate)?;
+    let seller_amount = listing.price.safe_sub(fee)?;
+
+    transfer_from_player_to_vault(
+        buyer,
+        token_account,
+        seller_token_account,
+        token_mint,
+        token_program,
+        seller_amount,
+    )?;
+
+    if fee > 0 {
+        transfer_from_player_to_vault(
+            buyer,
+            token_account,
+            game_vault,
+            token_mint,
+            token_program,
+            fee,
+        )?;
+        game.developer_rewards_pool_balance = game.developer_rewards_pool_balance.safe_add(fee)?;
+    }
+
+    // Release the position token to the buyer
+    let seller_key = seller.key();
+    let listing_number = listing.listing_number.to_le_bytes();
+    let listing_signer_seeds: &[&[u8]] = &[
+        LISTING_SEED,
+        seller_key.as_ref(),
+        listing_number.as_ref(),
+        &[listing.bump],
+    ];
+
+    transfer_from_vault(
+        listing,
+        listing.bump,
+        escrow_vault,
+        position_token_account,
+        position_mint,
+        token_program,
+        1,
+    )?;
+
+    // Close the escrow vault, returning its rent to the seller
+    close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: escrow_vault.to_account_info(),
+            destination: seller.to_account_info(),
+            authority: listing.to_account_info(),
+        },
+        &[listing_signer_seeds],
+    ))?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the sale
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::FillPositionListing as u16,
+        event_type: EventType::FillPositionListing,
+        event_nonce: game.event_nonce,
+        data: EventData::FillPositionListing {
+            listing: listing.key(),
+            seller: seller.key(),
+            buyer: buyer.key(),
+            round_position: round_position.key(),
+            price: listing.price,
+            fee,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: buyer.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
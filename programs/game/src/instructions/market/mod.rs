@@ -0,0 +1,12 @@
+pub mod cancel_listing;
+pub mod cancel_position_listing;
+pub mod create_listing;
+pub mod create_position_listing;
+pub mod fill_listing;
+pub mod fill_position_listing;
+pub use cancel_listing::*;
+pub use cancel_position_listing::*;
+pub use create_listing::*;
+pub use create_position_listing::*;
+pub use fill_listing::*;
+pub use fill_position_listing::*;
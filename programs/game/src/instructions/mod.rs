@@ -1,9 +1,13 @@
+pub mod governance;
 pub mod manager;
+pub mod market;
 pub mod stake;
 pub mod player;
 pub mod team;
 
+pub use governance::*;
 pub use manager::*;
+pub use market::*;
 pub use stake::*;
 pub use player::*;
 pub use team::*;
@@ -0,0 +1,196 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, STREAK_SHIELD_VOUCHER_COST,
+    VOUCHER_MINT_SEED, VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    redeem_vouchers, refreeze_voucher_account, thaw_voucher_account_if_frozen, to_timestamp_u64,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `BuyStreakShield` instruction lets a player spend vouchers on a streak shield, which preserves their
+/// `consecutive_purchased_days` over one missed day when they purchase again the day after.
+/// The shield can be used for `STREAK_SHIELD_DURATION` after it is bought, and its cost funds the consumption pool.
+///
+/// Steps:
+/// 1. Give the player a streak shield, unless they hold an active one.
+/// 2. Burn `STREAK_SHIELD_VOUCHER_COST` vouchers from the player's voucher account.
+/// 3. Redeem the burned vouchers for their underlying tokens into the game vault, crediting the consumption pool.
+/// 4. Emit a `BuyStreakShield` event to record the purchase on-chain.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BuyStreakShield<'info> {
+    /// The player buying the shield. Must sign the transaction.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The player's data account, holding the shield.
+    #[account(
+        mut,
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = voucher_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The player's voucher account from which the shield's cost is burned.
+    #[account(mut)]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global game account, holding the consumption pool.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = game_vault)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The main game vault account receiving the tokens backing the burned vouchers.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The voucher state account managing voucher mint authority and supply.
+    #[account(
+        mut,
+        seeds = [VOUCHER_SEED], bump,
+        has_one = voucher_vault,
+    )]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher vault token account holding the underlying assets backing voucher tokens.
+    #[account(mut)]
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The voucher mint account used to burn voucher tokens.
+    #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL Token program used for burning and transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `BuyStreakShield` instruction, see `BuyStreakShield`.
+pub fn buy_streak_shield(ctx: Context<BuyStreakShield>) -> Result<()> {
+    // Obtain the current UNIX timestamp for the shield's expiry and event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let BuyStreakShield {
+        player,
+        player_data,
+        voucher_account,
+        game,
+        game_vault,
+        voucher,
+        voucher_vault,
+        voucher_mint,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    let voucher_cost = STREAK_SHIELD_VOUCHER_COST;
+    require!(
+        voucher_account.amount >= voucher_cost,
+        ErrorCode::InsufficientVoucherBalance
+    );
+
+    player_data.buy_streak_shield(timestamp)?;
+
+    // The shield's cost funds the consumption pool
+    game.consumption_rewards_pool_balance = game
+        .consumption_rewards_pool_balance
+        .safe_add(voucher_cost)?;
+
+    // Burn the voucher tokens from the player's voucher account
+    voucher.burn(voucher_cost)?;
+    // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+    burn(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Burn {
+                mint: voucher_mint.to_account_info(),
+                from: voucher_account.to_account_info(),
+                authority: player.to_account_info(),
+            },
+        ),
+        voucher_cost,
+    )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    game.record_burned_vouchers(voucher_cost)?;
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the burn
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Burn as u16,
+        event_type: EventType::Burn,
+        event_nonce: game.event_nonce,
+        data: EventData::Burn {
+            mint: voucher_mint.key(),
+            from: voucher_account.key(),
+            amount: voucher_cost,
+            reason: BurnReason::VoucherPayment,
+            total_burned: game.total_burned_vouchers,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    // Redeem the burned vouchers by transferring underlying tokens from voucher_vault to game_vault
+    redeem_vouchers(
+        voucher,
+        ctx.bumps.voucher,
+        voucher_vault,
+        game_vault,
+        token_mint,
+        token_program,
+        voucher_cost,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the shield
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::BuyStreakShield as u16,
+        event_type: EventType::BuyStreakShield,
+        event_nonce: game.event_nonce,
+        data: EventData::BuyStreakShield {
+            player: player.key(),
+            voucher_cost,
+            streak_shield_expiry: player_data.streak_shield_expiry,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
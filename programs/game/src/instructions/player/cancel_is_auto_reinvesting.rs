@@ -1,6 +1,6 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
@@ -16,6 +16,7 @@ use solana_program::sysvar::clock::Clock;
 /// 2. Update the player's data account to disable `is_auto_reinvesting`.
 /// 3. Decrement the `auto_reinvesting_players` count in the current round, maintaining accurate round-level statistics.
 /// 4. Emit a `CancelIsAutoReinvesting` event to log this action on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CancelIsAutoReinvesting<'info> {
     /// The player requesting to cancel auto-reinvestment. Must sign the transaction.
@@ -38,6 +39,10 @@ pub struct CancelIsAutoReinvesting<'info> {
         constraint = !current_round.is_over @ ErrorCode::RoundAlreadyEnded,
     )]
     pub current_round: Box<Account<'info, Round>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 pub fn cancel_is_auto_reinvesting(ctx: Context<CancelIsAutoReinvesting>) -> Result<()> {
@@ -73,7 +78,9 @@ pub fn cancel_is_auto_reinvesting(ctx: Context<CancelIsAutoReinvesting>) -> Resu
     game.increment_event_nonce()?;
 
     // Emit an event to record the cancellation action
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CancelIsAutoReinvesting as u16,
         event_type: EventType::CancelIsAutoReinvesting,
         event_nonce: game.event_nonce,
         data: EventData::CancelIsAutoReinvesting {
@@ -83,7 +90,8 @@ pub fn cancel_is_auto_reinvesting(ctx: Context<CancelIsAutoReinvesting>) -> Resu
         initiator_type: InitiatorType::PLAYER,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
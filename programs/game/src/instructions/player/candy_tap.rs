@@ -1,16 +1,18 @@
 use crate::constants::{
-    CONSTRUCTION_POOL_SHARE, CONSUMPTION_POOL_SHARE, GAME_SEED, GRAND_PRIZES_POOL_SHARE,
-    LOTTERY_POOL_SHARE, PLAYER_DATA_SEED, REFERRAL_POOL_SHARE,
+    EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, PLAYER_DATA_SEED, SESSION_KEY_SEED,
 };
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
+#[cfg(feature = "strict-invariants")]
+use crate::utils::{assert_invariants, InvariantSnapshot};
 use crate::utils::{
-    calculate_proportion, to_timestamp_u64, transfer_from_token_vault_to_token_account,
+    authorize_player_signer, calculate_sugar_rush_multiplier, calculate_sugar_rush_rewards,
+    distribute, to_timestamp_u64, transfer_from_vault, DistributionInput,
 };
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
-use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
 /// The `Purchase` instruction enables players to buy ORE tokens within the current round, affecting various in-game pools and distributions.
@@ -24,12 +26,20 @@ use solana_program::sysvar::clock::Clock;
 /// 5. Manage player state: update consecutive purchase days, settle pending construction rewards, and adjust ORE holdings and earnings_per_ore.
 /// 6. If vouchers are used as payment, burn them and redeem underlying tokens. Also, transfer funds from player accounts to game and round vaults as required.
 /// 7. Emit a `Purchase` event to record the transaction on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(last_active_participant: Pubkey)]
 pub struct CandyTap<'info> {
-    /// The player making the purchase. Must sign the transaction.
-    #[account(mut)]
-    pub player: Signer<'info>,
+    /// The wallet signing the transaction: the player, or the session signer of the player's `session_key`.
+    pub signer: Signer<'info>,
+
+    /// The player tapping candy.
+    /// CHECK: Must be the `signer`, or the player whose `session_key` authorizes the signer; checked in the handler.
+    pub player: UncheckedAccount<'info>,
+
+    /// The player's session key, required when a session signer signs on the player's behalf.
+    #[account(seeds = [SESSION_KEY_SEED, player.key().as_ref()], bump)]
+    pub session_key: Option<Box<Account<'info, SessionKey>>>,
 
     /// The player's data account, linking to their token and voucher accounts, and indicating their current team.
     #[account(mut, seeds = [PLAYER_DATA_SEED, player.key().as_ref()], bump)]
@@ -45,6 +55,10 @@ pub struct CandyTap<'info> {
     )]
     pub game: Box<Account<'info, Game>>,
 
+    /// The game config holding the pool shares.
+    #[account(seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
     /// The current round account, must be active (not ended), referencing its `round_vault`.
     #[account(
         mut,
@@ -64,15 +78,21 @@ pub struct CandyTap<'info> {
 
     /// The main game vault holding the platform's aggregated funds.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The round-specific vault holding tokens allocated for the current round.
     #[account(mut)]
-    pub round_vault: Box<Account<'info, TokenAccount>>,
+    pub round_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     /// The SPL Token program used for token operations like minting, burning, and transfers.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Handles the `Purchase` logic, applying cost calculations, distribution of funds to various pools,
@@ -82,12 +102,26 @@ pub fn candy_tap(ctx: Context<CandyTap>, last_active_participant: Pubkey) -> Res
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
+    // Check the signer acts for the player, directly or through their session key
+    authorize_player_signer(
+        ctx.accounts.player.key(),
+        ctx.accounts.signer.key(),
+        ctx.accounts
+            .session_key
+            .as_deref()
+            .map(|session_key| &**session_key),
+        SESSION_SCOPE_CANDY_TAP,
+        timestamp,
+    )?;
+
     // Extract references for clarity
     let CandyTap {
+        token_mint,
         player,
         player_data,
         last_active_participant_data,
         game,
+        game_config,
         game_vault,
         current_round,
         round_vault,
@@ -95,6 +129,10 @@ pub fn candy_tap(ctx: Context<CandyTap>, last_active_participant: Pubkey) -> Res
         ..
     } = ctx.accounts;
 
+    // Capture the monotonic counters checked by the invariants at the end of the instruction
+    #[cfg(feature = "strict-invariants")]
+    let invariant_snapshot = InvariantSnapshot::take(game, current_round);
+
     // Validate that the current round is active (has started)
     require!(
         current_round.start_time <= timestamp,
@@ -103,109 +141,97 @@ pub fn candy_tap(ctx: Context<CandyTap>, last_active_participant: Pubkey) -> Res
 
     require!(player_data.available_ores > 0, ErrorCode::NoOresAvailable);
 
-    // Calculate total cost in lamports for the requested ORE quantity
-    let elapsed_time =
+    // Throttle taps, both for the player and across the round
+    require!(
+        timestamp
+            >= player_data
+                .last_candy_tap_timestamp
+                .safe_add(game.candy_tap_cooldown_seconds)?,
+        ErrorCode::CandyTapThrottled
+    );
+    require!(
+        timestamp
+            >= current_round
+                .last_collected_sugar_rush_reward_timestamp
+                .safe_add(game.candy_tap_min_interval_seconds)?,
+        ErrorCode::CandyTapThrottled
+    );
+    player_data.last_candy_tap_timestamp = timestamp;
+
+    player_data.tap_count = player_data.tap_count.safe_add(1)?;
+
+    // A drained sugar rush pool has nothing left to pay
+    require!(
+        game.sugar_rush_rewards_pool_balance > 0,
+        ErrorCode::SugarRushPoolEmpty
+    );
+
+    // Escalate the sugar rush rewards with the time no one tapped, never beyond the sugar rush pool
+    let idle_seconds =
         timestamp.safe_sub(current_round.last_collected_sugar_rush_reward_timestamp)?;
-    let total_cost = game.sugar_rush_rewards_per_second.safe_mul(elapsed_time)?;
-    current_round.last_collected_sugar_rush_reward_timestamp = timestamp;
+    let sugar_rush_multiplier = calculate_sugar_rush_multiplier(
+        idle_seconds,
+        game.sugar_rush_escalation_delay_seconds,
+        game.sugar_rush_escalation_rate,
+        game.sugar_rush_max_multiplier,
+    );
+    let sugar_rush_rewards_per_second = game.sugar_rush_emission_rate()?;
+    let requested_sugar_rush_rewards = calculate_sugar_rush_rewards(
+        sugar_rush_rewards_per_second,
+        idle_seconds,
+        sugar_rush_multiplier,
+    )?;
+    let total_cost = requested_sugar_rush_rewards.min(game.sugar_rush_rewards_pool_balance);
+    current_round.collect_sugar_rush(timestamp, sugar_rush_multiplier)?;
 
     game.sugar_rush_rewards_pool_balance =
         game.sugar_rush_rewards_pool_balance.safe_sub(total_cost)?;
 
-    // Calculate proportional rewards for various pools
-    let construction_rewards = calculate_proportion(total_cost, CONSTRUCTION_POOL_SHARE)?;
-    let bonus_rewards = construction_rewards;
-    let lottery_rewards = calculate_proportion(total_cost, LOTTERY_POOL_SHARE)?;
-    let referral_rewards = calculate_proportion(total_cost, REFERRAL_POOL_SHARE)?;
-    let grand_prizes_rewards = calculate_proportion(total_cost, GRAND_PRIZES_POOL_SHARE)?;
-    let consumption_rewards = calculate_proportion(total_cost, CONSUMPTION_POOL_SHARE)?;
-    let developer_rewards = calculate_proportion(total_cost, CONSUMPTION_POOL_SHARE)?;
-
-    // Update game-level pools
-    game.construction_rewards_pool_balance = game
-        .construction_rewards_pool_balance
-        .safe_add(construction_rewards)?;
-    game.bonus_rewards_pool_balance = game.bonus_rewards_pool_balance.safe_add(bonus_rewards)?;
-    game.lottery_rewards_pool_balance = game
-        .lottery_rewards_pool_balance
-        .safe_add(lottery_rewards)?;
-    game.referral_rewards_pool_balance = game
-        .referral_rewards_pool_balance
-        .safe_add(referral_rewards)?;
-
-    // Update round-level pools
-    current_round.grand_prize_pool_balance = current_round
-        .grand_prize_pool_balance
-        .safe_add(grand_prizes_rewards)?;
-
-    // Update earnings_per_ore in the round
-    let available_ores = current_round.available_ores.max(1);
-    let earnings_per_ore_increment = construction_rewards.safe_div(available_ores as u64)?;
-    current_round.earnings_per_ore = current_round
-        .earnings_per_ore
-        .safe_add(earnings_per_ore_increment)?;
-
-    // Update round state: sold ORE, participant list, end time
+    // Split the sugar rush rewards across the reward pools, the referral share going to the last active participant
+    let distribution = distribute(
+        &DistributionInput {
+            total_cost,
+            consumption_base: total_cost,
+            has_referrer: true,
+            // A candy tap is no purchase, earning the consumption rewards at the base rate
+            purchase_volume: 0,
+            timestamp,
+        },
+        game_config,
+        game,
+        current_round,
+        last_active_participant_data,
+    )?;
     current_round.update_end_time(timestamp)?;
 
-    // Add referral rewards to the referrer's pending rewards
-    last_active_participant_data.collectable_referral_rewards = last_active_participant_data
-        .collectable_referral_rewards
-        .safe_add(referral_rewards)?;
-
-    // If mining pool balance is enough, add developer rewards
-    if game.consumption_rewards_pool_balance >= developer_rewards {
-        game.consumption_rewards_pool_balance = game
-            .consumption_rewards_pool_balance
-            .safe_sub(developer_rewards)?;
-        game.distributable_consumption_rewards = game
-            .distributable_consumption_rewards
-            .safe_sub(developer_rewards)?;
-        game.developer_rewards_pool_balance = game
-            .developer_rewards_pool_balance
-            .safe_add(developer_rewards)?;
-        msg!(
-            "Developer consumption pool increased by {}.",
-            developer_rewards
-        );
-    }
-
-    // If tokens are used (token_cost > 0), add consumption rewards
-    if game.distributable_consumption_rewards >= consumption_rewards {
-        game.distributable_consumption_rewards = game
-            .distributable_consumption_rewards
-            .safe_sub(consumption_rewards)?;
-        if player.key() == last_active_participant {
-            last_active_participant_data.collectable_consumption_rewards =
-                last_active_participant_data
-                    .collectable_consumption_rewards
-                    .safe_add(consumption_rewards)?;
-        } else {
-            player_data.collectable_consumption_rewards = player_data
-                .collectable_consumption_rewards
-                .safe_add(consumption_rewards)?;
-        }
-        msg!(
-            "Player earned {} consumption rewards for spending {} tokens.",
-            consumption_rewards,
-            total_cost
-        );
+    // The consumption rewards go to the last active participant when they tap themselves
+    if player.key() == last_active_participant {
+        last_active_participant_data.collectable_consumption_rewards = last_active_participant_data
+            .collectable_consumption_rewards
+            .safe_add(distribution.consumption_rewards)?;
+    } else {
+        player_data.collectable_consumption_rewards = player_data
+            .collectable_consumption_rewards
+            .safe_add(distribution.consumption_rewards)?;
     }
 
     // Transfer the initial grand prize amount from game_vault to round_vault.
-    transfer_from_token_vault_to_token_account(
+    transfer_from_vault(
         game,
+        ctx.bumps.game,
         &game_vault,
         &round_vault,
+        token_mint,
         &token_program,
-        grand_prizes_rewards,
-        &[GAME_SEED, &[ctx.bumps.game]],
+        distribution.round_vault_amount()?,
     )?;
 
     game.increment_event_nonce()?;
 
     // Emit an event recording the purchase
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CandyTap as u16,
         event_type: EventType::CandyTap,
         event_nonce: game.event_nonce,
         data: EventData::CandyTap {
@@ -213,11 +239,115 @@ pub fn candy_tap(ctx: Context<CandyTap>, last_active_participant: Pubkey) -> Res
             round: current_round.key(),
             player: player.key(),
             last_active_participant,
+            sugar_rush_rewards: total_cost,
+            idle_seconds,
+            sugar_rush_multiplier,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the sugar rush rate the tap was paid at
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SugarRushEmission as u16,
+        event_type: EventType::SugarRushEmission,
+        event_nonce: game.event_nonce,
+        data: EventData::SugarRushEmission {
+            round: current_round.key(),
+            mode: game.sugar_rush_emission_mode(),
+            rewards_per_second: sugar_rush_rewards_per_second,
+            sugar_rush_rewards_pool_balance: game.sugar_rush_rewards_pool_balance,
         },
         initiator_type: InitiatorType::PLAYER,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
+
+    // Log the sugar rush pool capping the rewards as it drains
+    if total_cost < requested_sugar_rush_rewards {
+        game.increment_event_nonce()?;
+
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::PoolExhausted as u16,
+            event_type: EventType::PoolExhausted,
+            event_nonce: game.event_nonce,
+            data: EventData::PoolExhausted {
+                pool: ExhaustedPool::SugarRush,
+                requested_amount: requested_sugar_rush_rewards,
+                paid_amount: total_cost,
+                pool_balance: game.sugar_rush_rewards_pool_balance,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    // Log the round moving to another phase
+    let phase = current_round.phase(timestamp);
+    if phase != distribution.phase {
+        game.increment_event_nonce()?;
+
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::RoundPhaseChanged as u16,
+            event_type: EventType::RoundPhaseChanged,
+            event_nonce: game.event_nonce,
+            data: EventData::RoundPhaseChanged {
+                round: current_round.key(),
+                previous_phase: distribution.phase,
+                phase,
+                available_ores: current_round.available_ores,
+                end_time: current_round.end_time,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    // Log the countdown reaching a milestone
+    if let Some(milestone_seconds) = current_round.reach_countdown_milestone(timestamp) {
+        game.increment_event_nonce()?;
+
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::CountdownMilestone as u16,
+            event_type: EventType::CountdownMilestone,
+            event_nonce: game.event_nonce,
+            data: EventData::CountdownMilestone {
+                round: current_round.key(),
+                milestone_seconds,
+                end_time: current_round.end_time,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    #[cfg(feature = "strict-invariants")]
+    {
+        game_vault.reload()?;
+        round_vault.reload()?;
+        assert_invariants(
+            &invariant_snapshot,
+            game,
+            current_round,
+            game_vault.amount,
+            round_vault.amount,
+        )?;
+    }
 
     Ok(())
 }
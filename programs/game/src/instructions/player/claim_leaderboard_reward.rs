@@ -0,0 +1,214 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, VOUCHER_MINT_SEED,
+    VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    refreeze_voucher_account, thaw_voucher_account_if_frozen, to_timestamp_u64,
+    transfer_from_zero_copy_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `ClaimLeaderboardReward` instruction lets a player holding a rank of a period's player leaderboard
+/// pull the rewards of that rank from the `period_vault` once the period has ended, either as tokens or as
+/// vouchers depending on the period's player payout asset.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimLeaderboardReward<'info> {
+    /// The player claiming the rank rewards. Must be the signer of the transaction.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The global game account, tracking the distributed individual rewards.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The player's data account, recording the collected individual rewards.
+    #[account(mut,
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = token_account,
+        has_one = voucher_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The ended period holding the final standings.
+    #[account(mut,
+        constraint = period.load()?.period_vault == period_vault.key(),
+    )]
+    pub period: AccountLoader<'info, Period>,
+
+    /// The period vault token account holding the leaderboard rewards.
+    #[account(mut)]
+    pub period_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's token account, receiving the rank rewards.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global `Voucher` account, managing voucher mint authority.
+    #[account(
+        mut,
+        seeds = [VOUCHER_SEED],
+        bump,
+        has_one = voucher_vault,
+    )]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher mint account used to mint vouchers to the player.
+    #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The voucher vault token account holding assets that back voucher issuance.
+    #[account(mut)]
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's voucher account, receiving the rank rewards of periods paying in vouchers.
+    #[account(mut)]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint representing the in-game currency.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program enabling token transfers.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the player leaderboard claim:
+///
+/// Steps:
+/// 1. Require the period to have ended and the player to hold the claimed rank, unclaimed so far.
+/// 2. Record the rewards on the player's data and the game's distributed individual rewards.
+/// 3. Transfer the rank rewards from the `period_vault` to the player's token account, or to the `voucher_vault`
+///    backing vouchers minted to the player's voucher account when the period pays in vouchers.
+/// 4. Emit a `ClaimLeaderboardReward` event.
+pub fn claim_leaderboard_reward(ctx: Context<ClaimLeaderboardReward>, rank: u8) -> Result<()> {
+    // Obtain the current UNIX timestamp
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let ClaimLeaderboardReward {
+        player,
+        game,
+        player_data,
+        period,
+        period_vault,
+        token_account,
+        voucher,
+        voucher_mint,
+        voucher_vault,
+        voucher_account,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require_keys_neq!(
+        player.key(),
+        game.default_player,
+        ErrorCode::NotLeaderboardRankHolder
+    );
+
+    // Mark the rank as claimed, keeping a copy of the period data for the transfer below
+    let (rewards, period_data) = {
+        let mut period_data = period.load_mut()?;
+        let rewards = period_data.claim_rank_rewards(false, rank, player.key(), timestamp)?;
+        (rewards, *period_data)
+    };
+
+    game.distributed_individual_rewards = game.distributed_individual_rewards.safe_add(rewards)?;
+    player_data.collect_individual_rewards(rewards)?;
+
+    let payout_asset = period_data.payout_asset(false);
+    match payout_asset {
+        PayoutAsset::Token => {
+            transfer_from_zero_copy_vault(
+                period,
+                &period_data,
+                period_data.bump,
+                period_vault,
+                token_account,
+                token_mint,
+                token_program,
+                rewards,
+            )?;
+        }
+        PayoutAsset::Voucher => {
+            voucher.mint(rewards)?;
+
+            // Move the underlying tokens to the voucher vault, backing the vouchers minted below
+            transfer_from_zero_copy_vault(
+                period,
+                &period_data,
+                period_data.bump,
+                period_vault,
+                voucher_vault,
+                token_mint,
+                token_program,
+                rewards,
+            )?;
+
+            let was_frozen = thaw_voucher_account_if_frozen(
+                voucher,
+                voucher_mint,
+                voucher_account,
+                token_program,
+                &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+            )?;
+            mint_to(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    MintTo {
+                        mint: voucher_mint.to_account_info(),
+                        to: voucher_account.to_account_info(),
+                        authority: voucher.to_account_info(),
+                    },
+                    &[&[VOUCHER_SEED, &[ctx.bumps.voucher]]],
+                ),
+                rewards,
+            )?;
+            refreeze_voucher_account(
+                voucher,
+                voucher_mint,
+                voucher_account,
+                token_program,
+                was_frozen,
+                &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+            )?;
+        }
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the claimed rank rewards
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::ClaimLeaderboardReward as u16,
+        event_type: EventType::ClaimLeaderboardReward,
+        event_nonce: game.event_nonce,
+        data: EventData::ClaimLeaderboardReward {
+            period: period.key(),
+            is_team_leaderboard: false,
+            recipient: player.key(),
+            rank,
+            rewards,
+            payout_asset,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
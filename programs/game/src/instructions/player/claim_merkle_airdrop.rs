@@ -0,0 +1,155 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, MERKLE_AIRDROP_BITMAP_SEED, MERKLE_AIRDROP_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    merkle_airdrop_leaf, to_timestamp_u64, transfer_from_vault, verify_merkle_proof,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `ClaimMerkleAirdrop` instruction lets a player collect their share of a `MerkleAirdrop`,
+/// proving with a merkle proof that the claim `(index, player, amount)` belongs to the airdrop.
+/// Each claim is collected once, as recorded on the airdrop's claimed bitmap.
+///
+/// Steps:
+/// 1. Verify the merkle proof of the claim against the airdrop's root.
+/// 2. Mark the claim as collected on the bitmap, rejecting double claims.
+/// 3. Transfer the claimed tokens from the airdrop's vault to the player's token account.
+/// 4. Emit a `ClaimMerkleAirdrop` event to record the claim on-chain.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimMerkleAirdrop<'info> {
+    /// The player claiming their share. Must sign the transaction.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The global `Game` account, tracking the event nonce.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The merkle airdrop claimed from.
+    #[account(
+        mut,
+        seeds = [MERKLE_AIRDROP_SEED, merkle_airdrop.airdrop_id.to_le_bytes().as_ref()],
+        bump = merkle_airdrop.bump,
+        has_one = airdrop_vault,
+    )]
+    pub merkle_airdrop: Box<Account<'info, MerkleAirdrop>>,
+
+    /// The bitmap recording the airdrop's collected claims.
+    #[account(
+        mut,
+        seeds = [MERKLE_AIRDROP_BITMAP_SEED, merkle_airdrop.key().as_ref()],
+        bump = merkle_airdrop_bitmap.bump,
+        has_one = merkle_airdrop,
+    )]
+    pub merkle_airdrop_bitmap: Box<Account<'info, MerkleAirdropBitmap>>,
+
+    /// The vault holding the airdrop's tokens.
+    #[account(mut)]
+    pub airdrop_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's associated token account receiving the claimed tokens, created if needed.
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program
+    )]
+    pub player_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The main token mint account.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program for the transfer.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program for creating the player's token account.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program, required to create the player's token account.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `ClaimMerkleAirdrop` instruction, see `ClaimMerkleAirdrop`.
+///
+/// # Arguments
+/// - `index`: The leaf index of the claim in the airdrop's merkle tree.
+/// - `amount`: The tokens of the claim.
+/// - `proof`: The sibling hashes from the claim's leaf up to the root.
+pub fn claim_merkle_airdrop(
+    ctx: Context<ClaimMerkleAirdrop>,
+    index: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let ClaimMerkleAirdrop {
+        player,
+        game,
+        merkle_airdrop,
+        merkle_airdrop_bitmap,
+        airdrop_vault,
+        player_token_account,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    // Verify the claim belongs to the airdrop
+    let leaf = merkle_airdrop_leaf(index, &player.key(), amount);
+    require!(
+        verify_merkle_proof(&proof, &merkle_airdrop.merkle_root, leaf),
+        ErrorCode::InvalidMerkleProof
+    );
+
+    // Collect the claim once, within the airdrop's total
+    merkle_airdrop_bitmap.set_claimed(index)?;
+    merkle_airdrop.record_claim(amount)?;
+
+    transfer_from_vault(
+        merkle_airdrop,
+        merkle_airdrop.bump,
+        airdrop_vault,
+        player_token_account,
+        token_mint,
+        token_program,
+        amount,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the claim
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::ClaimMerkleAirdrop as u16,
+        event_type: EventType::ClaimMerkleAirdrop,
+        event_nonce: game.event_nonce,
+        data: EventData::ClaimMerkleAirdrop {
+            merkle_airdrop: merkle_airdrop.key(),
+            player: player.key(),
+            index,
+            amount,
+            claimed_amount: merkle_airdrop.claimed_amount,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PREDICTION_MARKET_SEED, PREDICTION_SEED, VOUCHER_MINT_SEED,
+    VOUCHER_SEED,
+};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    refreeze_voucher_account, thaw_voucher_account_if_frozen, to_timestamp_u64, transfer_from_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `ClaimPrediction` instruction lets a predictor collect the payout of their prediction once its market
+/// is settled: their stake's share of the pool if they predicted the winner, their stake back if nobody did,
+/// and nothing otherwise. A prediction is claimed once.
+///
+/// Steps:
+/// 1. Record the payout of the prediction on the market, marking it claimed.
+/// 2. Transfer the payout from the market's escrow vault to the predictor's voucher account.
+/// 3. Emit a `ClaimPrediction` event to record the claim on-chain.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimPrediction<'info> {
+    /// The predictor claiming their payout.
+    pub predictor: Signer<'info>,
+
+    /// The global `Game` account, tracking the event nonce.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The settled prediction market.
+    #[account(
+        mut,
+        seeds = [PREDICTION_MARKET_SEED, prediction_market.round.as_ref()],
+        bump = prediction_market.bump,
+        has_one = escrow_vault,
+    )]
+    pub prediction_market: Box<Account<'info, PredictionMarket>>,
+
+    /// The vault escrowing the market's vouchers.
+    #[account(mut)]
+    pub escrow_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The predictor's prediction.
+    #[account(
+        mut,
+        seeds = [PREDICTION_SEED, prediction_market.key().as_ref(), predictor.key().as_ref()],
+        bump = prediction.bump,
+    )]
+    pub prediction: Box<Account<'info, Prediction>>,
+
+    /// The predictor's voucher account receiving the payout.
+    #[account(
+        mut,
+        token::mint = voucher_mint,
+        token::authority = predictor,
+        token::token_program = token_program,
+    )]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global voucher account, the freeze authority of the voucher accounts.
+    #[account(seeds = [VOUCHER_SEED], bump)]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher mint account.
+    #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program for the transfer.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `ClaimPrediction` instruction, see `ClaimPrediction`.
+pub fn claim_prediction(ctx: Context<ClaimPrediction>) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let ClaimPrediction {
+        predictor,
+        game,
+        prediction_market,
+        escrow_vault,
+        prediction,
+        voucher_account,
+        voucher,
+        voucher_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    let payout = prediction_market.record_claim(prediction)?;
+
+    if payout > 0 {
+        // Thaw the voucher account while the payout moves into it if it is soulbound-frozen
+        let was_frozen = thaw_voucher_account_if_frozen(
+            voucher,
+            voucher_mint,
+            voucher_account,
+            token_program,
+            &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+        )?;
+        transfer_from_vault(
+            prediction_market,
+            prediction_market.bump,
+            escrow_vault,
+            voucher_account,
+            voucher_mint,
+            token_program,
+            payout,
+        )?;
+        refreeze_voucher_account(
+            voucher,
+            voucher_mint,
+            voucher_account,
+            token_program,
+            was_frozen,
+            &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+        )?;
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the claim
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::ClaimPrediction as u16,
+        event_type: EventType::ClaimPrediction,
+        event_nonce: game.event_nonce,
+        data: EventData::ClaimPrediction {
+            prediction_market: prediction_market.key(),
+            predictor: predictor.key(),
+            amount: prediction.amount,
+            payout,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: predictor.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
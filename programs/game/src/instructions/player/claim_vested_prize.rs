@@ -0,0 +1,121 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, PRIZE_VESTING_SEED};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_vault};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `ClaimVestedPrize` instruction lets the first-place winner of a round streaming its grand prize claim
+/// the part of the prize vested so far, from the round vault to their token account.
+///
+/// Steps:
+/// 1. Record the claim of the vested and unclaimed part of the prize on the vesting, the round and the player.
+/// 2. Transfer it from the round vault to the player's token account.
+/// 3. Emit a `ClaimVestedPrize` event to record the claim on-chain.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimVestedPrize<'info> {
+    /// The winner claiming their streamed prize. Must sign the transaction.
+    pub player: Signer<'info>,
+
+    /// The global `Game` account, tracking the event nonce.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The player's data account, referencing their token account and tracking their streamed prizes.
+    #[account(
+        mut,
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = token_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The round the prize was won in, holding the streamed prize in its vault.
+    #[account(mut, has_one = round_vault)]
+    pub round: Box<Account<'info, Round>>,
+
+    /// The vesting streaming the prize to the player.
+    #[account(
+        mut,
+        seeds = [PRIZE_VESTING_SEED, round.key().as_ref(), player.key().as_ref()],
+        bump = prize_vesting.bump,
+    )]
+    pub prize_vesting: Box<Account<'info, PrizeVesting>>,
+
+    /// The round vault token account holding the streamed prize.
+    #[account(mut)]
+    pub round_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's token account receiving the vested prize.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint representing the in-game currency.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token program used for the transfer.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `ClaimVestedPrize` instruction, see `ClaimVestedPrize`.
+pub fn claim_vested_prize(ctx: Context<ClaimVestedPrize>) -> Result<()> {
+    // Obtain the current UNIX timestamp for the vesting and event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let ClaimVestedPrize {
+        player,
+        game,
+        player_data,
+        round,
+        prize_vesting,
+        round_vault,
+        token_account,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    // Claim the vested part of the prize held in the round vault
+    let amount = prize_vesting.claim(timestamp)?;
+    round.claim_vested_grand_prizes(amount)?;
+    player_data.claim_vested_grand_prizes(amount)?;
+
+    transfer_from_vault(
+        round,
+        round.bump,
+        round_vault,
+        token_account,
+        token_mint,
+        token_program,
+        amount,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the claim
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::ClaimVestedPrize as u16,
+        event_type: EventType::ClaimVestedPrize,
+        event_nonce: game.event_nonce,
+        data: EventData::ClaimVestedPrize {
+            prize_vesting: prize_vesting.key(),
+            player: player.key(),
+            amount,
+            claimed_amount: prize_vesting.claimed_amount,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -1,17 +1,24 @@
 use crate::constants::{
-    EXCHANGE_COLLATERAL_RATE, GAME_SEED, PLAYER_DATA_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
+    EVENT_RING_BUFFER_SEED, EXCHANGE_CONFIG_SEED, GAME_SEED, PLAYER_DATA_SEED, VOUCHER_MINT_SEED,
+    VOUCHER_SEED,
 };
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
-use crate::utils::{calculate_proportion, to_timestamp_u64, transfer_from_player_to_vault};
+use crate::utils::{
+    calculate_proportion, refreeze_voucher_account, thaw_voucher_account_if_frozen,
+    timestamp_to_days, to_timestamp_u64, transfer_from_player_to_vault,
+};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, mint_to, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
+use switchboard_on_demand::accounts::PullFeedAccountData;
+use switchboard_on_demand::PRECISION;
 
-/// The `CollateralExchange` instruction allows a player to convert their tokens (FGC/FGV) into vouchers at a predefined exchange rate.
+/// The `CollateralExchange` instruction allows a player to convert their tokens (FGC/FGV) into vouchers at the exchange rate set in `ExchangeConfig`.
 /// This process integrates seamlessly with the voucher minting system, ensuring the player's assets are properly secured and represented.
 /// By performing this exchange, the player obtains vouchers proportional to their input tokens, fueling their ability to participate in further ecosystem activities.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CollateralExchange<'info> {
     /// The player initiating the collateral exchange. Must sign the transaction.
@@ -31,13 +38,22 @@ pub struct CollateralExchange<'info> {
     )]
     pub player_data: Box<Account<'info, PlayerData>>,
 
+    /// The exchange config account, holding the exchange rate and limits.
+    #[account(mut, seeds = [EXCHANGE_CONFIG_SEED], bump)]
+    pub exchange_config: Box<Account<'info, ExchangeConfig>>,
+
+    /// CHECK: The Switchboard pull feed quoting the collateral in vouchers.
+    /// Required when the exchange is oracle-priced and verified against the configured feed.
+    #[account(address = exchange_config.price_feed @ ErrorCode::PriceFeedMismatch)]
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
     /// The player's token account from which tokens are deducted for the exchange.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The player's voucher account where newly minted vouchers will be deposited.
     #[account(mut)]
-    pub voucher_account: Box<Account<'info, TokenAccount>>,
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The global voucher state account, tracking mint authority and total issuance.
     #[account(
@@ -50,25 +66,33 @@ pub struct CollateralExchange<'info> {
 
     /// The voucher vault account holding the underlying tokens supporting the voucher supply.
     #[account(mut)]
-    pub voucher_vault: Box<Account<'info, TokenAccount>>,
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The voucher mint account, used to mint voucher tokens into the player's voucher account.
     #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
-    pub voucher_mint: Box<Account<'info, Mint>>,
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     /// The SPL Token program used for token operations such as `mint_to`.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Processes the collateral exchange logic:
 ///
 /// Steps:
 /// 1. Verify that the player holds sufficient tokens in `token_account`.
-/// 2. Calculate the number of vouchers to mint based on `EXCHANGE_COLLATERAL_RATE`.
-/// 3. Mint the corresponding voucher tokens to the player's `voucher_account`.
-/// 4. Transfer the exchanged tokens from the player's `token_account` to the `voucher_vault`.
-/// 5. Emit a `CollateralExchange` event to record the operation on-chain.
+/// 2. Enforce the player's daily exchange limit and the global exchange cap.
+/// 3. Calculate the number of vouchers to mint based on the oracle price within the configured bounds,
+///    falling back to the fixed exchange rate if the price is stale or not confident enough.
+/// 4. Mint the corresponding voucher tokens to the player's `voucher_account`.
+/// 5. Transfer the exchanged tokens from the player's `token_account` to the `voucher_vault`.
+/// 6. Emit a `CollateralExchange` event to record the operation on-chain.
 pub fn collateral_exchange(
     ctx: Context<CollateralExchange>,
     exchange_token_amount: u64,
@@ -79,8 +103,12 @@ pub fn collateral_exchange(
 
     // Extract references to key accounts for clarity
     let CollateralExchange {
+        token_mint,
         game,
         player,
+        player_data,
+        exchange_config,
+        price_feed,
         voucher,
         voucher_account,
         voucher_mint,
@@ -96,21 +124,58 @@ pub fn collateral_exchange(
         ErrorCode::InsufficientFundsToPayFee
     );
 
-    // Calculate how many vouchers will be minted based on the provided exchange rate
-    let voucher_amount = calculate_proportion(exchange_token_amount, EXCHANGE_COLLATERAL_RATE)?;
+    // Enforce the daily per-player limit and the global cap
+    let current_day = timestamp_to_days(timestamp)?;
+    player_data.record_exchange(
+        current_day,
+        exchange_token_amount,
+        exchange_config.daily_player_limit,
+    )?;
+    exchange_config.record_exchange(exchange_token_amount)?;
+
+    // Derive the exchange rate from the oracle price, falling back to the fixed rate
+    let oracle_exchange_rate = if exchange_config.is_oracle_priced() {
+        let price_feed = price_feed.as_ref().ok_or(ErrorCode::PriceFeedRequired)?;
+        let feed = PullFeedAccountData::parse(price_feed.data.borrow())
+            .map_err(|_| ErrorCode::InvalidPriceFeed)?;
+        exchange_config.oracle_exchange_rate(
+            feed.result.value,
+            feed.result.std_dev,
+            PRECISION,
+            feed.result.slot,
+            clock.slot,
+        )
+    } else {
+        None
+    };
+    let is_oracle_priced = oracle_exchange_rate.is_some();
+    let exchange_rate = oracle_exchange_rate.unwrap_or(exchange_config.exchange_rate);
+
+    // Calculate how many vouchers will be minted based on the exchange rate
+    let voucher_amount = calculate_proportion(exchange_token_amount, exchange_rate)?;
 
     // Update voucher state to reflect newly minted vouchers
-    voucher.mint(exchange_token_amount)?;
+    voucher.mint(voucher_amount)?;
 
     // Transfer the exchanged tokens from the player's token account to the voucher vault
     transfer_from_player_to_vault(
         player,
         token_account,
         voucher_vault,
+        token_mint,
         token_program,
         exchange_token_amount,
     )?;
 
+    // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
     // Mint voucher tokens into the player's voucher account
     mint_to(
         CpiContext::new_with_signer(
@@ -124,6 +189,14 @@ pub fn collateral_exchange(
         ),
         voucher_amount,
     )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
 
     msg!(
         "Collateral exchange: {} tokens in exchange for {} vouchers.",
@@ -134,7 +207,9 @@ pub fn collateral_exchange(
     game.increment_event_nonce()?;
 
     // Emit an event to note that collateral exchange took place
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CollateralExchange as u16,
         event_type: EventType::CollateralExchange,
         event_nonce: game.event_nonce,
         data: EventData::CollateralExchange {
@@ -142,11 +217,14 @@ pub fn collateral_exchange(
             voucher: voucher.key(),
             exchange_token_amount,
             voucher_amount,
+            exchange_rate,
+            is_oracle_priced,
         },
         initiator_type: InitiatorType::VOUCHER,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
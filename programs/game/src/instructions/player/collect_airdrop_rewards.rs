@@ -1,36 +1,33 @@
 use crate::constants::{
-    GAME_SEED, LAMPORTS_PER_TOKEN, PLAYER_DATA_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
+    AIRDROP_CAMPAIGN_SEED, AIRDROP_CLAIM_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED,
+    VOUCHER_MINT_SEED, VOUCHER_SEED,
 };
-use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::{
-    timestamp_to_days, to_timestamp_u64, transfer_from_token_vault_to_token_account,
+    refreeze_voucher_account, thaw_voucher_account_if_frozen, timestamp_to_days, to_timestamp_u64,
+    transfer_from_vault,
 };
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
-use anchor_spl::token::{self, mint_to, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
-/// The `CollectAirdropRewards` instruction allows players to claim their daily airdrop reward,
-/// provided they meet certain criteria such as having made a purchase on the current day and not having claimed already.
-/// This mechanism encourages regular participation and continuous engagement.
+/// The `CollectAirdropRewards` instruction allows players to claim their daily airdrop reward from an `AirdropCampaign`,
+/// provided they meet the campaign's eligibility rule, such as having made a purchase on the current day, and have not claimed
+/// from it already today. This mechanism encourages regular participation and continuous engagement.
 ///
 /// Steps:
-/// 1. Verify that the player has not already collected airdrop rewards today and has made a purchase on the current day.
-/// 2. Determine the airdrop reward amount based on the player's consecutive purchase streak.
-/// 3. Ensure that the game's daily airdrop cap and pool balances can cover this reward.
-/// 4. Mint the corresponding voucher tokens and transfer their underlying assets from the game vault to the player's voucher account.
+/// 1. Verify that the campaign is active, the player has not already collected its rewards today and meets its eligibility rule.
+/// 2. Determine the airdrop reward amount under the campaign's rule, e.g. based on the player's consecutive purchase streak.
+/// 3. Ensure that the campaign's daily cap and remaining budget can cover this reward.
+/// 4. Mint the corresponding voucher tokens and transfer their underlying assets from the campaign vault to the voucher vault.
 /// 5. Emit a `CollectAirdropReward` event to record the action on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CollectAirdropRewards<'info> {
-    /// The global `Game` account managing rewards, daily caps, and the current day's state.
-    #[account(
-        mut,
-        seeds = [GAME_SEED],
-        bump,
-        has_one = game_vault
-    )]
+    /// The global `Game` account, tracking the distributed airdrop rewards.
+    #[account(mut, seeds = [GAME_SEED], bump)]
     pub game: Box<Account<'info, Game>>,
 
     /// The global `Voucher` account, managing voucher mint authority and overall distribution.
@@ -44,15 +41,34 @@ pub struct CollectAirdropRewards<'info> {
 
     /// The voucher mint account used to mint vouchers to players.
     #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
-    pub voucher_mint: Box<Account<'info, Mint>>,
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The voucher vault token account holding assets that back voucher issuance.
     #[account(mut)]
-    pub voucher_vault: Box<Account<'info, TokenAccount>>,
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The airdrop campaign the rewards are collected from, with its budget, daily cap and eligibility rule.
+    #[account(
+        mut,
+        seeds = [AIRDROP_CAMPAIGN_SEED, airdrop_campaign.campaign_id.to_le_bytes().as_ref()],
+        bump = airdrop_campaign.bump,
+        has_one = campaign_vault,
+    )]
+    pub airdrop_campaign: Box<Account<'info, AirdropCampaign>>,
 
-    /// The game vault token account holding the game's assets, from which airdrop rewards originate.
+    /// The vault holding the campaign's budget, from which the airdrop rewards originate.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub campaign_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's claim record on the campaign, limiting their claims to one a day. Created on the first claim.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + AirdropClaim::INIT_SPACE,
+        seeds = [AIRDROP_CLAIM_SEED, airdrop_campaign.key().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub airdrop_claim: Box<Account<'info, AirdropClaim>>,
 
     /// The player claiming the airdrop rewards. Must sign the transaction.
     #[account(mut)]
@@ -68,11 +84,20 @@ pub struct CollectAirdropRewards<'info> {
 
     /// The player's voucher token account, where newly minted vouchers are deposited.
     #[account(mut)]
-    pub voucher_account: Box<Account<'info, TokenAccount>>,
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     /// The SPL token program used for token-related instructions (minting, transferring).
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program, required to create the claim record.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 pub fn collect_airdrop_rewards(ctx: Context<CollectAirdropRewards>) -> Result<()> {
@@ -81,10 +106,13 @@ pub fn collect_airdrop_rewards(ctx: Context<CollectAirdropRewards>) -> Result<()
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
     let CollectAirdropRewards {
+        token_mint,
         player,
         player_data,
         game,
-        game_vault,
+        airdrop_campaign,
+        campaign_vault,
+        airdrop_claim,
         voucher,
         voucher_vault,
         voucher_mint,
@@ -96,72 +124,53 @@ pub fn collect_airdrop_rewards(ctx: Context<CollectAirdropRewards>) -> Result<()
     // Convert current timestamp to a day index
     let current_day = timestamp_to_days(timestamp)?;
 
-    // Ensure the player has not already claimed airdrop rewards today
-    require!(
-        player_data.last_collected_airdrop_reward_day != current_day,
-        ErrorCode::AirdropRewardsAlreadyCollected
-    );
-
-    // Ensure the player made a purchase today, making them eligible for the airdrop
-    require!(
-        player_data.last_purchased_day == current_day,
-        ErrorCode::AirdropRewardsNotAvailable
-    );
-
-    // Determine airdrop rewards based on consecutive purchase streak
-    let airdrop_rewards = match player_data.consecutive_purchased_days {
-        1 => 100 * LAMPORTS_PER_TOKEN,
-        2 => 200 * LAMPORTS_PER_TOKEN,
-        3 => 300 * LAMPORTS_PER_TOKEN,
-        4 => 400 * LAMPORTS_PER_TOKEN,
-        5 => 500 * LAMPORTS_PER_TOKEN,
-        _ => 1000 * LAMPORTS_PER_TOKEN,
-    };
+    // Determine the airdrop rewards under the campaign's eligibility rule, once a day
+    let airdrop_rewards = airdrop_campaign.claimable_rewards(
+        player_data,
+        airdrop_claim.last_claimed_day,
+        current_day,
+        timestamp,
+    )?;
+
+    // Record the claim within the campaign's daily cap and budget
+    airdrop_campaign.record_claim(airdrop_rewards, current_day)?;
+    airdrop_claim.record_claim(
+        airdrop_campaign.key(),
+        player.key(),
+        airdrop_rewards,
+        current_day,
+        ctx.bumps.airdrop_claim,
+    )?;
 
     // Update the player's collected rewards and last collected day
     player_data.collected_airdrop_rewards = player_data
         .collected_airdrop_rewards
         .safe_add(airdrop_rewards)?;
     player_data.last_collected_airdrop_reward_day = current_day;
-
-    // If a new day has started, reset the game's current day and daily distributed amount
-    if current_day > game.current_day {
-        game.current_day = current_day;
-        game.current_day_distributed_airdrop_rewards = 0;
-    }
-
-    // Ensure we do not exceed the daily airdrop cap
-    let new_daily_total = game
-        .current_day_distributed_airdrop_rewards
-        .safe_add(airdrop_rewards)?;
-    require!(
-        game.current_day_cap_airdrop_rewards >= new_daily_total,
-        ErrorCode::ExceedsDailyAirdropCap
-    );
-    game.current_day_distributed_airdrop_rewards = new_daily_total;
-
-    // Ensure there are enough tokens in the airdrop pool
-    require!(
-        game.airdrop_rewards_pool_balance >= airdrop_rewards,
-        ErrorCode::InsufficientAirdropRewardBalance
-    );
-    game.airdrop_rewards_pool_balance = game
-        .airdrop_rewards_pool_balance
-        .safe_sub(airdrop_rewards)?;
     game.distributed_airdrop_rewards =
         game.distributed_airdrop_rewards.safe_add(airdrop_rewards)?;
 
     // Mint vouchers corresponding to the airdrop rewards
     voucher.mint(airdrop_rewards)?;
 
-    // Transfer the underlying tokens from the game vault to the voucher vault, backing the newly minted vouchers
-    transfer_from_token_vault_to_token_account(
-        game,
-        game_vault,
+    // Transfer the underlying tokens from the campaign vault to the voucher vault, backing the newly minted vouchers
+    transfer_from_vault(
+        airdrop_campaign,
+        airdrop_campaign.bump,
+        campaign_vault,
         voucher_vault,
+        token_mint,
         token_program,
         airdrop_rewards,
-        &[GAME_SEED, &[ctx.bumps.game]],
+    )?;
+
+    // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
     )?;
 
     // Mint vouchers into the player's voucher account
@@ -177,13 +186,23 @@ pub fn collect_airdrop_rewards(ctx: Context<CollectAirdropRewards>) -> Result<()
         ),
         airdrop_rewards,
     )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
 
     msg!("Airdrop rewards: {}", airdrop_rewards);
 
     game.increment_event_nonce()?;
 
     // Emit an event recording the airdrop claim action
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CollectAirdropReward as u16,
         event_type: EventType::CollectAirdropReward,
         event_nonce: game.event_nonce,
         data: EventData::CollectAirdropReward {
@@ -191,11 +210,13 @@ pub fn collect_airdrop_rewards(ctx: Context<CollectAirdropRewards>) -> Result<()
             player: player.key(),
             airdrop_rewards,
             voucher: voucher.key(),
+            airdrop_campaign: airdrop_campaign.key(),
         },
         initiator_type: InitiatorType::PLAYER,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
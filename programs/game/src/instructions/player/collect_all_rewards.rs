@@ -0,0 +1,369 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, PLAYER_DATA_SEED, VOUCHER_MINT_SEED,
+    VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    calculate_proportion, refreeze_voucher_account, thaw_voucher_account_if_frozen,
+    timestamp_to_days, to_timestamp_u64, transfer_from_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `CollectAllRewards` instruction lets a player claim their referral, consumption and airdrop rewards
+/// in a single transaction, instead of one `collect_*_rewards` instruction each.
+/// Categories with nothing to collect are skipped; the airdrop rewards are only collected when an airdrop campaign
+/// is passed and the player may collect its rewards today. The player's first claim on a campaign goes through
+/// `collect_airdrop_rewards`, which creates their claim record.
+///
+/// Steps:
+/// 1. Determine the referral and consumption rewards pending and, if a campaign is passed, the airdrop rewards
+///    the player may collect from it today. At least one must be non-zero.
+/// 2. Apply the same pool balance, campaign budget and daily airdrop cap checks as the individual collect instructions.
+/// 3. Pay the referral rewards in the asset chosen by the player, as `collect_referral_rewards` does.
+/// 4. Mint all voucher payouts to the player's voucher account at once, transferring their backing
+///    from the game vault and the campaign vault to the voucher vault.
+/// 5. Emit a single `CollectAllRewards` event recording every category collected.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CollectAllRewards<'info> {
+    /// The global game account holding the reward pools.
+    #[account(
+        mut,
+        seeds = [GAME_SEED],
+        bump,
+        has_one = game_vault
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game config holding the bonus rate of referral voucher payouts.
+    #[account(seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// The global `Voucher` account, managing voucher mint authority and overall distribution.
+    #[account(
+        mut,
+        seeds = [VOUCHER_SEED],
+        bump,
+        has_one = voucher_vault,
+    )]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher mint account used to mint vouchers to players.
+    #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The voucher vault token account holding assets that back voucher issuance.
+    #[account(mut)]
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The game vault token account from where the underlying tokens are sourced.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player collecting their rewards. Must sign the transaction.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The player's data account, tracking pending and collected rewards and the purchase streak.
+    #[account(mut,
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = token_account,
+        has_one = voucher_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The player's token account, receiving referral rewards paid in tokens.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's voucher token account, receiving voucher payouts.
+    #[account(mut)]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The SPL token program, facilitating minting and transfer operations.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The airdrop campaign to collect today's airdrop rewards from. Passed together with the two
+    /// airdrop accounts below, or not at all.
+    #[account(mut)]
+    pub airdrop_campaign: Option<Box<Account<'info, AirdropCampaign>>>,
+
+    /// The vault holding the campaign's budget.
+    #[account(mut)]
+    pub campaign_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The player's claim record on the campaign.
+    #[account(mut)]
+    pub airdrop_claim: Option<Box<Account<'info, AirdropClaim>>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+pub fn collect_all_rewards(
+    ctx: Context<CollectAllRewards>,
+    referral_payout: ReferralPayout,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp to record when the rewards were claimed
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let current_day = timestamp_to_days(timestamp)?;
+
+    // Extract references for clarity
+    let CollectAllRewards {
+        token_mint,
+        player,
+        player_data,
+        game,
+        game_config,
+        voucher,
+        voucher_mint,
+        voucher_vault,
+        game_vault,
+        token_account,
+        voucher_account,
+        token_program,
+        airdrop_campaign,
+        campaign_vault,
+        airdrop_claim,
+        ..
+    } = ctx.accounts;
+
+    let referral_rewards = player_data.collectable_referral_rewards;
+    let consumption_rewards = player_data.collectable_consumption_rewards;
+
+    // The airdrop rewards of the campaign passed, if the player may collect them today
+    let airdrop = match (
+        airdrop_campaign.as_deref_mut(),
+        campaign_vault.as_deref(),
+        airdrop_claim.as_deref_mut(),
+    ) {
+        (None, None, None) => None,
+        (Some(airdrop_campaign), Some(campaign_vault), Some(airdrop_claim)) => {
+            require_keys_eq!(
+                campaign_vault.key(),
+                airdrop_campaign.campaign_vault,
+                ErrorCode::InvalidAirdropCampaignAccounts
+            );
+            require!(
+                airdrop_claim.airdrop_campaign == airdrop_campaign.key()
+                    && airdrop_claim.player == player.key(),
+                ErrorCode::InvalidAirdropCampaignAccounts
+            );
+            Some((airdrop_campaign, campaign_vault, airdrop_claim))
+        }
+        _ => return err!(ErrorCode::InvalidAirdropCampaignAccounts),
+    };
+    let airdrop_rewards = match &airdrop {
+        Some((airdrop_campaign, _, airdrop_claim)) => airdrop_campaign
+            .claimable_rewards(
+                player_data,
+                airdrop_claim.last_claimed_day,
+                current_day,
+                timestamp,
+            )
+            .unwrap_or(0),
+        None => 0,
+    };
+
+    require!(
+        referral_rewards > 0 || consumption_rewards > 0 || airdrop_rewards > 0,
+        ErrorCode::NoRewardsToCollect
+    );
+
+    // Referral rewards
+    let mut voucher_bonus = 0;
+    let mut voucher_amount = 0;
+    if referral_rewards > 0 {
+        player_data.collect_referral_rewards(referral_rewards)?;
+
+        require!(
+            game.referral_rewards_pool_balance >= referral_rewards,
+            ErrorCode::InsufficientReferrerRewardBalance
+        );
+        game.referral_rewards_pool_balance = game
+            .referral_rewards_pool_balance
+            .safe_sub(referral_rewards)?;
+        game.distributed_referral_rewards = game
+            .distributed_referral_rewards
+            .safe_add(referral_rewards)?;
+
+        match referral_payout {
+            ReferralPayout::Token => {
+                // Transfer the rewards from the game vault to the player's token account
+                transfer_from_vault(
+                    game,
+                    ctx.bumps.game,
+                    game_vault,
+                    token_account,
+                    token_mint,
+                    token_program,
+                    referral_rewards,
+                )?;
+            }
+            ReferralPayout::Voucher => {
+                // Add the voucher bonus from the bonus pool, as far as it covers it
+                voucher_bonus = calculate_proportion(
+                    referral_rewards,
+                    game_config.referral_voucher_bonus_rate,
+                )?
+                .min(game.bonus_rewards_pool_balance);
+                game.bonus_rewards_pool_balance =
+                    game.bonus_rewards_pool_balance.safe_sub(voucher_bonus)?;
+                game.distributed_bonus_rewards =
+                    game.distributed_bonus_rewards.safe_add(voucher_bonus)?;
+
+                voucher_amount = referral_rewards.safe_add(voucher_bonus)?;
+            }
+        }
+    }
+
+    // Consumption rewards
+    if consumption_rewards > 0 {
+        player_data.collected_consumption_rewards = player_data
+            .collected_consumption_rewards
+            .safe_add(consumption_rewards)?;
+        player_data.collectable_consumption_rewards = 0;
+
+        require!(
+            consumption_rewards <= game.consumption_rewards_pool_balance,
+            ErrorCode::InsufficientConsumptionRewardBalance
+        );
+        game.consumption_rewards_pool_balance = game
+            .consumption_rewards_pool_balance
+            .safe_sub(consumption_rewards)?;
+        game.distributed_consumption_rewards = game
+            .distributed_consumption_rewards
+            .safe_add(consumption_rewards)?;
+
+        voucher_amount = voucher_amount.safe_add(consumption_rewards)?;
+    }
+
+    // Airdrop rewards, backed by the campaign vault
+    if let Some((airdrop_campaign, campaign_vault, airdrop_claim)) = airdrop {
+        if airdrop_rewards > 0 {
+            let claim_bump = airdrop_claim.bump;
+            airdrop_campaign.record_claim(airdrop_rewards, current_day)?;
+            airdrop_claim.record_claim(
+                airdrop_campaign.key(),
+                player.key(),
+                airdrop_rewards,
+                current_day,
+                claim_bump,
+            )?;
+
+            player_data.collected_airdrop_rewards = player_data
+                .collected_airdrop_rewards
+                .safe_add(airdrop_rewards)?;
+            player_data.last_collected_airdrop_reward_day = current_day;
+            game.distributed_airdrop_rewards =
+                game.distributed_airdrop_rewards.safe_add(airdrop_rewards)?;
+
+            // Transfer the underlying tokens from the campaign vault to the voucher vault
+            transfer_from_vault(
+                airdrop_campaign,
+                airdrop_campaign.bump,
+                campaign_vault,
+                voucher_vault,
+                token_mint,
+                token_program,
+                airdrop_rewards,
+            )?;
+        }
+    }
+
+    // Transfer the underlying tokens from the game vault to the voucher vault, backing the newly minted vouchers
+    if voucher_amount > 0 {
+        transfer_from_vault(
+            game,
+            ctx.bumps.game,
+            game_vault,
+            voucher_vault,
+            token_mint,
+            token_program,
+            voucher_amount,
+        )?;
+    }
+
+    // Mint every voucher payout at once
+    let voucher_amount = voucher_amount.safe_add(airdrop_rewards)?;
+    if voucher_amount > 0 {
+        voucher.mint(voucher_amount)?;
+
+        // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+        let was_frozen = thaw_voucher_account_if_frozen(
+            voucher,
+            voucher_mint,
+            voucher_account,
+            token_program,
+            &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+        )?;
+
+        mint_to(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                MintTo {
+                    mint: voucher_mint.to_account_info(),
+                    to: voucher_account.to_account_info(),
+                    authority: voucher.to_account_info(),
+                },
+                &[&[VOUCHER_SEED, &[ctx.bumps.voucher]]],
+            ),
+            voucher_amount,
+        )?;
+        refreeze_voucher_account(
+            voucher,
+            voucher_mint,
+            voucher_account,
+            token_program,
+            was_frozen,
+            &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+        )?;
+    }
+
+    msg!(
+        "Referral rewards: {}, voucher bonus: {}, consumption rewards: {}, airdrop rewards: {}",
+        referral_rewards,
+        voucher_bonus,
+        consumption_rewards,
+        airdrop_rewards
+    );
+
+    game.increment_event_nonce()?;
+
+    // Emit a single event logging every category collected
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CollectAllRewards as u16,
+        event_type: EventType::CollectAllRewards,
+        event_nonce: game.event_nonce,
+        data: EventData::CollectAllRewards {
+            game: game.key(),
+            player: player.key(),
+            referral_rewards,
+            referral_payout,
+            voucher_bonus,
+            consumption_rewards,
+            airdrop_rewards,
+            voucher: voucher.key(),
+            airdrop_campaign: airdrop_campaign
+                .as_ref()
+                .map(|airdrop_campaign| airdrop_campaign.key()),
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
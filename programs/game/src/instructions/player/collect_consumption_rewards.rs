@@ -1,11 +1,15 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED};
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
+};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
-use crate::utils::{to_timestamp_u64, transfer_from_token_vault_to_token_account};
+use crate::utils::{
+    refreeze_voucher_account, thaw_voucher_account_if_frozen, to_timestamp_u64, transfer_from_vault,
+};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
-use anchor_spl::token::{self, mint_to, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
 /// The `CollectConsumptionRewards` instruction enables a player to claim their accumulated consumption rewards.
@@ -17,6 +21,7 @@ use solana_program::sysvar::clock::Clock;
 /// 3. Update the player's and game's state, adjusting pool balances and distributed totals.
 /// 4. Mint voucher tokens to the player's voucher account, backed by transferring the corresponding assets from the `game_vault` to the `voucher_vault`.
 /// 5. Emit a `CollectConsumptionRewards` event to record the reward claim on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CollectConsumptionRewards<'info> {
     /// The player who is collecting their consumption rewards. Must sign the transaction.
@@ -34,7 +39,7 @@ pub struct CollectConsumptionRewards<'info> {
 
     /// The player's voucher token account where newly minted vouchers representing the claimed rewards will be deposited.
     #[account(mut)]
-    pub voucher_account: Box<Account<'info, TokenAccount>>,
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The global `Game` account, maintaining consumption reward pools and other economic parameters.
     #[account(
@@ -56,19 +61,25 @@ pub struct CollectConsumptionRewards<'info> {
 
     /// The voucher mint account used to create new voucher tokens.
     #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
-    pub voucher_mint: Box<Account<'info, Mint>>,
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The voucher vault holding the underlying assets that back the voucher tokens.
     #[account(mut)]
-    pub voucher_vault: Box<Account<'info, TokenAccount>>,
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The game vault token account from which the underlying assets are transferred to the voucher vault.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     /// The SPL token program used for token operations (minting, transferring).
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 pub fn collect_consumption_rewards(ctx: Context<CollectConsumptionRewards>) -> Result<()> {
@@ -77,6 +88,7 @@ pub fn collect_consumption_rewards(ctx: Context<CollectConsumptionRewards>) -> R
 
     // Extract references for clarity
     let CollectConsumptionRewards {
+        token_mint,
         player,
         player_data,
         game,
@@ -115,13 +127,23 @@ pub fn collect_consumption_rewards(ctx: Context<CollectConsumptionRewards>) -> R
     voucher.mint(consumption_rewards)?;
 
     // Transfer the underlying assets from the game vault to the voucher vault
-    transfer_from_token_vault_to_token_account(
+    transfer_from_vault(
         game,
+        ctx.bumps.game,
         game_vault,
         voucher_vault,
+        token_mint,
         token_program,
         consumption_rewards,
-        &[GAME_SEED, &[ctx.bumps.game]],
+    )?;
+
+    // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
     )?;
 
     mint_to(
@@ -136,13 +158,23 @@ pub fn collect_consumption_rewards(ctx: Context<CollectConsumptionRewards>) -> R
         ),
         consumption_rewards,
     )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
 
     msg!("Consumption rewards: {}", consumption_rewards);
 
     game.increment_event_nonce()?;
 
     // Emit an event to record the reward collection
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CollectConsumptionRewards as u16,
         event_type: EventType::CollectConsumptionRewards,
         event_nonce: game.event_nonce,
         data: EventData::CollectConsumptionRewards {
@@ -154,7 +186,8 @@ pub fn collect_consumption_rewards(ctx: Context<CollectConsumptionRewards>) -> R
         initiator_type: InitiatorType::PLAYER,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
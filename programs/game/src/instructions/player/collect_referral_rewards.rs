@@ -1,11 +1,17 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, PLAYER_DATA_SEED, VOUCHER_MINT_SEED,
+    VOUCHER_SEED,
+};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
-use crate::utils::{to_timestamp_u64, transfer_from_token_vault_to_token_account};
+use crate::utils::{
+    calculate_proportion, refreeze_voucher_account, thaw_voucher_account_if_frozen,
+    to_timestamp_u64, transfer_from_vault,
+};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
-use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
 /// The `CollectReferralRewards` instruction allows players to claim referral rewards they have accumulated through inviting other participants.
@@ -15,8 +21,13 @@ use solana_program::sysvar::clock::Clock;
 /// 1. Ensure the player has pending referral rewards available to collect.
 /// 2. Verify that the game's referral reward pool can cover the requested amount.
 /// 3. Update the player's and game's record of distributed referral rewards.
-/// 4. Mint corresponding voucher tokens to the player's voucher account and transfer the underlying assets from the game vault to the voucher vault.
+/// 4. Pay the rewards in the asset chosen by the player:
+///    - `Token`: transfer the rewards from the game vault to the player's token account.
+///    - `Voucher`: add the game config's `referral_voucher_bonus_rate` from the bonus pool, as far as it covers it,
+///      mint the corresponding voucher tokens to the player's voucher account and transfer the underlying assets
+///      from the game vault to the voucher vault.
 /// 5. Emit a `CollectReferralReward` event to record the referral reward claim on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CollectReferralRewards<'info> {
     /// The global game account holding reward pools and distribution logic.
@@ -28,9 +39,30 @@ pub struct CollectReferralRewards<'info> {
     )]
     pub game: Box<Account<'info, Game>>,
 
+    /// The game config holding the bonus rate of voucher payouts.
+    #[account(seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// The global `Voucher` account, managing voucher mint authority and overall distribution.
+    #[account(
+        mut,
+        seeds = [VOUCHER_SEED],
+        bump,
+        has_one = voucher_vault,
+    )]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher mint account used to mint vouchers to players.
+    #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The voucher vault token account holding assets that back voucher issuance.
+    #[account(mut)]
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
     /// The game vault token account from where the underlying tokens are sourced.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The player claiming referral rewards. Must sign the transaction.
     #[account(mut)]
@@ -40,31 +72,51 @@ pub struct CollectReferralRewards<'info> {
     #[account(mut,
         seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
         bump,
-        has_one = token_account
+        has_one = token_account,
+        has_one = voucher_account,
     )]
     pub player_data: Box<Account<'info, PlayerData>>,
 
-    /// The player's token account, holding the underlying tokens to be transferred.
+    /// The player's token account, receiving token payouts.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's voucher token account, receiving voucher payouts.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     /// The SPL token program, facilitating minting and transfer operations.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
-pub fn collect_referral_rewards(ctx: Context<CollectReferralRewards>) -> Result<()> {
+pub fn collect_referral_rewards(
+    ctx: Context<CollectReferralRewards>,
+    payout: ReferralPayout,
+) -> Result<()> {
     // Obtain the current UNIX timestamp to record when the referral rewards were claimed
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
     // Extract references for clarity
     let CollectReferralRewards {
+        token_mint,
         player,
         player_data,
         game,
+        game_config,
+        voucher,
+        voucher_mint,
+        voucher_vault,
         game_vault,
         token_account,
+        voucher_account,
         token_program,
         ..
     } = ctx.accounts;
@@ -74,10 +126,7 @@ pub fn collect_referral_rewards(ctx: Context<CollectReferralRewards>) -> Result<
     require!(referral_rewards > 0, ErrorCode::NoRewardsToCollect);
 
     // Update player's collected and pending referral rewards
-    player_data.collected_referral_rewards = player_data
-        .collected_referral_rewards
-        .safe_add(referral_rewards)?;
-    player_data.collectable_referral_rewards = 0;
+    player_data.collect_referral_rewards(referral_rewards)?;
 
     // Check that the game's referral reward pool has sufficient funds
     require!(
@@ -91,33 +140,106 @@ pub fn collect_referral_rewards(ctx: Context<CollectReferralRewards>) -> Result<
         .distributed_referral_rewards
         .safe_add(referral_rewards)?;
 
-    // Transfer the underlying tokens from the game vault to the voucher vault, backing the newly issued vouchers
-    transfer_from_token_vault_to_token_account(
-        game,
-        &game_vault,
-        &token_account,
-        &token_program,
+    let voucher_bonus = match payout {
+        ReferralPayout::Token => {
+            // Transfer the rewards from the game vault to the player's token account
+            transfer_from_vault(
+                game,
+                ctx.bumps.game,
+                game_vault,
+                token_account,
+                token_mint,
+                token_program,
+                referral_rewards,
+            )?;
+
+            0
+        }
+        ReferralPayout::Voucher => {
+            // Add the voucher bonus from the bonus pool, as far as it covers it
+            let voucher_bonus =
+                calculate_proportion(referral_rewards, game_config.referral_voucher_bonus_rate)?
+                    .min(game.bonus_rewards_pool_balance);
+            game.bonus_rewards_pool_balance =
+                game.bonus_rewards_pool_balance.safe_sub(voucher_bonus)?;
+            game.distributed_bonus_rewards =
+                game.distributed_bonus_rewards.safe_add(voucher_bonus)?;
+
+            let voucher_amount = referral_rewards.safe_add(voucher_bonus)?;
+            voucher.mint(voucher_amount)?;
+
+            // Transfer the underlying tokens from the game vault to the voucher vault, backing the newly minted vouchers
+            transfer_from_vault(
+                game,
+                ctx.bumps.game,
+                game_vault,
+                voucher_vault,
+                token_mint,
+                token_program,
+                voucher_amount,
+            )?;
+
+            // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+            let was_frozen = thaw_voucher_account_if_frozen(
+                voucher,
+                voucher_mint,
+                voucher_account,
+                token_program,
+                &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+            )?;
+
+            // Mint vouchers into the player's voucher account
+            mint_to(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    MintTo {
+                        mint: voucher_mint.to_account_info(),
+                        to: voucher_account.to_account_info(),
+                        authority: voucher.to_account_info(),
+                    },
+                    &[&[VOUCHER_SEED, &[ctx.bumps.voucher]]],
+                ),
+                voucher_amount,
+            )?;
+            refreeze_voucher_account(
+                voucher,
+                voucher_mint,
+                voucher_account,
+                token_program,
+                was_frozen,
+                &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+            )?;
+
+            voucher_bonus
+        }
+    };
+
+    msg!(
+        "Referral rewards: {}, voucher bonus: {}",
         referral_rewards,
-        &[GAME_SEED, &[ctx.bumps.game]],
-    )?;
-
-    msg!("Referral rewards: {}", referral_rewards);
+        voucher_bonus
+    );
 
     game.increment_event_nonce()?;
 
     // Emit a transfer event to log the referral reward collection
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CollectReferralReward as u16,
         event_type: EventType::CollectReferralReward,
         event_nonce: game.event_nonce,
         data: EventData::CollectReferralReward {
             game: game.key(),
             player: player.key(),
-            referral_rewards
+            referral_rewards,
+            payout,
+            voucher_bonus,
         },
         initiator_type: InitiatorType::PLAYER,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
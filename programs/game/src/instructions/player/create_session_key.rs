@@ -0,0 +1,150 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, MAX_SESSION_KEY_DURATION, PLAYER_DATA_SEED, SESSION_KEY_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{approve, Approve, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `CreateSessionKey` instruction lets a player authorize a session signer, such as a relayer, to sign
+/// the gameplay instructions selected by `scopes` on their behalf until the session expires.
+/// Calling it again replaces the player's session key. To let the session signer purchase ORE, the session
+/// key is approved as delegate of the player's token account for `token_allowance` tokens.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateSessionKey<'info> {
+    /// The player authorizing the session. Must sign the transaction and pays for the session key.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The player's data account, linking the player to their token account.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = token_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The player's token account, delegated to the session key for purchases.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's session key, created on first use and replaced afterwards.
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + SessionKey::INIT_SPACE,
+        seeds = [SESSION_KEY_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub session_key: Box<Account<'info, SessionKey>>,
+
+    /// The global game account, used for the event nonce.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The SPL token program, required to approve the delegate.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program, required to create the session key.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Creates or replaces the player's session key:
+///
+/// Steps:
+/// 1. Validate the scopes and the duration, bounded by `MAX_SESSION_KEY_DURATION`.
+/// 2. Record the session signer, scopes, daily ORE limit and expiry, resetting the daily counter.
+/// 3. Approve the session key as delegate of the player's token account when `token_allowance` is positive.
+/// 4. Emit a `CreateSessionKey` event.
+pub fn create_session_key(
+    ctx: Context<CreateSessionKey>,
+    session_signer: Pubkey,
+    scopes: u8,
+    max_daily_ores: u32,
+    token_allowance: u64,
+    duration: u64,
+) -> Result<()> {
+    // Fetch the current UNIX timestamp for the expiry and the event
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let CreateSessionKey {
+        player,
+        token_account,
+        session_key,
+        game,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require!(
+        scopes != 0 && scopes & !SESSION_SCOPE_ALL == 0,
+        ErrorCode::InvalidSessionKey
+    );
+    require!(
+        duration > 0 && duration <= MAX_SESSION_KEY_DURATION,
+        ErrorCode::InvalidSessionKey
+    );
+    require!(session_signer != player.key(), ErrorCode::InvalidSessionKey);
+
+    let expires_at = timestamp.safe_add(duration)?;
+    session_key.set_inner(SessionKey {
+        player: player.key(),
+        session_signer,
+        scopes,
+        max_daily_ores,
+        current_day: 0,
+        current_day_purchased_ores: 0,
+        expires_at,
+        bump: ctx.bumps.session_key,
+    });
+
+    // Let the session key spend the player's tokens on purchases
+    if token_allowance > 0 {
+        approve(
+            CpiContext::new(
+                token_program.to_account_info(),
+                Approve {
+                    to: token_account.to_account_info(),
+                    delegate: session_key.to_account_info(),
+                    authority: player.to_account_info(),
+                },
+            ),
+            token_allowance,
+        )?;
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the session key
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CreateSessionKey as u16,
+        event_type: EventType::CreateSessionKey,
+        event_nonce: game.event_nonce,
+        data: EventData::CreateSessionKey {
+            player: player.key(),
+            session_key: session_key.key(),
+            session_signer,
+            scopes,
+            max_daily_ores,
+            token_allowance,
+            expires_at,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -1,17 +1,18 @@
-use crate::constants::{GAME_SEED, TOKEN_MINT, VAULT_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, VAULT_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::{redeem_vouchers, to_timestamp_u64};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, burn, Burn, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
 /// The `Stake` instruction allows a player to stake tokens (shards) into the staking pool, create a stake order,
 /// and receive voucher tokens representing their staked assets. It involves transferring tokens from the player's
 /// account to a newly created stake order vault, then into the pool vault. Additionally, the player receives minted
 /// vouchers to confirm their stake, and rewards are allocated based on the configured APR.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     /// The player initiating the stake, must sign the transaction.
@@ -27,17 +28,19 @@ pub struct Deposit<'info> {
     /// The player's token account, from which staked tokens will be deducted.
     #[account(mut,
         associated_token::mint = token_mint,
-        associated_token::authority = player
+        associated_token::authority = player,
+        associated_token::token_program = token_program
     )]
-    pub token_0_account: Box<Account<'info, TokenAccount>>,
+    pub token_0_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The player's token account, from which staked tokens will be deducted.
     #[account(init_if_needed,
         payer = player,
         associated_token::mint = token_1_mint,
-        associated_token::authority = player
+        associated_token::authority = player,
+        associated_token::token_program = token_program
     )]
-    pub token_1_account: Box<Account<'info, TokenAccount>>,
+    pub token_1_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The deposit account, which holds the deposit information.
     #[account(mut, seeds = [VAULT_SEED], bump, has_one = token_mint, has_one = token_vault)]
@@ -45,25 +48,28 @@ pub struct Deposit<'info> {
 
     /// The token mint for the deposit token.
     #[account(mut)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The token mint for the deposit token.
-    #[account(mut, address = TOKEN_MINT)]
-    pub token_1_mint: Box<Account<'info, Mint>>,
+    #[account(mut, address = game.token_mint)]
+    pub token_1_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The token vault for the deposit token.
     #[account(mut)]
-    pub token_vault: Box<Account<'info, TokenAccount>>,
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The SPL token program, used for token operations like minting and transferring.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The associated token program, used for creating associated token accounts (like stake_order_vault).
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The system program, required for certain account creation operations.
     pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Executes the staking logic:
@@ -88,6 +94,7 @@ pub fn deposit(ctx: Context<Deposit>, token_amount: u64) -> Result<()> {
         token_1_account,
         vault,
         token_mint,
+        token_1_mint,
         token_vault,
         token_program,
         ..
@@ -119,17 +126,20 @@ pub fn deposit(ctx: Context<Deposit>, token_amount: u64) -> Result<()> {
     // transferring them from the voucher_vault to the game_vault.
     redeem_vouchers(
         vault,
+        ctx.bumps.vault,
         token_vault,
         token_1_account,
+        token_1_mint,
         token_program,
         token_amount,
-        &[VAULT_SEED, &[ctx.bumps.vault]],
     )?;
 
     game.increment_event_nonce()?;
 
     // Emit an event to record the staking action on-chain
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Deposit as u16,
         event_type: EventType::Deposit,
         event_nonce: game.event_nonce,
         data: EventData::Deposit {
@@ -140,7 +150,8 @@ pub fn deposit(ctx: Context<Deposit>, token_amount: u64) -> Result<()> {
         initiator_type: InitiatorType::DEPOSIT,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
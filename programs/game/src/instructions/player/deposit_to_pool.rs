@@ -0,0 +1,194 @@
+use crate::constants::{DEPOSIT_RECEIPT_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED, STAKE_POOL_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_player_to_vault};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `DepositToPool` instruction lets anyone fund one of the game's reward pools with game tokens.
+/// The tokens move to the vault backing the pool selected by the `DepositPurpose`, the pool balance is
+/// credited in the same instruction, and a `DepositReceipt` records the contribution so it cannot be misattributed.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(purpose: DepositPurpose, receipt_number: u32)]
+pub struct DepositToPool<'info> {
+    /// The contributor funding the pool, must sign the transaction.
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    /// The global game account, holding the period and round rewards pools.
+    #[account(
+        mut,
+        seeds = [GAME_SEED],
+        bump,
+        has_one = game_vault,
+        has_one = current_round,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game vault, receiving `FundPeriod` and `Donation` deposits.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The current round, holding the grand prize pool.
+    #[account(mut, has_one = round_vault)]
+    pub current_round: Box<Account<'info, Round>>,
+
+    /// The current round's vault, receiving `FundRound` deposits.
+    #[account(mut)]
+    pub round_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The stake pool, holding the token rewards pool.
+    #[account(mut, seeds = [STAKE_POOL_SEED], bump, has_one = stake_pool_token_vault)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The stake pool's token vault, receiving `FundStakeRewards` deposits.
+    #[account(mut)]
+    pub stake_pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The contributor's token account, from which the deposit is transferred.
+    #[account(mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = contributor,
+        associated_token::token_program = token_program
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The receipt recording the deposit.
+    #[account(
+        init,
+        payer = contributor,
+        space = 8 + DepositReceipt::INIT_SPACE,
+        seeds = [DEPOSIT_RECEIPT_SEED, contributor.key().as_ref(), receipt_number.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub deposit_receipt: Box<Account<'info, DepositReceipt>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program, required for the transfer.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program, required to create the receipt.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Deposits game tokens into the pool selected by `purpose`:
+///
+/// Steps:
+/// 1. Validate the amount and, when funding stake rewards, that the stake pool is still running.
+/// 2. Credit the pool balance and select the vault backing it.
+/// 3. Transfer the tokens from the contributor to that vault.
+/// 4. Record the deposit on a new `DepositReceipt`.
+/// 5. Emit a `DepositToPool` event.
+pub fn deposit_to_pool(
+    ctx: Context<DepositToPool>,
+    purpose: DepositPurpose,
+    receipt_number: u32,
+    amount: u64,
+) -> Result<()> {
+    // Fetch the current UNIX timestamp for the receipt and the event
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let DepositToPool {
+        contributor,
+        game,
+        game_vault,
+        current_round,
+        round_vault,
+        stake_pool,
+        stake_pool_token_vault,
+        token_account,
+        deposit_receipt,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    // Credit the pool and pick the vault backing it
+    let (pool, vault) = match purpose {
+        DepositPurpose::FundRound => {
+            require!(!current_round.is_over, ErrorCode::RoundAlreadyEnded);
+            current_round.grand_prize_pool_balance =
+                current_round.grand_prize_pool_balance.safe_add(amount)?;
+            (current_round.key(), &round_vault)
+        }
+        DepositPurpose::FundPeriod => {
+            game.period_rewards_pool_balance = game.period_rewards_pool_balance.safe_add(amount)?;
+            (game.key(), &game_vault)
+        }
+        DepositPurpose::FundStakeRewards => {
+            require!(
+                !stake_pool.is_decommissioned,
+                ErrorCode::StakePoolDecommissioned
+            );
+            require!(!stake_pool.is_winding_down, ErrorCode::StakePoolWindingDown);
+            stake_pool.add_rewards(amount)?;
+            (stake_pool.key(), &stake_pool_token_vault)
+        }
+        DepositPurpose::Donation => {
+            game.round_rewards_pool_balance = game.round_rewards_pool_balance.safe_add(amount)?;
+            (game.key(), &game_vault)
+        }
+        DepositPurpose::FundMaintenance => {
+            game.maintenance_pool_balance = game.maintenance_pool_balance.safe_add(amount)?;
+            (game.key(), &game_vault)
+        }
+    };
+
+    transfer_from_player_to_vault(
+        contributor,
+        token_account,
+        vault,
+        token_mint,
+        token_program,
+        amount,
+    )?;
+
+    deposit_receipt.set_inner(DepositReceipt {
+        contributor: contributor.key(),
+        receipt_number,
+        purpose,
+        pool,
+        vault: vault.key(),
+        amount,
+        timestamp,
+        bump: ctx.bumps.deposit_receipt,
+    });
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the deposit and its receipt
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::DepositToPool as u16,
+        event_type: EventType::DepositToPool,
+        event_nonce: game.event_nonce,
+        data: EventData::DepositToPool {
+            contributor: contributor.key(),
+            receipt: deposit_receipt.key(),
+            purpose,
+            pool,
+            vault: vault.key(),
+            amount,
+        },
+        initiator_type: InitiatorType::DEPOSIT,
+        initiator: contributor.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
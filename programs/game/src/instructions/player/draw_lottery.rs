@@ -1,29 +1,37 @@
 use crate::constants::{
-    GAME_SEED, MIN_LOTTERY_REWARDS_POOL_BALANCE, ONCE_DRAW_LOTTERY_VOUCHER_COST, PLAYER_DATA_SEED,
-    VOUCHER_MINT_SEED, VOUCHER_SEED,
+    BAN_ENTRY_SEED, EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, LOTTERY_TABLE_SEED,
+    PLAYER_DATA_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
 };
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
-use crate::utils::{redeem_vouchers, to_timestamp_u64};
+use crate::utils::{
+    is_randomness_seed_in_window, redeem_vouchers, refreeze_voucher_account,
+    thaw_voucher_account_if_frozen, to_timestamp_u64, Randomness,
+};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
-use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
-use switchboard_on_demand::accounts::RandomnessAccountData;
+use solana_program::sysvar::instructions as sysvar_instructions;
 
-/// The `DrawLottery` instruction enables a player to participate in a lottery draw using their voucher tokens.
+/// The `DrawLottery` instruction enables a player to participate in a lottery draw on a lottery table using their voucher tokens.
 /// The lottery mechanism depends on external randomness data (via Switchboard) and updates the global lottery and developer pools accordingly.
+/// Built with the `mock-randomness` feature, it also accepts a `MockRandomness` account owned by the program, so tests run the lottery without Switchboard.
 ///
 /// Steps:
-/// 1. Validate that the lottery pool has sufficient balance (`MIN_LOTTERY_REWARDS_POOL_BALANCE`).
-/// 2. Ensure the player has revealed the previous lottery result before attempting another draw.
-/// 3. Check that the player holds enough voucher tokens (`ONCE_DRAW_LOTTERY_VOUCHER_COST`).
-/// 4. Fetch and verify randomness data, ensuring it originates from the expected slot.
+/// 1. Validate that the lottery pool has sufficient balance (the game config's `min_lottery_rewards_pool_balance`),
+///    and covers the top prize of the table.
+/// 2. Ensure the player has revealed the previous lottery result before attempting another draw,
+///    and is neither self-excluded nor over their daily draw limit.
+/// 3. Check that the player holds enough voucher tokens (the table's `voucher_cost`).
+/// 4. Fetch and verify randomness data, ensuring it is not revealed yet and its seed slot is within the game config's `randomness_slot_window`.
 /// 5. Deduct a portion of the cost as developer rewards and allocate the remainder to the lottery pool.
-/// 6. Update the player's randomness-related data.
+/// 6. Update the player's randomness-related data, recording the table the result is revealed against,
+///    and record the bet on the table, and on today's stats if they were passed.
 /// 7. Burn the player's voucher tokens and redeem them for underlying tokens.
 /// 8. Emit a `DrawLottery` event to record the action on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct DrawLottery<'info> {
     /// The player initiating the lottery draw. Must sign the transaction.
@@ -39,9 +47,14 @@ pub struct DrawLottery<'info> {
     )]
     pub player_data: Box<Account<'info, PlayerData>>,
 
+    /// The player's ban entry, which must not exist.
+    /// CHECK: Derived from the player; checked to be empty in the handler.
+    #[account(seeds = [BAN_ENTRY_SEED, player.key().as_ref()], bump)]
+    pub ban_entry: UncheckedAccount<'info>,
+
     /// The player's voucher account from which voucher tokens will be burned to participate.
     #[account(mut)]
-    pub voucher_account: Box<Account<'info, TokenAccount>>,
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: The Switchboard randomness data account.
     /// Verified externally by the program logic to ensure proper seed_slot alignment.
@@ -56,9 +69,22 @@ pub struct DrawLottery<'info> {
     )]
     pub game: Box<Account<'info, Game>>,
 
+    /// The game config holding the minimum lottery pool balance and the randomness slot window.
+    #[account(seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// The lottery table drawn on, holding the draw cost and the multipliers, recording the bet. Must be enabled.
+    #[account(
+        mut,
+        seeds = [LOTTERY_TABLE_SEED, lottery_table.table_id.to_le_bytes().as_ref()],
+        bump = lottery_table.bump,
+        constraint = lottery_table.is_enabled @ ErrorCode::LotteryTableDisabled,
+    )]
+    pub lottery_table: Box<Account<'info, LotteryTable>>,
+
     /// The main game vault account from which tokens are sourced.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The voucher state account managing voucher mint authority and supply.
     #[account(
@@ -70,15 +96,30 @@ pub struct DrawLottery<'info> {
 
     /// The voucher vault token account holding the underlying assets backing voucher tokens.
     #[account(mut)]
-    pub voucher_vault: Box<Account<'info, TokenAccount>>,
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The voucher mint account used to create or burn voucher tokens.
     #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
-    pub voucher_mint: Box<Account<'info, Mint>>,
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     /// The SPL Token program used for minting, burning, and transferring tokens.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Today's stats account, recording the draw when passed.
+    #[account(mut)]
+    pub daily_stats: Option<Box<Account<'info, DailyStats>>>,
+
+    /// CHECK: The instructions sysvar, required when the game gates the instruction behind a compliance attestation,
+    /// verified by the ed25519 program instruction preceding it.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 pub fn draw_lottery(ctx: Context<DrawLottery>) -> Result<()> {
@@ -86,24 +127,40 @@ pub fn draw_lottery(ctx: Context<DrawLottery>) -> Result<()> {
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
+    // Reject banned wallets
+    BanEntry::require_not_banned(&ctx.accounts.ban_entry)?;
+
+    // Compliance-gated deployments require an eligibility attestation
+    ctx.accounts.game.require_compliance_attestation(
+        COMPLIANCE_GATE_DRAW_LOTTERY,
+        &ctx.accounts.player.key(),
+        ctx.accounts.instructions_sysvar.as_deref(),
+        timestamp,
+    )?;
+
     // Extract references for clarity
     let DrawLottery {
+        token_mint,
         player,
         player_data,
         voucher_account,
         randomness_account_data,
         game,
+        game_config,
+        lottery_table,
         game_vault,
         voucher,
         voucher_vault,
         voucher_mint,
         token_program,
+        daily_stats,
         ..
     } = ctx.accounts;
 
-    // Check that the lottery pool holds enough funds to justify a draw
+    // Check that the lottery pool holds enough funds to justify a draw and to pay the table's top prize
     require!(
-        game.lottery_rewards_pool_balance >= MIN_LOTTERY_REWARDS_POOL_BALANCE,
+        game.lottery_rewards_pool_balance >= game_config.min_lottery_rewards_pool_balance
+            && game.lottery_rewards_pool_balance >= lottery_table.max_lottery_rewards()?,
         ErrorCode::LotteryPoolIsEmpty
     );
 
@@ -113,7 +170,7 @@ pub fn draw_lottery(ctx: Context<DrawLottery>) -> Result<()> {
         ErrorCode::BeforeThisLotteryNeedToRevealLastResult
     );
 
-    let voucher_cost = ONCE_DRAW_LOTTERY_VOUCHER_COST;
+    let voucher_cost = lottery_table.voucher_cost;
 
     // Ensure the player has sufficient vouchers to pay the lottery cost
     require!(
@@ -122,46 +179,112 @@ pub fn draw_lottery(ctx: Context<DrawLottery>) -> Result<()> {
     );
 
     // Parse the randomness account data from Switchboard
-    let randomness_data = RandomnessAccountData::parse(randomness_account_data.data.borrow())
-        .map_err(|_| ErrorCode::RandomnessNotResolved)?;
+    let randomness_data =
+        Randomness::parse(randomness_account_data).ok_or(ErrorCode::RandomnessNotResolved)?;
 
     let current_slot = clock.slot;
 
-    // Verify that the randomness seed is from the immediately preceding slot
-    if randomness_data.seed_slot != current_slot - 1 {
-        return Err(ErrorCode::RandomnessAlreadyRevealed.into());
-    }
+    // The randomness must not be revealed yet, so the outcome is unknown when the draw is committed
+    require!(
+        randomness_data.reveal_slot < randomness_data.seed_slot,
+        ErrorCode::RandomnessAlreadyRevealed
+    );
+
+    // Verify that the randomness seed was taken within the configured window of slots before the draw
+    require!(
+        is_randomness_seed_in_window(
+            randomness_data.seed_slot,
+            current_slot,
+            game_config.randomness_slot_window,
+        ),
+        ErrorCode::RandomnessExpired
+    );
 
     // Update global game accounts with new balances
     game.lottery_rewards_pool_balance = game.lottery_rewards_pool_balance.safe_add(voucher_cost)?;
 
-    // Update the player's randomness provider and seed slot info
-    player_data.update_randomness(randomness_account_data.key(), randomness_data.seed_slot)?;
+    // Update the player's randomness provider, lottery table and seed slot info
+    player_data.update_randomness(
+        randomness_account_data.key(),
+        lottery_table.key(),
+        randomness_data.seed_slot,
+    )?;
+    player_data.draw_count = player_data.draw_count.safe_add(1)?;
+    // Enforce the player's self-exclusion and daily draw limit
+    player_data.record_spend_on_draw(timestamp)?;
+    lottery_table.record_bet(voucher_cost)?;
+
+    // Record the draw on today's stats if they were passed
+    if let Some(daily_stats) = daily_stats {
+        daily_stats.require_day(timestamp)?;
+        daily_stats.record_lottery_draw(voucher_cost)?;
+    }
 
     // Burn the voucher tokens from the player's voucher account
     voucher.burn(voucher_cost)?;
+    // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
     let cpi_accounts = Burn {
         mint: voucher_mint.to_account_info(),
         from: voucher_account.to_account_info(),
         authority: player.to_account_info(),
     };
     let cpi_context = CpiContext::new(token_program.to_account_info(), cpi_accounts);
-    token::burn(cpi_context, voucher_cost)?;
+    burn(cpi_context, voucher_cost)?;
+
+    game.record_burned_vouchers(voucher_cost)?;
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the burn
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Burn as u16,
+        event_type: EventType::Burn,
+        event_nonce: game.event_nonce,
+        data: EventData::Burn {
+            mint: voucher_mint.key(),
+            from: voucher_account.key(),
+            amount: voucher_cost,
+            reason: BurnReason::VoucherPayment,
+            total_burned: game.total_burned_vouchers,
+        },
+        initiator_type: InitiatorType::LOTTERY,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
 
     // Redeem the burned vouchers by transferring underlying tokens from voucher_vault to game_vault
     redeem_vouchers(
         voucher,
+        ctx.bumps.voucher,
         voucher_vault,
         game_vault,
+        token_mint,
         token_program,
         voucher_cost,
-        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
     )?;
 
     game.increment_event_nonce()?;
 
     // Emit an event capturing the lottery draw action
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::DrawLottery as u16,
         event_type: EventType::DrawLottery,
         event_nonce: game.event_nonce,
         data: EventData::DrawLottery {
@@ -170,11 +293,13 @@ pub fn draw_lottery(ctx: Context<DrawLottery>) -> Result<()> {
             randomness_provider: randomness_account_data.key(),
             bet_amount: voucher_cost,
             voucher: voucher.key(),
+            lottery_table: lottery_table.key(),
         },
         initiator_type: InitiatorType::LOTTERY,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
@@ -1,11 +1,11 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
-use crate::utils::{to_timestamp_u64, transfer_from_token_vault_to_token_account};
+use crate::utils::{to_timestamp_u64, transfer_from_vault};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
-use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 use std::cmp::min;
 
@@ -17,10 +17,11 @@ use std::cmp::min;
 /// 1. Verify the current round is ongoing and the player is participating in it.
 /// 2. Check that the player has ORE to justify an exit (no ORE means no need to exit).
 /// 3. Settle any pending construction rewards based on the round's current earnings rate.
-/// 4. Calculate and distribute construction rewards, bonus rewards, and exit rewards from the respective pools.
+/// 4. Calculate and distribute construction rewards, bonus rewards, and the exit rewards accrued to the player's ORE from the respective pools.
 /// 5. Deduct the player's ORE from the round's available ORE and update the round's end time if necessary.
 /// 6. Mark the player as exited, reset their round-related data, and transfer all due rewards to the player's token account.
 /// 7. Emit an `Exit` event to log the action on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Exit<'info> {
     /// The global game account referencing the current round and main vault.
@@ -35,7 +36,7 @@ pub struct Exit<'info> {
 
     /// The main game vault holding tokens used for various rewards and pools.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The current round account, ensuring the round is active (not ended).
     #[account(
@@ -58,11 +59,17 @@ pub struct Exit<'info> {
 
     /// The player's token account to which rewards will be transferred.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     /// The SPL token program used for transfers.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 pub fn exit(ctx: Context<Exit>) -> Result<()> {
@@ -72,6 +79,7 @@ pub fn exit(ctx: Context<Exit>) -> Result<()> {
 
     // Extract references for clarity
     let Exit {
+        token_mint,
         game,
         current_round,
         player,
@@ -131,21 +139,22 @@ pub fn exit(ctx: Context<Exit>) -> Result<()> {
         .safe_add(construction_rewards)?
         .safe_add(bonus_rewards)?;
 
-    // Calculate exit rewards based on elapsed time since last collection and ensure no exceedance of pool balance
-    let elapsed_time = timestamp.safe_sub(current_round.last_collected_exit_reward_timestamp)?;
-    let potential_exit_rewards = game.exit_rewards_per_second.safe_mul(elapsed_time)?;
-    let exit_rewards = min(potential_exit_rewards, game.exit_rewards_pool_balance);
+    // Settle the exit rewards accrued to the player's ORE while held, never exceeding the exit pool balance
+    current_round.accrue_exit_rewards(timestamp)?;
+    player_data.settle_collectable_exit_rewards(current_round.exit_rewards_per_ore)?;
+    let requested_exit_rewards = player_data.collectable_exit_rewards;
+    let exit_rewards = min(requested_exit_rewards, game.exit_rewards_pool_balance);
 
-    // Update player's collected exit rewards and mark new timestamp
+    // Update player's collected exit rewards
     player_data.collected_exit_rewards =
         player_data.collected_exit_rewards.safe_add(exit_rewards)?;
-    current_round.last_collected_exit_reward_timestamp = timestamp;
 
     // Deduct exit rewards from the game's exit pool and record them as distributed
     game.exit_rewards_pool_balance = game.exit_rewards_pool_balance.safe_sub(exit_rewards)?;
     game.distributed_exit_rewards = game.distributed_exit_rewards.safe_add(exit_rewards)?;
 
     // Remove the player's ORE from the round's available supply
+    let previous_phase = current_round.phase(timestamp);
     require!(
         current_round.available_ores >= available_ores,
         RoundError::InsufficientOres
@@ -159,15 +168,16 @@ pub fn exit(ctx: Context<Exit>) -> Result<()> {
     player_data.exit_round()?;
 
     // Transfer the player's rewards (bonus + exit rewards) from the game vault to player's token account
-    transfer_from_token_vault_to_token_account(
+    transfer_from_vault(
         game,
+        ctx.bumps.game,
         game_vault,
         token_account,
+        token_mint,
         token_program,
         construction_rewards
             .safe_add(bonus_rewards)?
             .safe_add(exit_rewards)?,
-        &[GAME_SEED, &[ctx.bumps.game]],
     )?;
 
     msg!("Construction rewards: {}", construction_rewards);
@@ -179,7 +189,9 @@ pub fn exit(ctx: Context<Exit>) -> Result<()> {
     game.increment_event_nonce()?;
 
     // Emit an event logging the player's exit, including how much ORE they held at exit
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Exit as u16,
         event_type: EventType::Exit,
         event_nonce: game.event_nonce,
         data: EventData::Exit {
@@ -192,7 +204,75 @@ pub fn exit(ctx: Context<Exit>) -> Result<()> {
         initiator_type: InitiatorType::PLAYER,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
+
+    // Log the exit pool capping the exit rewards as it drains
+    if exit_rewards < requested_exit_rewards {
+        game.increment_event_nonce()?;
+
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::PoolExhausted as u16,
+            event_type: EventType::PoolExhausted,
+            event_nonce: game.event_nonce,
+            data: EventData::PoolExhausted {
+                pool: ExhaustedPool::Exit,
+                requested_amount: requested_exit_rewards,
+                paid_amount: exit_rewards,
+                pool_balance: game.exit_rewards_pool_balance,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    // Log the round moving to another phase, e.g. back to bootstrapping once the last holder exits
+    let phase = current_round.phase(timestamp);
+    if phase != previous_phase {
+        game.increment_event_nonce()?;
+
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::RoundPhaseChanged as u16,
+            event_type: EventType::RoundPhaseChanged,
+            event_nonce: game.event_nonce,
+            data: EventData::RoundPhaseChanged {
+                round: current_round.key(),
+                previous_phase,
+                phase,
+                available_ores: current_round.available_ores,
+                end_time: current_round.end_time,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    // Log the countdown reaching a milestone
+    if let Some(milestone_seconds) = current_round.reach_countdown_milestone(timestamp) {
+        game.increment_event_nonce()?;
+
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::CountdownMilestone as u16,
+            event_type: EventType::CountdownMilestone,
+            event_nonce: game.event_nonce,
+            data: EventData::CountdownMilestone {
+                round: current_round.key(),
+                milestone_seconds,
+                end_time: current_round.end_time,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
 
     Ok(())
 }
@@ -0,0 +1,22 @@
+use crate::constants::LOTTERY_TABLE_SEED;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+/// The `GetLotteryStats` instruction is a read-only view of a lottery table's return to player accounting,
+/// letting anyone verify the realized return of a table through simulation.
+#[derive(Accounts)]
+pub struct GetLotteryStats<'info> {
+    /// The lottery table whose accounting is returned.
+    #[account(
+        seeds = [LOTTERY_TABLE_SEED, lottery_table.table_id.to_le_bytes().as_ref()],
+        bump = lottery_table.bump,
+    )]
+    pub lottery_table: Box<Account<'info, LotteryTable>>,
+}
+
+/// Executes the `GetLotteryStats` instruction:
+///
+/// - Returns the table's draw count, cumulative bets and payouts, and realized return to player as return data.
+pub fn get_lottery_stats(ctx: Context<GetLotteryStats>) -> Result<LotteryStats> {
+    Ok(ctx.accounts.lottery_table.stats())
+}
@@ -1,32 +1,86 @@
 pub mod cancel_is_auto_reinvesting;
 pub mod candy_tap;
+pub mod claim_leaderboard_reward;
 pub mod collateral_exchange;
 pub mod collect_airdrop_rewards;
 pub mod collect_consumption_rewards;
 pub mod collect_referral_rewards;
+pub mod create_session_key;
 pub mod deposit;
+pub mod deposit_to_pool;
 pub mod draw_lottery;
 pub mod exit;
 pub mod purchase;
+pub mod purchase_with_sol;
+pub mod purchase_with_tokens;
+pub mod redeem_position;
+pub mod redeem_vouchers;
+pub mod reenter_round;
 pub mod register;
+pub mod register_sponsored;
 pub mod reinvest;
 pub mod reveal_draw_lottery_result;
+pub mod revoke_session_key;
 pub mod set_is_auto_reinvesting;
 pub mod set_referrer;
+pub mod settle_and_purchase;
 pub mod settle_previous_round;
+pub mod tokenize_position;
+pub mod upgrade_to_self_custody;
+pub mod verify_leaderboard_rank;
+pub mod get_lottery_stats;
+pub mod set_spend_limit;
+pub mod self_exclude;
+pub mod view_player_summary;
+pub mod view_round_summary;
+pub mod view_pending_rewards;
+pub mod collect_all_rewards;
+pub mod claim_merkle_airdrop;
+pub mod place_prediction;
+pub mod settle_predictions;
+pub mod claim_prediction;
+pub mod claim_vested_prize;
+pub mod buy_streak_shield;
 pub use cancel_is_auto_reinvesting::*;
 pub use candy_tap::*;
+pub use claim_leaderboard_reward::*;
 pub use collateral_exchange::*;
 pub use collect_airdrop_rewards::*;
 pub use collect_consumption_rewards::*;
 pub use collect_referral_rewards::*;
+pub use create_session_key::*;
 pub use deposit::*;
+pub use deposit_to_pool::*;
 pub use draw_lottery::*;
 pub use exit::*;
 pub use purchase::*;
+pub use purchase_with_sol::*;
+pub use purchase_with_tokens::*;
+pub use redeem_position::*;
+pub use redeem_vouchers::*;
+pub use reenter_round::*;
 pub use register::*;
+pub use register_sponsored::*;
 pub use reinvest::*;
 pub use reveal_draw_lottery_result::*;
+pub use revoke_session_key::*;
 pub use set_is_auto_reinvesting::*;
 pub use set_referrer::*;
+pub use settle_and_purchase::*;
 pub use settle_previous_round::*;
+pub use tokenize_position::*;
+pub use upgrade_to_self_custody::*;
+pub use verify_leaderboard_rank::*;
+pub use get_lottery_stats::*;
+pub use set_spend_limit::*;
+pub use self_exclude::*;
+pub use view_player_summary::*;
+pub use view_round_summary::*;
+pub use view_pending_rewards::*;
+pub use collect_all_rewards::*;
+pub use claim_merkle_airdrop::*;
+pub use place_prediction::*;
+pub use settle_predictions::*;
+pub use claim_prediction::*;
+pub use claim_vested_prize::*;
+pub use buy_streak_shield::*;
@@ -0,0 +1,222 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PREDICTION_MARKET_SEED, PREDICTION_OUTCOME_SEED,
+    PREDICTION_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    refreeze_voucher_account, thaw_voucher_account_if_frozen, to_timestamp_u64,
+    transfer_from_player_to_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `PlacePrediction` instruction lets anyone, player or not, lock vouchers on the wallet they predict
+/// will win the current round, ending it as `last_active_participant_list[0]`. The vouchers are escrowed in
+/// the round's `PredictionMarket` until `settle_predictions`; predictions close once the round reaches its climax.
+///
+/// Steps:
+/// 1. Require the round to have started, and not to have reached its climax or ended.
+/// 2. Record the stake on the predictor's prediction, the predicted winner's outcome and the market.
+/// 3. Transfer the vouchers from the predictor's voucher account to the market's escrow vault.
+/// 4. Emit a `PlacePrediction` event to record the prediction on-chain.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(predicted_winner: Pubkey)]
+pub struct PlacePrediction<'info> {
+    /// The predictor locking the vouchers, paying for the prediction accounts.
+    #[account(mut)]
+    pub predictor: Signer<'info>,
+
+    /// The global `Game` account, referencing the current round.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = current_round)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The current round predicted on, which must not have ended.
+    #[account(constraint = !current_round.is_over @ ErrorCode::PredictionsClosed)]
+    pub current_round: Box<Account<'info, Round>>,
+
+    /// The round's prediction market, created on its first prediction.
+    #[account(
+        init_if_needed,
+        payer = predictor,
+        space = 8 + PredictionMarket::INIT_SPACE,
+        seeds = [PREDICTION_MARKET_SEED, current_round.key().as_ref()],
+        bump,
+    )]
+    pub prediction_market: Box<Account<'info, PredictionMarket>>,
+
+    /// The vault escrowing the market's vouchers.
+    #[account(
+        init_if_needed,
+        payer = predictor,
+        associated_token::mint = voucher_mint,
+        associated_token::authority = prediction_market,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The predictor's prediction on the round, created on their first stake.
+    #[account(
+        init_if_needed,
+        payer = predictor,
+        space = 8 + Prediction::INIT_SPACE,
+        seeds = [PREDICTION_SEED, prediction_market.key().as_ref(), predictor.key().as_ref()],
+        bump,
+    )]
+    pub prediction: Box<Account<'info, Prediction>>,
+
+    /// The stakes on the predicted winner, created on the first prediction on them.
+    #[account(
+        init_if_needed,
+        payer = predictor,
+        space = 8 + PredictionOutcome::INIT_SPACE,
+        seeds = [PREDICTION_OUTCOME_SEED, prediction_market.key().as_ref(), predicted_winner.as_ref()],
+        bump,
+    )]
+    pub prediction_outcome: Box<Account<'info, PredictionOutcome>>,
+
+    /// The predictor's voucher account paying the stake.
+    #[account(
+        mut,
+        token::mint = voucher_mint,
+        token::authority = predictor,
+        token::token_program = token_program,
+    )]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global voucher account, the freeze authority of the voucher accounts.
+    #[account(seeds = [VOUCHER_SEED], bump)]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher mint account.
+    #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program for creating the escrow vault and the transfer.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program for creating the escrow vault.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program, required to create the prediction accounts.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the `PlacePrediction` instruction, see `PlacePrediction`.
+///
+/// # Arguments
+/// - `predicted_winner`: The wallet predicted to win the round.
+/// - `amount`: The vouchers locked on the prediction.
+pub fn place_prediction(
+    ctx: Context<PlacePrediction>,
+    predicted_winner: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for the round checks and event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let PlacePrediction {
+        predictor,
+        game,
+        current_round,
+        prediction_market,
+        escrow_vault,
+        prediction,
+        prediction_outcome,
+        voucher_account,
+        voucher,
+        voucher_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    // Predictions are open from the round's start until its climax
+    require!(
+        timestamp >= current_round.start_time,
+        ErrorCode::RoundNotStarted
+    );
+    require!(
+        timestamp < current_round.end_time && current_round.phase(timestamp) != RoundPhase::Climax,
+        ErrorCode::PredictionsClosed
+    );
+
+    if prediction_market.round == Pubkey::default() {
+        prediction_market.initialize(
+            current_round.key(),
+            escrow_vault.key(),
+            ctx.bumps.prediction_market,
+        );
+    }
+
+    // Record the stake on the prediction, the predicted winner and the market
+    let is_new = prediction.record_stake(
+        prediction_market.key(),
+        predictor.key(),
+        predicted_winner,
+        amount,
+        ctx.bumps.prediction,
+    )?;
+    prediction_outcome.record_stake(
+        prediction_market.key(),
+        predicted_winner,
+        amount,
+        ctx.bumps.prediction_outcome,
+    )?;
+    prediction_market.record_prediction(amount, is_new)?;
+
+    // Thaw the voucher account while the stake moves out of it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+    transfer_from_player_to_vault(
+        predictor,
+        voucher_account,
+        escrow_vault,
+        voucher_mint,
+        token_program,
+        amount,
+    )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the prediction
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::PlacePrediction as u16,
+        event_type: EventType::PlacePrediction,
+        event_nonce: game.event_nonce,
+        data: EventData::PlacePrediction {
+            prediction_market: prediction_market.key(),
+            predictor: predictor.key(),
+            predicted_winner,
+            amount,
+            total_staked: prediction_market.total_staked,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: predictor.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
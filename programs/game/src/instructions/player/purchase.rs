@@ -1,19 +1,26 @@
 use crate::constants::{
-    CONSTRUCTION_POOL_SHARE, CONSUMPTION_POOL_SHARE, GAME_SEED, GRAND_PRIZES_POOL_SHARE,
-    LAMPORTS_PER_ORE, LOTTERY_POOL_SHARE, PLAYER_DATA_SEED, REFERRAL_POOL_SHARE, TOKEN_MINT,
-    VOUCHER_MINT_SEED, VOUCHER_SEED,
+    BAN_ENTRY_SEED, BUBBLEGUM_PROGRAM_ID, EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED,
+    PLAYER_DATA_SEED, SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, SPL_NOOP_PROGRAM_ID, VOUCHER_MINT_SEED,
+    VOUCHER_SEED,
 };
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{
+    EventData, EventType, InitiatorType, SoftFailureReason, TransferEvent, EVENT_SCHEMA_VERSION,
+};
 use crate::state::*;
+#[cfg(feature = "strict-invariants")]
+use crate::utils::{assert_invariants, InvariantSnapshot};
 use crate::utils::{
-    calculate_proportion, redeem_vouchers, timestamp_to_days, to_timestamp_u64,
-    transfer_from_player_to_vault,
+    distribute, mint_round_badge, record_purchased_ores, redeem_vouchers, refreeze_voucher_account,
+    release_carryover, thaw_voucher_account_if_frozen, timestamp_to_days, to_timestamp_u64,
+    transfer_from_player_to_vault, transfer_from_vault, DistributionInput, PurchaseRecord,
+    RoundBadgeAccounts,
 };
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
-use anchor_spl::token::{self, burn, Burn, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
+use solana_program::sysvar::instructions as sysvar_instructions;
 use std::cmp::min;
 
 /// The `Purchase` instruction enables players to buy ORE tokens within the current round, affecting various in-game pools and distributions.
@@ -21,12 +28,20 @@ use std::cmp::min;
 ///
 /// Steps:
 /// 1. Validate that the current round has started and handle edge cases if the round end conditions are met.
-/// 2. Ensure the player has sufficient funds (vouchers + tokens) to cover the ORE purchase cost.
+///    A zero ORE call counted towards ending the round is tipped from the maintenance pool.
+/// 2. Release construction rewards the player carried over from their previous round into their token account,
+///    then ensure the player has sufficient funds (vouchers + tokens) to cover the ORE purchase cost.
+///    When a voucher kind is passed, its vouchers cover the cost the standard vouchers do not, at the kind's value rate.
 /// 3. Calculate proportional allocations (construction, bonus, lottery, developer, referral, and grand prize pools) from the purchase amount.
 /// 4. Update round and game-level states, adjusting earnings_per_ore, sold_ores, participant lists, and round end time.
 /// 5. Manage player state: update consecutive purchase days, settle pending construction rewards, and adjust ORE holdings and earnings_per_ore.
+///    Records the purchase on today's stats if they were passed.
+///    The purchase counts against the player's daily ORE limit, and fails if they are self-excluded or banned.
 /// 6. If vouchers are used as payment, burn them and redeem underlying tokens. Also, transfer funds from player accounts to game and round vaults as required.
 /// 7. Emit a `Purchase` event to record the transaction on-chain.
+/// 8. When round badges are enabled and the badge accounts are passed, mint a compressed NFT badge recording the
+///    round number and entry day on the player's first purchase in the round.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Purchase<'info> {
     /// The player making the purchase. Must sign the transaction.
@@ -44,6 +59,11 @@ pub struct Purchase<'info> {
     )]
     pub player_data: Box<Account<'info, PlayerData>>,
 
+    /// The player's ban entry, which must not exist.
+    /// CHECK: Derived from the player; checked to be empty in the handler.
+    #[account(seeds = [BAN_ENTRY_SEED, player.key().as_ref()], bump)]
+    pub ban_entry: UncheckedAccount<'info>,
+
     /// The referrer's data account, tracking pending referral rewards due to them.
     #[account(
         mut,
@@ -63,6 +83,10 @@ pub struct Purchase<'info> {
     )]
     pub game: Box<Account<'info, Game>>,
 
+    /// The game config holding the pool shares and the ORE cost.
+    #[account(seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
     /// The current round account, must be active (not ended), referencing its `round_vault`.
     #[account(
         mut,
@@ -73,7 +97,7 @@ pub struct Purchase<'info> {
 
     /// The current period account representing a leaderboard period.
     #[account(mut)]
-    pub current_period: Box<Account<'info, Period>>,
+    pub current_period: AccountLoader<'info, Period>,
 
     /// The team account the player belongs to, or the default team if none.
     #[account(mut)]
@@ -89,44 +113,116 @@ pub struct Purchase<'info> {
 
     /// The main game vault holding the platform's aggregated funds.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The round-specific vault holding tokens allocated for the current round.
     #[account(mut)]
-    pub round_vault: Box<Account<'info, TokenAccount>>,
+    pub round_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The voucher vault account holding underlying assets backing voucher tokens.
     #[account(mut)]
-    pub voucher_vault: Box<Account<'info, TokenAccount>>,
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The player's token account used to pay part of the purchase cost and receive rewards.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The player's voucher account, storing voucher tokens that can be burned for payment.
     #[account(mut)]
-    pub voucher_account: Box<Account<'info, TokenAccount>>,
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The voucher mint account used for issuing and burning voucher tokens.
     #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
-    pub voucher_mint: Box<Account<'info, Mint>>,
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The token mint account used for issuing and burning token tokens.
-    #[account(mut, address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(mut, address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The SPL Token program used for token operations like minting, burning, and transfers.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// A registered voucher kind paying, at its value rate, the part of the cost the standard vouchers
+    /// do not cover. Passed together with the three voucher kind accounts below, or not at all.
+    #[account(mut)]
+    pub voucher_kind: Option<Box<Account<'info, VoucherKind>>>,
+
+    /// The mint of the voucher kind.
+    #[account(mut)]
+    pub voucher_kind_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// The vault holding the tokens backing the voucher kind.
+    #[account(mut)]
+    pub voucher_kind_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The player's token account of the voucher kind, burned for payment.
+    #[account(mut)]
+    pub voucher_kind_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Today's stats account, recording the purchase when passed.
+    #[account(mut)]
+    pub daily_stats: Option<Box<Account<'info, DailyStats>>>,
+
+    /// The Bubblegum tree config of the game's badge tree. Passed together with the five round badge
+    /// accounts below, or not at all.
+    /// CHECK: Validated by the Bubblegum program.
+    #[account(mut)]
+    pub badge_tree_config: Option<UncheckedAccount<'info>>,
+
+    /// The game's badge merkle tree, receiving the badge of the player's first purchase in the round.
+    /// CHECK: Checked against the game's `badge_merkle_tree`, and validated by the Bubblegum program.
+    #[account(mut)]
+    pub badge_merkle_tree: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The Bubblegum program minting the badge.
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The SPL noop program logging the badge for indexers.
+    #[account(address = SPL_NOOP_PROGRAM_ID)]
+    pub log_wrapper: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The SPL account compression program storing the badge tree.
+    #[account(address = SPL_ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: Option<UncheckedAccount<'info>>,
+
+    /// The system program, required by the badge mint.
+    pub system_program: Option<Program<'info, System>>,
+
+    /// CHECK: The instructions sysvar, required when the game gates the instruction behind a compliance attestation,
+    /// verified by the ed25519 program instruction preceding it.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Handles the `Purchase` logic, applying cost calculations, distribution of funds to various pools,
 /// updating leaderboards and player states, and managing the round lifecycle if conditions warrant ending the round.
+///
+/// Purchase is the hot path of the game and must stay within `PURCHASE_COMPUTE_UNIT_BUDGET`:
+/// leaderboards are maintained by bounded insertion rather than re-sorting, construction rewards are
+/// settled once, and diagnostic logs are only compiled in with the `verbose-logs` feature.
 pub fn purchase(ctx: Context<Purchase>, purchased_ores: u32) -> Result<()> {
     // Obtain current Solana time for logic and event logging
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
+    // Reject banned wallets
+    BanEntry::require_not_banned(&ctx.accounts.ban_entry)?;
+
+    // Purchases in compliance-gated deployments require an eligibility attestation, round-end calls purchasing zero ORE do not
+    if purchased_ores > 0 {
+        ctx.accounts.game.require_compliance_attestation(
+            COMPLIANCE_GATE_PURCHASE,
+            &ctx.accounts.player.key(),
+            ctx.accounts.instructions_sysvar.as_deref(),
+            timestamp,
+        )?;
+    }
+
     // Extract references for clarity
     let Purchase {
         player,
@@ -134,6 +230,7 @@ pub fn purchase(ctx: Context<Purchase>, purchased_ores: u32) -> Result<()> {
         token_account,
         voucher_account,
         game,
+        game_config,
         game_vault,
         current_round,
         round_vault,
@@ -145,9 +242,24 @@ pub fn purchase(ctx: Context<Purchase>, purchased_ores: u32) -> Result<()> {
         referrer_data,
         token_mint,
         token_program,
+        voucher_kind,
+        voucher_kind_mint,
+        voucher_kind_vault,
+        voucher_kind_account,
+        daily_stats,
+        badge_tree_config,
+        badge_merkle_tree,
+        bubblegum_program,
+        log_wrapper,
+        compression_program,
+        system_program,
         ..
     } = ctx.accounts;
 
+    // Capture the monotonic counters checked by the invariants at the end of the instruction
+    #[cfg(feature = "strict-invariants")]
+    let invariant_snapshot = InvariantSnapshot::take(game, current_round);
+
     // Validate that the current round is active (has started)
     require!(
         current_round.start_time <= timestamp,
@@ -158,9 +270,17 @@ pub fn purchase(ctx: Context<Purchase>, purchased_ores: u32) -> Result<()> {
 
     // If the round end_time has passed and no ORE are purchased, handle round end scenario
     if current_round.end_time <= timestamp && purchased_ores == 0 {
-        handle_round_end(current_round, current_period, clock.slot, timestamp)?;
+        let previous_call_count = current_round.call_count;
+        let truncated_period_times = handle_round_end(
+            current_round,
+            &mut *current_period.load_mut()?,
+            clock.slot,
+            timestamp,
+        )?;
 
-        emit!(TransferEvent {
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::RoundEnd as u16,
             event_type: EventType::RoundEnd,
             event_nonce: game.event_nonce,
             data: EventData::RoundEnd {
@@ -172,7 +292,81 @@ pub fn purchase(ctx: Context<Purchase>, purchased_ores: u32) -> Result<()> {
             initiator_type: InitiatorType::SYSTEM,
             initiator: player.key(),
             timestamp,
-        });
+        };
+        record_event!(ctx, event);
+
+        // Log the truncation of a period ending with the round
+        if let Some((original_start_time, original_end_time)) = truncated_period_times {
+            let period_data = current_period.load()?;
+
+            game.increment_event_nonce()?;
+
+            let event = TransferEvent {
+                event_schema_version: EVENT_SCHEMA_VERSION,
+                event_tag: EventType::PeriodTruncated as u16,
+                event_type: EventType::PeriodTruncated,
+                event_nonce: game.event_nonce,
+                data: EventData::PeriodTruncated {
+                    period: current_period.key(),
+                    round: current_round.key(),
+                    original_start_time,
+                    original_end_time,
+                    start_time: period_data.start_time,
+                    end_time: period_data.end_time,
+                },
+                initiator_type: InitiatorType::SYSTEM,
+                initiator: player.key(),
+                timestamp,
+            };
+            record_event!(ctx, event);
+        }
+
+        // Tip the caller from the maintenance pool if the call was counted towards ending the round
+        if current_round.call_count > previous_call_count {
+            let tip = game.take_crank_tip(game_config.crank_tip, 1)?;
+            if tip > 0 {
+                transfer_from_vault(
+                    game,
+                    ctx.bumps.game,
+                    game_vault,
+                    token_account,
+                    token_mint,
+                    token_program,
+                    tip,
+                )?;
+
+                game.increment_event_nonce()?;
+
+                let event = TransferEvent {
+                    event_schema_version: EVENT_SCHEMA_VERSION,
+                    event_tag: EventType::CrankTip as u16,
+                    event_type: EventType::CrankTip,
+                    event_nonce: game.event_nonce,
+                    data: EventData::CrankTip {
+                        crank: MaintenanceCrank::RoundEnd,
+                        caller: player.key(),
+                        tip_account: token_account.key(),
+                        tasks: 1,
+                        tip,
+                        maintenance_pool_balance: game.maintenance_pool_balance,
+                    },
+                    initiator_type: InitiatorType::SYSTEM,
+                    initiator: player.key(),
+                    timestamp,
+                };
+                record_event!(ctx, event);
+            }
+        } else {
+            // Report a call coming too soon to be counted as a soft failure
+            let event = TransferEvent::soft_failure(
+                game,
+                SoftFailureReason::RoundEndCallTooSoon,
+                player.key(),
+                timestamp,
+            )?;
+            record_event!(ctx, event);
+        }
+
         return Ok(());
     }
 
@@ -182,14 +376,29 @@ pub fn purchase(ctx: Context<Purchase>, purchased_ores: u32) -> Result<()> {
         ErrorCode::PurchaseQuantityMustGreaterThanZero
     );
 
-    // The player must have settled previous rounds or must already be in this current round
-    require!(
-        player_data.is_exited || player_data.current_round == current_round.key(),
-        ErrorCode::NeedToSettlePreviousRound
-    );
+    // The player must have settled previous rounds or must already be in this current round,
+    // and must have re-entered it after exiting it
+    player_data.require_can_join_round(current_round.key())?;
+
+    // Spend construction rewards carried over from the player's previous round first
+    let carryover = release_carryover(game, player_data)?;
+    if carryover > 0 {
+        transfer_from_vault(
+            game,
+            ctx.bumps.game,
+            game_vault,
+            token_account,
+            token_mint,
+            token_program,
+            carryover,
+        )?;
+        token_account.reload()?;
+    }
 
     // Calculate total cost in lamports for the requested ORE quantity
-    let total_cost = LAMPORTS_PER_ORE.safe_mul(purchased_ores as u64)?;
+    let total_cost = game_config
+        .lamports_per_ore
+        .safe_mul(purchased_ores as u64)?;
 
     // Determine player's available voucher and token balances
     let voucher_balance: u64 = voucher_account.amount;
@@ -197,166 +406,153 @@ pub fn purchase(ctx: Context<Purchase>, purchased_ores: u32) -> Result<()> {
 
     // Decide how much cost is covered by vouchers vs tokens
     let voucher_cost = min(voucher_balance, total_cost);
-    let token_cost = total_cost.safe_sub(voucher_cost)?;
 
-    // Check if total funds (vouchers + tokens) cover the total_cost
-    let player_balance = token_balance.safe_add(voucher_balance)?;
+    // Cover the rest with the voucher kind if passed, at its value rate
+    let (voucher_kind_burned, voucher_kind_value) = match (
+        voucher_kind.as_deref(),
+        voucher_kind_mint.as_deref(),
+        voucher_kind_vault.as_deref(),
+        voucher_kind_account.as_deref(),
+    ) {
+        (None, None, None, None) => (0, 0),
+        (
+            Some(voucher_kind),
+            Some(voucher_kind_mint),
+            Some(voucher_kind_vault),
+            Some(voucher_kind_account),
+        ) => {
+            require_keys_eq!(
+                voucher_kind_mint.key(),
+                voucher_kind.voucher_mint,
+                ErrorCode::InvalidVoucherKindAccounts
+            );
+            require_keys_eq!(
+                voucher_kind_vault.key(),
+                voucher_kind.voucher_vault,
+                ErrorCode::InvalidVoucherKindAccounts
+            );
+            require_keys_eq!(
+                voucher_kind_account.mint,
+                voucher_kind.voucher_mint,
+                ErrorCode::InvalidVoucherKindAccounts
+            );
+            voucher_kind.require_enabled()?;
+            voucher_kind.payment(
+                total_cost.safe_sub(voucher_cost)?,
+                voucher_kind_account.amount,
+            )?
+        }
+        _ => return err!(ErrorCode::InvalidVoucherKindAccounts),
+    };
+    let token_cost = total_cost
+        .safe_sub(voucher_cost)?
+        .safe_sub(voucher_kind_value)?;
+
+    // Check if total funds (vouchers + voucher kind + tokens) cover the total_cost
+    let player_balance = token_balance
+        .safe_add(voucher_balance)?
+        .safe_add(voucher_kind_value)?;
     require!(
         player_balance >= total_cost,
         ErrorCode::InsufficientFundsToPayFee
     );
 
-    let current_ores = current_round.available_ores;
-
-    // Calculate proportional rewards for various pools
-    let construction_rewards = calculate_proportion(total_cost, CONSTRUCTION_POOL_SHARE)?;
-    let bonus_rewards = construction_rewards;
-    let lottery_rewards = calculate_proportion(total_cost, LOTTERY_POOL_SHARE)?;
-    let referral_rewards = calculate_proportion(total_cost, REFERRAL_POOL_SHARE)?;
-    let grand_prizes_rewards = calculate_proportion(total_cost, GRAND_PRIZES_POOL_SHARE)?;
-    let consumption_rewards = calculate_proportion(token_cost, CONSUMPTION_POOL_SHARE)?;
-    let developer_rewards = calculate_proportion(token_cost, CONSUMPTION_POOL_SHARE)?;
-
-    let current_round_key = current_round.key();
-    let current_period_key = current_period.key();
-    let current_day = timestamp_to_days(timestamp)?;
-
-    // Update the player to reflect they are now in the current round and period
-    player_data.current_round = current_round_key;
-    if player_data.current_period != current_period_key {
-        player_data.current_period_purchased_ores = 0;
-    }
-    player_data.current_period = current_period_key;
-    // Update consecutive purchase days if needed
-    if player_data.last_purchased_day != current_day {
-        if player_data.last_purchased_day + 1 == current_day {
-            player_data.consecutive_purchased_days =
-                player_data.consecutive_purchased_days.safe_add(1)?;
-        } else {
-            player_data.consecutive_purchased_days = 1;
-        }
-    }
-    player_data.last_purchased_day = current_day;
-    // Mark player as not exited since they are making a new purchase
-    player_data.is_exited = false;
-    // Update team to reflect they are now in the current period
-    if team.current_period != current_period_key {
-        team.current_period_purchased_ores = 0;
-    }
-    team.current_period = current_period_key;
-
-    // Update game-level pools
-    if current_ores > 0 {
-        game.construction_rewards_pool_balance = game
-            .construction_rewards_pool_balance
-            .safe_add(construction_rewards)?;
-        game.bonus_rewards_pool_balance =
-            game.bonus_rewards_pool_balance.safe_add(bonus_rewards)?;
-    } else {
-        current_round.grand_prize_pool_balance = current_round
-            .grand_prize_pool_balance
-            .safe_add(construction_rewards)?
-            .safe_add(bonus_rewards)?;
-    }
-    game.lottery_rewards_pool_balance = game
-        .lottery_rewards_pool_balance
-        .safe_add(lottery_rewards)?;
-    if player_data.referrer != game.default_player {
-        game.referral_rewards_pool_balance = game
-            .referral_rewards_pool_balance
-            .safe_add(referral_rewards)?;
-    }
-
-    // Update round-level pools
-    current_round.grand_prize_pool_balance = current_round
-        .grand_prize_pool_balance
-        .safe_add(grand_prizes_rewards)?;
-
-    if current_ores > 0 {
-        // Update earnings_per_ore in the round
-        let available_ores = current_round.available_ores.max(1);
-        let earnings_per_ore_increment = construction_rewards.safe_div(available_ores as u64)?;
-        current_round.earnings_per_ore = current_round
-            .earnings_per_ore
-            .safe_add(earnings_per_ore_increment)?;
-    }
-
-    // Update round state: sold ORE, participant list, end time
-    current_round.available_ores = current_round.available_ores.safe_add(purchased_ores)?;
-    current_round.sold_ores = current_round.sold_ores.safe_add(purchased_ores)?;
-    current_round.update_last_active_participant_list(player.key())?;
-    current_round.update_end_time(timestamp)?;
+    // Split the payment across the reward pools
+    let distribution = distribute(
+        &DistributionInput {
+            total_cost,
+            consumption_base: token_cost,
+            has_referrer: player_data.referrer != game.default_player,
+            purchase_volume: player_data.purchase_volume(timestamp)?,
+            timestamp,
+        },
+        game_config,
+        game,
+        current_round,
+        referrer_data,
+    )?;
+    player_data.collectable_consumption_rewards = player_data
+        .collectable_consumption_rewards
+        .safe_add(distribution.consumption_rewards)?;
 
-    // Settle any pending construction rewards before adding newly purchased ORE
-    player_data.settle_collectable_construction_rewards(current_round.earnings_per_ore)?;
+    // Whether this is the player's first purchase in the round, earning them a round badge
+    let is_first_purchase_of_round = player_data.current_round != current_round.key();
 
-    // Update player ORE holdings and earnings rate
-    player_data.available_ores = player_data.available_ores.safe_add(purchased_ores)?;
-    player_data.purchased_ores = player_data.purchased_ores.safe_add(purchased_ores)?;
+    // Whether this is the player's first purchase of the day, counted as a unique buyer
+    let is_first_purchase_of_day = player_data.last_purchased_day != timestamp_to_days(timestamp)?;
 
-    if player_data.referrer != game.default_player {
-        // Add referral rewards to the referrer's pending rewards
-        referrer_data.collectable_referral_rewards = referrer_data
-            .collectable_referral_rewards
-            .safe_add(referral_rewards)?;
-    }
+    // Record the purchased ORE on the round, the player, their team and the leaderboards,
+    // boosting the construction rewards it settles by the player's stake
+    let (construction_rewards, boost) = record_purchased_ores(
+        &PurchaseRecord {
+            player: player.key(),
+            round: current_round.key(),
+            period: current_period.key(),
+            team: team.key(),
+            default_team: game.default_team,
+            purchased_ores,
+            total_cost,
+            timestamp,
+        },
+        game,
+        current_round,
+        &mut *current_period.load_mut()?,
+        player_data,
+        team,
+    )?;
 
-    // If the player is part of a team, update team ORE and period data
-    team.purchased_ores = team.purchased_ores.safe_add(purchased_ores)?;
-    team.last_updated_timestamp = timestamp;
-
-    // If the current period is ongoing, update leaderboards
-    if current_period.is_ongoing(timestamp) {
-        player_data.current_period_purchased_ores = player_data
-            .current_period_purchased_ores
-            .safe_add(purchased_ores)?;
-        current_period
-            .update_top_player(player.key(), player_data.current_period_purchased_ores)?;
-
-        team.current_period_purchased_ores = team
-            .current_period_purchased_ores
-            .safe_add(purchased_ores)?;
-        if player_data.team != game.default_team {
-            current_period.update_top_team_list(team.key(), team.current_period_purchased_ores)?;
-        }
-    }
+    if boost > 0 {
+        game.increment_event_nonce()?;
 
-    // If mining pool balance is enough, add developer rewards
-    if developer_rewards > 0 && game.consumption_rewards_pool_balance >= developer_rewards {
-        game.consumption_rewards_pool_balance = game
-            .consumption_rewards_pool_balance
-            .safe_sub(developer_rewards)?;
-        game.distributable_consumption_rewards = game
-            .distributable_consumption_rewards
-            .safe_sub(developer_rewards)?;
-        game.developer_rewards_pool_balance = game
-            .developer_rewards_pool_balance
-            .safe_add(developer_rewards)?;
-        msg!(
-            "Developer consumption pool increased by {}.",
-            developer_rewards
-        );
+        // Emit an event recording the boost
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::ConstructionRewardsBoosted as u16,
+            event_type: EventType::ConstructionRewardsBoosted,
+            event_nonce: game.event_nonce,
+            data: EventData::ConstructionRewardsBoosted {
+                player: player.key(),
+                construction_rewards,
+                boost_factor_bps: player_data.boost_factor_bps,
+                boost,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
     }
 
-    // If tokens are used (token_cost > 0), add consumption rewards
-    if consumption_rewards > 0 && game.distributable_consumption_rewards >= consumption_rewards {
-        game.distributable_consumption_rewards = game
-            .distributable_consumption_rewards
-            .safe_sub(consumption_rewards)?;
-        player_data.collectable_consumption_rewards = player_data
-            .collectable_consumption_rewards
-            .safe_add(consumption_rewards)?;
-        msg!(
-            "Player earned {} consumption rewards for spending {} tokens.",
-            consumption_rewards,
-            token_cost
-        );
+    // Mark player as not exited since they are making a new purchase
+    player_data.is_exited = false;
+    player_data.purchase_count = player_data.purchase_count.safe_add(1)?;
+    // Enforce the player's self-exclusion and daily ORE limit
+    player_data.record_spend_on_ores(purchased_ores, timestamp)?;
+
+    // Record the purchase on today's stats if they were passed
+    if let Some(daily_stats) = daily_stats {
+        daily_stats.require_day(timestamp)?;
+        daily_stats.record_purchase(
+            purchased_ores,
+            total_cost,
+            voucher_cost,
+            is_first_purchase_of_day,
+        )?;
     }
 
     // If vouchers are used to pay (voucher_cost > 0), burn them and redeem underlying tokens
     if voucher_cost > 0 {
         voucher.burn(voucher_cost)?;
 
+        // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+        let was_frozen = thaw_voucher_account_if_frozen(
+            voucher,
+            voucher_mint,
+            voucher_account,
+            token_program,
+            &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+        )?;
+
         burn(
             CpiContext::new(
                 token_program.to_account_info(),
@@ -368,34 +564,112 @@ pub fn purchase(ctx: Context<Purchase>, purchased_ores: u32) -> Result<()> {
             ),
             voucher_cost,
         )?;
+        refreeze_voucher_account(
+            voucher,
+            voucher_mint,
+            voucher_account,
+            token_program,
+            was_frozen,
+            &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+        )?;
+
+        game.record_burned_vouchers(voucher_cost)?;
+        game.increment_event_nonce()?;
+
+        // Emit an event recording the burn
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::Burn as u16,
+            event_type: EventType::Burn,
+            event_nonce: game.event_nonce,
+            data: EventData::Burn {
+                mint: voucher_mint.key(),
+                from: voucher_account.key(),
+                amount: voucher_cost,
+                reason: BurnReason::VoucherPayment,
+                total_burned: game.total_burned_vouchers,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
 
         redeem_vouchers(
             voucher,
+            ctx.bumps.voucher,
             voucher_vault,
             token_account,
+            token_mint,
             token_program,
             voucher_cost,
-            &[VOUCHER_SEED, &[ctx.bumps.voucher]],
         )?;
 
+        #[cfg(feature = "verbose-logs")]
         msg!(
             "Burned {} vouchers from the player's account.",
             voucher_cost
         );
     }
 
-    let mut transfer_to_game_vault_amount = lottery_rewards.safe_add(referral_rewards)?;
-
-    let mut transfer_to_round_vault_amount = grand_prizes_rewards;
-
-    if current_ores > 0 {
-        transfer_to_game_vault_amount = transfer_to_game_vault_amount
-            .safe_add(construction_rewards)?
-            .safe_add(bonus_rewards)?;
-    } else {
-        transfer_to_round_vault_amount = transfer_to_round_vault_amount
-            .safe_add(construction_rewards)?
-            .safe_add(bonus_rewards)?;
+    // If the voucher kind pays part of the cost, burn its vouchers and release the tokens backing them
+    if voucher_kind_burned > 0 {
+        if let (
+            Some(voucher_kind),
+            Some(voucher_kind_mint),
+            Some(voucher_kind_vault),
+            Some(voucher_kind_account),
+        ) = (
+            voucher_kind,
+            voucher_kind_mint,
+            voucher_kind_vault,
+            voucher_kind_account,
+        ) {
+            voucher_kind.burn(voucher_kind_burned)?;
+
+            burn(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    Burn {
+                        mint: voucher_kind_mint.to_account_info(),
+                        from: voucher_kind_account.to_account_info(),
+                        authority: player.to_account_info(),
+                    },
+                ),
+                voucher_kind_burned,
+            )?;
+
+            transfer_from_vault(
+                voucher_kind,
+                voucher_kind.bump,
+                voucher_kind_vault,
+                token_account,
+                token_mint,
+                token_program,
+                voucher_kind_value,
+            )?;
+
+            game.increment_event_nonce()?;
+
+            // Emit an event recording the burn
+            let event = TransferEvent {
+                event_schema_version: EVENT_SCHEMA_VERSION,
+                event_tag: EventType::Burn as u16,
+                event_type: EventType::Burn,
+                event_nonce: game.event_nonce,
+                data: EventData::Burn {
+                    mint: voucher_kind_mint.key(),
+                    from: voucher_kind_account.key(),
+                    amount: voucher_kind_burned,
+                    reason: BurnReason::VoucherKindPayment,
+                    total_burned: voucher_kind.total_burned,
+                },
+                initiator_type: InitiatorType::PLAYER,
+                initiator: player.key(),
+                timestamp,
+            };
+            record_event!(ctx, event);
+        }
     }
 
     // Transfer various pools' cost allocations to the appropriate vaults
@@ -404,11 +678,14 @@ pub fn purchase(ctx: Context<Purchase>, purchased_ores: u32) -> Result<()> {
         player,
         token_account,
         game_vault,
+        token_mint,
         token_program,
-        transfer_to_game_vault_amount,
+        distribution.game_vault_amount()?,
     )?;
 
-    if player_data.referrer == game.default_player {
+    // Burn the referral share of players without a referrer
+    let burned_referral_rewards = distribution.burned_referral_rewards();
+    if burned_referral_rewards > 0 {
         burn(
             CpiContext::new_with_signer(
                 token_program.to_account_info(),
@@ -419,8 +696,30 @@ pub fn purchase(ctx: Context<Purchase>, purchased_ores: u32) -> Result<()> {
                 },
                 &[&[GAME_SEED, &[ctx.bumps.game]]],
             ),
-            referral_rewards,
+            burned_referral_rewards,
         )?;
+
+        game.record_burned_tokens(burned_referral_rewards)?;
+        game.increment_event_nonce()?;
+
+        // Emit an event recording the burn
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::Burn as u16,
+            event_type: EventType::Burn,
+            event_nonce: game.event_nonce,
+            data: EventData::Burn {
+                mint: token_mint.key(),
+                from: game_vault.key(),
+                amount: burned_referral_rewards,
+                reason: BurnReason::ReferralRewards,
+                total_burned: game.total_burned_tokens,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
     }
 
     // Construction and grand prize rewards go to the round_vault
@@ -428,12 +727,15 @@ pub fn purchase(ctx: Context<Purchase>, purchased_ores: u32) -> Result<()> {
         player,
         token_account,
         round_vault,
+        token_mint,
         token_program,
-        transfer_to_round_vault_amount,
+        distribution.round_vault_amount()?,
     )?;
 
     // Emit an event recording the purchase
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Purchase as u16,
         event_type: EventType::Purchase,
         event_nonce: game.event_nonce,
         data: EventData::Purchase {
@@ -449,27 +751,157 @@ pub fn purchase(ctx: Context<Purchase>, purchased_ores: u32) -> Result<()> {
         initiator_type: InitiatorType::PLAYER,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
+
+    // Mint a round badge on the player's first purchase in the round, if the badge accounts were passed
+    let badge_accounts = match (
+        badge_tree_config.as_ref(),
+        badge_merkle_tree.as_ref(),
+        bubblegum_program.as_ref(),
+        log_wrapper.as_ref(),
+        compression_program.as_ref(),
+        system_program.as_ref(),
+    ) {
+        (None, None, None, None, None, None) => None,
+        (
+            Some(tree_config),
+            Some(merkle_tree),
+            Some(bubblegum_program),
+            Some(log_wrapper),
+            Some(compression_program),
+            Some(system_program),
+        ) => Some(RoundBadgeAccounts {
+            tree_config,
+            merkle_tree,
+            bubblegum_program,
+            log_wrapper,
+            compression_program,
+            system_program,
+        }),
+        _ => return err!(ErrorCode::InvalidRoundBadgeAccounts),
+    };
+    if let Some(badge_accounts) = badge_accounts {
+        if game.is_badge_enabled && is_first_purchase_of_round {
+            require_keys_eq!(
+                badge_accounts.merkle_tree.key(),
+                game.badge_merkle_tree,
+                ErrorCode::InvalidRoundBadgeAccounts
+            );
+
+            let entry_day = timestamp_to_days(timestamp)?;
+            mint_round_badge(
+                &badge_accounts,
+                game,
+                ctx.bumps.game,
+                player,
+                current_round.round_number,
+                entry_day,
+            )?;
+
+            game.increment_event_nonce()?;
+
+            let event = TransferEvent {
+                event_schema_version: EVENT_SCHEMA_VERSION,
+                event_tag: EventType::MintRoundBadge as u16,
+                event_type: EventType::MintRoundBadge,
+                event_nonce: game.event_nonce,
+                data: EventData::MintRoundBadge {
+                    player: player.key(),
+                    round: current_round.key(),
+                    round_number: current_round.round_number,
+                    entry_day,
+                    badge_merkle_tree: game.badge_merkle_tree,
+                },
+                initiator_type: InitiatorType::PLAYER,
+                initiator: player.key(),
+                timestamp,
+            };
+            record_event!(ctx, event);
+        }
+    }
+
+    // Log the round moving to another phase
+    let phase = current_round.phase(timestamp);
+    if phase != distribution.phase {
+        game.increment_event_nonce()?;
+
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::RoundPhaseChanged as u16,
+            event_type: EventType::RoundPhaseChanged,
+            event_nonce: game.event_nonce,
+            data: EventData::RoundPhaseChanged {
+                round: current_round.key(),
+                previous_phase: distribution.phase,
+                phase,
+                available_ores: current_round.available_ores,
+                end_time: current_round.end_time,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    // Log the countdown reaching a milestone
+    if let Some(milestone_seconds) = current_round.reach_countdown_milestone(timestamp) {
+        game.increment_event_nonce()?;
+
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::CountdownMilestone as u16,
+            event_type: EventType::CountdownMilestone,
+            event_nonce: game.event_nonce,
+            data: EventData::CountdownMilestone {
+                round: current_round.key(),
+                milestone_seconds,
+                end_time: current_round.end_time,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    #[cfg(feature = "strict-invariants")]
+    {
+        game_vault.reload()?;
+        round_vault.reload()?;
+        assert_invariants(
+            &invariant_snapshot,
+            game,
+            current_round,
+            game_vault.amount,
+            round_vault.amount,
+        )?;
+    }
 
     Ok(())
 }
 
 /// Handle round-end conditions if no ORE is purchased and the end_time has passed.
 /// This function checks certain Solana slot conditions and adjusts the round state accordingly.
+/// A period configured to end with the round is truncated, in which case its original start and end times are returned.
 fn handle_round_end(
     current_round: &mut Round,
     current_period: &mut Period,
     current_slot: u64,
     timestamp: u64,
-) -> Result<()> {
+) -> Result<Option<(u64, u64)>> {
     if current_slot < current_round.last_call_slot.safe_add(150)? {
-        msg!("Call count must be after 150 slots");
-        msg!(
-            "Current round last call slot: {}",
-            current_round.last_call_slot
-        );
-        msg!("Current slot: {}", current_slot);
-        return Ok(());
+        #[cfg(feature = "verbose-logs")]
+        {
+            msg!("Call count must be after 150 slots");
+            msg!(
+                "Current round last call slot: {}",
+                current_round.last_call_slot
+            );
+            msg!("Current slot: {}", current_slot);
+        }
+        return Ok(None);
     }
 
     current_round.last_call_slot = current_slot;
@@ -478,6 +910,14 @@ fn handle_round_end(
     // After a specific number of calls (e.g., 10), mark the round as over.
     if current_round.call_count >= 10 {
         current_round.is_over = true;
+
+        // Periods that outlive the round keep running until their own end time.
+        if current_period.ends_with_round == 0 {
+            return Ok(None);
+        }
+
+        let original_period_times = (current_period.start_time, current_period.end_time);
+
         // If the current period is ongoing, end it now.
         // If the period hasn't started (start_time > timestamp), adjust period times.
         if current_period.is_ongoing(timestamp) {
@@ -485,8 +925,12 @@ fn handle_round_end(
         } else if current_period.start_time > timestamp {
             current_period.start_time = timestamp;
             current_period.end_time = timestamp;
+        } else {
+            return Ok(None);
         }
+
+        return Ok(Some(original_period_times));
     }
 
-    Ok(())
+    Ok(None)
 }
@@ -0,0 +1,167 @@
+use crate::constants::EVENT_RING_BUFFER_SEED;
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::instructions::purchase::*;
+use crate::state::EventRingBuffer;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_safe_math::SafeMath;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token::{sync_native, SyncNative, Token};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::invoke;
+use solana_program::sysvar::clock::Clock;
+
+/// The `PurchaseWithSol` instruction lets a player buy ORE without holding the game token first.
+/// The player's SOL is wrapped and swapped for game tokens through the AMM pool whitelisted on the `Game` account,
+/// and the received tokens then pay for the ORE through the regular `Purchase` flow.
+///
+/// The swap accounts, in the order expected by the AMM program, are passed through `remaining_accounts`
+/// and must include the whitelisted `swap_pool`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PurchaseWithSol<'info> {
+    /// The accounts of the regular purchase, receiving the swapped tokens in `purchase.token_account`.
+    pub purchase: Purchase<'info>,
+
+    /// The player's wrapped SOL account, funded with the SOL to swap.
+    #[account(mut,
+        token::mint = native_mint,
+        token::authority = purchase.player,
+        token::token_program = wrapped_sol_token_program,
+    )]
+    pub wrapped_sol_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The native SOL mint.
+    #[account(address = native_mint::ID)]
+    pub native_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: The AMM program whitelisted on the game account.
+    #[account(executable, address = purchase.game.swap_program @ ErrorCode::SwapPoolNotConfigured)]
+    pub swap_program: UncheckedAccount<'info>,
+
+    /// The SPL token program owning the wrapped SOL account.
+    pub wrapped_sol_token_program: Program<'info, Token>,
+
+    /// The system program, used to fund the wrapped SOL account.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the SOL purchase:
+///
+/// Steps:
+/// 1. Verify the whitelisted swap pool is part of the swap accounts.
+/// 2. Wrap `sol_amount` lamports into the player's wrapped SOL account.
+/// 3. Swap through the whitelisted AMM with the player-provided `swap_data`.
+/// 4. Require at least `min_token_amount` game tokens to be received.
+/// 5. Emit a `PurchaseWithSol` event and hand over to the regular `Purchase` flow.
+pub fn purchase_with_sol<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PurchaseWithSol<'info>>,
+    purchased_ores: u32,
+    sol_amount: u64,
+    min_token_amount: u64,
+    swap_data: Vec<u8>,
+) -> Result<()> {
+    // Obtain current Solana time for event logging
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let remaining_accounts = ctx.remaining_accounts;
+
+    let PurchaseWithSol {
+        purchase,
+        wrapped_sol_account,
+        swap_program,
+        wrapped_sol_token_program,
+        system_program,
+        ..
+    } = ctx.accounts;
+
+    // Only the whitelisted pool may be traded against
+    let swap_pool = purchase.game.swap_pool;
+    require!(
+        purchase.game.swap_program != Pubkey::default(),
+        ErrorCode::SwapPoolNotConfigured
+    );
+    require!(
+        remaining_accounts
+            .iter()
+            .any(|account| account.key() == swap_pool),
+        ErrorCode::SwapPoolMismatch
+    );
+
+    // Wrap the SOL to swap
+    transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: purchase.player.to_account_info(),
+                to: wrapped_sol_account.to_account_info(),
+            },
+        ),
+        sol_amount,
+    )?;
+    sync_native(CpiContext::new(
+        wrapped_sol_token_program.to_account_info(),
+        SyncNative {
+            account: wrapped_sol_account.to_account_info(),
+        },
+    ))?;
+
+    // Swap the wrapped SOL for game tokens
+    let token_balance = purchase.token_account.amount;
+
+    let swap_instruction = Instruction {
+        program_id: swap_program.key(),
+        accounts: remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: swap_data,
+    };
+    let mut swap_account_infos = remaining_accounts.to_vec();
+    swap_account_infos.push(swap_program.to_account_info());
+    invoke(&swap_instruction, &swap_account_infos)?;
+
+    purchase.token_account.reload()?;
+    let token_amount = purchase.token_account.amount.safe_sub(token_balance)?;
+    require!(
+        token_amount >= min_token_amount,
+        ErrorCode::SwapSlippageExceeded
+    );
+
+    purchase.game.increment_event_nonce()?;
+
+    // Emit an event recording the swap
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::PurchaseWithSol as u16,
+        event_type: EventType::PurchaseWithSol,
+        event_nonce: purchase.game.event_nonce,
+        data: EventData::PurchaseWithSol {
+            player: purchase.player.key(),
+            swap_pool,
+            sol_amount,
+            token_amount,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: purchase.player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    // Pay for the ORE with the swapped tokens
+    crate::instructions::purchase::purchase(
+        Context::new(ctx.program_id, purchase, &[], ctx.bumps.purchase),
+        purchased_ores,
+    )
+}
@@ -0,0 +1,463 @@
+use crate::constants::{
+    BAN_ENTRY_SEED, EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, PLAYER_DATA_SEED,
+    SESSION_KEY_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+#[cfg(feature = "strict-invariants")]
+use crate::utils::{assert_invariants, InvariantSnapshot};
+use crate::utils::{
+    authorize_player_signer, distribute, record_purchased_ores, release_carryover,
+    timestamp_to_days, to_timestamp_u64, transfer_from_player_or_session_to_vault,
+    transfer_from_vault, DistributionInput, PurchaseRecord,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+use solana_program::sysvar::instructions as sysvar_instructions;
+
+/// The `PurchaseWithTokens` instruction is the lean variant of `Purchase` for players paying entirely in tokens.
+/// It leaves out the voucher accounts, reducing transaction size and compute for the common case;
+/// players spending vouchers, and the round-end calls purchasing zero ORE, go through `Purchase`.
+///
+/// Steps:
+/// 1. Validate that the current round has started and that a positive quantity of ORE is purchased.
+/// 2. Release construction rewards the player carried over from their previous round into their token account,
+///    then ensure the player's token balance covers the ORE purchase cost.
+/// 3. Calculate proportional allocations (construction, bonus, lottery, developer, referral, and grand prize pools) from the purchase amount.
+/// 4. Update round and game-level states, adjusting earnings_per_ore, sold_ores, participant lists, and round end time.
+/// 5. Manage player state: update consecutive purchase days, settle pending construction rewards, and adjust ORE holdings and earnings_per_ore.
+///    Records the purchase on today's stats if they were passed.
+///    The purchase counts against the player's daily ORE limit, and fails if they are self-excluded or banned.
+/// 6. Transfer funds from the player's token account to the game and round vaults.
+/// 7. Emit a `Purchase` event to record the transaction on-chain.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PurchaseWithTokens<'info> {
+    /// The wallet signing the transaction: the player, or the session signer of the player's `session_key`.
+    pub signer: Signer<'info>,
+
+    /// The player making the purchase.
+    /// CHECK: Must be the `signer`, or the player whose `session_key` authorizes the signer; checked in the handler.
+    pub player: UncheckedAccount<'info>,
+
+    /// The player's session key, required when a session signer signs on the player's behalf.
+    #[account(mut, seeds = [SESSION_KEY_SEED, player.key().as_ref()], bump)]
+    pub session_key: Option<Box<Account<'info, SessionKey>>>,
+
+    /// The player's data account, linking to their token account, and indicating their current team.
+    #[account(
+        mut,
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = token_account,
+        has_one = team,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The player's ban entry, which must not exist.
+    /// CHECK: Derived from the player; checked to be empty in the handler.
+    #[account(seeds = [BAN_ENTRY_SEED, player.key().as_ref()], bump)]
+    pub ban_entry: UncheckedAccount<'info>,
+
+    /// The referrer's data account, tracking pending referral rewards due to them.
+    #[account(
+        mut,
+        seeds = [PLAYER_DATA_SEED, player_data.referrer.as_ref()],
+        bump
+    )]
+    pub referrer_data: Box<Account<'info, PlayerData>>,
+
+    /// The global `Game` account, referencing current round, period, and main vault.
+    #[account(
+        mut,
+        seeds = [GAME_SEED],
+        bump,
+        has_one = current_round,
+        has_one = current_period,
+        has_one = game_vault,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game config holding the pool shares and the ORE cost.
+    #[account(seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// The current round account, must be active (not ended), referencing its `round_vault`.
+    #[account(
+        mut,
+        constraint = !current_round.is_over @ ErrorCode::RoundAlreadyEnded,
+        has_one = round_vault,
+    )]
+    pub current_round: Box<Account<'info, Round>>,
+
+    /// The current period account representing a leaderboard period.
+    #[account(mut)]
+    pub current_period: AccountLoader<'info, Period>,
+
+    /// The team account the player belongs to, or the default team if none.
+    #[account(mut)]
+    pub team: Box<Account<'info, Team>>,
+
+    /// The main game vault holding the platform's aggregated funds.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The round-specific vault holding tokens allocated for the current round.
+    #[account(mut)]
+    pub round_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's token account paying the purchase cost.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint account used for issuing and burning token tokens.
+    #[account(mut, address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL Token program used for token operations like burning and transfers.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Today's stats account, recording the purchase when passed.
+    #[account(mut)]
+    pub daily_stats: Option<Box<Account<'info, DailyStats>>>,
+
+    /// CHECK: The instructions sysvar, required when the game gates the instruction behind a compliance attestation,
+    /// verified by the ed25519 program instruction preceding it.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Handles the `PurchaseWithTokens` logic, following the `Purchase` flow with the whole cost paid in tokens.
+pub fn purchase_with_tokens(ctx: Context<PurchaseWithTokens>, purchased_ores: u32) -> Result<()> {
+    // Obtain current Solana time for logic and event logging
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Check the signer acts for the player, directly or through their session key
+    let is_session = authorize_player_signer(
+        ctx.accounts.player.key(),
+        ctx.accounts.signer.key(),
+        ctx.accounts
+            .session_key
+            .as_deref()
+            .map(|session_key| &**session_key),
+        SESSION_SCOPE_PURCHASE,
+        timestamp,
+    )?;
+
+    // Reject banned wallets
+    BanEntry::require_not_banned(&ctx.accounts.ban_entry)?;
+
+    // Compliance-gated deployments require an eligibility attestation
+    ctx.accounts.game.require_compliance_attestation(
+        COMPLIANCE_GATE_PURCHASE,
+        &ctx.accounts.player.key(),
+        ctx.accounts.instructions_sysvar.as_deref(),
+        timestamp,
+    )?;
+
+    // Extract references for clarity
+    let PurchaseWithTokens {
+        signer,
+        player,
+        session_key,
+        player_data,
+        token_account,
+        game,
+        game_config,
+        game_vault,
+        current_round,
+        round_vault,
+        current_period,
+        team,
+        referrer_data,
+        token_mint,
+        token_program,
+        daily_stats,
+        ..
+    } = ctx.accounts;
+
+    // Capture the monotonic counters checked by the invariants at the end of the instruction
+    #[cfg(feature = "strict-invariants")]
+    let invariant_snapshot = InvariantSnapshot::take(game, current_round);
+
+    // Validate that the current round is active (has started)
+    require!(
+        current_round.start_time <= timestamp,
+        ErrorCode::RoundNotStarted
+    );
+
+    // Ensure a positive ORE purchase quantity
+    require!(
+        purchased_ores > 0,
+        ErrorCode::PurchaseQuantityMustGreaterThanZero
+    );
+
+    // The player must have settled previous rounds or must already be in this current round,
+    // and must have re-entered it after exiting it
+    player_data.require_can_join_round(current_round.key())?;
+
+    // Spend construction rewards carried over from the player's previous round first
+    let carryover = release_carryover(game, player_data)?;
+    if carryover > 0 {
+        transfer_from_vault(
+            game,
+            ctx.bumps.game,
+            game_vault,
+            token_account,
+            token_mint,
+            token_program,
+            carryover,
+        )?;
+        token_account.reload()?;
+    }
+
+    // Calculate total cost in lamports for the requested ORE quantity
+    let total_cost = game_config
+        .lamports_per_ore
+        .safe_mul(purchased_ores as u64)?;
+
+    // The whole cost is paid in tokens
+    let token_cost = total_cost;
+    require!(
+        token_account.amount >= token_cost,
+        ErrorCode::InsufficientFundsToPayFee
+    );
+
+    // Split the payment across the reward pools
+    let distribution = distribute(
+        &DistributionInput {
+            total_cost,
+            consumption_base: token_cost,
+            has_referrer: player_data.referrer != game.default_player,
+            purchase_volume: player_data.purchase_volume(timestamp)?,
+            timestamp,
+        },
+        game_config,
+        game,
+        current_round,
+        referrer_data,
+    )?;
+    player_data.collectable_consumption_rewards = player_data
+        .collectable_consumption_rewards
+        .safe_add(distribution.consumption_rewards)?;
+
+    // Whether this is the player's first purchase of the day, counted as a unique buyer
+    let is_first_purchase_of_day = player_data.last_purchased_day != timestamp_to_days(timestamp)?;
+
+    // Record the purchased ORE on the round, the player, their team and the leaderboards,
+    // boosting the construction rewards it settles by the player's stake
+    let (construction_rewards, boost) = record_purchased_ores(
+        &PurchaseRecord {
+            player: player.key(),
+            round: current_round.key(),
+            period: current_period.key(),
+            team: team.key(),
+            default_team: game.default_team,
+            purchased_ores,
+            total_cost,
+            timestamp,
+        },
+        game,
+        current_round,
+        &mut *current_period.load_mut()?,
+        player_data,
+        team,
+    )?;
+
+    if boost > 0 {
+        game.increment_event_nonce()?;
+
+        // Emit an event recording the boost
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::ConstructionRewardsBoosted as u16,
+            event_type: EventType::ConstructionRewardsBoosted,
+            event_nonce: game.event_nonce,
+            data: EventData::ConstructionRewardsBoosted {
+                player: player.key(),
+                construction_rewards,
+                boost_factor_bps: player_data.boost_factor_bps,
+                boost,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    // Mark player as not exited since they are making a new purchase
+    player_data.is_exited = false;
+    player_data.purchase_count = player_data.purchase_count.safe_add(1)?;
+    // Enforce the player's self-exclusion and daily ORE limit
+    player_data.record_spend_on_ores(purchased_ores, timestamp)?;
+
+    // Record the purchase on today's stats if they were passed
+    if let Some(daily_stats) = daily_stats {
+        daily_stats.require_day(timestamp)?;
+        daily_stats.record_purchase(purchased_ores, total_cost, 0, is_first_purchase_of_day)?;
+    }
+
+    // Count session purchases against the session's daily limit, and let the session key pay as delegate
+    let payer_session_key = match session_key {
+        Some(session_key) if is_session => {
+            session_key.record_purchased_ores(purchased_ores, timestamp)?;
+            Some(&**session_key)
+        }
+        _ => None,
+    };
+
+    // Transfer various pools' cost allocations to the appropriate vaults
+    // Bonus, lottery, developer, and referral rewards go to the game_vault
+    transfer_from_player_or_session_to_vault(
+        signer,
+        payer_session_key,
+        token_account,
+        game_vault,
+        token_mint,
+        token_program,
+        distribution.game_vault_amount()?,
+    )?;
+
+    // Burn the referral share of players without a referrer
+    let burned_referral_rewards = distribution.burned_referral_rewards();
+    if burned_referral_rewards > 0 {
+        burn(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Burn {
+                    mint: token_mint.to_account_info(),
+                    from: game_vault.to_account_info(),
+                    authority: game.to_account_info(),
+                },
+                &[&[GAME_SEED, &[ctx.bumps.game]]],
+            ),
+            burned_referral_rewards,
+        )?;
+
+        game.record_burned_tokens(burned_referral_rewards)?;
+        game.increment_event_nonce()?;
+
+        // Emit an event recording the burn
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::Burn as u16,
+            event_type: EventType::Burn,
+            event_nonce: game.event_nonce,
+            data: EventData::Burn {
+                mint: token_mint.key(),
+                from: game_vault.key(),
+                amount: burned_referral_rewards,
+                reason: BurnReason::ReferralRewards,
+                total_burned: game.total_burned_tokens,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    // Construction and grand prize rewards go to the round_vault
+    transfer_from_player_or_session_to_vault(
+        signer,
+        payer_session_key,
+        token_account,
+        round_vault,
+        token_mint,
+        token_program,
+        distribution.round_vault_amount()?,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the purchase
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Purchase as u16,
+        event_type: EventType::Purchase,
+        event_nonce: game.event_nonce,
+        data: EventData::Purchase {
+            game: game.key(),
+            round: current_round.key(),
+            period: current_period.key(),
+            player: player.key(),
+            referrer: player_data.referrer,
+            team: team.key(),
+            purchased_ores,
+            voucher: Pubkey::default(),
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    // Log the round moving to another phase
+    let phase = current_round.phase(timestamp);
+    if phase != distribution.phase {
+        game.increment_event_nonce()?;
+
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::RoundPhaseChanged as u16,
+            event_type: EventType::RoundPhaseChanged,
+            event_nonce: game.event_nonce,
+            data: EventData::RoundPhaseChanged {
+                round: current_round.key(),
+                previous_phase: distribution.phase,
+                phase,
+                available_ores: current_round.available_ores,
+                end_time: current_round.end_time,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    // Log the countdown reaching a milestone
+    if let Some(milestone_seconds) = current_round.reach_countdown_milestone(timestamp) {
+        game.increment_event_nonce()?;
+
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::CountdownMilestone as u16,
+            event_type: EventType::CountdownMilestone,
+            event_nonce: game.event_nonce,
+            data: EventData::CountdownMilestone {
+                round: current_round.key(),
+                milestone_seconds,
+                end_time: current_round.end_time,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    #[cfg(feature = "strict-invariants")]
+    {
+        game_vault.reload()?;
+        round_vault.reload()?;
+        assert_invariants(
+            &invariant_snapshot,
+            game,
+            current_round,
+            game_vault.amount,
+            round_vault.amount,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{calculate_accrued_earnings, to_timestamp_u64, transfer_from_vault};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `RedeemPosition` instruction lets the holder of a round position token redeem it.
+/// The token is burned and the `RoundPosition` closed. While the round is ongoing, the position's ORE and
+/// pending construction rewards are merged into the holder's `PlayerData`; once the round is over, the
+/// position is settled like `settle_previous_round` and its construction rewards are paid to the holder.
+/// The exit rewards the position's ORE accrued while tokenized follow the ORE: they are merged into the
+/// holder's collectable exit rewards while the round is ongoing, and forfeited once it is over.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RedeemPosition<'info> {
+    /// The holder of the position token. Must sign the transaction and receives the position's rent.
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// The holder's data account, receiving the position's ORE or recording its settled rewards.
+    #[account(mut,
+        seeds = [PLAYER_DATA_SEED, holder.key().as_ref()],
+        bump,
+        has_one = token_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The holder's token account receiving the settled rewards.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The position being redeemed, closed to the holder.
+    #[account(
+        mut,
+        has_one = round,
+        has_one = position_mint,
+        close = holder,
+    )]
+    pub round_position: Box<Account<'info, RoundPosition>>,
+
+    /// The round the position belongs to.
+    #[account(mut)]
+    pub round: Box<Account<'info, Round>>,
+
+    /// The mint of the position token.
+    #[account(mut)]
+    pub position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The holder's token account holding the position token, burned on redemption.
+    #[account(mut,
+        associated_token::mint = position_mint,
+        associated_token::authority = holder,
+        associated_token::token_program = token_program
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global game account, holding the construction rewards pool.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = game_vault)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The main game vault paying the settled rewards.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program, required for the burn and the transfer.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Redeems a round position:
+///
+/// Steps:
+/// 1. Burn the holder's position token.
+/// 2. Compute the construction rewards the position accrued since its checkpoint.
+/// 3. If the round is ongoing, merge the ORE and the accrued construction and exit rewards into the holder's
+///    holdings in that round.
+/// 4. If the round is over, remove the ORE from the round and pay the accrued rewards to the holder.
+/// 5. Emit a `RedeemPosition` event.
+pub fn redeem_position(ctx: Context<RedeemPosition>) -> Result<()> {
+    // Fetch the current UNIX timestamp for the event
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let RedeemPosition {
+        holder,
+        player_data,
+        token_account,
+        round_position,
+        round,
+        position_mint,
+        position_token_account,
+        game,
+        game_vault,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    burn(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Burn {
+                mint: position_mint.to_account_info(),
+                from: position_token_account.to_account_info(),
+                authority: holder.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    let ores = round_position.ores;
+    let construction_rewards = calculate_accrued_earnings(
+        round
+            .earnings_per_ore
+            .safe_sub(round_position.earnings_per_ore)?,
+        ores,
+    )?;
+    let is_settled = round.is_over;
+
+    if !is_settled {
+        // The holder must have settled previous rounds or already be in this round,
+        // and must have re-entered it after exiting it
+        player_data.require_can_join_round(round.key())?;
+
+        // Settle the holder's earnings before their holdings grow, then merge the position
+        round.accrue_exit_rewards(timestamp)?;
+        player_data.settle_collectable_construction_rewards(round.earnings_per_ore)?;
+        player_data.settle_collectable_exit_rewards(round.exit_rewards_per_ore)?;
+        let exit_rewards = calculate_accrued_earnings(
+            round
+                .exit_rewards_per_ore
+                .safe_sub(round_position.exit_rewards_per_ore)?,
+            ores,
+        )?;
+        player_data.current_round = round.key();
+        player_data.is_exited = false;
+        player_data.available_ores = player_data.available_ores.safe_add(ores)?;
+        player_data.collectable_construction_rewards = player_data
+            .collectable_construction_rewards
+            .safe_add(construction_rewards)?;
+        player_data.collectable_exit_rewards = player_data
+            .collectable_exit_rewards
+            .safe_add(exit_rewards)?;
+    } else {
+        // Deduct the rewards from the construction pool and the ORE from the round
+        game.construction_rewards_pool_balance = game
+            .construction_rewards_pool_balance
+            .safe_sub(construction_rewards)?;
+        game.distributed_construction_rewards = game
+            .distributed_construction_rewards
+            .safe_add(construction_rewards)?;
+
+        require!(round.available_ores >= ores, RoundError::InsufficientOres);
+        round.available_ores = round.available_ores.safe_sub(ores)?;
+
+        player_data.collected_construction_rewards = player_data
+            .collected_construction_rewards
+            .safe_add(construction_rewards)?;
+
+        transfer_from_vault(
+            game,
+            ctx.bumps.game,
+            game_vault,
+            token_account,
+            token_mint,
+            token_program,
+            construction_rewards,
+        )?;
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the redemption
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::RedeemPosition as u16,
+        event_type: EventType::RedeemPosition,
+        event_nonce: game.event_nonce,
+        data: EventData::RedeemPosition {
+            round: round.key(),
+            position: round_position.key(),
+            holder: holder.key(),
+            ores,
+            construction_rewards,
+            is_settled,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: holder.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
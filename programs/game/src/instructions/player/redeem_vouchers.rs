@@ -0,0 +1,221 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    self, calculate_proportion, refreeze_voucher_account, thaw_voucher_account_if_frozen,
+    to_timestamp_u64,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `RedeemVouchers` instruction lets a player convert vouchers back into the underlying tokens.
+///
+/// Steps:
+/// 1. Validate the amount and the player's voucher balance.
+/// 2. Burn the vouchers from the player's voucher account.
+/// 3. Release the underlying tokens from the `voucher_vault` to the player's token account, withholding
+///    the `redemption_fee_rate` share into the game vault as developer rewards.
+/// 4. Emit a `RedeemVouchers` event to record the action on-chain.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RedeemVouchers<'info> {
+    /// The player redeeming vouchers. Must sign the transaction.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The player's data account, linking the player to their token and voucher accounts.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = token_account,
+        has_one = voucher_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The player's token account, receiving the underlying tokens.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's voucher account from which the vouchers are burned.
+    #[account(mut)]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global game account, holding the developer rewards pool credited with the redemption fee.
+    #[account(
+        mut,
+        seeds = [GAME_SEED],
+        bump,
+        has_one = game_vault,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The main game vault receiving the redemption fee.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The voucher state account managing voucher mint authority and supply.
+    #[account(
+        mut,
+        seeds = [VOUCHER_SEED], bump,
+        has_one = voucher_vault,
+    )]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher vault token account holding the underlying assets backing voucher tokens.
+    #[account(mut)]
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The voucher mint account used to burn voucher tokens.
+    #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The SPL Token program used for burning and transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+pub fn redeem_vouchers(ctx: Context<RedeemVouchers>, amount: u64) -> Result<()> {
+    // Retrieve the current cluster time for event logging
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references for clarity
+    let RedeemVouchers {
+        token_mint,
+        player,
+        token_account,
+        voucher_account,
+        game,
+        game_vault,
+        voucher,
+        voucher_vault,
+        voucher_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        voucher_account.amount >= amount,
+        ErrorCode::InsufficientVoucherBalance
+    );
+
+    // Split the underlying tokens between the player and the redemption fee
+    let fee_amount = calculate_proportion(amount, voucher.redemption_fee_rate)?;
+    let token_amount = amount.safe_sub(fee_amount)?;
+
+    // Burn the vouchers from the player's voucher account
+    voucher.burn(amount)?;
+
+    // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    burn(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Burn {
+                mint: voucher_mint.to_account_info(),
+                from: voucher_account.to_account_info(),
+                authority: player.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    game.record_burned_vouchers(amount)?;
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the burn
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Burn as u16,
+        event_type: EventType::Burn,
+        event_nonce: game.event_nonce,
+        data: EventData::Burn {
+            mint: voucher_mint.key(),
+            from: voucher_account.key(),
+            amount,
+            reason: BurnReason::VoucherRedemption,
+            total_burned: game.total_burned_vouchers,
+        },
+        initiator_type: InitiatorType::VOUCHER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    // Release the underlying tokens to the player
+    utils::redeem_vouchers(
+        voucher,
+        ctx.bumps.voucher,
+        voucher_vault,
+        token_account,
+        token_mint,
+        token_program,
+        token_amount,
+    )?;
+
+    // Withhold the redemption fee in the game vault as developer rewards
+    if fee_amount > 0 {
+        utils::redeem_vouchers(
+            voucher,
+            ctx.bumps.voucher,
+            voucher_vault,
+            game_vault,
+            token_mint,
+            token_program,
+            fee_amount,
+        )?;
+
+        game.developer_rewards_pool_balance =
+            game.developer_rewards_pool_balance.safe_add(fee_amount)?;
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit an event capturing the redemption
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::RedeemVouchers as u16,
+        event_type: EventType::RedeemVouchers,
+        event_nonce: game.event_nonce,
+        data: EventData::RedeemVouchers {
+            player: player.key(),
+            voucher: voucher.key(),
+            voucher_amount: amount,
+            token_amount,
+            fee_amount,
+        },
+        initiator_type: InitiatorType::VOUCHER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
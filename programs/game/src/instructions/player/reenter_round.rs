@@ -0,0 +1,140 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_player_to_vault};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `ReenterRound` instruction lets a player who exited the current round come back into it.
+/// The player pays the game's `reentry_fee` into the round's grand prize pool, and restarts earning from
+/// the round's current earnings and exit rewards per ORE, keeping their purchase streak and counters.
+/// Purchases in the round are rejected with `NeedToReenterRound` until the player re-enters.
+///
+/// Steps:
+/// 1. Validate that the round has started and that the player exited it.
+/// 2. Transfer the re-entry fee from the player's token account to the round vault, adding it to the grand prize pool.
+/// 3. Bring the player back into the round.
+/// 4. Emit a `ReenterRound` event to record the action on-chain.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReenterRound<'info> {
+    /// The player re-entering the round. Must sign the transaction.
+    pub player: Signer<'info>,
+
+    /// The player's data account. The player must have exited the current round.
+    #[account(mut,
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = token_account,
+        has_one = current_round @ ErrorCode::PlayerNotExitedFromRound,
+        constraint = player_data.is_exited @ ErrorCode::PlayerNotExitedFromRound,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The global game account referencing the current round and the re-entry fee.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = current_round)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The current round, which must still be ongoing, referencing its `round_vault`.
+    #[account(
+        mut,
+        constraint = !current_round.is_over @ ErrorCode::RoundAlreadyEnded,
+        has_one = round_vault,
+    )]
+    pub current_round: Box<Account<'info, Round>>,
+
+    /// The round vault receiving the re-entry fee.
+    #[account(mut)]
+    pub round_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's token account paying the re-entry fee.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program used for the fee transfer.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Handles the `ReenterRound` logic, charging the re-entry fee and bringing the player back into the round.
+pub fn reenter_round(ctx: Context<ReenterRound>) -> Result<()> {
+    // Obtain current Solana time for logic and event logging
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let ReenterRound {
+        player,
+        player_data,
+        game,
+        current_round,
+        round_vault,
+        token_account,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    // Validate that the current round is active (has started)
+    require!(
+        current_round.start_time <= timestamp,
+        ErrorCode::RoundNotStarted
+    );
+
+    // Pay the re-entry fee into the grand prize pool
+    let reentry_fee = game.reentry_fee;
+    if reentry_fee > 0 {
+        require!(
+            token_account.amount >= reentry_fee,
+            ErrorCode::InsufficientFundsToPayFee
+        );
+        current_round.grand_prize_pool_balance = current_round
+            .grand_prize_pool_balance
+            .safe_add(reentry_fee)?;
+        transfer_from_player_to_vault(
+            player,
+            token_account,
+            round_vault,
+            token_mint,
+            token_program,
+            reentry_fee,
+        )?;
+    }
+
+    // Bring the player back, earning only what accrues to the round from now on
+    current_round.accrue_exit_rewards(timestamp)?;
+    player_data.reenter_round(
+        current_round.earnings_per_ore,
+        current_round.exit_rewards_per_ore,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the re-entry
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::ReenterRound as u16,
+        event_type: EventType::ReenterRound,
+        event_nonce: game.event_nonce,
+        data: EventData::ReenterRound {
+            round: current_round.key(),
+            player: player.key(),
+            reentry_fee,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
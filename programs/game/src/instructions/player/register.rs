@@ -1,14 +1,18 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED, TOKEN_MINT, VOUCHER_MINT_SEED, VOUCHER_SEED};
+use crate::constants::{
+    BAN_ENTRY_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, VOUCHER_MINT_SEED,
+    VOUCHER_SEED,
+};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
-use crate::utils::transfer_from_token_vault_to_token_account;
+use crate::utils::transfer_from_vault;
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, mint_to, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
+use solana_program::sysvar::instructions as sysvar_instructions;
 
 /// The `Register` instruction handles the onboarding process for a new player in the game ecosystem.
 /// Upon registration, the player links their wallet, initializes their player data account,
@@ -18,10 +22,12 @@ use solana_program::sysvar::clock::Clock;
 /// 1. Validate the referrer is not the player themselves (no self-referral).
 /// 2. Initialize a new `PlayerData` account, associating it with the player's `token_account` and `voucher_account`.
 /// 3. Increment the referrer's referral count.
-/// 4. If registration reward slots are still available, distribute the registration reward to the player's voucher account:
+/// 4. If registration reward slots are still available and the player passes the game's `RegistrationGate`, distribute the registration reward,
+///    decayed by the game's registration reward schedule, to the player's voucher account:
 ///    - Deduct from `registration_rewards_pool_balance` and update `distributed_registration_rewards`.
 ///    - Mint voucher tokens corresponding to the registration reward and transfer underlying tokens from the `game_vault` to `voucher_vault`.
 /// 5. Emit a `Register` event to log the new player onboarding action.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(referrer: Pubkey)]
 pub struct Register<'info> {
@@ -43,6 +49,11 @@ pub struct Register<'info> {
     )]
     pub player_data: Box<Account<'info, PlayerData>>,
 
+    /// The player's ban entry, which must not exist.
+    /// CHECK: Derived from the player; checked to be empty in the handler.
+    #[account(seeds = [BAN_ENTRY_SEED, player.key().as_ref()], bump)]
+    pub ban_entry: UncheckedAccount<'info>,
+
     /// The referrer's data account, from which we increment the referral count upon a successful registration.
     #[account(
         mut,
@@ -61,28 +72,30 @@ pub struct Register<'info> {
     pub game: Box<Account<'info, Game>>,
 
     /// The token mint representing the in-game token currency.
-    #[account(address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The voucher mint used for generating and distributing voucher tokens.
     #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
-    pub voucher_mint: Box<Account<'info, Mint>>,
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The player's associated token account for the in-game token. Created if it doesn't exist.
     #[account(
         associated_token::mint = token_mint,
-        associated_token::authority = player
+        associated_token::authority = player,
+        associated_token::token_program = token_program
     )]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The player's associated voucher account, created if needed.
     #[account(
         init_if_needed,
         payer = player,
         associated_token::mint = voucher_mint,
-        associated_token::authority = player
+        associated_token::authority = player,
+        associated_token::token_program = token_program
     )]
-    pub voucher_account: Box<Account<'info, TokenAccount>>,
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The global voucher state, controlling voucher mint authority and linking to `voucher_vault`.
     #[account(
@@ -94,21 +107,29 @@ pub struct Register<'info> {
 
     /// The voucher vault token account holding underlying assets backing the voucher tokens.
     #[account(mut)]
-    pub voucher_vault: Box<Account<'info, TokenAccount>>,
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The main game vault token account holding tokens for rewards and distributions.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The SPL token program enabling minting, burning, and transfer operations.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The Associated Token program used to create associated token accounts for the player.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The System program for basic Solana operations like account creation.
     pub system_program: Program<'info, System>,
+
+    /// CHECK: The instructions sysvar, required when the registration rewards are gated by an attestation,
+    /// verified by the ed25519 program instruction preceding the registration.
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Executes the registration logic:
@@ -120,8 +141,12 @@ pub fn register(ctx: Context<Register>, referrer: Pubkey) -> Result<()> {
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
+    // Reject banned wallets
+    BanEntry::require_not_banned(&ctx.accounts.ban_entry)?;
+
     // Extract references for clarity
     let Register {
+        token_mint,
         player,
         player_data,
         referrer_data,
@@ -134,6 +159,7 @@ pub fn register(ctx: Context<Register>, referrer: Pubkey) -> Result<()> {
         voucher_vault,
         game_vault,
         token_program,
+        instructions_sysvar,
         ..
     } = ctx.accounts;
 
@@ -149,31 +175,46 @@ pub fn register(ctx: Context<Register>, referrer: Pubkey) -> Result<()> {
     // Increment the referrer's referral count
     referrer_data.increment_referral_count()?;
 
+    // Only players passing the registration gate receive the registration rewards
+    let is_gate_passed = game.is_registration_gate_passed(
+        &player.key(),
+        token_account.amount,
+        referrer_data,
+        instructions_sysvar.as_deref(),
+    )?;
+    if !is_gate_passed {
+        msg!("Registration gate not passed, no registration rewards");
+    }
+
+    // The registration rewards decay as more of them are distributed
+    let registration_rewards = game.current_registration_rewards()?;
+
     // Check if registration rewards are still available and distribute if yes
-    if game.registration_rewards_pool_balance >= game.registration_rewards {
+    if is_gate_passed && game.registration_rewards_pool_balance >= registration_rewards {
         require!(
-            game.registration_rewards <= game.registration_rewards_pool_balance,
+            registration_rewards <= game.registration_rewards_pool_balance,
             ErrorCode::InsufficientRegistrationRewardBalance
         );
         // Deduct from registration pool and update distributed amount
         game.registration_rewards_pool_balance = game
             .registration_rewards_pool_balance
-            .safe_sub(game.registration_rewards)?;
+            .safe_sub(registration_rewards)?;
         game.distributed_registration_rewards = game
             .distributed_registration_rewards
-            .safe_add(game.registration_rewards)?;
+            .safe_add(registration_rewards)?;
 
         // Mint voucher tokens for the registration reward
-        voucher.mint(game.registration_rewards)?;
+        voucher.mint(registration_rewards)?;
 
         // Transfer the underlying tokens from the game vault to the voucher vault
-        transfer_from_token_vault_to_token_account(
+        transfer_from_vault(
             game,
+            ctx.bumps.game,
             game_vault,
             voucher_vault,
+            token_mint,
             token_program,
-            game.registration_rewards,
-            &[GAME_SEED, &[ctx.bumps.game]],
+            registration_rewards,
         )?;
 
         mint_to(
@@ -186,14 +227,16 @@ pub fn register(ctx: Context<Register>, referrer: Pubkey) -> Result<()> {
                 },
                 &[&[VOUCHER_SEED, &[ctx.bumps.voucher]]],
             ),
-            game.registration_rewards,
+            registration_rewards,
         )?;
     }
 
     game.increment_event_nonce()?;
 
     // Emit a Register event to record the player's onboarding
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Register as u16,
         event_type: EventType::Register,
         event_nonce: game.event_nonce,
         data: EventData::Register {
@@ -204,7 +247,8 @@ pub fn register(ctx: Context<Register>, referrer: Pubkey) -> Result<()> {
         initiator_type: InitiatorType::PLAYER,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
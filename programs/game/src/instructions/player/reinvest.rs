@@ -1,19 +1,21 @@
 use crate::constants::{
-    CONSTRUCTION_POOL_SHARE, CONSUMPTION_POOL_SHARE, GAME_SEED, GRAND_PRIZES_POOL_SHARE,
-    LAMPORTS_PER_ORE, LOTTERY_POOL_SHARE, PLAYER_DATA_SEED, REFERRAL_POOL_SHARE, TOKEN_MINT,
+    BAN_ENTRY_SEED, EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, PLAYER_DATA_SEED,
 };
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
+#[cfg(feature = "strict-invariants")]
+use crate::utils::{assert_invariants, InvariantSnapshot};
 use crate::utils::{
-    calculate_proportion, timestamp_to_days, to_timestamp_u64,
-    transfer_from_token_vault_to_token_account,
+    distribute, record_purchased_ores, to_timestamp_u64, transfer_from_vault, DistributionInput,
+    PurchaseRecord,
 };
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
-use anchor_spl::token::{self, burn, Burn, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Reinvest<'info> {
     /// The player initiating the reinvest action. Must sign the transaction.
@@ -35,6 +37,11 @@ pub struct Reinvest<'info> {
     )]
     pub player_data: Box<Account<'info, PlayerData>>,
 
+    /// The player's ban entry, which must not exist.
+    /// CHECK: Derived from the player; checked to be empty in the handler.
+    #[account(seeds = [BAN_ENTRY_SEED, player.key().as_ref()], bump)]
+    pub ban_entry: UncheckedAccount<'info>,
+
     /// The team account the player belongs to, or the default team if none.
     #[account(mut)]
     pub team: Box<Account<'info, Team>>,
@@ -56,25 +63,32 @@ pub struct Reinvest<'info> {
     )]
     pub game: Box<Account<'info, Game>>,
 
+    /// The game config holding the pool shares and the ORE cost.
+    #[account(seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
     /// The current period account representing a leaderboard period.
     #[account(mut)]
-    pub current_period: Box<Account<'info, Period>>,
+    pub current_period: AccountLoader<'info, Period>,
 
     /// The main game vault holding tokens for various pools and distributions.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The round-specific vault holding tokens allocated to the current round.
     #[account(mut)]
-    pub round_vault: Box<Account<'info, TokenAccount>>,
+    pub round_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The token mint account used for issuing and burning token tokens.
-    #[account(mut, address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(mut, address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The SPL token program enabling token transfers and interactions.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// The `reinvest` instruction allows a player to use their accumulated pending construction rewards
@@ -83,8 +97,8 @@ pub struct Reinvest<'info> {
 /// Steps:
 /// 1. Validate that the round is active and the player is currently participating in it.
 /// 2. Settle any pending construction rewards based on the current `earnings_per_ore`.
-/// 3. Convert the player's pending rewards into ORE (based on LAMPORTS_PER_ORE).
-/// 4. Ensure that the conversion results in at least one ORE to be purchased.
+/// 3. Convert the player's pending rewards into ORE (based on the game config's lamports_per_ore).
+/// 4. Ensure that the conversion results in at least one ORE to be purchased, within the player's self-exclusion and daily ORE limit.
 /// 5. From the total cost of these ORE, calculate proportional allocations to various pools (construction, bonus, lottery, grand prizes).
 /// 6. Update the round and game account balances accordingly, adjusting `earnings_per_ore`, `available_ores`, and possibly round timing.
 /// 7. Update the player's ORE holdings and earnings rate reference.
@@ -96,10 +110,14 @@ pub fn reinvest(ctx: Context<Reinvest>) -> Result<()> {
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
+    // Reject banned wallets
+    BanEntry::require_not_banned(&ctx.accounts.ban_entry)?;
+
     let Reinvest {
         player,
         player_data,
         game,
+        game_config,
         current_round,
         current_period,
         round_vault,
@@ -111,6 +129,10 @@ pub fn reinvest(ctx: Context<Reinvest>) -> Result<()> {
         ..
     } = ctx.accounts;
 
+    // Capture the monotonic counters checked by the invariants at the end of the instruction
+    #[cfg(feature = "strict-invariants")]
+    let invariant_snapshot = InvariantSnapshot::take(game, current_round);
+
     // Ensure the round has started (player cannot reinvest before the round's start_time)
     require!(
         current_round.start_time <= timestamp,
@@ -132,7 +154,9 @@ pub fn reinvest(ctx: Context<Reinvest>) -> Result<()> {
     let rewards = player_data.collectable_construction_rewards;
 
     // Determine how many ORE can be purchased from the player's pending construction rewards.
-    let purchased_ores = rewards.safe_mul(2)?.safe_div(LAMPORTS_PER_ORE)? as u32;
+    let purchased_ores = rewards
+        .safe_mul(2)?
+        .safe_div(game_config.lamports_per_ore)? as u32;
 
     // At least one ORE must be purchasable to justify reinvest.
     require!(
@@ -140,7 +164,12 @@ pub fn reinvest(ctx: Context<Reinvest>) -> Result<()> {
         ErrorCode::InsufficientSalaryToPurchaseBoxes
     );
 
-    let total_cost = LAMPORTS_PER_ORE.safe_mul(purchased_ores as u64)?;
+    // Enforce the player's self-exclusion and daily ORE limit
+    player_data.record_spend_on_ores(purchased_ores, timestamp)?;
+
+    let total_cost = game_config
+        .lamports_per_ore
+        .safe_mul(purchased_ores as u64)?;
     let half_cost = total_cost.safe_div(2)?;
 
     // Deduct total_cost from player's collectable_construction_rewards after reinvesting.
@@ -155,144 +184,80 @@ pub fn reinvest(ctx: Context<Reinvest>) -> Result<()> {
         game.distributed_construction_rewards.safe_add(half_cost)?;
     game.distributed_bonus_rewards = game.distributed_bonus_rewards.safe_add(half_cost)?;
 
-    // Update the player to reflect they are now in the current round and period
-    player_data.current_round = current_round.key();
-    if player_data.current_period != current_period.key() {
-        player_data.current_period = current_period.key();
-        player_data.current_period_purchased_ores = 0;
-    }
-
-    // Update consecutive purchase days if needed
-    let current_day = timestamp_to_days(timestamp)?;
-    if player_data.last_purchased_day != current_day {
-        if player_data.last_purchased_day + 1 == current_day {
-            player_data.consecutive_purchased_days =
-                player_data.consecutive_purchased_days.safe_add(1)?;
-        } else {
-            player_data.consecutive_purchased_days = 1;
-        }
-        player_data.last_purchased_day = current_day;
-    }
-
-    // Calculate proportional rewards for various pools
-    let construction_rewards = calculate_proportion(total_cost, CONSTRUCTION_POOL_SHARE)?;
-    let bonus_rewards = construction_rewards;
-    let lottery_rewards = calculate_proportion(total_cost, LOTTERY_POOL_SHARE)?;
-    let referral_rewards = calculate_proportion(total_cost, REFERRAL_POOL_SHARE)?;
-    let grand_prizes_rewards = calculate_proportion(total_cost, GRAND_PRIZES_POOL_SHARE)?;
-    let consumption_rewards = calculate_proportion(total_cost, CONSUMPTION_POOL_SHARE)?;
-    let developer_rewards = calculate_proportion(total_cost, CONSUMPTION_POOL_SHARE)?;
-
-    // Update game-level pools
-    game.construction_rewards_pool_balance = game
-        .construction_rewards_pool_balance
-        .safe_add(construction_rewards)?;
-    game.bonus_rewards_pool_balance = game.bonus_rewards_pool_balance.safe_add(bonus_rewards)?;
-    game.lottery_rewards_pool_balance = game
-        .lottery_rewards_pool_balance
-        .safe_add(lottery_rewards)?;
-    if player_data.referrer != game.default_player {
-        game.referral_rewards_pool_balance = game
-            .referral_rewards_pool_balance
-            .safe_add(referral_rewards)?;
-    }
-
-    // Update round-level pools
-    current_round.grand_prize_pool_balance = current_round
-        .grand_prize_pool_balance
-        .safe_add(grand_prizes_rewards)?;
-
-    if player_data.referrer != game.default_player {
-        // Add referral rewards to the referrer's pending rewards
-        referrer_data.collectable_referral_rewards = referrer_data
-            .collectable_referral_rewards
-            .safe_add(referral_rewards)?;
-    }
-
-    // Update earnings_per_ore in the round
-    let available_ores = current_round.available_ores.max(1);
-    let earnings_per_ore_increment = construction_rewards.safe_div(available_ores as u64)?;
-    current_round.earnings_per_ore = current_round
-        .earnings_per_ore
-        .safe_add(earnings_per_ore_increment)?;
-
-    // Update round state: sold ORE, participant list, end time
-    current_round.available_ores = current_round.available_ores.safe_add(purchased_ores)?;
-    current_round.sold_ores = current_round.sold_ores.safe_add(purchased_ores)?;
-    current_round.update_last_active_participant_list(player.key())?;
-    current_round.update_end_time(timestamp)?;
-
-    // Settle any pending construction rewards before adding newly purchased ORE
-    player_data.settle_collectable_construction_rewards(current_round.earnings_per_ore)?;
-
-    // Update player ORE holdings and earnings rate
-    player_data.available_ores = player_data.available_ores.safe_add(purchased_ores)?;
-    player_data.purchased_ores = player_data.purchased_ores.safe_add(purchased_ores)?;
-
-    // If the player is part of a team, update team ORE and period data
-    team.update_current_period(current_period.key());
-    team.purchased_ores = team.purchased_ores.safe_add(purchased_ores)?;
-    team.last_updated_timestamp = timestamp;
-
-    // If the current period is ongoing, update leaderboards
-    if current_period.is_ongoing(timestamp) {
-        player_data.current_period_purchased_ores = player_data
-            .current_period_purchased_ores
-            .safe_add(purchased_ores)?;
-        current_period
-            .update_top_player(player.key(), player_data.current_period_purchased_ores)?;
-
-        team.current_period_purchased_ores = team
-            .current_period_purchased_ores
-            .safe_add(purchased_ores)?;
-        if player_data.team != game.default_team {
-            current_period.update_top_team_list(team.key(), team.current_period_purchased_ores)?;
-        }
-    }
-
-    // If mining pool balance is enough, add developer rewards
-    if game.consumption_rewards_pool_balance >= developer_rewards {
-        game.consumption_rewards_pool_balance = game
-            .consumption_rewards_pool_balance
-            .safe_sub(developer_rewards)?;
-        game.distributable_consumption_rewards = game
-            .distributable_consumption_rewards
-            .safe_sub(developer_rewards)?;
-        game.developer_rewards_pool_balance = game
-            .developer_rewards_pool_balance
-            .safe_add(developer_rewards)?;
-        msg!(
-            "Developer consumption pool increased by {}.",
-            developer_rewards
-        );
-    }
+    // Split the reinvested rewards across the reward pools
+    let distribution = distribute(
+        &DistributionInput {
+            total_cost,
+            consumption_base: total_cost,
+            has_referrer: player_data.referrer != game.default_player,
+            purchase_volume: player_data.purchase_volume(timestamp)?,
+            timestamp,
+        },
+        game_config,
+        game,
+        current_round,
+        referrer_data,
+    )?;
+    player_data.collectable_consumption_rewards = player_data
+        .collectable_consumption_rewards
+        .safe_add(distribution.consumption_rewards)?;
+
+    // Record the purchased ORE on the round, the player, their team and the leaderboards,
+    // boosting the construction rewards it settles by the player's stake
+    let (construction_rewards, boost) = record_purchased_ores(
+        &PurchaseRecord {
+            player: player.key(),
+            round: current_round.key(),
+            period: current_period.key(),
+            team: team.key(),
+            default_team: game.default_team,
+            purchased_ores,
+            total_cost,
+            timestamp,
+        },
+        game,
+        current_round,
+        &mut *current_period.load_mut()?,
+        player_data,
+        team,
+    )?;
 
-    // If tokens are used (total_cost > 0), add consumption rewards
-    if game.distributable_consumption_rewards >= consumption_rewards {
-        game.distributable_consumption_rewards = game
-            .distributable_consumption_rewards
-            .safe_sub(consumption_rewards)?;
-        player_data.collectable_consumption_rewards = player_data
-            .collectable_consumption_rewards
-            .safe_add(consumption_rewards)?;
-        msg!(
-            "Player earned {} consumption rewards for spending {} tokens.",
-            consumption_rewards,
-            total_cost
-        );
+    if boost > 0 {
+        game.increment_event_nonce()?;
+
+        // Emit an event recording the boost
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::ConstructionRewardsBoosted as u16,
+            event_type: EventType::ConstructionRewardsBoosted,
+            event_nonce: game.event_nonce,
+            data: EventData::ConstructionRewardsBoosted {
+                player: player.key(),
+                construction_rewards,
+                boost_factor_bps: player_data.boost_factor_bps,
+                boost,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
     }
 
     // Transfer grand prizes rewards from the game_vault to the round_vault, reflecting resource redistribution.
-    transfer_from_token_vault_to_token_account(
+    transfer_from_vault(
         game,
+        ctx.bumps.game,
         &game_vault,
         &round_vault,
+        token_mint,
         &token_program,
-        grand_prizes_rewards,
-        &[GAME_SEED, &[ctx.bumps.game]],
+        distribution.round_vault_amount()?,
     )?;
 
-    if player_data.referrer == game.default_player {
+    // Burn the referral share of players without a referrer
+    let burned_referral_rewards = distribution.burned_referral_rewards();
+    if burned_referral_rewards > 0 {
         burn(
             CpiContext::new_with_signer(
                 token_program.to_account_info(),
@@ -303,14 +268,38 @@ pub fn reinvest(ctx: Context<Reinvest>) -> Result<()> {
                 },
                 &[&[GAME_SEED, &[ctx.bumps.game]]],
             ),
-            referral_rewards,
+            burned_referral_rewards,
         )?;
+
+        game.record_burned_tokens(burned_referral_rewards)?;
+        game.increment_event_nonce()?;
+
+        // Emit an event recording the burn
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::Burn as u16,
+            event_type: EventType::Burn,
+            event_nonce: game.event_nonce,
+            data: EventData::Burn {
+                mint: token_mint.key(),
+                from: game_vault.key(),
+                amount: burned_referral_rewards,
+                reason: BurnReason::ReferralRewards,
+                total_burned: game.total_burned_tokens,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
     }
 
     game.increment_event_nonce()?;
 
     // Emit a Reinvest event, recording how many ORE were bought via reinvestment.
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Reinvest as u16,
         event_type: EventType::Reinvest,
         event_nonce: game.event_nonce,
         data: EventData::Reinvest {
@@ -324,7 +313,66 @@ pub fn reinvest(ctx: Context<Reinvest>) -> Result<()> {
         initiator_type: InitiatorType::PLAYER,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
+
+    // Log the round moving to another phase
+    let phase = current_round.phase(timestamp);
+    if phase != distribution.phase {
+        game.increment_event_nonce()?;
+
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::RoundPhaseChanged as u16,
+            event_type: EventType::RoundPhaseChanged,
+            event_nonce: game.event_nonce,
+            data: EventData::RoundPhaseChanged {
+                round: current_round.key(),
+                previous_phase: distribution.phase,
+                phase,
+                available_ores: current_round.available_ores,
+                end_time: current_round.end_time,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    // Log the countdown reaching a milestone
+    if let Some(milestone_seconds) = current_round.reach_countdown_milestone(timestamp) {
+        game.increment_event_nonce()?;
+
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::CountdownMilestone as u16,
+            event_type: EventType::CountdownMilestone,
+            event_nonce: game.event_nonce,
+            data: EventData::CountdownMilestone {
+                round: current_round.key(),
+                milestone_seconds,
+                end_time: current_round.end_time,
+            },
+            initiator_type: InitiatorType::PLAYER,
+            initiator: player.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    #[cfg(feature = "strict-invariants")]
+    {
+        game_vault.reload()?;
+        round_vault.reload()?;
+        assert_invariants(
+            &invariant_snapshot,
+            game,
+            current_round,
+            game_vault.amount,
+            round_vault.amount,
+        )?;
+    }
 
     Ok(())
 }
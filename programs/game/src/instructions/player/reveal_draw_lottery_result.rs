@@ -1,52 +1,68 @@
-use crate::constants::{GAME_SEED, ONCE_DRAW_LOTTERY_VOUCHER_COST, PLAYER_DATA_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, SESSION_KEY_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
-use crate::utils::{
-    calculate_multiplier, get_symbol_id, to_timestamp_u64,
-    transfer_from_token_vault_to_token_account,
-};
+use crate::utils::{authorize_player_signer, to_timestamp_u64, transfer_from_vault, Randomness};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
-use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
-use switchboard_on_demand::accounts::RandomnessAccountData;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RevealDrawLotteryResult<'info> {
     /// The global game account, referencing main vaults and configurations.
     #[account(mut, seeds = [GAME_SEED], bump, has_one = game_vault)]
     pub game: Box<Account<'info, Game>>,
 
-    /// The player revealing the lottery result, must sign the transaction.
-    #[account(mut)]
-    pub player: Signer<'info>,
+    /// The wallet signing the transaction: the player, or the session signer of the player's `session_key`.
+    pub signer: Signer<'info>,
+
+    /// The player revealing the lottery result.
+    /// CHECK: Must be the `signer`, or the player whose `session_key` authorizes the signer; checked in the handler.
+    pub player: UncheckedAccount<'info>,
+
+    /// The player's session key, required when a session signer signs on the player's behalf.
+    #[account(seeds = [SESSION_KEY_SEED, player.key().as_ref()], bump)]
+    pub session_key: Option<Box<Account<'info, SessionKey>>>,
 
-    /// The player's data account, linked to their randomness provider and token account.
+    /// The player's data account, linked to their randomness provider, lottery table and token account.
     /// It stores info about the committed random slot, spin symbols, and result multipliers.
     #[account(mut,
         seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
         bump,
         has_one = randomness_provider,
+        has_one = lottery_table,
         has_one = token_account,
     )]
     pub player_data: Box<Account<'info, PlayerData>>,
 
+    /// The lottery table the player drew on, holding the draw cost and the multipliers, recording the payout.
+    /// Disabled tables still reveal the draws committed to them.
+    #[account(mut)]
+    pub lottery_table: Box<Account<'info, LotteryTable>>,
+
     /// The randomness provider account (Switchboard). It's an UncheckedAccount because validation occurs at runtime.
-    /// CHECK: Validated at runtime via RandomnessAccountData parsing.
+    /// CHECK: Validated at runtime via `Randomness::parse`.
     pub randomness_provider: UncheckedAccount<'info>,
 
     /// The main game vault holding tokens for rewards and payouts.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The player's token account where lottery rewards will be deposited if they win.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     /// The SPL token program enabling token transfers.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// The `reveal_draw_lottery_result` instruction finalizes a previously initiated lottery draw by revealing the outcome.
@@ -55,8 +71,11 @@ pub struct RevealDrawLotteryResult<'info> {
 /// Steps:
 /// 1. Fetch the randomness data from the `randomness_provider` and ensure it matches the committed slot in `player_data`.
 /// 2. Confirm that the randomness is resolved and fresh (not expired or invalid).
-/// 3. Derive symbol IDs from the random values and calculate a multiplier to determine lottery rewards.
+/// 3. Spin the reels of the lottery table drawn on with the random values, and read the multiplier of the symbols shown
+///    from the table to determine lottery rewards.
 /// 4. If the player wins (multiplier > 0), deduct the corresponding rewards from the lottery pool and transfer them to the player's token account.
+///    The payout is recorded on the table, which is paused with a `LotteryTablePaused` event if its realized return to player
+///    now exceeds its ceiling.
 /// 5. Update `player_data` with the revealed symbols, multiplier, and collected lottery rewards if any.
 /// 6. Emit a `RevealDrawLotteryResult` event to log the outcome on-chain.
 
@@ -65,9 +84,23 @@ pub fn reveal_draw_lottery_result(ctx: Context<RevealDrawLotteryResult>) -> Resu
     let clock: Clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
+    // Check the signer acts for the player, directly or through their session key
+    authorize_player_signer(
+        ctx.accounts.player.key(),
+        ctx.accounts.signer.key(),
+        ctx.accounts
+            .session_key
+            .as_deref()
+            .map(|session_key| &**session_key),
+        SESSION_SCOPE_REVEAL_LOTTERY,
+        timestamp,
+    )?;
+
     // Extract references to the accounts for clarity.
     let RevealDrawLotteryResult {
+        token_mint,
         game,
+        lottery_table,
         player,
         player_data,
         randomness_provider,
@@ -78,8 +111,8 @@ pub fn reveal_draw_lottery_result(ctx: Context<RevealDrawLotteryResult>) -> Resu
     } = ctx.accounts;
 
     // Parse the randomness data from the Switchboard randomness account.
-    let randomness_data = RandomnessAccountData::parse(randomness_provider.data.borrow())
-        .map_err(|_| ErrorCode::InvalidRandomnessAccount)?;
+    let randomness_data =
+        Randomness::parse(randomness_provider).ok_or(ErrorCode::InvalidRandomnessAccount)?;
 
     // Ensure randomness is resolved (seed_slot != 0 means we have valid randomness).
     require!(
@@ -94,19 +127,14 @@ pub fn reveal_draw_lottery_result(ctx: Context<RevealDrawLotteryResult>) -> Resu
     );
 
     // Obtain the revealed random value from Switchboard.
-    let revealed_random_value = randomness_data
-        .get_value(&clock)
-        .map_err(|_| ErrorCode::RandomnessNotResolved)?;
-
-    // Derive symbol IDs from the random values for the lottery outcome.
-    let symbol1_id = get_symbol_id(revealed_random_value[0]);
-    let symbol2_id = get_symbol_id(revealed_random_value[1]);
-    let symbol3_id = get_symbol_id(revealed_random_value[2]);
+    let revealed_random_value = randomness_data.value(&clock)?;
 
-    let symbols = [symbol1_id, symbol2_id, symbol3_id];
-
-    // Calculate the multiplier for the player's winnings based on the revealed symbols.
-    let multiplier = calculate_multiplier(symbols);
+    // Spin the table's reels with the random values, reading the multiplier of the symbols shown from the table.
+    let (symbols, multiplier) = lottery_table.spin([
+        revealed_random_value[0],
+        revealed_random_value[1],
+        revealed_random_value[2],
+    ]);
 
     // Update player's spin symbols, multiplier, and result revealed flag.
     player_data.spin_symbols = symbols;
@@ -115,10 +143,17 @@ pub fn reveal_draw_lottery_result(ctx: Context<RevealDrawLotteryResult>) -> Resu
     player_data.commit_slot = 0;
 
     // Calculate lottery rewards if the player wins.
-    let lottery_rewards = ONCE_DRAW_LOTTERY_VOUCHER_COST.safe_mul(multiplier as u64)?;
+    let lottery_rewards = lottery_table.voucher_cost.safe_mul(multiplier as u64)?;
 
     // If multiplier > 0, player wins and receives lottery rewards.
     if multiplier > 0 {
+        // The pool covered the table's top prize at the draw, but other wins may have drained it since:
+        // refuse the reveal until the pool is refilled rather than underpay the win
+        require!(
+            game.lottery_rewards_pool_balance >= lottery_rewards,
+            ErrorCode::LotteryPoolIsEmpty
+        );
+
         // Deduct lottery rewards from the game's lottery pool.
         game.lottery_rewards_pool_balance = game
             .lottery_rewards_pool_balance
@@ -132,14 +167,38 @@ pub fn reveal_draw_lottery_result(ctx: Context<RevealDrawLotteryResult>) -> Resu
             .safe_add(lottery_rewards)?;
 
         // Transfer the winning tokens to the player's token account.
-        transfer_from_token_vault_to_token_account(
+        transfer_from_vault(
             game,
+            ctx.bumps.game,
             &game_vault,
             &token_account,
+            token_mint,
             &token_program,
             lottery_rewards,
-            &[GAME_SEED, &[ctx.bumps.game]],
         )?;
+
+        // Pause the table if it pays out more than its return to player ceiling
+        if lottery_table.record_payout(lottery_rewards)? {
+            game.increment_event_nonce()?;
+
+            // Emit an event recording the pause
+            let event = TransferEvent {
+                event_schema_version: EVENT_SCHEMA_VERSION,
+                event_tag: EventType::LotteryTablePaused as u16,
+                event_type: EventType::LotteryTablePaused,
+                event_nonce: game.event_nonce,
+                data: EventData::LotteryTablePaused {
+                    lottery_table: lottery_table.key(),
+                    total_bets: lottery_table.total_bets,
+                    total_payouts: lottery_table.total_payouts,
+                    max_return_to_player_rate: lottery_table.max_return_to_player_rate,
+                },
+                initiator_type: InitiatorType::LOTTERY,
+                initiator: player.key(),
+                timestamp,
+            };
+            record_event!(ctx, event);
+        }
     }
 
     msg!(
@@ -152,7 +211,9 @@ pub fn reveal_draw_lottery_result(ctx: Context<RevealDrawLotteryResult>) -> Resu
 
     // Emit the event capturing the revealed draw lottery result,
     // including the symbols, multiplier, and any awarded lottery rewards.
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::RevealDrawLotteryResult as u16,
         event_type: EventType::RevealDrawLotteryResult,
         event_nonce: game.event_nonce,
         data: EventData::RevealDrawLotteryResult {
@@ -165,7 +226,8 @@ pub fn reveal_draw_lottery_result(ctx: Context<RevealDrawLotteryResult>) -> Resu
         initiator_type: InitiatorType::LOTTERY,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
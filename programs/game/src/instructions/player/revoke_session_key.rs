@@ -0,0 +1,101 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, SESSION_KEY_SEED};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{revoke, Revoke, TokenAccount, TokenInterface};
+use solana_program::program_option::COption;
+use solana_program::sysvar::clock::Clock;
+
+/// The `RevokeSessionKey` instruction lets a player end their session before it expires.
+/// The session key is closed and, if it is still the delegate of the player's token account, the delegation is revoked.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    /// The player revoking the session. Must sign the transaction and receives the session key's rent.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The player's data account, linking the player to their token account.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = token_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The player's token account, possibly delegated to the session key.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's session key, closed to the player.
+    #[account(
+        mut,
+        seeds = [SESSION_KEY_SEED, player.key().as_ref()],
+        bump = session_key.bump,
+        close = player,
+    )]
+    pub session_key: Box<Account<'info, SessionKey>>,
+
+    /// The global game account, used for the event nonce.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The SPL token program, required to revoke the delegate.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Revokes the player's session key:
+///
+/// Steps:
+/// 1. Revoke the token account delegation if it is held by the session key.
+/// 2. Close the session key.
+/// 3. Emit a `RevokeSessionKey` event.
+pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+    // Fetch the current UNIX timestamp for the event
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let RevokeSessionKey {
+        player,
+        token_account,
+        session_key,
+        game,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    if token_account.delegate == COption::Some(session_key.key()) {
+        revoke(CpiContext::new(
+            token_program.to_account_info(),
+            Revoke {
+                source: token_account.to_account_info(),
+                authority: player.to_account_info(),
+            },
+        ))?;
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the revocation
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::RevokeSessionKey as u16,
+        event_type: EventType::RevokeSessionKey,
+        event_nonce: game.event_nonce,
+        data: EventData::RevokeSessionKey {
+            player: player.key(),
+            session_key: session_key.key(),
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SelfExclude<'info> {
+    /// The player excluding themselves. Must sign the transaction.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The player's data account, holding their self-exclusion.
+    #[account(mut, seeds = [PLAYER_DATA_SEED, player.key().as_ref()], bump)]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The global game account, tracking the event nonce.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// The `self_exclude` instruction lets a player exclude themselves from purchasing ORE and drawing the lottery until a given time.
+/// Only the player can set their exclusion: extending it takes effect immediately, while shortening it
+/// cannot end it before the responsible play cooldown has passed.
+///
+/// Steps:
+/// 1. Extend the exclusion, or shorten it no sooner than the cooldown allows.
+/// 2. Emit a `SelfExclude` event to record the exclusion on-chain.
+pub fn self_exclude(ctx: Context<SelfExclude>, until_timestamp: u64) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging and the cooldown.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SelfExclude {
+        player,
+        player_data,
+        game,
+        ..
+    } = ctx.accounts;
+
+    let self_excluded_until = player_data.self_exclude(until_timestamp, timestamp)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event to log the player's self-exclusion
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SelfExclude as u16,
+        event_type: EventType::SelfExclude,
+        event_nonce: game.event_nonce,
+        data: EventData::SelfExclude {
+            player: player.key(),
+            self_excluded_until,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
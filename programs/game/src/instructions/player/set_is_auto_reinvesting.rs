@@ -1,12 +1,13 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 use solana_program::sysvar::clock::Clock;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SetIsAutoReinvesting<'info> {
     /// The player enabling auto-reinvest. Must sign the transaction.
@@ -26,6 +27,10 @@ pub struct SetIsAutoReinvesting<'info> {
         constraint = !current_round.is_over @ ErrorCode::RoundAlreadyEnded,
     )]
     pub current_round: Box<Account<'info, Round>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// The `set_is_auto_reinvesting` instruction allows a player to enable automatic reinvestment of their earnings.
@@ -65,7 +70,9 @@ pub fn set_is_auto_reinvesting(ctx: Context<SetIsAutoReinvesting>) -> Result<()>
     game.increment_event_nonce()?;
 
     // Emit an event to log that the player enabled auto-reinvest
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetIsAutoReinvesting as u16,
         event_type: EventType::SetIsAutoReinvesting,
         event_nonce: game.event_nonce,
         data: EventData::SetIsAutoReinvesting {
@@ -75,7 +82,8 @@ pub fn set_is_auto_reinvesting(ctx: Context<SetIsAutoReinvesting>) -> Result<()>
         initiator_type: InitiatorType::PLAYER,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
@@ -1,11 +1,12 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
 use solana_program::sysvar::clock::Clock;
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(referrer: Pubkey)]
 pub struct SetReferrer<'info> {
@@ -33,6 +34,10 @@ pub struct SetReferrer<'info> {
         bump
     )]
     pub referrer_data: Box<Account<'info, PlayerData>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// The `set_referrer` instruction allows a player to assign a referrer for the first time.
@@ -67,17 +72,20 @@ pub fn set_referrer(ctx: Context<SetReferrer>, referrer: Pubkey) -> Result<()> {
     game.increment_event_nonce()?;
 
     // Emit an event that the player successfully set a new referrer
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetReferrer as u16,
         event_type: EventType::SetReferrer,
         event_nonce: game.event_nonce,
         data: EventData::SetReferrer {
             player: player.key(),
-            referrer
+            referrer,
         },
         initiator_type: InitiatorType::PLAYER,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
@@ -0,0 +1,73 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetSpendLimit<'info> {
+    /// The player limiting their own spending. Must sign the transaction.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The player's data account, holding their spend limits.
+    #[account(mut, seeds = [PLAYER_DATA_SEED, player.key().as_ref()], bump)]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The global game account, tracking the event nonce.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// The `set_spend_limit` instruction lets a player opt into daily limits on the ORE they purchase and the lottery draws they make.
+/// Only the player can set their limits: tighter limits take effect immediately, looser ones once the responsible play cooldown has passed.
+///
+/// Steps:
+/// 1. Apply the tightened limits, and schedule the loosened ones after the cooldown.
+/// 2. Emit a `SetSpendLimit` event to record the limits on-chain.
+pub fn set_spend_limit(
+    ctx: Context<SetSpendLimit>,
+    daily_ores: u32,
+    daily_lottery_draws: u32,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging and the cooldown.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SetSpendLimit {
+        player,
+        player_data,
+        game,
+        ..
+    } = ctx.accounts;
+
+    let unlock_timestamp =
+        player_data.set_spend_limit(daily_ores, daily_lottery_draws, timestamp)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event to log the player's new spend limits
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SetSpendLimit as u16,
+        event_type: EventType::SetSpendLimit,
+        event_nonce: game.event_nonce,
+        data: EventData::SetSpendLimit {
+            player: player.key(),
+            daily_ore_limit: daily_ores,
+            daily_draw_limit: daily_lottery_draws,
+            unlock_timestamp,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
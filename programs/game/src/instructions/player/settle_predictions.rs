@@ -0,0 +1,103 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PREDICTION_MARKET_SEED, PREDICTION_OUTCOME_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `SettlePredictions` instruction lets anyone settle a round's prediction market once the round is over,
+/// recording its winner, `last_active_participant_list[0]`, and the vouchers staked on them. The winner's
+/// `PredictionOutcome` is passed at its derived address, so that a winner nobody predicted, whose outcome was
+/// never created, can be told apart: the market then refunds every prediction.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettlePredictions<'info> {
+    /// The caller running the crank.
+    pub caller: Signer<'info>,
+
+    /// The global `Game` account, tracking the event nonce.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The round predicted on, which must be over.
+    #[account(constraint = round.is_over @ ErrorCode::RoundInProgress)]
+    pub round: Box<Account<'info, Round>>,
+
+    /// The round's prediction market.
+    #[account(
+        mut,
+        seeds = [PREDICTION_MARKET_SEED, round.key().as_ref()],
+        bump = prediction_market.bump,
+    )]
+    pub prediction_market: Box<Account<'info, PredictionMarket>>,
+
+    /// CHECK: The stakes on the round's winner, derived from the winner; uninitialized if nobody predicted them.
+    #[account(
+        seeds = [
+            PREDICTION_OUTCOME_SEED,
+            prediction_market.key().as_ref(),
+            round.last_active_participant_list[0].as_ref(),
+        ],
+        bump,
+    )]
+    pub winner_prediction_outcome: UncheckedAccount<'info>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the prediction market settlement:
+///
+/// Steps:
+/// 1. Read the winner of the round and the vouchers staked on them, none if their outcome was never created.
+/// 2. Settle the market on the winner, refunding every prediction if nobody predicted them.
+/// 3. Emit a `SettlePredictions` event.
+pub fn settle_predictions(ctx: Context<SettlePredictions>) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let SettlePredictions {
+        caller,
+        game,
+        round,
+        prediction_market,
+        winner_prediction_outcome,
+        ..
+    } = ctx.accounts;
+
+    let winner = round.last_active_participant_list[0];
+    let winning_staked = if winner_prediction_outcome.data_is_empty() {
+        0
+    } else {
+        PredictionOutcome::try_deserialize(&mut &winner_prediction_outcome.data.borrow()[..])?
+            .total_staked
+    };
+    prediction_market.settle(winner, winning_staked)?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the settlement
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SettlePredictions as u16,
+        event_type: EventType::SettlePredictions,
+        event_nonce: game.event_nonce,
+        data: EventData::SettlePredictions {
+            prediction_market: prediction_market.key(),
+            round: round.key(),
+            winner,
+            winning_staked,
+            total_staked: prediction_market.total_staked,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: caller.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -1,13 +1,15 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
-use crate::utils::{to_timestamp_u64, transfer_from_token_vault_to_token_account};
+use crate::utils::{
+    carry_over_construction_rewards, settle_round, to_timestamp_u64, transfer_from_vault,
+};
 use anchor_lang::prelude::*;
-use anchor_safe_math::SafeMath;
-use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SettlePreviousRound<'info> {
     // The player who initiates the settlement of the previous round, must sign the transaction.
@@ -27,7 +29,7 @@ pub struct SettlePreviousRound<'info> {
 
     // The player's token account to which settled rewards will be transferred.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // The global game account. No special constraints here besides referencing current_round and vaults if needed.
     #[account(mut, seeds = [GAME_SEED], bump, has_one = game_vault)]
@@ -35,7 +37,7 @@ pub struct SettlePreviousRound<'info> {
 
     // The game's vault token account holding tokens allocated for the settled rewards.
     #[account(mut)]
-    pub game_vault: Box<Account<'info, TokenAccount>>,
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // The round account must be over to settle it. Ensures that the round vault is accessible.
     #[account(mut,
@@ -43,30 +45,40 @@ pub struct SettlePreviousRound<'info> {
     )]
     pub current_round: Box<Account<'info, Round>>,
 
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     // The SPL token program enabling token transfers.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// The `settle_previous_round` instruction allows a player who participated in a now-concluded round to finalize their position:
 /// - Settle construction rewards (based on player's available ORE and earnings_per_ore).
 /// - Clear ORE from the player's holdings and reduce ORE from the round's available supply.
-/// - Transfer the settled rewards from the round vault to the player's token account.
+/// - Transfer the settled rewards from the round vault to the player's token account, or carry them over to the player's
+///   first purchase of the next round.
 /// - Mark the player as exited from that round, enabling them to join new rounds or take other actions.
 ///
 /// Steps:
 /// 1. Verify the round has ended and the player is still associated with it.
 /// 2. Settle pending construction rewards according to the final earnings_per_ore.
 /// 3. Deduct the corresponding ORE from the round and the player's holdings, distributing the earned construction rewards.
-/// 4. Transfer these rewards from the round vault to the player's token account.
+/// 4. Transfer these rewards from the round vault to the player's token account, or credit them to the player's
+///    carryover balance if `carry_over` is set.
 /// 5. Emit a `SettlePreviousRound` event to record the completion of this settlement action.
-pub fn settle_previous_round(ctx: Context<SettlePreviousRound>) -> Result<()> {
+/// 6. Emit a `PlayerSnapshot` event with the player's activity counters.
+pub fn settle_previous_round(ctx: Context<SettlePreviousRound>, carry_over: bool) -> Result<()> {
     // Obtain the current UNIX timestamp for event logging and logical checks.
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
     // Extract references for cleaner readability.
     let SettlePreviousRound {
+        token_mint,
         player,
         player_data,
         game,
@@ -77,66 +89,71 @@ pub fn settle_previous_round(ctx: Context<SettlePreviousRound>) -> Result<()> {
         ..
     } = ctx.accounts;
 
-    // Settle any pending construction rewards based on current_round.earnings_per_ore.
-    player_data.settle_collectable_construction_rewards(current_round.earnings_per_ore)?;
-
-    let construction_rewards = player_data.collectable_construction_rewards;
-    player_data.collectable_construction_rewards = player_data
-        .collectable_construction_rewards
-        .safe_sub(construction_rewards)?;
-    let player_available_ores = player_data.available_ores;
-
-    // Deduct construction rewards from the game's construction pool and update distributed metrics.
-    game.construction_rewards_pool_balance = game
-        .construction_rewards_pool_balance
-        .safe_sub(construction_rewards)?;
-    game.distributed_construction_rewards = game
-        .distributed_construction_rewards
-        .safe_add(construction_rewards)?;
-
-    // Ensure the round has enough ORE to cover the player's holdings and reduce it accordingly.
-    require!(
-        current_round.available_ores >= player_available_ores,
-        RoundError::InsufficientOres
-    );
-    current_round.available_ores = current_round
-        .available_ores
-        .safe_sub(player_available_ores)?;
-
-    // Update the player's collected construction rewards and mark them as exited from the round.
-    player_data.collected_construction_rewards = player_data
-        .collected_construction_rewards
-        .safe_add(construction_rewards)?;
-    player_data.exit_round()?;
-
-    // Transfer the settled construction rewards from the round vault to the player's token account.
-    transfer_from_token_vault_to_token_account(
-        game,
-        &game_vault,
-        &token_account,
-        &token_program,
-        construction_rewards,
-        &[GAME_SEED, &[ctx.bumps.game]],
-    )?;
+    // Settle the player's holdings and construction rewards in the finished round.
+    let (player_available_ores, construction_rewards) =
+        settle_round(game, current_round, player_data)?;
+
+    if carry_over {
+        // Keep the settled construction rewards in the vault for the player's next purchase.
+        carry_over_construction_rewards(game, player_data, construction_rewards)?;
+    } else {
+        // Transfer the settled construction rewards from the round vault to the player's token account.
+        transfer_from_vault(
+            game,
+            ctx.bumps.game,
+            &game_vault,
+            &token_account,
+            token_mint,
+            &token_program,
+            construction_rewards,
+        )?;
+    }
 
     msg!("Construction rewards: {}", construction_rewards);
 
     game.increment_event_nonce()?;
 
     // Emit a `SettlePreviousRound` event to log the completion of this settlement action.
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::SettlePreviousRound as u16,
         event_type: EventType::SettlePreviousRound,
         event_nonce: game.event_nonce,
         data: EventData::SettlePreviousRound {
             round: current_round.key(),
             player: player.key(),
             available_ores: player_available_ores,
-            construction_rewards
+            construction_rewards,
+            carried_over: carry_over,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    game.increment_event_nonce()?;
+
+    // Emit a `PlayerSnapshot` event with the player's activity counters for analytics.
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::PlayerSnapshot as u16,
+        event_type: EventType::PlayerSnapshot,
+        event_nonce: game.event_nonce,
+        data: EventData::PlayerSnapshot {
+            player: player.key(),
+            round: current_round.key(),
+            purchased_ores: player_data.purchased_ores,
+            purchase_count: player_data.purchase_count,
+            tap_count: player_data.tap_count,
+            draw_count: player_data.draw_count,
+            stake_count: player_data.stake_count,
         },
         initiator_type: InitiatorType::PLAYER,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
@@ -0,0 +1,179 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, ROUND_POSITION_MINT_SEED,
+    ROUND_POSITION_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `TokenizePosition` instruction detaches ORE from a player's holdings in the current round into a
+/// `RoundPosition`, and mints the single token representing it to the player. The position keeps earning
+/// construction rewards and can be moved between wallets or sold like any token, then redeemed by its holder.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TokenizePosition<'info> {
+    /// The player tokenizing part of their holdings. Must sign the transaction and pays for the position.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The player's data account, holding the ORE to tokenize. The player must be in the current round.
+    #[account(mut,
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = current_round,
+        constraint = !player_data.is_exited @ ErrorCode::PlayerAlreadyExited,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The global game account referencing the current round.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = current_round)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The current round, which must still be ongoing. Accrues the exit rewards before the holdings change.
+    #[account(mut, constraint = !current_round.is_over @ ErrorCode::RoundAlreadyEnded)]
+    pub current_round: Box<Account<'info, Round>>,
+
+    /// The new position, derived from the player and their current `nonce`.
+    #[account(
+        init,
+        payer = player,
+        space = 8 + RoundPosition::INIT_SPACE,
+        seeds = [ROUND_POSITION_SEED, player.key().as_ref(), player_data.nonce.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub round_position: Box<Account<'info, RoundPosition>>,
+
+    /// The mint of the token representing the position, with the position as mint and freeze authority.
+    #[account(
+        init,
+        payer = player,
+        seeds = [ROUND_POSITION_MINT_SEED, round_position.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = round_position,
+        mint::freeze_authority = round_position,
+        mint::token_program = token_program,
+    )]
+    pub position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The player's associated token account receiving the position token.
+    #[account(
+        init,
+        payer = player,
+        associated_token::mint = position_mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The SPL token program, required to mint the position token.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program, used to create the `position_token_account`.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program, required to create the position.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Tokenizes `ores` of the player's holdings in the current round:
+///
+/// Steps:
+/// 1. Validate the ORE amount against the player's holdings.
+/// 2. Settle the player's pending construction and exit rewards, so the detached ORE carries no unsettled earnings.
+/// 3. Move the ORE from the player to the new position, checkpointed at the round's `earnings_per_ore`
+///    and `exit_rewards_per_ore`.
+/// 4. Mint the position token to the player and advance the player's `nonce`.
+/// 5. Emit a `TokenizePosition` event.
+pub fn tokenize_position(ctx: Context<TokenizePosition>, ores: u32) -> Result<()> {
+    // Fetch the current UNIX timestamp for the event
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let TokenizePosition {
+        player,
+        player_data,
+        game,
+        current_round,
+        round_position,
+        position_mint,
+        position_token_account,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require!(
+        ores > 0 && ores <= player_data.available_ores,
+        ErrorCode::InvalidPositionOres
+    );
+
+    // Settle the player's earnings before their holdings shrink
+    current_round.accrue_exit_rewards(timestamp)?;
+    player_data.settle_collectable_construction_rewards(current_round.earnings_per_ore)?;
+    player_data.settle_collectable_exit_rewards(current_round.exit_rewards_per_ore)?;
+    player_data.available_ores = player_data.available_ores.safe_sub(ores)?;
+
+    round_position.set_inner(RoundPosition {
+        round: current_round.key(),
+        position_mint: position_mint.key(),
+        ores,
+        earnings_per_ore: current_round.earnings_per_ore,
+        exit_rewards_per_ore: current_round.exit_rewards_per_ore,
+        bump: ctx.bumps.round_position,
+    });
+
+    // Mint the single token representing the position
+    let player_key = player.key();
+    let nonce = player_data.nonce.to_le_bytes();
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: position_mint.to_account_info(),
+                to: position_token_account.to_account_info(),
+                authority: round_position.to_account_info(),
+            },
+            &[&[
+                ROUND_POSITION_SEED,
+                player_key.as_ref(),
+                nonce.as_ref(),
+                &[ctx.bumps.round_position],
+            ]],
+        ),
+        1,
+    )?;
+    player_data.increment_nonce()?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the tokenized position
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::TokenizePosition as u16,
+        event_type: EventType::TokenizePosition,
+        event_nonce: game.event_nonce,
+        data: EventData::TokenizePosition {
+            round: current_round.key(),
+            position: round_position.key(),
+            position_mint: position_mint.key(),
+            player: player.key(),
+            ores,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
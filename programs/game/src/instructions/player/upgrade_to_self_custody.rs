@@ -0,0 +1,304 @@
+use crate::constants::{
+    BAN_ENTRY_SEED, CLAIM_CODE_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED,
+    STAKE_ORDER_INDEX_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    refreeze_voucher_account, thaw_voucher_account_if_frozen, to_timestamp_u64,
+    transfer_from_player_to_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    freeze_account, FreezeAccount, Mint, TokenAccount, TokenInterface,
+};
+use solana_program::hash::hash;
+use solana_program::sysvar::clock::Clock;
+
+/// The `UpgradeToSelfCustody` instruction re-keys a custodial player account, registered with `register_custodial`,
+/// to the user's own wallet. The user presents the claim code matching the account's `ClaimCode`, and the operator
+/// co-signs with the custodial key to release the tokens and vouchers it holds.
+///
+/// `PlayerData` is derived from the player's key, so the account is copied to the wallet's address and the custodial
+/// one closed. Other accounts referencing the custodial key cannot follow, so the account must be team-less, without
+/// referees or uncollected referral rewards, out of any round, without purchases in the current period, without a
+/// pending lottery reveal, without active stake orders and without a grand prize still vesting. Neither the custodial
+/// key nor the wallet may be banned, and while vouchers are soulbound the wallet's voucher account is frozen.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpgradeToSelfCustody<'info> {
+    /// The user's own wallet, taking over the account. Pays for the new accounts and receives the closed accounts' rent.
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    /// The custodial key the account was registered for, co-signed by the operator.
+    pub custodial_player: Signer<'info>,
+
+    /// The custodial player's data account, closed once copied.
+    #[account(
+        mut,
+        seeds = [PLAYER_DATA_SEED, custodial_player.key().as_ref()],
+        bump,
+        close = wallet,
+    )]
+    pub custodial_player_data: Box<Account<'info, PlayerData>>,
+
+    /// The custodial key's stake order index, which must list no active stake order if it exists.
+    /// CHECK: Derived from the custodial key; checked in the handler.
+    #[account(seeds = [STAKE_ORDER_INDEX_SEED, custodial_player.key().as_ref()], bump)]
+    pub custodial_stake_order_index: UncheckedAccount<'info>,
+
+    /// The custodial key's ban entry, which must not exist.
+    /// CHECK: Derived from the custodial key; checked to be empty in the handler.
+    #[account(seeds = [BAN_ENTRY_SEED, custodial_player.key().as_ref()], bump)]
+    pub custodial_ban_entry: UncheckedAccount<'info>,
+
+    /// The wallet's ban entry, which must not exist.
+    /// CHECK: Derived from the wallet; checked to be empty in the handler.
+    #[account(seeds = [BAN_ENTRY_SEED, wallet.key().as_ref()], bump)]
+    pub ban_entry: UncheckedAccount<'info>,
+
+    /// The claim code of the custodial account, consumed by the upgrade.
+    #[account(
+        mut,
+        seeds = [CLAIM_CODE_SEED, custodial_player.key().as_ref()],
+        bump = claim_code.bump,
+        close = wallet,
+    )]
+    pub claim_code: Box<Account<'info, ClaimCode>>,
+
+    /// The custodial token account, emptied into the wallet's token account.
+    #[account(mut, address = custodial_player_data.token_account)]
+    pub custodial_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The custodial voucher account, emptied into the wallet's voucher account.
+    #[account(mut, address = custodial_player_data.voucher_account)]
+    pub custodial_voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The wallet's data account, a copy of the custodial one.
+    #[account(
+        init,
+        payer = wallet,
+        space = custodial_player_data.to_account_info().data_len(),
+        seeds = [PLAYER_DATA_SEED, wallet.key().as_ref()],
+        bump,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The wallet's associated token account for the in-game token, created if needed.
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        associated_token::mint = token_mint,
+        associated_token::authority = wallet,
+        associated_token::token_program = token_program
+    )]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The wallet's associated voucher account, created if needed and frozen while vouchers are soulbound.
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        associated_token::mint = voucher_mint,
+        associated_token::authority = wallet,
+        associated_token::token_program = token_program
+    )]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global game account, referencing the default team.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The voucher state account, freeze authority of the voucher mint.
+    #[account(seeds = [VOUCHER_SEED], bump)]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The token mint representing the in-game token currency.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The voucher mint.
+    #[account(seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Associated Token program used to create the wallet's token accounts.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The System program for account creation.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Upgrades a custodial player account to self-custody:
+///
+/// Steps:
+/// 1. Verify the claim code against the stored hash.
+/// 2. Verify neither the custodial key nor the wallet is banned.
+/// 3. Verify no other account references the custodial key, and no stake order, prize vesting or referral credit is left to it.
+/// 4. Copy the custodial `PlayerData` to the wallet's address, linked to the wallet's token accounts.
+/// 5. Move the custodial tokens and vouchers to the wallet, thawing the voucher accounts if they are soulbound-frozen.
+/// 6. Freeze the wallet's voucher account if vouchers are soulbound.
+/// 7. Close the custodial `PlayerData` and the claim code.
+/// 8. Emit an `UpgradeToSelfCustody` event.
+pub fn upgrade_to_self_custody(
+    ctx: Context<UpgradeToSelfCustody>,
+    claim_code: Vec<u8>,
+) -> Result<()> {
+    // Get current UNIX timestamp for event logging
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let UpgradeToSelfCustody {
+        wallet,
+        custodial_player,
+        custodial_player_data,
+        custodial_stake_order_index,
+        custodial_ban_entry,
+        ban_entry,
+        claim_code: claim_code_account,
+        custodial_token_account,
+        custodial_voucher_account,
+        player_data,
+        token_account,
+        voucher_account,
+        game,
+        voucher,
+        token_mint,
+        voucher_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require!(
+        hash(&claim_code).to_bytes() == claim_code_account.claim_code_hash,
+        ErrorCode::InvalidClaimCode
+    );
+
+    // Reject banned keys, whose bans would not follow the account
+    BanEntry::require_not_banned(custodial_ban_entry)?;
+    BanEntry::require_not_banned(ban_entry)?;
+
+    require!(
+        custodial_player_data.team == game.default_team
+            && custodial_player_data.team_applications.is_empty()
+            && custodial_player_data.referral_count == 0
+            && custodial_player_data.is_exited
+            && custodial_player_data.current_period_purchased_ores == 0
+            && custodial_player_data.result_revealed,
+        ErrorCode::CustodialAccountNotUpgradable
+    );
+
+    // Stake orders, their boost, streamed prizes and referral credits stay keyed to the custodial key
+    require!(
+        !StakeOrderIndex::has_active_orders(custodial_stake_order_index)?
+            && custodial_player_data.stake_boost_amount == 0
+            && custodial_player_data.vesting_grand_prizes == 0
+            && custodial_player_data.collectable_referral_rewards == 0,
+        ErrorCode::CustodialAccountNotUpgradable
+    );
+
+    // Copy the account to the wallet's address
+    player_data.set_inner(PlayerData {
+        player: wallet.key(),
+        token_account: token_account.key(),
+        voucher_account: voucher_account.key(),
+        ..(***custodial_player_data).clone()
+    });
+
+    // Move the custodial balances to the wallet
+    let token_amount = custodial_token_account.amount;
+    if token_amount > 0 {
+        transfer_from_player_to_vault(
+            custodial_player,
+            custodial_token_account,
+            token_account,
+            token_mint,
+            token_program,
+            token_amount,
+        )?;
+    }
+
+    let voucher_amount = custodial_voucher_account.amount;
+    if voucher_amount > 0 {
+        let was_frozen = thaw_voucher_account_if_frozen(
+            voucher,
+            voucher_mint,
+            custodial_voucher_account,
+            token_program,
+            &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+        )?;
+        let wallet_was_frozen = thaw_voucher_account_if_frozen(
+            voucher,
+            voucher_mint,
+            voucher_account,
+            token_program,
+            &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+        )?;
+        transfer_from_player_to_vault(
+            custodial_player,
+            custodial_voucher_account,
+            voucher_account,
+            voucher_mint,
+            token_program,
+            voucher_amount,
+        )?;
+        refreeze_voucher_account(
+            voucher,
+            voucher_mint,
+            custodial_voucher_account,
+            token_program,
+            was_frozen,
+            &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+        )?;
+        refreeze_voucher_account(
+            voucher,
+            voucher_mint,
+            voucher_account,
+            token_program,
+            wallet_was_frozen,
+            &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+        )?;
+    }
+
+    // Soulbound vouchers must not leave the wallet through its new voucher account
+    if voucher.is_transfer_restricted && !voucher_account.is_frozen() {
+        freeze_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            FreezeAccount {
+                account: voucher_account.to_account_info(),
+                mint: voucher_mint.to_account_info(),
+                authority: voucher.to_account_info(),
+            },
+            &[&[VOUCHER_SEED, &[ctx.bumps.voucher]]],
+        ))?;
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the upgrade
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::UpgradeToSelfCustody as u16,
+        event_type: EventType::UpgradeToSelfCustody,
+        event_nonce: game.event_nonce,
+        data: EventData::UpgradeToSelfCustody {
+            custodial_player: custodial_player.key(),
+            player: wallet.key(),
+            token_amount,
+            voucher_amount,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: wallet.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use solana_program::sysvar::clock::Clock;
+
+/// The `VerifyLeaderboardRank` instruction proves a player's rank in their current period on-chain,
+/// so reward claims can rely on ranks beyond the top 10 tracked on the `Period` account.
+///
+/// Every leaderboard page above the player's page must be passed through `remaining_accounts`,
+/// ordered by page index, so that all higher-scoring players are accounted for.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VerifyLeaderboardRank<'info> {
+    /// The player whose rank is verified.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The global game account.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The player's data account, holding their score for the current period.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The leaderboard page listing the player.
+    #[account(
+        constraint = leaderboard_page.load()?.period == player_data.current_period @ ErrorCode::LeaderboardPageMismatch,
+    )]
+    pub leaderboard_page: AccountLoader<'info, LeaderboardPage>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the rank verification:
+///
+/// Steps:
+/// 1. Require the player to be listed on the page with their current score.
+/// 2. Require the higher pages of the same period, in page index order, and count their entries.
+/// 3. Compute the rank as the entries on higher pages plus the player's position on their page.
+/// 4. Emit a `VerifyLeaderboardRank` event and return the rank.
+pub fn verify_leaderboard_rank<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VerifyLeaderboardRank<'info>>,
+) -> Result<u32> {
+    // Obtain the current UNIX timestamp for event logging
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let VerifyLeaderboardRank {
+        player,
+        game,
+        player_data,
+        leaderboard_page,
+        ..
+    } = ctx.accounts;
+
+    let score = player_data.current_period_purchased_ores;
+    let (period, page_index, position) = {
+        let page = leaderboard_page.load()?;
+        let position = page
+            .position_of(player.key())
+            .filter(|&position| page.entries[position as usize - 1].score == score)
+            .ok_or(ErrorCode::PlayerNotOnLeaderboardPage)?;
+        (page.period, page.page_index, position)
+    };
+
+    // Every page above the player's page must be provided, in order
+    require!(
+        ctx.remaining_accounts.len() == page_index as usize,
+        ErrorCode::InvalidLeaderboardPageOrder
+    );
+
+    let mut rank = position;
+    for (index, account) in ctx.remaining_accounts.iter().enumerate() {
+        let higher_page = AccountLoader::<LeaderboardPage>::try_from(account)?;
+        let higher_page = higher_page.load()?;
+        require!(
+            higher_page.period == period,
+            ErrorCode::LeaderboardPageMismatch
+        );
+        require!(
+            higher_page.page_index as usize == index,
+            ErrorCode::InvalidLeaderboardPageOrder
+        );
+        rank = rank.safe_add(higher_page.len as u32)?;
+    }
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the verified rank
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::VerifyLeaderboardRank as u16,
+        event_type: EventType::VerifyLeaderboardRank,
+        event_nonce: game.event_nonce,
+        data: EventData::VerifyLeaderboardRank {
+            period,
+            player: player.key(),
+            score,
+            rank,
+        },
+        initiator_type: InitiatorType::PLAYER,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(rank)
+}
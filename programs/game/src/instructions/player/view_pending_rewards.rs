@@ -0,0 +1,36 @@
+use crate::constants::PLAYER_DATA_SEED;
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `ViewPendingRewards` instruction is a read-only view of the rewards a player could collect,
+/// settling the construction and exit rewards accrued in their round the same way the settlement does,
+/// without recording anything.
+#[derive(Accounts)]
+pub struct ViewPendingRewards<'info> {
+    /// The player data whose pending rewards are returned.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, player_data.player.as_ref()],
+        bump,
+        has_one = current_round,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The player's current round, providing the earnings and exit rewards per ORE.
+    pub current_round: Box<Account<'info, Round>>,
+}
+
+/// Executes the `ViewPendingRewards` instruction:
+///
+/// - Returns the player's collectable and accrued rewards at the current time as return data.
+pub fn view_pending_rewards(ctx: Context<ViewPendingRewards>) -> Result<PendingRewards> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let ViewPendingRewards {
+        player_data,
+        current_round,
+    } = ctx.accounts;
+
+    player_data.pending_rewards(current_round, timestamp)
+}
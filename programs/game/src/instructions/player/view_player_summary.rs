@@ -0,0 +1,23 @@
+use crate::constants::PLAYER_DATA_SEED;
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `ViewPlayerSummary` instruction is a read-only view of a player's state and purchase streak,
+/// returned through simulation so clients do not re-derive the streak rules.
+#[derive(Accounts)]
+pub struct ViewPlayerSummary<'info> {
+    /// The player data whose summary is returned.
+    #[account(seeds = [PLAYER_DATA_SEED, player_data.player.as_ref()], bump)]
+    pub player_data: Box<Account<'info, PlayerData>>,
+}
+
+/// Executes the `ViewPlayerSummary` instruction:
+///
+/// - Returns the player's ORE, streak status and airdrop eligibility at the current time as return data.
+pub fn view_player_summary(ctx: Context<ViewPlayerSummary>) -> Result<PlayerSummary> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    ctx.accounts.player_data.summary(timestamp)
+}
@@ -0,0 +1,26 @@
+use crate::constants::ROUND_SEED;
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `ViewRoundSummary` instruction is a read-only view of a round's state, phase and countdown,
+/// returned through simulation so clients do not re-derive them.
+#[derive(Accounts)]
+pub struct ViewRoundSummary<'info> {
+    /// The round whose summary is returned.
+    #[account(
+        seeds = [ROUND_SEED, round.round_number.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Box<Account<'info, Round>>,
+}
+
+/// Executes the `ViewRoundSummary` instruction:
+///
+/// - Returns the round's phase, time to its end and exit rewards per ORE at the current time as return data.
+pub fn view_round_summary(ctx: Context<ViewRoundSummary>) -> Result<RoundSummary> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    ctx.accounts.round.summary(timestamp)
+}
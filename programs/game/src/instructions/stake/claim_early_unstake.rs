@@ -0,0 +1,191 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_INDEX_SEED, STAKE_ORDER_SEED,
+    STAKE_POOL_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    to_timestamp_u64, transfer_from_token_vault_to_token_account, transfer_from_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `ClaimEarlyUnstake` instruction settles a stake order after `request_early_unstake`.
+/// Once the order's `early_claimable_timestamp` is reached, the player receives their principal plus
+/// the reduced rewards, and the emitted event reports exactly which rewards were forfeited.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(order_number: u16)]
+pub struct ClaimEarlyUnstake<'info> {
+    /// The player claiming the order. Must be the signer of the transaction.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The global game account.
+    #[account(mut,
+        seeds = [GAME_SEED], bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The player's data account, linking the player to their token account.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = token_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The early-unstaked order to be claimed. The order must not be completed yet.
+    #[account(mut,
+        seeds = [
+            STAKE_ORDER_SEED,
+            player.key().as_ref(),
+            order_number.to_le_bytes().as_ref()
+        ],
+        bump,
+        has_one = stake_order_vault,
+        constraint = !stake_order.is_completed @ ErrorCode::StakeOrderAlreadyCompleted,
+        constraint = stake_order.is_early_unstaked @ ErrorCode::StakeOrderNotEarlyUnstaked,
+    )]
+    pub stake_order: Box<Account<'info, StakeOrder>>,
+
+    /// The player's stake order index, from which the claimed order is removed if it exists.
+    /// Players whose orders predate the index have none, and are not charged to create it here.
+    /// CHECK: Derived from the player; deserialized by the handler unless empty.
+    #[account(mut, seeds = [STAKE_ORDER_INDEX_SEED, player.key().as_ref()], bump)]
+    pub stake_order_index: UncheckedAccount<'info>,
+
+    /// The associated token vault for this stake order, holding the staked principal.
+    #[account(mut)]
+    pub stake_order_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's token account, where the principal and reduced rewards will be transferred.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global stake pool account, tracking total staked amounts and rewards distribution.
+    #[account(mut,
+        seeds = [STAKE_POOL_SEED],
+        bump,
+        has_one = stake_pool_token_vault,
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The stake pool's token vault, holding the rewards pool tokens.
+    #[account(mut)]
+    pub stake_pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The SPL token program, required for token transfer operations.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Claims an early-unstaked order:
+///
+/// Steps:
+/// 1. Check that the order's `early_claimable_timestamp` has been reached.
+/// 2. Mark the order as completed and adjust the pool state accordingly.
+/// 3. Transfer the principal and the reduced rewards to the player's token account.
+/// 4. Remove the order from the player's stake order index.
+/// 5. Emit a `ClaimEarlyUnstake` event including the forfeited rewards.
+pub fn claim_early_unstake(ctx: Context<ClaimEarlyUnstake>, order_number: u16) -> Result<()> {
+    // Obtain the current UNIX timestamp
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references to the relevant accounts
+    let ClaimEarlyUnstake {
+        token_mint,
+        game,
+        player,
+        stake_pool,
+        token_account,
+        stake_order,
+        stake_order_index,
+        stake_order_vault,
+        token_program,
+        stake_pool_token_vault,
+        ..
+    } = ctx.accounts;
+
+    // Check that the early unlock duration has passed
+    require!(
+        stake_order.can_claim_early_unstake(timestamp),
+        ErrorCode::StakeOrderCannotUnstake
+    );
+
+    let stake_amount = stake_order.stake_amount;
+    let token_rewards =
+        stake_order.claim_interest(timestamp, stake_pool.distributable_token_rewards)?;
+
+    // Mark the order as completed and update the stake pool state.
+    stake_order.complete_with_unpaid_interest(timestamp)?;
+    stake_pool.complete_order(stake_amount)?;
+
+    stake_pool.pay_interest(token_rewards)?;
+
+    game.distributed_stake_rewards = game.distributed_stake_rewards.safe_add(token_rewards)?;
+
+    // Return the staked principal to the player
+    transfer_from_token_vault_to_token_account(
+        stake_order,
+        stake_order_vault,
+        token_account,
+        token_mint,
+        token_program,
+        stake_amount,
+        &[
+            STAKE_ORDER_SEED,
+            player.key().as_ref(),
+            order_number.to_le_bytes().as_ref(),
+            &[ctx.bumps.stake_order],
+        ],
+    )?;
+
+    // Pay the reduced rewards from the pool vault
+    transfer_from_vault(
+        stake_pool,
+        ctx.bumps.stake_pool,
+        stake_pool_token_vault,
+        token_account,
+        token_mint,
+        token_program,
+        token_rewards,
+    )?;
+
+    StakeOrderIndex::update_if_exists(stake_order_index, |index| index.remove_order(order_number))?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the claim and the forfeited rewards
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::ClaimEarlyUnstake as u16,
+        event_type: EventType::ClaimEarlyUnstake,
+        event_nonce: game.event_nonce,
+        data: EventData::ClaimEarlyUnstake {
+            player: player.key(),
+            stake_order: stake_order.key(),
+            stake_amount,
+            token_rewards: stake_order.token_rewards,
+            forfeited_token_rewards: stake_order.forfeited_token_rewards,
+            forfeited_voucher_rewards: stake_order.forfeited_voucher_rewards,
+            stake_pool: stake_pool.key(),
+        },
+        initiator_type: InitiatorType::STAKE,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_SEED, STAKE_POOL_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_vault};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `ClaimStakeInterest` instruction pays out the interest a stake order has accrued since its last claim.
+/// The principal stays staked; interest keeps accruing at the order's APR until the end of its lock duration.
+/// A completed order can still claim the interest the pool could not pay when it was unstaked.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(order_number: u16)]
+pub struct ClaimStakeInterest<'info> {
+    /// The player claiming the interest. Must be the signer of the transaction.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The global game account.
+    #[account(mut,
+        seeds = [GAME_SEED], bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The player's data account, linking the player to their token account.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = token_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The stake order accruing the interest, or a completed order still owed unpaid interest.
+    #[account(mut,
+        seeds = [
+            STAKE_ORDER_SEED,
+            player.key().as_ref(),
+            order_number.to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub stake_order: Box<Account<'info, StakeOrder>>,
+
+    /// The player's token account, where the interest will be transferred.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global stake pool account, tracking total staked amounts and rewards distribution.
+    #[account(mut,
+        seeds = [STAKE_POOL_SEED],
+        bump,
+        has_one = stake_pool_token_vault,
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The stake pool's token vault, holding the rewards pool tokens.
+    #[account(mut)]
+    pub stake_pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The SPL token program, required for token transfer operations.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Claims the interest accrued by a stake order:
+///
+/// Steps:
+/// 1. Compute the interest accrued since `last_claimed_timestamp`, or the unpaid interest of a completed order,
+///    limited to the pool's distributable rewards.
+/// 2. Record the claim on the order and the pool.
+/// 3. Transfer the interest from the pool vault to the player's token account.
+/// 4. Emit a `ClaimStakeInterest` event.
+pub fn claim_stake_interest(ctx: Context<ClaimStakeInterest>, _order_number: u16) -> Result<()> {
+    // Obtain the current UNIX timestamp
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references to the relevant accounts
+    let ClaimStakeInterest {
+        token_mint,
+        game,
+        player,
+        stake_pool,
+        token_account,
+        stake_order,
+        token_program,
+        stake_pool_token_vault,
+        ..
+    } = ctx.accounts;
+
+    let token_rewards =
+        stake_order.claim_interest(timestamp, stake_pool.distributable_token_rewards)?;
+    require!(token_rewards > 0, ErrorCode::NoStakeInterestToClaim);
+
+    stake_pool.pay_interest(token_rewards)?;
+    game.distributed_stake_rewards = game.distributed_stake_rewards.safe_add(token_rewards)?;
+
+    // Pay the interest from the pool vault
+    transfer_from_vault(
+        stake_pool,
+        ctx.bumps.stake_pool,
+        stake_pool_token_vault,
+        token_account,
+        token_mint,
+        token_program,
+        token_rewards,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the interest claim
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::ClaimStakeInterest as u16,
+        event_type: EventType::ClaimStakeInterest,
+        event_nonce: game.event_nonce,
+        data: EventData::ClaimStakeInterest {
+            player: player.key(),
+            stake_order: stake_order.key(),
+            token_rewards,
+            total_token_rewards: stake_order.token_rewards,
+            stake_pool: stake_pool.key(),
+        },
+        initiator_type: InitiatorType::STAKE,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,376 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_INDEX_SEED, STAKE_ORDER_SEED,
+    STAKE_POOL_SEED, VAULT_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    calculate_proportion, redeem_vouchers, refreeze_voucher_account,
+    thaw_voucher_account_if_frozen, to_timestamp_u64, transfer_from_player_to_vault,
+    transfer_from_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `DepositAndStake` instruction composes `deposit` and `stake` in one call, so a player cannot be left
+/// holding deposited game tokens when the stake transaction fails to land. The deposit tokens are exchanged
+/// for game tokens through the deposit vault, and every whole shard of them is staked right away; the part
+/// of the deposit short of a whole shard stays in the player's token account.
+/// Like `stake`, it fails once the player holds `MAX_ACTIVE_STAKE_ORDERS` (32) active stake orders.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositAndStake<'info> {
+    /// The player initiating the stake, must sign the transaction.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The global game account.
+    #[account(mut,
+        seeds = [GAME_SEED], bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The player's data account, storing player-specific state.
+    /// Verified by `seeds` and `has_one` constraints ensuring token_account and voucher_account association.
+    #[account(
+        mut,
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = token_account,
+        has_one = voucher_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The global staking pool account, maintaining state of APR, total staked amount, and reward distribution.
+    /// Verified by `seeds` and associations to `stake_pool_token_vault` and `token_mint`.
+    #[account(mut,
+        seeds = [STAKE_POOL_SEED],
+        bump,
+        has_one = stake_pool_voucher_vault
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The stake order account to be created for this staking operation.
+    /// Represents a single stake position belonging to the player.
+    #[account(init,
+        payer = player,
+        space = 8 + StakeOrder::INIT_SPACE,
+        seeds = [STAKE_ORDER_SEED, player.key().as_ref(), player_data.nonce.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stake_order: Box<Account<'info, StakeOrder>>,
+
+    /// The player's stake order index, listing all active stake orders.
+    /// Created on the player's first stake after the index was introduced.
+    #[account(init_if_needed,
+        payer = player,
+        space = 8 + StakeOrderIndex::INIT_SPACE,
+        seeds = [STAKE_ORDER_INDEX_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub stake_order_index: Box<Account<'info, StakeOrderIndex>>,
+
+    /// The associated token account (vault) for the stake order.
+    /// Holds the staked tokens for this particular order.
+    #[account(
+        init,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = stake_order,
+        associated_token::token_program = token_program
+    )]
+    pub stake_order_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's account of the deposit token, from which the deposited tokens are burned.
+    #[account(mut,
+        associated_token::mint = deposit_token_mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program
+    )]
+    pub deposit_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The deposit vault exchanging deposit tokens for game tokens.
+    #[account(mut,
+        seeds = [VAULT_SEED],
+        bump,
+        constraint = vault.token_mint == deposit_token_mint.key(),
+        constraint = vault.token_vault == vault_token_vault.key(),
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    /// The mint of the deposit token.
+    #[account(mut)]
+    pub deposit_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The deposit vault's game token account, paying out the deposited game tokens.
+    #[account(mut)]
+    pub vault_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's token account, receiving the deposited game tokens and paying the stake.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's voucher account, where newly minted vouchers will be credited.
+    #[account(mut)]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global voucher account, acting as the freeze authority of the voucher mint.
+    #[account(seeds = [VOUCHER_SEED], bump, has_one = voucher_mint)]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher token mint account.
+    #[account(seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The stake pool's voucher vault holding the staked assets and available rewards.
+    #[account(mut)]
+    pub stake_pool_voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint for the stake token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program, used for token operations like minting and transferring.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The associated token program, used for creating associated token accounts (like stake_order_vault).
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The system program, required for certain account creation operations.
+    pub system_program: Program<'info, System>,
+
+    /// Today's stats account, recording the stake when passed.
+    #[account(mut)]
+    pub daily_stats: Option<Box<Account<'info, DailyStats>>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the deposit and the stake:
+/// 1. Validates the `token_amount` covers at least one shard and the deposit vault holds enough game tokens.
+/// 2. Burns the deposit tokens and redeems the same amount of game tokens from the deposit vault into the
+///    player's token account, emitting a `Deposit` event.
+/// 3. Stakes the whole shards of the deposit as `stake` does: creates a stake order accruing interest at the
+///    pool APR, transfers the staked tokens to the `stake_order_vault` and credits the voucher rewards.
+///    Records the stake on today's stats if they were passed.
+/// 4. Emits a `Stake` event logging the stake operation.
+pub fn deposit_and_stake(ctx: Context<DepositAndStake>, token_amount: u64) -> Result<()> {
+    // Fetch the current UNIX timestamp for record keeping
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references to accounts for easier manipulation
+    let DepositAndStake {
+        deposit_token_account,
+        vault,
+        deposit_token_mint,
+        vault_token_vault,
+        token_mint,
+        voucher,
+        voucher_mint,
+        game,
+        player,
+        player_data,
+        stake_pool,
+        stake_order,
+        stake_order_index,
+        stake_order_vault,
+        token_account,
+        voucher_account,
+        stake_pool_voucher_vault,
+        token_program,
+        daily_stats,
+        ..
+    } = ctx.accounts;
+
+    // Stake every whole shard of the deposit
+    let shards_amount = token_amount / stake_pool.one_shard;
+    require!(shards_amount > 0, ErrorCode::InvalidAmount);
+
+    // Refuse new orders while the pool is being wound down or decommissioned
+    require!(!stake_pool.is_winding_down, ErrorCode::StakePoolWindingDown);
+    require!(
+        !stake_pool.is_decommissioned,
+        ErrorCode::StakePoolDecommissioned
+    );
+
+    // Compute one shard and full stake amount in lamports
+    let one_shard = stake_pool.one_shard;
+    let stake_amount = shards_amount.safe_mul(one_shard)?;
+
+    // Ensure the player holds the deposit and the vault can pay it out
+    require!(
+        deposit_token_account.amount >= token_amount,
+        ErrorCode::InvalidAmount
+    );
+    require!(vault.token_amount >= token_amount, ErrorCode::InvalidAmount);
+
+    vault.deposit(token_amount)?;
+
+    burn(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Burn {
+                mint: deposit_token_mint.to_account_info(),
+                from: deposit_token_account.to_account_info(),
+                authority: player.to_account_info(),
+            },
+        ),
+        token_amount,
+    )?;
+
+    // Pay out the deposited game tokens into the player's token account, which stakes them below
+    redeem_vouchers(
+        vault,
+        ctx.bumps.vault,
+        vault_token_vault,
+        token_account,
+        token_mint,
+        token_program,
+        token_amount,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event to record the deposit on-chain
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Deposit as u16,
+        event_type: EventType::Deposit,
+        event_nonce: game.event_nonce,
+        data: EventData::Deposit {
+            player: player.key(),
+            vault: vault.key(),
+            token_amount,
+        },
+        initiator_type: InitiatorType::DEPOSIT,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    // Use player's nonce as the stake_number for this new order
+    let stake_number = player_data.nonce;
+    let annual_rate = stake_pool.annual_rate;
+
+    // Project the token interest accrued over the full lock duration; it is paid as it is claimed.
+    // Vouchers are issued up front for the same amount.
+    let token_rewards = calculate_proportion(stake_amount, annual_rate)?;
+    let voucher_rewards = token_rewards;
+
+    require!(
+        voucher_rewards <= stake_pool.voucher_rewards_pool_balance,
+        ErrorCode::InsufficientRemainingVoucherRewards
+    );
+
+    stake_pool.staked_amount = stake_pool.staked_amount.safe_add(stake_amount)?;
+    stake_pool.active_orders = stake_pool.active_orders.safe_add(1)?;
+
+    stake_pool.voucher_rewards_pool_balance = stake_pool
+        .voucher_rewards_pool_balance
+        .safe_sub(voucher_rewards)?;
+    stake_pool.distributed_voucher_rewards = stake_pool
+        .distributed_voucher_rewards
+        .safe_add(voucher_rewards)?;
+
+    game.distributed_stake_rewards = game.distributed_stake_rewards.safe_add(voucher_rewards)?;
+
+    // Initialize the stake order with the calculated values and vault info
+    stake_order.initialize(
+        stake_number,
+        stake_amount,
+        annual_rate,
+        stake_pool.lock_duration,
+        voucher_rewards,
+        stake_order_vault.key(),
+        timestamp,
+        ctx.bumps.stake_order,
+    )?;
+
+    // List the new order in the player's stake order index
+    stake_order_index.initialize_if_needed(player.key(), ctx.bumps.stake_order_index)?;
+    stake_order_index.add_order(stake_number, stake_order.unstaked_timestamp)?;
+
+    // Increment the player_data nonce to ensure uniqueness for future orders
+    player_data.increment_nonce()?;
+    player_data.stake_count = player_data.stake_count.safe_add(1)?;
+    // Count the new order in the player's stake boost
+    player_data.add_stake_boost(stake_order, timestamp)?;
+
+    // Record the stake on today's stats if they were passed
+    if let Some(daily_stats) = daily_stats {
+        daily_stats.require_day(timestamp)?;
+        daily_stats.record_stake(stake_amount)?;
+    }
+
+    // Transfer the stake_amount from player's token account to the order vault
+    transfer_from_player_to_vault(
+        player,
+        token_account,
+        stake_order_vault,
+        token_mint,
+        token_program,
+        stake_amount,
+    )?;
+
+    // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    // Transfer the equivalent of staked tokens from the pool vault to the voucher vault,
+    // representing the locked value behind the vouchers just minted.
+    transfer_from_vault(
+        stake_pool,
+        ctx.bumps.stake_pool,
+        stake_pool_voucher_vault,
+        voucher_account,
+        voucher_mint,
+        token_program,
+        voucher_rewards,
+    )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event to record the staking action on-chain
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Stake as u16,
+        event_type: EventType::Stake,
+        event_nonce: game.event_nonce,
+        data: EventData::Stake {
+            player: player.key(),
+            stake_order: stake_order.key(),
+            stake_pool: stake_pool.key(),
+            stake_amount,
+            annual_rate,
+            lock_duration: stake_pool.lock_duration,
+            token_rewards,
+            voucher_rewards,
+        },
+        initiator_type: InitiatorType::STAKE,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
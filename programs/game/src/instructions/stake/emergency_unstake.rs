@@ -0,0 +1,165 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_INDEX_SEED, STAKE_ORDER_SEED,
+    STAKE_POOL_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_token_vault_to_token_account};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `EmergencyUnstake` instruction lets a player withdraw the principal of a stake order immediately
+/// while the stake pool is being wound down. The lock period is ignored and all unpaid token rewards
+/// of the order are forfeited back to the pool's distributable rewards.
+///
+/// The game has no pause state: winding down the stake pool is its pause for staking, so it is the only
+/// condition opening emergency withdrawals.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(order_number: u16)]
+pub struct EmergencyUnstake<'info> {
+    /// The player withdrawing their principal. Must be the signer of the transaction.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The global game account.
+    #[account(mut,
+        seeds = [GAME_SEED], bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The player's data account, linking the player to their token account.
+    #[account(
+        mut,
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = token_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The stake order to be withdrawn. The order must not be completed yet.
+    #[account(mut,
+        seeds = [
+            STAKE_ORDER_SEED,
+            player.key().as_ref(),
+            order_number.to_le_bytes().as_ref()
+        ],
+        bump,
+        has_one = stake_order_vault,
+        constraint = !stake_order.is_completed @ ErrorCode::StakeOrderAlreadyCompleted,
+        constraint = !stake_order.is_voucher_stake @ ErrorCode::StakeOrderAssetMismatch,
+    )]
+    pub stake_order: Box<Account<'info, StakeOrder>>,
+
+    /// The player's stake order index, from which the withdrawn order is removed if it exists.
+    /// Players whose orders predate the index have none, and are not charged to create it here.
+    /// CHECK: Derived from the player; deserialized by the handler unless empty.
+    #[account(mut, seeds = [STAKE_ORDER_INDEX_SEED, player.key().as_ref()], bump)]
+    pub stake_order_index: UncheckedAccount<'info>,
+
+    /// The associated token vault for this stake order, holding the staked principal.
+    #[account(mut)]
+    pub stake_order_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's token account, where the principal will be transferred.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global stake pool account, which must be winding down.
+    #[account(mut,
+        seeds = [STAKE_POOL_SEED],
+        bump,
+        constraint = stake_pool.is_winding_down @ ErrorCode::StakePoolNotWindingDown,
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The SPL token program, required for token transfer operations.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Withdraws the principal of a stake order from a winding-down pool:
+///
+/// Steps:
+/// 1. Mark the order as completed and remove its principal from the pool.
+/// 2. Return the order's unpaid token rewards to the pool's distributable rewards.
+/// 3. Transfer the principal from the order vault back to the player's token account.
+/// 4. Remove the order from the player's stake order index.
+/// 5. Emit an `EmergencyUnstake` event recording the forfeited rewards.
+pub fn emergency_unstake(ctx: Context<EmergencyUnstake>, order_number: u16) -> Result<()> {
+    // Obtain the current UNIX timestamp
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references to the relevant accounts
+    let EmergencyUnstake {
+        token_mint,
+        game,
+        player,
+        player_data,
+        stake_pool,
+        token_account,
+        stake_order,
+        stake_order_index,
+        stake_order_vault,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    let stake_amount = stake_order.stake_amount;
+    let forfeited_token_rewards = stake_order.accrued_interest(timestamp)?;
+
+    // Complete the order without paying any rewards
+    stake_order.complete()?;
+    player_data.remove_stake_boost(stake_order, timestamp)?;
+    stake_pool.complete_order(stake_amount)?;
+
+    // Return the staked principal to the player
+    transfer_from_token_vault_to_token_account(
+        stake_order,
+        stake_order_vault,
+        token_account,
+        token_mint,
+        token_program,
+        stake_amount,
+        &[
+            STAKE_ORDER_SEED,
+            player.key().as_ref(),
+            order_number.to_le_bytes().as_ref(),
+            &[ctx.bumps.stake_order],
+        ],
+    )?;
+
+    StakeOrderIndex::update_if_exists(stake_order_index, |index| index.remove_order(order_number))?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the emergency withdrawal
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::EmergencyUnstake as u16,
+        event_type: EventType::EmergencyUnstake,
+        event_nonce: game.event_nonce,
+        data: EventData::EmergencyUnstake {
+            player: player.key(),
+            stake_order: stake_order.key(),
+            stake_amount,
+            forfeited_token_rewards,
+            stake_pool: stake_pool.key(),
+        },
+        initiator_type: InitiatorType::STAKE,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
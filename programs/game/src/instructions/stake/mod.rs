@@ -1,7 +1,23 @@
 pub mod request_early_unstake;
 pub mod stake;
 pub mod unstake;
+pub mod unstake_matured_batch;
+pub mod update_stake_rates;
+pub mod emergency_unstake;
+pub mod stake_vouchers;
+pub mod unstake_vouchers;
+pub mod claim_early_unstake;
+pub mod claim_stake_interest;
+pub mod deposit_and_stake;
 
 pub use request_early_unstake::*;
 pub use stake::*;
 pub use unstake::*;
+pub use unstake_matured_batch::*;
+pub use update_stake_rates::*;
+pub use emergency_unstake::*;
+pub use stake_vouchers::*;
+pub use unstake_vouchers::*;
+pub use claim_early_unstake::*;
+pub use claim_stake_interest::*;
+pub use deposit_and_stake::*;
@@ -1,19 +1,20 @@
 use crate::constants::{
-    GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_SEED, STAKE_POOL_SEED, TOKEN_MINT, VOUCHER_MINT_SEED,
-    VOUCHER_SEED,
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_INDEX_SEED, STAKE_ORDER_SEED,
+    STAKE_POOL_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
 };
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
-use crate::utils::to_timestamp_u64;
+use crate::utils::{refreeze_voucher_account, thaw_voucher_account_if_frozen, to_timestamp_u64};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
-use anchor_spl::token::{self, burn, Burn, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
 /// The `RequestEarlyUnstake` instruction enables a player to initiate an early unlock of their staked tokens before the normal lock period ends.
-/// Early unlocking comes at a reduced APR, resulting in fewer rewards. This process involves adjusting the stake order, burning vouchers,
-/// and reallocating unused rewards back to the game pool.
+/// Early unlocking comes at a reduced APR, resulting in fewer rewards. This process involves adjusting the stake order
+/// so that interest stops accruing, and burning the vouchers issued for the order.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(order_number: u16)]
 pub struct RequestEarlyUnstake<'info> {
@@ -40,25 +41,27 @@ pub struct RequestEarlyUnstake<'info> {
 
     /// The player's voucher token account, holding vouchers representing staked value.
     #[account(mut)]
-    pub voucher_account: Box<Account<'info, TokenAccount>>,
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The stake order to be unlocked early.
     /// Verified by `seeds` to ensure it belongs to the `player`.
     #[account(mut,
         seeds = [STAKE_ORDER_SEED, player.key().as_ref(), order_number.to_le_bytes().as_ref()],
         bump,
+        constraint = !stake_order.is_voucher_stake @ ErrorCode::StakeOrderAssetMismatch,
     )]
     pub stake_order: Box<Account<'info, StakeOrder>>,
 
-    /// The associated token vault for the stake order, initially holding staked tokens and allocated rewards.
-    #[account(mut)]
-    pub stake_pool_token_vault: Box<Account<'info, TokenAccount>>,
+    /// The player's stake order index, updated with the order's early unstake status if it exists.
+    /// Players whose orders predate the index have none, and are not charged to create it here.
+    /// CHECK: Derived from the player; deserialized by the handler unless empty.
+    #[account(mut, seeds = [STAKE_ORDER_INDEX_SEED, player.key().as_ref()], bump)]
+    pub stake_order_index: UncheckedAccount<'info>,
 
     /// The global stake pool account managing staking rates, rewards distribution, and total staked amounts.
     #[account(mut,
         seeds = [STAKE_POOL_SEED],
         bump,
-        has_one = stake_pool_token_vault,
     )]
     pub stake_pool: Box<Account<'info, StakePool>>,
 
@@ -74,29 +77,31 @@ pub struct RequestEarlyUnstake<'info> {
 
     /// The voucher vault holding tokens that back the voucher supply.
     #[account(mut)]
-    pub voucher_vault: Box<Account<'info, TokenAccount>>,
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The token mint account used to issue and burn voucher tokens.
     #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
-    pub voucher_mint: Box<Account<'info, Mint>>,
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The token mint account used to issue and burn underlying tokens.
-    #[account(mut, address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(mut, address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The SPL token program used for all token operations.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Processes an early unlock request for a stake order:
 ///
 /// 1. Verifies that the stake order is still locked and not completed.
 /// 2. Ensures the player holds enough vouchers corresponding to the staked amount.
-/// 3. Adjusts the stake order's APR to the early unlock rate and recalculates rewards based on the elapsed time.
-/// 4. Burns the player's vouchers equal to the staked amount and redeems the underlying tokens.
-/// 5. Returns unused rewards to the game's mining pool and updates relevant accounts.
-/// 6. Emits a `RequestEarlyUnstake` event for record-keeping.
+/// 3. Adjusts the stake order's APR to the early unlock rate and stops interest accrual at the elapsed time.
+/// 4. Burns the player's vouchers issued for the order and the underlying tokens backing them.
+/// 5. Emits a `RequestEarlyUnstake` event for record-keeping.
 pub fn request_early_unstake(ctx: Context<RequestEarlyUnstake>, order_number: u16) -> Result<()> {
     // Fetch the current UNIX timestamp from the clock sysvar
     let clock = Clock::get()?;
@@ -110,7 +115,7 @@ pub fn request_early_unstake(ctx: Context<RequestEarlyUnstake>, order_number: u1
         voucher_account,
         stake_pool,
         stake_order,
-        stake_pool_token_vault,
+        stake_order_index,
         voucher,
         voucher_vault,
         voucher_mint,
@@ -147,9 +152,11 @@ pub fn request_early_unstake(ctx: Context<RequestEarlyUnstake>, order_number: u1
         ErrorCode::InsufficientVoucherBalance
     );
 
-    let token_rewards = stake_order.token_rewards;
     let voucher_rewards = stake_order.voucher_rewards;
 
+    // The order no longer counts in the player's stake boost once its lock is cut short
+    player_data.remove_stake_boost(stake_order, timestamp)?;
+
     // Request early unlock, recomputing rewards at the reduced APR
     stake_order.request_early_unstake(
         timestamp,
@@ -157,7 +164,10 @@ pub fn request_early_unstake(ctx: Context<RequestEarlyUnstake>, order_number: u1
         stake_pool.early_unlock_duration,
     )?;
 
-    let burned_token_rewards = token_rewards.safe_sub(stake_order.token_rewards)?;
+    StakeOrderIndex::update_if_exists(stake_order_index, |index| {
+        index.mark_early_unstaked(order_number, stake_order.unstaked_timestamp)
+    })?;
+
     let burned_voucher_rewards = voucher_rewards.safe_sub(stake_order.voucher_rewards)?;
 
     game.distributed_stake_rewards = game
@@ -168,13 +178,6 @@ pub fn request_early_unstake(ctx: Context<RequestEarlyUnstake>, order_number: u1
         .distributed_voucher_rewards
         .safe_sub(burned_voucher_rewards)?;
 
-    stake_pool.token_rewards_pool_balance = stake_pool
-        .token_rewards_pool_balance
-        .safe_sub(burned_token_rewards)?;
-
-    stake_pool.burned_token_rewards = stake_pool
-        .burned_token_rewards
-        .safe_add(burned_token_rewards)?;
     stake_pool.burned_voucher_rewards = stake_pool
         .burned_voucher_rewards
         .safe_add(burned_voucher_rewards)?;
@@ -182,6 +185,15 @@ pub fn request_early_unstake(ctx: Context<RequestEarlyUnstake>, order_number: u1
     // Update the voucher state by "burning" the corresponding staked amount (removing vouchers from circulation)
     voucher.burn(burned_voucher_rewards)?;
 
+    // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
     burn(
         CpiContext::new(
             token_program.to_account_info(),
@@ -193,6 +205,36 @@ pub fn request_early_unstake(ctx: Context<RequestEarlyUnstake>, order_number: u1
         ),
         burned_voucher_rewards,
     )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    game.record_burned_vouchers(burned_voucher_rewards)?;
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the burn
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Burn as u16,
+        event_type: EventType::Burn,
+        event_nonce: game.event_nonce,
+        data: EventData::Burn {
+            mint: voucher_mint.key(),
+            from: voucher_account.key(),
+            amount: burned_voucher_rewards,
+            reason: BurnReason::EarlyUnstake,
+            total_burned: game.total_burned_vouchers,
+        },
+        initiator_type: InitiatorType::STAKE,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
 
     burn(
         CpiContext::new_with_signer(
@@ -207,23 +249,34 @@ pub fn request_early_unstake(ctx: Context<RequestEarlyUnstake>, order_number: u1
         burned_voucher_rewards,
     )?;
 
-    burn(
-        CpiContext::new_with_signer(
-            token_program.to_account_info(),
-            Burn {
-                mint: token_mint.to_account_info(),
-                from: stake_pool_token_vault.to_account_info(),
-                authority: stake_pool.to_account_info(),
-            },
-            &[&[STAKE_POOL_SEED, &[ctx.bumps.stake_pool]]],
-        ),
-        burned_token_rewards,
-    )?;
+    game.record_burned_tokens(burned_voucher_rewards)?;
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the burn
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Burn as u16,
+        event_type: EventType::Burn,
+        event_nonce: game.event_nonce,
+        data: EventData::Burn {
+            mint: token_mint.key(),
+            from: voucher_vault.key(),
+            amount: burned_voucher_rewards,
+            reason: BurnReason::EarlyUnstake,
+            total_burned: game.total_burned_tokens,
+        },
+        initiator_type: InitiatorType::STAKE,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
 
     game.increment_event_nonce()?;
 
     // Emit an event capturing the early unlock request
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::RequestEarlyUnstake as u16,
         event_type: EventType::RequestEarlyUnstake,
         event_nonce: game.event_nonce,
         data: EventData::RequestEarlyUnstake {
@@ -235,7 +288,8 @@ pub fn request_early_unstake(ctx: Context<RequestEarlyUnstake>, order_number: u1
         initiator_type: InitiatorType::STAKE,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
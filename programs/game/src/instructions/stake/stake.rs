@@ -1,23 +1,26 @@
 use crate::constants::{
-    GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_SEED, STAKE_POOL_SEED, TOKEN_MINT,
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_INDEX_SEED, STAKE_ORDER_SEED,
+    STAKE_POOL_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
 };
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::{
-    calculate_proportion, to_timestamp_u64, transfer_from_player_to_vault,
-    transfer_from_token_vault_to_token_account,
+    calculate_proportion, refreeze_voucher_account, thaw_voucher_account_if_frozen,
+    to_timestamp_u64, transfer_from_player_to_vault, transfer_from_vault,
 };
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
 /// The `Stake` instruction allows a player to stake tokens (shards) into the staking pool, create a stake order,
 /// and receive voucher tokens representing their staked assets. It involves transferring tokens from the player's
 /// account to a newly created stake order vault, then into the pool vault. Additionally, the player receives minted
 /// vouchers to confirm their stake, and rewards are allocated based on the configured APR.
+/// A player may hold at most `MAX_ACTIVE_STAKE_ORDERS` (32) active stake orders at once.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Stake<'info> {
     /// The player initiating the stake, must sign the transaction.
@@ -60,48 +63,75 @@ pub struct Stake<'info> {
     )]
     pub stake_order: Box<Account<'info, StakeOrder>>,
 
+    /// The player's stake order index, listing all active stake orders.
+    /// Created on the player's first stake after the index was introduced.
+    #[account(init_if_needed,
+        payer = player,
+        space = 8 + StakeOrderIndex::INIT_SPACE,
+        seeds = [STAKE_ORDER_INDEX_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub stake_order_index: Box<Account<'info, StakeOrderIndex>>,
+
     /// The associated token account (vault) for the stake order.
     /// Holds the staked tokens for this particular order.
     #[account(
         init,
         payer = player,
         associated_token::mint = token_mint,
-        associated_token::authority = stake_order
+        associated_token::authority = stake_order,
+        associated_token::token_program = token_program
     )]
-    pub stake_order_vault: Box<Account<'info, TokenAccount>>,
+    pub stake_order_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The player's token account, from which staked tokens will be deducted.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The player's voucher account, where newly minted vouchers will be credited.
     #[account(mut)]
-    pub voucher_account: Box<Account<'info, TokenAccount>>,
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global voucher account, acting as the freeze authority of the voucher mint.
+    #[account(seeds = [VOUCHER_SEED], bump, has_one = voucher_mint)]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher token mint account.
+    #[account(seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The stake pool's voucher vault holding the staked assets and available rewards.
     #[account(mut)]
-    pub stake_pool_voucher_vault: Box<Account<'info, TokenAccount>>,
+    pub stake_pool_voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The token mint for the stake token.
-    #[account(address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The SPL token program, used for token operations like minting and transferring.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The associated token program, used for creating associated token accounts (like stake_order_vault).
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The system program, required for certain account creation operations.
     pub system_program: Program<'info, System>,
+
+    /// Today's stats account, recording the stake when passed.
+    #[account(mut)]
+    pub daily_stats: Option<Box<Account<'info, DailyStats>>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Executes the staking logic:
 /// 1. Validates the input `shards_amount`.
 /// 2. Converts `shards_amount` into `stake_amount` using predefined constants (`ONE_MILLION` and `LAMPORTS_PER_TOKEN`).
 /// 3. Ensures the player has sufficient tokens.
-/// 4. Creates a stake order and allocates reward tokens from the pool.
+/// 4. Creates a stake order accruing interest at the pool APR and allocates voucher rewards from the pool.
+///    Records the stake on today's stats if they were passed.
 /// 5. Transfers the staked tokens from the player's token account to the `stake_order_vault`,
 ///    then from `stake_order_vault` to the `stake_pool_token_vault`.
 /// 6. Mints voucher tokens to the player's voucher account and moves corresponding tokens to the `voucher_vault`.
@@ -113,22 +143,34 @@ pub fn stake(ctx: Context<Stake>, shards_amount: u64) -> Result<()> {
 
     // Extract references to accounts for easier manipulation
     let Stake {
+        token_mint,
+        voucher,
+        voucher_mint,
         game,
         player,
         player_data,
         stake_pool,
         stake_order,
+        stake_order_index,
         stake_order_vault,
         token_account,
         voucher_account,
         stake_pool_voucher_vault,
         token_program,
+        daily_stats,
         ..
     } = ctx.accounts;
 
     // Validate that the player is staking a positive amount
     require!(shards_amount > 0, ErrorCode::InvalidAmount);
 
+    // Refuse new orders while the pool is being wound down or decommissioned
+    require!(!stake_pool.is_winding_down, ErrorCode::StakePoolWindingDown);
+    require!(
+        !stake_pool.is_decommissioned,
+        ErrorCode::StakePoolDecommissioned
+    );
+
     // Compute one shard and full stake amount in lamports
     let one_shard = stake_pool.one_shard;
     let stake_amount = shards_amount.safe_mul(one_shard)?;
@@ -143,14 +185,11 @@ pub fn stake(ctx: Context<Stake>, shards_amount: u64) -> Result<()> {
     let stake_number = player_data.nonce;
     let annual_rate = stake_pool.annual_rate;
 
-    // Allocate rewards for this order and update pool state
+    // Project the token interest accrued over the full lock duration; it is paid as it is claimed.
+    // Vouchers are issued up front for the same amount.
     let token_rewards = calculate_proportion(stake_amount, annual_rate)?;
     let voucher_rewards = token_rewards;
 
-    require!(
-        token_rewards <= stake_pool.distributable_token_rewards,
-        ErrorCode::InsufficientRemainingTokenRewards
-    );
     require!(
         voucher_rewards <= stake_pool.voucher_rewards_pool_balance,
         ErrorCode::InsufficientRemainingVoucherRewards
@@ -159,9 +198,6 @@ pub fn stake(ctx: Context<Stake>, shards_amount: u64) -> Result<()> {
     stake_pool.staked_amount = stake_pool.staked_amount.safe_add(stake_amount)?;
     stake_pool.active_orders = stake_pool.active_orders.safe_add(1)?;
 
-    stake_pool.distributable_token_rewards = stake_pool
-        .distributable_token_rewards
-        .safe_sub(token_rewards)?;
     stake_pool.voucher_rewards_pool_balance = stake_pool
         .voucher_rewards_pool_balance
         .safe_sub(voucher_rewards)?;
@@ -177,40 +213,73 @@ pub fn stake(ctx: Context<Stake>, shards_amount: u64) -> Result<()> {
         stake_amount,
         annual_rate,
         stake_pool.lock_duration,
-        token_rewards,
         voucher_rewards,
         stake_order_vault.key(),
         timestamp,
         ctx.bumps.stake_order,
     )?;
 
+    // List the new order in the player's stake order index
+    stake_order_index.initialize_if_needed(player.key(), ctx.bumps.stake_order_index)?;
+    stake_order_index.add_order(stake_number, stake_order.unstaked_timestamp)?;
+
     // Increment the player_data nonce to ensure uniqueness for future orders
     player_data.increment_nonce()?;
+    player_data.stake_count = player_data.stake_count.safe_add(1)?;
+    // Count the new order in the player's stake boost
+    player_data.add_stake_boost(stake_order, timestamp)?;
+
+    // Record the stake on today's stats if they were passed
+    if let Some(daily_stats) = daily_stats {
+        daily_stats.require_day(timestamp)?;
+        daily_stats.record_stake(stake_amount)?;
+    }
 
     // Transfer the stake_amount from player's token account to the order vault
     transfer_from_player_to_vault(
         player,
         token_account,
         stake_order_vault,
+        token_mint,
         token_program,
         stake_amount,
     )?;
 
+    // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
     // Transfer the equivalent of staked tokens from the pool vault to the voucher vault,
     // representing the locked value behind the vouchers just minted.
-    transfer_from_token_vault_to_token_account(
+    transfer_from_vault(
         stake_pool,
+        ctx.bumps.stake_pool,
         stake_pool_voucher_vault,
         voucher_account,
+        voucher_mint,
         token_program,
         voucher_rewards,
-        &[STAKE_POOL_SEED, &[ctx.bumps.stake_pool]],
+    )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
     )?;
 
     game.increment_event_nonce()?;
 
     // Emit an event to record the staking action on-chain
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Stake as u16,
         event_type: EventType::Stake,
         event_nonce: game.event_nonce,
         data: EventData::Stake {
@@ -226,7 +295,8 @@ pub fn stake(ctx: Context<Stake>, shards_amount: u64) -> Result<()> {
         initiator_type: InitiatorType::STAKE,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
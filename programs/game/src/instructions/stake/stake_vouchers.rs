@@ -0,0 +1,225 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_INDEX_SEED, STAKE_ORDER_SEED,
+    STAKE_POOL_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    calculate_proportion, refreeze_voucher_account, thaw_voucher_account_if_frozen,
+    to_timestamp_u64, transfer_from_player_to_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `StakeVouchers` instruction lets a player stake voucher tokens instead of the base token.
+/// The vouchers are escrowed in the pool's `stake_pool_voucher_vault` for the lock duration and earn
+/// token rewards at the pool's APR, giving vouchers an additional sink. No voucher rewards are granted.
+/// Voucher orders count towards the `MAX_ACTIVE_STAKE_ORDERS` (32) active stake orders a player may hold.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StakeVouchers<'info> {
+    /// The player initiating the stake, must sign the transaction.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The global game account.
+    #[account(mut,
+        seeds = [GAME_SEED], bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The player's data account, storing player-specific state.
+    #[account(
+        mut,
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = voucher_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The global staking pool account.
+    #[account(mut,
+        seeds = [STAKE_POOL_SEED],
+        bump,
+        has_one = stake_pool_voucher_vault
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The stake order account to be created for this staking operation.
+    #[account(init,
+        payer = player,
+        space = 8 + StakeOrder::INIT_SPACE,
+        seeds = [STAKE_ORDER_SEED, player.key().as_ref(), player_data.nonce.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub stake_order: Box<Account<'info, StakeOrder>>,
+
+    /// The player's stake order index, listing all active stake orders.
+    #[account(init_if_needed,
+        payer = player,
+        space = 8 + StakeOrderIndex::INIT_SPACE,
+        seeds = [STAKE_ORDER_INDEX_SEED, player.key().as_ref()],
+        bump,
+    )]
+    pub stake_order_index: Box<Account<'info, StakeOrderIndex>>,
+
+    /// The player's voucher account, from which the staked vouchers are deducted.
+    #[account(mut)]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global voucher account, acting as the freeze authority of the voucher mint.
+    #[account(seeds = [VOUCHER_SEED], bump, has_one = voucher_mint)]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher token mint account.
+    #[account(seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The stake pool's voucher vault, escrowing the staked vouchers.
+    #[account(mut)]
+    pub stake_pool_voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The SPL token program, used for token operations.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program, required for account creation.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the voucher staking logic:
+/// 1. Converts `shards_amount` into the voucher `stake_amount` using the pool's `one_shard`.
+/// 2. Ensures the player holds enough vouchers.
+/// 3. Creates a voucher stake order and lists it in the player's stake order index.
+/// 4. Escrows the vouchers in the `stake_pool_voucher_vault`.
+/// 5. Emits a `StakeVouchers` event.
+pub fn stake_vouchers(ctx: Context<StakeVouchers>, shards_amount: u64) -> Result<()> {
+    // Fetch the current UNIX timestamp for record keeping
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references to accounts for easier manipulation
+    let StakeVouchers {
+        voucher,
+        voucher_mint,
+        game,
+        player,
+        player_data,
+        stake_pool,
+        stake_order,
+        stake_order_index,
+        voucher_account,
+        stake_pool_voucher_vault,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    // Validate that the player is staking a positive amount
+    require!(shards_amount > 0, ErrorCode::InvalidAmount);
+
+    // Refuse new orders while the pool is being wound down or decommissioned
+    require!(!stake_pool.is_winding_down, ErrorCode::StakePoolWindingDown);
+    require!(
+        !stake_pool.is_decommissioned,
+        ErrorCode::StakePoolDecommissioned
+    );
+
+    let stake_amount = shards_amount.safe_mul(stake_pool.one_shard)?;
+
+    // Ensure the player has enough vouchers in their voucher account
+    require!(
+        voucher_account.amount >= stake_amount,
+        ErrorCode::InsufficientVoucherBalance
+    );
+
+    // Use player's nonce as the stake_number for this new order
+    let stake_number = player_data.nonce;
+    let annual_rate = stake_pool.annual_rate;
+
+    // Project the token interest the order accrues over its full lock duration
+    let token_rewards = calculate_proportion(stake_amount, annual_rate)?;
+
+    stake_pool.staked_voucher_amount = stake_pool.staked_voucher_amount.safe_add(stake_amount)?;
+    stake_pool.active_orders = stake_pool.active_orders.safe_add(1)?;
+
+    // Initialize the stake order; the escrow vault is the pool's voucher vault
+    stake_order.initialize(
+        stake_number,
+        stake_amount,
+        annual_rate,
+        stake_pool.lock_duration,
+        0,
+        stake_pool_voucher_vault.key(),
+        timestamp,
+        ctx.bumps.stake_order,
+    )?;
+    stake_order.is_voucher_stake = true;
+
+    // List the new order in the player's stake order index
+    stake_order_index.initialize_if_needed(player.key(), ctx.bumps.stake_order_index)?;
+    stake_order_index.add_order(stake_number, stake_order.unstaked_timestamp)?;
+
+    // Increment the player_data nonce to ensure uniqueness for future orders
+    player_data.increment_nonce()?;
+    player_data.stake_count = player_data.stake_count.safe_add(1)?;
+    // Count the new order in the player's stake boost
+    player_data.add_stake_boost(stake_order, timestamp)?;
+
+    // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    // Escrow the vouchers in the pool's voucher vault
+    transfer_from_player_to_vault(
+        player,
+        voucher_account,
+        stake_pool_voucher_vault,
+        voucher_mint,
+        token_program,
+        stake_amount,
+    )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event to record the voucher staking action on-chain
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::StakeVouchers as u16,
+        event_type: EventType::StakeVouchers,
+        event_nonce: game.event_nonce,
+        data: EventData::StakeVouchers {
+            player: player.key(),
+            stake_amount,
+            stake_order: stake_order.key(),
+            stake_pool: stake_pool.key(),
+            annual_rate,
+            lock_duration: stake_pool.lock_duration,
+            token_rewards,
+        },
+        initiator_type: InitiatorType::STAKE,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
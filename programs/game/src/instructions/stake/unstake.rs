@@ -1,15 +1,22 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_SEED, STAKE_POOL_SEED};
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_INDEX_SEED, STAKE_ORDER_SEED,
+    STAKE_POOL_SEED,
+};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
-use crate::utils::{to_timestamp_u64, transfer_from_token_vault_to_token_account};
+use crate::utils::{
+    to_timestamp_u64, transfer_from_token_vault_to_token_account, transfer_from_vault,
+};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
-use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
 /// The `Unstake` instruction allows a player to withdraw their originally staked tokens plus accrued rewards from a completed or fully vested stake order.
-/// Once the lock-up period (or early unlock duration) has passed, the player can unstake their tokens and claim rewards directly to their token account.
+/// Once the lock-up period has passed, the player can unstake their tokens and claim rewards directly to their token account.
+/// Orders that requested an early unstake are settled through `claim_early_unstake` instead.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(order_number: u16)]
 pub struct Unstake<'info> {
@@ -26,6 +33,7 @@ pub struct Unstake<'info> {
     /// The player's data account tracking their state, including orders created.
     /// Verified by `seeds` to ensure the correct association with the player.
     #[account(
+        mut,
         seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
         bump,
         has_one = token_account,
@@ -44,16 +52,24 @@ pub struct Unstake<'info> {
         bump,
         has_one = stake_order_vault,
         constraint = stake_order.is_completed == false,
+        constraint = !stake_order.is_voucher_stake @ ErrorCode::StakeOrderAssetMismatch,
+        constraint = !stake_order.is_early_unstaked @ ErrorCode::StakeOrderAlreadyEarlyUnstaked,
     )]
     pub stake_order: Box<Account<'info, StakeOrder>>,
 
+    /// The player's stake order index, from which the completed order is removed if it exists.
+    /// Players whose orders predate the index have none, and are not charged to create it here.
+    /// CHECK: Derived from the player; deserialized by the handler unless empty.
+    #[account(mut, seeds = [STAKE_ORDER_INDEX_SEED, player.key().as_ref()], bump)]
+    pub stake_order_index: UncheckedAccount<'info>,
+
     /// The associated token vault for this stake order, holding the staked tokens and accumulated rewards.
     #[account(mut)]
-    pub stake_order_vault: Box<Account<'info, TokenAccount>>,
+    pub stake_order_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The player's token account, where the unstaked tokens and rewards will be transferred.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The global stake pool account, tracking total staked amounts and rewards distribution.
     /// Verified by `seeds` for correct program-derived address derivation.
@@ -66,11 +82,17 @@ pub struct Unstake<'info> {
 
     /// The stake pool's token vault, holding the rewards pool tokens.
     #[account(mut)]
-    pub stake_pool_token_vault: Box<Account<'info, TokenAccount>>,
+    pub stake_pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     /// The SPL token program, required for token transfer operations.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// The `unstake` function completes an existing stake order and returns the staked principal plus accrued rewards to the player.
@@ -88,12 +110,14 @@ pub fn unstake(ctx: Context<Unstake>, order_number: u16) -> Result<()> {
 
     // Extract references to the relevant accounts
     let Unstake {
+        token_mint,
         game,
         player,
         player_data,
         stake_pool,
         token_account,
         stake_order,
+        stake_order_index,
         stake_order_vault,
         token_program,
         stake_pool_token_vault,
@@ -113,20 +137,18 @@ pub fn unstake(ctx: Context<Unstake>, order_number: u16) -> Result<()> {
         ErrorCode::StakeOrderCannotUnstake
     );
 
-    // Calculate the amount to transfer: principal + rewards
+    // Calculate the amount to transfer: principal + interest not claimed yet
     let stake_amount = stake_order.stake_amount;
-    let token_rewards = stake_order.token_rewards;
+    let token_rewards =
+        stake_order.claim_interest(timestamp, stake_pool.distributable_token_rewards)?;
 
     // Mark the order as completed and update the stake pool state.
-    stake_order.complete()?;
+    stake_order.complete_with_unpaid_interest(timestamp)?;
+    player_data.remove_stake_boost(stake_order, timestamp)?;
     stake_pool.complete_order(stake_amount)?;
+    StakeOrderIndex::update_if_exists(stake_order_index, |index| index.remove_order(order_number))?;
 
-    stake_pool.token_rewards_pool_balance = stake_pool
-        .token_rewards_pool_balance
-        .safe_sub(token_rewards)?;
-    stake_pool.distributed_token_rewards = stake_pool
-        .distributed_token_rewards
-        .safe_add(token_rewards)?;
+    stake_pool.pay_interest(token_rewards)?;
 
     game.distributed_stake_rewards = game.distributed_stake_rewards.safe_add(token_rewards)?;
 
@@ -136,6 +158,7 @@ pub fn unstake(ctx: Context<Unstake>, order_number: u16) -> Result<()> {
         stake_order,
         stake_order_vault,
         token_account,
+        token_mint,
         token_program,
         stake_amount,
         &[
@@ -147,19 +170,22 @@ pub fn unstake(ctx: Context<Unstake>, order_number: u16) -> Result<()> {
     )?;
 
     // Move reward tokens from the order vault to the pool vault, finalizing the staking process
-    transfer_from_token_vault_to_token_account(
+    transfer_from_vault(
         stake_pool,
+        ctx.bumps.stake_pool,
         stake_pool_token_vault,
         token_account,
+        token_mint,
         token_program,
         token_rewards,
-        &[STAKE_POOL_SEED, &[ctx.bumps.stake_pool]],
     )?;
 
     game.increment_event_nonce()?;
 
     // Emit an event logging the unstake action
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::Unstake as u16,
         event_type: EventType::Unstake,
         event_nonce: game.event_nonce,
         data: EventData::Unstake {
@@ -173,7 +199,8 @@ pub fn unstake(ctx: Context<Unstake>, order_number: u16) -> Result<()> {
         initiator_type: InitiatorType::STAKE,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
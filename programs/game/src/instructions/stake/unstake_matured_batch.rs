@@ -0,0 +1,317 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_INDEX_SEED,
+    STAKE_ORDER_SEED, STAKE_POOL_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{
+    EventData, EventType, InitiatorType, SoftFailureReason, TransferEvent, EVENT_SCHEMA_VERSION,
+};
+use crate::state::*;
+use crate::utils::{
+    to_timestamp_u64, transfer_from_token_vault_to_token_account, transfer_from_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// Number of remaining accounts supplied for each stake order:
+/// `[player_data, stake_order, stake_order_vault, token_account, stake_order_index]`,
+/// the player data being writable to remove the order from the owner's stake boost.
+const ACCOUNTS_PER_ORDER: usize = 5;
+
+/// The `UnstakeMaturedBatch` instruction lets anyone complete matured stake orders on behalf of their owners.
+/// Players frequently forget to come back once their lock period ends, leaving principal and rewards idle;
+/// this crank pays them out to the owners' token accounts without requiring the owners to sign,
+/// and tips the caller the game config's `crank_tip` per completed order from the maintenance pool.
+/// Orders whose accrued interest exceeds the pool's distributable rewards are left to their owners.
+///
+/// The orders are passed through `remaining_accounts`, `ACCOUNTS_PER_ORDER` accounts per order.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnstakeMaturedBatch<'info> {
+    /// The caller running the crank. Must sign the transaction.
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// The global game account, holding the maintenance pool.
+    #[account(mut, seeds = [GAME_SEED], bump, has_one = game_vault)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The game config holding the crank tip.
+    #[account(seeds = [GAME_CONFIG_SEED], bump)]
+    pub game_config: Box<Account<'info, GameConfig>>,
+
+    /// The main game vault token account holding the maintenance pool.
+    #[account(mut)]
+    pub game_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The caller's token account, receiving the crank tip.
+    #[account(mut, token::mint = token_mint)]
+    pub tip_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global stake pool account, tracking total staked amounts and rewards distribution.
+    #[account(mut,
+        seeds = [STAKE_POOL_SEED],
+        bump,
+        has_one = stake_pool_token_vault,
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The stake pool's token vault, holding the rewards pool tokens.
+    #[account(mut)]
+    pub stake_pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The SPL token program, required for token transfer operations.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Completes every matured stake order supplied in `remaining_accounts`.
+///
+/// Steps:
+/// 1. Validate that the remaining accounts come in groups of `ACCOUNTS_PER_ORDER`.
+/// 2. For each group, verify the player data, stake order and index PDAs, and that the vault and token account belong to the order and its owner.
+/// 3. Skip orders that are already completed, have not yet reached `unstaked_timestamp`, or have accrued more
+///    interest than the pool can still distribute.
+/// 4. Complete the order, return the principal from the order vault and the rewards from the pool vault to the owner.
+/// 5. Remove the order from the owner's stake order index and emit an `Unstake` event.
+/// 6. Tip the caller per completed order from the maintenance pool and emit a `CrankTip` event,
+///    or emit a `SoftFailure` event if no order was completed.
+pub fn unstake_matured_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UnstakeMaturedBatch<'info>>,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let program_id = ctx.program_id;
+    let remaining_accounts = ctx.remaining_accounts;
+
+    // Extract references to the relevant accounts
+    let UnstakeMaturedBatch {
+        token_mint,
+        game,
+        game_config,
+        game_vault,
+        tip_account,
+        caller,
+        stake_pool,
+        stake_pool_token_vault,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require!(
+        !remaining_accounts.is_empty()
+            && remaining_accounts.len() % ACCOUNTS_PER_ORDER == 0,
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    let mut completed_orders: u32 = 0;
+
+    for accounts in remaining_accounts.chunks(ACCOUNTS_PER_ORDER) {
+        // The player data ties the order to its owner and their token account
+        let mut player_data: Account<'info, PlayerData> = Account::try_from(&accounts[0])?;
+        let player = player_data.player;
+        let (player_data_address, _) =
+            Pubkey::find_program_address(&[PLAYER_DATA_SEED, player.as_ref()], program_id);
+        require_keys_eq!(
+            player_data.key(),
+            player_data_address,
+            ErrorCode::StakeOrderOwnerMismatch
+        );
+
+        // The stake order must be derived from the owner and its own order number
+        let mut stake_order: Account<'info, StakeOrder> = Account::try_from(&accounts[1])?;
+        let order_number = stake_order.stake_number;
+        let stake_order_address = Pubkey::create_program_address(
+            &[
+                STAKE_ORDER_SEED,
+                player.as_ref(),
+                order_number.to_le_bytes().as_ref(),
+                &[stake_order.bump],
+            ],
+            program_id,
+        )
+        .map_err(|_| ErrorCode::StakeOrderOwnerMismatch)?;
+        require_keys_eq!(
+            stake_order.key(),
+            stake_order_address,
+            ErrorCode::StakeOrderOwnerMismatch
+        );
+
+        // Leave orders that are already settled, still locked, voucher-denominated or
+        // early-unstaked (claimed through `claim_early_unstake`) untouched
+        if stake_order.is_completed
+            || stake_order.is_voucher_stake
+            || stake_order.is_early_unstaked
+            || !stake_order.can_unstake(timestamp)
+        {
+            continue;
+        }
+
+        // Leave orders the pool can not pay in full to their owners, who keep the unpaid interest
+        // claimable when unstaking themselves
+        if stake_order.accrued_interest(timestamp)? > stake_pool.distributable_token_rewards {
+            continue;
+        }
+
+        let stake_order_vault: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(&accounts[2])?;
+        require_keys_eq!(
+            stake_order_vault.key(),
+            stake_order.stake_order_vault,
+            ErrorCode::StakeOrderOwnerMismatch
+        );
+
+        let token_account: InterfaceAccount<'info, TokenAccount> =
+            InterfaceAccount::try_from(&accounts[3])?;
+        require_keys_eq!(
+            token_account.key(),
+            player_data.token_account,
+            ErrorCode::StakeOrderOwnerMismatch
+        );
+
+        let (stake_order_index_address, _) =
+            Pubkey::find_program_address(&[STAKE_ORDER_INDEX_SEED, player.as_ref()], program_id);
+        require_keys_eq!(
+            accounts[4].key(),
+            stake_order_index_address,
+            ErrorCode::StakeOrderOwnerMismatch
+        );
+
+        let stake_amount = stake_order.stake_amount;
+        let token_rewards =
+            stake_order.claim_interest(timestamp, stake_pool.distributable_token_rewards)?;
+
+        // Mark the order as completed and update the stake pool state.
+        stake_order.complete()?;
+        stake_pool.complete_order(stake_amount)?;
+        player_data.remove_stake_boost(&mut stake_order, timestamp)?;
+
+        stake_pool.pay_interest(token_rewards)?;
+
+        game.distributed_stake_rewards = game.distributed_stake_rewards.safe_add(token_rewards)?;
+
+        // Return the principal from the order vault to the owner's token account
+        transfer_from_token_vault_to_token_account(
+            &stake_order,
+            &stake_order_vault,
+            &token_account,
+            token_mint,
+            token_program,
+            stake_amount,
+            &[
+                STAKE_ORDER_SEED,
+                player.as_ref(),
+                order_number.to_le_bytes().as_ref(),
+                &[stake_order.bump],
+            ],
+        )?;
+
+        // Pay the rewards from the pool vault to the owner's token account
+        transfer_from_vault(
+            stake_pool,
+            ctx.bumps.stake_pool,
+            stake_pool_token_vault,
+            &token_account,
+            token_mint,
+            token_program,
+            token_rewards,
+        )?;
+
+        stake_order.exit(program_id)?;
+        player_data.exit(program_id)?;
+
+        // Orders created before the index existed have no index account to update
+        if !accounts[4].data_is_empty() {
+            let mut stake_order_index: Account<'info, StakeOrderIndex> =
+                Account::try_from(&accounts[4])?;
+            stake_order_index.remove_order(order_number)?;
+            stake_order_index.exit(program_id)?;
+        }
+
+        completed_orders = completed_orders.safe_add(1)?;
+
+        game.increment_event_nonce()?;
+
+        // Emit an event logging the unstake action on behalf of the owner
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::Unstake as u16,
+            event_type: EventType::Unstake,
+            event_nonce: game.event_nonce,
+            data: EventData::Unstake {
+                player,
+                stake_order: stake_order.key(),
+                stake_amount,
+                token_rewards: stake_order.token_rewards,
+                voucher_rewards: stake_order.voucher_rewards,
+                stake_pool: stake_pool.key(),
+            },
+            initiator_type: InitiatorType::SYSTEM,
+            initiator: caller.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    msg!("Completed matured stake orders: {}", completed_orders);
+
+    // Report a call completing no order as a soft failure
+    if completed_orders == 0 {
+        let event = TransferEvent::soft_failure(
+            game,
+            SoftFailureReason::NoMaturedStakeOrders,
+            caller.key(),
+            timestamp,
+        )?;
+        record_event!(ctx, event);
+        return Ok(());
+    }
+
+    // Tip the caller for each completed order, paying less once the maintenance pool runs low
+    let tip = game.take_crank_tip(game_config.crank_tip, completed_orders)?;
+    if tip > 0 {
+        transfer_from_vault(
+            game,
+            ctx.bumps.game,
+            game_vault,
+            tip_account,
+            token_mint,
+            token_program,
+            tip,
+        )?;
+
+        game.increment_event_nonce()?;
+
+        // Emit an event logging the tip paid for the crank
+        let event = TransferEvent {
+            event_schema_version: EVENT_SCHEMA_VERSION,
+            event_tag: EventType::CrankTip as u16,
+            event_type: EventType::CrankTip,
+            event_nonce: game.event_nonce,
+            data: EventData::CrankTip {
+                crank: MaintenanceCrank::MaturedUnstake,
+                caller: caller.key(),
+                tip_account: tip_account.key(),
+                tasks: completed_orders,
+                tip,
+                maintenance_pool_balance: game.maintenance_pool_balance,
+            },
+            initiator_type: InitiatorType::SYSTEM,
+            initiator: caller.key(),
+            timestamp,
+        };
+        record_event!(ctx, event);
+    }
+
+    Ok(())
+}
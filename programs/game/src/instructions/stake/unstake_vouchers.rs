@@ -0,0 +1,231 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, STAKE_ORDER_INDEX_SEED, STAKE_ORDER_SEED,
+    STAKE_POOL_SEED, VOUCHER_MINT_SEED, VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    refreeze_voucher_account, thaw_voucher_account_if_frozen, to_timestamp_u64, transfer_from_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `UnstakeVouchers` instruction returns the escrowed vouchers of a voucher stake order to the player.
+/// A matured order also pays its token rewards. While the pool is winding down the vouchers can be
+/// withdrawn before maturity, in which case the token rewards are forfeited back to the pool.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(order_number: u16)]
+pub struct UnstakeVouchers<'info> {
+    /// The player initiating the unstake operation. Must be the signer of the transaction.
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The global game account.
+    #[account(mut,
+        seeds = [GAME_SEED], bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The player's data account, linking the player to their token and voucher accounts.
+    #[account(
+        mut,
+        seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
+        bump,
+        has_one = token_account,
+        has_one = voucher_account,
+    )]
+    pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The voucher stake order to be unstaked. The order must not be completed yet.
+    #[account(mut,
+        seeds = [
+            STAKE_ORDER_SEED,
+            player.key().as_ref(),
+            order_number.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = !stake_order.is_completed @ ErrorCode::StakeOrderAlreadyCompleted,
+        constraint = stake_order.is_voucher_stake @ ErrorCode::StakeOrderAssetMismatch,
+    )]
+    pub stake_order: Box<Account<'info, StakeOrder>>,
+
+    /// The player's stake order index, from which the completed order is removed if it exists.
+    /// Players whose orders predate the index have none, and are not charged to create it here.
+    /// CHECK: Derived from the player; deserialized by the handler unless empty.
+    #[account(mut, seeds = [STAKE_ORDER_INDEX_SEED, player.key().as_ref()], bump)]
+    pub stake_order_index: UncheckedAccount<'info>,
+
+    /// The player's token account, receiving the token rewards.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The player's voucher account, receiving the escrowed vouchers.
+    #[account(mut)]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global voucher account, acting as the freeze authority of the voucher mint.
+    #[account(seeds = [VOUCHER_SEED], bump, has_one = voucher_mint)]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher token mint account.
+    #[account(seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The global stake pool account.
+    #[account(mut,
+        seeds = [STAKE_POOL_SEED],
+        bump,
+        has_one = stake_pool_token_vault,
+        has_one = stake_pool_voucher_vault,
+    )]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The stake pool's token vault, holding the rewards pool tokens.
+    #[account(mut)]
+    pub stake_pool_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The stake pool's voucher vault, escrowing the staked vouchers.
+    #[account(mut)]
+    pub stake_pool_voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The SPL token program, required for token transfer operations.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Completes a voucher stake order:
+///
+/// Steps:
+/// 1. Require the order to be matured, or the pool to be winding down.
+/// 2. Claim the token rewards if matured, keeping any interest the pool can not pay claimable, otherwise
+///    forfeit them back to the pool.
+/// 3. Mark the order as completed and release its escrowed vouchers from the pool accounting.
+/// 4. Return the vouchers from the `stake_pool_voucher_vault` to the player's voucher account.
+/// 5. Emit an `UnstakeVouchers` event.
+pub fn unstake_vouchers(ctx: Context<UnstakeVouchers>, order_number: u16) -> Result<()> {
+    // Obtain the current UNIX timestamp
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references to the relevant accounts
+    let UnstakeVouchers {
+        token_mint,
+        voucher,
+        voucher_mint,
+        game,
+        player,
+        player_data,
+        stake_pool,
+        token_account,
+        voucher_account,
+        stake_order,
+        stake_order_index,
+        stake_pool_token_vault,
+        stake_pool_voucher_vault,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    let is_matured = stake_order.can_unstake(timestamp);
+    require!(
+        is_matured || stake_pool.is_winding_down,
+        ErrorCode::StakeOrderCannotUnstake
+    );
+
+    let stake_amount = stake_order.stake_amount;
+
+    // Interest not claimed yet is forfeited when withdrawing before maturity, and kept claimable
+    // if the pool can not pay it in full at maturity
+    let token_rewards = if is_matured {
+        let token_rewards =
+            stake_order.claim_interest(timestamp, stake_pool.distributable_token_rewards)?;
+        stake_order.complete_with_unpaid_interest(timestamp)?;
+        token_rewards
+    } else {
+        stake_order.complete()?;
+        0
+    };
+
+    // Update the stake pool state now that the order is completed.
+    player_data.remove_stake_boost(stake_order, timestamp)?;
+    stake_pool.complete_voucher_order(stake_amount)?;
+
+    if token_rewards > 0 {
+        stake_pool.pay_interest(token_rewards)?;
+
+        game.distributed_stake_rewards = game.distributed_stake_rewards.safe_add(token_rewards)?;
+
+        transfer_from_vault(
+            stake_pool,
+            ctx.bumps.stake_pool,
+            stake_pool_token_vault,
+            token_account,
+            token_mint,
+            token_program,
+            token_rewards,
+        )?;
+    }
+
+    // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    // Return the escrowed vouchers to the player
+    transfer_from_vault(
+        stake_pool,
+        ctx.bumps.stake_pool,
+        stake_pool_voucher_vault,
+        voucher_account,
+        voucher_mint,
+        token_program,
+        stake_amount,
+    )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    StakeOrderIndex::update_if_exists(stake_order_index, |index| index.remove_order(order_number))?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the voucher unstake action
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::UnstakeVouchers as u16,
+        event_type: EventType::UnstakeVouchers,
+        event_nonce: game.event_nonce,
+        data: EventData::UnstakeVouchers {
+            player: player.key(),
+            stake_order: stake_order.key(),
+            stake_amount,
+            token_rewards,
+            stake_pool: stake_pool.key(),
+        },
+        initiator_type: InitiatorType::STAKE,
+        initiator: player.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, STAKE_POOL_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `UpdateStakeRates` instruction is a bot crank that re-derives the stake pool APR from its utilization.
+///
+/// Utilization is `staked_amount / (staked_amount + distributable_token_rewards)`. While it stays at or
+/// below `target_utilization` new orders earn `max_annual_rate`; above it the APR falls linearly and
+/// reaches `min_annual_rate` once the distributable rewards are exhausted. Orders already created keep their rate.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateStakeRates<'info> {
+    /// The bot authority triggering the crank. Must sign the transaction.
+    #[account(mut)]
+    pub bot_authority: Signer<'info>,
+
+    /// The global game account, verifying the bot authority.
+    #[account(mut,
+        seeds = [GAME_SEED], bump,
+        has_one = bot_authority @ ErrorCode::AuthorityMismatch,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The global stake pool whose `annual_rate` is recomputed.
+    #[account(mut, seeds = [STAKE_POOL_SEED], bump)]
+    pub stake_pool: Box<Account<'info, StakePool>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Recomputes the stake pool APR:
+///
+/// 1. Derive the current utilization from the pool balances.
+/// 2. Apply the utilization curve to obtain the new `annual_rate`.
+/// 3. Emit an `UpdateStakeRates` event exposing the previous and new rate.
+pub fn update_stake_rates(ctx: Context<UpdateStakeRates>) -> Result<()> {
+    // Obtain the current UNIX timestamp for event logging.
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let UpdateStakeRates {
+        bot_authority,
+        game,
+        stake_pool,
+        ..
+    } = ctx.accounts;
+
+    let previous_annual_rate = stake_pool.annual_rate;
+    let utilization = stake_pool.apply_rate_curve()?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event exposing the rate change
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::UpdateStakeRates as u16,
+        event_type: EventType::UpdateStakeRates,
+        event_nonce: game.event_nonce,
+        data: EventData::UpdateStakeRates {
+            stake_pool: stake_pool.key(),
+            utilization,
+            previous_annual_rate,
+            annual_rate: stake_pool.annual_rate,
+        },
+        initiator_type: InitiatorType::STAKE,
+        initiator: bot_authority.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
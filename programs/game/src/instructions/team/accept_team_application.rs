@@ -1,6 +1,6 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{BAN_ENTRY_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
@@ -8,6 +8,7 @@ use solana_program::sysvar::clock::Clock;
 
 /// The `AcceptTeamApplication` instruction allows a team captain or manager to accept a player's application to join the team.
 /// Upon acceptance, the applicant is removed from the team's application list and added as a member.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(applicant: Pubkey)]
 pub struct AcceptTeamApplication<'info> {
@@ -40,6 +41,15 @@ pub struct AcceptTeamApplication<'info> {
         bump,
     )]
     pub applicant_data: Box<Account<'info, PlayerData>>,
+
+    /// The applicant's ban entry, which must not exist.
+    /// CHECK: Derived from the applicant; checked to be empty in the handler.
+    #[account(seeds = [BAN_ENTRY_SEED, applicant.as_ref()], bump)]
+    pub ban_entry: UncheckedAccount<'info>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Accepts the team application from the `applicant`, finalizing their addition to the team membership.
@@ -57,6 +67,9 @@ pub fn accept_team_application(
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
+    // Reject banned wallets
+    BanEntry::require_not_banned(&ctx.accounts.ban_entry)?;
+
     // Extract references to relevant accounts for clarity
     let AcceptTeamApplication {
         game,
@@ -82,7 +95,9 @@ pub fn accept_team_application(
     game.increment_event_nonce()?;
 
     // Emit an event to record the successful acceptance of the application
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::AcceptTeamApplication as u16,
         event_type: EventType::AcceptTeamApplication,
         event_nonce: game.event_nonce,
         data: EventData::AcceptTeamApplication {
@@ -92,7 +107,8 @@ pub fn accept_team_application(
         initiator_type: InitiatorType::TEAM,
         initiator: acceptor.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
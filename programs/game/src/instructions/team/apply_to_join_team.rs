@@ -1,6 +1,6 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{BAN_ENTRY_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
@@ -11,7 +11,9 @@ use solana_program::sysvar::clock::Clock;
 /// 1. Adding the team to the player's application list.
 /// 2. Adding the player's public key to the team's application list.
 ///
-/// To mitigate frequent switching and spamming of team applications, a cooldown mechanism (`can_apply_to_team_timestamp`) is enforced.
+/// To mitigate frequent switching and spamming of team applications, a cooldown mechanism (`can_apply_to_team_timestamp`) is enforced,
+/// along with a daily cap on applications and a cooldown before reapplying to a team that rejected the player.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ApplyToJoinTeam<'info> {
     /// The target team that the player wishes to join.
@@ -29,18 +31,36 @@ pub struct ApplyToJoinTeam<'info> {
 
     /// The player's data account, which stores personal state such as cooldown timestamps, current team, etc.
     /// Uses `PLAYER_DATA_SEED` and `bump` to ensure correct derivation.
+    /// Grown at the player's expense when the new application does not fit in it; never shrunk.
     #[account(mut,
         seeds = [PLAYER_DATA_SEED, player.key().as_ref()],
         bump,
+        realloc = PlayerData::space_for_team_applications(player_data.team_applications.len() + 1)
+            .max(player_data.to_account_info().data_len()),
+        realloc::payer = player,
+        realloc::zero = false,
     )]
     pub player_data: Box<Account<'info, PlayerData>>,
+
+    /// The player's ban entry, which must not exist.
+    /// CHECK: Derived from the player; checked to be empty in the handler.
+    #[account(seeds = [BAN_ENTRY_SEED, player.key().as_ref()], bump)]
+    pub ban_entry: UncheckedAccount<'info>,
+
+    /// The system program, required to fund the growth of `player_data`.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Attempts to apply to join a given team:
 ///
 /// Steps:
 /// 1. Verify that the player's cooldown period has passed, ensuring they are allowed to apply again.
-/// 2. Add the team to the player's team application list, recording the player's intent to join.
+/// 2. Add the team to the player's team application list, recording the player's intent to join. The application counts
+///    toward the game's `max_team_applications_per_day`, and is refused while the team's rejection of the player is on cooldown.
 /// 3. Add the player to the team's application list, waiting for captain or manager approval.
 /// 4. Emit a `ApplyToJoinTeam` event to record this action on-chain.
 pub fn apply_to_join_team(ctx: Context<ApplyToJoinTeam>) -> Result<()> {
@@ -48,6 +68,9 @@ pub fn apply_to_join_team(ctx: Context<ApplyToJoinTeam>) -> Result<()> {
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
+    // Reject banned wallets
+    BanEntry::require_not_banned(&ctx.accounts.ban_entry)?;
+
     // Extract references to accounts for ease of use
     let ApplyToJoinTeam {
         game,
@@ -63,8 +86,8 @@ pub fn apply_to_join_team(ctx: Context<ApplyToJoinTeam>) -> Result<()> {
         ErrorCode::TeamJoinCooldown
     );
 
-    // Add the team to the player's application list
-    player_data.apply_to_join_team(team.key())?;
+    // Add the team to the player's application list, within the daily cap and outside any reapplication cooldown
+    player_data.apply_to_join_team(team.key(), game.max_team_applications_per_day, timestamp)?;
 
     // Add the player to the team's application list
     team.apply_to_join_team(player.key())?;
@@ -72,17 +95,20 @@ pub fn apply_to_join_team(ctx: Context<ApplyToJoinTeam>) -> Result<()> {
     game.increment_event_nonce()?;
 
     // Emit an event capturing the team application action
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::ApplyToJoinTeam as u16,
         event_type: EventType::ApplyToJoinTeam,
         event_nonce: game.event_nonce,
         data: EventData::ApplyToJoinTeam {
             team: team.key(),
-            player: player.key()
+            player: player.key(),
         },
         initiator_type: InitiatorType::TEAM,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
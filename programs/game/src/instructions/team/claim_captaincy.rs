@@ -0,0 +1,102 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `ClaimCaptaincy` instruction lets a member take over the captaincy of a team whose captain went inactive,
+/// their last activity being their last purchase or candy tap.
+///
+/// Once the captain has been inactive for the game's `captain_inactivity_seconds`, the longest-tenured manager
+/// may claim the captaincy. Once inactive for twice as long, or right away if the team has no managers,
+/// any member may claim it.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimCaptaincy<'info> {
+    /// The member claiming the captaincy.
+    pub claimant: Signer<'info>,
+
+    /// The claimant's player data account, ensuring the claimant is tied to this team.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, claimant.key().as_ref()],
+        bump,
+        has_one = team
+    )]
+    pub claimant_player_data: Box<Account<'info, PlayerData>>,
+
+    /// The team account whose captaincy is claimed.
+    /// Mutated to reflect the change in captain.
+    #[account(mut)]
+    pub team: Box<Account<'info, Team>>,
+
+    /// The current captain's player data account, recording their last activity.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, team.captain.as_ref()],
+        bump,
+    )]
+    pub captain_player_data: Box<Account<'info, PlayerData>>,
+
+    /// The global game account, holding the captain inactivity window.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Hands the captaincy of the team to the claimant:
+///
+/// Steps:
+/// 1. Measure how long the captain has been inactive, since their last purchase or candy tap.
+/// 2. Check the claimant may claim the captaincy after that inactivity, then make them captain.
+/// 3. Emit a `ClaimCaptaincy` event to record the succession on-chain.
+pub fn claim_captaincy(ctx: Context<ClaimCaptaincy>) -> Result<()> {
+    // Fetch the current UNIX timestamp for the inactivity check and event logging
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references for clarity
+    let ClaimCaptaincy {
+        claimant,
+        team,
+        captain_player_data,
+        game,
+        ..
+    } = ctx.accounts;
+
+    // Measure the captain's inactivity
+    let captain_inactive_seconds =
+        timestamp.saturating_sub(captain_player_data.last_activity_timestamp()?);
+
+    // Hand the captaincy to the claimant, if they are next in succession
+    let previous_captain = team.claim_captaincy(
+        claimant.key(),
+        captain_inactive_seconds,
+        game.captain_inactivity_seconds,
+    )?;
+    team.last_updated_timestamp = timestamp;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the succession
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::ClaimCaptaincy as u16,
+        event_type: EventType::ClaimCaptaincy,
+        event_nonce: game.event_nonce,
+        data: EventData::ClaimCaptaincy {
+            team: team.key(),
+            previous_captain,
+            new_captain: claimant.key(),
+            captain_inactive_seconds,
+        },
+        initiator_type: InitiatorType::TEAM,
+        initiator: claimant.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
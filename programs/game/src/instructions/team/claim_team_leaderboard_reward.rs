@@ -0,0 +1,154 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_zero_copy_vault};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `ClaimTeamLeaderboardReward` instruction lets the captain of a team holding a rank of a period's
+/// team leaderboard pull the rewards of that rank into the team vault once the period has ended.
+/// The rewards then become distributable to the members through `distribute_team_rewards`.
+///
+/// The ranks held by teams since merged into this one are claimed by passing, through `remaining_accounts`,
+/// the `TeamMerge` records leading from the rank holder to this team, the rank holder's record first.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimTeamLeaderboardReward<'info> {
+    /// The captain (signer) of the team claiming the rank rewards.
+    #[account(mut)]
+    pub captain: Signer<'info>,
+
+    /// The global game account, tracking the distributed team rewards.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The team claiming the rank rewards.
+    #[account(mut,
+        has_one = captain @ ErrorCode::AuthorityMismatch,
+        has_one = team_vault,
+    )]
+    pub team: Box<Account<'info, Team>>,
+
+    /// The ended period holding the final standings.
+    #[account(mut,
+        constraint = period.load()?.period_vault == period_vault.key(),
+    )]
+    pub period: AccountLoader<'info, Period>,
+
+    /// The period vault token account holding the leaderboard rewards.
+    #[account(mut)]
+    pub period_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The team vault token account, receiving the rank rewards.
+    #[account(mut)]
+    pub team_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint representing the in-game currency.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program enabling token transfers.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the team leaderboard claim:
+///
+/// Steps:
+/// 1. Require the period to have ended and the team, or a team merged into it, to hold the claimed rank,
+///    unclaimed so far.
+/// 2. Add the rewards to the team's `distributable_team_rewards`, or `distributable_team_voucher_rewards` if the period
+///    pays the team leaderboard in vouchers, and to the game's distributed team rewards.
+/// 3. Transfer the rank rewards from the `period_vault` to the team vault.
+/// 4. Emit a `ClaimLeaderboardReward` event.
+pub fn claim_team_leaderboard_reward(
+    ctx: Context<ClaimTeamLeaderboardReward>,
+    rank: u8,
+) -> Result<()> {
+    // Obtain the current UNIX timestamp
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let ClaimTeamLeaderboardReward {
+        captain,
+        game,
+        team,
+        period,
+        period_vault,
+        team_vault,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require_keys_neq!(
+        team.key(),
+        game.default_team,
+        ErrorCode::NotLeaderboardRankHolder
+    );
+
+    // Ranks held by teams since merged into this one are claimed through their merge records
+    let rank_holder =
+        TeamMerge::resolve_rank_holder(team.key(), ctx.remaining_accounts, ctx.program_id)?;
+
+    // Mark the rank as claimed, keeping a copy of the period data for the transfer below
+    let (rewards, period_data) = {
+        let mut period_data = period.load_mut()?;
+        let rewards = period_data.claim_rank_rewards(true, rank, rank_holder, timestamp)?;
+        (rewards, *period_data)
+    };
+
+    game.distributed_team_rewards = game.distributed_team_rewards.safe_add(rewards)?;
+    // Rewards paid in vouchers stay tokens in the team vault until they are distributed to members as vouchers
+    let payout_asset = period_data.payout_asset(true);
+    match payout_asset {
+        PayoutAsset::Token => {
+            team.distributable_team_rewards = team.distributable_team_rewards.safe_add(rewards)?;
+        }
+        PayoutAsset::Voucher => {
+            team.distributable_team_voucher_rewards =
+                team.distributable_team_voucher_rewards.safe_add(rewards)?;
+        }
+    }
+
+    transfer_from_zero_copy_vault(
+        period,
+        &period_data,
+        period_data.bump,
+        period_vault,
+        team_vault,
+        token_mint,
+        token_program,
+        rewards,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the claimed rank rewards
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::ClaimLeaderboardReward as u16,
+        event_type: EventType::ClaimLeaderboardReward,
+        event_nonce: game.event_nonce,
+        data: EventData::ClaimLeaderboardReward {
+            period: period.key(),
+            is_team_leaderboard: true,
+            recipient: team.key(),
+            rank,
+            rewards,
+            payout_asset,
+        },
+        initiator_type: InitiatorType::TEAM,
+        initiator: captain.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -1,14 +1,15 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED, TEAM_SEED, TOKEN_MINT};
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, TEAM_SEED};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
 /// The `CreateTeam` instruction enables a player to establish a new team.
 /// This involves creating a new `Team` account, initializing its vault, and updating the player's state to indicate that they have formed (and joined) this team as the captain.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateTeam<'info> {
     /// The player who initiates the team creation. Must sign the transaction.
@@ -42,27 +43,31 @@ pub struct CreateTeam<'info> {
         init,
         payer = player,
         associated_token::mint = token_mint,
-        associated_token::authority = team
+        associated_token::authority = team,
+        associated_token::token_program = token_program
     )]
-    pub team_vault: Box<Account<'info, TokenAccount>>,
+    pub team_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The global game state, maintaining references to token mint, and the `team_nonce` used to name new teams.
     #[account(mut, seeds = [GAME_SEED], bump)]
     pub game: Box<Account<'info, Game>>,
 
     /// The mint for the in-game token. The `game` has a `has_one` relationship ensuring consistency.
-    #[account(address = TOKEN_MINT)]
-    pub token_mint: Box<Account<'info, Mint>>,
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// The SPL token program, used for token-related operations.
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// The associated token program, used to create associated token accounts (like `team_vault`).
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The system program used for account creations and other Solana native operations.
     pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Creates a new team and updates the player's state to reflect their captaincy of this newly formed team:
@@ -107,17 +112,20 @@ pub fn create_team(ctx: Context<CreateTeam>) -> Result<()> {
     game.increment_event_nonce()?;
 
     // Emit an event logging the creation of a new team
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::CreateTeam as u16,
         event_type: EventType::CreateTeam,
         event_nonce: game.event_nonce,
         data: EventData::CreateTeam {
             team: team.key(),
-            player: player.key()
+            player: player.key(),
         },
         initiator_type: InitiatorType::TEAM,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
@@ -1,15 +1,18 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED, TEAM_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, TEAM_LEDGER_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
-use crate::utils::{to_timestamp_u64, transfer_from_token_vault_to_token_account};
+use crate::utils::{to_timestamp_u64, transfer_from_vault};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use solana_program::sysvar::clock::Clock;
 
 /// The `DistributeTeamRewards` instruction allows the team captain to distribute team-level rewards to a specific team member.
 /// This involves transferring a specified `team_rewards` amount from the team vault to the member's token account,
 /// and updating both the team and the member's player data to reflect the distribution.
+/// Once the team vault holds more than the game's `team_distribution_approval_threshold`, distributions must instead
+/// be proposed through `propose_team_distribution`, and approved by a manager or left unvetoed.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(member: Pubkey)]
 pub struct DistributeTeamRewards<'info> {
@@ -48,25 +51,45 @@ pub struct DistributeTeamRewards<'info> {
 
     /// The team vault token account holding tokens allocated to the team.
     #[account(mut)]
-    pub team_vault: Box<Account<'info, TokenAccount>>,
+    pub team_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// The member's token account where the team rewards will be deposited.
     #[account(mut)]
-    pub token_account: Box<Account<'info, TokenAccount>>,
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
     /// The token program, enabling token-related CPI calls (transfers, etc.).
-    #[account(address = token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The ledger recording the distributions out of the team vault, created on first use.
+    #[account(
+        init_if_needed,
+        payer = captain,
+        space = 8 + TeamLedger::INIT_SPACE,
+        seeds = [TEAM_LEDGER_SEED, team.key().as_ref()],
+        bump,
+    )]
+    pub team_ledger: Box<Account<'info, TeamLedger>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+
+    pub system_program: Program<'info, System>,
 }
 
 /// Distributes `team_rewards` amount of tokens from the team vault to a specific team member's token account.
 ///
 /// Steps:
-/// 1. Ensure the caller (`captain`) is authorized by verifying their captain role in the team.
+/// 1. Ensure the caller (`captain`) is authorized by verifying their captain role in the team,
+///    and the team vault does not require the distribution to be approved.
 /// 2. Update the team's internal records to deduct from the `distributable_team_rewards`.
 /// 3. Update the member's player data to record the newly collected team rewards.
 /// 4. Transfer the requested `team_rewards` from the `team_vault` to the member's `token_account`.
-/// 5. Emit a `DistributeTeamRewards` event to log the transaction on-chain.
+/// 5. Record the distribution on the `team_ledger`.
+/// 6. Emit a `DistributeTeamRewards` event to log the transaction on-chain.
 pub fn distribute_team_rewards(
     ctx: Context<DistributeTeamRewards>,
     member: Pubkey,
@@ -78,6 +101,7 @@ pub fn distribute_team_rewards(
 
     // Extract references for clarity
     let DistributeTeamRewards {
+        token_mint,
         game,
         captain,
         member_player_data,
@@ -85,9 +109,16 @@ pub fn distribute_team_rewards(
         team_vault,
         token_account,
         token_program,
+        team_ledger,
         ..
     } = ctx.accounts;
 
+    // Large team vaults are only distributed from through approved proposals
+    require!(
+        !game.requires_team_distribution_approval(team_vault.amount),
+        ErrorCode::TeamDistributionRequiresApproval
+    );
+
     // Update the team's reward pool to reflect the distribution
     team.distribute_team_rewards(team_rewards)?;
 
@@ -95,34 +126,45 @@ pub fn distribute_team_rewards(
     member_player_data.collect_team_rewards(team_rewards)?;
 
     // Transfer the specified `team_rewards` tokens from the team vault to the member's token account
-    transfer_from_token_vault_to_token_account(
+    transfer_from_vault(
         team,
+        team.bump,
         &team_vault,
         &token_account,
+        token_mint,
         &token_program,
         team_rewards,
-        &[
-            TEAM_SEED,
-            team.team_number.to_le_bytes().as_ref(),
-            &[team.bump],
-        ],
     )?;
 
+    // Record the distribution on the team ledger
+    team_ledger.initialize_if_needed(team.key(), ctx.bumps.team_ledger);
+    team_ledger.record(TeamLedgerEntry {
+        captain: captain.key(),
+        member,
+        amount: team_rewards,
+        payout_asset: PayoutAsset::Token,
+        timestamp,
+    })?;
+
     game.increment_event_nonce()?;
 
     // Emit an event logging the team rewards distribution
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::DistributeTeamRewards as u16,
         event_type: EventType::DistributeTeamRewards,
         event_nonce: game.event_nonce,
         data: EventData::DistributeTeamRewards {
             team: team.key(),
             member,
-            team_rewards
+            team_rewards,
+            payout_asset: PayoutAsset::Token,
         },
         initiator_type: InitiatorType::TEAM,
         initiator: captain.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
@@ -0,0 +1,217 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, TEAM_LEDGER_SEED, VOUCHER_MINT_SEED,
+    VOUCHER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{
+    refreeze_voucher_account, thaw_voucher_account_if_frozen, to_timestamp_u64, transfer_from_vault,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `DistributeTeamVoucherRewards` instruction allows the team captain to distribute the team rewards of periods
+/// paying in vouchers to a specific team member. The rewards move from the team vault to the voucher vault,
+/// backing the vouchers minted to the member's voucher account.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct DistributeTeamVoucherRewards<'info> {
+    /// The team account holding references to team resources, including the `team_vault` and the `captain`.
+    #[account(mut,
+        has_one = captain @ ErrorCode::AuthorityMismatch,
+        has_one = team_vault
+    )]
+    pub team: Box<Account<'info, Team>>,
+
+    /// The global game account.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The captain (signer) of the team who is authorizing the reward distribution.
+    #[account(mut)]
+    pub captain: Signer<'info>,
+
+    /// The captain's player data account, ensuring that the captain belongs to this team.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, captain.key().as_ref()],
+        bump,
+        has_one = team
+    )]
+    pub captain_data: Box<Account<'info, PlayerData>>,
+
+    /// The member's player data account, who will receive the distributed team rewards as vouchers.
+    #[account(mut,
+        seeds = [PLAYER_DATA_SEED, member.as_ref()],
+        bump,
+        has_one = voucher_account,
+    )]
+    pub member_player_data: Box<Account<'info, PlayerData>>,
+
+    /// The team vault token account holding tokens allocated to the team.
+    #[account(mut)]
+    pub team_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global `Voucher` account, managing voucher mint authority.
+    #[account(
+        mut,
+        seeds = [VOUCHER_SEED],
+        bump,
+        has_one = voucher_vault,
+    )]
+    pub voucher: Box<Account<'info, Voucher>>,
+
+    /// The voucher mint account used to mint vouchers to the member.
+    #[account(mut, seeds = [VOUCHER_MINT_SEED], bump)]
+    pub voucher_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The voucher vault token account holding assets that back voucher issuance.
+    #[account(mut)]
+    pub voucher_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The member's voucher account where the vouchers will be minted.
+    #[account(mut)]
+    pub voucher_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The token program, enabling token-related CPI calls (transfers, minting, etc.).
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The ledger recording the distributions out of the team vault, created on first use.
+    #[account(
+        init_if_needed,
+        payer = captain,
+        space = 8 + TeamLedger::INIT_SPACE,
+        seeds = [TEAM_LEDGER_SEED, team.key().as_ref()],
+        bump,
+    )]
+    pub team_ledger: Box<Account<'info, TeamLedger>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Distributes `team_rewards` of the team's voucher rewards to a specific team member as vouchers.
+///
+/// Steps:
+/// 1. Update the team's internal records to deduct from the `distributable_team_voucher_rewards`.
+/// 2. Update the member's player data to record the newly collected team rewards.
+/// 3. Transfer the requested `team_rewards` from the `team_vault` to the `voucher_vault`.
+/// 4. Mint the corresponding vouchers to the member's `voucher_account`.
+/// 5. Record the distribution on the `team_ledger`.
+/// 6. Emit a `DistributeTeamRewards` event to log the transaction on-chain.
+pub fn distribute_team_voucher_rewards(
+    ctx: Context<DistributeTeamVoucherRewards>,
+    member: Pubkey,
+    team_rewards: u64,
+) -> Result<()> {
+    // Fetch the current UNIX timestamp to record the operation time
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references for clarity
+    let DistributeTeamVoucherRewards {
+        token_mint,
+        game,
+        captain,
+        member_player_data,
+        team,
+        team_vault,
+        voucher,
+        voucher_mint,
+        voucher_vault,
+        voucher_account,
+        token_program,
+        team_ledger,
+        ..
+    } = ctx.accounts;
+
+    // Update the team's reward pool to reflect the distribution
+    team.distribute_team_voucher_rewards(team_rewards)?;
+
+    // Add the distributed rewards to the member's collected team rewards
+    member_player_data.collect_team_rewards(team_rewards)?;
+
+    voucher.mint(team_rewards)?;
+
+    // Transfer the underlying tokens from the team vault to the voucher vault, backing the newly minted vouchers
+    transfer_from_vault(
+        team,
+        team.bump,
+        team_vault,
+        voucher_vault,
+        token_mint,
+        token_program,
+        team_rewards,
+    )?;
+
+    // Thaw the voucher account while vouchers move through it if it is soulbound-frozen
+    let was_frozen = thaw_voucher_account_if_frozen(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    // Mint vouchers into the member's voucher account
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: voucher_mint.to_account_info(),
+                to: voucher_account.to_account_info(),
+                authority: voucher.to_account_info(),
+            },
+            &[&[VOUCHER_SEED, &[ctx.bumps.voucher]]],
+        ),
+        team_rewards,
+    )?;
+    refreeze_voucher_account(
+        voucher,
+        voucher_mint,
+        voucher_account,
+        token_program,
+        was_frozen,
+        &[VOUCHER_SEED, &[ctx.bumps.voucher]],
+    )?;
+
+    // Record the distribution on the team ledger
+    team_ledger.initialize_if_needed(team.key(), ctx.bumps.team_ledger);
+    team_ledger.record(TeamLedgerEntry {
+        captain: captain.key(),
+        member,
+        amount: team_rewards,
+        payout_asset: PayoutAsset::Voucher,
+        timestamp,
+    })?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the team rewards distribution
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::DistributeTeamRewards as u16,
+        event_type: EventType::DistributeTeamRewards,
+        event_nonce: game.event_nonce,
+        data: EventData::DistributeTeamRewards {
+            team: team.key(),
+            member,
+            team_rewards,
+            payout_asset: PayoutAsset::Voucher,
+        },
+        initiator_type: InitiatorType::TEAM,
+        initiator: captain.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
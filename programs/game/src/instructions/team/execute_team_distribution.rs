@@ -0,0 +1,177 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PENDING_DISTRIBUTION_SEED, PLAYER_DATA_SEED,
+    TEAM_LEDGER_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_vault};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `ExecuteTeamDistribution` instruction lets the captain of a team execute a team reward distribution proposed
+/// through `propose_team_distribution`: right away when the game authority co-signs it as `approver`, or otherwise
+/// once its veto period has ended. Team managers are appointed by the captain alone, so they may only veto. The pending distribution is closed, its rent returned to the captain.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteTeamDistribution<'info> {
+    /// The team account whose rewards are distributed.
+    #[account(mut,
+        has_one = captain @ ErrorCode::AuthorityMismatch,
+        has_one = team_vault
+    )]
+    pub team: Box<Account<'info, Team>>,
+
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The captain (signer) of the team executing the distribution.
+    #[account(mut)]
+    pub captain: Signer<'info>,
+
+    /// The game authority co-signing the distribution, which then executes without waiting out the veto period.
+    pub approver: Option<Signer<'info>>,
+
+    /// The pending distribution executed, closed on exit.
+    #[account(mut,
+        close = captain,
+        seeds = [PENDING_DISTRIBUTION_SEED, team.key().as_ref(), pending_distribution.member.as_ref()],
+        bump = pending_distribution.bump,
+        has_one = team,
+    )]
+    pub pending_distribution: Box<Account<'info, PendingDistribution>>,
+
+    /// The member's player data account, who will receive the distributed team rewards.
+    #[account(mut,
+        seeds = [PLAYER_DATA_SEED, pending_distribution.member.as_ref()],
+        bump,
+        has_one = token_account,
+    )]
+    pub member_player_data: Box<Account<'info, PlayerData>>,
+
+    /// The team vault token account holding tokens allocated to the team.
+    #[account(mut)]
+    pub team_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The member's token account where the team rewards will be deposited.
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The token program, enabling token-related CPI calls (transfers, etc.).
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The ledger recording the distributions out of the team vault, created on first use.
+    #[account(
+        init_if_needed,
+        payer = captain,
+        space = 8 + TeamLedger::INIT_SPACE,
+        seeds = [TEAM_LEDGER_SEED, team.key().as_ref()],
+        bump,
+    )]
+    pub team_ledger: Box<Account<'info, TeamLedger>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Executes a pending team reward distribution.
+///
+/// Steps:
+/// 1. Check the game authority approves the distribution, or its veto period has ended.
+/// 2. Deduct the rewards from the team's `distributable_team_rewards`, no longer counting the distribution as pending,
+///    and record them on the member's player data.
+/// 3. Transfer the rewards from the `team_vault` to the member's `token_account`.
+/// 4. Record the distribution on the `team_ledger`.
+/// 5. Emit a `DistributeTeamRewards` event to log the distribution on-chain.
+pub fn execute_team_distribution(ctx: Context<ExecuteTeamDistribution>) -> Result<()> {
+    // Fetch the current UNIX timestamp to check the veto period
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references for clarity
+    let ExecuteTeamDistribution {
+        token_mint,
+        game,
+        captain,
+        approver,
+        pending_distribution,
+        member_player_data,
+        team,
+        team_vault,
+        token_account,
+        token_program,
+        team_ledger,
+        ..
+    } = ctx.accounts;
+
+    // The game authority's co-signature lifts the veto period
+    match approver {
+        Some(approver) => {
+            require_keys_eq!(approver.key(), game.authority, ErrorCode::NotAuthorized)
+        }
+        None => require!(
+            pending_distribution.is_veto_period_ended(timestamp),
+            ErrorCode::TeamDistributionInVetoPeriod
+        ),
+    }
+
+    let member = pending_distribution.member;
+    let team_rewards = pending_distribution.team_rewards;
+
+    // Update the team's reward pool to reflect the distribution
+    team.distribute_team_rewards(team_rewards)?;
+    team.remove_pending_distribution()?;
+
+    // Add the distributed rewards to the member's collected team rewards
+    member_player_data.collect_team_rewards(team_rewards)?;
+
+    // Transfer the `team_rewards` tokens from the team vault to the member's token account
+    transfer_from_vault(
+        team,
+        team.bump,
+        &team_vault,
+        &token_account,
+        token_mint,
+        &token_program,
+        team_rewards,
+    )?;
+
+    // Record the distribution on the team ledger
+    team_ledger.initialize_if_needed(team.key(), ctx.bumps.team_ledger);
+    team_ledger.record(TeamLedgerEntry {
+        captain: captain.key(),
+        member,
+        amount: team_rewards,
+        payout_asset: PayoutAsset::Token,
+        timestamp,
+    })?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the team rewards distribution
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::DistributeTeamRewards as u16,
+        event_type: EventType::DistributeTeamRewards,
+        event_nonce: game.event_nonce,
+        data: EventData::DistributeTeamRewards {
+            team: team.key(),
+            member,
+            team_rewards,
+            payout_asset: PayoutAsset::Token,
+        },
+        initiator_type: InitiatorType::TEAM,
+        initiator: captain.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, TEAM_QUEST_COMPLETION_SEED, TEAM_QUEST_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_vault};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::sysvar::clock::Clock;
+
+/// The `FinalizeTeamQuest` instruction lets anyone pay a team the bonus of a period's `TeamQuest` once the period
+/// has ended, if the team's members purchased at least the quest's goal during it. The bonus moves from the quest
+/// vault to the team vault and becomes distributable to the members through `distribute_team_rewards`.
+/// The team's `TeamQuestCompletion` record, paid for by the caller, keeps the bonus from being paid twice.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeTeamQuest<'info> {
+    /// The caller running the crank, paying for the completion record.
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// The global game account, tracking the distributed team rewards.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The ended period the quest ran in.
+    pub period: AccountLoader<'info, Period>,
+
+    /// The quest being finalized for the team.
+    #[account(
+        mut,
+        seeds = [TEAM_QUEST_SEED, period.key().as_ref()],
+        bump = team_quest.bump,
+        has_one = quest_vault,
+    )]
+    pub team_quest: Box<Account<'info, TeamQuest>>,
+
+    /// The vault holding the quest's budget.
+    #[account(mut)]
+    pub quest_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The team that completed the quest.
+    #[account(mut, has_one = team_vault)]
+    pub team: Box<Account<'info, Team>>,
+
+    /// The team vault token account, receiving the bonus.
+    #[account(mut)]
+    pub team_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The record of the team's completion, created once.
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + TeamQuestCompletion::INIT_SPACE,
+        seeds = [TEAM_QUEST_COMPLETION_SEED, team_quest.key().as_ref(), team.key().as_ref()],
+        bump,
+    )]
+    pub team_quest_completion: Box<Account<'info, TeamQuestCompletion>>,
+
+    /// The token mint representing the in-game currency.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The SPL token program enabling token transfers.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The system program, required to create the completion record.
+    pub system_program: Program<'info, System>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Executes the team quest finalization:
+///
+/// Steps:
+/// 1. Require the period to have ended, and the team not to be the default team.
+/// 2. Require the team to have purchased the quest's goal during the period, and the quest's budget to cover the bonus.
+/// 3. Record the completion, and add the bonus to the team's `distributable_team_rewards` and to the game's distributed team rewards.
+/// 4. Transfer the bonus from the quest vault to the team vault.
+/// 5. Emit a `FinalizeTeamQuest` event.
+pub fn finalize_team_quest(ctx: Context<FinalizeTeamQuest>) -> Result<()> {
+    // Obtain the current UNIX timestamp
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+
+    let FinalizeTeamQuest {
+        caller,
+        game,
+        period,
+        team_quest,
+        quest_vault,
+        team,
+        team_vault,
+        team_quest_completion,
+        token_mint,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    require!(
+        period.load()?.is_ended(timestamp),
+        ErrorCode::PeriodNotEnded
+    );
+    require_keys_neq!(
+        team.key(),
+        game.default_team,
+        ErrorCode::TeamQuestNotCompleted
+    );
+
+    // Pay the bonus of a team that reached the goal, within the quest's budget
+    let purchased_ores = team.period_purchased_ores(period.key());
+    let bonus_rewards = team_quest.record_completion(purchased_ores)?;
+    team_quest_completion.initialize(
+        team_quest.key(),
+        team.key(),
+        purchased_ores,
+        bonus_rewards,
+        ctx.bumps.team_quest_completion,
+    );
+
+    team.distributable_team_rewards = team.distributable_team_rewards.safe_add(bonus_rewards)?;
+    game.distributed_team_rewards = game.distributed_team_rewards.safe_add(bonus_rewards)?;
+
+    transfer_from_vault(
+        team_quest,
+        team_quest.bump,
+        quest_vault,
+        team_vault,
+        token_mint,
+        token_program,
+        bonus_rewards,
+    )?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the team's bonus
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::FinalizeTeamQuest as u16,
+        event_type: EventType::FinalizeTeamQuest,
+        event_nonce: game.event_nonce,
+        data: EventData::FinalizeTeamQuest {
+            team_quest: team_quest.key(),
+            team: team.key(),
+            purchased_ores,
+            bonus_rewards,
+        },
+        initiator_type: InitiatorType::SYSTEM,
+        initiator: caller.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
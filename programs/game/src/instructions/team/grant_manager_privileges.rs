@@ -1,6 +1,6 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
@@ -8,6 +8,7 @@ use solana_program::sysvar::clock::Clock;
 
 /// The `GrantManagerPrivileges` instruction allows the team captain to promote a team member to a manager.
 /// Managers can have additional privileges such as accepting team applications, distributing rewards, or other administrative tasks defined by the program.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(member: Pubkey)]
 pub struct GrantManagerPrivileges<'info> {
@@ -38,6 +39,10 @@ pub struct GrantManagerPrivileges<'info> {
         bump,
     )]
     pub member_data: Box<Account<'info, PlayerData>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Grants manager privileges to a specific team member:
@@ -75,7 +80,9 @@ pub fn grant_manager_privileges(
     game.increment_event_nonce()?;
 
     // Emit an event logging the action
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::GrantManagerPrivileges as u16,
         event_type: EventType::GrantManagerPrivileges,
         event_nonce: game.event_nonce,
         data: EventData::GrantManagerPrivileges {
@@ -85,7 +92,8 @@ pub fn grant_manager_privileges(
         initiator_type: InitiatorType::TEAM,
         initiator: captain.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
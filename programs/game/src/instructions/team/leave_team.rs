@@ -1,6 +1,6 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
@@ -14,6 +14,7 @@ use solana_program::sysvar::clock::Clock;
 /// 2. Remove the player from the current team's member list.
 /// 3. Update the player's data to reflect that they have left the team and apply a cooldown period before they can join another team.
 /// 4. Emit a `LeaveTeam` event to record the action on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct LeaveTeam<'info> {
     /// The player leaving the team. Must be the transaction signer.
@@ -37,6 +38,10 @@ pub struct LeaveTeam<'info> {
     /// The global game account, providing default team references and other configuration.
     #[account(mut, seeds = [GAME_SEED], bump)]
     pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 pub fn leave_team(ctx: Context<LeaveTeam>) -> Result<()> {
@@ -70,7 +75,9 @@ pub fn leave_team(ctx: Context<LeaveTeam>) -> Result<()> {
 
     game.increment_event_nonce()?;
     // Emit an event logging the player's departure from the team
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::LeaveTeam as u16,
         event_type: EventType::LeaveTeam,
         event_nonce: game.event_nonce,
         data: EventData::LeaveTeam {
@@ -80,7 +87,8 @@ pub fn leave_team(ctx: Context<LeaveTeam>) -> Result<()> {
         initiator_type: InitiatorType::TEAM,
         initiator: player.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
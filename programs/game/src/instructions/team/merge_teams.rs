@@ -0,0 +1,230 @@
+use crate::constants::{
+    BAN_ENTRY_SEED, EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED, TEAM_MERGE_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::{to_timestamp_u64, transfer_from_vault, VaultAuthority};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+use solana_program::sysvar::clock::Clock;
+
+/// Number of remaining accounts supplied for each absorbed member: `[player_data, ban_entry]`,
+/// the player data being writable to move the member to the surviving team.
+const ACCOUNTS_PER_MEMBER: usize = 2;
+
+/// The `MergeTeams` instruction lets the captains of two teams, both signing, merge the absorbed team into the
+/// surviving one. The absorbed team's members join the surviving team, its vault balance and reward counters move
+/// to the surviving team, and the absorbed team and its vault are closed, their rent returned to its captain.
+/// A `TeamMerge` record, paid by the absorbed team's captain, lets the surviving team claim the ranks the absorbed
+/// team still holds on the leaderboards of ended periods. The absorbed team may have no pending distributions.
+///
+/// The player data of every member of the absorbed team, its captain included, is passed writable through
+/// `remaining_accounts` along with the member's ban entry address, `ACCOUNTS_PER_MEMBER` accounts per member,
+/// so that each member is moved to the surviving team. Banned members can not be merged, as they could not join it.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MergeTeams<'info> {
+    /// The captain of the surviving team, co-signing the merge.
+    pub surviving_captain: Signer<'info>,
+
+    /// The captain of the absorbed team, co-signing the merge and receiving the rent of the closed accounts.
+    #[account(mut)]
+    pub absorbed_captain: Signer<'info>,
+
+    /// The team the members are merged into.
+    #[account(mut,
+        constraint = surviving_team.captain == surviving_captain.key() @ ErrorCode::AuthorityMismatch,
+        constraint = surviving_team.team_vault == surviving_team_vault.key() @ ErrorCode::InvalidTeamMerge,
+        constraint = surviving_team.key() != game.default_team @ ErrorCode::InvalidTeamMerge,
+    )]
+    pub surviving_team: Box<Account<'info, Team>>,
+
+    /// The team merged into the surviving team, closed by the merge.
+    #[account(mut,
+        close = absorbed_captain,
+        constraint = absorbed_team.captain == absorbed_captain.key() @ ErrorCode::AuthorityMismatch,
+        constraint = absorbed_team.team_vault == absorbed_team_vault.key() @ ErrorCode::InvalidTeamMerge,
+        constraint = absorbed_team.key() != game.default_team @ ErrorCode::InvalidTeamMerge,
+        constraint = absorbed_team.key() != surviving_team.key() @ ErrorCode::InvalidTeamMerge,
+    )]
+    pub absorbed_team: Box<Account<'info, Team>>,
+
+    /// The vault of the surviving team, receiving the absorbed team's tokens.
+    #[account(mut)]
+    pub surviving_team_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault of the absorbed team, emptied and closed.
+    #[account(mut)]
+    pub absorbed_team_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The global game account, referencing the default team and the current period.
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The token mint for the game token.
+    #[account(address = game.token_mint)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token program, enabling the transfer and the closing of the absorbed team's vault.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The record of the merge, through which the surviving team claims the absorbed team's leaderboard ranks.
+    #[account(
+        init,
+        payer = absorbed_captain,
+        space = 8 + TeamMerge::INIT_SPACE,
+        seeds = [TEAM_MERGE_SEED, absorbed_team.key().as_ref()],
+        bump,
+    )]
+    pub team_merge: Box<Account<'info, TeamMerge>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+
+    /// The system program, required to create the merge record.
+    pub system_program: Program<'info, System>,
+}
+
+/// Merges the absorbed team into the surviving team:
+///
+/// Steps:
+/// 1. Move the absorbed team's members and reward counters into the surviving team, which must have room for them.
+///    The absorbed team may have no pending applications or distributions, and no ores purchased in the current period.
+/// 2. Point the player data of every absorbed member, all passed through `remaining_accounts`, at the surviving team,
+///    rejecting banned members.
+/// 3. Transfer the absorbed team's vault balance to the surviving team's vault, then close the absorbed vault.
+/// 4. Record the merge, so that the absorbed team's ranks in ended periods can be claimed by the surviving team.
+/// 5. Emit a `MergeTeams` event; the absorbed team account is closed on exit.
+pub fn merge_teams<'info>(ctx: Context<'_, '_, 'info, 'info, MergeTeams<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let program_id = ctx.program_id;
+    let remaining_accounts = ctx.remaining_accounts;
+
+    let MergeTeams {
+        surviving_captain,
+        absorbed_captain,
+        surviving_team,
+        absorbed_team,
+        surviving_team_vault,
+        absorbed_team_vault,
+        game,
+        token_mint,
+        token_program,
+        team_merge,
+        ..
+    } = ctx.accounts;
+
+    // Move the members and reward counters into the surviving team
+    let moved_members = surviving_team.absorb(absorbed_team, game.current_period)?;
+    surviving_team.last_updated_timestamp = timestamp;
+
+    // Every absorbed member's player data must be supplied, once, to be moved to the surviving team
+    require!(
+        remaining_accounts.len() == moved_members.len() * ACCOUNTS_PER_MEMBER,
+        ErrorCode::InvalidRemainingAccounts
+    );
+    let mut moved_players: Vec<Pubkey> = Vec::with_capacity(moved_members.len());
+    for accounts in remaining_accounts.chunks(ACCOUNTS_PER_MEMBER) {
+        let account = &accounts[0];
+        let ban_entry = &accounts[1];
+        require_keys_eq!(
+            *account.owner,
+            *program_id,
+            ErrorCode::InvalidRemainingAccounts
+        );
+        require!(account.is_writable, ErrorCode::InvalidRemainingAccounts);
+
+        let mut player_data = PlayerData::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        let (player_data_address, _) = Pubkey::find_program_address(
+            &[PLAYER_DATA_SEED, player_data.player.as_ref()],
+            program_id,
+        );
+        require_keys_eq!(
+            player_data_address,
+            account.key(),
+            ErrorCode::InvalidRemainingAccounts
+        );
+        require!(
+            player_data.team == absorbed_team.key()
+                && moved_members.contains(&player_data.player)
+                && !moved_players.contains(&player_data.player),
+            ErrorCode::InvalidRemainingAccounts
+        );
+
+        // Reject banned members, who could not join the surviving team either
+        let (ban_entry_address, _) = Pubkey::find_program_address(
+            &[BAN_ENTRY_SEED, player_data.player.as_ref()],
+            program_id,
+        );
+        require_keys_eq!(
+            ban_entry_address,
+            ban_entry.key(),
+            ErrorCode::InvalidRemainingAccounts
+        );
+        BanEntry::require_not_banned(ban_entry)?;
+
+        player_data.join_team(surviving_team.key())?;
+        player_data.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+        moved_players.push(player_data.player);
+    }
+
+    // Move the absorbed team's tokens to the surviving team's vault, then close the absorbed vault
+    let moved_tokens = absorbed_team_vault.amount;
+    if moved_tokens > 0 {
+        transfer_from_vault(
+            absorbed_team,
+            absorbed_team.bump,
+            absorbed_team_vault,
+            surviving_team_vault,
+            token_mint,
+            token_program,
+            moved_tokens,
+        )?;
+    }
+    absorbed_team.with_signer_seeds(absorbed_team.bump, |seeds| {
+        close_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            CloseAccount {
+                account: absorbed_team_vault.to_account_info(),
+                destination: absorbed_captain.to_account_info(),
+                authority: absorbed_team.to_account_info(),
+            },
+            &[seeds],
+        ))
+    })?;
+
+    // Keep the absorbed team's leaderboard ranks claimable by the surviving team
+    team_merge.set_inner(TeamMerge {
+        absorbed_team: absorbed_team.key(),
+        surviving_team: surviving_team.key(),
+        merged_timestamp: timestamp,
+        bump: ctx.bumps.team_merge,
+    });
+
+    game.increment_event_nonce()?;
+
+    // Emit an event recording the merge
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::MergeTeams as u16,
+        event_type: EventType::MergeTeams,
+        event_nonce: game.event_nonce,
+        data: EventData::MergeTeams {
+            surviving_team: surviving_team.key(),
+            absorbed_team: absorbed_team.key(),
+            moved_members: moved_members.len() as u16,
+            moved_tokens,
+        },
+        initiator_type: InitiatorType::TEAM,
+        initiator: surviving_captain.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
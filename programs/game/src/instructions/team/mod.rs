@@ -1,5 +1,6 @@
 pub mod accept_team_application;
 pub mod apply_to_join_team;
+pub mod claim_team_leaderboard_reward;
 pub mod create_team;
 pub mod distribute_team_rewards;
 pub mod grant_manager_privileges;
@@ -8,8 +9,16 @@ pub mod reject_team_application;
 pub mod remove_member_from_team;
 pub mod revoke_manager_privileges;
 pub mod transfer_team_captaincy;
+pub mod distribute_team_voucher_rewards;
+pub mod finalize_team_quest;
+pub mod merge_teams;
+pub mod claim_captaincy;
+pub mod propose_team_distribution;
+pub mod execute_team_distribution;
+pub mod veto_team_distribution;
 pub use accept_team_application::*;
 pub use apply_to_join_team::*;
+pub use claim_team_leaderboard_reward::*;
 pub use create_team::*;
 pub use distribute_team_rewards::*;
 pub use grant_manager_privileges::*;
@@ -18,3 +27,10 @@ pub use reject_team_application::*;
 pub use remove_member_from_team::*;
 pub use revoke_manager_privileges::*;
 pub use transfer_team_captaincy::*;
+pub use distribute_team_voucher_rewards::*;
+pub use finalize_team_quest::*;
+pub use merge_teams::*;
+pub use claim_captaincy::*;
+pub use propose_team_distribution::*;
+pub use execute_team_distribution::*;
+pub use veto_team_distribution::*;
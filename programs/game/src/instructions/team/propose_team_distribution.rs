@@ -0,0 +1,121 @@
+use crate::constants::{
+    EVENT_RING_BUFFER_SEED, GAME_SEED, PENDING_DISTRIBUTION_SEED, PLAYER_DATA_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `ProposeTeamDistribution` instruction lets the captain of a team propose distributing team rewards to a member,
+/// as required once the team vault holds more than the game's `team_distribution_approval_threshold`.
+/// The proposal is executed through `execute_team_distribution`, right away with the co-signature of the game authority,
+/// or once the game's `team_distribution_veto_seconds` have passed without one of the team's current managers vetoing it.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct ProposeTeamDistribution<'info> {
+    /// The team account whose rewards are proposed for distribution, counting its pending distributions.
+    #[account(mut, has_one = captain @ ErrorCode::AuthorityMismatch)]
+    pub team: Box<Account<'info, Team>>,
+
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The captain (signer) of the team proposing the distribution, paying the rent of the pending distribution.
+    #[account(mut)]
+    pub captain: Signer<'info>,
+
+    /// The captain's player data account, ensuring that the captain belongs to this team.
+    #[account(
+        seeds = [PLAYER_DATA_SEED, captain.key().as_ref()],
+        bump,
+        has_one = team
+    )]
+    pub captain_data: Box<Account<'info, PlayerData>>,
+
+    /// The pending distribution recording the proposal, one per member at a time.
+    #[account(
+        init,
+        payer = captain,
+        space = 8 + PendingDistribution::INIT_SPACE,
+        seeds = [PENDING_DISTRIBUTION_SEED, team.key().as_ref(), member.as_ref()],
+        bump,
+    )]
+    pub pending_distribution: Box<Account<'info, PendingDistribution>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Proposes distributing `team_rewards` of the team to `member`.
+///
+/// Steps:
+/// 1. Check the team has the `team_rewards` to distribute.
+/// 2. Record the proposal and the managers able to veto it, executable without approval once the veto period ends,
+///    and count it among the team's pending distributions.
+/// 3. Emit a `ProposeTeamDistribution` event to log the proposal on-chain.
+pub fn propose_team_distribution(
+    ctx: Context<ProposeTeamDistribution>,
+    member: Pubkey,
+    team_rewards: u64,
+) -> Result<()> {
+    // Fetch the current UNIX timestamp to start the veto period
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references for clarity
+    let ProposeTeamDistribution {
+        team,
+        game,
+        captain,
+        pending_distribution,
+        ..
+    } = ctx.accounts;
+
+    // The rewards are only deducted at execution, but must be available when proposed
+    require!(
+        team.distributable_team_rewards >= team_rewards,
+        ErrorCode::InsufficientTeamRewardBalance
+    );
+
+    pending_distribution.initialize(
+        PendingDistributionParams {
+            team: team.key(),
+            member,
+            team_rewards,
+            proposer: captain.key(),
+            managers: team.manager_list.clone(),
+        },
+        timestamp,
+        game.team_distribution_veto_seconds,
+        ctx.bumps.pending_distribution,
+    )?;
+    team.add_pending_distribution()?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the proposal
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::ProposeTeamDistribution as u16,
+        event_type: EventType::ProposeTeamDistribution,
+        event_nonce: game.event_nonce,
+        data: EventData::ProposeTeamDistribution {
+            team: team.key(),
+            member,
+            team_rewards,
+            executable_timestamp: pending_distribution.executable_timestamp,
+        },
+        initiator_type: InitiatorType::TEAM,
+        initiator: captain.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
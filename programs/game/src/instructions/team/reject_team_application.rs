@@ -1,13 +1,15 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
 use solana_program::sysvar::clock::Clock;
 
 /// The `RejectTeamApplication` instruction allows a team's captain or manager to decline a player's application to join the team.
 /// Once rejected, the player is removed from the team's application list, and the team is removed from the player's application list.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(applicant: Pubkey)]
 pub struct RejectTeamApplication<'info> {
@@ -38,6 +40,10 @@ pub struct RejectTeamApplication<'info> {
         bump,
     )]
     pub applicant_data: Box<Account<'info, PlayerData>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 /// Rejects a previously made team application:
@@ -45,7 +51,8 @@ pub struct RejectTeamApplication<'info> {
 /// Steps:
 /// 1. Verify that the `rejector` is either the team captain or a manager, ensuring the authority to reject applications.
 /// 2. Remove the applicant from the team's application list.
-/// 3. Remove the team from the applicant's application list.
+/// 3. Remove the team from the applicant's application list, barring the applicant from applying to the team again
+///    for the game's `team_reapplication_cooldown_seconds`.
 /// 4. Emit a `RejectTeamApplication` event to record the action on-chain.
 pub fn reject_team_application(
     ctx: Context<RejectTeamApplication>,
@@ -74,12 +81,18 @@ pub fn reject_team_application(
     team.reject_team_application(applicant)?;
 
     // Remove the team from the applicant's application list
-    applicant_data.reject_team_application(team.key())?;
+    // Bar the applicant from applying to the team again until the reapplication cooldown ends
+    applicant_data.reject_team_application(
+        team.key(),
+        timestamp.safe_add(game.team_reapplication_cooldown_seconds)?,
+    )?;
 
     game.increment_event_nonce()?;
 
     // Emit an event recording the rejection of the team application
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::RejectTeamApplication as u16,
         event_type: EventType::RejectTeamApplication,
         event_nonce: game.event_nonce,
         data: EventData::RejectTeamApplication {
@@ -89,7 +102,8 @@ pub fn reject_team_application(
         initiator_type: InitiatorType::TEAM,
         initiator: rejector.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
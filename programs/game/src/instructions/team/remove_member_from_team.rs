@@ -1,6 +1,6 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
@@ -16,6 +16,7 @@ use solana_program::sysvar::clock::Clock;
 /// 3. If removing a manager, ensure the caller is the captain, since only the captain can remove managers.
 /// 4. Remove the member from the team's member list and update their player data to revert them to the default team, applying a cooldown before rejoining any team.
 /// 5. Emit a `RemoveMemberFromTeam` event recording the action on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(member_to_remove: Pubkey)]
 pub struct RemoveMemberFromTeam<'info> {
@@ -42,6 +43,10 @@ pub struct RemoveMemberFromTeam<'info> {
     /// The global game account, providing reference to the `default_team` and other configurations.
     #[account(mut, seeds = [GAME_SEED], bump)]
     pub game: Box<Account<'info, Game>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 pub fn remove_member_from_team(
@@ -94,7 +99,9 @@ pub fn remove_member_from_team(
     game.increment_event_nonce()?;
 
     // Emit an event noting the member removal action
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::RemoveMemberFromTeam as u16,
         event_type: EventType::RemoveMemberFromTeam,
         event_nonce: game.event_nonce,
         data: EventData::RemoveMemberFromTeam {
@@ -104,7 +111,8 @@ pub fn remove_member_from_team(
         initiator_type: InitiatorType::TEAM,
         initiator: manager.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
@@ -1,6 +1,6 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
@@ -14,6 +14,7 @@ use solana_program::sysvar::clock::Clock;
 /// 2. Prevent the captain from revoking their own privileges, maintaining logical consistency.
 /// 3. Remove the specified manager from the team's manager list.
 /// 4. Emit a `RevokeManagerPrivileges` event to record the action on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(manager: Pubkey)]
 pub struct RevokeManagerPrivileges<'info> {
@@ -43,6 +44,10 @@ pub struct RevokeManagerPrivileges<'info> {
         bump,
     )]
     pub manager_data: Box<Account<'info, PlayerData>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 pub fn revoke_manager_privileges(
@@ -73,7 +78,9 @@ pub fn revoke_manager_privileges(
     game.increment_event_nonce()?;
 
     // Emit an event to record the revocation action
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::RevokeManagerPrivileges as u16,
         event_type: EventType::RevokeManagerPrivileges,
         event_nonce: game.event_nonce,
         data: EventData::RevokeManagerPrivileges {
@@ -83,7 +90,8 @@ pub fn revoke_manager_privileges(
         initiator_type: InitiatorType::TEAM,
         initiator: captain.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
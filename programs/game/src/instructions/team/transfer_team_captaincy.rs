@@ -1,6 +1,6 @@
-use crate::constants::{GAME_SEED, PLAYER_DATA_SEED};
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PLAYER_DATA_SEED};
 use crate::errors::ErrorCode;
-use crate::events::{EventData, EventType, InitiatorType, TransferEvent};
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
 use crate::state::*;
 use crate::utils::to_timestamp_u64;
 use anchor_lang::prelude::*;
@@ -15,6 +15,7 @@ use solana_program::sysvar::clock::Clock;
 /// 3. Check that the recipient is a member of the team (handled by team's internal logic).
 /// 4. Update the team account to reflect the new captain.
 /// 5. Emit a `TransferTeamCaptaincy` event to record this leadership change on-chain.
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(member: Pubkey)]
 pub struct TransferTeamCaptaincy<'info> {
@@ -45,6 +46,10 @@ pub struct TransferTeamCaptaincy<'info> {
         bump,
     )]
     pub member_player_data: Box<Account<'info, PlayerData>>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
 }
 
 pub fn transfer_team_captaincy(ctx: Context<TransferTeamCaptaincy>, member: Pubkey) -> Result<()> {
@@ -69,7 +74,9 @@ pub fn transfer_team_captaincy(ctx: Context<TransferTeamCaptaincy>, member: Pubk
     game.increment_event_nonce()?;
 
     // Emit an event logging the leadership change
-    emit!(TransferEvent {
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::TransferTeamCaptaincy as u16,
         event_type: EventType::TransferTeamCaptaincy,
         event_nonce: game.event_nonce,
         data: EventData::TransferTeamCaptaincy {
@@ -80,7 +87,8 @@ pub fn transfer_team_captaincy(ctx: Context<TransferTeamCaptaincy>, member: Pubk
         initiator_type: InitiatorType::TEAM,
         initiator: captain.key(),
         timestamp,
-    });
+    };
+    record_event!(ctx, event);
 
     Ok(())
 }
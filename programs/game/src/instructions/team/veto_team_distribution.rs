@@ -0,0 +1,96 @@
+use crate::constants::{EVENT_RING_BUFFER_SEED, GAME_SEED, PENDING_DISTRIBUTION_SEED};
+use crate::errors::ErrorCode;
+use crate::events::{EventData, EventType, InitiatorType, TransferEvent, EVENT_SCHEMA_VERSION};
+use crate::state::*;
+use crate::utils::to_timestamp_u64;
+use anchor_lang::prelude::*;
+use solana_program::sysvar::clock::Clock;
+
+/// The `VetoTeamDistribution` instruction lets a manager of a team veto a team reward distribution proposed by its
+/// captain, before its veto period ends. Only the managers of the team when the distribution was proposed may veto it,
+/// even if the captain has since revoked their privileges or removed them from the team. The pending distribution is closed, its rent returned to the proposer.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VetoTeamDistribution<'info> {
+    /// The team account whose proposed distribution is vetoed, counting its pending distributions.
+    #[account(mut)]
+    pub team: Box<Account<'info, Team>>,
+
+    #[account(mut, seeds = [GAME_SEED], bump)]
+    pub game: Box<Account<'info, Game>>,
+
+    /// The manager (signer) vetoing the distribution.
+    pub manager: Signer<'info>,
+
+    /// The pending distribution vetoed, closed on exit.
+    #[account(mut,
+        close = proposer,
+        seeds = [PENDING_DISTRIBUTION_SEED, team.key().as_ref(), pending_distribution.member.as_ref()],
+        bump = pending_distribution.bump,
+        has_one = team,
+        has_one = proposer,
+    )]
+    pub pending_distribution: Box<Account<'info, PendingDistribution>>,
+
+    /// The captain who proposed the distribution, refunded its rent.
+    /// CHECK: Must be the `proposer` of the pending distribution, checked by its `has_one` constraint.
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// The event ring buffer recording the most recent events.
+    #[account(mut, seeds = [EVENT_RING_BUFFER_SEED], bump)]
+    pub event_ring_buffer: AccountLoader<'info, EventRingBuffer>,
+}
+
+/// Vetoes a pending team reward distribution.
+///
+/// Steps:
+/// 1. Check the signer was a manager of the team when the distribution was proposed, and its veto period has not ended.
+/// 2. Stop counting the distribution among the team's pending distributions.
+/// 3. Emit a `VetoTeamDistribution` event to log the veto on-chain; the pending distribution is closed on exit.
+pub fn veto_team_distribution(ctx: Context<VetoTeamDistribution>) -> Result<()> {
+    // Fetch the current UNIX timestamp to check the veto period
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Extract references for clarity
+    let VetoTeamDistribution {
+        team,
+        game,
+        manager,
+        pending_distribution,
+        ..
+    } = ctx.accounts;
+
+    require!(
+        pending_distribution.can_veto(manager.key()),
+        ErrorCode::NotAuthorized
+    );
+    require!(
+        !pending_distribution.is_veto_period_ended(timestamp),
+        ErrorCode::TeamDistributionVetoPeriodEnded
+    );
+    team.remove_pending_distribution()?;
+
+    game.increment_event_nonce()?;
+
+    // Emit an event logging the veto
+    let event = TransferEvent {
+        event_schema_version: EVENT_SCHEMA_VERSION,
+        event_tag: EventType::VetoTeamDistribution as u16,
+        event_type: EventType::VetoTeamDistribution,
+        event_nonce: game.event_nonce,
+        data: EventData::VetoTeamDistribution {
+            team: team.key(),
+            member: pending_distribution.member,
+            team_rewards: pending_distribution.team_rewards,
+            manager: manager.key(),
+        },
+        initiator_type: InitiatorType::TEAM,
+        initiator: manager.key(),
+        timestamp,
+    };
+    record_event!(ctx, event);
+
+    Ok(())
+}
@@ -0,0 +1,259 @@
+//! The public interface of the game for partner programs and clients.
+//!
+//! It gathers the PDA seeds and address derivations an integrator needs to assemble the accounts of an
+//! instruction, so they do not have to reach into the program's internal modules. With the `cpi` feature
+//! enabled, it also re-exports the CPI builders of the player-facing instructions with their typed account
+//! structs, e.g. `interface::purchase(CpiContext::new(game_program, interface::accounts::Purchase { .. }), ores)`.
+//!
+//! Every instruction emitting events through `emit_cpi!` additionally takes the `event_authority` account,
+//! derived with `find_event_authority_address`, and the game program itself as `program`.
+
+pub use crate::constants::{
+    AIRDROP_CAMPAIGN_SEED, AIRDROP_CLAIM_SEED, BAN_ENTRY_SEED, DAILY_STATS_SEED,
+    EVENT_RING_BUFFER_SEED, GAME_CONFIG_SEED, GAME_SEED, MERKLE_AIRDROP_BITMAP_SEED,
+    MERKLE_AIRDROP_SEED, PENDING_DISTRIBUTION_SEED, PERIOD_SEED, PLAYER_DATA_SEED,
+    PREDICTION_MARKET_SEED, PREDICTION_OUTCOME_SEED, PREDICTION_SEED, PRIZE_VESTING_SEED,
+    PROPOSAL_SEED, ROUND_SEED, SESSION_KEY_SEED, STAKE_ORDER_INDEX_SEED, STAKE_ORDER_SEED,
+    STAKE_POOL_SEED, TEAM_LEDGER_SEED, TEAM_MERGE_SEED, TEAM_QUEST_COMPLETION_SEED,
+    TEAM_QUEST_SEED, TEAM_SEED, VOTE_RECORD_SEED, VOUCHER_KIND_MINT_SEED, VOUCHER_KIND_SEED,
+    VOUCHER_MINT_SEED, VOUCHER_SEED,
+};
+
+#[cfg(feature = "cpi")]
+pub use crate::cpi::{
+    accounts, collect_airdrop_rewards, collect_all_rewards, collect_consumption_rewards,
+    collect_referral_rewards, deposit_and_stake, purchase, purchase_with_tokens,
+    settle_and_purchase, stake,
+};
+
+use anchor_lang::prelude::Pubkey;
+
+/// Seed of the PDA signing the events the program emits through `emit_cpi!`.
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Derives the address of the global `Game` account.
+pub fn find_game_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME_SEED], &crate::ID)
+}
+
+/// Derives the address of the `GameConfig` account.
+pub fn find_game_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GAME_CONFIG_SEED], &crate::ID)
+}
+
+/// Derives the address of the event ring buffer every event-emitting instruction writes to.
+pub fn find_event_ring_buffer_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_RING_BUFFER_SEED], &crate::ID)
+}
+
+/// Derives the address of the `event_authority` account taken by every event-emitting instruction.
+pub fn find_event_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &crate::ID)
+}
+
+/// Derives the address of the global `Voucher` account.
+pub fn find_voucher_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOUCHER_SEED], &crate::ID)
+}
+
+/// Derives the address of the voucher mint.
+pub fn find_voucher_mint_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOUCHER_MINT_SEED], &crate::ID)
+}
+
+/// Derives the address of the `VoucherKind` registered as `kind_id`.
+pub fn find_voucher_kind_address(kind_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOUCHER_KIND_SEED, &kind_id.to_le_bytes()], &crate::ID)
+}
+
+/// Derives the address of the mint of the `VoucherKind` registered as `kind_id`.
+pub fn find_voucher_kind_mint_address(kind_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOUCHER_KIND_MINT_SEED, &kind_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Derives the address of the `PlayerData` account of `player`.
+pub fn find_player_data_address(player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PLAYER_DATA_SEED, player.as_ref()], &crate::ID)
+}
+
+/// Derives the address of the `SessionKey` account of `player`.
+pub fn find_session_key_address(player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SESSION_KEY_SEED, player.as_ref()], &crate::ID)
+}
+
+/// Derives the address of the `Round` numbered `round_number`; the current one is `Game::round_nonce`.
+pub fn find_round_address(round_number: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ROUND_SEED, &round_number.to_le_bytes()], &crate::ID)
+}
+
+/// Derives the address of the `Period` numbered `period_number`; the current one is `Game::period_nonce`.
+pub fn find_period_address(period_number: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PERIOD_SEED, &period_number.to_le_bytes()], &crate::ID)
+}
+
+/// Derives the address of the `Team` numbered `team_number`.
+pub fn find_team_address(team_number: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TEAM_SEED, &team_number.to_le_bytes()], &crate::ID)
+}
+
+/// Derives the address of the `StakePool`.
+pub fn find_stake_pool_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_POOL_SEED], &crate::ID)
+}
+
+/// Derives the address of the stake order `order_number` of `player`; a new stake uses `PlayerData::nonce`.
+pub fn find_stake_order_address(player: &Pubkey, order_number: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            STAKE_ORDER_SEED,
+            player.as_ref(),
+            &order_number.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Derives the address of the `StakeOrderIndex` of `player`.
+pub fn find_stake_order_index_address(player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_ORDER_INDEX_SEED, player.as_ref()], &crate::ID)
+}
+
+/// Derives the address of the `DailyStats` of `day` (`timestamp / SECONDS_PER_DAY`).
+pub fn find_daily_stats_address(day: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DAILY_STATS_SEED, &day.to_le_bytes()], &crate::ID)
+}
+
+/// Derives the address of the `AirdropCampaign` created as `campaign_id`.
+pub fn find_airdrop_campaign_address(campaign_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[AIRDROP_CAMPAIGN_SEED, &campaign_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Derives the address of the `AirdropClaim` of `player` on the campaign `airdrop_campaign`.
+pub fn find_airdrop_claim_address(airdrop_campaign: &Pubkey, player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            AIRDROP_CLAIM_SEED,
+            airdrop_campaign.as_ref(),
+            player.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Derives the address of the `MerkleAirdrop` created as `airdrop_id`.
+pub fn find_merkle_airdrop_address(airdrop_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MERKLE_AIRDROP_SEED, &airdrop_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Derives the address of the `MerkleAirdropBitmap` of the airdrop `merkle_airdrop`.
+pub fn find_merkle_airdrop_bitmap_address(merkle_airdrop: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MERKLE_AIRDROP_BITMAP_SEED, merkle_airdrop.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Derives the address of the `TeamQuest` of the period `period`.
+pub fn find_team_quest_address(period: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TEAM_QUEST_SEED, period.as_ref()], &crate::ID)
+}
+
+/// Derives the address of the `TeamQuestCompletion` of `team` on the quest `team_quest`.
+pub fn find_team_quest_completion_address(team_quest: &Pubkey, team: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            TEAM_QUEST_COMPLETION_SEED,
+            team_quest.as_ref(),
+            team.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Derives the address of the `PredictionMarket` of the round `round`.
+pub fn find_prediction_market_address(round: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PREDICTION_MARKET_SEED, round.as_ref()], &crate::ID)
+}
+
+/// Derives the address of the `Prediction` of `predictor` on the market `prediction_market`.
+pub fn find_prediction_address(prediction_market: &Pubkey, predictor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREDICTION_SEED,
+            prediction_market.as_ref(),
+            predictor.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Derives the address of the `PredictionOutcome` of `predicted_winner` on the market `prediction_market`.
+pub fn find_prediction_outcome_address(
+    prediction_market: &Pubkey,
+    predicted_winner: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREDICTION_OUTCOME_SEED,
+            prediction_market.as_ref(),
+            predicted_winner.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Derives the address of the `PrizeVesting` streaming the first-place grand prize of `round` to `player`.
+pub fn find_prize_vesting_address(round: &Pubkey, player: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PRIZE_VESTING_SEED, round.as_ref(), player.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Derives the address of the `PendingDistribution` proposing a distribution of `team`'s rewards to `member`.
+pub fn find_pending_distribution_address(team: &Pubkey, member: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PENDING_DISTRIBUTION_SEED, team.as_ref(), member.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Derives the address of the `TeamLedger` recording the distributions out of `team`'s vault.
+pub fn find_team_ledger_address(team: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TEAM_LEDGER_SEED, team.as_ref()], &crate::ID)
+}
+
+/// Derives the address of the `TeamMerge` recording the merge of `absorbed_team` into another team.
+pub fn find_team_merge_address(absorbed_team: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TEAM_MERGE_SEED, absorbed_team.as_ref()], &crate::ID)
+}
+
+/// Derives the address of the `BanEntry` banning `wallet`, passed to the instructions rejecting banned wallets.
+pub fn find_ban_entry_address(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BAN_ENTRY_SEED, wallet.as_ref()], &crate::ID)
+}
+
+/// Derives the address of the governance `Proposal` created with `proposal_number`, the game's `proposal_nonce` at the time.
+pub fn find_proposal_address(proposal_number: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROPOSAL_SEED, proposal_number.to_le_bytes().as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Derives the address of the `VoteRecord` of `voter` on `proposal`.
+pub fn find_vote_record_address(proposal: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOTE_RECORD_SEED, proposal.as_ref(), voter.as_ref()],
+        &crate::ID,
+    )
+}
@@ -1,18 +1,37 @@
 use anchor_lang::prelude::*;
 
+// `MockRandomness` accounts let anyone pick the outcome of a lottery draw, so the feature is limited to
+// host tests and localnet builds.
+#[cfg(all(
+    feature = "mock-randomness",
+    target_os = "solana",
+    not(feature = "localnet-defaults")
+))]
+compile_error!("the `mock-randomness` feature may only be combined with `localnet-defaults` in an on-chain build");
+
 /// Internal modules and utilities
 #[doc(hidden)]
 pub mod constants;
 #[doc(hidden)]
 pub mod errors;
+#[macro_use]
 pub mod events;
 #[doc(hidden)]
 pub mod instructions;
+pub mod interface;
 pub mod state;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing;
 #[doc(hidden)]
 pub mod utils;
 
 use instructions::*;
+use state::{
+    AirdropCampaignParams, ConsumptionRewardTier, DefaultWinnerPolicy, DepositPurpose,
+    GameConfigParams, GovernanceAction, LotteryStats, LotteryTableParams, PendingRewards,
+    PeriodConfig, PlayerSummary, ReferralPayout, RegistrationGate, RoundSummary, ShortfallPool,
+    SugarRushEmissionMode,
+};
 
 declare_id!("HCMBs4McFkMXzrCi9xbgSejtok3q8qD2WHZbbHwGxWLy");
 
@@ -53,18 +72,18 @@ mod game {
         instructions::distribute_grand_prizes::distribute_grand_prizes(ctx, index, player)
     }
 
-    /// Distributes rewards to the top-ranking players on the leaderboard.
+    /// Streams the current round's first-place grand prize over a number of days instead of paying it at once.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `player_leaderboard_winner`: The public key of the winner who topped the leaderboard.
-    pub fn distribute_leaderboard_rewards(
-        ctx: Context<DistributeLeaderboardRewards>,
-        player_leaderboard_winner: Pubkey,
+    /// - `grand_prize_vesting_days`: The streaming duration in days, 0 to pay the prize instantly.
+    pub fn set_grand_prize_vesting(
+        ctx: Context<SetGrandPrizeVesting>,
+        grand_prize_vesting_days: u16,
     ) -> Result<()> {
-        instructions::distribute_leaderboard_rewards::distribute_leaderboard_rewards(
+        instructions::manager::set_grand_prize_vesting::set_grand_prize_vesting(
             ctx,
-            player_leaderboard_winner,
+            grand_prize_vesting_days,
         )
     }
 
@@ -100,12 +119,14 @@ mod game {
     /// - `leaderboard_duration`: The duration of the leaderboard phase in seconds.
     /// - `team_rewards`: The total reward amount allocated for teams.
     /// - `individual_rewards`: The total reward amount allocated for individual players.
+    /// - `config`: The payout curves and assets, round coupling, team scoring mode and team qualification thresholds.
     pub fn create_period(
         ctx: Context<CreatePeriod>,
         start_time: u64,
         leaderboard_duration: u64,
         team_rewards: u64,
         individual_rewards: u64,
+        config: PeriodConfig,
     ) -> Result<()> {
         instructions::create_period::create_period(
             ctx,
@@ -113,9 +134,101 @@ mod game {
             leaderboard_duration,
             team_rewards,
             individual_rewards,
+            config,
         )
     }
 
+    /// Rolls the ended current period over to the next one, tipping the caller from the maintenance pool.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn rollover_period(ctx: Context<RolloverPeriod>) -> Result<()> {
+        instructions::rollover_period::rollover_period(ctx)
+    }
+
+    /// Sweeps the referral rewards a referrer left uncollected past their expiry back to the referral pool.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn sweep_expired_referral_rewards(ctx: Context<SweepExpiredReferralRewards>) -> Result<()> {
+        instructions::manager::sweep_expired_referral_rewards::sweep_expired_referral_rewards(ctx)
+    }
+
+    /// Upgrades legacy `Round` and `PlayerData` accounts to their current layout, scaling `earnings_per_ore`.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context. The accounts to migrate are passed through `remaining_accounts`.
+    pub fn migrate_earnings_per_ore<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateEarningsPerOre<'info>>,
+    ) -> Result<()> {
+        instructions::migrate_earnings_per_ore::migrate_earnings_per_ore(ctx)
+    }
+
+    /// Upgrades the legacy `StakePool` and `StakeOrder` accounts to the layout with the appended staking fields.
+    /// Releases the interest reserved by legacy orders that are still running back to the pool.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context. The stake orders to migrate are passed through `remaining_accounts`.
+    pub fn migrate_stake_accounts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateStakeAccounts<'info>>,
+    ) -> Result<()> {
+        instructions::migrate_stake_accounts::migrate_stake_accounts(ctx)
+    }
+
+    /// Upgrades the legacy `Game` account to its current layout, and creates the `GameConfig` and the
+    /// `EventRingBuffer` if they do not exist yet. Must run right after the program upgrade.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn migrate_game(ctx: Context<MigrateGame>) -> Result<()> {
+        instructions::migrate_game::migrate_game(ctx)
+    }
+
+    /// Upgrades the legacy `Team` accounts to the layout with the appended team fields.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context. The teams to migrate are passed through `remaining_accounts`.
+    pub fn migrate_team_accounts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateTeamAccounts<'info>>,
+    ) -> Result<()> {
+        instructions::migrate_team_accounts::migrate_team_accounts(ctx)
+    }
+
+    /// Converts the legacy borsh `Period` accounts to the zero-copy layout, turning their reward split into rank rewards.
+    /// The current period must be migrated right after the program upgrade.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context. The periods to migrate are passed through `remaining_accounts`.
+    pub fn migrate_periods<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigratePeriods<'info>>,
+    ) -> Result<()> {
+        instructions::migrate_periods::migrate_periods(ctx)
+    }
+
+    /// Upgrades the legacy `Voucher` account to the layout with the soulbound mode and the redemption fee.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn migrate_voucher(ctx: Context<MigrateVoucher>) -> Result<()> {
+        instructions::migrate_voucher::migrate_voucher(ctx)
+    }
+
+    /// Audits the game vault and the current round vault against the pool balances they back.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn reconcile_vault(ctx: Context<ReconcileVault>) -> Result<()> {
+        instructions::reconcile_vault::reconcile_vault(ctx)
+    }
+
+    /// Recomputes the balances of the game, round and period vaults from their pool fields and reports the deltas.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn reconcile_game(ctx: Context<ReconcileGame>) -> Result<()> {
+        instructions::reconcile_game::reconcile_game(ctx)
+    }
+
     /// Creates a new round, specifying start time, duration, and the initial grand prize pool balance.
     ///
     /// # Parameters
@@ -165,310 +278,1600 @@ mod game {
         )
     }
 
-    /// Initializes a voucher account, allowing for tokenized representation of pool deposits.
+    /// Updates the utilization curve used to derive the stake pool APR.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn initialize_voucher(ctx: Context<InitializeVoucher>) -> Result<()> {
-        instructions::initialize_voucher::initialize_voucher(ctx)
+    /// - `min_annual_rate`: The APR applied at full utilization.
+    /// - `max_annual_rate`: The APR applied at or below the target utilization.
+    /// - `target_utilization`: The utilization above which the APR starts decreasing.
+    pub fn set_stake_rate_curve(
+        ctx: Context<SetStakeRateCurve>,
+        min_annual_rate: u8,
+        max_annual_rate: u8,
+        target_utilization: u8,
+    ) -> Result<()> {
+        instructions::manager::set_stake_rate_curve::set_stake_rate_curve(
+            ctx,
+            min_annual_rate,
+            max_annual_rate,
+            target_utilization,
+        )
     }
 
-    /// Performs initial setup for the program, allocating necessary state and configuration.
+    /// Starts or stops winding down the stake pool, enabling emergency withdrawals of principal.
+    /// The game has no pause state, so winding down also pauses staking.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `bot_authority`: The public key of the bot authority.
-    pub fn initialize(
-        ctx: Context<Initialize>,
-        bot_authority: Pubkey,
-        round_rewards: u64,
-        period_rewards: u64,
-        registration_rewards: u64,
-        airdrop_rewards: u64,
-        exit_rewards: u64,
-        lottery_rewards: u64,
-        consumption_rewards: u64,
-        sugar_rush_rewards: u64,
+    /// - `is_winding_down`: Whether the stake pool is being wound down.
+    pub fn set_stake_pool_winding_down(
+        ctx: Context<SetStakePoolWindingDown>,
+        is_winding_down: bool,
     ) -> Result<()> {
-        instructions::initialize::initialize(
+        instructions::manager::set_stake_pool_winding_down::set_stake_pool_winding_down(
             ctx,
-            bot_authority,
-            round_rewards,
-            period_rewards,
-            registration_rewards,
-            airdrop_rewards,
-            exit_rewards,
-            lottery_rewards,
-            consumption_rewards,
-            sugar_rush_rewards,
+            is_winding_down,
         )
     }
 
-    /// Stakes a specified amount of tokens into the pool to earn ongoing rewards.
+    /// Decommissions the stake pool, and sweeps its residual balances once all orders have matured.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `amount`: The amount of tokens to stake.
-    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
-        instructions::stake::stake(ctx, amount)
+    pub fn decommission_stake_pool(ctx: Context<DecommissionStakePool>) -> Result<()> {
+        instructions::manager::decommission_stake_pool::decommission_stake_pool(ctx)
     }
 
-    /// Requests an early unstake of previously staked tokens before the lock-up period ends, possibly incurring penalties.
+    /// Enables or disables the voucher soulbound mode.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `order_number`: The identifier of the stake order to be released early.
-    pub fn request_early_unstake(
-        ctx: Context<RequestEarlyUnstake>,
-        order_number: u16,
+    /// - `is_transfer_restricted`: Whether player voucher accounts may be frozen.
+    pub fn set_voucher_transfer_restriction(
+        ctx: Context<SetVoucherTransferRestriction>,
+        is_transfer_restricted: bool,
     ) -> Result<()> {
-        instructions::stake::request_early_unstake::request_early_unstake(ctx, order_number)
+        instructions::manager::set_voucher_transfer_restriction::set_voucher_transfer_restriction(
+            ctx,
+            is_transfer_restricted,
+        )
     }
 
-    /// Unstakes tokens that have reached their required staking period and can now be withdrawn without penalty.
+    /// Registers a custodial player account for a user without a wallet, to be claimed later with a claim code.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `order_number`: The identifier of the fully matured stake order to be withdrawn.
-    pub fn unstake(ctx: Context<Unstake>, order_number: u16) -> Result<()> {
-        instructions::unstake::unstake(ctx, order_number)
+    /// - `player_pubkey`: The custodial key the account is registered for.
+    /// - `referrer`: The public key of the referring player.
+    /// - `claim_code_hash`: The SHA-256 hash of the claim code handed to the user.
+    pub fn register_custodial(
+        ctx: Context<RegisterCustodial>,
+        player_pubkey: Pubkey,
+        referrer: Pubkey,
+        claim_code_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::manager::register_custodial::register_custodial(
+            ctx,
+            player_pubkey,
+            referrer,
+            claim_code_hash,
+        )
     }
 
-    /// Cancels the auto-reinvest setting for a player, stopping automatic compounding of earnings.
+    /// Creates or updates the voucher mint's metadata shown by wallets.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn cancel_is_auto_reinvesting(ctx: Context<CancelIsAutoReinvesting>) -> Result<()> {
-        instructions::cancel_is_auto_reinvesting::cancel_is_auto_reinvesting(ctx)
+    /// - `name`: The token name.
+    /// - `symbol`: The token symbol.
+    /// - `uri`: The URI of the off-chain metadata JSON.
+    pub fn set_voucher_metadata(
+        ctx: Context<SetVoucherMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::manager::set_voucher_metadata::set_voucher_metadata(ctx, name, symbol, uri)
     }
 
-    /// Candy tap is a function that allows players to tap into the game's rewards pool.
+    /// Freezes a player's voucher account so vouchers can only move through game instructions.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn candy_tap(ctx: Context<CandyTap>, last_active_participant: Pubkey) -> Result<()> {
-        instructions::candy_tap::candy_tap(ctx, last_active_participant)
+    pub fn freeze_voucher_account(ctx: Context<FreezeVoucherAccount>) -> Result<()> {
+        instructions::manager::freeze_voucher_account::freeze_voucher_account(ctx)
     }
 
-    /// Collects any available airdrop rewards for the player.
+    /// Thaws a frozen voucher account.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn collect_airdrop_rewards(ctx: Context<CollectAirdropRewards>) -> Result<()> {
-        instructions::collect_airdrop_rewards::collect_airdrop_rewards(ctx)
+    pub fn thaw_voucher_account(ctx: Context<ThawVoucherAccount>) -> Result<()> {
+        instructions::manager::thaw_voucher_account::thaw_voucher_account(ctx)
     }
 
-    /// Collects rewards earned through player consumption or spending activities.
+    /// Sets the percentage of the underlying tokens withheld when vouchers are redeemed.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn collect_consumption_rewards(ctx: Context<CollectConsumptionRewards>) -> Result<()> {
-        instructions::collect_consumption_rewards::collect_consumption_rewards(ctx)
+    /// - `redemption_fee_rate`: The redemption fee (0-100).
+    pub fn set_voucher_redemption_fee(
+        ctx: Context<SetVoucherRedemptionFee>,
+        redemption_fee_rate: u8,
+    ) -> Result<()> {
+        instructions::manager::set_voucher_redemption_fee::set_voucher_redemption_fee(
+            ctx,
+            redemption_fee_rate,
+        )
     }
 
-    /// Exchanges collateral tokens into the corresponding in-game currency or resource.
+    /// Registers a voucher kind with its own mint, worth `value_rate` percent of a token on a purchase.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `amount`: The amount of collateral to be exchanged.
-    pub fn collateral_exchange(ctx: Context<CollateralExchange>, amount: u64) -> Result<()> {
-        instructions::collateral_exchange::collateral_exchange(ctx, amount)
+    /// - `kind_id`: The identifier of the kind.
+    /// - `value_rate`: The value of one voucher of the kind, as a percentage of a token.
+    pub fn register_voucher_kind(
+        ctx: Context<RegisterVoucherKind>,
+        kind_id: u16,
+        value_rate: u16,
+    ) -> Result<()> {
+        instructions::manager::register_voucher_kind::register_voucher_kind(
+            ctx, kind_id, value_rate,
+        )
     }
 
-    /// Collects referral rewards earned by inviting new participants to the platform.
+    /// Updates the value rate of a voucher kind and whether it may be issued and spent.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn collect_referral_rewards(ctx: Context<CollectReferralRewards>) -> Result<()> {
-        instructions::collect_referral_rewards::collect_referral_rewards(ctx)
+    /// - `value_rate`: The value of one voucher of the kind, as a percentage of a token.
+    /// - `is_enabled`: Whether the kind may be issued and spent.
+    pub fn update_voucher_kind(
+        ctx: Context<UpdateVoucherKind>,
+        value_rate: u16,
+        is_enabled: bool,
+    ) -> Result<()> {
+        instructions::manager::update_voucher_kind::update_voucher_kind(ctx, value_rate, is_enabled)
     }
 
-    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-        instructions::deposit::deposit(ctx, amount)
+    /// Issues vouchers of a kind to a recipient, depositing the tokens backing them.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `amount`: The vouchers of the kind to issue.
+    pub fn issue_voucher_kind(ctx: Context<IssueVoucherKind>, amount: u64) -> Result<()> {
+        instructions::manager::issue_voucher_kind::issue_voucher_kind(ctx, amount)
     }
 
-    /// Conducts a lottery draw to determine winners from a pool of participants.
+    /// Creates an airdrop campaign, moving `game_pool_budget` tokens of the game's airdrop pool into its vault.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn draw_lottery(ctx: Context<DrawLottery>) -> Result<()> {
-        instructions::draw_lottery::draw_lottery(ctx)
+    /// - `campaign_id`: The identifier of the campaign.
+    /// - `params`: The eligibility rule, rewards, daily cap and window of the campaign.
+    /// - `game_pool_budget`: The tokens moved from the game's airdrop rewards pool into the campaign.
+    pub fn create_airdrop_campaign(
+        ctx: Context<CreateAirdropCampaign>,
+        campaign_id: u16,
+        params: AirdropCampaignParams,
+        game_pool_budget: u64,
+    ) -> Result<()> {
+        instructions::manager::create_airdrop_campaign::create_airdrop_campaign(
+            ctx,
+            campaign_id,
+            params,
+            game_pool_budget,
+        )
     }
 
-    /// Exits from the current game or round, potentially collecting any accrued exit rewards.
+    /// Deposits tokens from the authority into an airdrop campaign's budget.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn exit(ctx: Context<Exit>) -> Result<()> {
-        instructions::exit::exit(ctx)
+    /// - `amount`: The tokens to deposit.
+    pub fn fund_airdrop_campaign(ctx: Context<FundAirdropCampaign>, amount: u64) -> Result<()> {
+        instructions::manager::fund_airdrop_campaign::fund_airdrop_campaign(ctx, amount)
     }
 
-    /// Registers a new player into the game, optionally associating them with a referrer.
+    /// Enables or disables collecting an airdrop campaign's rewards.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `referrer`: The public key of the referrer (if any).
-    pub fn register(ctx: Context<Register>, referrer: Pubkey) -> Result<()> {
-        instructions::register::register(ctx, referrer)
+    /// - `is_enabled`: Whether the campaign's rewards may be collected.
+    pub fn set_airdrop_campaign_enabled(
+        ctx: Context<SetAirdropCampaignEnabled>,
+        is_enabled: bool,
+    ) -> Result<()> {
+        instructions::manager::set_airdrop_campaign_enabled::set_airdrop_campaign_enabled(
+            ctx, is_enabled,
+        )
     }
 
-    /// Purchases a specified quantity of in-game assets or lottery entries.
+    /// Creates a merkle airdrop of retroactive rewards, depositing the tokens covering its claims.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `purchase_quantity`: The number of units or tickets to purchase.
-    pub fn purchase(ctx: Context<Purchase>, purchase_quantity: u32) -> Result<()> {
-        instructions::purchase::purchase(ctx, purchase_quantity)
+    /// - `airdrop_id`: The identifier of the airdrop.
+    /// - `merkle_root`: The root of the merkle tree of the `(index, player, amount)` claims.
+    /// - `total_amount`: The tokens deposited to cover every claim.
+    /// - `claim_count`: The number of claims, sizing the claimed bitmap.
+    pub fn create_merkle_airdrop(
+        ctx: Context<CreateMerkleAirdrop>,
+        airdrop_id: u16,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        claim_count: u32,
+    ) -> Result<()> {
+        instructions::manager::create_merkle_airdrop::create_merkle_airdrop(
+            ctx,
+            airdrop_id,
+            merkle_root,
+            total_amount,
+            claim_count,
+        )
     }
 
-    /// Reinvests a player's claims or accrued rewards back into the game environment.
+    /// Creates a period's team quest, paying a bonus to every team purchasing at least `goal_ores` ORE during it.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn reinvest(ctx: Context<Reinvest>) -> Result<()> {
-        instructions::reinvest::reinvest(ctx)
+    /// - `goal_ores`: The ORE a team must purchase during the period to complete the quest.
+    /// - `bonus_rewards`: The rewards of every team completing the quest.
+    /// - `budget`: The tokens deposited to pay the bonuses, at least one bonus.
+    pub fn create_team_quest(
+        ctx: Context<CreateTeamQuest>,
+        goal_ores: u32,
+        bonus_rewards: u64,
+        budget: u64,
+    ) -> Result<()> {
+        instructions::manager::create_team_quest::create_team_quest(
+            ctx,
+            goal_ores,
+            bonus_rewards,
+            budget,
+        )
     }
 
-    /// Reveals the outcome of the previously drawn lottery, finalizing the results on-chain.
+    /// Creates the collateral exchange configuration with its rate and limits.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn reveal_draw_lottery_result(ctx: Context<RevealDrawLotteryResult>) -> Result<()> {
-        instructions::reveal_draw_lottery_result::reveal_draw_lottery_result(ctx)
+    /// - `exchange_rate`: The percentage of the exchanged tokens minted as vouchers, at most 100.
+    /// - `daily_player_limit`: The per-player daily exchange limit, zero for unlimited.
+    /// - `global_cap`: The total exchange cap, zero for unlimited.
+    pub fn initialize_exchange_config(
+        ctx: Context<InitializeExchangeConfig>,
+        exchange_rate: u8,
+        daily_player_limit: u64,
+        global_cap: u64,
+    ) -> Result<()> {
+        instructions::manager::initialize_exchange_config::initialize_exchange_config(
+            ctx,
+            exchange_rate,
+            daily_player_limit,
+            global_cap,
+        )
     }
 
-    /// Enables automatic reinvestment for a player, compounding their returns without manual intervention.
+    /// Updates the collateral exchange rate and limits.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn set_is_auto_reinvesting(ctx: Context<SetIsAutoReinvesting>) -> Result<()> {
-        instructions::set_is_auto_reinvesting::set_is_auto_reinvesting(ctx)
+    /// - `exchange_rate`: The percentage of the exchanged tokens minted as vouchers, at most 100.
+    /// - `daily_player_limit`: The per-player daily exchange limit, zero for unlimited.
+    /// - `global_cap`: The total exchange cap, zero for unlimited.
+    pub fn update_exchange_config(
+        ctx: Context<UpdateExchangeConfig>,
+        exchange_rate: u8,
+        daily_player_limit: u64,
+        global_cap: u64,
+    ) -> Result<()> {
+        instructions::manager::update_exchange_config::update_exchange_config(
+            ctx,
+            exchange_rate,
+            daily_player_limit,
+            global_cap,
+        )
     }
 
-    /// Assigns a referrer to a player, establishing referral relationships.
+    /// Whitelists the AMM program and pool used by `purchase_with_sol`.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `referrer`: The public key of the referrer.
-    pub fn set_referrer(ctx: Context<SetReferrer>, referrer: Pubkey) -> Result<()> {
-        instructions::set_referrer::set_referrer(ctx, referrer)
+    /// - `swap_program`: The AMM program, `Pubkey::default()` to disable SOL purchases.
+    /// - `swap_pool`: The AMM pool trading wrapped SOL against the game token.
+    pub fn set_swap_pool(
+        ctx: Context<SetSwapPool>,
+        swap_program: Pubkey,
+        swap_pool: Pubkey,
+    ) -> Result<()> {
+        instructions::manager::set_swap_pool::set_swap_pool(ctx, swap_program, swap_pool)
     }
 
-    /// Settles the previous round, finalizing and distributing any outstanding rewards.
+    /// Sets the merkle tree of the round participation badges minted by `purchase`, and toggles them.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn settle_previous_round(ctx: Context<SettlePreviousRound>) -> Result<()> {
-        instructions::settle_previous_round::settle_previous_round(ctx)
+    /// - `badge_merkle_tree`: The Bubblegum merkle tree, delegated to the game account.
+    /// - `is_badge_enabled`: Whether a badge is minted on a player's first purchase in a round.
+    pub fn set_round_badge(
+        ctx: Context<SetRoundBadge>,
+        badge_merkle_tree: Pubkey,
+        is_badge_enabled: bool,
+    ) -> Result<()> {
+        instructions::manager::set_round_badge::set_round_badge(
+            ctx,
+            badge_merkle_tree,
+            is_badge_enabled,
+        )
     }
 
-    /// Accepts a player's application to join a team, officially adding them to the team.
+    /// Sets the window, relative to now, in which rounds and periods may be scheduled to start.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `applicant`: The public key of the player requesting to join.
-    pub fn accept_team_application(
-        ctx: Context<AcceptTeamApplication>,
-        applicant: Pubkey,
+    /// - `min_start_lead_seconds`: The minimum delay between now and a start time.
+    /// - `max_future_start_seconds`: The maximum delay between now and a start time.
+    pub fn set_start_time_window(
+        ctx: Context<SetStartTimeWindow>,
+        min_start_lead_seconds: u64,
+        max_future_start_seconds: u64,
     ) -> Result<()> {
-        instructions::accept_team_application::accept_team_application(ctx, applicant)
+        instructions::manager::set_start_time_window::set_start_time_window(
+            ctx,
+            min_start_lead_seconds,
+            max_future_start_seconds,
+        )
     }
 
-    /// Allows a player to apply to join an existing team, pending acceptance by the team management.
+    /// Sets how often candy taps may happen, per player and across a round.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn apply_to_join_team(ctx: Context<ApplyToJoinTeam>) -> Result<()> {
-        instructions::apply_to_join_team::apply_to_join_team(ctx)
+    /// - `candy_tap_cooldown_seconds`: The wait between two candy taps of the same player.
+    /// - `candy_tap_min_interval_seconds`: The wait between two candy taps of any players in a round.
+    pub fn set_candy_tap_throttle(
+        ctx: Context<SetCandyTapThrottle>,
+        candy_tap_cooldown_seconds: u64,
+        candy_tap_min_interval_seconds: u64,
+    ) -> Result<()> {
+        instructions::manager::set_candy_tap_throttle::set_candy_tap_throttle(
+            ctx,
+            candy_tap_cooldown_seconds,
+            candy_tap_min_interval_seconds,
+        )
     }
 
-    /// Creates a new team, enabling a group of players to form a team with collective goals and rewards.
+    /// Sets the limits on team applications, bounding how often a player may apply to teams.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn create_team(ctx: Context<CreateTeam>) -> Result<()> {
-        instructions::create_team::create_team(ctx)
+    /// - `max_team_applications_per_day`: The team applications a player may submit per day, at least one.
+    /// - `team_reapplication_cooldown_seconds`: The wait before a player may apply again to a team that rejected them.
+    pub fn set_team_application_limits(
+        ctx: Context<SetTeamApplicationLimits>,
+        max_team_applications_per_day: u8,
+        team_reapplication_cooldown_seconds: u64,
+    ) -> Result<()> {
+        instructions::manager::set_team_application_limits::set_team_application_limits(
+            ctx,
+            max_team_applications_per_day,
+            team_reapplication_cooldown_seconds,
+        )
     }
 
-    /// Distributes team-level rewards to a specific team member.
+    /// Sets the inactivity of a captain after which the captaincy of their team may be claimed.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `member`: The public key of the team member receiving rewards.
-    /// - `reward_amount`: The amount of rewards to distribute.
-    pub fn distribute_team_rewards(
-        ctx: Context<DistributeTeamRewards>,
-        member: Pubkey,
-        reward_amount: u64,
+    /// - `captain_inactivity_seconds`: The inactivity after which the longest-tenured manager may claim the captaincy,
+    ///   any member being able to after twice as long.
+    pub fn set_captain_inactivity_window(
+        ctx: Context<SetCaptainInactivityWindow>,
+        captain_inactivity_seconds: u64,
     ) -> Result<()> {
-        instructions::distribute_team_rewards::distribute_team_rewards(ctx, member, reward_amount)
+        instructions::manager::set_captain_inactivity_window::set_captain_inactivity_window(
+            ctx,
+            captain_inactivity_seconds,
+        )
     }
 
-    /// Grants manager-level privileges within the team to a specific member, allowing them to manage membership and rewards.
+    /// Sets the approval required by team reward distributions out of large team vaults.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `member`: The public key of the member to be granted manager privileges.
-    pub fn grant_manager_privileges(
-        ctx: Context<GrantManagerPrivileges>,
-        member: Pubkey,
+    /// - `team_distribution_approval_threshold`: The team vault balance above which distributions must be proposed.
+    /// - `team_distribution_veto_seconds`: The veto period a proposal waits for unless the game authority approves it.
+    pub fn set_team_distribution_approval(
+        ctx: Context<SetTeamDistributionApproval>,
+        team_distribution_approval_threshold: u64,
+        team_distribution_veto_seconds: u64,
     ) -> Result<()> {
-        instructions::grant_manager_privileges::grant_manager_privileges(ctx, member)
+        instructions::manager::set_team_distribution_approval::set_team_distribution_approval(
+            ctx,
+            team_distribution_approval_threshold,
+            team_distribution_veto_seconds,
+        )
     }
 
-    /// Allows a member to voluntarily leave a team.
+    /// Bans a wallet from the game while an exploit it abuses is fixed.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    pub fn leave_team(ctx: Context<LeaveTeam>) -> Result<()> {
-        instructions::leave_team::leave_team(ctx)
+    /// - `wallet`: The wallet banned.
+    /// - `reason_code`: An operator-defined code for the reason of the ban.
+    pub fn ban_player(ctx: Context<BanPlayer>, wallet: Pubkey, reason_code: u16) -> Result<()> {
+        instructions::manager::ban_player::ban_player(ctx, wallet, reason_code)
     }
 
-    /// Rejects a team application from a particular applicant.
+    /// Lifts the ban of a wallet.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `applicant`: The public key of the applicant to reject.
-    pub fn reject_team_application(
-        ctx: Context<RejectTeamApplication>,
-        applicant: Pubkey,
+    pub fn unban_player(ctx: Context<UnbanPlayer>) -> Result<()> {
+        instructions::manager::unban_player::unban_player(ctx)
+    }
+
+    /// Sets the compliance attestation gating selected instructions in geo-gated deployments.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `compliance_signer`: The key signing the eligibility attestations, the default key disabling the gating.
+    /// - `compliance_gated_instructions`: The `COMPLIANCE_GATE_*` flags of the instructions requiring an attestation.
+    /// - `compliance_attestation_max_age_seconds`: How long an attestation stays valid after being issued.
+    pub fn set_compliance_attestation(
+        ctx: Context<SetComplianceAttestation>,
+        compliance_signer: Pubkey,
+        compliance_gated_instructions: u8,
+        compliance_attestation_max_age_seconds: u64,
     ) -> Result<()> {
-        instructions::reject_team_application::reject_team_application(ctx, applicant)
+        instructions::manager::set_compliance_attestation::set_compliance_attestation(
+            ctx,
+            compliance_signer,
+            compliance_gated_instructions,
+            compliance_attestation_max_age_seconds,
+        )
     }
 
-    /// Removes a member from a team, revoking their participation and any associated privileges.
+    /// Sets the fee paid into the grand prize pool to re-enter a round after exiting it.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `member_to_remove`: The public key of the member to be removed.
-    pub fn remove_member_from_team(
-        ctx: Context<RemoveMemberFromTeam>,
-        member_to_remove: Pubkey,
+    /// - `reentry_fee`: The re-entry fee in token lamports.
+    pub fn set_reentry_fee(ctx: Context<SetReentryFee>, reentry_fee: u64) -> Result<()> {
+        instructions::manager::set_reentry_fee::set_reentry_fee(ctx, reentry_fee)
+    }
+
+    /// Covers a payout shortfall of the grand prize, construction or exit pool from the insurance pool.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `pool`: The pool short of funds.
+    /// - `amount`: The shortfall to cover.
+    pub fn cover_shortfall(
+        ctx: Context<CoverShortfall>,
+        pool: ShortfallPool,
+        amount: u64,
     ) -> Result<()> {
-        instructions::remove_member_from_team::remove_member_from_team(ctx, member_to_remove)
+        instructions::manager::cover_shortfall::cover_shortfall(ctx, pool, amount)
     }
 
-    /// Revokes previously granted manager privileges from a specific team member.
+    /// Sets what happens to the grand prizes and leaderboard rewards won by the default player or team.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `manager`: The public key of the member whose manager privileges will be revoked.
-    pub fn revoke_manager_privileges(
-        ctx: Context<RevokeManagerPrivileges>,
-        manager: Pubkey,
+    /// - `policy`: Burn them, roll them into the next period, or send them to the grand prizes or the insurance pool.
+    pub fn set_default_winner_policy(
+        ctx: Context<SetDefaultWinnerPolicy>,
+        policy: DefaultWinnerPolicy,
     ) -> Result<()> {
-        instructions::revoke_manager_privileges::revoke_manager_privileges(ctx, manager)
+        instructions::manager::set_default_winner_policy::set_default_winner_policy(ctx, policy)
     }
 
-    /// Transfers the role of team captain to another member.
+    /// Sets the proof a new player must give to receive the registration rewards.
     ///
     /// # Parameters
     /// - `ctx`: Execution context.
-    /// - `member`: The public key of the member to become the new captain.
-    pub fn transfer_team_captaincy(
-        ctx: Context<TransferTeamCaptaincy>,
-        member: Pubkey,
+    /// - `gate`: The registration gate.
+    /// - `min_deposit`: The game token balance required by `RegistrationGate::MinDeposit`.
+    /// - `attestor`: The key signing the attestations of `RegistrationGate::Attestation`.
+    pub fn set_registration_gate(
+        ctx: Context<SetRegistrationGate>,
+        gate: RegistrationGate,
+        min_deposit: u64,
+        attestor: Pubkey,
     ) -> Result<()> {
-        instructions::transfer_team_captaincy::transfer_team_captaincy(ctx, member)
+        instructions::manager::set_registration_gate::set_registration_gate(
+            ctx,
+            gate,
+            min_deposit,
+            attestor,
+        )
+    }
+
+    /// Sets how the registration rewards decay as more of them are distributed.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `initial_rewards`: The registration rewards of the first players.
+    /// - `decay_interval`: The registration rewards distributed between two decreases, zero for fixed rewards.
+    /// - `decay_amount`: The decrease of the registration rewards at every interval.
+    /// - `floor_rewards`: The registration rewards the schedule never decays below.
+    pub fn set_registration_reward_schedule(
+        ctx: Context<SetRegistrationRewardSchedule>,
+        initial_rewards: u64,
+        decay_interval: u64,
+        decay_amount: u64,
+        floor_rewards: u64,
+    ) -> Result<()> {
+        instructions::manager::set_registration_reward_schedule::set_registration_reward_schedule(
+            ctx,
+            initial_rewards,
+            decay_interval,
+            decay_amount,
+            floor_rewards,
+        )
+    }
+
+    /// Sets the tiers raising the consumption rewards rate with a player's purchase volume over the last 30 days.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `tiers`: The tiers by ascending minimum purchase volume, at most `CONSUMPTION_REWARD_TIER_COUNT`.
+    pub fn set_consumption_reward_tiers(
+        ctx: Context<SetConsumptionRewardTiers>,
+        tiers: Vec<ConsumptionRewardTier>,
+    ) -> Result<()> {
+        instructions::manager::set_consumption_reward_tiers::set_consumption_reward_tiers(
+            ctx, tiers,
+        )
+    }
+
+    /// Updates the pool shares and costs of the game config.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `params`: The new pool shares and costs.
+    pub fn update_game_config(
+        ctx: Context<UpdateGameConfig>,
+        params: GameConfigParams,
+    ) -> Result<()> {
+        instructions::manager::update_game_config::update_game_config(ctx, params)
+    }
+
+    /// Creates a lottery table with its own draw cost, reel symbols and multipliers.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `table_id`: The identifier of the new table.
+    /// - `params`: The cost, symbols and multipliers of the table.
+    pub fn create_lottery_table(
+        ctx: Context<CreateLotteryTable>,
+        table_id: u16,
+        params: LotteryTableParams,
+    ) -> Result<()> {
+        instructions::manager::create_lottery_table::create_lottery_table(ctx, table_id, params)
+    }
+
+    /// Enables or disables new draws on a lottery table.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `is_enabled`: Whether new draws may use the table.
+    pub fn set_lottery_table_enabled(
+        ctx: Context<SetLotteryTableEnabled>,
+        is_enabled: bool,
+    ) -> Result<()> {
+        instructions::manager::set_lottery_table_enabled::set_lottery_table_enabled(ctx, is_enabled)
+    }
+
+    /// Sets the curve escalating the sugar rush rewards the longer no one taps candy.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `sugar_rush_escalation_delay_seconds`: The idle time before the rewards start escalating.
+    /// - `sugar_rush_escalation_rate`: The growth of the multiplier per idle minute (in basis points).
+    /// - `sugar_rush_max_multiplier`: The highest multiplier (in basis points), at least 1x.
+    pub fn set_sugar_rush_escalation(
+        ctx: Context<SetSugarRushEscalation>,
+        sugar_rush_escalation_delay_seconds: u64,
+        sugar_rush_escalation_rate: u16,
+        sugar_rush_max_multiplier: u16,
+    ) -> Result<()> {
+        instructions::manager::set_sugar_rush_escalation::set_sugar_rush_escalation(
+            ctx,
+            sugar_rush_escalation_delay_seconds,
+            sugar_rush_escalation_rate,
+            sugar_rush_max_multiplier,
+        )
+    }
+
+    /// Sets the controller emitting the sugar rush rewards, at a fixed rate or in proportion to the remaining pool.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `mode`: How the sugar rush rewards per second are set.
+    /// - `rewards_per_second`: The rate under `Fixed`, and the highest rate under `Proportional`.
+    /// - `horizon_seconds`: Under `Proportional`, the time the remaining sugar rush pool is emitted over.
+    /// - `min_rewards_per_second`: Under `Proportional`, the lowest rate.
+    pub fn set_sugar_rush_emission(
+        ctx: Context<SetSugarRushEmission>,
+        mode: SugarRushEmissionMode,
+        rewards_per_second: u64,
+        horizon_seconds: u64,
+        min_rewards_per_second: u64,
+    ) -> Result<()> {
+        instructions::manager::set_sugar_rush_emission::set_sugar_rush_emission(
+            ctx,
+            mode,
+            rewards_per_second,
+            horizon_seconds,
+            min_rewards_per_second,
+        )
+    }
+
+    /// Configures the oracle price feed the collateral exchange rate tracks.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `price_feed`: The Switchboard pull feed, `Pubkey::default()` to use the fixed rate only.
+    /// - `min_exchange_rate`: The lowest exchange rate the oracle price may set.
+    /// - `max_exchange_rate`: The highest exchange rate the oracle price may set, at most 100.
+    /// - `max_staleness_slots`: The maximum age of the oracle price, in slots.
+    /// - `max_confidence_rate`: The maximum standard deviation, as a percentage of the price.
+    pub fn set_exchange_price_feed(
+        ctx: Context<SetExchangePriceFeed>,
+        price_feed: Pubkey,
+        min_exchange_rate: u8,
+        max_exchange_rate: u8,
+        max_staleness_slots: u64,
+        max_confidence_rate: u8,
+    ) -> Result<()> {
+        instructions::manager::set_exchange_price_feed::set_exchange_price_feed(
+            ctx,
+            price_feed,
+            min_exchange_rate,
+            max_exchange_rate,
+            max_staleness_slots,
+            max_confidence_rate,
+        )
+    }
+
+    /// Creates the next leaderboard page of a period, covering the scores right below the previous page.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `min_score`: The lowest score covered by the new page.
+    pub fn initialize_leaderboard_page(
+        ctx: Context<InitializeLeaderboardPage>,
+        min_score: u32,
+    ) -> Result<()> {
+        instructions::manager::initialize_leaderboard_page::initialize_leaderboard_page(
+            ctx, min_score,
+        )
+    }
+
+    /// Creates the stats account of today or a later day, recording the day's activity.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `day`: The day index (`timestamp / SECONDS_PER_DAY`).
+    pub fn initialize_daily_stats(ctx: Context<InitializeDailyStats>, day: u32) -> Result<()> {
+        instructions::manager::initialize_daily_stats::initialize_daily_stats(ctx, day)
+    }
+
+    /// Records the bot authority's heartbeat, keeping the backup authorities locked out.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn record_bot_heartbeat(ctx: Context<RecordBotHeartbeat>) -> Result<()> {
+        instructions::manager::record_bot_heartbeat::record_bot_heartbeat(ctx)
+    }
+
+    /// Sets the backup authorities standing in for the bot authority once its heartbeat is stale.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `backup_authorities`: The backup authorities, at most `MAX_BACKUP_AUTHORITIES`.
+    /// - `bot_heartbeat_timeout_seconds`: How long the bot may miss its heartbeat before the backups take over.
+    pub fn set_backup_authorities(
+        ctx: Context<SetBackupAuthorities>,
+        backup_authorities: Vec<Pubkey>,
+        bot_heartbeat_timeout_seconds: u64,
+    ) -> Result<()> {
+        instructions::manager::set_backup_authorities::set_backup_authorities(
+            ctx,
+            backup_authorities,
+            bot_heartbeat_timeout_seconds,
+        )
+    }
+
+    /// Records a player's current score on a leaderboard page, removing them if it left the page's bucket.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `player`: The public key of the player.
+    pub fn update_leaderboard_page(
+        ctx: Context<UpdateLeaderboardPage>,
+        player: Pubkey,
+    ) -> Result<()> {
+        instructions::manager::update_leaderboard_page::update_leaderboard_page(ctx, player)
+    }
+
+    /// Initializes a voucher account, allowing for tokenized representation of pool deposits.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn initialize_voucher(ctx: Context<InitializeVoucher>) -> Result<()> {
+        instructions::initialize_voucher::initialize_voucher(ctx)
+    }
+
+    /// Performs initial setup for the program, allocating necessary state and configuration.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `bot_authority`: The public key of the bot authority.
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        bot_authority: Pubkey,
+        round_rewards: u64,
+        period_rewards: u64,
+        registration_rewards: u64,
+        airdrop_rewards: u64,
+        exit_rewards: u64,
+        lottery_rewards: u64,
+        consumption_rewards: u64,
+        sugar_rush_rewards: u64,
+    ) -> Result<()> {
+        instructions::initialize::initialize(
+            ctx,
+            bot_authority,
+            round_rewards,
+            period_rewards,
+            registration_rewards,
+            airdrop_rewards,
+            exit_rewards,
+            lottery_rewards,
+            consumption_rewards,
+            sugar_rush_rewards,
+        )
+    }
+
+    /// Stakes a specified amount of tokens into the pool to earn ongoing rewards.
+    /// Fails once the player holds `MAX_ACTIVE_STAKE_ORDERS` (32) active stake orders.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `amount`: The amount of tokens to stake.
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        instructions::stake::stake(ctx, amount)
+    }
+
+    /// Deposits tokens through the deposit vault and stakes every whole shard of the game tokens received, in one call.
+    /// Fails once the player holds `MAX_ACTIVE_STAKE_ORDERS` (32) active stake orders.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `token_amount`: The amount of deposit tokens to deposit.
+    pub fn deposit_and_stake(ctx: Context<DepositAndStake>, token_amount: u64) -> Result<()> {
+        instructions::deposit_and_stake::deposit_and_stake(ctx, token_amount)
+    }
+
+    /// Requests an early unstake of previously staked tokens before the lock-up period ends, possibly incurring penalties.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `order_number`: The identifier of the stake order to be released early.
+    pub fn request_early_unstake(
+        ctx: Context<RequestEarlyUnstake>,
+        order_number: u16,
+    ) -> Result<()> {
+        instructions::stake::request_early_unstake::request_early_unstake(ctx, order_number)
+    }
+
+    /// Claims the principal and reduced rewards of an early-unstaked order once its early unlock duration has passed.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `order_number`: The identifier of the early-unstaked order to claim.
+    pub fn claim_early_unstake(ctx: Context<ClaimEarlyUnstake>, order_number: u16) -> Result<()> {
+        instructions::claim_early_unstake::claim_early_unstake(ctx, order_number)
+    }
+
+    /// Claims the interest accrued by a stake order since its last claim, leaving the principal staked.
+    /// Also pays a completed order the interest the pool could not pay when it was unstaked.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `order_number`: The identifier of the stake order.
+    pub fn claim_stake_interest(ctx: Context<ClaimStakeInterest>, order_number: u16) -> Result<()> {
+        instructions::claim_stake_interest::claim_stake_interest(ctx, order_number)
+    }
+
+    /// Unstakes tokens that have reached their required staking period and can now be withdrawn without penalty.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `order_number`: The identifier of the fully matured stake order to be withdrawn.
+    pub fn unstake(ctx: Context<Unstake>, order_number: u16) -> Result<()> {
+        instructions::unstake::unstake(ctx, order_number)
+    }
+
+    /// Completes matured stake orders on behalf of their owners, paying principal and rewards to their token accounts.
+    /// Anyone may run it, and is tipped per completed order from the maintenance pool.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context. The stake orders are passed through `remaining_accounts`.
+    pub fn unstake_matured_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UnstakeMaturedBatch<'info>>,
+    ) -> Result<()> {
+        instructions::unstake_matured_batch::unstake_matured_batch(ctx)
+    }
+
+    /// Recomputes the stake pool APR from its current utilization.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn update_stake_rates(ctx: Context<UpdateStakeRates>) -> Result<()> {
+        instructions::update_stake_rates::update_stake_rates(ctx)
+    }
+
+    /// Withdraws the principal of a stake order immediately while the stake pool is winding down, forfeiting its rewards.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `order_number`: The identifier of the stake order to withdraw.
+    pub fn emergency_unstake(ctx: Context<EmergencyUnstake>, order_number: u16) -> Result<()> {
+        instructions::emergency_unstake::emergency_unstake(ctx, order_number)
+    }
+
+    /// Stakes voucher tokens to earn token rewards, escrowing them in the stake pool's voucher vault.
+    /// Fails once the player holds `MAX_ACTIVE_STAKE_ORDERS` (32) active stake orders.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `amount`: The number of shards of vouchers to stake.
+    pub fn stake_vouchers(ctx: Context<StakeVouchers>, amount: u64) -> Result<()> {
+        instructions::stake_vouchers::stake_vouchers(ctx, amount)
+    }
+
+    /// Returns the escrowed vouchers of a voucher stake order, paying its token rewards once matured.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `order_number`: The identifier of the voucher stake order to withdraw.
+    pub fn unstake_vouchers(ctx: Context<UnstakeVouchers>, order_number: u16) -> Result<()> {
+        instructions::unstake_vouchers::unstake_vouchers(ctx, order_number)
+    }
+
+    /// Cancels the auto-reinvest setting for a player, stopping automatic compounding of earnings.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn cancel_is_auto_reinvesting(ctx: Context<CancelIsAutoReinvesting>) -> Result<()> {
+        instructions::cancel_is_auto_reinvesting::cancel_is_auto_reinvesting(ctx)
+    }
+
+    /// Candy tap is a function that allows players to tap into the game's rewards pool.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn candy_tap(ctx: Context<CandyTap>, last_active_participant: Pubkey) -> Result<()> {
+        instructions::candy_tap::candy_tap(ctx, last_active_participant)
+    }
+
+    /// Claims the rewards of the player's final rank on an ended period's player leaderboard.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `rank`: The 0-based rank held by the player.
+    pub fn claim_leaderboard_reward(ctx: Context<ClaimLeaderboardReward>, rank: u8) -> Result<()> {
+        instructions::claim_leaderboard_reward::claim_leaderboard_reward(ctx, rank)
+    }
+
+    /// Collects any available airdrop rewards for the player.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn collect_airdrop_rewards(ctx: Context<CollectAirdropRewards>) -> Result<()> {
+        instructions::collect_airdrop_rewards::collect_airdrop_rewards(ctx)
+    }
+
+    /// Collects rewards earned through player consumption or spending activities.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn collect_consumption_rewards(ctx: Context<CollectConsumptionRewards>) -> Result<()> {
+        instructions::collect_consumption_rewards::collect_consumption_rewards(ctx)
+    }
+
+    /// Exchanges collateral tokens into the corresponding in-game currency or resource.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `amount`: The amount of collateral to be exchanged.
+    pub fn collateral_exchange(ctx: Context<CollateralExchange>, amount: u64) -> Result<()> {
+        instructions::collateral_exchange::collateral_exchange(ctx, amount)
+    }
+
+    /// Collects referral rewards earned by inviting new participants to the platform.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `payout`: Collect the rewards as tokens, or as vouchers with a bonus.
+    pub fn collect_referral_rewards(
+        ctx: Context<CollectReferralRewards>,
+        payout: ReferralPayout,
+    ) -> Result<()> {
+        instructions::collect_referral_rewards::collect_referral_rewards(ctx, payout)
+    }
+
+    /// Collects the pending referral, consumption and airdrop rewards in one instruction, skipping those with nothing to collect.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `referral_payout`: Collect the referral rewards as tokens, or as vouchers with a bonus.
+    pub fn collect_all_rewards(
+        ctx: Context<CollectAllRewards>,
+        referral_payout: ReferralPayout,
+    ) -> Result<()> {
+        instructions::collect_all_rewards::collect_all_rewards(ctx, referral_payout)
+    }
+
+    /// Claims the player's share of a merkle airdrop, proven against its merkle root.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `index`: The leaf index of the claim in the airdrop's merkle tree.
+    /// - `amount`: The tokens of the claim.
+    /// - `proof`: The sibling hashes from the claim's leaf up to the root.
+    pub fn claim_merkle_airdrop(
+        ctx: Context<ClaimMerkleAirdrop>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_merkle_airdrop::claim_merkle_airdrop(ctx, index, amount, proof)
+    }
+
+    /// Locks vouchers on the wallet predicted to win the current round. Open to players and non-players
+    /// until the round reaches its climax.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `predicted_winner`: The wallet predicted to win the round.
+    /// - `amount`: The vouchers locked on the prediction.
+    pub fn place_prediction(
+        ctx: Context<PlacePrediction>,
+        predicted_winner: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::place_prediction::place_prediction(ctx, predicted_winner, amount)
+    }
+
+    /// Settles an ended round's prediction market on the round's winner. Callable by anyone.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn settle_predictions(ctx: Context<SettlePredictions>) -> Result<()> {
+        instructions::settle_predictions::settle_predictions(ctx)
+    }
+
+    /// Claims the payout of a prediction on a settled prediction market.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn claim_prediction(ctx: Context<ClaimPrediction>) -> Result<()> {
+        instructions::claim_prediction::claim_prediction(ctx)
+    }
+
+    /// Claims the vested part of a streamed first-place grand prize.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn claim_vested_prize(ctx: Context<ClaimVestedPrize>) -> Result<()> {
+        instructions::claim_vested_prize::claim_vested_prize(ctx)
+    }
+
+    /// Spends vouchers on a streak shield, preserving the player's purchase streak over one missed day.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn buy_streak_shield(ctx: Context<BuyStreakShield>) -> Result<()> {
+        instructions::buy_streak_shield::buy_streak_shield(ctx)
+    }
+
+    /// Authorizes a session signer to call the selected gameplay instructions on the player's behalf.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `session_signer`: The key allowed to sign on the player's behalf.
+    /// - `scopes`: The bitmask of `SESSION_SCOPE_*` instructions the session signer may call.
+    /// - `max_daily_ores`: The ORE the session signer may purchase per day.
+    /// - `token_allowance`: The tokens the session key may spend on purchases.
+    /// - `duration`: The validity of the session, in seconds.
+    pub fn create_session_key(
+        ctx: Context<CreateSessionKey>,
+        session_signer: Pubkey,
+        scopes: u8,
+        max_daily_ores: u32,
+        token_allowance: u64,
+        duration: u64,
+    ) -> Result<()> {
+        instructions::create_session_key::create_session_key(
+            ctx,
+            session_signer,
+            scopes,
+            max_daily_ores,
+            token_allowance,
+            duration,
+        )
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        instructions::deposit::deposit(ctx, amount)
+    }
+
+    /// Funds a reward pool with game tokens, recording the contribution on a deposit receipt.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `purpose`: The pool credited with the deposit.
+    /// - `receipt_number`: The number of the contributor's new receipt.
+    /// - `amount`: The amount of game tokens deposited.
+    pub fn deposit_to_pool(
+        ctx: Context<DepositToPool>,
+        purpose: DepositPurpose,
+        receipt_number: u32,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::deposit_to_pool::deposit_to_pool(ctx, purpose, receipt_number, amount)
+    }
+
+    /// Conducts a lottery draw on the given lottery table, committing to a randomness seed.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn draw_lottery(ctx: Context<DrawLottery>) -> Result<()> {
+        instructions::draw_lottery::draw_lottery(ctx)
+    }
+
+    /// Exits from the current game or round, potentially collecting any accrued exit rewards.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn exit(ctx: Context<Exit>) -> Result<()> {
+        instructions::exit::exit(ctx)
+    }
+
+    /// Registers a new player into the game, optionally associating them with a referrer.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `referrer`: The public key of the referrer (if any).
+    pub fn register(ctx: Context<Register>, referrer: Pubkey) -> Result<()> {
+        instructions::register::register(ctx, referrer)
+    }
+
+    /// Registers a new player whose account rent is paid by a sponsor.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `referrer`: The public key of the referring player.
+    pub fn register_sponsored(ctx: Context<RegisterSponsored>, referrer: Pubkey) -> Result<()> {
+        instructions::register_sponsored::register_sponsored(ctx, referrer)
+    }
+
+    /// Purchases a specified quantity of in-game assets or lottery entries.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `purchase_quantity`: The number of units or tickets to purchase.
+    pub fn purchase(ctx: Context<Purchase>, purchase_quantity: u32) -> Result<()> {
+        instructions::purchase::purchase(ctx, purchase_quantity)
+    }
+
+    /// Purchases ORE paying entirely in tokens, without the voucher accounts.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `purchase_quantity`: The number of ORE to purchase.
+    pub fn purchase_with_tokens(
+        ctx: Context<PurchaseWithTokens>,
+        purchase_quantity: u32,
+    ) -> Result<()> {
+        instructions::purchase_with_tokens::purchase_with_tokens(ctx, purchase_quantity)
+    }
+
+    /// Purchases ORE with SOL, swapping it for game tokens through the whitelisted AMM pool first.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context. The swap accounts are passed through `remaining_accounts`.
+    /// - `purchase_quantity`: The number of ORE to purchase.
+    /// - `sol_amount`: The lamports to swap.
+    /// - `min_token_amount`: The minimum game tokens the swap must return.
+    /// - `swap_data`: The AMM swap instruction data.
+    pub fn purchase_with_sol<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PurchaseWithSol<'info>>,
+        purchase_quantity: u32,
+        sol_amount: u64,
+        min_token_amount: u64,
+        swap_data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::purchase_with_sol::purchase_with_sol(
+            ctx,
+            purchase_quantity,
+            sol_amount,
+            min_token_amount,
+            swap_data,
+        )
+    }
+
+    /// Redeems a round position token, merging its ORE into the holder's holdings or settling it once the round is over.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn redeem_position(ctx: Context<RedeemPosition>) -> Result<()> {
+        instructions::redeem_position::redeem_position(ctx)
+    }
+
+    /// Redeems vouchers for the underlying tokens, net of the voucher redemption fee.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `amount`: The amount of vouchers to redeem.
+    pub fn redeem_vouchers(ctx: Context<RedeemVouchers>, amount: u64) -> Result<()> {
+        instructions::redeem_vouchers::redeem_vouchers(ctx, amount)
+    }
+
+    /// Re-enters the current round after exiting it, paying the re-entry fee into the grand prize pool.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn reenter_round(ctx: Context<ReenterRound>) -> Result<()> {
+        instructions::reenter_round::reenter_round(ctx)
+    }
+
+    /// Reinvests a player's claims or accrued rewards back into the game environment.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn reinvest(ctx: Context<Reinvest>) -> Result<()> {
+        instructions::reinvest::reinvest(ctx)
+    }
+
+    /// Reveals the outcome of the previously drawn lottery, finalizing the results on-chain.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn reveal_draw_lottery_result(ctx: Context<RevealDrawLotteryResult>) -> Result<()> {
+        instructions::reveal_draw_lottery_result::reveal_draw_lottery_result(ctx)
+    }
+
+    /// Returns the return to player accounting of a lottery table as return data.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn get_lottery_stats(ctx: Context<GetLotteryStats>) -> Result<LotteryStats> {
+        instructions::get_lottery_stats::get_lottery_stats(ctx)
+    }
+
+    /// Returns a player's state, purchase streak and airdrop eligibility as return data.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn view_player_summary(ctx: Context<ViewPlayerSummary>) -> Result<PlayerSummary> {
+        instructions::view_player_summary::view_player_summary(ctx)
+    }
+
+    /// Returns a round's phase, countdown and earnings per ORE as return data.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn view_round_summary(ctx: Context<ViewRoundSummary>) -> Result<RoundSummary> {
+        instructions::view_round_summary::view_round_summary(ctx)
+    }
+
+    /// Returns the rewards a player could collect, including those accrued since their last settlement, as return data.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn view_pending_rewards(ctx: Context<ViewPendingRewards>) -> Result<PendingRewards> {
+        instructions::view_pending_rewards::view_pending_rewards(ctx)
+    }
+
+    /// Sets the caller's daily limits on ORE purchases and lottery draws. Looser limits take effect after a cooldown.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `daily_ores`: The ORE the player may purchase per day, zero for unlimited.
+    /// - `daily_lottery_draws`: The lottery draws the player may make per day, zero for unlimited.
+    pub fn set_spend_limit(
+        ctx: Context<SetSpendLimit>,
+        daily_ores: u32,
+        daily_lottery_draws: u32,
+    ) -> Result<()> {
+        instructions::set_spend_limit::set_spend_limit(ctx, daily_ores, daily_lottery_draws)
+    }
+
+    /// Excludes the caller from ORE purchases and lottery draws until a given time.
+    /// Shortening the exclusion cannot end it before a cooldown has passed.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `until_timestamp`: The UNIX timestamp until which the player is excluded.
+    pub fn self_exclude(ctx: Context<SelfExclude>, until_timestamp: u64) -> Result<()> {
+        instructions::self_exclude::self_exclude(ctx, until_timestamp)
+    }
+
+    /// Enables automatic reinvestment for a player, compounding their returns without manual intervention.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn set_is_auto_reinvesting(ctx: Context<SetIsAutoReinvesting>) -> Result<()> {
+        instructions::set_is_auto_reinvesting::set_is_auto_reinvesting(ctx)
+    }
+
+    /// Revokes the player's session key and its token delegation.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+        instructions::revoke_session_key::revoke_session_key(ctx)
+    }
+
+    /// Assigns a referrer to a player, establishing referral relationships.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `referrer`: The public key of the referrer.
+    pub fn set_referrer(ctx: Context<SetReferrer>, referrer: Pubkey) -> Result<()> {
+        instructions::set_referrer::set_referrer(ctx, referrer)
+    }
+
+    /// Settles the previous round, finalizing and distributing any outstanding rewards.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `carry_over`: Whether to carry the construction rewards over to the player's next purchase instead of paying them out.
+    pub fn settle_previous_round(
+        ctx: Context<SettlePreviousRound>,
+        carry_over: bool,
+    ) -> Result<()> {
+        instructions::settle_previous_round::settle_previous_round(ctx, carry_over)
+    }
+
+    /// Settles the player's finished round and purchases ORE in the current round in one step.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `purchased_ores`: Number of ORE to purchase.
+    pub fn settle_and_purchase(ctx: Context<SettleAndPurchase>, purchased_ores: u32) -> Result<()> {
+        instructions::settle_and_purchase::settle_and_purchase(ctx, purchased_ores)
+    }
+
+    /// Detaches ORE from the player's holdings in the current round into a transferable position token.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `ores`: The amount of ORE to tokenize.
+    pub fn tokenize_position(ctx: Context<TokenizePosition>, ores: u32) -> Result<()> {
+        instructions::tokenize_position::tokenize_position(ctx, ores)
+    }
+
+    /// Re-keys a custodial player account to the user's own wallet, unlocked by the account's claim code.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `claim_code`: The claim code handed to the user at registration.
+    pub fn upgrade_to_self_custody(
+        ctx: Context<UpgradeToSelfCustody>,
+        claim_code: Vec<u8>,
+    ) -> Result<()> {
+        instructions::upgrade_to_self_custody::upgrade_to_self_custody(ctx, claim_code)
+    }
+
+    /// Proves the player's rank in their current period from the leaderboard pages.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context. The leaderboard pages above the player's page are passed through `remaining_accounts`.
+    pub fn verify_leaderboard_rank<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyLeaderboardRank<'info>>,
+    ) -> Result<u32> {
+        instructions::verify_leaderboard_rank::verify_leaderboard_rank(ctx)
+    }
+
+    /// Accepts a player's application to join a team, officially adding them to the team.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `applicant`: The public key of the player requesting to join.
+    pub fn accept_team_application(
+        ctx: Context<AcceptTeamApplication>,
+        applicant: Pubkey,
+    ) -> Result<()> {
+        instructions::accept_team_application::accept_team_application(ctx, applicant)
+    }
+
+    /// Allows a player to apply to join an existing team, pending acceptance by the team management.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn apply_to_join_team(ctx: Context<ApplyToJoinTeam>) -> Result<()> {
+        instructions::apply_to_join_team::apply_to_join_team(ctx)
+    }
+
+    /// Claims the rewards of the team's final rank on an ended period's team leaderboard into the team vault.
+    /// Ranks held by teams merged into this one are claimed with their `TeamMerge` records.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context, with the `TeamMerge` records leading from the rank holder in `remaining_accounts`.
+    /// - `rank`: The 0-based rank held by the team.
+    pub fn claim_team_leaderboard_reward(
+        ctx: Context<ClaimTeamLeaderboardReward>,
+        rank: u8,
+    ) -> Result<()> {
+        instructions::claim_team_leaderboard_reward::claim_team_leaderboard_reward(ctx, rank)
+    }
+
+    /// Pays a team the bonus of an ended period's team quest, if it reached the quest's goal. Callable by anyone.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn finalize_team_quest(ctx: Context<FinalizeTeamQuest>) -> Result<()> {
+        instructions::finalize_team_quest::finalize_team_quest(ctx)
+    }
+
+    /// Creates a new team, enabling a group of players to form a team with collective goals and rewards.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn create_team(ctx: Context<CreateTeam>) -> Result<()> {
+        instructions::create_team::create_team(ctx)
+    }
+
+    /// Distributes team-level rewards to a specific team member.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `member`: The public key of the team member receiving rewards.
+    /// - `reward_amount`: The amount of rewards to distribute.
+    pub fn distribute_team_rewards(
+        ctx: Context<DistributeTeamRewards>,
+        member: Pubkey,
+        reward_amount: u64,
+    ) -> Result<()> {
+        instructions::distribute_team_rewards::distribute_team_rewards(ctx, member, reward_amount)
+    }
+
+    /// Proposes a distribution of team rewards to a specific team member, as required for large team vaults.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `member`: The public key of the team member receiving rewards.
+    /// - `reward_amount`: The amount of rewards to distribute.
+    pub fn propose_team_distribution(
+        ctx: Context<ProposeTeamDistribution>,
+        member: Pubkey,
+        reward_amount: u64,
+    ) -> Result<()> {
+        instructions::propose_team_distribution::propose_team_distribution(
+            ctx,
+            member,
+            reward_amount,
+        )
+    }
+
+    /// Executes a proposed team reward distribution, approved by the game authority or after its veto period.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn execute_team_distribution(ctx: Context<ExecuteTeamDistribution>) -> Result<()> {
+        instructions::execute_team_distribution::execute_team_distribution(ctx)
+    }
+
+    /// Vetoes a proposed team reward distribution during its veto period, as one of the team managers at the time it was proposed.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn veto_team_distribution(ctx: Context<VetoTeamDistribution>) -> Result<()> {
+        instructions::veto_team_distribution::veto_team_distribution(ctx)
+    }
+
+    /// Distributes the team rewards of periods paying in vouchers to a specific team member, as vouchers.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `member`: The public key of the team member receiving rewards.
+    /// - `reward_amount`: The amount of rewards to distribute.
+    pub fn distribute_team_voucher_rewards(
+        ctx: Context<DistributeTeamVoucherRewards>,
+        member: Pubkey,
+        reward_amount: u64,
+    ) -> Result<()> {
+        instructions::distribute_team_voucher_rewards::distribute_team_voucher_rewards(
+            ctx,
+            member,
+            reward_amount,
+        )
+    }
+
+    /// Grants manager-level privileges within the team to a specific member, allowing them to manage membership and rewards.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `member`: The public key of the member to be granted manager privileges.
+    pub fn grant_manager_privileges(
+        ctx: Context<GrantManagerPrivileges>,
+        member: Pubkey,
+    ) -> Result<()> {
+        instructions::grant_manager_privileges::grant_manager_privileges(ctx, member)
+    }
+
+    /// Allows a member to voluntarily leave a team.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn leave_team(ctx: Context<LeaveTeam>) -> Result<()> {
+        instructions::leave_team::leave_team(ctx)
+    }
+
+    /// Merges the absorbed team into the surviving team, both captains co-signing.
+    /// The surviving team can then claim the absorbed team's ranks in ended periods through the merge record.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context, with the player data of every absorbed member in `remaining_accounts`.
+    pub fn merge_teams<'info>(ctx: Context<'_, '_, 'info, 'info, MergeTeams<'info>>) -> Result<()> {
+        instructions::merge_teams::merge_teams(ctx)
+    }
+
+    /// Rejects a team application from a particular applicant.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `applicant`: The public key of the applicant to reject.
+    pub fn reject_team_application(
+        ctx: Context<RejectTeamApplication>,
+        applicant: Pubkey,
+    ) -> Result<()> {
+        instructions::reject_team_application::reject_team_application(ctx, applicant)
+    }
+
+    /// Removes a member from a team, revoking their participation and any associated privileges.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `member_to_remove`: The public key of the member to be removed.
+    pub fn remove_member_from_team(
+        ctx: Context<RemoveMemberFromTeam>,
+        member_to_remove: Pubkey,
+    ) -> Result<()> {
+        instructions::remove_member_from_team::remove_member_from_team(ctx, member_to_remove)
+    }
+
+    /// Revokes previously granted manager privileges from a specific team member.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `manager`: The public key of the member whose manager privileges will be revoked.
+    pub fn revoke_manager_privileges(
+        ctx: Context<RevokeManagerPrivileges>,
+        manager: Pubkey,
+    ) -> Result<()> {
+        instructions::revoke_manager_privileges::revoke_manager_privileges(ctx, manager)
+    }
+
+    /// Transfers the role of team captain to another member.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `member`: The public key of the member to become the new captain.
+    pub fn transfer_team_captaincy(
+        ctx: Context<TransferTeamCaptaincy>,
+        member: Pubkey,
+    ) -> Result<()> {
+        instructions::transfer_team_captaincy::transfer_team_captaincy(ctx, member)
+    }
+
+    /// Claims the captaincy of a team whose captain went inactive, as its longest-tenured manager,
+    /// or as any member after twice the inactivity window.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn claim_captaincy(ctx: Context<ClaimCaptaincy>) -> Result<()> {
+        instructions::claim_captaincy::claim_captaincy(ctx)
+    }
+
+    /// Escrows vouchers into a new marketplace listing offered at a fixed price in game tokens.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `listing_number`: The number of the seller's new listing.
+    /// - `voucher_amount`: The amount of vouchers listed.
+    /// - `price`: The price in game tokens asked for the listing, fee included.
+    pub fn create_listing(
+        ctx: Context<CreateListing>,
+        listing_number: u32,
+        voucher_amount: u64,
+        price: u64,
+    ) -> Result<()> {
+        instructions::create_listing::create_listing(ctx, listing_number, voucher_amount, price)
+    }
+
+    /// Buys the vouchers of a marketplace listing, paying the seller and the protocol fee.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn fill_listing(ctx: Context<FillListing>) -> Result<()> {
+        instructions::fill_listing::fill_listing(ctx)
+    }
+
+    /// Cancels a marketplace listing, refunding the escrowed vouchers to the seller.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        instructions::cancel_listing::cancel_listing(ctx)
+    }
+
+    /// Escrows a tokenized round position into a new marketplace listing offered at a fixed price in game tokens.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `listing_number`: The number of the seller's new listing.
+    /// - `price`: The price in game tokens asked for the listing, fee included.
+    pub fn create_position_listing(
+        ctx: Context<CreatePositionListing>,
+        listing_number: u32,
+        price: u64,
+    ) -> Result<()> {
+        instructions::create_position_listing::create_position_listing(ctx, listing_number, price)
+    }
+
+    /// Buys the round position of a marketplace listing, paying the seller and the protocol fee.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn fill_position_listing(ctx: Context<FillPositionListing>) -> Result<()> {
+        instructions::fill_position_listing::fill_position_listing(ctx)
+    }
+
+    /// Cancels a marketplace listing of a round position, refunding the position token to the seller.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn cancel_position_listing(ctx: Context<CancelPositionListing>) -> Result<()> {
+        instructions::cancel_position_listing::cancel_position_listing(ctx)
+    }
+
+    /// Puts a game parameter change to the vote of the community, snapshotting the voting power.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    /// - `action`: The parameter change applied if the proposal passes.
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: GovernanceAction) -> Result<()> {
+        instructions::create_proposal::create_proposal(ctx, action)
+    }
+
+    /// Votes on a governance proposal with the voting power at its snapshot.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context. The voter's stake orders are passed through `remaining_accounts`.
+    /// - `is_in_favor`: Whether the vote is in favor of the proposal.
+    pub fn cast_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CastVote<'info>>,
+        is_in_favor: bool,
+    ) -> Result<()> {
+        instructions::cast_vote::cast_vote(ctx, is_in_favor)
+    }
+
+    /// Applies the parameter change of a passed governance proposal once its timelock has ended.
+    ///
+    /// # Parameters
+    /// - `ctx`: Execution context.
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        instructions::execute_proposal::execute_proposal(ctx)
     }
 }
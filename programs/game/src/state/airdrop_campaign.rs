@@ -0,0 +1,227 @@
+use crate::constants::AIRDROP_CAMPAIGN_SEED;
+use crate::errors::ErrorCode;
+use crate::state::PlayerData;
+use crate::utils::{calculate_airdrop_rewards, VaultAuthority};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// The `AirdropCampaign` account funds one airdrop from its own vault, with its own budget, daily cap,
+/// eligibility rule and time window, so several airdrops can run in parallel or by season.
+/// Players collect its rewards as vouchers, at most once a day, recorded on their `AirdropClaim`.
+///
+/// # Fields
+/// - `campaign_id`: The identifier of the campaign, seeding its PDA.
+/// - `campaign_vault`: The token account holding the campaign's budget, owned by the campaign.
+/// - `eligibility`: The `AirdropEligibility` rule a player must meet to collect the campaign's rewards.
+/// - `rewards_per_claim`: The rewards of a claim under the fixed reward rules.
+/// - `daily_cap`: The rewards the campaign distributes per day at most.
+/// - `start_time`, `end_time`: The window in which the rewards may be collected.
+/// - `funded_amount`: The cumulative tokens deposited into the campaign's vault.
+/// - `distributed_amount`: The cumulative rewards collected from the campaign.
+/// - `current_day`, `current_day_distributed_amount`: The day of the last claim and the rewards collected on it.
+/// - `is_enabled`: Whether the rewards may be collected.
+/// - `bump`: The PDA bump seed of the campaign.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct AirdropCampaign {
+    pub campaign_id: u16,
+    pub campaign_vault: Pubkey,
+    pub eligibility: AirdropEligibility,
+    pub rewards_per_claim: u64,
+    pub daily_cap: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub funded_amount: u64,
+    pub distributed_amount: u64,
+    pub current_day: u32,
+    pub current_day_distributed_amount: u64,
+    pub is_enabled: bool,
+    pub bump: u8,
+}
+
+/// The eligibility rule, rewards, daily cap and window of an `AirdropCampaign`, see its fields.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AirdropCampaignParams {
+    pub eligibility: AirdropEligibility,
+    pub rewards_per_claim: u64,
+    pub daily_cap: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+/// Who may collect an airdrop campaign's rewards, once a day, and how much.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum AirdropEligibility {
+    /// Players who purchased ORE today, rewarded by their purchase streak as `calculate_airdrop_rewards`.
+    PurchaseStreak,
+    /// Players who purchased ORE today, rewarded `rewards_per_claim`.
+    DailyPurchase,
+    /// Every registered player, rewarded `rewards_per_claim`.
+    DailyCheckIn,
+}
+
+impl AirdropCampaign {
+    /// Initializes an enabled campaign with an empty budget.
+    ///
+    /// # Arguments
+    /// - `campaign_id`: The identifier of the campaign.
+    /// - `campaign_vault`: The token account holding the campaign's budget.
+    /// - `params`: The eligibility rule, rewards, daily cap and window of the campaign.
+    /// - `bump`: The PDA bump seed.
+    pub fn initialize(
+        &mut self,
+        campaign_id: u16,
+        campaign_vault: Pubkey,
+        params: &AirdropCampaignParams,
+        bump: u8,
+    ) -> Result<()> {
+        require!(
+            params.daily_cap > 0
+                && params.start_time < params.end_time
+                && (params.rewards_per_claim > 0
+                    || params.eligibility == AirdropEligibility::PurchaseStreak),
+            ErrorCode::InvalidAirdropCampaign
+        );
+
+        *self = AirdropCampaign {
+            campaign_id,
+            campaign_vault,
+            eligibility: params.eligibility,
+            rewards_per_claim: params.rewards_per_claim,
+            daily_cap: params.daily_cap,
+            start_time: params.start_time,
+            end_time: params.end_time,
+            funded_amount: 0,
+            distributed_amount: 0,
+            current_day: 0,
+            current_day_distributed_amount: 0,
+            is_enabled: true,
+            bump,
+        };
+        Ok(())
+    }
+
+    /// Records `amount` tokens deposited into the campaign's vault.
+    pub fn fund(&mut self, amount: u64) -> Result<()> {
+        self.funded_amount = self.funded_amount.safe_add(amount)?;
+        Ok(())
+    }
+
+    /// The tokens left in the campaign's budget.
+    pub fn remaining_budget(&self) -> Result<u64> {
+        Ok(self.funded_amount.safe_sub(self.distributed_amount)?)
+    }
+
+    /// Returns the rewards the player may collect from the campaign today, or why they may not.
+    ///
+    /// # Arguments
+    /// - `player_data`: The player collecting the rewards.
+    /// - `last_claimed_day`: The day the player last collected the campaign's rewards.
+    /// - `current_day`: Today's day index.
+    /// - `current_time`: The current UNIX timestamp.
+    pub fn claimable_rewards(
+        &self,
+        player_data: &PlayerData,
+        last_claimed_day: u32,
+        current_day: u32,
+        current_time: u64,
+    ) -> std::result::Result<u64, ErrorCode> {
+        if !self.is_enabled || current_time < self.start_time || self.end_time <= current_time {
+            return Err(ErrorCode::AirdropCampaignNotActive);
+        }
+        if last_claimed_day == current_day {
+            return Err(ErrorCode::AirdropRewardsAlreadyCollected);
+        }
+
+        let has_purchased_today = player_data.last_purchased_day == current_day;
+        match self.eligibility {
+            AirdropEligibility::PurchaseStreak if has_purchased_today => Ok(
+                calculate_airdrop_rewards(player_data.consecutive_purchased_days),
+            ),
+            AirdropEligibility::DailyPurchase if has_purchased_today => Ok(self.rewards_per_claim),
+            AirdropEligibility::DailyCheckIn => Ok(self.rewards_per_claim),
+            _ => Err(ErrorCode::AirdropRewardsNotAvailable),
+        }
+    }
+
+    /// Records `amount` rewards collected today, within the campaign's daily cap and remaining budget.
+    ///
+    /// # Arguments
+    /// - `amount`: The rewards collected.
+    /// - `current_day`: Today's day index.
+    pub fn record_claim(&mut self, amount: u64, current_day: u32) -> Result<()> {
+        // If a new day has started, reset the daily distributed amount
+        if current_day > self.current_day {
+            self.current_day = current_day;
+            self.current_day_distributed_amount = 0;
+        }
+
+        let new_daily_total = self.current_day_distributed_amount.safe_add(amount)?;
+        require!(
+            new_daily_total <= self.daily_cap,
+            ErrorCode::ExceedsDailyAirdropCap
+        );
+        require!(
+            amount <= self.remaining_budget()?,
+            ErrorCode::InsufficientAirdropRewardBalance
+        );
+
+        self.current_day_distributed_amount = new_daily_total;
+        self.distributed_amount = self.distributed_amount.safe_add(amount)?;
+        Ok(())
+    }
+}
+
+impl VaultAuthority for AirdropCampaign {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[
+            AIRDROP_CAMPAIGN_SEED,
+            &self.campaign_id.to_le_bytes(),
+            &[bump],
+        ])
+    }
+}
+
+/// The `AirdropClaim` account records a player's claims on one `AirdropCampaign`, limiting them to one a day.
+///
+/// # Fields
+/// - `airdrop_campaign`: The campaign claimed from.
+/// - `player`: The player claiming.
+/// - `last_claimed_day`: The day of the player's last claim.
+/// - `claimed_amount`: The cumulative rewards the player collected from the campaign.
+/// - `bump`: The PDA bump seed of the claim record.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct AirdropClaim {
+    pub airdrop_campaign: Pubkey,
+    pub player: Pubkey,
+    pub last_claimed_day: u32,
+    pub claimed_amount: u64,
+    pub bump: u8,
+}
+
+impl AirdropClaim {
+    /// Records a claim of `amount` rewards today, binding a newly created claim record to its campaign and player.
+    ///
+    /// # Arguments
+    /// - `airdrop_campaign`: The campaign claimed from.
+    /// - `player`: The player claiming.
+    /// - `amount`: The rewards collected.
+    /// - `current_day`: Today's day index.
+    /// - `bump`: The PDA bump seed.
+    pub fn record_claim(
+        &mut self,
+        airdrop_campaign: Pubkey,
+        player: Pubkey,
+        amount: u64,
+        current_day: u32,
+        bump: u8,
+    ) -> Result<()> {
+        self.airdrop_campaign = airdrop_campaign;
+        self.player = player;
+        self.last_claimed_day = current_day;
+        self.claimed_amount = self.claimed_amount.safe_add(amount)?;
+        self.bump = bump;
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// The `BanEntry` account bans a wallet from the game, so that a wallet abusing an exploit is stopped while a fix
+/// ships. Its existence is the ban: instructions rejecting banned wallets take the wallet's ban entry address and
+/// fail with `PlayerBanned` unless the account is empty. The game authority creates it with `ban_player`, and
+/// closes it with `unban_player`.
+///
+/// # Fields
+/// - `wallet`: The banned wallet.
+/// - `reason_code`: An operator-defined code for the reason of the ban.
+/// - `banned_timestamp`: The UNIX timestamp the wallet was banned at.
+/// - `bump`: The PDA bump seed of the ban entry.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct BanEntry {
+    pub wallet: Pubkey,
+    pub reason_code: u16,
+    pub banned_timestamp: u64,
+    pub bump: u8,
+}
+
+impl BanEntry {
+    /// Bans `wallet` from `timestamp`.
+    pub fn initialize(&mut self, wallet: Pubkey, reason_code: u16, timestamp: u64, bump: u8) {
+        *self = BanEntry {
+            wallet,
+            reason_code,
+            banned_timestamp: timestamp,
+            bump,
+        };
+    }
+
+    /// Fails with `PlayerBanned` if `ban_entry`, the ban entry address of a wallet, holds a ban.
+    /// Only this program can allocate the address, so an empty account means the wallet is not banned.
+    pub fn require_not_banned(ban_entry: &AccountInfo) -> Result<()> {
+        require!(ban_entry.data_is_empty(), ErrorCode::PlayerBanned);
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// The `ClaimCode` account lets the user behind a custodial player account take it over with their own wallet.
+/// It is created by `register_custodial` with the hash of a claim code handed to the user off-chain, and
+/// consumed by `upgrade_to_self_custody`, which only succeeds when the matching claim code is presented.
+///
+/// # Fields
+/// - `player`: The custodial player the claim code unlocks.
+/// - `claim_code_hash`: The SHA-256 hash of the claim code.
+/// - `bump`: The PDA bump seed of the claim code.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct ClaimCode {
+    pub player: Pubkey,
+    pub claim_code_hash: [u8; 32],
+    pub bump: u8,
+}
@@ -0,0 +1,93 @@
+use crate::errors::ErrorCode;
+use crate::utils::timestamp_to_days;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// The `DailyStats` account accumulates the global activity of one day, so the basic daily KPIs
+/// can be read on-chain without an indexer. It is created ahead of its day by anyone, and the hot paths
+/// update it when the day's account is passed to them.
+///
+/// # Fields
+/// - `day`: The day index (`timestamp / SECONDS_PER_DAY`), seeding the PDA.
+/// - `purchase_count`, `purchased_ores`: The purchases made and the ORE they bought.
+/// - `purchase_volume`: The cost of those purchases, in token lamports, whether paid in tokens or vouchers.
+/// - `unique_buyers`: The players whose first purchase of the day was recorded.
+/// - `burned_vouchers`: The vouchers spent on purchases and lottery draws.
+/// - `lottery_draw_count`, `lottery_volume`: The lottery draws and their cost, in voucher lamports.
+/// - `stake_count`, `staked_tokens`: The token stake orders created and the tokens staked.
+/// - `bump`: The PDA bump seed.
+#[account]
+#[derive(Debug, Default, InitSpace)]
+pub struct DailyStats {
+    pub day: u32,
+    pub purchase_count: u32,
+    pub purchased_ores: u64,
+    pub purchase_volume: u64,
+    pub unique_buyers: u32,
+    pub burned_vouchers: u64,
+    pub lottery_draw_count: u32,
+    pub lottery_volume: u64,
+    pub stake_count: u32,
+    pub staked_tokens: u64,
+    pub bump: u8,
+}
+
+impl DailyStats {
+    /// Initializes the stats of `day`.
+    pub fn initialize(&mut self, day: u32, bump: u8) -> Result<()> {
+        *self = DailyStats {
+            day,
+            bump,
+            ..Default::default()
+        };
+        Ok(())
+    }
+
+    /// Checks the stats are those of the day of `timestamp`.
+    pub fn require_day(&self, timestamp: u64) -> Result<()> {
+        require!(
+            self.day == timestamp_to_days(timestamp)?,
+            ErrorCode::DailyStatsDayMismatch
+        );
+        Ok(())
+    }
+
+    /// Records a purchase.
+    ///
+    /// # Arguments
+    /// - `purchased_ores`: The ORE bought.
+    /// - `total_cost`: The cost of the purchase, in token lamports.
+    /// - `voucher_cost`: The part of the cost paid with vouchers, which are burned.
+    /// - `is_first_purchase_of_day`: Whether it is the player's first purchase of the day.
+    pub fn record_purchase(
+        &mut self,
+        purchased_ores: u32,
+        total_cost: u64,
+        voucher_cost: u64,
+        is_first_purchase_of_day: bool,
+    ) -> Result<()> {
+        self.purchase_count = self.purchase_count.safe_add(1)?;
+        self.purchased_ores = self.purchased_ores.safe_add(purchased_ores as u64)?;
+        self.purchase_volume = self.purchase_volume.safe_add(total_cost)?;
+        self.burned_vouchers = self.burned_vouchers.safe_add(voucher_cost)?;
+        if is_first_purchase_of_day {
+            self.unique_buyers = self.unique_buyers.safe_add(1)?;
+        }
+        Ok(())
+    }
+
+    /// Records a lottery draw paid with `voucher_cost` burned vouchers.
+    pub fn record_lottery_draw(&mut self, voucher_cost: u64) -> Result<()> {
+        self.lottery_draw_count = self.lottery_draw_count.safe_add(1)?;
+        self.lottery_volume = self.lottery_volume.safe_add(voucher_cost)?;
+        self.burned_vouchers = self.burned_vouchers.safe_add(voucher_cost)?;
+        Ok(())
+    }
+
+    /// Records a token stake order of `stake_amount`.
+    pub fn record_stake(&mut self, stake_amount: u64) -> Result<()> {
+        self.stake_count = self.stake_count.safe_add(1)?;
+        self.staked_tokens = self.staked_tokens.safe_add(stake_amount)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+/// The `DepositReceipt` account is the contributor's verifiable record of a `deposit_to_pool` call.
+/// One receipt is created per deposit, derived from the contributor and a receipt number of their choice.
+///
+/// # Fields
+/// - `contributor`: The public key of the contributor who signed and paid for the deposit.
+/// - `receipt_number`: The number distinguishing the contributor's receipts.
+/// - `purpose`: The `DepositPurpose` selecting the pool credited.
+/// - `pool`: The account holding the credited pool balance (the round, the game or the stake pool).
+/// - `vault`: The token vault the deposit was transferred to.
+/// - `amount`: The amount deposited.
+/// - `timestamp`: The UNIX timestamp of the deposit.
+/// - `bump`: The PDA bump seed of the receipt.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct DepositReceipt {
+    pub contributor: Pubkey,
+    pub receipt_number: u32,
+    pub purpose: DepositPurpose,
+    pub pool: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub timestamp: u64,
+    pub bump: u8,
+}
+
+/// The pool a deposit is credited to.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+#[repr(u8)]
+pub enum DepositPurpose {
+    /// Adds to the grand prize pool of the current round, held by the round vault.
+    FundRound = 0,
+    /// Adds to the game's period rewards pool, funding future leaderboard periods.
+    FundPeriod = 1,
+    /// Adds to the stake pool's token rewards, increasing its capacity to pay interest.
+    FundStakeRewards = 2,
+    /// Adds to the game's round rewards pool, seeding the grand prizes of future rounds.
+    Donation = 3,
+    /// Adds to the game's maintenance pool, funding the tips of the permissionless maintenance cranks.
+    FundMaintenance = 4,
+}
@@ -0,0 +1,58 @@
+use crate::events::TransferEvent;
+use anchor_lang::prelude::*;
+
+/// The number of records kept by the `EventRingBuffer` before the oldest ones are overwritten.
+pub const EVENT_RING_BUFFER_CAPACITY: usize = 128;
+
+/// A compact record of an emitted `TransferEvent`.
+///
+/// # Fields
+/// - `timestamp`: The UNIX timestamp of the event.
+/// - `initiator`: The public key of the entity that triggered the event.
+/// - `event_nonce`: The global event nonce, letting clients detect gaps and order records.
+/// - `event_tag`: The stable `event_tag` of the event type.
+#[zero_copy]
+#[derive(Debug, Default)]
+pub struct EventRecord {
+    pub timestamp: u64,
+    pub initiator: Pubkey,
+    pub event_nonce: u32,
+    pub event_tag: u16,
+    pub _padding: [u8; 2],
+}
+
+/// The `EventRingBuffer` account keeps the most recent events on-chain in a fixed-size, zero-copy buffer.
+/// Light clients can read it to recover events missed from the logs without running a full-history indexer.
+///
+/// # Fields
+/// - `head`: The index at which the next record is written.
+/// - `len`: The number of records currently stored, up to `EVENT_RING_BUFFER_CAPACITY`.
+/// - `records`: The stored records; once full, each new record overwrites the oldest one.
+#[account(zero_copy)]
+pub struct EventRingBuffer {
+    pub head: u32,
+    pub len: u32,
+    pub records: [EventRecord; EVENT_RING_BUFFER_CAPACITY],
+}
+
+impl EventRingBuffer {
+    /// Appends a record of the event, overwriting the oldest record once the buffer is full.
+    ///
+    /// # Arguments
+    /// - `event`: The event being emitted.
+    pub fn push(&mut self, event: &TransferEvent) -> Result<()> {
+        self.records[self.head as usize] = EventRecord {
+            timestamp: event.timestamp,
+            initiator: event.initiator,
+            event_nonce: event.event_nonce,
+            event_tag: event.event_tag,
+            _padding: [0; 2],
+        };
+
+        // `head` and `len` never exceed the capacity, so the increments cannot overflow
+        self.head = (self.head + 1) % EVENT_RING_BUFFER_CAPACITY as u32;
+        self.len = (self.len + 1).min(EVENT_RING_BUFFER_CAPACITY as u32);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use crate::errors::ErrorCode;
+use crate::utils::calculate_oracle_rate;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// The highest exchange rate, minting one voucher per exchanged token. The voucher vault backs every voucher
+/// with one token, which `redeem_vouchers`, purchases and the stake pool decommission release, so no rate may
+/// mint more vouchers than the tokens deposited.
+pub const MAX_EXCHANGE_RATE: u8 = 100;
+
+#[account]
+#[derive(Debug, Default, InitSpace)]
+/// The `ExchangeConfig` account holds the parameters of the collateral exchange, letting the authority
+/// manage the token-to-voucher peg and the exchange limits without redeploying the program.
+pub struct ExchangeConfig {
+    /// The percentage of the exchanged tokens minted as vouchers (100 = 1:1), at most `MAX_EXCHANGE_RATE`.
+    /// Used as the fallback fixed rate while the price feed is unset, stale or not confident enough.
+    pub exchange_rate: u8,
+
+    /// The maximum amount of tokens a single player may exchange per day. Zero disables the limit.
+    pub daily_player_limit: u64,
+
+    /// The maximum amount of tokens that may be exchanged in total. Zero disables the cap.
+    pub global_cap: u64,
+
+    /// The cumulative amount of tokens exchanged for vouchers.
+    pub total_exchanged_amount: u64,
+
+    /// The Switchboard pull feed quoting the collateral in vouchers. `Pubkey::default()` disables oracle pricing.
+    pub price_feed: Pubkey,
+
+    /// The lowest exchange rate the oracle price may set.
+    pub min_exchange_rate: u8,
+
+    /// The highest exchange rate the oracle price may set, at most `MAX_EXCHANGE_RATE`.
+    pub max_exchange_rate: u8,
+
+    /// The maximum age of the oracle price, in slots.
+    pub max_staleness_slots: u64,
+
+    /// The maximum standard deviation of the oracle submissions, as a percentage of the price.
+    pub max_confidence_rate: u8,
+}
+
+impl ExchangeConfig {
+    /// Initializes the exchange configuration.
+    ///
+    /// # Arguments
+    /// - `exchange_rate`: The percentage of the exchanged tokens minted as vouchers.
+    /// - `daily_player_limit`: The per-player daily exchange limit, zero for unlimited.
+    /// - `global_cap`: The total exchange cap, zero for unlimited.
+    pub fn initialize(
+        &mut self,
+        exchange_rate: u8,
+        daily_player_limit: u64,
+        global_cap: u64,
+    ) -> Result<()> {
+        *self = ExchangeConfig::default();
+
+        self.update(exchange_rate, daily_player_limit, global_cap)
+    }
+
+    /// Updates the exchange rate and limits, leaving the exchanged total untouched.
+    ///
+    /// # Arguments
+    /// - `exchange_rate`: The percentage of the exchanged tokens minted as vouchers.
+    /// - `daily_player_limit`: The per-player daily exchange limit, zero for unlimited.
+    /// - `global_cap`: The total exchange cap, zero for unlimited.
+    pub fn update(
+        &mut self,
+        exchange_rate: u8,
+        daily_player_limit: u64,
+        global_cap: u64,
+    ) -> Result<()> {
+        require!(
+            exchange_rate > 0 && exchange_rate <= MAX_EXCHANGE_RATE,
+            ErrorCode::InvalidExchangeRate
+        );
+
+        self.exchange_rate = exchange_rate;
+        self.daily_player_limit = daily_player_limit;
+        self.global_cap = global_cap;
+
+        Ok(())
+    }
+
+    /// Records an exchange against the global cap.
+    ///
+    /// # Arguments
+    /// - `amount`: The amount of tokens being exchanged.
+    pub fn record_exchange(&mut self, amount: u64) -> Result<()> {
+        let total_exchanged_amount = self.total_exchanged_amount.safe_add(amount)?;
+        require!(
+            self.global_cap == 0 || total_exchanged_amount <= self.global_cap,
+            ErrorCode::ExceedsExchangeGlobalCap
+        );
+        self.total_exchanged_amount = total_exchanged_amount;
+
+        Ok(())
+    }
+
+    /// Configures the oracle price feed and the bounds within which it may move the exchange rate.
+    ///
+    /// # Arguments
+    /// - `price_feed`: The Switchboard pull feed account, `Pubkey::default()` to disable oracle pricing.
+    /// - `min_exchange_rate`: The lowest exchange rate the oracle price may set.
+    /// - `max_exchange_rate`: The highest exchange rate the oracle price may set, at most `MAX_EXCHANGE_RATE`.
+    /// - `max_staleness_slots`: The maximum age of the oracle price, in slots.
+    /// - `max_confidence_rate`: The maximum standard deviation, as a percentage of the price.
+    pub fn set_price_feed(
+        &mut self,
+        price_feed: Pubkey,
+        min_exchange_rate: u8,
+        max_exchange_rate: u8,
+        max_staleness_slots: u64,
+        max_confidence_rate: u8,
+    ) -> Result<()> {
+        require!(
+            min_exchange_rate > 0
+                && min_exchange_rate <= max_exchange_rate
+                && max_exchange_rate <= MAX_EXCHANGE_RATE,
+            ErrorCode::InvalidExchangeRateBounds
+        );
+
+        self.price_feed = price_feed;
+        self.min_exchange_rate = min_exchange_rate;
+        self.max_exchange_rate = max_exchange_rate;
+        self.max_staleness_slots = max_staleness_slots;
+        self.max_confidence_rate = max_confidence_rate;
+
+        Ok(())
+    }
+
+    /// Whether the exchange rate is derived from the oracle price feed.
+    pub fn is_oracle_priced(&self) -> bool {
+        self.price_feed != Pubkey::default()
+    }
+
+    /// Derives the exchange rate from an oracle price, clamped to the configured bounds.
+    ///
+    /// # Arguments
+    /// - `price`: The oracle price, scaled by `10^precision`.
+    /// - `std_dev`: The standard deviation of the oracle submissions, with the same scale.
+    /// - `precision`: The number of decimals of the oracle price.
+    /// - `price_slot`: The slot at which the oracle price was signed.
+    /// - `current_slot`: The current slot.
+    ///
+    /// # Returns
+    /// The oracle exchange rate, or `None` if the price is stale or not confident enough.
+    pub fn oracle_exchange_rate(
+        &self,
+        price: i128,
+        std_dev: i128,
+        precision: u32,
+        price_slot: u64,
+        current_slot: u64,
+    ) -> Option<u8> {
+        if price_slot == 0 || current_slot.saturating_sub(price_slot) > self.max_staleness_slots {
+            return None;
+        }
+
+        let rate = calculate_oracle_rate(price, std_dev, precision, self.max_confidence_rate)?;
+        let rate = rate.clamp(self.min_exchange_rate as u64, self.max_exchange_rate as u64);
+
+        Some(rate as u8)
+    }
+}
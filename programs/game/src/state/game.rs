@@ -1,11 +1,35 @@
 use crate::constants::{
-    DAILY_AIRDROP_REWARDS_CAP, DEFAULT_PERIOD_NUMBER, DEFAULT_ROUND_NUMBER, DEFAULT_TEAM_NUMBER,
-    EXIT_REWARDS_PER_SECOND, REGISTRATION_REWARD, SUGAR_RUSH_REWARDS_PER_SECOND,
-    TEAM_JOIN_COOLDOWN_SECONDS,
+    BOT_HEARTBEAT_TIMEOUT_SECONDS, CANDY_TAP_COOLDOWN_SECONDS, CANDY_TAP_MIN_INTERVAL_SECONDS,
+    CAPTAIN_INACTIVITY_SECONDS, COMPLIANCE_ATTESTATION_MAX_AGE_SECONDS,
+    CONSUMPTION_REWARD_TIER_COUNT, DAILY_AIRDROP_REWARDS_CAP, DEFAULT_PERIOD_NUMBER,
+    DEFAULT_ROUND_NUMBER, DEFAULT_TEAM_NUMBER, EXIT_REWARDS_PER_SECOND, GAME_SEED,
+    MAX_BACKUP_AUTHORITIES, MAX_START_TIME_DELAY, MIN_START_LEAD_TIME, REENTRY_FEE,
+    REGISTRATION_REWARD, SUGAR_RUSH_ESCALATION_DELAY_SECONDS, SUGAR_RUSH_ESCALATION_RATE,
+    SUGAR_RUSH_MAX_MULTIPLIER, SUGAR_RUSH_REWARDS_PER_SECOND, TEAM_APPLICATIONS_PER_DAY,
+    TEAM_DISTRIBUTION_APPROVAL_THRESHOLD, TEAM_DISTRIBUTION_VETO_SECONDS,
+    TEAM_JOIN_COOLDOWN_SECONDS, TEAM_REAPPLICATION_COOLDOWN_SECONDS,
+};
+use crate::errors::ErrorCode;
+use crate::state::PlayerData;
+use crate::utils::{
+    calculate_crank_tip, calculate_registration_rewards, calculate_sugar_rush_emission_rate,
+    ed25519_attested_message, is_bot_heartbeat_stale, is_ed25519_attested, VaultAuthority,
 };
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 
+/// Flag gating `purchase` and its variants behind a compliance attestation.
+pub const COMPLIANCE_GATE_PURCHASE: u8 = 1 << 0;
+
+/// Flag gating `draw_lottery` behind a compliance attestation.
+pub const COMPLIANCE_GATE_DRAW_LOTTERY: u8 = 1 << 1;
+
+/// Union of all compliance gate flags.
+pub const COMPLIANCE_GATE_ALL: u8 = COMPLIANCE_GATE_PURCHASE | COMPLIANCE_GATE_DRAW_LOTTERY;
+
+/// The size of a compliance attestation message: the player's key, then the issuance UNIX timestamp in little-endian.
+pub const COMPLIANCE_ATTESTATION_MESSAGE_SIZE: usize = 40;
+
 /// The `Game` account holds the global state of the entire platform. It tracks all
 /// key parameters and balances, including reward pool balances, default entities,
 /// and various nonces for PDA derivations. This structure is crucial for maintaining
@@ -13,21 +37,58 @@ use anchor_safe_math::SafeMath;
 ///
 /// # Fields
 /// - `authority`: The public key authorized to manage key aspects of the game (e.g., initializing rounds, updating configs).
+/// - `bot_authority`: The public key running the bot-only instructions (cranks, auto reinvest, distributions).
+/// - `backup_authorities`: Up to `MAX_BACKUP_AUTHORITIES` keys authorized for the failover-enabled bot-only instructions
+///   while the bot's `last_bot_heartbeat` is older than `bot_heartbeat_timeout_seconds`. Unused slots hold the default key.
 /// - `token_mint`: The public key of the token mint that represents the in-game currency.
 /// - `game_vault`: A vault account holding game funds. It stores tokens used for rewards and payouts.
 /// - `default_team`: A default team entity assigned to newly registered players before they join or create their own team.
 /// - `default_player`: A default player profile assigned during initial player creation.
 /// - `current_round`: The public key of the currently active round in the game.
 /// - `current_period`: The public key of the currently active leaderboard period.
+/// - `previous_period`: The period `current_period` replaced, whose purchased ores are the snapshot of governance voting power.
+/// - `swap_program`, `swap_pool`: The whitelisted AMM program and pool through which `purchase_with_sol` swaps SOL for game tokens.
+/// - `badge_merkle_tree`, `is_badge_enabled`: The Bubblegum merkle tree, delegated to the game account, into which
+///   `purchase` mints a compressed NFT badge on a player's first purchase in a round, and whether it does.
 /// - `current_day`: The current day index, computed as a timestamp-based day number (e.g., `timestamp / 86400`).
 /// - Various pool balances for different reward categories (mining, bonus, lottery, developer, referral, registration, airdrop, consumption, exit).
 ///   These track available resources to be distributed to players and teams.
+/// - `insurance_pool_balance`: Funded by a slice of every purchase, covering shortfalls of the grand prize, construction and exit pools.
+/// - `carryover_pool_balance`: The construction rewards players carried over from a settled round, released by their next purchase.
+/// - `maintenance_pool_balance`: Funded by deposits, paying the tips of the permissionless maintenance cranks.
 /// - Various counters (`distributed_*_rewards`) tracking the total amount of distributed rewards per category, aiding in analytics and caps enforcement.
+/// - `total_burned_tokens`, `total_burned_vouchers`: The game tokens and vouchers burned across every burn site, tracking the deflation of the supply.
+/// - `expired_referral_rewards`: The referral rewards left uncollected past their expiry and swept back to the referral pool,
+///   which still holds them but no longer owes them to any referrer.
 /// - `current_day_distributed_airdrop_rewards`: Keeps track of how much airdrop reward has been distributed today to ensure it does not exceed the daily cap.
 /// - `current_day_cap_airdrop_rewards`: The daily airdrop cap, usually set to `DAILY_AIRDROP_REWARDS_CAP`.
-/// - `registration_rewards`: The fixed amount allocated for each player registration.
+/// - `registration_rewards`: The registration rewards of the first players, usually `REGISTRATION_REWARD`.
+/// - `registration_reward_decay_interval`, `registration_reward_decay_amount`, `registration_reward_floor`: The schedule
+///   decreasing the registration rewards by `registration_reward_decay_amount` every time another `registration_reward_decay_interval`
+///   of them has been distributed, down to `registration_reward_floor`. A zero interval keeps them fixed.
+/// - `registration_gate`, `registration_min_deposit`, `registration_attestor`: The `RegistrationGate` a new player must pass
+///   to receive the registration rewards, the token balance required by `MinDeposit` and the key signing the attestations of `Attestation`.
+/// - `consumption_reward_tiers`: The `ConsumptionRewardTier`s raising a player's consumption rewards rate with their
+///   purchase volume over the last 30 days, by ascending volume; unused tiers are left zeroed at the end.
 /// - `remaining_registration_slots`: How many registration rewards are still available to new players, enabling a limited incentive system.
-/// - `team_nonce`, `round_nonce`, `period_nonce`: Incrementing counters used for PDA (Program Derived Address) derivation to ensure uniqueness of program accounts.
+/// - `max_future_start_seconds`, `min_start_lead_seconds`: The window, relative to now, in which rounds and periods may be scheduled to start.
+/// - `sugar_rush_escalation_delay_seconds`, `sugar_rush_escalation_rate`, `sugar_rush_max_multiplier`: The curve escalating the sugar rush rewards the longer no one taps candy.
+/// - `sugar_rush_emission_mode`, `sugar_rush_emission_horizon_seconds`, `sugar_rush_min_rewards_per_second`: The
+///   `SugarRushEmissionMode` setting the sugar rush rewards per second, and under `Proportional` the time the remaining
+///   sugar rush pool is emitted over and the lowest rate, `sugar_rush_rewards_per_second` then being the highest.
+/// - `reentry_fee`: The fee a player pays into the round's grand prize pool to re-enter a round they exited.
+/// - `default_winner_policy`: The `DefaultWinnerPolicy` applied to the grand prizes and leaderboard rewards won by the default player or team.
+/// - `candy_tap_cooldown_seconds`, `candy_tap_min_interval_seconds`: The wait between two candy taps of a player, and between two candy taps in a round.
+/// - `max_team_applications_per_day`, `team_reapplication_cooldown_seconds`: The team applications a player may submit per day,
+///   and the wait before a player may apply again to a team that rejected them.
+/// - `captain_inactivity_seconds`: The inactivity of a captain after which their longest-tenured manager may claim
+///   the captaincy, any member being able to after twice as long.
+/// - `team_distribution_approval_threshold`, `team_distribution_veto_seconds`: The team vault balance above which team
+///   reward distributions must be proposed, and the veto period a proposal waits for unless the game authority approves it.
+/// - `compliance_signer`, `compliance_gated_instructions`, `compliance_attestation_max_age_seconds`: The key signing the
+///   eligibility attestations of geo-gated deployments, the `COMPLIANCE_GATE_*` flags of the instructions requiring one,
+///   and how long an attestation stays valid. The default key disables the gating.
+/// - `team_nonce`, `round_nonce`, `period_nonce`, `proposal_nonce`: Incrementing counters used for PDA (Program Derived Address) derivation to ensure uniqueness of program accounts.
 #[account]
 #[derive(Debug, Default, InitSpace)]
 pub struct Game {
@@ -91,6 +152,67 @@ pub struct Game {
     pub round_nonce: u16,
     pub period_nonce: u16,
     pub current_day: u32,
+
+    // Fields appended since the first deployment, converted by `migrate_game`. New fields go at the end.
+    pub backup_authorities: [Pubkey; MAX_BACKUP_AUTHORITIES],
+    pub previous_period: Pubkey,
+
+    // Whitelisted swap used by `purchase_with_sol`
+    pub swap_program: Pubkey,
+    pub swap_pool: Pubkey,
+
+    // Round participation badges minted by `purchase`
+    pub badge_merkle_tree: Pubkey,
+    pub is_badge_enabled: bool,
+
+    pub insurance_pool_balance: u64,
+    pub carryover_pool_balance: u64,
+    pub maintenance_pool_balance: u64,
+
+    pub total_burned_tokens: u64,
+    pub total_burned_vouchers: u64,
+    pub expired_referral_rewards: u64,
+
+    pub registration_reward_decay_interval: u64,
+    pub registration_reward_decay_amount: u64,
+    pub registration_reward_floor: u64,
+    pub registration_gate: u8,
+    pub registration_min_deposit: u64,
+    pub registration_attestor: Pubkey,
+    // Consumption reward tiers by purchase volume
+    pub consumption_reward_tiers: [ConsumptionRewardTier; CONSUMPTION_REWARD_TIER_COUNT],
+
+    pub sugar_rush_escalation_delay_seconds: u64,
+    pub sugar_rush_escalation_rate: u16,
+    pub sugar_rush_max_multiplier: u16,
+    pub sugar_rush_emission_mode: u8,
+    pub sugar_rush_emission_horizon_seconds: u64,
+    pub sugar_rush_min_rewards_per_second: u64,
+
+    pub max_team_applications_per_day: u8,
+    pub team_reapplication_cooldown_seconds: u64,
+    pub captain_inactivity_seconds: u64,
+    pub team_distribution_approval_threshold: u64,
+    pub team_distribution_veto_seconds: u64,
+    pub compliance_signer: Pubkey,
+    pub compliance_gated_instructions: u8,
+    pub compliance_attestation_max_age_seconds: u64,
+    pub reentry_fee: u64,
+    pub default_winner_policy: u8,
+
+    // Start time window of rounds and periods
+    pub max_future_start_seconds: u64,
+    pub min_start_lead_seconds: u64,
+
+    // Candy tap throttle
+    pub candy_tap_cooldown_seconds: u64,
+    pub candy_tap_min_interval_seconds: u64,
+
+    // Bot failover
+    pub last_bot_heartbeat: u64,
+    pub bot_heartbeat_timeout_seconds: u64,
+
+    pub proposal_nonce: u32,
 }
 
 impl Game {
@@ -129,7 +251,22 @@ impl Game {
             registration_rewards: REGISTRATION_REWARD,
             sugar_rush_rewards_per_second: SUGAR_RUSH_REWARDS_PER_SECOND,
             exit_rewards_per_second: EXIT_REWARDS_PER_SECOND,
+            sugar_rush_escalation_delay_seconds: SUGAR_RUSH_ESCALATION_DELAY_SECONDS,
+            sugar_rush_escalation_rate: SUGAR_RUSH_ESCALATION_RATE,
+            sugar_rush_max_multiplier: SUGAR_RUSH_MAX_MULTIPLIER,
             team_join_cooldown_seconds: TEAM_JOIN_COOLDOWN_SECONDS,
+            max_team_applications_per_day: TEAM_APPLICATIONS_PER_DAY,
+            team_reapplication_cooldown_seconds: TEAM_REAPPLICATION_COOLDOWN_SECONDS,
+            captain_inactivity_seconds: CAPTAIN_INACTIVITY_SECONDS,
+            team_distribution_approval_threshold: TEAM_DISTRIBUTION_APPROVAL_THRESHOLD,
+            team_distribution_veto_seconds: TEAM_DISTRIBUTION_VETO_SECONDS,
+            compliance_attestation_max_age_seconds: COMPLIANCE_ATTESTATION_MAX_AGE_SECONDS,
+            reentry_fee: REENTRY_FEE,
+            max_future_start_seconds: MAX_START_TIME_DELAY,
+            min_start_lead_seconds: MIN_START_LEAD_TIME,
+            candy_tap_cooldown_seconds: CANDY_TAP_COOLDOWN_SECONDS,
+            candy_tap_min_interval_seconds: CANDY_TAP_MIN_INTERVAL_SECONDS,
+            bot_heartbeat_timeout_seconds: BOT_HEARTBEAT_TIMEOUT_SECONDS,
             current_day_cap_airdrop_rewards: DAILY_AIRDROP_REWARDS_CAP,
 
             lottery_rewards_pool_balance: lottery_rewards,
@@ -149,6 +286,532 @@ impl Game {
         Ok(())
     }
 
+    /// Whitelists the AMM program and pool used to swap SOL for game tokens.
+    ///
+    /// # Arguments
+    /// - `swap_program`: The AMM program, `Pubkey::default()` to disable SOL purchases.
+    /// - `swap_pool`: The AMM pool trading wrapped SOL against the game token.
+    pub fn set_swap_pool(&mut self, swap_program: Pubkey, swap_pool: Pubkey) -> Result<()> {
+        self.swap_program = swap_program;
+        self.swap_pool = swap_pool;
+        Ok(())
+    }
+
+    /// Sets the merkle tree of the round participation badges and whether `purchase` mints them.
+    ///
+    /// # Arguments
+    /// - `badge_merkle_tree`: The Bubblegum merkle tree, whose tree delegate must be the game account.
+    /// - `is_badge_enabled`: Whether badges are minted on a player's first purchase in a round.
+    pub fn set_round_badge(
+        &mut self,
+        badge_merkle_tree: Pubkey,
+        is_badge_enabled: bool,
+    ) -> Result<()> {
+        self.badge_merkle_tree = badge_merkle_tree;
+        self.is_badge_enabled = is_badge_enabled;
+        Ok(())
+    }
+
+    /// Sets the window, relative to now, in which rounds and periods may be scheduled to start.
+    ///
+    /// # Arguments
+    /// - `min_start_lead_seconds`: The minimum delay before a start time.
+    /// - `max_future_start_seconds`: The maximum delay before a start time, at least `min_start_lead_seconds`.
+    pub fn set_start_time_window(
+        &mut self,
+        min_start_lead_seconds: u64,
+        max_future_start_seconds: u64,
+    ) -> Result<()> {
+        require!(
+            min_start_lead_seconds <= max_future_start_seconds,
+            ErrorCode::InvalidStartTimeWindow
+        );
+        self.min_start_lead_seconds = min_start_lead_seconds;
+        self.max_future_start_seconds = max_future_start_seconds;
+        Ok(())
+    }
+
+    /// Sets the curve escalating the sugar rush rewards the longer no one taps candy.
+    ///
+    /// # Arguments
+    /// - `sugar_rush_escalation_delay_seconds`: The idle time before the rewards start escalating.
+    /// - `sugar_rush_escalation_rate`: The growth of the multiplier per idle minute (in basis points).
+    /// - `sugar_rush_max_multiplier`: The highest multiplier (in basis points), at least 1x.
+    pub fn set_sugar_rush_escalation(
+        &mut self,
+        sugar_rush_escalation_delay_seconds: u64,
+        sugar_rush_escalation_rate: u16,
+        sugar_rush_max_multiplier: u16,
+    ) -> Result<()> {
+        require!(
+            sugar_rush_max_multiplier >= 100,
+            ErrorCode::InvalidSugarRushEscalation
+        );
+        self.sugar_rush_escalation_delay_seconds = sugar_rush_escalation_delay_seconds;
+        self.sugar_rush_escalation_rate = sugar_rush_escalation_rate;
+        self.sugar_rush_max_multiplier = sugar_rush_max_multiplier;
+        Ok(())
+    }
+
+    /// Sets the limits on team applications.
+    ///
+    /// # Arguments
+    /// - `max_team_applications_per_day`: The team applications a player may submit per day, at least one.
+    /// - `team_reapplication_cooldown_seconds`: The wait before a player may apply again to a team that rejected them.
+    pub fn set_team_application_limits(
+        &mut self,
+        max_team_applications_per_day: u8,
+        team_reapplication_cooldown_seconds: u64,
+    ) -> Result<()> {
+        require!(
+            max_team_applications_per_day > 0,
+            ErrorCode::InvalidTeamApplicationLimits
+        );
+        self.max_team_applications_per_day = max_team_applications_per_day;
+        self.team_reapplication_cooldown_seconds = team_reapplication_cooldown_seconds;
+        Ok(())
+    }
+
+    /// Sets the inactivity of a captain after which the captaincy of their team may be claimed.
+    pub fn set_captain_inactivity_window(&mut self, captain_inactivity_seconds: u64) -> Result<()> {
+        require!(
+            captain_inactivity_seconds > 0,
+            ErrorCode::InvalidCaptainInactivityWindow
+        );
+        self.captain_inactivity_seconds = captain_inactivity_seconds;
+        Ok(())
+    }
+
+    /// Sets the approval required by team reward distributions out of large team vaults.
+    ///
+    /// # Arguments
+    /// - `team_distribution_approval_threshold`: The team vault balance above which distributions must be proposed.
+    /// - `team_distribution_veto_seconds`: The veto period a proposal waits for unless the game authority approves it.
+    pub fn set_team_distribution_approval(
+        &mut self,
+        team_distribution_approval_threshold: u64,
+        team_distribution_veto_seconds: u64,
+    ) -> Result<()> {
+        require!(
+            team_distribution_veto_seconds > 0,
+            ErrorCode::InvalidTeamDistributionApproval
+        );
+        self.team_distribution_approval_threshold = team_distribution_approval_threshold;
+        self.team_distribution_veto_seconds = team_distribution_veto_seconds;
+        Ok(())
+    }
+
+    /// Checks if team reward distributions out of a team vault holding `team_vault_balance` must be proposed.
+    pub fn requires_team_distribution_approval(&self, team_vault_balance: u64) -> bool {
+        team_vault_balance > self.team_distribution_approval_threshold
+    }
+
+    /// Sets the compliance attestation gating selected instructions.
+    ///
+    /// # Arguments
+    /// - `signer`: The key signing the attestations, the default key disabling the gating.
+    /// - `gated_instructions`: The `COMPLIANCE_GATE_*` flags of the instructions requiring an attestation.
+    /// - `max_age_seconds`: How long an attestation stays valid after being issued, which may not be zero.
+    pub fn set_compliance_attestation(
+        &mut self,
+        signer: Pubkey,
+        gated_instructions: u8,
+        max_age_seconds: u64,
+    ) -> Result<()> {
+        require!(
+            gated_instructions & !COMPLIANCE_GATE_ALL == 0 && max_age_seconds > 0,
+            ErrorCode::InvalidComplianceAttestation
+        );
+        self.compliance_signer = signer;
+        self.compliance_gated_instructions = gated_instructions;
+        self.compliance_attestation_max_age_seconds = max_age_seconds;
+        Ok(())
+    }
+
+    /// Checks that `player` proves their eligibility when the instruction flagged `gate` is gated.
+    ///
+    /// The transaction must verify, in the ed25519 program instruction preceding the gated one, a signature of
+    /// `compliance_signer` over a compliance attestation message for `player`, issued at most
+    /// `compliance_attestation_max_age_seconds` ago.
+    ///
+    /// # Arguments
+    /// - `gate`: The `COMPLIANCE_GATE_*` flag of the instruction.
+    /// - `player`: The player acting.
+    /// - `instructions_sysvar`: The instructions sysvar, required to verify an attestation.
+    /// - `timestamp`: The current UNIX timestamp.
+    pub fn require_compliance_attestation(
+        &self,
+        gate: u8,
+        player: &Pubkey,
+        instructions_sysvar: Option<&AccountInfo>,
+        timestamp: u64,
+    ) -> Result<()> {
+        if self.compliance_signer == Pubkey::default()
+            || self.compliance_gated_instructions & gate == 0
+        {
+            return Ok(());
+        }
+
+        let message = match instructions_sysvar {
+            Some(instructions_sysvar) => {
+                ed25519_attested_message(instructions_sysvar, &self.compliance_signer)?
+            }
+            None => None,
+        }
+        .ok_or(ErrorCode::ComplianceAttestationRequired)?;
+        require!(
+            message.len() == COMPLIANCE_ATTESTATION_MESSAGE_SIZE
+                && message[..32] == player.as_ref()[..],
+            ErrorCode::ComplianceAttestationRequired
+        );
+
+        let mut issued_timestamp = [0u8; 8];
+        issued_timestamp.copy_from_slice(&message[32..]);
+        let issued_timestamp = u64::from_le_bytes(issued_timestamp);
+        require!(
+            issued_timestamp <= timestamp
+                && timestamp - issued_timestamp <= self.compliance_attestation_max_age_seconds,
+            ErrorCode::ComplianceAttestationExpired
+        );
+        Ok(())
+    }
+
+    /// Sets the controller emitting the sugar rush rewards.
+    ///
+    /// # Arguments
+    /// - `mode`: How the sugar rush rewards per second are set.
+    /// - `rewards_per_second`: The rate under `Fixed`, and the highest rate under `Proportional`.
+    /// - `horizon_seconds`: Under `Proportional`, the time the remaining sugar rush pool is emitted over.
+    /// - `min_rewards_per_second`: Under `Proportional`, the lowest rate, at most `rewards_per_second`.
+    pub fn set_sugar_rush_emission(
+        &mut self,
+        mode: SugarRushEmissionMode,
+        rewards_per_second: u64,
+        horizon_seconds: u64,
+        min_rewards_per_second: u64,
+    ) -> Result<()> {
+        if mode == SugarRushEmissionMode::Proportional {
+            require!(
+                horizon_seconds > 0 && min_rewards_per_second <= rewards_per_second,
+                ErrorCode::InvalidSugarRushEmission
+            );
+        }
+        self.sugar_rush_emission_mode = mode as u8;
+        self.sugar_rush_rewards_per_second = rewards_per_second;
+        self.sugar_rush_emission_horizon_seconds = horizon_seconds;
+        self.sugar_rush_min_rewards_per_second = min_rewards_per_second;
+        Ok(())
+    }
+
+    /// Returns how the sugar rush rewards per second are set.
+    pub fn sugar_rush_emission_mode(&self) -> SugarRushEmissionMode {
+        match self.sugar_rush_emission_mode {
+            1 => SugarRushEmissionMode::Proportional,
+            _ => SugarRushEmissionMode::Fixed,
+        }
+    }
+
+    /// Returns the sugar rush rewards per second in effect, following the emission controller.
+    pub fn sugar_rush_emission_rate(&self) -> Result<u64> {
+        match self.sugar_rush_emission_mode() {
+            SugarRushEmissionMode::Fixed => Ok(self.sugar_rush_rewards_per_second),
+            SugarRushEmissionMode::Proportional => Ok(calculate_sugar_rush_emission_rate(
+                self.sugar_rush_rewards_pool_balance,
+                self.sugar_rush_emission_horizon_seconds,
+                self.sugar_rush_min_rewards_per_second,
+                self.sugar_rush_rewards_per_second,
+            )?),
+        }
+    }
+
+    /// Sets the fee paid into the grand prize pool to re-enter a round after exiting it.
+    ///
+    /// # Arguments
+    /// - `reentry_fee`: The re-entry fee in token lamports.
+    pub fn set_reentry_fee(&mut self, reentry_fee: u64) -> Result<()> {
+        self.reentry_fee = reentry_fee;
+        Ok(())
+    }
+
+    /// Sets what happens to the rewards won by the default player or team.
+    ///
+    /// # Arguments
+    /// - `policy`: The policy applied to the grand prizes and leaderboard rewards nobody can claim.
+    pub fn set_default_winner_policy(&mut self, policy: DefaultWinnerPolicy) -> Result<()> {
+        self.default_winner_policy = policy as u8;
+        Ok(())
+    }
+
+    /// Returns what happens to the rewards won by the default player or team.
+    pub fn default_winner_policy(&self) -> DefaultWinnerPolicy {
+        match self.default_winner_policy {
+            1 => DefaultWinnerPolicy::RollToNextPeriod,
+            2 => DefaultWinnerPolicy::GrandPrize,
+            3 => DefaultWinnerPolicy::Insurance,
+            _ => DefaultWinnerPolicy::Burn,
+        }
+    }
+
+    /// Credits the rewards won by the default player or team, once moved to the game vault,
+    /// to the pool selected by `policy`. Burned rewards credit no pool.
+    ///
+    /// # Arguments
+    /// - `policy`: The policy applied to the rewards.
+    /// - `amount`: The rewards moved to the game vault.
+    pub fn credit_default_winner_rewards(
+        &mut self,
+        policy: DefaultWinnerPolicy,
+        amount: u64,
+    ) -> Result<()> {
+        match policy {
+            DefaultWinnerPolicy::Burn => {}
+            DefaultWinnerPolicy::RollToNextPeriod => {
+                self.period_rewards_pool_balance =
+                    self.period_rewards_pool_balance.safe_add(amount)?;
+            }
+            DefaultWinnerPolicy::GrandPrize => {
+                self.round_rewards_pool_balance =
+                    self.round_rewards_pool_balance.safe_add(amount)?;
+            }
+            DefaultWinnerPolicy::Insurance => {
+                self.insurance_pool_balance = self.insurance_pool_balance.safe_add(amount)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the schedule of the registration rewards.
+    ///
+    /// # Arguments
+    /// - `initial_rewards`: The registration rewards of the first players.
+    /// - `decay_interval`: The registration rewards distributed between two decreases, zero for fixed rewards.
+    /// - `decay_amount`: The decrease of the registration rewards at every interval.
+    /// - `floor_rewards`: The registration rewards the schedule never decays below, at most `initial_rewards`.
+    pub fn set_registration_reward_schedule(
+        &mut self,
+        initial_rewards: u64,
+        decay_interval: u64,
+        decay_amount: u64,
+        floor_rewards: u64,
+    ) -> Result<()> {
+        require!(
+            floor_rewards <= initial_rewards,
+            ErrorCode::InvalidRegistrationRewardSchedule
+        );
+
+        self.registration_rewards = initial_rewards;
+        self.registration_reward_decay_interval = decay_interval;
+        self.registration_reward_decay_amount = decay_amount;
+        self.registration_reward_floor = floor_rewards;
+        Ok(())
+    }
+
+    /// Sets the tiers of the consumption rewards rate by purchase volume.
+    ///
+    /// # Arguments
+    /// - `tiers`: At most `CONSUMPTION_REWARD_TIER_COUNT` tiers by strictly ascending `min_purchase_volume`,
+    ///   each with a rate between 1% and 100%.
+    pub fn set_consumption_reward_tiers(&mut self, tiers: &[ConsumptionRewardTier]) -> Result<()> {
+        require!(
+            tiers.len() <= CONSUMPTION_REWARD_TIER_COUNT
+                && tiers
+                    .iter()
+                    .all(|tier| tier.is_used() && tier.consumption_rate <= 100)
+                && tiers
+                    .windows(2)
+                    .all(|pair| pair[0].min_purchase_volume < pair[1].min_purchase_volume),
+            ErrorCode::InvalidConsumptionRewardTiers
+        );
+
+        self.consumption_reward_tiers =
+            [ConsumptionRewardTier::default(); CONSUMPTION_REWARD_TIER_COUNT];
+        self.consumption_reward_tiers[..tiers.len()].copy_from_slice(tiers);
+        Ok(())
+    }
+
+    /// Returns the registration rewards of the next new player, decayed by those already distributed.
+    pub fn current_registration_rewards(&self) -> Result<u64> {
+        Ok(calculate_registration_rewards(
+            self.registration_rewards,
+            self.distributed_registration_rewards,
+            self.registration_reward_decay_interval,
+            self.registration_reward_decay_amount,
+            self.registration_reward_floor,
+        )?)
+    }
+
+    /// Sets the gate a new player must pass to receive the registration rewards.
+    ///
+    /// # Arguments
+    /// - `gate`: The registration gate.
+    /// - `min_deposit`: The token balance required by `RegistrationGate::MinDeposit`, which may not be zero with it.
+    /// - `attestor`: The key signing the attestations of `RegistrationGate::Attestation`, which may not be the default key with it.
+    pub fn set_registration_gate(
+        &mut self,
+        gate: RegistrationGate,
+        min_deposit: u64,
+        attestor: Pubkey,
+    ) -> Result<()> {
+        match gate {
+            RegistrationGate::MinDeposit => {
+                require!(min_deposit > 0, ErrorCode::InvalidRegistrationGate)
+            }
+            RegistrationGate::Attestation => {
+                require!(
+                    attestor != Pubkey::default(),
+                    ErrorCode::InvalidRegistrationGate
+                )
+            }
+            RegistrationGate::Open | RegistrationGate::ActiveReferrer => {}
+        }
+
+        self.registration_gate = gate as u8;
+        self.registration_min_deposit = min_deposit;
+        self.registration_attestor = attestor;
+        Ok(())
+    }
+
+    /// Returns the gate a new player must pass to receive the registration rewards.
+    pub fn registration_gate(&self) -> RegistrationGate {
+        match self.registration_gate {
+            1 => RegistrationGate::MinDeposit,
+            2 => RegistrationGate::ActiveReferrer,
+            3 => RegistrationGate::Attestation,
+            _ => RegistrationGate::Open,
+        }
+    }
+
+    /// Checks whether a new player passes the registration gate, and may receive the registration rewards.
+    ///
+    /// # Arguments
+    /// - `player`: The registering player.
+    /// - `token_balance`: The player's game token balance.
+    /// - `referrer_data`: The player data of the player's referrer.
+    /// - `instructions_sysvar`: The instructions sysvar, required to verify an attestation.
+    pub fn is_registration_gate_passed(
+        &self,
+        player: &Pubkey,
+        token_balance: u64,
+        referrer_data: &PlayerData,
+        instructions_sysvar: Option<&AccountInfo>,
+    ) -> Result<bool> {
+        Ok(match self.registration_gate() {
+            RegistrationGate::Open => true,
+            RegistrationGate::MinDeposit => token_balance >= self.registration_min_deposit,
+            RegistrationGate::ActiveReferrer => {
+                referrer_data.current_period == self.current_period
+                    && referrer_data.current_period_purchased_ores > 0
+            }
+            RegistrationGate::Attestation => match instructions_sysvar {
+                Some(instructions_sysvar) => is_ed25519_attested(
+                    instructions_sysvar,
+                    &self.registration_attestor,
+                    player.as_ref(),
+                )?,
+                None => false,
+            },
+        })
+    }
+
+    /// Sets how often candy taps may happen.
+    ///
+    /// # Arguments
+    /// - `candy_tap_cooldown_seconds`: The wait between two candy taps of the same player.
+    /// - `candy_tap_min_interval_seconds`: The wait between two candy taps of any players in a round.
+    pub fn set_candy_tap_throttle(
+        &mut self,
+        candy_tap_cooldown_seconds: u64,
+        candy_tap_min_interval_seconds: u64,
+    ) -> Result<()> {
+        self.candy_tap_cooldown_seconds = candy_tap_cooldown_seconds;
+        self.candy_tap_min_interval_seconds = candy_tap_min_interval_seconds;
+        Ok(())
+    }
+
+    /// Records the bot's heartbeat, withdrawing the backup authorities until it goes stale again.
+    ///
+    /// # Arguments
+    /// - `timestamp`: The current timestamp.
+    pub fn record_bot_heartbeat(&mut self, timestamp: u64) -> Result<()> {
+        self.last_bot_heartbeat = timestamp;
+        Ok(())
+    }
+
+    /// Sets the backup authorities standing in for the bot once its heartbeat is stale.
+    ///
+    /// # Arguments
+    /// - `backup_authorities`: The backup authorities, at most `MAX_BACKUP_AUTHORITIES`. They replace the previous ones.
+    /// - `bot_heartbeat_timeout_seconds`: How long the bot may miss its heartbeat before the backups take over.
+    pub fn set_backup_authorities(
+        &mut self,
+        backup_authorities: &[Pubkey],
+        bot_heartbeat_timeout_seconds: u64,
+    ) -> Result<()> {
+        require!(
+            backup_authorities.len() <= MAX_BACKUP_AUTHORITIES && bot_heartbeat_timeout_seconds > 0,
+            ErrorCode::InvalidBackupAuthorities
+        );
+        self.backup_authorities = [Pubkey::default(); MAX_BACKUP_AUTHORITIES];
+        self.backup_authorities[..backup_authorities.len()].copy_from_slice(backup_authorities);
+        self.bot_heartbeat_timeout_seconds = bot_heartbeat_timeout_seconds;
+        Ok(())
+    }
+
+    /// Checks that `signer` may run a failover-enabled bot-only instruction: the bot authority at any time,
+    /// or a backup authority while the bot's heartbeat is stale.
+    ///
+    /// # Arguments
+    /// - `signer`: The key signing the instruction.
+    /// - `timestamp`: The current timestamp.
+    pub fn require_bot_authority(&self, signer: Pubkey, timestamp: u64) -> Result<()> {
+        if signer == self.bot_authority {
+            return Ok(());
+        }
+        require!(
+            signer != Pubkey::default()
+                && self.backup_authorities.contains(&signer)
+                && is_bot_heartbeat_stale(
+                    self.last_bot_heartbeat,
+                    self.bot_heartbeat_timeout_seconds,
+                    timestamp
+                ),
+            ErrorCode::AuthorityMismatch
+        );
+        Ok(())
+    }
+
+    /// Takes the tip of a permissionless maintenance crank from the maintenance pool.
+    ///
+    /// # Arguments
+    /// - `tip_per_task`: The tip for each unit of work the crank completed.
+    /// - `tasks`: The units of work the crank completed.
+    ///
+    /// # Returns
+    /// Returns the tip to pay the caller from the game vault, capped at the maintenance pool balance.
+    pub fn take_crank_tip(&mut self, tip_per_task: u64, tasks: u32) -> Result<u64> {
+        let tip = calculate_crank_tip(tip_per_task, tasks, self.maintenance_pool_balance);
+        self.maintenance_pool_balance = self.maintenance_pool_balance.safe_sub(tip)?;
+        Ok(tip)
+    }
+
+    /// Adds `amount` to the game tokens burned.
+    pub fn record_burned_tokens(&mut self, amount: u64) -> Result<()> {
+        self.total_burned_tokens = self.total_burned_tokens.safe_add(amount)?;
+        Ok(())
+    }
+
+    /// Adds `amount` to the vouchers burned.
+    pub fn record_burned_vouchers(&mut self, amount: u64) -> Result<()> {
+        self.total_burned_vouchers = self.total_burned_vouchers.safe_add(amount)?;
+        Ok(())
+    }
+
+    /// Adds `amount` to the referral rewards expired back to the referral pool.
+    pub fn record_expired_referral_rewards(&mut self, amount: u64) -> Result<()> {
+        self.expired_referral_rewards = self.expired_referral_rewards.safe_add(amount)?;
+        Ok(())
+    }
+
     /// Increments the `team_nonce` by one, ensuring new unique team PDAs.
     pub fn increment_team_nonce(&mut self) -> Result<()> {
         self.team_nonce = self.team_nonce.safe_add(1)?;
@@ -167,9 +830,157 @@ impl Game {
         Ok(())
     }
 
+    /// Increments the `proposal_nonce` by one, ensuring unique governance proposal PDAs.
+    pub fn increment_proposal_nonce(&mut self) -> Result<()> {
+        self.proposal_nonce = self.proposal_nonce.safe_add(1)?;
+        Ok(())
+    }
+
+    /// Returns the sum of the reward pool balances backed by the game vault.
+    pub fn total_pool_balance(&self) -> Result<u64> {
+        Ok(self
+            .construction_rewards_pool_balance
+            .safe_add(self.bonus_rewards_pool_balance)?
+            .safe_add(self.lottery_rewards_pool_balance)?
+            .safe_add(self.developer_rewards_pool_balance)?
+            .safe_add(self.referral_rewards_pool_balance)?
+            .safe_add(self.round_rewards_pool_balance)?
+            .safe_add(self.period_rewards_pool_balance)?
+            .safe_add(self.registration_rewards_pool_balance)?
+            .safe_add(self.airdrop_rewards_pool_balance)?
+            .safe_add(self.consumption_rewards_pool_balance)?
+            .safe_add(self.exit_rewards_pool_balance)?
+            .safe_add(self.sugar_rush_rewards_pool_balance)?
+            .safe_add(self.insurance_pool_balance)?
+            .safe_add(self.carryover_pool_balance)?
+            .safe_add(self.maintenance_pool_balance)?)
+    }
+
     /// Increments the `event_nonce` by one, ensuring unique event IDs.
     pub fn increment_event_nonce(&mut self) -> Result<()> {
         self.event_nonce = self.event_nonce.safe_add(1)?;
         Ok(())
     }
 }
+
+/// The pool a shortfall is covered for from the insurance pool.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ShortfallPool {
+    /// The grand prize pool of a round, held by the round vault.
+    GrandPrize = 0,
+    /// The game's construction rewards pool.
+    Construction = 1,
+    /// The game's exit rewards pool.
+    Exit = 2,
+}
+
+/// A reward pool found unable to pay in full, whose payout was capped at its remaining balance or refused.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ExhaustedPool {
+    /// The sugar rush rewards pool, paying candy taps.
+    SugarRush = 0,
+    /// The exit rewards pool, paying exits.
+    Exit = 1,
+}
+
+/// The permissionless maintenance crank a tip is paid for.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MaintenanceCrank {
+    /// A counted call ending the current round, made through `purchase` with zero ORE.
+    RoundEnd = 0,
+    /// The rollover of an ended period to the next one.
+    PeriodRollover = 1,
+    /// The completion of matured stake orders on behalf of their owners.
+    MaturedUnstake = 2,
+}
+
+/// Why tokens or vouchers were burned, logged with every `Burn` event.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum BurnReason {
+    /// The referral share of a purchase by a player without a referrer.
+    ReferralRewards = 0,
+    /// The grand prizes won by the default team or player.
+    GrandPrizes = 1,
+    /// The vouchers spent on a purchase or a lottery draw.
+    VoucherPayment = 2,
+    /// The vouchers redeemed for game tokens.
+    VoucherRedemption = 3,
+    /// The voucher rewards forfeited by an early unstake, and the game tokens backing them.
+    EarlyUnstake = 4,
+    /// The vouchers left in a decommissioned stake pool.
+    StakePoolDecommission = 5,
+    /// The leaderboard rewards of the ranks held by the default team or player.
+    LeaderboardRewards = 6,
+    /// The vouchers of a voucher kind spent on a purchase.
+    VoucherKindPayment = 7,
+}
+
+/// What happens to the grand prizes and leaderboard rewards won by the default player or team, which nobody can claim.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DefaultWinnerPolicy {
+    /// The rewards are burned.
+    Burn = 0,
+    /// The rewards roll into the next period: carried over to its leaderboards, or added to the period rewards pool funding it.
+    RollToNextPeriod = 1,
+    /// The rewards are added to the round rewards pool, seeding the grand prizes of the next rounds.
+    GrandPrize = 2,
+    /// The rewards are added to the insurance pool.
+    Insurance = 3,
+}
+
+/// How the sugar rush rewards per second are set, keeping the sugar rush pool from draining early or sitting unused.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SugarRushEmissionMode {
+    /// The rate is `sugar_rush_rewards_per_second`.
+    Fixed = 0,
+    /// The rate emits the remaining pool over `sugar_rush_emission_horizon_seconds`, slowing down as the pool drains,
+    /// within `sugar_rush_min_rewards_per_second` and `sugar_rush_rewards_per_second`.
+    Proportional = 1,
+}
+
+/// The proof a new player must give to receive the registration rewards, keeping them from being farmed
+/// by creating wallets. Players failing the gate still register, without the rewards.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RegistrationGate {
+    /// Every new player receives the registration rewards.
+    Open = 0,
+    /// The player must hold at least `registration_min_deposit` game tokens.
+    MinDeposit = 1,
+    /// The player's referrer must have purchased ORE in the current period.
+    ActiveReferrer = 2,
+    /// The transaction must verify, in the ed25519 program instruction preceding the registration,
+    /// a signature of `registration_attestor` over the player's key.
+    Attestation = 3,
+}
+
+/// A tier of the consumption rewards rate, applying to players whose purchase volume over the last 30 days
+/// reaches `min_purchase_volume`. A tier with a zero `consumption_rate` is unused.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub struct ConsumptionRewardTier {
+    /// The purchase volume, in tokens and vouchers, from which the tier applies.
+    pub min_purchase_volume: u64,
+    /// The percentage of the token part of a purchase returned as consumption rewards.
+    pub consumption_rate: u8,
+}
+
+impl ConsumptionRewardTier {
+    /// Whether the tier is in use.
+    pub fn is_used(&self) -> bool {
+        self.consumption_rate > 0
+    }
+}
+
+impl VaultAuthority for Game {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[GAME_SEED, &[bump]])
+    }
+}
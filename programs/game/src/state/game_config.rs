@@ -0,0 +1,141 @@
+use crate::constants::{
+    CONSTRUCTION_POOL_SHARE, CONSUMPTION_POOL_SHARE, CRANK_TIP, INSURANCE_POOL_SHARE,
+    LAMPORTS_PER_ORE, LOTTERY_POOL_SHARE, MARKET_FEE_RATE, MIN_LOTTERY_REWARDS_POOL_BALANCE,
+    PERIOD_ROLLOVER_TIP, RANDOMNESS_SLOT_WINDOW, REFERRAL_POOL_SHARE, REFERRAL_VOUCHER_BONUS_RATE,
+};
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Debug, Default, InitSpace)]
+/// The `GameConfig` account holds the tunables that differ between clusters (pool shares and costs),
+/// created at `initialize` from the `constants` defaults and adjustable by the game authority without redeploying the program.
+pub struct GameConfig {
+    /// Percentage of a purchase allocated to construction rewards, and again to bonus rewards.
+    pub construction_pool_share: u8,
+
+    /// Percentage of a purchase allocated to lottery rewards.
+    pub lottery_pool_share: u8,
+
+    /// Percentage of a purchase allocated to referral rewards.
+    pub referral_pool_share: u8,
+
+    /// Percentage of a purchase allocated to the insurance pool.
+    pub insurance_pool_share: u8,
+
+    /// Percentage of the token part of a purchase moved from the consumption pool to the developer rewards.
+    pub consumption_pool_share: u8,
+
+    /// The cost of one ORE, in token lamports.
+    pub lamports_per_ore: u64,
+
+    /// The lottery pool balance required to draw the lottery.
+    pub min_lottery_rewards_pool_balance: u64,
+
+    /// The number of slots after its seed slot a randomness seed can still be committed to by a lottery draw.
+    pub randomness_slot_window: u64,
+
+    /// Percentage of the price of a filled marketplace listing credited to the developer rewards pool.
+    pub market_fee_rate: u8,
+
+    /// Percentage of referral rewards added from the bonus pool when a player collects them as vouchers.
+    pub referral_voucher_bonus_rate: u8,
+
+    /// Tip paid from the maintenance pool to whoever rolls an ended period over.
+    pub period_rollover_tip: u64,
+
+    /// Tip paid from the maintenance pool for each counted round end call and each completed matured stake order.
+    pub crank_tip: u64,
+}
+
+/// The tunables of a `GameConfig`, see its fields.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct GameConfigParams {
+    pub construction_pool_share: u8,
+    pub lottery_pool_share: u8,
+    pub referral_pool_share: u8,
+    pub insurance_pool_share: u8,
+    pub consumption_pool_share: u8,
+    pub lamports_per_ore: u64,
+    pub min_lottery_rewards_pool_balance: u64,
+    pub randomness_slot_window: u64,
+    pub market_fee_rate: u8,
+    pub referral_voucher_bonus_rate: u8,
+    pub period_rollover_tip: u64,
+    pub crank_tip: u64,
+}
+
+impl Default for GameConfigParams {
+    /// The tunables of the `constants`, chosen for localnet with the `localnet-defaults` feature.
+    fn default() -> Self {
+        GameConfigParams {
+            construction_pool_share: CONSTRUCTION_POOL_SHARE,
+            lottery_pool_share: LOTTERY_POOL_SHARE,
+            referral_pool_share: REFERRAL_POOL_SHARE,
+            insurance_pool_share: INSURANCE_POOL_SHARE,
+            consumption_pool_share: CONSUMPTION_POOL_SHARE,
+            lamports_per_ore: LAMPORTS_PER_ORE,
+            min_lottery_rewards_pool_balance: MIN_LOTTERY_REWARDS_POOL_BALANCE,
+            randomness_slot_window: RANDOMNESS_SLOT_WINDOW,
+            market_fee_rate: MARKET_FEE_RATE,
+            referral_voucher_bonus_rate: REFERRAL_VOUCHER_BONUS_RATE,
+            period_rollover_tip: PERIOD_ROLLOVER_TIP,
+            crank_tip: CRANK_TIP,
+        }
+    }
+}
+
+impl GameConfig {
+    /// Initializes the game configuration with the `constants` defaults.
+    pub fn initialize(&mut self) -> Result<()> {
+        *self = GameConfig::default();
+
+        self.update(&GameConfigParams::default())
+    }
+
+    /// Validates and stores new tunables.
+    ///
+    /// # Arguments
+    /// - `params`: The new tunables. The construction (counted twice, for the bonus share), lottery, referral
+    ///   and insurance shares leave the rest of a purchase to the grand prizes, so they may not exceed 100%.
+    pub fn update(&mut self, params: &GameConfigParams) -> Result<()> {
+        let allocated_share = 2 * params.construction_pool_share as u16
+            + params.lottery_pool_share as u16
+            + params.referral_pool_share as u16
+            + params.insurance_pool_share as u16;
+        require!(allocated_share <= 100, ErrorCode::InvalidGameConfig);
+        require!(
+            params.consumption_pool_share <= 100
+                && params.market_fee_rate <= 100
+                && params.referral_voucher_bonus_rate <= 100,
+            ErrorCode::InvalidGameConfig
+        );
+        require!(
+            params.lamports_per_ore > 0 && params.randomness_slot_window > 0,
+            ErrorCode::InvalidGameConfig
+        );
+
+        self.construction_pool_share = params.construction_pool_share;
+        self.lottery_pool_share = params.lottery_pool_share;
+        self.referral_pool_share = params.referral_pool_share;
+        self.insurance_pool_share = params.insurance_pool_share;
+        self.consumption_pool_share = params.consumption_pool_share;
+        self.lamports_per_ore = params.lamports_per_ore;
+        self.min_lottery_rewards_pool_balance = params.min_lottery_rewards_pool_balance;
+        self.randomness_slot_window = params.randomness_slot_window;
+        self.market_fee_rate = params.market_fee_rate;
+        self.referral_voucher_bonus_rate = params.referral_voucher_bonus_rate;
+        self.period_rollover_tip = params.period_rollover_tip;
+        self.crank_tip = params.crank_tip;
+
+        Ok(())
+    }
+
+    /// Percentage of a purchase allocated to grand prizes: what the other shares leave.
+    pub fn grand_prizes_pool_share(&self) -> u8 {
+        100 - 2 * self.construction_pool_share
+            - self.lottery_pool_share
+            - self.referral_pool_share
+            - self.insurance_pool_share
+    }
+}
@@ -0,0 +1,135 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// The number of entries held by a single `LeaderboardPage`.
+pub const LEADERBOARD_PAGE_CAPACITY: usize = 100;
+
+/// A player ranked on a `LeaderboardPage`.
+///
+/// # Fields
+/// - `player`: The public key of the player.
+/// - `score`: The ores purchased by the player during the period.
+#[zero_copy]
+#[derive(Debug, Default)]
+pub struct LeaderboardEntry {
+    pub player: Pubkey,
+    pub score: u32,
+}
+
+/// The `LeaderboardPage` account extends a period's leaderboard beyond the top 10.
+/// Each page holds the players whose score falls within its bucket, sorted by descending score,
+/// and is kept up to date by the bot crank as purchases happen.
+///
+/// Pages are created from the top down and tile the score range without gaps or overlaps,
+/// so a player's rank is the number of entries on all higher pages plus their position on their own page.
+///
+/// # Fields
+/// - `period`: The period this page belongs to.
+/// - `min_score`: The lowest score covered by this page, inclusive.
+/// - `max_score`: The highest score covered by this page, inclusive.
+/// - `page_index`: The position of this page, `0` being the page with the highest scores.
+/// - `len`: The number of entries in use.
+/// - `bump`: The PDA bump seed.
+/// - `entries`: The ranked entries; only the first `len` are meaningful.
+#[account(zero_copy)]
+pub struct LeaderboardPage {
+    pub period: Pubkey,
+    pub min_score: u32,
+    pub max_score: u32,
+    pub page_index: u16,
+    pub len: u16,
+    pub bump: u8,
+    pub _padding: [u8; 3],
+    pub entries: [LeaderboardEntry; LEADERBOARD_PAGE_CAPACITY],
+}
+
+impl LeaderboardPage {
+    /// Initializes an empty page covering the scores from `min_score` to `max_score`.
+    ///
+    /// # Arguments
+    /// - `period`: The period this page belongs to.
+    /// - `page_index`: The position of this page.
+    /// - `min_score`: The lowest score covered by this page.
+    /// - `max_score`: The highest score covered by this page.
+    /// - `bump`: The PDA bump seed.
+    pub fn initialize(
+        &mut self,
+        period: Pubkey,
+        page_index: u16,
+        min_score: u32,
+        max_score: u32,
+        bump: u8,
+    ) {
+        self.period = period;
+        self.page_index = page_index;
+        self.min_score = min_score;
+        self.max_score = max_score;
+        self.len = 0;
+        self.bump = bump;
+    }
+
+    /// Whether `score` falls within this page's bucket.
+    pub fn covers(&self, score: u32) -> bool {
+        score >= self.min_score && score <= self.max_score
+    }
+
+    /// Returns the 1-based position of `player` on this page, if listed.
+    pub fn position_of(&self, player: Pubkey) -> Option<u32> {
+        self.entries[..self.len as usize]
+            .iter()
+            .position(|entry| entry.player == player)
+            .map(|index| index as u32 + 1)
+    }
+
+    /// Records the player's latest score, keeping the entries sorted in descending order.
+    /// A player whose score left the bucket is removed from the page, so the crank can re-list them
+    /// on the page now covering their score.
+    ///
+    /// # Arguments
+    /// - `player`: The public key of the player.
+    /// - `score`: The player's current score for the period.
+    ///
+    /// # Returns
+    /// The player's 1-based position on this page, or `0` if they are not listed on it.
+    pub fn update_entry(&mut self, player: Pubkey, score: u32) -> Result<u32> {
+        let len = self.len as usize;
+        let existing = self.entries[..len]
+            .iter()
+            .position(|entry| entry.player == player);
+
+        if !self.covers(score) {
+            if let Some(index) = existing {
+                self.entries.copy_within(index + 1..len, index);
+                self.entries[len - 1] = LeaderboardEntry::default();
+                self.len -= 1;
+            }
+            return Ok(0);
+        }
+
+        let mut index = match existing {
+            Some(index) => index,
+            None => {
+                require!(
+                    len < LEADERBOARD_PAGE_CAPACITY,
+                    ErrorCode::LeaderboardPageFull
+                );
+                self.len += 1;
+                len
+            }
+        };
+        self.entries[index] = LeaderboardEntry { player, score };
+
+        // Move the entry up while it outscores its predecessor, or down while its successor outscores it
+        let last = self.len as usize - 1;
+        while index > 0 && self.entries[index - 1].score < score {
+            self.entries.swap(index - 1, index);
+            index -= 1;
+        }
+        while index < last && self.entries[index + 1].score > score {
+            self.entries.swap(index, index + 1);
+            index += 1;
+        }
+
+        Ok(index as u32 + 1)
+    }
+}
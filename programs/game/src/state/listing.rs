@@ -0,0 +1,47 @@
+use crate::constants::LISTING_SEED;
+use crate::utils::VaultAuthority;
+use anchor_lang::prelude::*;
+
+/// The `Listing` account is a seller's open offer on the marketplace, of either vouchers or a tokenized round position.
+/// The listed vouchers or position token are held in an escrow token account owned by the listing until the
+/// listing is filled by a buyer or cancelled by the seller, after which the listing is closed.
+///
+/// # Fields
+/// - `seller`: The public key of the player selling the vouchers.
+/// - `listing_number`: The number distinguishing the seller's listings.
+/// - `escrow_vault`: The token account, owned by the listing, holding the listed vouchers or position token.
+/// - `voucher_amount`: The amount of vouchers escrowed, zero for a position listing.
+/// - `price`: The price in game tokens asked for the whole listing, fee included.
+/// - `created_timestamp`: The UNIX timestamp the listing was created at.
+/// - `bump`: The PDA bump seed of the listing.
+/// - `round_position`: The `RoundPosition` whose token is escrowed, `Pubkey::default()` for a voucher listing.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct Listing {
+    pub seller: Pubkey,
+    pub listing_number: u32,
+    pub escrow_vault: Pubkey,
+    pub voucher_amount: u64,
+    pub price: u64,
+    pub created_timestamp: u64,
+    pub bump: u8,
+    pub round_position: Pubkey,
+}
+
+impl Listing {
+    /// Checks if the listing offers a tokenized round position rather than vouchers.
+    pub fn is_position_listing(&self) -> bool {
+        self.round_position != Pubkey::default()
+    }
+}
+
+impl VaultAuthority for Listing {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[
+            LISTING_SEED,
+            self.seller.as_ref(),
+            &self.listing_number.to_le_bytes(),
+            &[bump],
+        ])
+    }
+}
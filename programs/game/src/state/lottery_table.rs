@@ -0,0 +1,215 @@
+use crate::constants::{
+    CLASSIC_LOTTERY_PAIR_MULTIPLIERS, CLASSIC_LOTTERY_REEL_SYMBOLS,
+    CLASSIC_LOTTERY_SINGLE_MULTIPLIERS, CLASSIC_LOTTERY_TRIPLE_MULTIPLIERS,
+    LOTTERY_MAX_RETURN_TO_PLAYER_RATE, LOTTERY_REEL_STOPS, LOTTERY_RETURN_TO_PLAYER_MIN_BETS,
+    LOTTERY_TABLE_SYMBOLS, ONCE_DRAW_LOTTERY_VOUCHER_COST,
+};
+use crate::errors::ErrorCode;
+use crate::utils::{
+    calculate_return_to_player_rate, calculate_spin_multiplier, get_reel_stop,
+    is_return_to_player_exceeded,
+};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// The `LotteryTable` account describes one themed slot machine of the lottery: what a draw costs,
+/// which symbol each reel stop shows and what each combination of symbols pays.
+/// Players pick the table they draw on; the result is revealed against the same table.
+///
+/// The table keeps its cumulative bets and payouts, so its realized return to player can be verified on-chain,
+/// and pauses itself when that return exceeds its ceiling.
+///
+/// # Fields
+/// - `table_id`: The identifier of the table, seeding its PDA.
+/// - `voucher_cost`: The cost of one draw, in voucher lamports. Winnings are a multiple of it.
+/// - `reel_symbols`: The symbol shown at each reel stop.
+/// - `triple_multipliers`: The multiplier of each symbol showing on all three reels.
+/// - `pair_multipliers`: The multiplier of each symbol showing on exactly two reels.
+/// - `single_multipliers`: The multiplier of each symbol showing on exactly one reel.
+/// - `max_return_to_player_rate`: The return to player ceiling, as a percentage of the bets.
+/// - `return_to_player_min_bets`: The bets the table takes before the ceiling is enforced.
+/// - `draw_count`: The number of draws on the table.
+/// - `total_bets`: The cumulative draw costs paid on the table, in voucher lamports.
+/// - `total_payouts`: The cumulative lottery rewards paid out by the table, in token lamports.
+/// - `is_enabled`: Whether new draws may use the table. Pending draws can always be revealed.
+/// - `bump`: The PDA bump seed of the table.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct LotteryTable {
+    pub table_id: u16,
+    pub voucher_cost: u64,
+    pub reel_symbols: [u8; LOTTERY_REEL_STOPS],
+    pub triple_multipliers: [u16; LOTTERY_TABLE_SYMBOLS],
+    pub pair_multipliers: [u16; LOTTERY_TABLE_SYMBOLS],
+    pub single_multipliers: [u16; LOTTERY_TABLE_SYMBOLS],
+    pub max_return_to_player_rate: u16,
+    pub return_to_player_min_bets: u64,
+    pub draw_count: u64,
+    pub total_bets: u64,
+    pub total_payouts: u64,
+    pub is_enabled: bool,
+    pub bump: u8,
+}
+
+/// The cost, symbols, multipliers and return to player ceiling of a `LotteryTable`, see its fields.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LotteryTableParams {
+    pub voucher_cost: u64,
+    pub reel_symbols: [u8; LOTTERY_REEL_STOPS],
+    pub triple_multipliers: [u16; LOTTERY_TABLE_SYMBOLS],
+    pub pair_multipliers: [u16; LOTTERY_TABLE_SYMBOLS],
+    pub single_multipliers: [u16; LOTTERY_TABLE_SYMBOLS],
+    pub max_return_to_player_rate: u16,
+    pub return_to_player_min_bets: u64,
+}
+
+/// The return to player accounting of a `LotteryTable`, returned by `get_lottery_stats`.
+///
+/// # Fields
+/// - `table_id`, `draw_count`, `total_bets`, `total_payouts`, `max_return_to_player_rate`, `is_enabled`: As on the table.
+/// - `return_to_player_rate`: The realized return to player, as a percentage of the bets.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct LotteryStats {
+    pub table_id: u16,
+    pub draw_count: u64,
+    pub total_bets: u64,
+    pub total_payouts: u64,
+    pub return_to_player_rate: u64,
+    pub max_return_to_player_rate: u16,
+    pub is_enabled: bool,
+}
+
+impl Default for LotteryTableParams {
+    /// The classic table of 7s, cherries, bells and lemons from the `constants`.
+    fn default() -> Self {
+        LotteryTableParams {
+            voucher_cost: ONCE_DRAW_LOTTERY_VOUCHER_COST,
+            reel_symbols: CLASSIC_LOTTERY_REEL_SYMBOLS,
+            triple_multipliers: CLASSIC_LOTTERY_TRIPLE_MULTIPLIERS,
+            pair_multipliers: CLASSIC_LOTTERY_PAIR_MULTIPLIERS,
+            single_multipliers: CLASSIC_LOTTERY_SINGLE_MULTIPLIERS,
+            max_return_to_player_rate: LOTTERY_MAX_RETURN_TO_PLAYER_RATE,
+            return_to_player_min_bets: LOTTERY_RETURN_TO_PLAYER_MIN_BETS,
+        }
+    }
+}
+
+impl LotteryTable {
+    /// Initializes an enabled lottery table.
+    ///
+    /// # Arguments
+    /// - `table_id`: The identifier of the table.
+    /// - `params`: The cost, symbols, multipliers and return to player ceiling of the table. The cost and the ceiling
+    ///   may not be zero, and every reel stop must show one of the `LOTTERY_TABLE_SYMBOLS` symbols.
+    /// - `bump`: The PDA bump seed.
+    pub fn initialize(
+        &mut self,
+        table_id: u16,
+        params: &LotteryTableParams,
+        bump: u8,
+    ) -> Result<()> {
+        require!(
+            params.voucher_cost > 0
+                && params.max_return_to_player_rate > 0
+                && params
+                    .reel_symbols
+                    .iter()
+                    .all(|&symbol| (symbol as usize) < LOTTERY_TABLE_SYMBOLS),
+            ErrorCode::InvalidLotteryTable
+        );
+
+        self.table_id = table_id;
+        self.voucher_cost = params.voucher_cost;
+        self.reel_symbols = params.reel_symbols;
+        self.triple_multipliers = params.triple_multipliers;
+        self.pair_multipliers = params.pair_multipliers;
+        self.single_multipliers = params.single_multipliers;
+        self.max_return_to_player_rate = params.max_return_to_player_rate;
+        self.return_to_player_min_bets = params.return_to_player_min_bets;
+        self.draw_count = 0;
+        self.total_bets = 0;
+        self.total_payouts = 0;
+        self.is_enabled = true;
+        self.bump = bump;
+
+        Ok(())
+    }
+
+    /// The largest amount a single draw on this table can win, in token lamports.
+    pub fn max_lottery_rewards(&self) -> Result<u64> {
+        let max_multiplier = self
+            .triple_multipliers
+            .iter()
+            .chain(self.pair_multipliers.iter())
+            .chain(self.single_multipliers.iter())
+            .copied()
+            .max()
+            .unwrap_or(0);
+
+        Ok(self.voucher_cost.safe_mul(max_multiplier as u64)?)
+    }
+
+    /// Spins the reels with the given random bytes.
+    ///
+    /// # Returns
+    /// The reel stops landed on and the multiplier of the symbols they show.
+    pub fn spin(&self, random_bytes: [u8; 3]) -> ([u8; 3], u16) {
+        let reel_stops =
+            random_bytes.map(|random_byte| get_reel_stop(random_byte, LOTTERY_REEL_STOPS));
+        let symbols = reel_stops.map(|reel_stop| self.reel_symbols[reel_stop as usize]);
+
+        let multiplier = calculate_spin_multiplier(
+            symbols,
+            &self.triple_multipliers,
+            &self.pair_multipliers,
+            &self.single_multipliers,
+        );
+
+        (reel_stops, multiplier)
+    }
+
+    /// Records a draw and its cost.
+    pub fn record_bet(&mut self, voucher_cost: u64) -> Result<()> {
+        self.draw_count = self.draw_count.safe_add(1)?;
+        self.total_bets = self.total_bets.safe_add(voucher_cost)?;
+        Ok(())
+    }
+
+    /// Records the lottery rewards paid out for a draw, pausing the table if its realized return to player
+    /// now exceeds its ceiling.
+    ///
+    /// # Returns
+    /// Whether this payout paused the table.
+    pub fn record_payout(&mut self, lottery_rewards: u64) -> Result<bool> {
+        self.total_payouts = self.total_payouts.safe_add(lottery_rewards)?;
+
+        let is_exceeded = is_return_to_player_exceeded(
+            self.total_bets,
+            self.total_payouts,
+            self.max_return_to_player_rate,
+            self.return_to_player_min_bets,
+        );
+        let is_paused = is_exceeded && self.is_enabled;
+        if is_paused {
+            self.is_enabled = false;
+        }
+
+        Ok(is_paused)
+    }
+
+    /// The return to player accounting of the table.
+    pub fn stats(&self) -> LotteryStats {
+        LotteryStats {
+            table_id: self.table_id,
+            draw_count: self.draw_count,
+            total_bets: self.total_bets,
+            total_payouts: self.total_payouts,
+            return_to_player_rate: calculate_return_to_player_rate(
+                self.total_bets,
+                self.total_payouts,
+            ),
+            max_return_to_player_rate: self.max_return_to_player_rate,
+            is_enabled: self.is_enabled,
+        }
+    }
+}
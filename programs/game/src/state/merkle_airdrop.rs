@@ -0,0 +1,134 @@
+use crate::constants::MERKLE_AIRDROP_SEED;
+use crate::errors::ErrorCode;
+use crate::utils::VaultAuthority;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// The maximum number of claims of a `MerkleAirdrop`, bounded by the size of its `MerkleAirdropBitmap`
+/// an instruction can create.
+pub const MAX_MERKLE_AIRDROP_CLAIMS: u32 = 80_000;
+
+/// The `MerkleAirdrop` account distributes off-chain computed retroactive rewards, e.g. a compensation
+/// for an incident, from its own vault. Each claim is a leaf `(index, player, amount)` of the merkle tree
+/// committed to by `merkle_root`, and is marked on the airdrop's `MerkleAirdropBitmap` once collected.
+///
+/// # Fields
+/// - `airdrop_id`: The identifier of the airdrop, seeding its PDA.
+/// - `merkle_root`: The root of the merkle tree of the claims.
+/// - `airdrop_vault`: The token account holding the airdrop's tokens, owned by the airdrop.
+/// - `total_amount`: The tokens deposited to cover every claim.
+/// - `claimed_amount`: The cumulative tokens claimed.
+/// - `claim_count`: The number of leaves of the merkle tree.
+/// - `claimed_count`: The number of claims collected.
+/// - `bump`: The PDA bump seed of the airdrop.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct MerkleAirdrop {
+    pub airdrop_id: u16,
+    pub merkle_root: [u8; 32],
+    pub airdrop_vault: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub claim_count: u32,
+    pub claimed_count: u32,
+    pub bump: u8,
+}
+
+impl MerkleAirdrop {
+    /// Initializes an airdrop of `total_amount` tokens over `claim_count` claims.
+    ///
+    /// # Arguments
+    /// - `airdrop_id`: The identifier of the airdrop.
+    /// - `merkle_root`: The root of the merkle tree of the claims.
+    /// - `airdrop_vault`: The token account holding the airdrop's tokens.
+    /// - `total_amount`: The tokens deposited to cover every claim.
+    /// - `claim_count`: The number of leaves of the merkle tree.
+    /// - `bump`: The PDA bump seed.
+    pub fn initialize(
+        &mut self,
+        airdrop_id: u16,
+        merkle_root: [u8; 32],
+        airdrop_vault: Pubkey,
+        total_amount: u64,
+        claim_count: u32,
+        bump: u8,
+    ) -> Result<()> {
+        require!(
+            total_amount > 0 && (1..=MAX_MERKLE_AIRDROP_CLAIMS).contains(&claim_count),
+            ErrorCode::InvalidMerkleAirdrop
+        );
+
+        *self = MerkleAirdrop {
+            airdrop_id,
+            merkle_root,
+            airdrop_vault,
+            total_amount,
+            claimed_amount: 0,
+            claim_count,
+            claimed_count: 0,
+            bump,
+        };
+        Ok(())
+    }
+
+    /// Records a claim of `amount` tokens, within the airdrop's total.
+    pub fn record_claim(&mut self, amount: u64) -> Result<()> {
+        let claimed_amount = self.claimed_amount.safe_add(amount)?;
+        require!(
+            claimed_amount <= self.total_amount,
+            ErrorCode::ExceedsMerkleAirdropTotal
+        );
+
+        self.claimed_amount = claimed_amount;
+        self.claimed_count = self.claimed_count.safe_add(1)?;
+        Ok(())
+    }
+}
+
+impl VaultAuthority for MerkleAirdrop {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[MERKLE_AIRDROP_SEED, &self.airdrop_id.to_le_bytes(), &[bump]])
+    }
+}
+
+/// The `MerkleAirdropBitmap` account records which claims of a `MerkleAirdrop` were collected,
+/// one bit per leaf index, preventing double claims.
+///
+/// # Fields
+/// - `merkle_airdrop`: The airdrop whose claims are recorded.
+/// - `bump`: The PDA bump seed of the bitmap.
+/// - `claimed`: The claimed bits, bit `index % 8` of byte `index / 8` being set once claim `index` is collected.
+#[account]
+#[derive(Debug)]
+pub struct MerkleAirdropBitmap {
+    pub merkle_airdrop: Pubkey,
+    pub bump: u8,
+    pub claimed: Vec<u8>,
+}
+
+impl MerkleAirdropBitmap {
+    /// The account space of a bitmap of `claim_count` claims, discriminator included.
+    pub fn space(claim_count: u32) -> usize {
+        8 + 32 + 1 + 4 + (claim_count as usize).div_ceil(8)
+    }
+
+    /// Initializes an empty bitmap of `claim_count` claims.
+    pub fn initialize(&mut self, merkle_airdrop: Pubkey, claim_count: u32, bump: u8) {
+        self.merkle_airdrop = merkle_airdrop;
+        self.bump = bump;
+        self.claimed = vec![0; (claim_count as usize).div_ceil(8)];
+    }
+
+    /// Marks claim `index` as collected, failing if it already was or lies outside the bitmap.
+    pub fn set_claimed(&mut self, index: u32) -> Result<()> {
+        let byte = self
+            .claimed
+            .get_mut(index as usize / 8)
+            .ok_or(ErrorCode::InvalidMerkleProof)?;
+        let mask = 1u8 << (index % 8);
+        require!(*byte & mask == 0, ErrorCode::MerkleAirdropAlreadyClaimed);
+
+        *byte |= mask;
+        Ok(())
+    }
+}
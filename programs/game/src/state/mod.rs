@@ -1,16 +1,62 @@
+pub mod airdrop_campaign;
+pub mod ban_entry;
+pub mod claim_code;
+pub mod daily_stats;
+pub mod event_ring_buffer;
+pub mod exchange_config;
+pub mod deposit_receipt;
 pub mod game;
+pub mod game_config;
+pub mod leaderboard_page;
+pub mod listing;
+pub mod lottery_table;
+pub mod merkle_airdrop;
+pub mod pending_distribution;
 pub mod period;
 pub mod player;
+pub mod prediction_market;
+pub mod prize_vesting;
+pub mod proposal;
 pub mod round;
+pub mod round_position;
+pub mod session_key;
 pub mod stake;
 pub mod team;
+pub mod team_ledger;
+pub mod team_merge;
+pub mod team_quest;
 pub mod vault;
+pub mod vault_ledger;
 pub mod voucher;
+pub mod voucher_kind;
+pub use airdrop_campaign::*;
+pub use ban_entry::*;
+pub use claim_code::*;
+pub use daily_stats::*;
+pub use event_ring_buffer::*;
+pub use exchange_config::*;
+pub use deposit_receipt::*;
 pub use game::*;
+pub use game_config::*;
+pub use leaderboard_page::*;
+pub use listing::*;
+pub use lottery_table::*;
+pub use merkle_airdrop::*;
+pub use pending_distribution::*;
 pub use period::*;
 pub use player::*;
+pub use prediction_market::*;
+pub use prize_vesting::*;
+pub use proposal::*;
 pub use round::*;
+pub use round_position::*;
+pub use session_key::*;
 pub use stake::*;
 pub use team::*;
+pub use team_ledger::*;
+pub use team_merge::*;
+pub use team_quest::*;
 pub use vault::*;
+pub use vault_ledger::*;
 pub use voucher::*;
+pub use voucher_kind::*;
@@ -0,0 +1,77 @@
+use crate::state::MAX_MANAGER_LIST_LENGTH;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// The `PendingDistribution` account holds a team reward distribution proposed by the captain of a team whose vault
+/// holds more than the game's `team_distribution_approval_threshold`, so that a rogue captain cannot drain the vault
+/// at once. It executes right away with the co-signature of the game authority, or once its veto period has ended
+/// without a manager vetoing it.
+///
+/// The managers able to veto are those of the team when the distribution was proposed, so that the captain cannot
+/// silence them by revoking their privileges, nor veto through managers granted afterwards.
+///
+/// # Fields
+/// - `team`: The team whose rewards are distributed.
+/// - `member`: The member receiving the rewards.
+/// - `team_rewards`: The rewards distributed.
+/// - `proposer`: The captain who proposed the distribution, refunded the rent when it is vetoed.
+/// - `proposed_timestamp`: The UNIX timestamp the distribution was proposed at.
+/// - `executable_timestamp`: The UNIX timestamp the veto period ends at.
+/// - `managers`: The team's managers when the distribution was proposed, who may veto it.
+/// - `bump`: The PDA bump seed of the pending distribution.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct PendingDistribution {
+    pub team: Pubkey,
+    pub member: Pubkey,
+    pub team_rewards: u64,
+    pub proposer: Pubkey,
+    pub proposed_timestamp: u64,
+    pub executable_timestamp: u64,
+    #[max_len(MAX_MANAGER_LIST_LENGTH)]
+    pub managers: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+/// The distribution a captain proposes, and the managers of the team at that time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingDistributionParams {
+    pub team: Pubkey,
+    pub member: Pubkey,
+    pub team_rewards: u64,
+    pub proposer: Pubkey,
+    pub managers: Vec<Pubkey>,
+}
+
+impl PendingDistribution {
+    /// Proposes the distribution described by `params`, executable without approval after `veto_seconds`.
+    pub fn initialize(
+        &mut self,
+        params: PendingDistributionParams,
+        timestamp: u64,
+        veto_seconds: u64,
+        bump: u8,
+    ) -> Result<()> {
+        *self = PendingDistribution {
+            team: params.team,
+            member: params.member,
+            team_rewards: params.team_rewards,
+            proposer: params.proposer,
+            proposed_timestamp: timestamp,
+            executable_timestamp: timestamp.safe_add(veto_seconds)?,
+            managers: params.managers,
+            bump,
+        };
+        Ok(())
+    }
+
+    /// Checks if `manager` was a manager of the team when the distribution was proposed.
+    pub fn can_veto(&self, manager: Pubkey) -> bool {
+        self.managers.contains(&manager)
+    }
+
+    /// Checks if the veto period of the distribution has ended at `timestamp`.
+    pub fn is_veto_period_ended(&self, timestamp: u64) -> bool {
+        timestamp >= self.executable_timestamp
+    }
+}
@@ -1,36 +1,54 @@
+use crate::constants::PERIOD_SEED;
 use crate::errors::ErrorCode;
+use crate::state::{Team, TeamScoringMode};
+use crate::utils::{calculate_rank_rewards, VaultAuthority};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 
 /// Number of top player winners recorded for each period.
-const PLAYER_WINNERS_COUNT: usize = 10;
+pub const PLAYER_WINNERS_COUNT: usize = 10;
 
 /// Number of top team winners recorded for each period.
-const TEAM_WINNERS_COUNT: usize = 10;
+pub const TEAM_WINNERS_COUNT: usize = 10;
 
-#[account]
-#[derive(Debug, Default, InitSpace)]
+#[account(zero_copy)]
+#[derive(Debug)]
 /// The `Period` account represents a leaderboard period in the game.
 /// Each `Period` tracks a set duration (start and end times), reward allocations for top teams and players,
 /// and maintains sorted lists of the top-performing teams and players. Rewards are distributed at the end of the period.
 ///
+/// The account is zero-copy: the leaderboards are fixed-size arrays updated in place, so purchases do not
+/// reserialize the whole account. Fields are ordered by alignment to keep the layout free of padding.
+/// Periods created while the account was borsh-serialized are converted by `migrate_periods`, which must run
+/// on the current period right after the program upgrade.
+///
 /// # Fields
-/// - `period_number`: A unique sequential number identifying this period.
 /// - `period_vault`: A token account holding resources allocated for the period.
-/// - `team_reward_pool_balance`: The total token balance allocated for team rewards during this period.
-/// - `individual_reward_pool_balance`: The total token balance allocated for individual player rewards.
+/// - `team_reward_pool_balance`: The total token balance allocated for team rewards during this period,
+///   including the rewards carried over from the previous period.
+/// - `individual_reward_pool_balance`: The total token balance allocated for individual player rewards,
+///   including the rewards carried over from the previous period.
 /// - `start_time`: The UNIX timestamp marking when the period begins.
 /// - `end_time`: The UNIX timestamp marking when the period ends.
-/// - `top_player_list`: A vector of `TopPlayerAccount`, each representing a top player's performance (tracked by purchased ores).
-/// - `top_team_list`: A vector of `TopTeamAccount`, each representing a top team's performance.
-/// - `team_rewards`: The total amount of rewards dedicated to teams.
-/// - `team_first_place_rewards`, `team_second_place_rewards`, `team_third_place_rewards`:
-///   The share of `team_rewards` allocated to the top three teams, respectively.
-/// - `individual_rewards`: The total amount of rewards dedicated to individual players.
-/// - `is_distribution_completed`: A boolean flag indicating whether the rewards for this period have been distributed.
+/// - `team_rewards`: The amount of rewards dedicated to teams, funded from the period rewards pool.
+/// - `individual_rewards`: The amount of rewards dedicated to individual players, funded from the period rewards pool.
+/// - `team_rank_rewards`: The share of `team_reward_pool_balance` allocated to each rank of `top_team_list`.
+/// - `player_rank_rewards`: The share of `individual_reward_pool_balance` allocated to each rank of `top_player_list`.
+/// - `top_player_list`: The top players sorted by purchased ores, padded with the default player.
+/// - `top_team_list`: The top teams sorted by purchased ores, padded with the default team.
+/// - `leaderboard_page_floor`: The lowest score covered by the leaderboard pages created so far.
+/// - `min_team_purchased_ores`: The ores a team must purchase during the period to enter `top_team_list`.
+/// - `period_number`: A unique sequential number identifying this period.
+/// - `leaderboard_page_count`: The number of `LeaderboardPage` accounts created for this period.
+/// - `team_claimed_ranks`: A bitmask of the team ranks whose rewards have been claimed.
+/// - `player_claimed_ranks`: A bitmask of the player ranks whose rewards have been claimed.
+/// - `ends_with_round`: Non-zero if the period is truncated when the current round ends.
+/// - `team_scoring_mode`: The `TeamScoringMode` scoring the team leaderboard.
+/// - `team_payout_curve`, `player_payout_curve`: The percentages behind `team_rank_rewards` and `player_rank_rewards`.
+/// - `min_team_active_members`: The members who must purchase during the period for a team to enter `top_team_list`.
+/// - `team_payout_asset`, `player_payout_asset`: The `PayoutAsset` the team and individual rank rewards are paid in.
 /// - `bump`: A PDA bump seed.
 pub struct Period {
-    pub period_number: u16,
     pub period_vault: Pubkey,
 
     pub team_reward_pool_balance: u64,
@@ -39,26 +57,73 @@ pub struct Period {
     pub start_time: u64,
     pub end_time: u64,
 
-    #[max_len(PLAYER_WINNERS_COUNT)]
-    pub top_player_list: Vec<TopPlayerAccount>,
-
-    #[max_len(TEAM_WINNERS_COUNT)]
-    pub top_team_list: Vec<TopTeamAccount>,
-
     pub team_rewards: u64,
-    pub team_first_place_rewards: u64,
-    pub team_second_place_rewards: u64,
-    pub team_third_place_rewards: u64,
     pub individual_rewards: u64,
 
-    pub is_distribution_completed: bool,
+    pub team_rank_rewards: [u64; TEAM_WINNERS_COUNT],
+    pub player_rank_rewards: [u64; PLAYER_WINNERS_COUNT],
+
+    pub top_player_list: [TopPlayerAccount; PLAYER_WINNERS_COUNT],
+    pub top_team_list: [TopTeamAccount; TEAM_WINNERS_COUNT],
+
+    pub leaderboard_page_floor: u32,
+    pub min_team_purchased_ores: u32,
+
+    pub period_number: u16,
+    pub leaderboard_page_count: u16,
+    pub team_claimed_ranks: u16,
+    pub player_claimed_ranks: u16,
     pub bump: u8,
+    pub team_payout_curve: [u8; TEAM_WINNERS_COUNT],
+    pub player_payout_curve: [u8; PLAYER_WINNERS_COUNT],
+    pub ends_with_round: u8,
+    pub team_scoring_mode: u8,
+    pub min_team_active_members: u8,
+    pub team_payout_asset: u8,
+    pub player_payout_asset: u8,
+    pub _padding: [u8; 6],
+}
+
+/// The configuration of a `Period`, chosen in `create_period` and repeated on rollover.
+///
+/// # Fields
+/// - `team_payout_curve`: The percentage of the team rewards paid to each team rank, summing to 100.
+/// - `player_payout_curve`: The percentage of the individual rewards paid to each player rank, summing to 100.
+/// - `ends_with_round`: Whether the period is truncated when the current round ends.
+/// - `team_scoring_mode`: How the team leaderboard is scored.
+/// - `min_team_active_members`: The members who must purchase during the period for a team to be ranked.
+/// - `min_team_purchased_ores`: The ores a team must purchase during the period to be ranked.
+/// - `team_payout_asset`, `player_payout_asset`: Whether the team and individual rank rewards are paid in tokens or vouchers.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PeriodConfig {
+    pub team_payout_curve: [u8; TEAM_WINNERS_COUNT],
+    pub player_payout_curve: [u8; PLAYER_WINNERS_COUNT],
+    pub ends_with_round: bool,
+    pub team_scoring_mode: TeamScoringMode,
+    pub min_team_active_members: u8,
+    pub min_team_purchased_ores: u32,
+    pub team_payout_asset: PayoutAsset,
+    pub player_payout_asset: PayoutAsset,
+}
+
+/// The asset leaderboard rank rewards are paid in.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+#[repr(u8)]
+pub enum PayoutAsset {
+    /// Liquid game tokens.
+    #[default]
+    Token = 0,
+    /// Vouchers minted against the rewards, encouraging their reinvestment.
+    Voucher = 1,
 }
 
 /// Represents a top-performing player in the `Period`.
 /// Each entry stores the player's public key and their total purchased ores,
 /// which serve as a performance metric.
-#[derive(Debug, InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Default)]
+#[zero_copy]
+#[derive(Debug, Default)]
 pub struct TopPlayerAccount {
     /// The public key of the player
     pub player: Pubkey,
@@ -70,7 +135,8 @@ pub struct TopPlayerAccount {
 /// Represents a top-performing team in the `Period`.
 /// Each entry stores the team's public key and total purchased ores,
 /// reflecting collective team performance.
-#[derive(Debug, InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Default)]
+#[zero_copy]
+#[derive(Debug, Default)]
 pub struct TopTeamAccount {
     /// The public key of the team
     pub team: Pubkey,
@@ -113,13 +179,6 @@ impl Period {
             .checked_add(leaderboard_duration)
             .ok_or(ErrorCode::InvalidTimestamp)?;
 
-        // Compute the distribution for first, second, and third place teams
-        let team_first_place_rewards = team_rewards.safe_div(2)?;
-        let team_second_place_rewards = team_first_place_rewards.safe_div(5)?.safe_mul(3)?;
-        let team_third_place_rewards = team_rewards
-            .safe_sub(team_first_place_rewards)?
-            .safe_sub(team_second_place_rewards)?;
-
         *self = Period {
             period_number,
             period_vault,
@@ -128,26 +187,31 @@ impl Period {
             team_reward_pool_balance: team_rewards,
             individual_reward_pool_balance: individual_rewards,
             team_rewards,
-            team_first_place_rewards,
-            team_second_place_rewards,
-            team_third_place_rewards,
             individual_rewards,
-            top_player_list: vec![
-                TopPlayerAccount {
-                    player: default_player,
-                    purchased_ores: 0,
-                };
-                PLAYER_WINNERS_COUNT
-            ],
-            top_team_list: vec![
-                TopTeamAccount {
-                    team: default_team,
-                    purchased_ores: 0,
-                };
-                TEAM_WINNERS_COUNT
-            ],
+            team_rank_rewards: [0; TEAM_WINNERS_COUNT],
+            player_rank_rewards: [0; PLAYER_WINNERS_COUNT],
+            top_player_list: [TopPlayerAccount {
+                player: default_player,
+                purchased_ores: 0,
+            }; PLAYER_WINNERS_COUNT],
+            top_team_list: [TopTeamAccount {
+                team: default_team,
+                purchased_ores: 0,
+            }; TEAM_WINNERS_COUNT],
+            leaderboard_page_floor: 0,
+            leaderboard_page_count: 0,
+            team_claimed_ranks: 0,
+            player_claimed_ranks: 0,
             bump,
-            ..Default::default()
+            team_payout_curve: [0; TEAM_WINNERS_COUNT],
+            player_payout_curve: [0; PLAYER_WINNERS_COUNT],
+            ends_with_round: 0,
+            team_scoring_mode: TeamScoringMode::TotalOres as u8,
+            min_team_purchased_ores: 0,
+            min_team_active_members: 0,
+            team_payout_asset: PayoutAsset::Token as u8,
+            player_payout_asset: PayoutAsset::Token as u8,
+            _padding: [0; 6],
         };
 
         Ok(())
@@ -165,55 +229,42 @@ impl Period {
     }
 
     /// Updates or inserts a player's record in the top player list based on purchased ores.
-    /// If the player already exists, their ores count is updated; otherwise, a new entry is added.
-    /// After updating, the list is re-sorted to maintain the ordering by purchased ores in descending order.
+    /// If the player already exists, their ores count is updated; otherwise, the player replaces the last entry
+    /// if they outperform it. The entry is then moved in place to keep the list sorted in descending order.
     ///
     /// # Arguments
     /// - `player`: The public key of the player.
     /// - `purchased_ores`: The updated purchased ore count for this player.
     pub fn update_top_player(&mut self, player: Pubkey, purchased_ores: u32) -> Result<()> {
-        if let Some(existing_player) = self.top_player_list.iter_mut().find(|p| p.player == player)
-        {
-            existing_player.purchased_ores = purchased_ores;
-        } else {
-            self.top_player_list.push(TopPlayerAccount {
+        upsert_sorted(
+            &mut self.top_player_list,
+            |p| p.player == player,
+            |p| p.purchased_ores,
+            TopPlayerAccount {
                 player,
                 purchased_ores,
-            });
-        }
-
-        self.top_player_list
-            .sort_by(|a, b| b.purchased_ores.cmp(&a.purchased_ores));
-
-        if self.top_player_list.len() > PLAYER_WINNERS_COUNT {
-            self.top_player_list.truncate(PLAYER_WINNERS_COUNT);
-        }
+            },
+        );
 
         Ok(())
     }
 
-    /// Similar to `update_top_player`, updates or inserts a team record based on purchased ores.
-    /// After updating or inserting, the list is sorted to keep top teams in descending order of performance.
+    /// Similar to `update_top_player`, updates or inserts a team record based on purchased ores,
+    /// keeping the top teams in descending order of performance.
     ///
     /// # Arguments
     /// - `team`: The public key of the team.
     /// - `purchased_ores`: The updated purchased ore count for this team.
     pub fn update_top_team_list(&mut self, team: Pubkey, purchased_ores: u32) -> Result<()> {
-        if let Some(existing_team) = self.top_team_list.iter_mut().find(|s| s.team == team) {
-            existing_team.purchased_ores = purchased_ores;
-        } else {
-            self.top_team_list.push(TopTeamAccount {
+        upsert_sorted(
+            &mut self.top_team_list,
+            |t| t.team == team,
+            |t| t.purchased_ores,
+            TopTeamAccount {
                 team,
                 purchased_ores,
-            });
-        }
-
-        self.top_team_list
-            .sort_by(|a, b| b.purchased_ores.cmp(&a.purchased_ores));
-
-        if self.top_team_list.len() > TEAM_WINNERS_COUNT {
-            self.top_team_list.truncate(TEAM_WINNERS_COUNT);
-        }
+            },
+        );
 
         Ok(())
     }
@@ -229,14 +280,258 @@ impl Period {
         current_time >= self.end_time
     }
 
-    /// Marks this period's rewards distribution as completed.
-    /// Fails if distribution was already marked as completed, ensuring that rewards cannot be granted twice.
-    pub fn mark_distribution_completed(&mut self) -> Result<()> {
+    /// Applies the period configuration and splits the team and individual reward pools across the ranks
+    /// of the leaderboards. Any rounding remainder goes to the first rank.
+    ///
+    /// # Arguments
+    /// - `config`: The payout curves, round coupling, team scoring mode and team qualification thresholds.
+    pub fn configure(&mut self, config: &PeriodConfig) -> Result<()> {
+        self.team_rank_rewards =
+            calculate_rank_rewards(self.team_reward_pool_balance, &config.team_payout_curve)?
+                .ok_or(ErrorCode::InvalidPayoutCurve)?;
+        self.player_rank_rewards = calculate_rank_rewards(
+            self.individual_reward_pool_balance,
+            &config.player_payout_curve,
+        )?
+        .ok_or(ErrorCode::InvalidPayoutCurve)?;
+        self.team_payout_curve = config.team_payout_curve;
+        self.player_payout_curve = config.player_payout_curve;
+        self.ends_with_round = config.ends_with_round as u8;
+        self.team_scoring_mode = config.team_scoring_mode as u8;
+        self.min_team_active_members = config.min_team_active_members;
+        self.min_team_purchased_ores = config.min_team_purchased_ores;
+        self.team_payout_asset = config.team_payout_asset as u8;
+        self.player_payout_asset = config.player_payout_asset as u8;
+
+        Ok(())
+    }
+
+    /// Returns the configuration of the period, to be repeated by the next period on rollover.
+    pub fn config(&self) -> PeriodConfig {
+        PeriodConfig {
+            team_payout_curve: self.team_payout_curve,
+            player_payout_curve: self.player_payout_curve,
+            ends_with_round: self.ends_with_round != 0,
+            team_scoring_mode: self.team_scoring_mode(),
+            min_team_active_members: self.min_team_active_members,
+            min_team_purchased_ores: self.min_team_purchased_ores,
+            team_payout_asset: self.payout_asset(true),
+            player_payout_asset: self.payout_asset(false),
+        }
+    }
+
+    /// Adds the rewards carried over from the previous period to the reward pools.
+    /// Must be called before `configure`, which splits the pools across the ranks.
+    ///
+    /// # Arguments
+    /// - `team_rewards`: The team rewards carried over.
+    /// - `individual_rewards`: The individual rewards carried over.
+    pub fn add_carried_over_rewards(
+        &mut self,
+        team_rewards: u64,
+        individual_rewards: u64,
+    ) -> Result<()> {
+        self.team_reward_pool_balance = self.team_reward_pool_balance.safe_add(team_rewards)?;
+        self.individual_reward_pool_balance = self
+            .individual_reward_pool_balance
+            .safe_add(individual_rewards)?;
+
+        Ok(())
+    }
+
+    /// Whether the team meets the period's participation thresholds to enter `top_team_list`.
+    pub fn is_team_qualified(&self, team: &Team) -> bool {
+        team.current_period_active_members >= self.min_team_active_members as u16
+            && team.current_period_purchased_ores >= self.min_team_purchased_ores
+    }
+
+    /// Takes the rewards of the ranks still held by the default team or player, which nobody can claim,
+    /// marking them as claimed so they can be carried over to the next period.
+    ///
+    /// # Arguments
+    /// - `default_team`: The default team padding `top_team_list`.
+    /// - `default_player`: The default player padding `top_player_list`.
+    ///
+    /// # Returns
+    /// The unawarded team and individual rewards.
+    pub fn take_unawarded_rewards(
+        &mut self,
+        default_team: Pubkey,
+        default_player: Pubkey,
+    ) -> Result<(u64, u64)> {
+        let mut team_rewards: u64 = 0;
+        for (rank, entry) in self.top_team_list.iter().enumerate() {
+            let rank_bit = 1u16 << rank;
+            if entry.team == default_team && self.team_claimed_ranks & rank_bit == 0 {
+                self.team_claimed_ranks |= rank_bit;
+                team_rewards = team_rewards.safe_add(self.team_rank_rewards[rank])?;
+            }
+        }
+
+        let mut individual_rewards: u64 = 0;
+        for (rank, entry) in self.top_player_list.iter().enumerate() {
+            let rank_bit = 1u16 << rank;
+            if entry.player == default_player && self.player_claimed_ranks & rank_bit == 0 {
+                self.player_claimed_ranks |= rank_bit;
+                individual_rewards = individual_rewards.safe_add(self.player_rank_rewards[rank])?;
+            }
+        }
+
+        Ok((team_rewards, individual_rewards))
+    }
+
+    /// Returns the rewards of the team and player ranks not yet claimed, which the `period_vault` must hold.
+    pub fn unclaimed_rewards(&self) -> Result<u64> {
+        let mut unclaimed_rewards: u64 = 0;
+        for (rank, rewards) in self.team_rank_rewards.iter().enumerate() {
+            if self.team_claimed_ranks & (1u16 << rank) == 0 {
+                unclaimed_rewards = unclaimed_rewards.safe_add(*rewards)?;
+            }
+        }
+        for (rank, rewards) in self.player_rank_rewards.iter().enumerate() {
+            if self.player_claimed_ranks & (1u16 << rank) == 0 {
+                unclaimed_rewards = unclaimed_rewards.safe_add(*rewards)?;
+            }
+        }
+        Ok(unclaimed_rewards)
+    }
+
+    /// Returns the asset the rank rewards of the team or player leaderboard are paid in.
+    pub fn payout_asset(&self, is_team_leaderboard: bool) -> PayoutAsset {
+        let payout_asset = if is_team_leaderboard {
+            self.team_payout_asset
+        } else {
+            self.player_payout_asset
+        };
+        match payout_asset {
+            1 => PayoutAsset::Voucher,
+            _ => PayoutAsset::Token,
+        }
+    }
+
+    /// Returns how the team leaderboard is scored during the period.
+    pub fn team_scoring_mode(&self) -> TeamScoringMode {
+        match self.team_scoring_mode {
+            1 => TeamScoringMode::AveragePerMember,
+            2 => TeamScoringMode::TopMembers,
+            _ => TeamScoringMode::TotalOres,
+        }
+    }
+
+    /// Claims the rewards of a leaderboard rank once the period has ended.
+    /// Each rank can be claimed once, and only by the team or player holding it in the final standings.
+    ///
+    /// # Arguments
+    /// - `is_team_leaderboard`: Whether the rank is on the team leaderboard rather than the player leaderboard.
+    /// - `rank`: The 0-based rank being claimed.
+    /// - `recipient`: The team or player claiming the rank.
+    /// - `current_time`: A UNIX timestamp representing the current time.
+    ///
+    /// # Returns
+    /// The rewards allocated to the rank.
+    pub fn claim_rank_rewards(
+        &mut self,
+        is_team_leaderboard: bool,
+        rank: u8,
+        recipient: Pubkey,
+        current_time: u64,
+    ) -> Result<u64> {
+        require!(self.is_ended(current_time), ErrorCode::PeriodNotEnded);
+
+        let index = rank as usize;
+        let (holder, rewards, claimed_ranks) = if is_team_leaderboard {
+            require!(
+                index < TEAM_WINNERS_COUNT,
+                ErrorCode::InvalidLeaderboardRank
+            );
+            (
+                self.top_team_list[index].team,
+                self.team_rank_rewards[index],
+                &mut self.team_claimed_ranks,
+            )
+        } else {
+            require!(
+                index < PLAYER_WINNERS_COUNT,
+                ErrorCode::InvalidLeaderboardRank
+            );
+            (
+                self.top_player_list[index].player,
+                self.player_rank_rewards[index],
+                &mut self.player_claimed_ranks,
+            )
+        };
+        require_keys_eq!(holder, recipient, ErrorCode::NotLeaderboardRankHolder);
+
+        let rank_bit = 1u16 << rank;
         require!(
-            !self.is_distribution_completed,
+            *claimed_ranks & rank_bit == 0,
             ErrorCode::AlreadyDistributed
         );
-        self.is_distribution_completed = true;
-        Ok(())
+        *claimed_ranks |= rank_bit;
+
+        Ok(rewards)
+    }
+
+    /// Registers a new leaderboard page directly below the existing ones.
+    /// Pages tile the score range from the top down without gaps or overlaps: the first page reaches
+    /// `u32::MAX` and each next page ends right below the floor of the previous one.
+    ///
+    /// # Arguments
+    /// - `min_score`: The lowest score covered by the new page.
+    ///
+    /// # Returns
+    /// The index and the highest score covered by the new page.
+    pub fn add_leaderboard_page(&mut self, min_score: u32) -> Result<(u16, u32)> {
+        let max_score = if self.leaderboard_page_count == 0 {
+            u32::MAX
+        } else {
+            self.leaderboard_page_floor
+                .checked_sub(1)
+                .ok_or(ErrorCode::InvalidLeaderboardPageRange)?
+        };
+        require!(
+            min_score <= max_score,
+            ErrorCode::InvalidLeaderboardPageRange
+        );
+
+        let page_index = self.leaderboard_page_count;
+        self.leaderboard_page_count = self.leaderboard_page_count.safe_add(1)?;
+        self.leaderboard_page_floor = min_score;
+
+        Ok((page_index, max_score))
+    }
+}
+
+/// Updates or inserts `entry` in a leaderboard sorted by descending score, in place.
+/// An entry that is not on the leaderboard replaces the last one only if it scores strictly higher,
+/// so earlier entries win ties as they did with a stable sort.
+fn upsert_sorted<T: Copy>(
+    list: &mut [T],
+    is_entry: impl Fn(&T) -> bool,
+    score: impl Fn(&T) -> u32,
+    entry: T,
+) {
+    let last = list.len() - 1;
+    let mut index = match list.iter().position(&is_entry) {
+        Some(index) => index,
+        None if score(&entry) > score(&list[last]) => last,
+        None => return,
+    };
+    list[index] = entry;
+
+    // Move the entry up while it outscores its predecessor, or down while its successor outscores it
+    while index > 0 && score(&list[index - 1]) < score(&list[index]) {
+        list.swap(index - 1, index);
+        index -= 1;
+    }
+    while index < last && score(&list[index + 1]) > score(&list[index]) {
+        list.swap(index, index + 1);
+        index += 1;
+    }
+}
+
+impl VaultAuthority for Period {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[PERIOD_SEED, &self.period_number.to_le_bytes(), &[bump]])
     }
 }
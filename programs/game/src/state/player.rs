@@ -1,9 +1,29 @@
+use crate::constants::{
+    LOCK_DURATION, MAX_STAKE_BOOST_BPS, PURCHASE_VOLUME_WINDOW_COUNT, PURCHASE_VOLUME_WINDOW_DAYS,
+    REFERRAL_REWARDS_EXPIRY_SECONDS, RESPONSIBLE_PLAY_COOLDOWN_SECONDS, SECONDS_PER_DAY,
+    STAKE_BOOST_FULL_BALANCE, STREAK_SHIELD_DURATION,
+};
 use crate::errors::ErrorCode;
+use crate::state::{Round, StakeOrder};
+use crate::utils::{
+    calculate_accrued_earnings, calculate_airdrop_rewards, calculate_stake_boost,
+    is_limit_loosened, timestamp_to_days,
+};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 
+/// Number of team applications `PlayerData` has room for when created.
 const MAX_TEAM_APPLICATIONS: usize = 3;
 
+/// Number of team applications a player may hold at once, growing `PlayerData` beyond `MAX_TEAM_APPLICATIONS` on demand.
+const TEAM_APPLICATIONS_LIMIT: usize = 10;
+
+/// Number of team rejections `PlayerData` remembers, each barring a new application to the team until its cooldown ends.
+const TEAM_REJECTION_HISTORY: usize = 3;
+
+/// Number of referral reward credits `PlayerData` timestamps, the oldest being merged into the next once it is full.
+const REFERRAL_REWARD_CREDIT_HISTORY: usize = 8;
+
 /// The `PlayerData` account maintains state for an individual player within the game.
 /// It tracks the player's associated accounts, their team status, referral relationships,
 /// participation in rounds and periods, and various types of rewards (referral, construction, grand prize, lottery, etc.).
@@ -15,15 +35,23 @@ const MAX_TEAM_APPLICATIONS: usize = 3;
 /// - `voucher_account`: The player's voucher account representing staked or deposited tokens.
 /// - `team`: The public key of the team the player currently belongs to. If this is the `default_team`, the player is effectively team-less.
 /// - `team_applications`: A list of teams to which the player has applied but not yet joined.
-///   Limited by `MAX_TEAM_APPLICATIONS` to prevent spam and complexity.
+///   Room for `MAX_TEAM_APPLICATIONS` is allocated up front; the account is reallocated when a player applies beyond it,
+///   up to `TEAM_APPLICATIONS_LIMIT` to prevent spam and complexity.
 /// - `can_apply_to_team_timestamp`: A UNIX timestamp indicating when the player can next apply to a team.
 ///   Useful for cooldowns or preventing immediate re-application after leaving a team.
+/// - `team_application_day`, `team_applications_today`: Track the team applications submitted on the current day,
+///   enforcing the game's daily cap.
+/// - `team_rejections`: The most recent teams that rejected the player, with when the player may apply to each again.
 /// - `referrer`: The public key of the entity who referred this player, if any.
 /// - `referral_count`: How many players this player has referred.
 /// - `collectable_referral_rewards`: Accumulated referral rewards not yet collected.
 /// - `collected_referral_rewards`: Total referral rewards already collected by this player.
+/// - `referral_reward_credits`: The referral rewards not yet collected, by the day they were credited and oldest first,
+///   expiring `REFERRAL_REWARDS_EXPIRY_SECONDS` after their credit. Unused slots are left zeroed at the end.
 /// - `current_round`, `current_period`: Identify which round and period the player is currently participating in, used for calculating round/period-specific earnings.
 /// - `current_period_purchased_ores`: The amount of ores purchased by this player in the current period, used for leaderboard or reward calculations.
+/// - `previous_period`, `previous_period_purchased_ores`: The period before `current_period` the player purchased ores in,
+///   and the ores purchased in it, counted as governance voting power.
 /// - `earnings_per_ore`: The player's current earnings rate per ore unit in the ongoing round.
 /// - `collectable_construction_rewards`, `collected_construction_rewards`: Track construction-related rewards (e.g., rewards from building game infrastructure).
 /// - `collected_grand_prizes`: Total grand prizes that the player has already claimed.
@@ -32,14 +60,36 @@ const MAX_TEAM_APPLICATIONS: usize = 3;
 /// - `is_auto_reinvesting`: Indicates whether earnings are automatically reinvested for compounding returns.
 /// - `consecutive_purchased_days`: How many consecutive days the player has made a purchase, useful for streak-based rewards.
 /// - `last_purchased_day`: The most recent day on which the player purchased ores, helping track consecutive purchase streaks.
+/// - `purchase_volume_windows`, `last_purchase_volume_window`: The tokens and vouchers spent on ORE in each of the last
+///   `PURCHASE_VOLUME_WINDOW_COUNT` rolling windows of `PURCHASE_VOLUME_WINDOW_DAYS` days, indexed by window number modulo
+///   their count, and the number of the latest window recorded; their sum is the purchase volume selecting the consumption reward tier.
+/// - `has_streak_shield`, `streak_shield_expiry`: Whether the player holds a streak shield, preserving their
+///   `consecutive_purchased_days` over one missed day, and the timestamp until which it can be used.
 /// - `last_collected_airdrop_reward_day`: The day on which the player last collected airdrop rewards, enforcing daily airdrop limits.
 /// - `collected_airdrop_rewards`: How many airdrop rewards the player has accumulated so far.
-/// - `randomness_provider`, `commit_slot`, `spin_symbols`, `result_multiplier`, `result_revealed`:
-///   Fields tracking the player's lottery spin or randomness-based game interactions, including the randomness provider account and the outcome of a spin.
+/// - `randomness_provider`, `lottery_table`, `commit_slot`, `spin_symbols`, `result_multiplier`, `result_revealed`:
+///   Fields tracking the player's lottery spin or randomness-based game interactions, including the randomness provider account,
+///   the lottery table drawn on and the outcome of a spin.
 /// - `collectable_consumption_rewards`, `collected_consumption_rewards`: Track rewards based on player consumption or spending behavior in the game.
 /// - `is_exited`: Indicates whether the player has exited the game, resetting round participation and disabling certain activities.
+/// - `exit_rewards_per_ore`, `collectable_exit_rewards`: The round's exit rewards per ORE last settled for the player, and the exit rewards settled so far, paid out on exit.
 /// - `collected_exit_rewards`: Total exit rewards collected by the player.
+/// - `carryover_balance`: Construction rewards settled from a finished round and carried over, spent by the player's first purchase of the next round.
 /// - `collected_lottery_rewards`, `collected_individual_rewards`, `collected_team_rewards`: Tally various categories of collected rewards for accounting and analytics.
+/// - `purchase_count`, `tap_count`, `draw_count`, `stake_count`: Count the player's purchases, candy taps, lottery draws and stakes for analytics.
+/// - `stake_boost_amount`, `stake_boost_lock_end_weight`: The amount staked by the player's active stake orders, and the sum
+///   of each order's amount times its lock end, from which the vote-escrowed stake is derived at any time.
+/// - `boost_factor_bps`: The boost, in basis points, the player's vote-escrowed stake granted to construction rewards
+///   when last refreshed on a stake, unstake or purchase.
+/// - `last_candy_tap_timestamp`: The UNIX timestamp of the player's last candy tap, enforcing the candy tap cooldown.
+/// - `daily_ore_limit`, `daily_draw_limit`: The opt-in limits on the ores purchased and lottery draws per day, zero for unlimited.
+/// - `pending_daily_ore_limit`, `pending_daily_draw_limit`, `spend_limit_unlock_timestamp`: Looser limits requested by the player,
+///   taking effect at the unlock timestamp once the responsible play cooldown has passed; a zero timestamp means none are pending.
+/// - `last_spend_day`, `current_day_purchased_ores`, `current_day_draw_count`: Track the ores purchased and lottery draws on the current day,
+///   enforcing the daily limits.
+/// - `self_excluded_until`: The UNIX timestamp until which the player excluded themselves from purchases and lottery draws.
+/// - `vesting_grand_prizes`: The first-place grand prizes streamed to the player through a `PrizeVesting` and not claimed yet.
+/// - `last_exchanged_day`, `current_day_exchanged_amount`: Track the tokens exchanged for vouchers on the current day, enforcing the daily exchange limit.
 /// - `nonce`: A counter used for generating unique PDAs or other player-specific keys.
 #[account]
 #[derive(Debug, Default, InitSpace)]
@@ -55,21 +105,30 @@ pub struct PlayerData {
     #[max_len(MAX_TEAM_APPLICATIONS)]
     pub team_applications: Vec<Pubkey>,
     pub can_apply_to_team_timestamp: u64,
+    pub team_application_day: u32,
+    pub team_applications_today: u8,
+    pub team_rejections: [TeamRejection; TEAM_REJECTION_HISTORY],
 
     // Referral related
     pub referrer: Pubkey,
     pub referral_count: u16,
     pub collectable_referral_rewards: u64,
     pub collected_referral_rewards: u64,
+    pub referral_reward_credits: [ReferralRewardCredit; REFERRAL_REWARD_CREDIT_HISTORY],
 
     // Round & Period related
     pub current_round: Pubkey,
     pub current_period: Pubkey,
     pub current_period_purchased_ores: u32,
+    pub previous_period: Pubkey,
+    pub previous_period_purchased_ores: u32,
     pub is_exited: bool,
 
-    pub earnings_per_ore: u64,
+    pub earnings_per_ore: u128,
     pub collectable_construction_rewards: u64,
+    pub exit_rewards_per_ore: u128,
+    pub collectable_exit_rewards: u64,
+    pub carryover_balance: u64,
 
     // Ore related
     pub available_ores: u32,
@@ -79,6 +138,10 @@ pub struct PlayerData {
     // Purchase tracking
     pub consecutive_purchased_days: u16,
     pub last_purchased_day: u32,
+    pub purchase_volume_windows: [u64; PURCHASE_VOLUME_WINDOW_COUNT],
+    pub last_purchase_volume_window: u32,
+    pub has_streak_shield: bool,
+    pub streak_shield_expiry: u64,
 
     // Airdrop related
     pub last_collected_airdrop_reward_day: u32,
@@ -86,6 +149,7 @@ pub struct PlayerData {
 
     // Randomness & Spin related
     pub randomness_provider: Pubkey,
+    pub lottery_table: Pubkey,
     pub commit_slot: u64,
     pub spin_symbols: [u8; 3],
     pub result_multiplier: u16,
@@ -100,6 +164,38 @@ pub struct PlayerData {
     pub collected_lottery_rewards: u64,
     pub collected_individual_rewards: u64,
     pub collected_team_rewards: u64,
+
+    // Activity counters
+    pub purchase_count: u32,
+    pub tap_count: u32,
+    pub draw_count: u32,
+    pub stake_count: u32,
+
+    // Stake boost related
+    pub stake_boost_amount: u64,
+    pub stake_boost_lock_end_weight: u128,
+    pub boost_factor_bps: u16,
+
+    // Candy tap related
+    pub last_candy_tap_timestamp: u64,
+
+    // Responsible play related
+    pub daily_ore_limit: u32,
+    pub daily_draw_limit: u32,
+    pub pending_daily_ore_limit: u32,
+    pub pending_daily_draw_limit: u32,
+    pub spend_limit_unlock_timestamp: u64,
+    pub last_spend_day: u32,
+    pub current_day_purchased_ores: u32,
+    pub current_day_draw_count: u32,
+    pub self_excluded_until: u64,
+
+    // Prize vesting related
+    pub vesting_grand_prizes: u64,
+
+    // Collateral exchange related
+    pub last_exchanged_day: u32,
+    pub current_day_exchanged_amount: u64,
 }
 
 impl PlayerData {
@@ -168,7 +264,19 @@ impl PlayerData {
 
     /// Checks if the team application list is currently full.
     pub fn is_team_application_list_full(&self) -> bool {
-        self.team_applications.len() == MAX_TEAM_APPLICATIONS
+        self.team_applications.len() >= TEAM_APPLICATIONS_LIMIT
+    }
+
+    /// Returns the account size needed to hold `team_applications` applications, never below the initial size.
+    pub fn space_for_team_applications(team_applications: usize) -> usize {
+        8 + PlayerData::INIT_SPACE + 32 * team_applications.saturating_sub(MAX_TEAM_APPLICATIONS)
+    }
+
+    /// The UNIX timestamp of the player's last sign of activity: the start of their last purchase day,
+    /// or their last candy tap if later.
+    pub fn last_activity_timestamp(&self) -> Result<u64> {
+        let last_purchase_timestamp = (self.last_purchased_day as u64).safe_mul(SECONDS_PER_DAY)?;
+        Ok(last_purchase_timestamp.max(self.last_candy_tap_timestamp))
     }
 
     /// Joins a given team, updating the player's current `team` field.
@@ -191,7 +299,37 @@ impl PlayerData {
     }
 
     /// Applies to join a new team, adding it to the player's application list if space is available and not already present.
-    pub fn apply_to_join_team(&mut self, team: Pubkey) -> Result<()> {
+    /// The application counts toward the daily cap, and is refused while a rejection by the team is on cooldown.
+    ///
+    /// # Arguments
+    /// - `team`: The team applied to.
+    /// - `max_team_applications_per_day`: The team applications a player may submit per day.
+    /// - `timestamp`: The current UNIX timestamp.
+    pub fn apply_to_join_team(
+        &mut self,
+        team: Pubkey,
+        max_team_applications_per_day: u8,
+        timestamp: u64,
+    ) -> Result<()> {
+        require!(
+            !self
+                .team_rejections
+                .iter()
+                .any(|rejection| rejection.team == team
+                    && rejection.can_reapply_timestamp > timestamp),
+            ErrorCode::TeamReapplicationCooldown
+        );
+
+        let today = timestamp_to_days(timestamp)?;
+        if self.team_application_day != today {
+            self.team_application_day = today;
+            self.team_applications_today = 0;
+        }
+        require!(
+            self.team_applications_today < max_team_applications_per_day,
+            ErrorCode::TeamApplicationDailyCapReached
+        );
+
         require!(
             !self.is_team_application_list_full(),
             ErrorCode::PlayerTeamApplicationListFull
@@ -202,6 +340,7 @@ impl PlayerData {
         );
 
         self.team_applications.push(team);
+        self.team_applications_today = self.team_applications_today.safe_add(1)?;
         Ok(())
     }
 
@@ -216,13 +355,38 @@ impl PlayerData {
         Ok(())
     }
 
-    /// Rejects a team application by removing it from the application list.
-    pub fn reject_team_application(&mut self, team: Pubkey) -> Result<()> {
+    /// Rejects a team application by removing it from the application list, and remembers the rejection
+    /// in place of the one with the earliest cooldown end, barring a new application to the team until
+    /// `can_reapply_timestamp`.
+    pub fn reject_team_application(
+        &mut self,
+        team: Pubkey,
+        can_reapply_timestamp: u64,
+    ) -> Result<()> {
         require!(
             self.is_team_application_list_contains(team),
             ErrorCode::PlayerTeamApplicationNotFound
         );
         self.cancel_team_application(team)?;
+
+        let slot = match self
+            .team_rejections
+            .iter()
+            .position(|rejection| rejection.team == team)
+        {
+            Some(index) => index,
+            None => self
+                .team_rejections
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, rejection)| rejection.can_reapply_timestamp)
+                .map(|(index, _)| index)
+                .unwrap_or(0),
+        };
+        self.team_rejections[slot] = TeamRejection {
+            team,
+            can_reapply_timestamp,
+        };
         Ok(())
     }
 
@@ -238,11 +402,336 @@ impl PlayerData {
         Ok(())
     }
 
-    /// Adds referral rewards to the player's pending referral rewards balance.
-    pub fn add_collectable_referral_rewards(&mut self, referral_rewards: u64) -> Result<()> {
+    /// Adds referral rewards to the player's pending referral rewards balance, timestamping the credit so it expires
+    /// `REFERRAL_REWARDS_EXPIRY_SECONDS` later. Credits of the same day share a slot, taking the latest timestamp;
+    /// once every slot is used, the oldest credit is merged into the next one, deferring its expiry rather than advancing any.
+    ///
+    /// # Arguments
+    /// - `referral_rewards`: The referral rewards credited.
+    /// - `timestamp`: The current UNIX timestamp.
+    pub fn add_collectable_referral_rewards(
+        &mut self,
+        referral_rewards: u64,
+        timestamp: u64,
+    ) -> Result<()> {
+        if referral_rewards == 0 {
+            return Ok(());
+        }
         self.collectable_referral_rewards = self
             .collectable_referral_rewards
             .safe_add(referral_rewards)?;
+
+        let credit_count = self.referral_reward_credit_count();
+        if let Some(latest) = credit_count
+            .checked_sub(1)
+            .map(|index| &mut self.referral_reward_credits[index])
+        {
+            if timestamp_to_days(latest.credited_timestamp)? == timestamp_to_days(timestamp)? {
+                latest.amount = latest.amount.safe_add(referral_rewards)?;
+                latest.credited_timestamp = timestamp;
+                return Ok(());
+            }
+        }
+
+        let index = if credit_count == REFERRAL_REWARD_CREDIT_HISTORY {
+            let oldest = self.referral_reward_credits[0].amount;
+            self.referral_reward_credits[1].amount =
+                self.referral_reward_credits[1].amount.safe_add(oldest)?;
+            self.referral_reward_credits.rotate_left(1);
+            credit_count - 1
+        } else {
+            credit_count
+        };
+        self.referral_reward_credits[index] = ReferralRewardCredit {
+            amount: referral_rewards,
+            credited_timestamp: timestamp,
+        };
+        Ok(())
+    }
+
+    /// Removes the referral reward credits older than `REFERRAL_REWARDS_EXPIRY_SECONDS` from the player's pending
+    /// referral rewards balance. Rewards credited before credits were timestamped never expire.
+    ///
+    /// # Returns
+    /// The referral rewards expired, zero if none were.
+    pub fn expire_referral_rewards(&mut self, timestamp: u64) -> Result<u64> {
+        let credit_count = self.referral_reward_credit_count();
+        let expired_count = self.referral_reward_credits[..credit_count]
+            .iter()
+            .take_while(|credit| {
+                credit
+                    .credited_timestamp
+                    .saturating_add(REFERRAL_REWARDS_EXPIRY_SECONDS)
+                    <= timestamp
+            })
+            .count();
+
+        let mut expired_referral_rewards: u64 = 0;
+        for credit in &self.referral_reward_credits[..expired_count] {
+            expired_referral_rewards = expired_referral_rewards.safe_add(credit.amount)?;
+        }
+        self.referral_reward_credits.rotate_left(expired_count);
+        for credit in
+            &mut self.referral_reward_credits[REFERRAL_REWARD_CREDIT_HISTORY - expired_count..]
+        {
+            *credit = ReferralRewardCredit::default();
+        }
+
+        self.collectable_referral_rewards = self
+            .collectable_referral_rewards
+            .safe_sub(expired_referral_rewards)?;
+        Ok(expired_referral_rewards)
+    }
+
+    /// Collects the player's pending referral rewards, clearing their credits.
+    pub fn collect_referral_rewards(&mut self, referral_rewards: u64) -> Result<()> {
+        self.collected_referral_rewards =
+            self.collected_referral_rewards.safe_add(referral_rewards)?;
+        self.collectable_referral_rewards = 0;
+        self.referral_reward_credits = Default::default();
+        Ok(())
+    }
+
+    /// Returns the number of referral reward credits in use.
+    fn referral_reward_credit_count(&self) -> usize {
+        self.referral_reward_credits
+            .iter()
+            .take_while(|credit| credit.amount > 0)
+            .count()
+    }
+
+    /// Records a collateral exchange against the player's daily exchange limit,
+    /// resetting the daily amount when a new day has started.
+    ///
+    /// # Arguments
+    /// - `current_day`: The current day index.
+    /// - `amount`: The amount of tokens being exchanged.
+    /// - `daily_limit`: The per-player daily exchange limit, zero for unlimited.
+    pub fn record_exchange(
+        &mut self,
+        current_day: u32,
+        amount: u64,
+        daily_limit: u64,
+    ) -> Result<()> {
+        if current_day != self.last_exchanged_day {
+            self.last_exchanged_day = current_day;
+            self.current_day_exchanged_amount = 0;
+        }
+
+        let current_day_exchanged_amount = self.current_day_exchanged_amount.safe_add(amount)?;
+        require!(
+            daily_limit == 0 || current_day_exchanged_amount <= daily_limit,
+            ErrorCode::ExceedsDailyExchangeLimit
+        );
+        self.current_day_exchanged_amount = current_day_exchanged_amount;
+
+        Ok(())
+    }
+
+    /// Sets the player's daily spend limits. Tighter limits take effect immediately, while looser ones
+    /// wait for the responsible play cooldown; a new request replaces any pending one.
+    ///
+    /// # Arguments
+    /// - `daily_ore_limit`: The ores the player may purchase per day, zero for unlimited.
+    /// - `daily_draw_limit`: The lottery draws the player may make per day, zero for unlimited.
+    /// - `timestamp`: The current UNIX timestamp.
+    ///
+    /// # Returns
+    /// The timestamp at which the looser limits take effect, or `0` if the limits took effect immediately.
+    pub fn set_spend_limit(
+        &mut self,
+        daily_ore_limit: u32,
+        daily_draw_limit: u32,
+        timestamp: u64,
+    ) -> Result<u64> {
+        self.apply_pending_spend_limit(timestamp);
+
+        let is_ore_limit_loosened = is_limit_loosened(self.daily_ore_limit, daily_ore_limit);
+        let is_draw_limit_loosened = is_limit_loosened(self.daily_draw_limit, daily_draw_limit);
+
+        // Tightened limits take effect immediately
+        if !is_ore_limit_loosened {
+            self.daily_ore_limit = daily_ore_limit;
+        }
+        if !is_draw_limit_loosened {
+            self.daily_draw_limit = daily_draw_limit;
+        }
+
+        if is_ore_limit_loosened || is_draw_limit_loosened {
+            self.pending_daily_ore_limit = daily_ore_limit;
+            self.pending_daily_draw_limit = daily_draw_limit;
+            self.spend_limit_unlock_timestamp =
+                timestamp.safe_add(RESPONSIBLE_PLAY_COOLDOWN_SECONDS)?;
+        } else {
+            self.pending_daily_ore_limit = 0;
+            self.pending_daily_draw_limit = 0;
+            self.spend_limit_unlock_timestamp = 0;
+        }
+
+        Ok(self.spend_limit_unlock_timestamp)
+    }
+
+    /// Applies the pending looser spend limits once their unlock timestamp has passed.
+    fn apply_pending_spend_limit(&mut self, timestamp: u64) {
+        if self.spend_limit_unlock_timestamp != 0 && timestamp >= self.spend_limit_unlock_timestamp
+        {
+            self.daily_ore_limit = self.pending_daily_ore_limit;
+            self.daily_draw_limit = self.pending_daily_draw_limit;
+            self.pending_daily_ore_limit = 0;
+            self.pending_daily_draw_limit = 0;
+            self.spend_limit_unlock_timestamp = 0;
+        }
+    }
+
+    /// Excludes the player from purchases and lottery draws until `until_timestamp`. Extending the exclusion
+    /// takes effect immediately, while shortening it cannot end it before the responsible play cooldown has passed.
+    ///
+    /// # Returns
+    /// The UNIX timestamp until which the player is now excluded.
+    pub fn self_exclude(&mut self, until_timestamp: u64, timestamp: u64) -> Result<u64> {
+        self.self_excluded_until = if until_timestamp >= self.self_excluded_until
+            || self.self_excluded_until <= timestamp
+        {
+            until_timestamp
+        } else {
+            let earliest_end = timestamp.safe_add(RESPONSIBLE_PLAY_COOLDOWN_SECONDS)?;
+            until_timestamp
+                .max(earliest_end)
+                .min(self.self_excluded_until)
+        };
+
+        Ok(self.self_excluded_until)
+    }
+
+    /// Resets the daily spend counters when a new day has started, and applies pending looser limits.
+    /// Fails if the player is self-excluded.
+    fn start_spend(&mut self, timestamp: u64) -> Result<()> {
+        require!(
+            timestamp >= self.self_excluded_until,
+            ErrorCode::PlayerSelfExcluded
+        );
+
+        self.apply_pending_spend_limit(timestamp);
+
+        let current_day = timestamp_to_days(timestamp)?;
+        if current_day != self.last_spend_day {
+            self.last_spend_day = current_day;
+            self.current_day_purchased_ores = 0;
+            self.current_day_draw_count = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Records purchased ores against the player's self-exclusion and daily ore limit.
+    pub fn record_spend_on_ores(&mut self, purchased_ores: u32, timestamp: u64) -> Result<()> {
+        self.start_spend(timestamp)?;
+
+        let current_day_purchased_ores =
+            self.current_day_purchased_ores.safe_add(purchased_ores)?;
+        require!(
+            self.daily_ore_limit == 0 || current_day_purchased_ores <= self.daily_ore_limit,
+            ErrorCode::ExceedsDailyOreLimit
+        );
+        self.current_day_purchased_ores = current_day_purchased_ores;
+
+        Ok(())
+    }
+
+    /// Records a lottery draw against the player's self-exclusion and daily draw limit.
+    pub fn record_spend_on_draw(&mut self, timestamp: u64) -> Result<()> {
+        self.start_spend(timestamp)?;
+
+        let current_day_draw_count = self.current_day_draw_count.safe_add(1)?;
+        require!(
+            self.daily_draw_limit == 0 || current_day_draw_count <= self.daily_draw_limit,
+            ErrorCode::ExceedsDailyDrawLimit
+        );
+        self.current_day_draw_count = current_day_draw_count;
+
+        Ok(())
+    }
+
+    /// Returns the tokens and vouchers the player spent on ORE over the last 30 days, within the rolling window granularity.
+    pub fn purchase_volume(&self, timestamp: u64) -> Result<u64> {
+        let current_window = timestamp_to_days(timestamp)? / PURCHASE_VOLUME_WINDOW_DAYS;
+        let elapsed_windows =
+            current_window.saturating_sub(self.last_purchase_volume_window) as usize;
+
+        // Only the windows recorded within the last `PURCHASE_VOLUME_WINDOW_COUNT` still count
+        let mut volume: u64 = 0;
+        for age in elapsed_windows..PURCHASE_VOLUME_WINDOW_COUNT {
+            let Some(window) = current_window.checked_sub(age as u32) else {
+                break;
+            };
+            volume = volume.safe_add(
+                self.purchase_volume_windows[window as usize % PURCHASE_VOLUME_WINDOW_COUNT],
+            )?;
+        }
+        Ok(volume)
+    }
+
+    /// Updates the purchase streak with a purchase on the day of `timestamp`: a purchase the day after the last one
+    /// extends the streak, and so does one after a single missed day while the player holds an unexpired streak shield,
+    /// which it consumes. Any other gap restarts the streak.
+    ///
+    /// # Returns
+    /// Returns whether the streak shield was consumed.
+    pub fn record_purchased_day(&mut self, timestamp: u64) -> Result<bool> {
+        let current_day = timestamp_to_days(timestamp)?;
+        if self.last_purchased_day == current_day {
+            return Ok(false);
+        }
+
+        let is_shield_used = self.last_purchased_day.saturating_add(2) == current_day
+            && self.is_streak_shield_active(timestamp);
+        if self.last_purchased_day.saturating_add(1) == current_day || is_shield_used {
+            self.consecutive_purchased_days = self.consecutive_purchased_days.safe_add(1)?;
+        } else {
+            self.consecutive_purchased_days = 1;
+        }
+        if is_shield_used {
+            self.has_streak_shield = false;
+        }
+        self.last_purchased_day = current_day;
+
+        Ok(is_shield_used)
+    }
+
+    /// Whether the player holds a streak shield that can still be used at `timestamp`.
+    pub fn is_streak_shield_active(&self, timestamp: u64) -> bool {
+        self.has_streak_shield && timestamp < self.streak_shield_expiry
+    }
+
+    /// Gives the player a streak shield usable for `STREAK_SHIELD_DURATION` from `timestamp`.
+    /// A player holds at most one active shield.
+    pub fn buy_streak_shield(&mut self, timestamp: u64) -> Result<()> {
+        require!(
+            !self.is_streak_shield_active(timestamp),
+            ErrorCode::StreakShieldActive
+        );
+
+        self.has_streak_shield = true;
+        self.streak_shield_expiry = timestamp.safe_add(STREAK_SHIELD_DURATION)?;
+        Ok(())
+    }
+
+    /// Adds `amount` tokens and vouchers spent on ORE to the player's current purchase volume window,
+    /// clearing the windows that rolled over since the last purchase.
+    pub fn record_purchase_volume(&mut self, amount: u64, timestamp: u64) -> Result<()> {
+        let current_window = timestamp_to_days(timestamp)? / PURCHASE_VOLUME_WINDOW_DAYS;
+        if current_window != self.last_purchase_volume_window {
+            let elapsed_windows = current_window.saturating_sub(self.last_purchase_volume_window);
+            for age in 0..elapsed_windows.min(PURCHASE_VOLUME_WINDOW_COUNT as u32) {
+                self.purchase_volume_windows
+                    [(current_window - age) as usize % PURCHASE_VOLUME_WINDOW_COUNT] = 0;
+            }
+            self.last_purchase_volume_window = current_window;
+        }
+
+        let window = current_window as usize % PURCHASE_VOLUME_WINDOW_COUNT;
+        self.purchase_volume_windows[window] =
+            self.purchase_volume_windows[window].safe_add(amount)?;
         Ok(())
     }
 
@@ -260,6 +749,18 @@ impl PlayerData {
         Ok(())
     }
 
+    /// Records grand prizes streamed to the player through a `PrizeVesting`.
+    pub fn vest_grand_prizes(&mut self, grand_prizes: u64) -> Result<()> {
+        self.vesting_grand_prizes = self.vesting_grand_prizes.safe_add(grand_prizes)?;
+        Ok(())
+    }
+
+    /// Records the claim of vested grand prizes.
+    pub fn claim_vested_grand_prizes(&mut self, amount: u64) -> Result<()> {
+        self.vesting_grand_prizes = self.vesting_grand_prizes.safe_sub(amount)?;
+        Ok(())
+    }
+
     /// Collects lottery rewards.
     pub fn collect_lottery_rewards(&mut self, lottery_rewards: u64) -> Result<()> {
         self.collected_lottery_rewards =
@@ -281,30 +782,125 @@ impl PlayerData {
         Ok(())
     }
 
+    /// Adds a new stake order to the player's vote-escrowed stake, weighted by its lock end,
+    /// refreshing `boost_factor_bps`.
+    pub fn add_stake_boost(&mut self, stake_order: &StakeOrder, timestamp: u64) -> Result<u16> {
+        self.stake_boost_amount = self.stake_boost_amount.safe_add(stake_order.stake_amount)?;
+        self.stake_boost_lock_end_weight = self
+            .stake_boost_lock_end_weight
+            .safe_add(stake_order.stake_amount as u128 * stake_order.boost_lock_end as u128)?;
+        self.refresh_stake_boost(timestamp)
+    }
+
+    /// Removes a stake order from the player's vote-escrowed stake, once, at the lock end it was added with,
+    /// refreshing `boost_factor_bps`. Orders created before the stake boost were never added and are left as is.
+    pub fn remove_stake_boost(
+        &mut self,
+        stake_order: &mut StakeOrder,
+        timestamp: u64,
+    ) -> Result<u16> {
+        if stake_order.boost_lock_end > 0 {
+            self.stake_boost_amount = self.stake_boost_amount.safe_sub(stake_order.stake_amount)?;
+            self.stake_boost_lock_end_weight = self
+                .stake_boost_lock_end_weight
+                .safe_sub(stake_order.stake_amount as u128 * stake_order.boost_lock_end as u128)?;
+            stake_order.boost_lock_end = 0;
+        }
+        self.refresh_stake_boost(timestamp)
+    }
+
+    /// Recomputes `boost_factor_bps` from the player's vote-escrowed stake at `timestamp`,
+    /// capped at `MAX_STAKE_BOOST_BPS`.
+    pub fn refresh_stake_boost(&mut self, timestamp: u64) -> Result<u16> {
+        self.boost_factor_bps = calculate_stake_boost(
+            self.stake_boost_amount,
+            self.stake_boost_lock_end_weight,
+            timestamp,
+            LOCK_DURATION,
+            STAKE_BOOST_FULL_BALANCE,
+            MAX_STAKE_BOOST_BPS,
+        )?;
+        Ok(self.boost_factor_bps)
+    }
+
     /// Settles pending construction rewards based on changes in `earnings_per_ore`.
     /// This is used, for instance, when an updated earnings rate is applied after a round ends,
     /// enabling additional construction rewards to be calculated.
+    /// Returns the construction rewards settled.
     pub fn settle_collectable_construction_rewards(
         &mut self,
-        round_earnings_per_ore: u64,
-    ) -> Result<()> {
+        round_earnings_per_ore: u128,
+    ) -> Result<u64> {
         let delta_earnings_per_ore = round_earnings_per_ore.safe_sub(self.earnings_per_ore)?;
         let additional_rewards_fraction =
-            delta_earnings_per_ore.safe_mul(self.available_ores as u64)?;
+            calculate_accrued_earnings(delta_earnings_per_ore, self.available_ores)?;
         self.earnings_per_ore = round_earnings_per_ore;
         self.collectable_construction_rewards = self
             .collectable_construction_rewards
             .safe_add(additional_rewards_fraction)?;
+        Ok(additional_rewards_fraction)
+    }
+
+    /// Settles the exit rewards accrued to the player's ORE since the last settlement,
+    /// adding them to `collectable_exit_rewards`.
+    ///
+    /// # Arguments
+    /// - `round_exit_rewards_per_ore`: The round's current exit rewards per ORE.
+    pub fn settle_collectable_exit_rewards(
+        &mut self,
+        round_exit_rewards_per_ore: u128,
+    ) -> Result<()> {
+        let delta_exit_rewards_per_ore =
+            round_exit_rewards_per_ore.safe_sub(self.exit_rewards_per_ore)?;
+        let additional_exit_rewards =
+            calculate_accrued_earnings(delta_exit_rewards_per_ore, self.available_ores)?;
+        self.exit_rewards_per_ore = round_exit_rewards_per_ore;
+        self.collectable_exit_rewards = self
+            .collectable_exit_rewards
+            .safe_add(additional_exit_rewards)?;
+        Ok(())
+    }
+
+    /// Checks the player may add ORE to `round`: they are already holding ORE in it, or they have
+    /// settled out of every previous round. Players who exited `round` itself must re-enter it first.
+    ///
+    /// # Arguments
+    /// - `round`: The round the player adds ORE to.
+    pub fn require_can_join_round(&self, round: Pubkey) -> Result<()> {
+        if self.current_round == round {
+            require!(!self.is_exited, ErrorCode::NeedToReenterRound);
+        } else {
+            require!(self.is_exited, ErrorCode::NeedToSettlePreviousRound);
+        }
         Ok(())
     }
 
-    /// Updates the randomness-related fields, resetting spin symbols and result state.
+    /// Brings a player who exited the round back into it, restarting their earnings from the round's
+    /// current `earnings_per_ore` and `exit_rewards_per_ore`.
+    ///
+    /// # Arguments
+    /// - `round_earnings_per_ore`: The round's current earnings per ORE.
+    /// - `round_exit_rewards_per_ore`: The round's current exit rewards per ORE.
+    pub fn reenter_round(
+        &mut self,
+        round_earnings_per_ore: u128,
+        round_exit_rewards_per_ore: u128,
+    ) -> Result<()> {
+        self.earnings_per_ore = round_earnings_per_ore;
+        self.exit_rewards_per_ore = round_exit_rewards_per_ore;
+        self.is_exited = false;
+        Ok(())
+    }
+
+    /// Updates the randomness-related fields and the lottery table drawn on, resetting spin symbols and result state.
     pub fn update_randomness(
         &mut self,
         randomness_provider: Pubkey,
+        lottery_table: Pubkey,
         commit_slot: u64,
     ) -> Result<()> {
         self.randomness_provider = randomness_provider;
+        self.lottery_table = lottery_table;
         self.commit_slot = commit_slot;
         self.spin_symbols = [0; 3];
         self.result_multiplier = 0;
@@ -315,16 +911,202 @@ impl PlayerData {
     /// Exits the current round, clearing round and period-specific data and resetting certain fields to their default states.
     pub fn exit_round(&mut self) -> Result<()> {
         self.earnings_per_ore = 0;
+        self.exit_rewards_per_ore = 0;
+        self.collectable_exit_rewards = 0;
         self.available_ores = 0;
         self.is_auto_reinvesting = false;
         self.is_exited = true;
         Ok(())
     }
 
+    /// Updates the current period of the player, keeping the ores purchased in the previous one when it changes.
+    pub fn update_current_period(&mut self, current_period: Pubkey) {
+        if self.current_period != current_period {
+            self.previous_period = self.current_period;
+            self.previous_period_purchased_ores = self.current_period_purchased_ores;
+            self.current_period = current_period;
+            self.current_period_purchased_ores = 0;
+        }
+    }
+
+    /// Returns the ores the player purchased during `period`, zero if it is neither their current nor their previous period.
+    pub fn period_purchased_ores(&self, period: Pubkey) -> u32 {
+        if period == Pubkey::default() {
+            0
+        } else if period == self.current_period {
+            self.current_period_purchased_ores
+        } else if period == self.previous_period {
+            self.previous_period_purchased_ores
+        } else {
+            0
+        }
+    }
+
     /// Resets period-specific data without fully exiting the round,
     /// useful when a new period starts and previous period counts should be cleared.
     pub fn reset_period_data(&mut self) -> Result<()> {
         self.current_period_purchased_ores = 0;
         Ok(())
     }
+
+    /// Returns the player's state and purchase streak at `current_time`, for the `view_player_summary` instruction.
+    ///
+    /// # Arguments
+    /// - `current_time`: The current UNIX timestamp.
+    pub fn summary(&self, current_time: u64) -> Result<PlayerSummary> {
+        let current_day = timestamp_to_days(current_time)?;
+        let has_purchased_today = self.last_purchased_day == current_day;
+        // The streak survives until the end of the day after the last purchase, or the day after that while shielded
+        let is_streak_active = has_purchased_today
+            || self.last_purchased_day.saturating_add(1) == current_day
+            || (self.last_purchased_day.saturating_add(2) == current_day
+                && self.is_streak_shield_active(current_time));
+
+        Ok(PlayerSummary {
+            player: self.player,
+            team: self.team,
+            current_round: self.current_round,
+            current_period: self.current_period,
+            is_exited: self.is_exited,
+            available_ores: self.available_ores,
+            purchased_ores: self.purchased_ores,
+            current_period_purchased_ores: self.current_period_purchased_ores,
+            consecutive_purchased_days: if is_streak_active {
+                self.consecutive_purchased_days
+            } else {
+                0
+            },
+            has_purchased_today,
+            is_streak_active,
+            can_collect_airdrop_rewards: has_purchased_today
+                && self.last_collected_airdrop_reward_day != current_day,
+            is_auto_reinvesting: self.is_auto_reinvesting,
+            is_self_excluded: current_time < self.self_excluded_until,
+            purchase_count: self.purchase_count,
+            draw_count: self.draw_count,
+            stake_count: self.stake_count,
+        })
+    }
+
+    /// Returns the rewards the player could collect at `current_time`, settling the construction and exit
+    /// rewards accrued in `round` since the last settlement without recording them, for the
+    /// `view_pending_rewards` instruction.
+    ///
+    /// # Arguments
+    /// - `round`: The player's current round.
+    /// - `current_time`: The current UNIX timestamp.
+    pub fn pending_rewards(&self, round: &Round, current_time: u64) -> Result<PendingRewards> {
+        let (accrued_construction_rewards, accrued_exit_rewards) = if self.is_exited {
+            (0, 0)
+        } else {
+            (
+                calculate_accrued_earnings(
+                    round.earnings_per_ore.safe_sub(self.earnings_per_ore)?,
+                    self.available_ores,
+                )?,
+                calculate_accrued_earnings(
+                    round
+                        .projected_exit_rewards_per_ore(current_time)?
+                        .safe_sub(self.exit_rewards_per_ore)?,
+                    self.available_ores,
+                )?,
+            )
+        };
+
+        let summary = self.summary(current_time)?;
+
+        Ok(PendingRewards {
+            construction_rewards: self
+                .collectable_construction_rewards
+                .safe_add(accrued_construction_rewards)?,
+            exit_rewards: self
+                .collectable_exit_rewards
+                .safe_add(accrued_exit_rewards)?,
+            consumption_rewards: self.collectable_consumption_rewards,
+            referral_rewards: self.collectable_referral_rewards,
+            carryover_balance: self.carryover_balance,
+            airdrop_rewards: if summary.can_collect_airdrop_rewards {
+                calculate_airdrop_rewards(self.consecutive_purchased_days)
+            } else {
+                0
+            },
+        })
+    }
+}
+
+/// A team that rejected the player's application, and when the player may apply to it again.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub struct TeamRejection {
+    pub team: Pubkey,
+    pub can_reapply_timestamp: u64,
+}
+
+/// Referral rewards credited to the player on one day, expiring `REFERRAL_REWARDS_EXPIRY_SECONDS` after `credited_timestamp`.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub struct ReferralRewardCredit {
+    pub amount: u64,
+    pub credited_timestamp: u64,
+}
+
+/// The state of a `PlayerData` and its purchase streak at the time of the call, returned by `view_player_summary`.
+///
+/// # Fields
+/// - `player`, `team`, `current_round`, `current_period`, `is_exited`, `available_ores`, `purchased_ores`,
+///   `current_period_purchased_ores`, `is_auto_reinvesting`, `purchase_count`, `draw_count`, `stake_count`: As on the player data.
+/// - `consecutive_purchased_days`: The current purchase streak, zero once the player missed a day.
+/// - `has_purchased_today`: Whether the player purchased ORE today.
+/// - `is_streak_active`: Whether a purchase today extends the streak rather than starting a new one.
+/// - `can_collect_airdrop_rewards`: Whether the player may collect today's airdrop rewards.
+/// - `is_self_excluded`: Whether the player is excluded from purchases and lottery draws.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PlayerSummary {
+    pub player: Pubkey,
+    pub team: Pubkey,
+    pub current_round: Pubkey,
+    pub current_period: Pubkey,
+    pub is_exited: bool,
+    pub available_ores: u32,
+    pub purchased_ores: u32,
+    pub current_period_purchased_ores: u32,
+    pub consecutive_purchased_days: u16,
+    pub has_purchased_today: bool,
+    pub is_streak_active: bool,
+    pub can_collect_airdrop_rewards: bool,
+    pub is_auto_reinvesting: bool,
+    pub is_self_excluded: bool,
+    pub purchase_count: u32,
+    pub draw_count: u32,
+    pub stake_count: u32,
+}
+
+/// The rewards a player could collect at the time of the call, returned by `view_pending_rewards`.
+///
+/// # Fields
+/// - `construction_rewards`: The settled construction rewards plus those accrued at the round's current earnings per ORE.
+/// - `exit_rewards`: The settled exit rewards plus those accrued up to the time of the call, paid out on exit.
+/// - `consumption_rewards`, `referral_rewards`: The collectable consumption and referral rewards.
+/// - `carryover_balance`: The construction rewards carried over from a previous round, spent by the next purchase.
+/// - `airdrop_rewards`: Today's airdrop rewards for the purchase streak, as a `PurchaseStreak` campaign pays them, zero if they cannot be collected.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PendingRewards {
+    pub construction_rewards: u64,
+    pub exit_rewards: u64,
+    pub consumption_rewards: u64,
+    pub referral_rewards: u64,
+    pub carryover_balance: u64,
+    pub airdrop_rewards: u64,
+}
+
+/// The asset a player collects their referral rewards in.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ReferralPayout {
+    /// Game tokens, transferred to the player's token account.
+    Token = 0,
+    /// Vouchers, minted to the player's voucher account with the game config's `referral_voucher_bonus_rate` on top.
+    Voucher = 1,
 }
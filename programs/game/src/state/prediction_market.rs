@@ -0,0 +1,200 @@
+use crate::constants::PREDICTION_MARKET_SEED;
+use crate::errors::ErrorCode;
+use crate::utils::{calculate_prediction_payout, VaultAuthority};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// The `PredictionMarket` account pools the vouchers locked on a round's winner, the wallet ending the round as
+/// `last_active_participant_list[0]`. Anyone may predict a winner while the round runs, up to its climax;
+/// once the round is over, `settle_predictions` records the winner, and the correct predictions split the pool
+/// pro rata to their stakes. If nobody predicted the winner, every prediction is refunded instead.
+///
+/// # Fields
+/// - `round`: The round predicted on, seeding the market's PDA.
+/// - `escrow_vault`: The voucher account holding the locked vouchers, owned by the market.
+/// - `total_staked`: The vouchers locked on all predictions.
+/// - `prediction_count`: The number of predictions placed.
+/// - `winner`: The round's winner, set on settlement.
+/// - `winning_staked`: The vouchers locked on the winner, set on settlement.
+/// - `is_settled`: Whether the market was settled.
+/// - `claimed_amount`: The vouchers paid out to the predictors.
+/// - `bump`: The PDA bump seed of the market.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct PredictionMarket {
+    pub round: Pubkey,
+    pub escrow_vault: Pubkey,
+    pub total_staked: u64,
+    pub prediction_count: u32,
+    pub winner: Pubkey,
+    pub winning_staked: u64,
+    pub is_settled: bool,
+    pub claimed_amount: u64,
+    pub bump: u8,
+}
+
+impl PredictionMarket {
+    /// Initializes the market of `round` on its first prediction.
+    pub fn initialize(&mut self, round: Pubkey, escrow_vault: Pubkey, bump: u8) {
+        *self = PredictionMarket {
+            round,
+            escrow_vault,
+            total_staked: 0,
+            prediction_count: 0,
+            winner: Pubkey::default(),
+            winning_staked: 0,
+            is_settled: false,
+            claimed_amount: 0,
+            bump,
+        };
+    }
+
+    /// Records `amount` vouchers locked on a prediction, `is_new` when it is the predictor's first.
+    pub fn record_prediction(&mut self, amount: u64, is_new: bool) -> Result<()> {
+        require!(!self.is_settled, ErrorCode::PredictionMarketSettled);
+        self.total_staked = self.total_staked.safe_add(amount)?;
+        if is_new {
+            self.prediction_count = self.prediction_count.safe_add(1)?;
+        }
+        Ok(())
+    }
+
+    /// Settles the market on `winner`, with `winning_staked` vouchers locked on it.
+    pub fn settle(&mut self, winner: Pubkey, winning_staked: u64) -> Result<()> {
+        require!(!self.is_settled, ErrorCode::PredictionMarketSettled);
+        self.winner = winner;
+        self.winning_staked = winning_staked;
+        self.is_settled = true;
+        Ok(())
+    }
+
+    /// Whether nobody predicted the winner, so that every prediction is refunded.
+    pub fn is_refunding(&self) -> bool {
+        self.winning_staked == 0
+    }
+
+    /// Records the payout of `prediction`, marking it claimed.
+    ///
+    /// # Returns
+    /// Returns the vouchers paid out: the stake's pro rata share of the pool for a correct prediction,
+    /// the stake itself when the market is refunding, or nothing for a wrong prediction.
+    pub fn record_claim(&mut self, prediction: &mut Prediction) -> Result<u64> {
+        require!(self.is_settled, ErrorCode::PredictionMarketNotSettled);
+        require!(!prediction.is_claimed, ErrorCode::PredictionAlreadyClaimed);
+
+        let payout = if self.is_refunding() {
+            prediction.amount
+        } else if prediction.predicted_winner == self.winner {
+            calculate_prediction_payout(prediction.amount, self.total_staked, self.winning_staked)?
+        } else {
+            0
+        };
+
+        prediction.is_claimed = true;
+        self.claimed_amount = self.claimed_amount.safe_add(payout)?;
+        Ok(payout)
+    }
+}
+
+impl VaultAuthority for PredictionMarket {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[PREDICTION_MARKET_SEED, self.round.as_ref(), &[bump]])
+    }
+}
+
+/// The `Prediction` account records the vouchers a predictor locked on a round's winner.
+/// A predictor predicts a single winner per round, and may add to their stake on it.
+///
+/// # Fields
+/// - `prediction_market`: The market predicted on.
+/// - `predictor`: The wallet locking the vouchers, player or not.
+/// - `predicted_winner`: The wallet predicted to win the round.
+/// - `amount`: The vouchers locked.
+/// - `is_claimed`: Whether the prediction was claimed after settlement.
+/// - `bump`: The PDA bump seed of the prediction.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct Prediction {
+    pub prediction_market: Pubkey,
+    pub predictor: Pubkey,
+    pub predicted_winner: Pubkey,
+    pub amount: u64,
+    pub is_claimed: bool,
+    pub bump: u8,
+}
+
+impl Prediction {
+    /// Adds `amount` vouchers locked by `predictor` on `predicted_winner`, initializing the prediction on the first.
+    ///
+    /// # Returns
+    /// Returns whether the prediction is new.
+    pub fn record_stake(
+        &mut self,
+        prediction_market: Pubkey,
+        predictor: Pubkey,
+        predicted_winner: Pubkey,
+        amount: u64,
+        bump: u8,
+    ) -> Result<bool> {
+        require!(amount > 0, ErrorCode::InvalidPrediction);
+
+        let is_new = self.prediction_market == Pubkey::default();
+        if is_new {
+            *self = Prediction {
+                prediction_market,
+                predictor,
+                predicted_winner,
+                amount,
+                is_claimed: false,
+                bump,
+            };
+        } else {
+            require_keys_eq!(
+                self.predicted_winner,
+                predicted_winner,
+                ErrorCode::InvalidPrediction
+            );
+            self.amount = self.amount.safe_add(amount)?;
+        }
+        Ok(is_new)
+    }
+}
+
+/// The `PredictionOutcome` account sums the vouchers locked on one wallet winning a round,
+/// read by `settle_predictions` to size the winners' split.
+///
+/// # Fields
+/// - `prediction_market`: The market predicted on.
+/// - `predicted_winner`: The wallet predicted to win the round.
+/// - `total_staked`: The vouchers locked on the wallet.
+/// - `bump`: The PDA bump seed of the outcome.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct PredictionOutcome {
+    pub prediction_market: Pubkey,
+    pub predicted_winner: Pubkey,
+    pub total_staked: u64,
+    pub bump: u8,
+}
+
+impl PredictionOutcome {
+    /// Adds `amount` vouchers locked on `predicted_winner`, initializing the outcome on the first.
+    pub fn record_stake(
+        &mut self,
+        prediction_market: Pubkey,
+        predicted_winner: Pubkey,
+        amount: u64,
+        bump: u8,
+    ) -> Result<()> {
+        if self.prediction_market == Pubkey::default() {
+            *self = PredictionOutcome {
+                prediction_market,
+                predicted_winner,
+                total_staked: 0,
+                bump,
+            };
+        }
+        self.total_staked = self.total_staked.safe_add(amount)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use crate::constants::SECONDS_PER_DAY;
+use crate::errors::ErrorCode;
+use crate::utils::calculate_vested_amount;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// The `PrizeVesting` account streams a round's first-place grand prize to its winner linearly over the round's
+/// `grand_prize_vesting_days`, instead of paying it at once, so that a large prize does not hit the market in one
+/// sale. The prize stays in the round vault, and the winner claims the vested part through `claim_vested_prize`.
+///
+/// # Fields
+/// - `round`: The round the prize was won in.
+/// - `player`: The winner of the prize.
+/// - `total_amount`: The prize streamed.
+/// - `claimed_amount`: The part of the prize claimed so far.
+/// - `start_time`: The UNIX timestamp the stream started at, when the prize was distributed.
+/// - `end_time`: The UNIX timestamp the whole prize is vested at.
+/// - `bump`: The PDA bump seed of the vesting.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct PrizeVesting {
+    pub round: Pubkey,
+    pub player: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub bump: u8,
+}
+
+impl PrizeVesting {
+    /// Starts streaming `total_amount` to `player` over `vesting_days` from `start_time`.
+    pub fn initialize(
+        &mut self,
+        round: Pubkey,
+        player: Pubkey,
+        total_amount: u64,
+        start_time: u64,
+        vesting_days: u16,
+        bump: u8,
+    ) -> Result<()> {
+        let end_time = start_time.safe_add((vesting_days as u64).safe_mul(SECONDS_PER_DAY)?)?;
+
+        *self = PrizeVesting {
+            round,
+            player,
+            total_amount,
+            claimed_amount: 0,
+            start_time,
+            end_time,
+            bump,
+        };
+        Ok(())
+    }
+
+    /// Returns the part of the prize vested at `timestamp` and not yet claimed.
+    pub fn claimable_amount(&self, timestamp: u64) -> Result<u64> {
+        let vested_amount = calculate_vested_amount(
+            self.total_amount,
+            timestamp.saturating_sub(self.start_time),
+            self.end_time.safe_sub(self.start_time)?,
+        )?;
+        Ok(vested_amount.safe_sub(self.claimed_amount)?)
+    }
+
+    /// Records the claim of the part of the prize vested at `timestamp`.
+    ///
+    /// # Returns
+    /// Returns the amount claimed.
+    pub fn claim(&mut self, timestamp: u64) -> Result<u64> {
+        let amount = self.claimable_amount(timestamp)?;
+        require!(amount > 0, ErrorCode::NoVestedPrizeToClaim);
+
+        self.claimed_amount = self.claimed_amount.safe_add(amount)?;
+        Ok(amount)
+    }
+}
@@ -0,0 +1,176 @@
+use crate::errors::ErrorCode;
+use crate::state::GameConfigParams;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// The `Proposal` account holds a game parameter change put to the vote of the community.
+/// Voting power is snapshotted: it counts the tokens staked by orders created before the proposal,
+/// plus the ORE purchased in the period before the current one at its creation, valued at the game config's `lamports_per_ore`.
+/// A proposal that reached `GOVERNANCE_QUORUM` with a majority in favor is executed once its timelock has ended.
+///
+/// # Fields
+/// - `proposal_number`: The game's `proposal_nonce` the proposal was created with.
+/// - `proposer`: The player who created the proposal, paying its rent.
+/// - `action`: The parameter change applied when the proposal is executed.
+/// - `snapshot_period`: The period whose purchased ORE counts as voting power.
+/// - `snapshot_timestamp`: The UNIX timestamp the proposal was created at, before which stake orders must have been created to count.
+/// - `voting_end_timestamp`: The UNIX timestamp the voting period ends at.
+/// - `executable_timestamp`: The UNIX timestamp the timelock ends at, from which a passed proposal can be executed.
+/// - `votes_for`, `votes_against`: The voting power cast in favor of and against the proposal.
+/// - `voter_count`: The number of votes cast.
+/// - `is_executed`: Whether the proposal has been executed.
+/// - `bump`: The PDA bump seed of the proposal.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct Proposal {
+    pub proposal_number: u32,
+    pub proposer: Pubkey,
+    pub action: GovernanceAction,
+
+    pub snapshot_period: Pubkey,
+    pub snapshot_timestamp: u64,
+    pub voting_end_timestamp: u64,
+    pub executable_timestamp: u64,
+
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub voter_count: u32,
+    pub is_executed: bool,
+
+    pub bump: u8,
+}
+
+impl Proposal {
+    /// Opens the vote on `params.action` at `timestamp`, see `ProposalParams`.
+    pub fn initialize(
+        &mut self,
+        proposal_number: u32,
+        proposer: Pubkey,
+        params: ProposalParams,
+        timestamp: u64,
+        bump: u8,
+    ) -> Result<()> {
+        let voting_end_timestamp = timestamp.safe_add(params.voting_seconds)?;
+        *self = Proposal {
+            proposal_number,
+            proposer,
+            action: params.action,
+            snapshot_period: params.snapshot_period,
+            snapshot_timestamp: timestamp,
+            voting_end_timestamp,
+            executable_timestamp: voting_end_timestamp.safe_add(params.timelock_seconds)?,
+            votes_for: 0,
+            votes_against: 0,
+            voter_count: 0,
+            is_executed: false,
+            bump,
+        };
+        Ok(())
+    }
+
+    /// Casts `voting_power` in favor of the proposal, or against it, while the vote is open.
+    pub fn cast_vote(
+        &mut self,
+        is_in_favor: bool,
+        voting_power: u64,
+        timestamp: u64,
+    ) -> Result<()> {
+        require!(
+            timestamp < self.voting_end_timestamp,
+            ErrorCode::ProposalVotingEnded
+        );
+        require!(voting_power > 0, ErrorCode::NoVotingPower);
+
+        if is_in_favor {
+            self.votes_for = self.votes_for.safe_add(voting_power)?;
+        } else {
+            self.votes_against = self.votes_against.safe_add(voting_power)?;
+        }
+        self.voter_count = self.voter_count.safe_add(1)?;
+        Ok(())
+    }
+
+    /// Whether the votes cast reached `quorum` with a majority in favor.
+    pub fn is_passed(&self, quorum: u64) -> bool {
+        self.votes_for > self.votes_against
+            && self.votes_for.saturating_add(self.votes_against) >= quorum
+    }
+
+    /// Marks the proposal as executed, once its vote passed and its timelock has ended.
+    pub fn execute(&mut self, quorum: u64, timestamp: u64) -> Result<()> {
+        require!(!self.is_executed, ErrorCode::ProposalAlreadyExecuted);
+        require!(
+            timestamp >= self.voting_end_timestamp,
+            ErrorCode::ProposalVotingInProgress
+        );
+        require!(self.is_passed(quorum), ErrorCode::ProposalNotPassed);
+        require!(
+            timestamp >= self.executable_timestamp,
+            ErrorCode::ProposalTimelocked
+        );
+
+        self.is_executed = true;
+        Ok(())
+    }
+}
+
+/// The parameter change, snapshot and schedule a `Proposal` is opened with.
+/// The vote lasts `voting_seconds`, and a passed proposal is executable `timelock_seconds` after it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProposalParams {
+    pub action: GovernanceAction,
+    pub snapshot_period: Pubkey,
+    pub voting_seconds: u64,
+    pub timelock_seconds: u64,
+}
+
+/// The `VoteRecord` account records the vote of a voter on a proposal, preventing them from voting twice.
+///
+/// # Fields
+/// - `proposal`: The proposal voted on.
+/// - `voter`: The player who voted.
+/// - `is_in_favor`: Whether the vote was cast in favor of the proposal.
+/// - `voting_power`: The voting power cast.
+/// - `bump`: The PDA bump seed of the vote record.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub is_in_favor: bool,
+    pub voting_power: u64,
+    pub bump: u8,
+}
+
+impl VoteRecord {
+    /// Records the vote of `voter` on `proposal`.
+    pub fn initialize(
+        &mut self,
+        proposal: Pubkey,
+        voter: Pubkey,
+        is_in_favor: bool,
+        voting_power: u64,
+        bump: u8,
+    ) {
+        *self = VoteRecord {
+            proposal,
+            voter,
+            is_in_favor,
+            voting_power,
+            bump,
+        };
+    }
+}
+
+/// A game parameter change a governance proposal applies when executed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub enum GovernanceAction {
+    /// Updates the pool shares and costs of the game config, as `update_game_config` does.
+    UpdateGameConfig { params: GameConfigParams },
+    /// Updates the APR curve of the stake pool, as `set_stake_rate_curve` does.
+    SetStakeRateCurve {
+        min_annual_rate: u8,
+        max_annual_rate: u8,
+        target_utilization: u8,
+    },
+}
@@ -1,5 +1,9 @@
-use crate::constants::{ACTION_TIME_EXTENSION, MAX_COUNTDOWN_SECONDS};
+use crate::constants::{
+    ACTION_TIME_EXTENSION, COUNTDOWN_MILESTONES, MAX_COUNTDOWN_SECONDS,
+    MAX_GRAND_PRIZE_VESTING_DAYS, ROUND_CLIMAX_SECONDS, ROUND_SEED,
+};
 use crate::errors::ErrorCode;
+use crate::utils::{calculate_earnings_per_ore_increment, calculate_prize_split, VaultAuthority};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 
@@ -36,8 +40,15 @@ const TOTAL_WINNERS: u8 = 10;
 /// - `auto_reinvesting_players`: How many players have opted for auto-reinvestment of their rewards.
 /// - `is_over`: Indicates whether the round is completed.
 /// - `is_grand_prize_distribution_completed`: Indicates whether all grand prizes have been fully distributed.
-/// - `exit_rewards_per_second`: The rate at which exit rewards accrue per second.
-/// - `last_collected_exit_reward_timestamp`: The last timestamp at which exit rewards were claimed or adjusted.
+/// - `exit_rewards_per_second`: The rate at which exit rewards accrue per second, shared pro rata by the ORE held.
+/// - `exit_rewards_per_ore`: The exit rewards accrued to each ORE held since the round started, scaled by `EARNINGS_PER_ORE_PRECISION`.
+/// - `last_collected_exit_reward_timestamp`: The last timestamp up to which exit rewards were accrued to the ORE held.
+/// - `last_collected_sugar_rush_reward_timestamp`: The timestamp of the last candy tap, from which sugar rush rewards accrue.
+/// - `sugar_rush_tap_count`: How many candy taps collected sugar rush rewards in this round.
+/// - `longest_sugar_rush_idle_seconds`, `highest_sugar_rush_multiplier`: The longest wait between two candy taps, and the highest sugar rush multiplier reached.
+/// - `grand_prize_vesting_days`: The days over which the first-place grand prize is streamed, or 0 to pay it instantly.
+/// - `vesting_grand_prizes`: The streamed grand prizes not yet claimed, held in the round vault.
+/// - `reached_countdown_milestones`: The bits of the `COUNTDOWN_MILESTONES` the countdown is at or below, already announced.
 /// - `bump`: A PDA bump seed for this round account.
 pub struct Round {
     pub round_number: u16,
@@ -49,7 +60,7 @@ pub struct Round {
     pub last_call_slot: u64,
     pub call_count: u8,
 
-    pub earnings_per_ore: u64,
+    pub earnings_per_ore: u128,
 
     pub sold_ores: u32,
     pub available_ores: u32,
@@ -70,9 +81,23 @@ pub struct Round {
     pub is_over: bool,
     pub is_grand_prize_distribution_completed: bool,
 
+    pub exit_rewards_per_second: u64,
+    pub exit_rewards_per_ore: u128,
     pub last_collected_exit_reward_timestamp: u64,
     pub last_collected_sugar_rush_reward_timestamp: u64,
 
+    // Sugar rush streak stats
+    pub sugar_rush_tap_count: u32,
+    pub longest_sugar_rush_idle_seconds: u64,
+    pub highest_sugar_rush_multiplier: u16,
+
+    // Grand prize streaming
+    pub grand_prize_vesting_days: u16,
+    pub vesting_grand_prizes: u64,
+
+    // Countdown milestones announced
+    pub reached_countdown_milestones: u8,
+
     pub bump: u8,
 }
 
@@ -85,6 +110,7 @@ impl Round {
     /// - `grand_prize_pool_balance`: The initial balance allocated to grand prizes.
     /// - `start_time`: The UNIX timestamp marking when this round starts.
     /// - `countdown_duration`: The duration of the round in seconds before it ends, absent extensions.
+    /// - `exit_rewards_per_second`: The rate at which exit rewards accrue to the ORE held.
     /// - `default_player`: A default player public key used to initialize the `last_active_participant_list`.
     /// - `bump`: The PDA bump seed.
     ///
@@ -97,6 +123,7 @@ impl Round {
         grand_prize_pool_balance: u64,
         start_time: u64,
         countdown_duration: u64,
+        exit_rewards_per_second: u64,
         default_player: Pubkey,
         bump: u8,
     ) -> Result<()> {
@@ -111,6 +138,7 @@ impl Round {
             start_time,
             end_time,
             last_active_participant_list: vec![default_player; MAX_LAST_ACTIVE_PARTICIPANT_LIST],
+            exit_rewards_per_second,
             last_collected_exit_reward_timestamp: start_time,
             last_collected_sugar_rush_reward_timestamp: start_time,
             bump,
@@ -120,6 +148,82 @@ impl Round {
         Ok(())
     }
 
+    /// Accrues the exit rewards emitted since the last accrual to the ORE held, pro rata.
+    /// Must be called before the ORE held in the round changes.
+    ///
+    /// # Arguments
+    /// - `current_time`: The current UNIX timestamp.
+    pub fn accrue_exit_rewards(&mut self, current_time: u64) -> Result<()> {
+        if current_time <= self.last_collected_exit_reward_timestamp {
+            return Ok(());
+        }
+
+        self.exit_rewards_per_ore = self.projected_exit_rewards_per_ore(current_time)?;
+        self.last_collected_exit_reward_timestamp = current_time;
+
+        Ok(())
+    }
+
+    /// Returns the exit rewards per ORE the round would reach if its exit rewards were accrued at `current_time`,
+    /// without accruing them.
+    ///
+    /// # Arguments
+    /// - `current_time`: The current UNIX timestamp.
+    pub fn projected_exit_rewards_per_ore(&self, current_time: u64) -> Result<u128> {
+        if current_time <= self.last_collected_exit_reward_timestamp || self.available_ores == 0 {
+            return Ok(self.exit_rewards_per_ore);
+        }
+
+        let elapsed_time = current_time.safe_sub(self.last_collected_exit_reward_timestamp)?;
+        let exit_rewards = self.exit_rewards_per_second.safe_mul(elapsed_time)?;
+        Ok(self
+            .exit_rewards_per_ore
+            .safe_add(calculate_earnings_per_ore_increment(
+                exit_rewards,
+                self.available_ores,
+            )?)?)
+    }
+
+    /// Returns the round's state and derived values at `current_time`, for the `view_round_summary` instruction.
+    ///
+    /// # Arguments
+    /// - `current_time`: The current UNIX timestamp.
+    pub fn summary(&self, current_time: u64) -> Result<RoundSummary> {
+        Ok(RoundSummary {
+            round_number: self.round_number,
+            phase: self.phase(current_time),
+            is_over: self.is_over,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            seconds_to_end: self.end_time.saturating_sub(current_time),
+            call_count: self.call_count,
+            sold_ores: self.sold_ores,
+            available_ores: self.available_ores,
+            earnings_per_ore: self.earnings_per_ore,
+            exit_rewards_per_ore: self.projected_exit_rewards_per_ore(current_time)?,
+            grand_prize_pool_balance: self.grand_prize_pool_balance,
+            last_active_participant: self.last_active_participant_list.first().copied(),
+        })
+    }
+
+    /// Collects the sugar rush rewards at a candy tap, restarting the escalation and recording the streak stats.
+    ///
+    /// # Arguments
+    /// - `current_time`: The current UNIX timestamp.
+    /// - `multiplier`: The sugar rush multiplier reached since the last candy tap (in basis points).
+    pub fn collect_sugar_rush(&mut self, current_time: u64, multiplier: u16) -> Result<()> {
+        let idle_seconds =
+            current_time.safe_sub(self.last_collected_sugar_rush_reward_timestamp)?;
+        self.last_collected_sugar_rush_reward_timestamp = current_time;
+
+        self.sugar_rush_tap_count = self.sugar_rush_tap_count.safe_add(1)?;
+        self.longest_sugar_rush_idle_seconds =
+            self.longest_sugar_rush_idle_seconds.max(idle_seconds);
+        self.highest_sugar_rush_multiplier = self.highest_sugar_rush_multiplier.max(multiplier);
+
+        Ok(())
+    }
+
     /// Updates the end time of the round, potentially extending it based on current conditions.
     /// This function resets `last_call_slot` and `call_count`, and applies logic to ensure the round
     /// does not extend indefinitely beyond `MAX_COUNTDOWN_SECONDS`.
@@ -158,9 +262,13 @@ impl Round {
     /// # Arguments
     /// - `player`: The public key of the active participant to add.
     pub fn update_last_active_participant_list(&mut self, player: Pubkey) -> Result<()> {
-        self.last_active_participant_list.retain(|&x| x != player);
-
-        if self.last_active_participant_list.len() >= MAX_LAST_ACTIVE_PARTICIPANT_LIST {
+        if let Some(index) = self
+            .last_active_participant_list
+            .iter()
+            .position(|&x| x == player)
+        {
+            self.last_active_participant_list.remove(index);
+        } else if self.last_active_participant_list.len() >= MAX_LAST_ACTIVE_PARTICIPANT_LIST {
             self.last_active_participant_list.pop();
         }
 
@@ -208,19 +316,130 @@ impl Round {
         Ok(reward_amount)
     }
 
+    /// Sets the days over which the first-place grand prize is streamed, 0 paying it instantly.
+    /// The streaming can only be changed before the round is over.
+    pub fn set_grand_prize_vesting_days(&mut self, grand_prize_vesting_days: u16) -> Result<()> {
+        require!(!self.is_over, ErrorCode::RoundAlreadyEnded);
+        require!(
+            grand_prize_vesting_days <= MAX_GRAND_PRIZE_VESTING_DAYS,
+            ErrorCode::InvalidGrandPrizeVesting
+        );
+        self.grand_prize_vesting_days = grand_prize_vesting_days;
+        Ok(())
+    }
+
+    /// Whether the grand prize of the winner at `index` is streamed rather than paid instantly.
+    /// Only the first-place prize is streamed; the smaller prizes are always paid instantly.
+    pub fn is_grand_prize_vested(&self, index: u8) -> bool {
+        index == 0 && self.grand_prize_vesting_days > 0
+    }
+
+    /// Records `amount` of grand prizes streamed to the first-place winner, held in the round vault until claimed.
+    pub fn vest_grand_prizes(&mut self, amount: u64) -> Result<()> {
+        self.vesting_grand_prizes = self.vesting_grand_prizes.safe_add(amount)?;
+        Ok(())
+    }
+
+    /// Records `amount` of streamed grand prizes claimed out of the round vault.
+    pub fn claim_vested_grand_prizes(&mut self, amount: u64) -> Result<()> {
+        self.vesting_grand_prizes = self.vesting_grand_prizes.safe_sub(amount)?;
+        Ok(())
+    }
+
+    /// Returns the phase of the round at `timestamp`, selecting how payments are distributed.
+    pub fn phase(&self, timestamp: u64) -> RoundPhase {
+        if self.available_ores == 0 {
+            RoundPhase::Bootstrapping
+        } else if self.end_time.saturating_sub(timestamp) <= ROUND_CLIMAX_SECONDS {
+            RoundPhase::Climax
+        } else {
+            RoundPhase::Active
+        }
+    }
+
+    /// Records the `COUNTDOWN_MILESTONES` the countdown has reached at `timestamp`, and re-arms those it rose back
+    /// above through extensions, so that they are announced again when next reached.
+    ///
+    /// # Returns
+    /// The newly reached milestone, the lowest one if several were reached at once, in seconds.
+    pub fn reach_countdown_milestone(&mut self, timestamp: u64) -> Option<u64> {
+        let seconds_to_end = self.end_time.saturating_sub(timestamp);
+        if seconds_to_end == 0 {
+            return None;
+        }
+
+        let mut reached_milestone = None;
+        for (index, &milestone) in COUNTDOWN_MILESTONES.iter().enumerate() {
+            let bit = 1u8 << index;
+            if seconds_to_end > milestone {
+                self.reached_countdown_milestones &= !bit;
+            } else if self.reached_countdown_milestones & bit == 0 {
+                self.reached_countdown_milestones |= bit;
+                reached_milestone = Some(milestone);
+            }
+        }
+        reached_milestone
+    }
+
     /// Calculates the amounts allocated to the top winner and the subsequent winners.
     /// Splits the `grand_prize_pool_balance` into `first_grand_prizes` and `second_grand_prizes`.
     fn calculate_prize_amounts(&mut self) -> Result<()> {
-        let half_prize = self.grand_prize_pool_balance.safe_div(2)?;
-        let shared_prize = half_prize.safe_div(TOTAL_WINNERS as u64)?;
-
-        self.first_grand_prizes = half_prize.safe_add(shared_prize)?;
-        self.second_grand_prizes = shared_prize;
+        (self.first_grand_prizes, self.second_grand_prizes) =
+            calculate_prize_split(self.grand_prize_pool_balance, TOTAL_WINNERS as u64)?;
 
         Ok(())
     }
 }
 
+/// The state of a `Round` and the values derived from it at the time of the call, returned by `view_round_summary`.
+///
+/// # Fields
+/// - `round_number`, `is_over`, `start_time`, `end_time`, `call_count`, `sold_ores`, `available_ores`,
+///   `earnings_per_ore`, `grand_prize_pool_balance`: As on the round.
+/// - `phase`: The economic phase of the round.
+/// - `seconds_to_end`: The countdown left until `end_time`, zero once it passed.
+/// - `exit_rewards_per_ore`: The round's exit rewards per ORE, including those accrued since the last accrual.
+/// - `last_active_participant`: The most recent purchaser, in line for the first grand prize.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RoundSummary {
+    pub round_number: u16,
+    pub phase: RoundPhase,
+    pub is_over: bool,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub seconds_to_end: u64,
+    pub call_count: u8,
+    pub sold_ores: u32,
+    pub available_ores: u32,
+    pub earnings_per_ore: u128,
+    pub exit_rewards_per_ore: u128,
+    pub grand_prize_pool_balance: u64,
+    pub last_active_participant: Option<Pubkey>,
+}
+
+/// The economic phase of a round, derived from the ORE held and the remaining countdown.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RoundPhase {
+    /// No ORE is held, either before the first purchase or after every holder exited.
+    /// The construction and bonus shares go to the grand prize pool, as no ORE can earn them.
+    #[default]
+    Bootstrapping = 0,
+    /// ORE is held and the countdown is above `ROUND_CLIMAX_SECONDS`.
+    /// The construction and bonus shares are earned by the ORE held.
+    Active = 1,
+    /// ORE is held and the countdown is at or below `ROUND_CLIMAX_SECONDS`.
+    /// Distributed like `Active`; reported separately so clients can follow the race for the grand prize.
+    Climax = 2,
+}
+
+impl RoundPhase {
+    /// Whether the construction and bonus shares are earned by the ORE held rather than the grand prize pool.
+    pub fn feeds_ore_holders(self) -> bool {
+        self != RoundPhase::Bootstrapping
+    }
+}
+
 /// Error codes related to round operations.
 /// These errors help identify why certain actions within a round failed.
 #[error_code]
@@ -239,3 +458,9 @@ pub enum RoundError {
     #[msg("Insufficient ores for subtraction")]
     InsufficientOres,
 }
+
+impl VaultAuthority for Round {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[ROUND_SEED, &self.round_number.to_le_bytes(), &[bump]])
+    }
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+/// The `RoundPosition` account backs a tokenized round position: ORE detached from a player's
+/// `PlayerData` and represented by a single token of its own mint, so that the position can change
+/// wallets or be sold. Whoever holds the token may redeem it, burning the token and closing the position.
+///
+/// # Fields
+/// - `round`: The round the ORE belongs to.
+/// - `position_mint`: The mint of the token representing the position.
+/// - `ores`: The amount of ORE in the position.
+/// - `earnings_per_ore`: The round's `earnings_per_ore` at which the position's construction rewards were last settled.
+/// - `exit_rewards_per_ore`: The round's `exit_rewards_per_ore` at which the position's exit rewards were last settled.
+/// - `bump`: The PDA bump seed of the position.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct RoundPosition {
+    pub round: Pubkey,
+    pub position_mint: Pubkey,
+    pub ores: u32,
+    pub earnings_per_ore: u128,
+    pub exit_rewards_per_ore: u128,
+    pub bump: u8,
+}
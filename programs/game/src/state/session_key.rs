@@ -0,0 +1,91 @@
+use crate::constants::SESSION_KEY_SEED;
+use crate::errors::ErrorCode;
+use crate::utils::{timestamp_to_days, VaultAuthority};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// Scope allowing the session signer to purchase ORE with `purchase_with_tokens`, within `max_daily_ores`.
+pub const SESSION_SCOPE_PURCHASE: u8 = 1 << 0;
+
+/// Scope allowing the session signer to tap candy with `candy_tap`.
+pub const SESSION_SCOPE_CANDY_TAP: u8 = 1 << 1;
+
+/// Scope allowing the session signer to reveal lottery draws with `reveal_draw_lottery_result`.
+pub const SESSION_SCOPE_REVEAL_LOTTERY: u8 = 1 << 2;
+
+/// Union of all session scopes.
+pub const SESSION_SCOPE_ALL: u8 =
+    SESSION_SCOPE_PURCHASE | SESSION_SCOPE_CANDY_TAP | SESSION_SCOPE_REVEAL_LOTTERY;
+
+/// The `SessionKey` account lets a player authorize another key, typically held by a relayer, to sign a
+/// limited set of gameplay instructions on their behalf until it expires. Each player has at most one session key.
+///
+/// # Fields
+/// - `player`: The player who authorized the session.
+/// - `session_signer`: The key allowed to sign on the player's behalf.
+/// - `scopes`: The bitmask of `SESSION_SCOPE_*` instructions the session signer may call.
+/// - `max_daily_ores`: The ORE the session signer may purchase per day.
+/// - `current_day`: The day `current_day_purchased_ores` refers to.
+/// - `current_day_purchased_ores`: The ORE purchased through the session on `current_day`.
+/// - `expires_at`: The UNIX timestamp from which the session is no longer valid.
+/// - `bump`: The PDA bump seed of the session key.
+#[account]
+#[derive(Debug, Default, InitSpace)]
+pub struct SessionKey {
+    pub player: Pubkey,
+    pub session_signer: Pubkey,
+    pub scopes: u8,
+    pub max_daily_ores: u32,
+    pub current_day: u32,
+    pub current_day_purchased_ores: u32,
+    pub expires_at: u64,
+    pub bump: u8,
+}
+
+impl SessionKey {
+    /// Checks that `signer` may call an instruction of the given `scope` on the player's behalf.
+    ///
+    /// # Arguments
+    /// - `signer`: The key that signed the transaction.
+    /// - `scope`: The `SESSION_SCOPE_*` of the instruction.
+    /// - `timestamp`: The current UNIX timestamp.
+    pub fn authorize(&self, signer: Pubkey, scope: u8, timestamp: u64) -> Result<()> {
+        require!(
+            self.session_signer == signer,
+            ErrorCode::SessionSignerMismatch
+        );
+        require!(timestamp < self.expires_at, ErrorCode::SessionKeyExpired);
+        require!(
+            self.scopes & scope == scope,
+            ErrorCode::SessionScopeNotAuthorized
+        );
+        Ok(())
+    }
+
+    /// Records ORE purchased through the session, enforcing the daily limit.
+    ///
+    /// # Arguments
+    /// - `ores`: The ORE purchased.
+    /// - `timestamp`: The current UNIX timestamp.
+    pub fn record_purchased_ores(&mut self, ores: u32, timestamp: u64) -> Result<()> {
+        let current_day = timestamp_to_days(timestamp)?;
+        if self.current_day != current_day {
+            self.current_day = current_day;
+            self.current_day_purchased_ores = 0;
+        }
+
+        let purchased_ores = self.current_day_purchased_ores.safe_add(ores)?;
+        require!(
+            purchased_ores <= self.max_daily_ores,
+            ErrorCode::SessionDailyOresExceeded
+        );
+        self.current_day_purchased_ores = purchased_ores;
+        Ok(())
+    }
+}
+
+impl VaultAuthority for SessionKey {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[SESSION_KEY_SEED, self.player.as_ref(), &[bump]])
+    }
+}
@@ -1,12 +1,19 @@
 use crate::constants::{
     ANNUAL_RATE, EARLY_UNLOCK_APR, EARLY_UNLOCK_DURATION, LAMPORTS_PER_TOKEN, LOCK_DURATION,
-    ONE_MILLION,
+    MIN_ANNUAL_RATE, ONE_MILLION, STAKE_POOL_SEED, TARGET_UTILIZATION,
 };
 use crate::errors::ErrorCode;
-use crate::utils::calculate_prorated_interest;
+use crate::utils::{
+    calculate_curve_rate, calculate_prorated_interest, calculate_utilization, VaultAuthority,
+};
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 
+/// Maximum number of active stake orders a player may hold at once, the capacity of their `StakeOrderIndex`.
+/// `stake`, `stake_vouchers` and `deposit_and_stake` fail with `StakeOrderIndexFull` beyond it, until an order
+/// is unstaked. Orders created before the index existed are not listed and do not count towards it.
+pub const MAX_ACTIVE_STAKE_ORDERS: usize = 32;
+
 /// The `StakePool` account stores global state for the staking system.
 /// The `StakePool` account stores global state for the staking system.
 /// It tracks total staked amounts, available and distributed rewards, as well as interest rates.
@@ -62,6 +69,26 @@ pub struct StakePool {
 
     /// The number of active stake orders currently outstanding.
     pub active_orders: u32,
+
+    /// The total amount of vouchers currently staked (escrowed in `stake_pool_voucher_vault`) in this pool.
+    pub staked_voucher_amount: u64,
+
+    /// The lowest APR the utilization curve can assign, reached at full utilization (in basis points).
+    pub min_annual_rate: u8,
+
+    /// The APR assigned while utilization is at or below `target_utilization` (in basis points).
+    pub max_annual_rate: u8,
+
+    /// The utilization (in basis points) above which the APR starts decreasing towards `min_annual_rate`.
+    pub target_utilization: u8,
+
+    /// Whether the pool is being wound down. New stakes are refused and players may
+    /// withdraw their principal immediately through `emergency_unstake`, forfeiting rewards.
+    /// Also serves as the pause of the stake pool, as the game has no pause state.
+    pub is_winding_down: bool,
+
+    /// Whether the pool has been decommissioned. New stakes are refused while existing orders run to maturity.
+    pub is_decommissioned: bool,
 }
 
 impl StakePool {
@@ -86,6 +113,9 @@ impl StakePool {
             early_unlock_rate: EARLY_UNLOCK_APR,
             lock_duration: LOCK_DURATION,
             early_unlock_duration: EARLY_UNLOCK_DURATION,
+            min_annual_rate: MIN_ANNUAL_RATE,
+            max_annual_rate: ANNUAL_RATE,
+            target_utilization: TARGET_UTILIZATION,
 
             token_rewards_pool_balance: token_rewards,
             distributable_token_rewards: token_rewards,
@@ -117,12 +147,94 @@ impl StakePool {
         Ok(())
     }
 
+    /// Updates the parameters of the utilization curve driving `annual_rate`.
+    ///
+    /// # Arguments
+    /// - `min_annual_rate`: APR at full utilization (in basis points).
+    /// - `max_annual_rate`: APR at or below the target utilization (in basis points).
+    /// - `target_utilization`: Utilization (in basis points) where the APR starts decreasing.
+    pub fn update_rate_curve(
+        &mut self,
+        min_annual_rate: u8,
+        max_annual_rate: u8,
+        target_utilization: u8,
+    ) -> Result<()> {
+        require!(
+            min_annual_rate <= max_annual_rate && target_utilization <= 100,
+            ErrorCode::InvalidStakeRateCurve
+        );
+        self.min_annual_rate = min_annual_rate;
+        self.max_annual_rate = max_annual_rate;
+        self.target_utilization = target_utilization;
+        Ok(())
+    }
+
+    /// Returns the current pool utilization: staked principal relative to the remaining distributable rewards.
+    pub fn utilization(&self) -> Result<u8> {
+        Ok(calculate_utilization(
+            self.staked_amount,
+            self.distributable_token_rewards,
+        )?)
+    }
+
+    /// Recomputes `annual_rate` from the utilization curve.
+    ///
+    /// # Returns
+    /// The utilization used to derive the new rate.
+    pub fn apply_rate_curve(&mut self) -> Result<u8> {
+        let utilization = self.utilization()?;
+        self.annual_rate = calculate_curve_rate(
+            utilization,
+            self.target_utilization,
+            self.min_annual_rate,
+            self.max_annual_rate,
+        )?;
+        Ok(utilization)
+    }
+
     /// Adds additional rewards to the pool, increasing its capacity to handle future orders.
     ///
     /// # Arguments
     /// - `amount`: The amount of additional rewards to add.
     pub fn add_rewards(&mut self, amount: u64) -> Result<()> {
         self.token_rewards_pool_balance = self.token_rewards_pool_balance.safe_add(amount)?;
+        self.distributable_token_rewards = self.distributable_token_rewards.safe_add(amount)?;
+        Ok(())
+    }
+
+    /// Records interest paid out of the pool to a stake order.
+    ///
+    /// # Arguments
+    /// - `token_rewards`: The interest transferred from `stake_pool_token_vault` to the player.
+    pub fn pay_interest(&mut self, token_rewards: u64) -> Result<()> {
+        self.distributable_token_rewards =
+            self.distributable_token_rewards.safe_sub(token_rewards)?;
+        self.token_rewards_pool_balance =
+            self.token_rewards_pool_balance.safe_sub(token_rewards)?;
+        self.distributed_token_rewards = self.distributed_token_rewards.safe_add(token_rewards)?;
+        Ok(())
+    }
+
+    /// Clears the remaining reward balances once the pool vaults have been swept.
+    pub fn sweep(&mut self) -> Result<()> {
+        require!(self.active_orders == 0, ErrorCode::StakePoolHasActiveOrders);
+        self.token_rewards_pool_balance = 0;
+        self.distributable_token_rewards = 0;
+        self.voucher_rewards_pool_balance = 0;
+        Ok(())
+    }
+
+    /// Completes a voucher stake order by removing its escrowed vouchers from the pool.
+    ///
+    /// # Arguments
+    /// - `staked_amount`: The vouchers originally staked in the order.
+    pub fn complete_voucher_order(&mut self, staked_amount: u64) -> Result<()> {
+        require!(
+            self.staked_voucher_amount >= staked_amount,
+            ErrorCode::StakeOrderInsufficientBalance
+        );
+        self.staked_voucher_amount = self.staked_voucher_amount.safe_sub(staked_amount)?;
+        self.active_orders = self.active_orders.safe_sub(1)?;
         Ok(())
     }
 
@@ -146,6 +258,9 @@ impl StakePool {
 /// It tracks the principal staked amount, the associated rewards, timestamps, and state flags for early unlocks.
 /// Each `StakeOrder` can either run its full course (LOCK_DURATION) at the full APR
 /// or be unlocked early at a reduced APR for fewer rewards.
+/// Token interest is not reserved at creation: it accrues over the lock duration and is paid from the pool
+/// whenever it is claimed through `claim_stake_interest` or when the order is unstaked.
+/// Orders that reserved their interest at creation release the reservation in `migrate_stake_accounts`.
 #[account]
 #[derive(Debug, Default, InitSpace)]
 pub struct StakeOrder {
@@ -155,13 +270,14 @@ pub struct StakeOrder {
     /// The amount staked in this order (principal). This amount is immutable after creation.
     pub stake_amount: u64,
 
-    /// The total amount of rewards initially locked in this order at creation time.
+    /// The total amount of token interest paid out to this order so far.
     pub token_rewards: u64,
 
     /// The total amount of rewards initially locked in this order at creation time.
     pub voucher_rewards: u64,
 
     /// A vault specifically associated with this stake order for holding staked assets.
+    /// For voucher stakes this is the pool's `stake_pool_voucher_vault`, where the vouchers are escrowed.
     pub stake_order_vault: Pubkey,
 
     /// The UNIX timestamp when the order was created.
@@ -186,6 +302,29 @@ pub struct StakeOrder {
 
     /// A PDA bump seed for the stake order account.
     pub bump: u8,
+
+    /// A flag indicating that the principal of this order is denominated in vouchers rather than tokens.
+    pub is_voucher_stake: bool,
+
+    /// The UNIX timestamp from which an early-unstaked order can be claimed through `claim_early_unstake`.
+    pub early_claimable_timestamp: u64,
+
+    /// The token rewards given up when early unstake was requested.
+    pub forfeited_token_rewards: u64,
+
+    /// The voucher rewards given up (and burned) when early unstake was requested.
+    pub forfeited_voucher_rewards: u64,
+
+    /// The UNIX timestamp up to which interest has been claimed, starting at `created_timestamp`.
+    pub last_claimed_timestamp: u64,
+
+    /// The lock end the order counts with in the player's stake boost, `0` once removed from it
+    /// or for orders created before the stake boost.
+    pub boost_lock_end: u64,
+
+    /// The interest accrued by the order that the pool could not pay when the order was completed.
+    /// It stays claimable through `claim_stake_interest` once the pool is refilled.
+    pub unpaid_token_rewards: u64,
 }
 
 impl StakeOrder {
@@ -194,7 +333,9 @@ impl StakeOrder {
     /// # Arguments
     /// - `stake_number`: A unique identifier for this order.
     /// - `stake_amount`: The principal staked amount.
-    /// - `reward_amount`: The initial computed rewards for the order.
+    /// - `annual_rate`: The APR at which interest accrues.
+    /// - `lock_duration`: The duration over which interest accrues.
+    /// - `voucher_rewards`: The vouchers issued for the order.
     /// - `stake_order_vault`: The vault holding the staked tokens for this order.
     /// - `created_timestamp`: The UNIX timestamp at order creation.
     /// - `bump`: PDA bump seed.
@@ -207,24 +348,25 @@ impl StakeOrder {
         stake_amount: u64,
         annual_rate: u8,
         lock_duration: u64,
-        token_rewards: u64,
         voucher_rewards: u64,
         stake_order_vault: Pubkey,
         created_timestamp: u64,
         bump: u8,
     ) -> Result<()> {
+        let unstaked_timestamp = created_timestamp.safe_add(lock_duration)?;
         *self = StakeOrder {
             stake_number,
             stake_amount,
-            token_rewards,
             voucher_rewards,
             stake_order_vault,
             created_timestamp,
-            unstaked_timestamp: created_timestamp.safe_add(lock_duration)?,
+            unstaked_timestamp,
             annual_rate,
             lock_duration,
             is_early_unstaked: false,
             is_completed: false,
+            last_claimed_timestamp: created_timestamp,
+            boost_lock_end: unstaked_timestamp,
             bump,
             ..Default::default()
         };
@@ -233,15 +375,13 @@ impl StakeOrder {
     }
 
     /// Requests an early unlock for this stake order.
-    /// Reduces the APR and recalculates rewards based on the elapsed time.
-    /// Adjusts `locked_rewards` to reflect the new, reduced reward amount and sets a shorter `unstaked_timestamp`.
+    /// Reduces the APR and stops interest accrual at the elapsed time, then sets a shorter `unstaked_timestamp`.
+    /// Interest already claimed at the original APR is kept; anything beyond it and the issued vouchers are forfeited.
     ///
     /// # Arguments
     /// - `current_timestamp`: The current UNIX timestamp to determine elapsed time.
     /// - `early_unstake_rate`: The reduced APR to apply for early unlocking.
-    ///
-    /// # Returns
-    /// Returns `unused_rewards`, the portion of initially locked rewards that become unused due to the reduced interest calculation.
+    /// - `early_unlock_duration`: The waiting period before the order can be claimed.
     pub fn request_early_unstake(
         &mut self,
         current_timestamp: u64,
@@ -253,19 +393,62 @@ impl StakeOrder {
             ErrorCode::EarlyUnlockAlreadyRequested
         );
         let elapsed_time = current_timestamp.safe_sub(self.created_timestamp)?;
+        let full_token_rewards =
+            calculate_prorated_interest(self.stake_amount, self.lock_duration, self.annual_rate)?;
         let new_token_rewards =
-            calculate_prorated_interest(self.stake_amount, elapsed_time, early_unstake_rate)?;
+            calculate_prorated_interest(self.stake_amount, elapsed_time, early_unstake_rate)?
+                .max(self.token_rewards);
+
+        self.forfeited_token_rewards = full_token_rewards.saturating_sub(new_token_rewards);
+        self.forfeited_voucher_rewards = self.voucher_rewards;
 
         self.lock_duration = elapsed_time;
-        self.token_rewards = new_token_rewards;
         self.voucher_rewards = 0;
         self.annual_rate = early_unstake_rate;
         self.unstaked_timestamp = current_timestamp.safe_add(early_unlock_duration)?;
+        self.early_claimable_timestamp = self.unstaked_timestamp;
         self.is_early_unstaked = true;
 
         Ok(())
     }
 
+    /// Returns the interest accrued at `annual_rate` up to `current_timestamp` (capped at the end of the
+    /// lock duration) that has not been claimed yet.
+    pub fn accrued_interest(&self, current_timestamp: u64) -> Result<u64> {
+        let accrual_end =
+            current_timestamp.min(self.created_timestamp.safe_add(self.lock_duration)?);
+        let elapsed_time = accrual_end.saturating_sub(self.created_timestamp);
+        let total_interest =
+            calculate_prorated_interest(self.stake_amount, elapsed_time, self.annual_rate)?;
+        Ok(total_interest.saturating_sub(self.token_rewards))
+    }
+
+    /// Claims the accrued interest, limited to what the pool can still distribute.
+    /// Any interest left unpaid stays claimable once the pool is refilled, including after the order
+    /// is completed through `complete_with_unpaid_interest`.
+    ///
+    /// # Arguments
+    /// - `current_timestamp`: The current UNIX timestamp.
+    /// - `available`: The rewards the pool can still distribute.
+    ///
+    /// # Returns
+    /// The interest to transfer to the player.
+    pub fn claim_interest(&mut self, current_timestamp: u64, available: u64) -> Result<u64> {
+        if self.is_completed {
+            let interest = self.unpaid_token_rewards.min(available);
+            self.unpaid_token_rewards = self.unpaid_token_rewards.safe_sub(interest)?;
+            self.token_rewards = self.token_rewards.safe_add(interest)?;
+            return Ok(interest);
+        }
+
+        let interest = self.accrued_interest(current_timestamp)?.min(available);
+        self.token_rewards = self.token_rewards.safe_add(interest)?;
+        self.last_claimed_timestamp = current_timestamp
+            .min(self.created_timestamp.safe_add(self.lock_duration)?)
+            .max(self.last_claimed_timestamp);
+        Ok(interest)
+    }
+
     /// Completes the stake order, setting `is_completed = true`.
     /// This usually occurs when the staking period ends and rewards are claimed.
     pub fn complete(&mut self) -> Result<()> {
@@ -275,9 +458,149 @@ impl StakeOrder {
         Ok(())
     }
 
+    /// Completes the stake order after its final interest claim, recording the accrued interest the pool
+    /// could not pay in `unpaid_token_rewards` instead of forfeiting it.
+    ///
+    /// # Arguments
+    /// - `current_timestamp`: The current UNIX timestamp.
+    pub fn complete_with_unpaid_interest(&mut self, current_timestamp: u64) -> Result<()> {
+        self.unpaid_token_rewards = self.accrued_interest(current_timestamp)?;
+        self.complete()
+    }
+
+    /// Checks if an early-unstaked order can be claimed at the given `current_timestamp`.
+    pub fn can_claim_early_unstake(&self, current_timestamp: u64) -> bool {
+        self.is_early_unstaked
+            && current_timestamp >= self.early_claimable_timestamp
+            && current_timestamp >= self.unstaked_timestamp
+    }
+
     /// Checks if the order can be unstaked at the given `current_timestamp`.
     /// The order is unstakeable if `current_timestamp >= unstaked_timestamp`.
     pub fn can_unstake(&self, current_timestamp: u64) -> bool {
         current_timestamp >= self.unstaked_timestamp
     }
 }
+
+/// A single entry of a player's `StakeOrderIndex`, describing one live stake order.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, InitSpace)]
+pub struct StakeOrderEntry {
+    /// The order number used to derive the `StakeOrder` PDA.
+    pub order_number: u16,
+
+    /// The UNIX timestamp at which the order becomes unstakeable.
+    pub unstaked_timestamp: u64,
+
+    /// Whether an early unstake has been requested for this order.
+    pub is_early_unstaked: bool,
+}
+
+/// The `StakeOrderIndex` account lists every active stake order belonging to a player.
+/// Wallets and the bot read this account to enumerate live orders instead of probing
+/// `StakeOrder` PDAs for every nonce value. Entries are added by `stake` and removed by `unstake`.
+/// A player holds at most `MAX_ACTIVE_STAKE_ORDERS` active orders.
+#[account]
+#[derive(Debug, Default, InitSpace)]
+pub struct StakeOrderIndex {
+    /// The player who owns the listed stake orders.
+    pub player: Pubkey,
+
+    /// The active (not yet completed) stake orders of the player.
+    #[max_len(MAX_ACTIVE_STAKE_ORDERS)]
+    pub orders: Vec<StakeOrderEntry>,
+
+    /// A PDA bump seed for the stake order index account.
+    pub bump: u8,
+}
+
+impl StakeOrderIndex {
+    /// Binds a freshly created index to its player. Does nothing if the index is already initialized.
+    pub fn initialize_if_needed(&mut self, player: Pubkey, bump: u8) -> Result<()> {
+        if self.player == Pubkey::default() {
+            self.player = player;
+            self.bump = bump;
+        }
+        Ok(())
+    }
+
+    /// Checks if `index`, the stake order index address of a player, lists any active order.
+    /// Only this program can allocate the address, so an empty account means the player never staked.
+    pub fn has_active_orders(index: &AccountInfo) -> Result<bool> {
+        if index.data_is_empty() {
+            return Ok(false);
+        }
+        let index = StakeOrderIndex::try_deserialize(&mut &index.try_borrow_data()?[..])?;
+        Ok(!index.orders.is_empty())
+    }
+
+    /// Checks if the index can not accept any more orders.
+    pub fn is_full(&self) -> bool {
+        self.orders.len() >= MAX_ACTIVE_STAKE_ORDERS
+    }
+
+    /// Checks if the given order number is listed as active.
+    pub fn contains(&self, order_number: u16) -> bool {
+        self.orders.iter().any(|x| x.order_number == order_number)
+    }
+
+    /// Records a newly created stake order.
+    pub fn add_order(&mut self, order_number: u16, unstaked_timestamp: u64) -> Result<()> {
+        require!(!self.is_full(), ErrorCode::StakeOrderIndexFull);
+        require!(
+            !self.contains(order_number),
+            ErrorCode::StakeOrderAlreadyIndexed
+        );
+
+        self.orders.push(StakeOrderEntry {
+            order_number,
+            unstaked_timestamp,
+            is_early_unstaked: false,
+        });
+        Ok(())
+    }
+
+    /// Flags an order as early unstaked and records its new unlock time.
+    /// Orders created before the index existed are not listed and are left untouched.
+    pub fn mark_early_unstaked(
+        &mut self,
+        order_number: u16,
+        unstaked_timestamp: u64,
+    ) -> Result<()> {
+        if let Some(entry) = self
+            .orders
+            .iter_mut()
+            .find(|x| x.order_number == order_number)
+        {
+            entry.unstaked_timestamp = unstaked_timestamp;
+            entry.is_early_unstaked = true;
+        }
+        Ok(())
+    }
+
+    /// Removes a completed order from the index.
+    pub fn remove_order(&mut self, order_number: u16) -> Result<()> {
+        self.orders.retain(|x| x.order_number != order_number);
+        Ok(())
+    }
+
+    /// Applies `update` to the index held by `index`, the stake order index address of a player, if it exists.
+    /// Players whose orders all predate the index have no index account, which exit paths do not create.
+    pub fn update_if_exists(
+        index: &AccountInfo,
+        update: impl FnOnce(&mut StakeOrderIndex) -> Result<()>,
+    ) -> Result<()> {
+        if index.data_is_empty() {
+            return Ok(());
+        }
+        let mut data = index.try_borrow_mut_data()?;
+        let mut stake_order_index = StakeOrderIndex::try_deserialize(&mut &data[..])?;
+        update(&mut stake_order_index)?;
+        stake_order_index.try_serialize(&mut &mut data[..])
+    }
+}
+
+impl VaultAuthority for StakePool {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[STAKE_POOL_SEED, &[bump]])
+    }
+}
@@ -1,10 +1,15 @@
+use crate::constants::TEAM_SEED;
 use crate::errors::ErrorCode;
+use crate::utils::VaultAuthority;
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 
 const MAX_APPLICATION_LIST_LENGTH: usize = 10;
 const MAX_MEMBER_LIST_LENGTH: usize = 30;
-const MAX_MANAGER_LIST_LENGTH: usize = 3;
+pub const MAX_MANAGER_LIST_LENGTH: usize = 3;
+
+/// Number of top members whose ores count towards the `TopMembers` team scoring mode.
+pub const TOP_MEMBERS_COUNT: usize = 5;
 
 #[account]
 #[derive(Debug, Default, InitSpace)]
@@ -22,8 +27,16 @@ const MAX_MANAGER_LIST_LENGTH: usize = 3;
 /// - `current_period`: The current competition period in which the team is participating.
 /// - `purchased_ores`: The cumulative total of ores purchased by team members over the team's lifetime.
 /// - `current_period_purchased_ores`: The total ores purchased by the team in the current period, useful for leaderboard standings.
+/// - `current_period_top_members`: The members with the most ores purchased in the current period, sorted in descending order.
+/// - `current_period_active_members`: The number of members who purchased ores in the current period.
+/// - `previous_period`, `previous_period_purchased_ores`: The period before `current_period` the team purchased ores in,
+///   and the ores it purchased then, kept for the team quests finalized after the team moved on to the next period.
 /// - `distributable_team_rewards`: The amount of rewards currently available for the team to collect.
+/// - `distributable_team_voucher_rewards`: The rewards of periods paying in vouchers, held as tokens in the team vault
+///   until they are distributed to members as vouchers.
 /// - `distributed_team_rewards`: The total amount of rewards the team has already claimed.
+/// - `pending_distributions`: The number of distributions proposed through `propose_team_distribution`
+///   that have not been executed or vetoed yet.
 /// - `last_updated_timestamp`: The UNIX timestamp when the team's data was last updated, useful for time-based logic.
 /// - `bump`: A PDA bump seed for the team account.
 pub struct Team {
@@ -51,6 +64,18 @@ pub struct Team {
     pub last_updated_timestamp: u64,
 
     pub bump: u8,
+
+    // Fields appended since the first deployment, converted by `migrate_team_accounts`. New fields go at the end.
+    #[max_len(TOP_MEMBERS_COUNT)]
+    pub current_period_top_members: Vec<TeamMemberScore>,
+    pub current_period_active_members: u16,
+
+    pub previous_period: Pubkey,
+    pub previous_period_purchased_ores: u32,
+
+    pub distributable_team_voucher_rewards: u64,
+
+    pub pending_distributions: u16,
 }
 
 impl Team {
@@ -199,17 +224,167 @@ impl Team {
         require!(!self.is_captain(player), ErrorCode::TeamCaptainCannotLeave);
         self.member_list.retain(|&x| x != player);
         self.manager_list.retain(|&x| x != player);
+        self.current_period_top_members
+            .retain(|member| member.member != player);
         Ok(())
     }
 
     /// Updates the current period for the team and resets period-based ore counts if the period changes.
     pub fn update_current_period(&mut self, current_period_pubkey: Pubkey) {
         if self.current_period != current_period_pubkey {
+            self.previous_period = self.current_period;
+            self.previous_period_purchased_ores = self.current_period_purchased_ores;
             self.current_period = current_period_pubkey;
             self.current_period_purchased_ores = 0;
+            self.current_period_top_members.clear();
+            self.current_period_active_members = 0;
         }
     }
 
+    /// Returns the ores the team purchased during `period`, zero if it is neither its current nor its previous period.
+    pub fn period_purchased_ores(&self, period: Pubkey) -> u32 {
+        if period == self.current_period {
+            self.current_period_purchased_ores
+        } else if period == self.previous_period {
+            self.previous_period_purchased_ores
+        } else {
+            0
+        }
+    }
+
+    /// Records a member's ores purchased in the current period, counting the member as active on their first purchase
+    /// and keeping the top members sorted in descending order.
+    ///
+    /// # Arguments
+    /// - `member`: The public key of the member.
+    /// - `added_ores`: The ores the member just purchased.
+    /// - `purchased_ores`: The member's total ores purchased in the current period, including `added_ores`.
+    pub fn record_member_ores(
+        &mut self,
+        member: Pubkey,
+        added_ores: u32,
+        purchased_ores: u32,
+    ) -> Result<()> {
+        if added_ores > 0 && purchased_ores == added_ores {
+            self.current_period_active_members = self.current_period_active_members.safe_add(1)?;
+        }
+
+        // Scores only grow, so a member outside a full list is only inserted once they outscore the last entry
+        let members = &mut self.current_period_top_members;
+        if let Some(index) = members.iter().position(|m| m.member == member) {
+            members.remove(index);
+        } else if members.len() >= TOP_MEMBERS_COUNT {
+            if members
+                .last()
+                .is_some_and(|last| last.purchased_ores >= purchased_ores)
+            {
+                return Ok(());
+            }
+            members.pop();
+        }
+
+        let index = members
+            .iter()
+            .position(|m| m.purchased_ores < purchased_ores)
+            .unwrap_or(members.len());
+        members.insert(
+            index,
+            TeamMemberScore {
+                member,
+                purchased_ores,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Computes the team's leaderboard score for the current period under the given scoring mode.
+    pub fn period_score(&self, team_scoring_mode: TeamScoringMode) -> Result<u32> {
+        match team_scoring_mode {
+            TeamScoringMode::TotalOres => Ok(self.current_period_purchased_ores),
+            TeamScoringMode::AveragePerMember => Ok(self
+                .current_period_purchased_ores
+                .safe_div(self.member_list.len().max(1) as u32)?),
+            TeamScoringMode::TopMembers => self
+                .current_period_top_members
+                .iter()
+                .try_fold(0u32, |score, member| {
+                    Ok(score.safe_add(member.purchased_ores)?)
+                }),
+        }
+    }
+
+    /// Hands the captaincy to `claimant` after the captain has been inactive for `captain_inactive_seconds`.
+    /// The longest-tenured manager, first in `manager_list`, may claim it once the inactivity reaches
+    /// `captain_inactivity_seconds`; any member may once it reaches twice as long, or right away if there are no managers.
+    ///
+    /// # Returns
+    /// The previous captain.
+    pub fn claim_captaincy(
+        &mut self,
+        claimant: Pubkey,
+        captain_inactive_seconds: u64,
+        captain_inactivity_seconds: u64,
+    ) -> Result<Pubkey> {
+        require!(self.is_member(claimant), ErrorCode::NotATeamMember);
+        require!(!self.is_captain(claimant), ErrorCode::CantTransferToSelf);
+        require!(
+            captain_inactive_seconds >= captain_inactivity_seconds,
+            ErrorCode::CaptainStillActive
+        );
+        if let Some(&successor) = self.manager_list.first() {
+            require!(
+                claimant == successor
+                    || captain_inactive_seconds >= captain_inactivity_seconds.saturating_mul(2),
+                ErrorCode::NotNextInSuccession
+            );
+        }
+
+        let previous_captain = self.captain;
+        self.transfer_captaincy(claimant)?;
+        Ok(previous_captain)
+    }
+
+    /// Absorbs `absorbed` into this team: its members join this team as plain members, and its lifetime ores
+    /// and reward counters are added to this team's. The absorbed team may have no pending applications, no
+    /// pending distributions, and no ores purchased in `current_period`, where it could still be ranked, and the
+    /// merged team must fit. Its ranks in ended periods stay claimable through its `TeamMerge` record.
+    ///
+    /// # Returns
+    /// The members who joined this team.
+    pub fn absorb(&mut self, absorbed: &Team, current_period: Pubkey) -> Result<Vec<Pubkey>> {
+        require!(
+            absorbed.application_list.is_empty(),
+            ErrorCode::InvalidTeamMerge
+        );
+        require!(
+            absorbed.pending_distributions == 0,
+            ErrorCode::AbsorbedTeamHasPendingDistributions
+        );
+        require!(
+            absorbed.period_purchased_ores(current_period) == 0,
+            ErrorCode::AbsorbedTeamRanked
+        );
+        require!(
+            self.member_list.len() + absorbed.member_list.len() <= MAX_MEMBER_LIST_LENGTH,
+            ErrorCode::TeamFull
+        );
+
+        self.member_list.extend_from_slice(&absorbed.member_list);
+        self.purchased_ores = self.purchased_ores.safe_add(absorbed.purchased_ores)?;
+        self.distributable_team_rewards = self
+            .distributable_team_rewards
+            .safe_add(absorbed.distributable_team_rewards)?;
+        self.distributable_team_voucher_rewards = self
+            .distributable_team_voucher_rewards
+            .safe_add(absorbed.distributable_team_voucher_rewards)?;
+        self.distributed_team_rewards = self
+            .distributed_team_rewards
+            .safe_add(absorbed.distributed_team_rewards)?;
+
+        Ok(absorbed.member_list.clone())
+    }
+
     /// Distributes a specified amount of team rewards if enough are available.
     pub fn distribute_team_rewards(&mut self, reward_amount: u64) -> Result<()> {
         require!(
@@ -221,4 +396,58 @@ impl Team {
         self.distributed_team_rewards = self.distributed_team_rewards.safe_add(reward_amount)?;
         Ok(())
     }
+
+    /// Distributes a specified amount of team rewards payable in vouchers if enough are available.
+    pub fn distribute_team_voucher_rewards(&mut self, reward_amount: u64) -> Result<()> {
+        require!(
+            self.distributable_team_voucher_rewards >= reward_amount,
+            ErrorCode::InsufficientTeamRewardBalance
+        );
+        self.distributable_team_voucher_rewards = self
+            .distributable_team_voucher_rewards
+            .safe_sub(reward_amount)?;
+        self.distributed_team_rewards = self.distributed_team_rewards.safe_add(reward_amount)?;
+        Ok(())
+    }
+
+    /// Counts a distribution proposed through `propose_team_distribution`.
+    pub fn add_pending_distribution(&mut self) -> Result<()> {
+        self.pending_distributions = self.pending_distributions.safe_add(1)?;
+        Ok(())
+    }
+
+    /// Stops counting a proposed distribution once it is executed or vetoed.
+    pub fn remove_pending_distribution(&mut self) -> Result<()> {
+        self.pending_distributions = self.pending_distributions.saturating_sub(1);
+        Ok(())
+    }
+}
+
+/// A team member's ores purchased in the current period, tracked for the `TopMembers` scoring mode.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, InitSpace)]
+pub struct TeamMemberScore {
+    /// The public key of the member
+    pub member: Pubkey,
+
+    /// The total amount of purchased ores by this member during the current period
+    pub purchased_ores: u32,
+}
+
+/// How a team's leaderboard score is computed during a period.
+/// Selected when the period is created, so that mega-teams do not always win.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TeamScoringMode {
+    /// The total ores purchased by all members.
+    TotalOres = 0,
+    /// The total ores purchased divided by the number of members.
+    AveragePerMember = 1,
+    /// The ores purchased by the `TOP_MEMBERS_COUNT` most active members only.
+    TopMembers = 2,
+}
+
+impl VaultAuthority for Team {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[TEAM_SEED, &self.team_number.to_le_bytes(), &[bump]])
+    }
 }
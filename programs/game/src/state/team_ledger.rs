@@ -0,0 +1,66 @@
+use crate::state::PayoutAsset;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// The number of distributions kept in a `TeamLedger`.
+pub const TEAM_LEDGER_LENGTH: usize = 16;
+
+/// The `TeamLedger` account keeps the most recent distributions out of a team's vault, so that members can audit
+/// their captain's spending without an external indexer. It is written by every team reward distribution, the oldest
+/// entry being overwritten once `TEAM_LEDGER_LENGTH` entries are kept.
+///
+/// # Fields
+/// - `team`: The team whose vault distributions are recorded.
+/// - `entries`: The most recent distributions, in a ring starting at `next_index`.
+/// - `next_index`: The index of the entry the next distribution is written to, the oldest one once the ring is full.
+/// - `distribution_count`: The number of distributions recorded since the ledger was created.
+/// - `total_distributed`: The total amount distributed since the ledger was created.
+/// - `bump`: The PDA bump seed of the ledger.
+#[account]
+#[derive(Debug, Default, InitSpace)]
+pub struct TeamLedger {
+    pub team: Pubkey,
+    pub entries: [TeamLedgerEntry; TEAM_LEDGER_LENGTH],
+    pub next_index: u8,
+    pub distribution_count: u32,
+    pub total_distributed: u64,
+    pub bump: u8,
+}
+
+/// A distribution out of a team's vault.
+///
+/// # Fields
+/// - `captain`: The captain who distributed the rewards.
+/// - `member`: The member who received the rewards.
+/// - `amount`: The rewards distributed.
+/// - `payout_asset`: The asset the rewards were paid in.
+/// - `timestamp`: The UNIX timestamp of the distribution.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub struct TeamLedgerEntry {
+    pub captain: Pubkey,
+    pub member: Pubkey,
+    pub amount: u64,
+    pub payout_asset: PayoutAsset,
+    pub timestamp: u64,
+}
+
+impl TeamLedger {
+    /// Binds a newly created ledger to its team. Does nothing for an existing ledger.
+    pub fn initialize_if_needed(&mut self, team: Pubkey, bump: u8) {
+        if self.team == Pubkey::default() {
+            self.team = team;
+            self.bump = bump;
+        }
+    }
+
+    /// Records a distribution, overwriting the oldest one once the ledger is full.
+    pub fn record(&mut self, entry: TeamLedgerEntry) -> Result<()> {
+        self.entries[self.next_index as usize] = entry;
+        self.next_index = ((self.next_index as usize + 1) % TEAM_LEDGER_LENGTH) as u8;
+        self.distribution_count = self.distribution_count.safe_add(1)?;
+        self.total_distributed = self.total_distributed.safe_add(entry.amount)?;
+        Ok(())
+    }
+}
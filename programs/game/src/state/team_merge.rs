@@ -0,0 +1,55 @@
+use crate::constants::TEAM_MERGE_SEED;
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// The `TeamMerge` account records a team closed by `merge_teams`, so that the ranks it still holds on the team
+/// leaderboards of ended periods remain claimable by the team it was merged into, through
+/// `claim_team_leaderboard_reward`.
+///
+/// # Fields
+/// - `absorbed_team`: The team closed by the merge.
+/// - `surviving_team`: The team the absorbed team was merged into.
+/// - `merged_timestamp`: The UNIX timestamp of the merge.
+/// - `bump`: The PDA bump seed of the merge record.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct TeamMerge {
+    pub absorbed_team: Pubkey,
+    pub surviving_team: Pubkey,
+    pub merged_timestamp: u64,
+    pub bump: u8,
+}
+
+impl TeamMerge {
+    /// Resolves the team holding a leaderboard rank on behalf of `team`, from the merge records leading to it.
+    /// `records` are the `TeamMerge` accounts of a chain of merges ending in `team`, the first absorbed team first:
+    /// each record's surviving team is the next record's absorbed team, and the last one's is `team`.
+    ///
+    /// # Returns
+    /// The first absorbed team of the chain, or `team` itself if no record is supplied.
+    pub fn resolve_rank_holder(
+        team: Pubkey,
+        records: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> Result<Pubkey> {
+        let mut rank_holder = team;
+        for record in records.iter().rev() {
+            require_keys_eq!(
+                *record.owner,
+                *program_id,
+                ErrorCode::InvalidRemainingAccounts
+            );
+            let team_merge = TeamMerge::try_deserialize(&mut &record.try_borrow_data()?[..])?;
+            let (team_merge_address, _) = Pubkey::find_program_address(
+                &[TEAM_MERGE_SEED, team_merge.absorbed_team.as_ref()],
+                program_id,
+            );
+            require!(
+                record.key() == team_merge_address && team_merge.surviving_team == rank_holder,
+                ErrorCode::InvalidRemainingAccounts
+            );
+            rank_holder = team_merge.absorbed_team;
+        }
+        Ok(rank_holder)
+    }
+}
@@ -0,0 +1,134 @@
+use crate::constants::TEAM_QUEST_SEED;
+use crate::errors::ErrorCode;
+use crate::utils::VaultAuthority;
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// The `TeamQuest` account sets a period's collective goal for the teams: every team whose members purchase
+/// at least `goal_ores` ORE during the period earns `bonus_rewards`, paid from the quest's vault into the team's
+/// `distributable_team_rewards` by the permissionless `finalize_team_quest` crank once the period has ended.
+///
+/// # Fields
+/// - `period`: The period the quest runs in, seeding its PDA.
+/// - `quest_vault`: The token account holding the quest's budget, owned by the quest.
+/// - `goal_ores`: The ORE a team must purchase during the period to complete the quest.
+/// - `bonus_rewards`: The rewards of every team completing the quest.
+/// - `funded_amount`: The tokens deposited into the quest's vault.
+/// - `distributed_amount`: The cumulative bonus rewards paid to the teams.
+/// - `completed_team_count`: The number of teams the quest was finalized for.
+/// - `bump`: The PDA bump seed of the quest.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct TeamQuest {
+    pub period: Pubkey,
+    pub quest_vault: Pubkey,
+    pub goal_ores: u32,
+    pub bonus_rewards: u64,
+    pub funded_amount: u64,
+    pub distributed_amount: u64,
+    pub completed_team_count: u32,
+    pub bump: u8,
+}
+
+impl TeamQuest {
+    /// Initializes the quest of `period` with a budget of `funded_amount` tokens.
+    ///
+    /// # Arguments
+    /// - `period`: The period the quest runs in.
+    /// - `quest_vault`: The token account holding the quest's budget.
+    /// - `goal_ores`: The ORE a team must purchase during the period.
+    /// - `bonus_rewards`: The rewards of every team completing the quest.
+    /// - `funded_amount`: The tokens deposited into the quest's vault, covering at least one bonus.
+    /// - `bump`: The PDA bump seed.
+    pub fn initialize(
+        &mut self,
+        period: Pubkey,
+        quest_vault: Pubkey,
+        goal_ores: u32,
+        bonus_rewards: u64,
+        funded_amount: u64,
+        bump: u8,
+    ) -> Result<()> {
+        require!(
+            goal_ores > 0 && bonus_rewards > 0 && funded_amount >= bonus_rewards,
+            ErrorCode::InvalidTeamQuest
+        );
+
+        *self = TeamQuest {
+            period,
+            quest_vault,
+            goal_ores,
+            bonus_rewards,
+            funded_amount,
+            distributed_amount: 0,
+            completed_team_count: 0,
+            bump,
+        };
+        Ok(())
+    }
+
+    /// Records the bonus rewards of a team completing the quest with `purchased_ores` ORE, within the quest's budget.
+    ///
+    /// # Returns
+    /// Returns the bonus rewards of the team.
+    pub fn record_completion(&mut self, purchased_ores: u32) -> Result<u64> {
+        require!(
+            purchased_ores >= self.goal_ores,
+            ErrorCode::TeamQuestNotCompleted
+        );
+
+        let distributed_amount = self.distributed_amount.safe_add(self.bonus_rewards)?;
+        require!(
+            distributed_amount <= self.funded_amount,
+            ErrorCode::InsufficientTeamQuestBudget
+        );
+
+        self.distributed_amount = distributed_amount;
+        self.completed_team_count = self.completed_team_count.safe_add(1)?;
+        Ok(self.bonus_rewards)
+    }
+}
+
+impl VaultAuthority for TeamQuest {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[TEAM_QUEST_SEED, self.period.as_ref(), &[bump]])
+    }
+}
+
+/// The `TeamQuestCompletion` account records that a team was paid the bonus of a `TeamQuest`, so it is paid once.
+///
+/// # Fields
+/// - `team_quest`: The completed quest.
+/// - `team`: The team that completed it.
+/// - `purchased_ores`: The ORE the team purchased during the quest's period.
+/// - `bonus_rewards`: The bonus rewards paid to the team.
+/// - `bump`: The PDA bump seed of the record.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct TeamQuestCompletion {
+    pub team_quest: Pubkey,
+    pub team: Pubkey,
+    pub purchased_ores: u32,
+    pub bonus_rewards: u64,
+    pub bump: u8,
+}
+
+impl TeamQuestCompletion {
+    /// Records the bonus rewards paid to `team` for completing `team_quest` with `purchased_ores` ORE.
+    pub fn initialize(
+        &mut self,
+        team_quest: Pubkey,
+        team: Pubkey,
+        purchased_ores: u32,
+        bonus_rewards: u64,
+        bump: u8,
+    ) {
+        *self = TeamQuestCompletion {
+            team_quest,
+            team,
+            purchased_ores,
+            bonus_rewards,
+            bump,
+        };
+    }
+}
@@ -1,3 +1,5 @@
+use crate::constants::VAULT_SEED;
+use crate::utils::VaultAuthority;
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 
@@ -37,3 +39,9 @@ impl Vault {
         Ok(())
     }
 }
+
+impl VaultAuthority for Vault {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[VAULT_SEED, &[bump]])
+    }
+}
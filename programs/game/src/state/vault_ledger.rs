@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// The `VaultLedger` account audits a single token vault against the logical pool balances it backs.
+/// The pool balances are updated by every instruction alongside its transfers and burns; reconciling
+/// compares their sum with the vault's actual token balance, so accounting drift becomes detectable.
+///
+/// # Fields
+/// - `vault`: The token vault audited by this ledger.
+/// - `backed_balance`: The sum of the pool balances backed by the vault at the last reconciliation.
+/// - `vault_balance`: The vault's token balance at the last reconciliation.
+/// - `last_reconciled_timestamp`: The UNIX timestamp of the last reconciliation.
+/// - `reconcile_count`: The number of reconciliations performed.
+/// - `discrepancy_count`: The number of reconciliations where the balances differed.
+/// - `bump`: The PDA bump seed of the ledger.
+#[account]
+#[derive(Debug, Default, InitSpace)]
+pub struct VaultLedger {
+    pub vault: Pubkey,
+
+    pub backed_balance: u64,
+    pub vault_balance: u64,
+
+    pub last_reconciled_timestamp: u64,
+    pub reconcile_count: u32,
+    pub discrepancy_count: u32,
+
+    pub bump: u8,
+}
+
+impl VaultLedger {
+    /// Binds a newly created ledger to its vault. Does nothing for an existing ledger.
+    pub fn initialize_if_needed(&mut self, vault: Pubkey, bump: u8) {
+        if self.vault == Pubkey::default() {
+            self.vault = vault;
+            self.bump = bump;
+        }
+    }
+
+    /// Records a reconciliation of the vault.
+    ///
+    /// # Arguments
+    /// - `backed_balance`: The sum of the pool balances backed by the vault.
+    /// - `vault_balance`: The vault's actual token balance.
+    /// - `timestamp`: The current UNIX timestamp.
+    ///
+    /// # Returns
+    /// Returns whether the two balances differ.
+    pub fn reconcile(
+        &mut self,
+        backed_balance: u64,
+        vault_balance: u64,
+        timestamp: u64,
+    ) -> Result<bool> {
+        let has_discrepancy = backed_balance != vault_balance;
+
+        self.backed_balance = backed_balance;
+        self.vault_balance = vault_balance;
+        self.last_reconciled_timestamp = timestamp;
+        self.reconcile_count = self.reconcile_count.safe_add(1)?;
+        if has_discrepancy {
+            self.discrepancy_count = self.discrepancy_count.safe_add(1)?;
+        }
+
+        Ok(has_discrepancy)
+    }
+}
+
+/// A vault's token balance compared with the balance its pool fields say it should hold, reported by `reconcile_game`.
+///
+/// # Fields
+/// - `vault`: The token vault audited.
+/// - `expected_balance`: The balance recomputed from the pool fields backed by the vault.
+/// - `actual_balance`: The vault's token balance.
+/// - `delta`: `actual_balance` minus `expected_balance`, negative when the vault is short of its obligations.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VaultReconciliation {
+    pub vault: Pubkey,
+    pub expected_balance: u64,
+    pub actual_balance: u64,
+    pub delta: i128,
+}
+
+impl VaultReconciliation {
+    /// Compares the `actual_balance` of `vault` with its `expected_balance`.
+    pub fn new(vault: Pubkey, expected_balance: u64, actual_balance: u64) -> Self {
+        Self {
+            vault,
+            expected_balance,
+            actual_balance,
+            delta: actual_balance as i128 - expected_balance as i128,
+        }
+    }
+
+    /// Whether the vault holds exactly its expected balance.
+    pub fn is_balanced(&self) -> bool {
+        self.delta == 0
+    }
+}
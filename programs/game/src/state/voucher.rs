@@ -1,3 +1,6 @@
+use crate::constants::VOUCHER_SEED;
+use crate::errors::ErrorCode;
+use crate::utils::VaultAuthority;
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
 
@@ -20,6 +23,14 @@ pub struct Voucher {
     /// The total supply of vouchers that have been issued and not burned.
     /// Represents the cumulative amount of vouchers ever minted minus those burned.
     pub total_supply: u64,
+
+    /// Whether vouchers are soulbound. While set, the authority may freeze player voucher accounts
+    /// so that vouchers can only move through game instructions.
+    pub is_transfer_restricted: bool,
+
+    /// The percentage of the underlying tokens withheld when a player redeems vouchers through `redeem_vouchers`.
+    /// The withheld tokens are credited to the developer rewards pool.
+    pub redemption_fee_rate: u8,
 }
 
 impl Voucher {
@@ -56,6 +67,28 @@ impl Voucher {
         Ok(())
     }
 
+    /// Enables or disables the voucher soulbound mode.
+    ///
+    /// # Arguments
+    /// - `is_transfer_restricted`: Whether player voucher accounts may be frozen.
+    pub fn set_transfer_restricted(&mut self, is_transfer_restricted: bool) -> Result<()> {
+        self.is_transfer_restricted = is_transfer_restricted;
+        Ok(())
+    }
+
+    /// Updates the fee charged when vouchers are redeemed for tokens.
+    ///
+    /// # Arguments
+    /// - `redemption_fee_rate`: The percentage (0-100) of the underlying tokens withheld on redemption.
+    pub fn set_redemption_fee_rate(&mut self, redemption_fee_rate: u8) -> Result<()> {
+        require!(
+            redemption_fee_rate <= 100,
+            ErrorCode::InvalidVoucherRedemptionFeeRate
+        );
+        self.redemption_fee_rate = redemption_fee_rate;
+        Ok(())
+    }
+
     /// Burns a specified `amount` of vouchers, decreasing the `total_supply`.
     /// This function should be called when vouchers are redeemed, destroyed, or otherwise removed from circulation.
     ///
@@ -69,3 +102,9 @@ impl Voucher {
         Ok(())
     }
 }
+
+impl VaultAuthority for Voucher {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[VOUCHER_SEED, &[bump]])
+    }
+}
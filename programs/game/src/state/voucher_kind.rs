@@ -0,0 +1,111 @@
+use crate::constants::{MAX_VOUCHER_KIND_VALUE_RATE, VOUCHER_KIND_SEED};
+use crate::errors::ErrorCode;
+use crate::utils::{calculate_voucher_kind_payment, calculate_voucher_kind_value, VaultAuthority};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+/// The `VoucherKind` account registers a promotional voucher currency next to the standard `Voucher`,
+/// such as a premium credit worth more than its face value. Each kind has its own mint, and a vault
+/// holding the tokens backing its vouchers at the kind's value rate.
+///
+/// # Fields
+/// - `kind_id`: The identifier of the kind, seeding its PDA and its mint's PDA.
+/// - `voucher_mint`: The mint of the kind's vouchers, with the kind as mint authority.
+/// - `voucher_vault`: The token account holding the tokens backing the kind's vouchers.
+/// - `value_rate`: The value of one voucher of the kind, as a percentage of a token.
+/// - `minted_amount`: The cumulative vouchers of the kind issued.
+/// - `total_supply`: The vouchers of the kind issued and not burned.
+/// - `total_burned`: The cumulative vouchers of the kind burned on purchases.
+/// - `is_enabled`: Whether the kind may be issued and spent.
+/// - `bump`: The PDA bump seed of the kind.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct VoucherKind {
+    pub kind_id: u16,
+    pub voucher_mint: Pubkey,
+    pub voucher_vault: Pubkey,
+    pub value_rate: u16,
+    pub minted_amount: u64,
+    pub total_supply: u64,
+    pub total_burned: u64,
+    pub is_enabled: bool,
+    pub bump: u8,
+}
+
+impl VoucherKind {
+    /// Initializes the voucher kind with its mint, vault and value rate, enabled.
+    pub fn initialize(
+        &mut self,
+        kind_id: u16,
+        voucher_mint: Pubkey,
+        voucher_vault: Pubkey,
+        value_rate: u16,
+        bump: u8,
+    ) -> Result<()> {
+        *self = VoucherKind {
+            kind_id,
+            voucher_mint,
+            voucher_vault,
+            value_rate: 0,
+            minted_amount: 0,
+            total_supply: 0,
+            total_burned: 0,
+            is_enabled: true,
+            bump,
+        };
+        self.set_value_rate(value_rate)
+    }
+
+    /// Updates the value of one voucher of the kind.
+    ///
+    /// # Arguments
+    /// - `value_rate`: The value as a percentage of a token, between 1 and `MAX_VOUCHER_KIND_VALUE_RATE`.
+    pub fn set_value_rate(&mut self, value_rate: u16) -> Result<()> {
+        require!(
+            value_rate > 0 && value_rate <= MAX_VOUCHER_KIND_VALUE_RATE,
+            ErrorCode::InvalidVoucherKindValueRate
+        );
+        self.value_rate = value_rate;
+        Ok(())
+    }
+
+    /// Ensures the kind may be issued and spent.
+    pub fn require_enabled(&self) -> Result<()> {
+        require!(self.is_enabled, ErrorCode::VoucherKindDisabled);
+        Ok(())
+    }
+
+    /// Returns the tokens backing `amount` vouchers of the kind.
+    pub fn value_of(&self, amount: u64) -> Result<u64> {
+        Ok(calculate_voucher_kind_value(amount, self.value_rate)?)
+    }
+
+    /// Returns the vouchers of the kind burned to pay `cost`, out of `voucher_balance`, and the tokens they are worth.
+    pub fn payment(&self, cost: u64, voucher_balance: u64) -> Result<(u64, u64)> {
+        Ok(calculate_voucher_kind_payment(
+            cost,
+            voucher_balance,
+            self.value_rate,
+        )?)
+    }
+
+    /// Records `amount` vouchers of the kind issued.
+    pub fn mint(&mut self, amount: u64) -> Result<()> {
+        self.minted_amount = self.minted_amount.safe_add(amount)?;
+        self.total_supply = self.total_supply.safe_add(amount)?;
+        Ok(())
+    }
+
+    /// Records `amount` vouchers of the kind burned on a purchase.
+    pub fn burn(&mut self, amount: u64) -> Result<()> {
+        self.total_supply = self.total_supply.safe_sub(amount)?;
+        self.total_burned = self.total_burned.safe_add(amount)?;
+        Ok(())
+    }
+}
+
+impl VaultAuthority for VoucherKind {
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R {
+        f(&[VOUCHER_KIND_SEED, &self.kind_id.to_le_bytes(), &[bump]])
+    }
+}
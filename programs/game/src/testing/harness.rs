@@ -0,0 +1,735 @@
+//! A `ProgramTest` harness driving the instruction handlers, enabled with the `test-utils` feature.
+//!
+//! `GameHarness` runs the program natively inside a `ProgramTestContext`, on a `MockClock` it renders into the
+//! `Clock` sysvar after every step, and sends the game's instructions with their accounts derived from the
+//! on-chain state, so scenario tests only pick the players and the amounts.
+
+use super::{account_data, GameAddresses, MockClock, PlayerAddresses};
+use crate::constants::{DEFAULT_PLAYER, TOKEN_DECIMALS};
+use crate::state::{Game, Period, PeriodConfig, PlayerData, Round, Voucher};
+use anchor_lang::{prelude::*, system_program, InstructionData, ToAccountMetas, ZeroCopy};
+use anchor_spl::associated_token::{
+    get_associated_token_address,
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent,
+};
+use anchor_spl::token::spl_token;
+use solana_program::{instruction::Instruction, program_option::COption, program_pack::Pack};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+
+/// The decimals of the voucher mint, as `initialize_voucher` creates it.
+const VOUCHER_DECIMALS: u8 = 6;
+
+/// The lamports each player created by the harness starts with, covering the rent of their accounts.
+pub const PLAYER_LAMPORTS: u64 = 10_000_000_000;
+
+/// Runs the program natively, leaking the account infos so they outlive the `'info` lifetime `entry` expects.
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> solana_program::entrypoint::ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    crate::entry(program_id, accounts, data)
+}
+
+/// A running game under test.
+///
+/// # Fields
+/// - `context`: The `ProgramTest` context the program runs in.
+/// - `clock`: The clock the context runs on, only moved by `advance`.
+/// - `addresses`: The PDAs of the global game accounts.
+/// - `authority`: The game authority, standing in for `SUPER_ADMIN` once the game is initialized.
+/// - `bot_authority`: The bot authority running the rounds and periods.
+/// - `token_mint`: The game token mint, minted from by `fund`.
+pub struct GameHarness {
+    pub context: ProgramTestContext,
+    pub clock: MockClock,
+    pub addresses: GameAddresses,
+    pub authority: Keypair,
+    pub bot_authority: Keypair,
+    pub token_mint: Pubkey,
+    mint_authority: Keypair,
+}
+
+impl GameHarness {
+    /// Starts the program with a game token mint and funded authorities, its clock at `unix_timestamp`.
+    /// The game itself is set up by `initialize`.
+    ///
+    /// `initialize_voucher` creates the voucher's metadata through the token metadata program, which is not
+    /// loaded in the context, so the voucher accounts are preloaded as that instruction leaves them instead.
+    pub async fn start(unix_timestamp: i64) -> Self {
+        let mut program_test = ProgramTest::new("game", crate::ID, processor!(process_instruction));
+        program_test.prefer_bpf(false);
+
+        let authority = Keypair::new();
+        let bot_authority = Keypair::new();
+        let mint_authority = Keypair::new();
+        for keypair in [&authority, &bot_authority] {
+            program_test.add_account(
+                keypair.pubkey(),
+                Account::new(PLAYER_LAMPORTS, 0, &system_program::ID),
+            );
+        }
+
+        let token_mint = Pubkey::new_unique();
+        program_test.add_account(
+            token_mint,
+            packed_account(
+                &spl_token::ID,
+                spl_token::state::Mint {
+                    mint_authority: COption::Some(mint_authority.pubkey()),
+                    supply: 0,
+                    decimals: TOKEN_DECIMALS,
+                    is_initialized: true,
+                    freeze_authority: COption::None,
+                },
+            ),
+        );
+
+        let addresses = GameAddresses::derive();
+        preload_voucher(&mut program_test, &addresses, token_mint);
+
+        let context = program_test.start_with_context().await;
+        let mut clock = MockClock::new(unix_timestamp);
+        clock.slot = context
+            .banks_client
+            .clone()
+            .get_sysvar::<Clock>()
+            .await
+            .expect("the clock is readable")
+            .slot;
+
+        let harness = GameHarness {
+            context,
+            clock,
+            addresses,
+            authority,
+            bot_authority,
+            token_mint,
+            mint_authority,
+        };
+        harness.context.set_sysvar(&harness.clock.sysvar());
+        harness
+    }
+
+    /// Advances the clock by `seconds`, warping the context to the matching slot.
+    pub async fn advance(&mut self, seconds: u64) {
+        self.clock.advance(seconds);
+        self.sync_clock().await;
+    }
+
+    /// Advances the clock to `unix_timestamp`, warping the context to the matching slot.
+    pub async fn advance_to(&mut self, unix_timestamp: u64) {
+        self.clock.advance_to(unix_timestamp);
+        self.sync_clock().await;
+    }
+
+    async fn sync_clock(&mut self) {
+        let slot = self
+            .context
+            .banks_client
+            .get_root_slot()
+            .await
+            .expect("the slot is readable");
+        if self.clock.slot > slot {
+            self.context
+                .warp_to_slot(self.clock.slot)
+                .expect("the context warps forward");
+        }
+        self.context.set_sysvar(&self.clock.sysvar());
+    }
+
+    /// Sends `instructions` in one transaction paid by the context's payer and signed by `signers`.
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> std::result::Result<(), BanksClientError> {
+        let blockhash = self
+            .context
+            .banks_client
+            .get_latest_blockhash()
+            .await
+            .expect("a blockhash is available");
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    /// Fetches and deserializes the Borsh account at `address`.
+    pub async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self.raw_account(address).await;
+        T::try_deserialize(&mut account.data.as_slice()).expect("the account deserializes")
+    }
+
+    /// Fetches the zero-copy account at `address`.
+    pub async fn zero_copy_account<T: ZeroCopy>(&mut self, address: Pubkey) -> T {
+        let account = self.raw_account(address).await;
+        assert_eq!(
+            &account.data[..8],
+            T::DISCRIMINATOR,
+            "the account is a zero-copy `T`"
+        );
+        bytemuck::pod_read_unaligned(&account.data[8..8 + std::mem::size_of::<T>()])
+    }
+
+    /// Fetches the token balance of the token account at `address`.
+    pub async fn token_balance(&mut self, address: Pubkey) -> u64 {
+        let account = self.raw_account(address).await;
+        spl_token::state::Account::unpack(&account.data)
+            .expect("the account is a token account")
+            .amount
+    }
+
+    /// Fetches the supply of the mint at `address`.
+    pub async fn mint_supply(&mut self, address: Pubkey) -> u64 {
+        let account = self.raw_account(address).await;
+        spl_token::state::Mint::unpack(&account.data)
+            .expect("the account is a mint")
+            .supply
+    }
+
+    async fn raw_account(&mut self, address: Pubkey) -> Account {
+        self.context
+            .banks_client
+            .get_account(address)
+            .await
+            .expect("the account is readable")
+            .unwrap_or_else(|| panic!("no account at {address}"))
+    }
+
+    /// Overwrites the Borsh account at `address` with `account`, keeping its owner and lamports so that the
+    /// bank's capitalization still checks out when the clock warps.
+    pub async fn set_account<T: AccountSerialize>(&mut self, address: Pubkey, account: &T) {
+        let mut raw = self.raw_account(address).await;
+        raw.data = account_data(account);
+        self.context.set_account(&address, &raw.into());
+    }
+
+    /// The game's token vault.
+    pub fn game_vault(&self) -> Pubkey {
+        get_associated_token_address(&self.addresses.game, &self.token_mint)
+    }
+
+    /// The voucher's token vault, backing the vouchers in circulation.
+    pub fn voucher_vault(&self) -> Pubkey {
+        get_associated_token_address(&self.addresses.voucher, &self.token_mint)
+    }
+
+    /// The game token account of `owner`.
+    pub fn token_account(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address(owner, &self.token_mint)
+    }
+
+    /// The voucher account of `owner`.
+    pub fn voucher_account(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address(owner, &self.addresses.voucher_mint)
+    }
+
+    /// Mints `amount` game tokens to `owner`, creating their token account if needed.
+    pub async fn fund(&mut self, owner: &Pubkey, amount: u64) {
+        let token_account = self.token_account(owner);
+        let instructions = [
+            create_associated_token_account_idempotent(
+                &self.context.payer.pubkey(),
+                owner,
+                &self.token_mint,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::mint_to(
+                &spl_token::ID,
+                &self.token_mint,
+                &token_account,
+                &self.mint_authority.pubkey(),
+                &[],
+                amount,
+            )
+            .expect("the mint instruction builds"),
+        ];
+        let mint_authority = self.mint_authority.insecure_clone();
+        self.process(&instructions, &[&mint_authority])
+            .await
+            .expect("the tokens are minted");
+    }
+
+    /// Creates a player wallet holding `PLAYER_LAMPORTS` and `tokens` game tokens.
+    pub async fn create_player(&mut self, tokens: u64) -> Keypair {
+        let player = Keypair::new();
+        let transfer = system_instruction::transfer(
+            &self.context.payer.pubkey(),
+            &player.pubkey(),
+            PLAYER_LAMPORTS,
+        );
+        self.process(&[transfer], &[])
+            .await
+            .expect("the player is funded");
+        self.fund(&player.pubkey(), tokens).await;
+        player
+    }
+
+    /// The PDAs of `player`'s accounts.
+    pub fn player(&self, player: &Pubkey) -> PlayerAddresses {
+        self.addresses.player(player)
+    }
+
+    /// Sets up the game: runs `initialize` with every reward pool funded with `pool_balance`, hands the game
+    /// authority to `authority`, then creates the default player and the default team.
+    pub async fn initialize(&mut self, pool_balance: u64) {
+        let authority = self.authority.insecure_clone();
+        self.fund(&authority.pubkey(), pool_balance * 8).await;
+
+        let initialize = self.instruction(
+            crate::accounts::Initialize {
+                authority: authority.pubkey(),
+                game: self.addresses.game,
+                game_config: self.addresses.game_config,
+                game_vault: self.game_vault(),
+                token_mint: self.token_mint,
+                token_account: self.token_account(&authority.pubkey()),
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+                event_ring_buffer: self.addresses.event_ring_buffer,
+                event_authority: self.addresses.event_authority,
+                program: crate::ID,
+            },
+            crate::instruction::Initialize {
+                bot_authority: self.bot_authority.pubkey(),
+                round_rewards: pool_balance,
+                period_rewards: pool_balance,
+                registration_rewards: pool_balance,
+                airdrop_rewards: pool_balance,
+                exit_rewards: pool_balance,
+                lottery_rewards: pool_balance,
+                consumption_rewards: pool_balance,
+                sugar_rush_rewards: pool_balance,
+            },
+        );
+        self.process(&[initialize], &[&authority])
+            .await
+            .expect("the game initializes");
+
+        // The game is handed to `SUPER_ADMIN`, whose key the harness does not hold
+        let mut game: Game = self.account(self.addresses.game).await;
+        game.authority = authority.pubkey();
+        self.set_account(self.addresses.game, &game).await;
+
+        let initialize_default_player = self.instruction(
+            crate::accounts::InitializeDefaultPlayer {
+                authority: authority.pubkey(),
+                game: self.addresses.game,
+                default_player: DEFAULT_PLAYER,
+                default_player_data: self.player(&DEFAULT_PLAYER).player_data,
+                token_mint: self.token_mint,
+                voucher_mint: self.addresses.voucher_mint,
+                token_account: self.token_account(&DEFAULT_PLAYER),
+                voucher_account: self.voucher_account(&DEFAULT_PLAYER),
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+                event_ring_buffer: self.addresses.event_ring_buffer,
+                event_authority: self.addresses.event_authority,
+                program: crate::ID,
+            },
+            crate::instruction::InitializeDefaultPlayer {},
+        );
+        let initialize_default_team = self.instruction(
+            crate::accounts::InitializeDefaultTeam {
+                authority: authority.pubkey(),
+                game: self.addresses.game,
+                default_team: self.addresses.default_team,
+                default_team_vault: self.token_account(&self.addresses.default_team),
+                token_mint: self.token_mint,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+                event_ring_buffer: self.addresses.event_ring_buffer,
+                event_authority: self.addresses.event_authority,
+                program: crate::ID,
+            },
+            crate::instruction::InitializeDefaultTeam {},
+        );
+        self.process(
+            &[initialize_default_player, initialize_default_team],
+            &[&authority],
+        )
+        .await
+        .expect("the default player and team initialize");
+    }
+
+    /// Creates the next round, starting now, funded with `initial_grand_prizes` out of the round rewards pool.
+    ///
+    /// # Returns
+    /// Returns the round's PDA.
+    pub async fn create_round(
+        &mut self,
+        countdown_duration: u64,
+        initial_grand_prizes: u64,
+    ) -> std::result::Result<Pubkey, BanksClientError> {
+        let game: Game = self.account(self.addresses.game).await;
+        let round = self.addresses.round(game.round_nonce);
+        let bot_authority = self.bot_authority.insecure_clone();
+        let create_round = self.instruction(
+            crate::accounts::CreateRound {
+                bot_authority: bot_authority.pubkey(),
+                game: self.addresses.game,
+                game_vault: self.game_vault(),
+                round,
+                token_mint: self.token_mint,
+                round_vault: self.token_account(&round),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                event_ring_buffer: self.addresses.event_ring_buffer,
+                event_authority: self.addresses.event_authority,
+                program: crate::ID,
+            },
+            crate::instruction::CreateRound {
+                start_time: self.clock.now(),
+                countdown_duration,
+                initial_grand_prize_pool_balance: initial_grand_prizes,
+            },
+        );
+        self.process(&[create_round], &[&bot_authority]).await?;
+        Ok(round)
+    }
+
+    /// Creates the next period under `config`, starting now, funded out of the period rewards pool.
+    ///
+    /// # Returns
+    /// Returns the period's PDA.
+    pub async fn create_period(
+        &mut self,
+        leaderboard_duration: u64,
+        team_rewards: u64,
+        individual_rewards: u64,
+        config: PeriodConfig,
+    ) -> std::result::Result<Pubkey, BanksClientError> {
+        let game: Game = self.account(self.addresses.game).await;
+        let period = self.addresses.period(game.period_nonce);
+        let bot_authority = self.bot_authority.insecure_clone();
+        let create_period = self.instruction(
+            crate::accounts::CreatePeriod {
+                bot_authority: bot_authority.pubkey(),
+                game: self.addresses.game,
+                period,
+                game_vault: self.game_vault(),
+                period_vault: self.token_account(&period),
+                token_mint: self.token_mint,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                event_ring_buffer: self.addresses.event_ring_buffer,
+                event_authority: self.addresses.event_authority,
+                program: crate::ID,
+            },
+            crate::instruction::CreatePeriod {
+                start_time: self.clock.now(),
+                leaderboard_duration,
+                team_rewards,
+                individual_rewards,
+                config,
+            },
+        );
+        self.process(&[create_period], &[&bot_authority]).await?;
+        Ok(period)
+    }
+
+    /// Registers `player`, referred by `referrer`.
+    pub async fn register(
+        &mut self,
+        player: &Keypair,
+        referrer: Pubkey,
+    ) -> std::result::Result<(), BanksClientError> {
+        let register = self.instruction(
+            crate::accounts::Register {
+                player: player.pubkey(),
+                player_data: self.player(&player.pubkey()).player_data,
+                ban_entry: self.player(&player.pubkey()).ban_entry,
+                referrer_data: self.player(&referrer).player_data,
+                game: self.addresses.game,
+                token_mint: self.token_mint,
+                voucher_mint: self.addresses.voucher_mint,
+                token_account: self.token_account(&player.pubkey()),
+                voucher_account: self.voucher_account(&player.pubkey()),
+                voucher: self.addresses.voucher,
+                voucher_vault: self.voucher_vault(),
+                game_vault: self.game_vault(),
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+                instructions_sysvar: None,
+                event_ring_buffer: self.addresses.event_ring_buffer,
+                event_authority: self.addresses.event_authority,
+                program: crate::ID,
+            },
+            crate::instruction::Register { referrer },
+        );
+        self.process(&[register], &[player]).await
+    }
+
+    /// Purchases `purchase_quantity` ORE for `player` in the current round, or with none once the round's
+    /// countdown is over, calls its end.
+    pub async fn purchase(
+        &mut self,
+        player: &Keypair,
+        purchase_quantity: u32,
+    ) -> std::result::Result<(), BanksClientError> {
+        let game: Game = self.account(self.addresses.game).await;
+        let addresses = self.player(&player.pubkey());
+        let player_data: PlayerData = self.account(addresses.player_data).await;
+        let purchase = self.instruction(
+            crate::accounts::Purchase {
+                player: player.pubkey(),
+                player_data: addresses.player_data,
+                ban_entry: addresses.ban_entry,
+                referrer_data: self.player(&player_data.referrer).player_data,
+                game: self.addresses.game,
+                game_config: self.addresses.game_config,
+                current_round: game.current_round,
+                current_period: game.current_period,
+                team: player_data.team,
+                voucher: self.addresses.voucher,
+                game_vault: self.game_vault(),
+                round_vault: self.token_account(&game.current_round),
+                voucher_vault: self.voucher_vault(),
+                token_account: player_data.token_account,
+                voucher_account: player_data.voucher_account,
+                voucher_mint: self.addresses.voucher_mint,
+                token_mint: self.token_mint,
+                token_program: spl_token::ID,
+                voucher_kind: None,
+                voucher_kind_mint: None,
+                voucher_kind_vault: None,
+                voucher_kind_account: None,
+                daily_stats: None,
+                badge_tree_config: None,
+                badge_merkle_tree: None,
+                bubblegum_program: None,
+                log_wrapper: None,
+                compression_program: None,
+                system_program: None,
+                instructions_sysvar: None,
+                event_ring_buffer: self.addresses.event_ring_buffer,
+                event_authority: self.addresses.event_authority,
+                program: crate::ID,
+            },
+            crate::instruction::Purchase { purchase_quantity },
+        );
+        self.process(&[purchase], &[player]).await
+    }
+
+    /// Taps the candy machine of the current round for `player`, collecting the sugar rush rewards.
+    pub async fn candy_tap(
+        &mut self,
+        player: &Keypair,
+    ) -> std::result::Result<(), BanksClientError> {
+        let game: Game = self.account(self.addresses.game).await;
+        let round: Round = self.account(game.current_round).await;
+        let last_active_participant = round.last_active_participant_list[0];
+        let candy_tap = self.instruction(
+            crate::accounts::CandyTap {
+                signer: player.pubkey(),
+                player: player.pubkey(),
+                session_key: None,
+                player_data: self.player(&player.pubkey()).player_data,
+                game: self.addresses.game,
+                game_config: self.addresses.game_config,
+                current_round: game.current_round,
+                last_active_participant_data: self.player(&last_active_participant).player_data,
+                game_vault: self.game_vault(),
+                round_vault: self.token_account(&game.current_round),
+                token_mint: self.token_mint,
+                token_program: spl_token::ID,
+                event_ring_buffer: self.addresses.event_ring_buffer,
+                event_authority: self.addresses.event_authority,
+                program: crate::ID,
+            },
+            crate::instruction::CandyTap {
+                last_active_participant,
+            },
+        );
+        self.process(&[candy_tap], &[player]).await
+    }
+
+    /// Pays the next grand prize of the ended `round` to its winner.
+    ///
+    /// # Returns
+    /// Returns the winner, whose prize the default player's rolls back into the game pools.
+    pub async fn distribute_grand_prizes(
+        &mut self,
+        round: Pubkey,
+    ) -> std::result::Result<Pubkey, BanksClientError> {
+        let round_data: Round = self.account(round).await;
+        let index = round_data.grand_prize_distribution_index;
+        let winner = round_data.last_active_participant_list[index as usize];
+        let bot_authority = self.bot_authority.insecure_clone();
+        let distribute_grand_prizes = self.instruction(
+            crate::accounts::DistributeGrandPrizes {
+                bot_authority: bot_authority.pubkey(),
+                game: self.addresses.game,
+                round,
+                player_data: self.player(&winner).player_data,
+                round_vault: self.token_account(&round),
+                game_vault: self.game_vault(),
+                token_account: self.token_account(&winner),
+                prize_vesting: None,
+                token_mint: self.token_mint,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                event_ring_buffer: self.addresses.event_ring_buffer,
+                event_authority: self.addresses.event_authority,
+                program: crate::ID,
+            },
+            crate::instruction::DistributeGrandPrizes {
+                index,
+                player: winner,
+            },
+        );
+        self.process(&[distribute_grand_prizes], &[&bot_authority])
+            .await?;
+        Ok(winner)
+    }
+
+    /// Claims the individual leaderboard rewards of `rank` in the ended `period` for `player`.
+    pub async fn claim_leaderboard_reward(
+        &mut self,
+        player: &Keypair,
+        period: Pubkey,
+        rank: u8,
+    ) -> std::result::Result<(), BanksClientError> {
+        let period_data: Period = self.zero_copy_account(period).await;
+        let claim_leaderboard_reward = self.instruction(
+            crate::accounts::ClaimLeaderboardReward {
+                player: player.pubkey(),
+                game: self.addresses.game,
+                player_data: self.player(&player.pubkey()).player_data,
+                period,
+                period_vault: period_data.period_vault,
+                token_account: self.token_account(&player.pubkey()),
+                voucher: self.addresses.voucher,
+                voucher_mint: self.addresses.voucher_mint,
+                voucher_vault: self.voucher_vault(),
+                voucher_account: self.voucher_account(&player.pubkey()),
+                token_mint: self.token_mint,
+                token_program: spl_token::ID,
+                event_ring_buffer: self.addresses.event_ring_buffer,
+                event_authority: self.addresses.event_authority,
+                program: crate::ID,
+            },
+            crate::instruction::ClaimLeaderboardReward { rank },
+        );
+        self.process(&[claim_leaderboard_reward], &[player]).await
+    }
+
+    /// Settles `player`'s holdings in their ended round, paying out or carrying over the construction rewards.
+    pub async fn settle_previous_round(
+        &mut self,
+        player: &Keypair,
+        carry_over: bool,
+    ) -> std::result::Result<(), BanksClientError> {
+        let addresses = self.player(&player.pubkey());
+        let player_data: PlayerData = self.account(addresses.player_data).await;
+        let settle_previous_round = self.instruction(
+            crate::accounts::SettlePreviousRound {
+                player: player.pubkey(),
+                player_data: addresses.player_data,
+                token_account: player_data.token_account,
+                game: self.addresses.game,
+                game_vault: self.game_vault(),
+                current_round: player_data.current_round,
+                token_mint: self.token_mint,
+                token_program: spl_token::ID,
+                event_ring_buffer: self.addresses.event_ring_buffer,
+                event_authority: self.addresses.event_authority,
+                program: crate::ID,
+            },
+            crate::instruction::SettlePreviousRound { carry_over },
+        );
+        self.process(&[settle_previous_round], &[player]).await
+    }
+
+    /// Builds an instruction of the program from its accounts and arguments.
+    pub fn instruction(
+        &self,
+        accounts: impl ToAccountMetas,
+        data: impl InstructionData,
+    ) -> Instruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: accounts.to_account_metas(None),
+            data: data.data(),
+        }
+    }
+}
+
+/// Preloads the voucher, its mint and its vault as `initialize_voucher` leaves them.
+fn preload_voucher(program_test: &mut ProgramTest, addresses: &GameAddresses, token_mint: Pubkey) {
+    let voucher = addresses.voucher;
+    let voucher_vault = get_associated_token_address(&voucher, &token_mint);
+
+    let mut state = Voucher::default();
+    state
+        .initialize(addresses.voucher_mint, voucher_vault)
+        .expect("the voucher initializes");
+    let data = account_data(&state);
+    let mut account = Account::new(
+        Rent::default().minimum_balance(data.len()),
+        data.len(),
+        &crate::ID,
+    );
+    account.data = data;
+    program_test.add_account(voucher, account);
+
+    program_test.add_account(
+        addresses.voucher_mint,
+        packed_account(
+            &spl_token::ID,
+            spl_token::state::Mint {
+                mint_authority: COption::Some(voucher),
+                supply: 0,
+                decimals: VOUCHER_DECIMALS,
+                is_initialized: true,
+                freeze_authority: COption::Some(voucher),
+            },
+        ),
+    );
+    program_test.add_account(
+        voucher_vault,
+        packed_account(
+            &spl_token::ID,
+            spl_token::state::Account {
+                mint: token_mint,
+                owner: voucher,
+                amount: 0,
+                delegate: COption::None,
+                state: spl_token::state::AccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: 0,
+                close_authority: COption::None,
+            },
+        ),
+    );
+}
+
+/// Builds a rent-exempt account owned by `owner` holding the packed `state`.
+fn packed_account<T: Pack>(owner: &Pubkey, state: T) -> Account {
+    let mut account = Account::new(Rent::default().minimum_balance(T::LEN), T::LEN, owner);
+    T::pack(state, &mut account.data).expect("the state packs");
+    account
+}
@@ -0,0 +1,264 @@
+//! Scenario test support, enabled with the `test-utils` feature.
+//!
+//! `harness::GameHarness` drives the instruction handlers in a `ProgramTest` context, stepping through countdowns,
+//! periods and cooldowns on a clock the test controls. `MockClock` is that clock: it only moves when told to, and
+//! renders the `Clock` sysvar the harness sets in the context after every step.
+//!
+//! `GameAddresses` derives the global PDAs once, and the per-round, per-period and per-player PDAs on demand,
+//! so that multi-round scenarios do not re-derive them by hand. The fixtures build the `Game`, `Round` and
+//! `Period` states the way `initialize`, `create_round` and `create_period` leave them, for the state-level
+//! unit tests, and `account_data` and `zero_copy_account_data` serialize accounts into the data to preload.
+//!
+//! Lottery draws are made deterministic by preloading the data of `MockRandomness::account_data` as the
+//! randomness account, owned by the program, which `draw_lottery` and `reveal_draw_lottery_result` accept with the `mock-randomness`
+//! feature, enabled by this one.
+
+#[cfg(feature = "test-utils")]
+pub mod harness;
+
+use crate::constants::{DEFAULT_PLAYER, DEFAULT_TEAM_NUMBER};
+use crate::interface::{
+    find_ban_entry_address, find_event_authority_address, find_event_ring_buffer_address,
+    find_game_address, find_game_config_address, find_period_address, find_player_data_address,
+    find_round_address, find_session_key_address, find_stake_order_address,
+    find_stake_order_index_address, find_stake_pool_address, find_team_address,
+    find_voucher_address, find_voucher_mint_address,
+};
+use crate::state::{Game, Period, PeriodConfig, Round};
+use anchor_lang::{prelude::*, Discriminator, ZeroCopy};
+
+/// Slots produced per second by a cluster running 400ms slots, used to advance the slot with the clock.
+pub const MOCK_SLOTS_PER_SECOND: f64 = 2.5;
+
+/// A deterministic clock: time only passes when a scenario advances it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MockClock {
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+impl MockClock {
+    /// Starts the clock at `unix_timestamp`, on slot 0.
+    pub fn new(unix_timestamp: i64) -> Self {
+        MockClock {
+            slot: 0,
+            unix_timestamp,
+        }
+    }
+
+    /// The current UNIX timestamp, as the handlers read it.
+    pub fn now(&self) -> u64 {
+        self.unix_timestamp as u64
+    }
+
+    /// Advances the clock by `seconds`, and the slot with it.
+    pub fn advance(&mut self, seconds: u64) -> &mut Self {
+        self.unix_timestamp += seconds as i64;
+        self.slot += (seconds as f64 * MOCK_SLOTS_PER_SECOND) as u64;
+        self
+    }
+
+    /// Advances the clock to `unix_timestamp`, which must not be in its past.
+    pub fn advance_to(&mut self, unix_timestamp: u64) -> &mut Self {
+        assert!(
+            unix_timestamp >= self.now(),
+            "the mock clock cannot move back"
+        );
+        self.advance(unix_timestamp - self.now())
+    }
+
+    /// Renders the `Clock` sysvar at the current time.
+    pub fn sysvar(&self) -> Clock {
+        Clock {
+            slot: self.slot,
+            epoch_start_timestamp: self.unix_timestamp,
+            epoch: 0,
+            leader_schedule_epoch: 0,
+            unix_timestamp: self.unix_timestamp,
+        }
+    }
+}
+
+/// The PDAs of the global game accounts, derived once per scenario.
+#[derive(Clone, Copy, Debug)]
+pub struct GameAddresses {
+    pub game: Pubkey,
+    pub game_config: Pubkey,
+    pub event_ring_buffer: Pubkey,
+    pub event_authority: Pubkey,
+    pub voucher: Pubkey,
+    pub voucher_mint: Pubkey,
+    pub stake_pool: Pubkey,
+    pub default_team: Pubkey,
+}
+
+/// The PDAs of a player's accounts.
+#[derive(Clone, Copy, Debug)]
+pub struct PlayerAddresses {
+    pub player: Pubkey,
+    pub player_data: Pubkey,
+    pub session_key: Pubkey,
+    pub stake_order_index: Pubkey,
+    pub ban_entry: Pubkey,
+}
+
+impl PlayerAddresses {
+    /// The PDA of the player's stake order `order_number`.
+    pub fn stake_order(&self, order_number: u16) -> Pubkey {
+        find_stake_order_address(&self.player, order_number).0
+    }
+}
+
+impl GameAddresses {
+    /// Derives the PDAs of the global game accounts.
+    pub fn derive() -> Self {
+        GameAddresses {
+            game: find_game_address().0,
+            game_config: find_game_config_address().0,
+            event_ring_buffer: find_event_ring_buffer_address().0,
+            event_authority: find_event_authority_address().0,
+            voucher: find_voucher_address().0,
+            voucher_mint: find_voucher_mint_address().0,
+            stake_pool: find_stake_pool_address().0,
+            default_team: find_team_address(DEFAULT_TEAM_NUMBER).0,
+        }
+    }
+
+    /// The PDA of round `round_number`.
+    pub fn round(&self, round_number: u16) -> Pubkey {
+        find_round_address(round_number).0
+    }
+
+    /// The PDA of period `period_number`.
+    pub fn period(&self, period_number: u16) -> Pubkey {
+        find_period_address(period_number).0
+    }
+
+    /// The PDA of team `team_number`.
+    pub fn team(&self, team_number: u32) -> Pubkey {
+        find_team_address(team_number).0
+    }
+
+    /// The PDAs of `player`'s accounts.
+    pub fn player(&self, player: &Pubkey) -> PlayerAddresses {
+        PlayerAddresses {
+            player: *player,
+            player_data: find_player_data_address(player).0,
+            session_key: find_session_key_address(player).0,
+            stake_order_index: find_stake_order_index_address(player).0,
+            ban_entry: find_ban_entry_address(player).0,
+        }
+    }
+}
+
+/// Builds the `Game` as `initialize` and the default player and team setup leave it, every reward pool
+/// holding `pool_balance`.
+pub fn game_fixture(
+    authority: Pubkey,
+    token_mint: Pubkey,
+    game_vault: Pubkey,
+    pool_balance: u64,
+) -> Result<Game> {
+    let mut game = Game::default();
+    game.initialize(
+        authority,
+        authority,
+        token_mint,
+        game_vault,
+        pool_balance,
+        pool_balance,
+        pool_balance,
+        pool_balance,
+        pool_balance,
+        pool_balance,
+        pool_balance,
+        pool_balance,
+    )?;
+    game.default_player = DEFAULT_PLAYER;
+    game.default_team = GameAddresses::derive().default_team;
+    Ok(game)
+}
+
+/// Builds the next round of `game` as `create_round` leaves it, starting now and funded with `grand_prizes`
+/// out of the round rewards pool, and makes it the game's current round.
+///
+/// # Returns
+/// Returns the round with its PDA.
+pub fn round_fixture(
+    game: &mut Game,
+    clock: &MockClock,
+    round_vault: Pubkey,
+    grand_prizes: u64,
+    countdown_duration: u64,
+) -> Result<(Pubkey, Round)> {
+    let (address, bump) = find_round_address(game.round_nonce);
+    let mut round = Round::default();
+    round.initialize(
+        game.round_nonce,
+        round_vault,
+        grand_prizes,
+        clock.now(),
+        countdown_duration,
+        game.exit_rewards_per_second,
+        game.default_player,
+        bump,
+    )?;
+
+    game.current_round = address;
+    game.round_rewards_pool_balance = game.round_rewards_pool_balance.saturating_sub(grand_prizes);
+    game.increment_round_nonce()?;
+    Ok((address, round))
+}
+
+/// Builds the next period of `game` as `create_period` leaves it under `config`, starting now and funded
+/// out of the period rewards pool, and makes it the game's current period.
+///
+/// # Returns
+/// Returns the period with its PDA.
+pub fn period_fixture(
+    game: &mut Game,
+    clock: &MockClock,
+    period_vault: Pubkey,
+    leaderboard_duration: u64,
+    team_rewards: u64,
+    individual_rewards: u64,
+    config: &PeriodConfig,
+) -> Result<(Pubkey, Period)> {
+    let (address, bump) = find_period_address(game.period_nonce);
+    let mut period: Period = bytemuck::Zeroable::zeroed();
+    period.initialize(
+        game.period_nonce,
+        period_vault,
+        clock.now(),
+        leaderboard_duration,
+        team_rewards,
+        individual_rewards,
+        game.default_player,
+        game.default_team,
+        bump,
+    )?;
+    period.configure(config)?;
+
+    game.current_period = address;
+    game.period_rewards_pool_balance = game
+        .period_rewards_pool_balance
+        .saturating_sub(team_rewards.saturating_add(individual_rewards));
+    game.increment_period_nonce()?;
+    Ok((address, period))
+}
+
+/// Serializes a Borsh account, discriminator first, into the data to preload at its address.
+pub fn account_data<T: AccountSerialize>(account: &T) -> Vec<u8> {
+    let mut data = Vec::new();
+    account
+        .try_serialize(&mut data)
+        .expect("the account serializes");
+    data
+}
+
+/// Serializes a zero-copy account, discriminator first, into the data to preload at its address.
+pub fn zero_copy_account_data<T: ZeroCopy + Discriminator>(account: &T) -> Vec<u8> {
+    let mut data = T::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(account));
+    data
+}
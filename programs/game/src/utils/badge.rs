@@ -0,0 +1,106 @@
+use crate::constants::{ROUND_BADGE_METADATA_URI, ROUND_BADGE_SYMBOL};
+use crate::state::Game;
+use crate::utils::VaultAuthority;
+use anchor_lang::prelude::*;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::invoke_signed;
+
+/// The Anchor discriminator of Bubblegum's `mint_v1` instruction.
+const BUBBLEGUM_MINT_V1_DISCRIMINATOR: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+
+/// The metadata of a compressed NFT, serialized as Bubblegum's `MetadataArgs`.
+#[derive(AnchorSerialize)]
+struct BubblegumMetadataArgs {
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    primary_sale_happened: bool,
+    is_mutable: bool,
+    edition_nonce: Option<u8>,
+    /// `Some(0)` is `TokenStandard::NonFungible`.
+    token_standard: Option<u8>,
+    collection: Option<(bool, Pubkey)>,
+    uses: Option<(u8, u64, u64)>,
+    /// `0` is `TokenProgramVersion::Original`.
+    token_program_version: u8,
+    creators: Vec<(Pubkey, bool, u8)>,
+}
+
+/// The accounts of a Bubblegum `mint_v1` minting a round badge.
+pub struct RoundBadgeAccounts<'a, 'info> {
+    pub tree_config: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub bubblegum_program: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+/// Mints a compressed NFT badge to `player` recording their entry into round `round_number` on day `entry_day`.
+///
+/// The badge tree's delegate must be the `Game` account, which signs the mint; the player pays for it.
+pub fn mint_round_badge<'info>(
+    accounts: &RoundBadgeAccounts<'_, 'info>,
+    game: &Account<'info, Game>,
+    game_bump: u8,
+    player: &AccountInfo<'info>,
+    round_number: u16,
+    entry_day: u32,
+) -> Result<()> {
+    let metadata = BubblegumMetadataArgs {
+        name: format!("Ark Round {} Day {}", round_number, entry_day),
+        symbol: ROUND_BADGE_SYMBOL.to_string(),
+        uri: format!(
+            "{}?round={}&day={}",
+            ROUND_BADGE_METADATA_URI, round_number, entry_day
+        ),
+        seller_fee_basis_points: 0,
+        primary_sale_happened: true,
+        is_mutable: false,
+        edition_nonce: None,
+        token_standard: Some(0),
+        collection: None,
+        uses: None,
+        token_program_version: 0,
+        creators: vec![],
+    };
+
+    let mut data = BUBBLEGUM_MINT_V1_DISCRIMINATOR.to_vec();
+    metadata.serialize(&mut data)?;
+
+    let instruction = Instruction {
+        program_id: accounts.bubblegum_program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.tree_config.key(), false),
+            AccountMeta::new_readonly(player.key(), false),
+            AccountMeta::new_readonly(player.key(), false),
+            AccountMeta::new(accounts.merkle_tree.key(), false),
+            AccountMeta::new(player.key(), true),
+            AccountMeta::new_readonly(game.key(), true),
+            AccountMeta::new_readonly(accounts.log_wrapper.key(), false),
+            AccountMeta::new_readonly(accounts.compression_program.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data,
+    };
+
+    game.with_signer_seeds(game_bump, |seeds| {
+        invoke_signed(
+            &instruction,
+            &[
+                accounts.tree_config.clone(),
+                player.clone(),
+                accounts.merkle_tree.clone(),
+                game.to_account_info(),
+                accounts.log_wrapper.clone(),
+                accounts.compression_program.clone(),
+                accounts.system_program.clone(),
+                accounts.bubblegum_program.clone(),
+            ],
+            &[seeds],
+        )
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,1388 @@
+use crate::constants::{
+    CONSTRUCTION_POOL_SHARE, GRAND_PRIZES_POOL_SHARE, INSURANCE_POOL_SHARE, LOTTERY_POOL_SHARE,
+    REFERRAL_POOL_SHARE,
+};
+use crate::errors::ErrorCode;
+use crate::state::{
+    ConsumptionRewardTier, Game, GameConfig, Period, PlayerData, Round, RoundError, RoundPhase,
+    ShortfallPool, Team,
+};
+use crate::utils::{calculate_boost, calculate_earnings_per_ore_increment, calculate_proportion};
+use anchor_lang::prelude::*;
+use anchor_safe_math::SafeMath;
+
+// The default shares taken from a payment add up to 100%, so the grand prize remainder never underflows
+const _: () = assert!(
+    2 * CONSTRUCTION_POOL_SHARE as u16
+        + LOTTERY_POOL_SHARE as u16
+        + REFERRAL_POOL_SHARE as u16
+        + INSURANCE_POOL_SHARE as u16
+        + GRAND_PRIZES_POOL_SHARE as u16
+        == 100
+);
+
+/// A payment entering the game economy, to be split across the reward pools.
+///
+/// # Fields
+/// - `total_cost`: The full amount paid, from which the construction, bonus, lottery, referral, insurance and grand prize shares are taken.
+/// - `consumption_base`: The part of the cost paid in tokens, from which the consumption and developer shares are taken.
+/// - `has_referrer`: Whether the referral share is credited to a referrer; otherwise it is left to be burned.
+/// - `purchase_volume`: The payer's purchase volume over the last 30 days before this payment, selecting their consumption reward tier.
+/// - `timestamp`: A UNIX timestamp representing the current time, selecting the phase of the round.
+pub struct DistributionInput {
+    pub total_cost: u64,
+    pub consumption_base: u64,
+    pub has_referrer: bool,
+    pub purchase_volume: u64,
+    pub timestamp: u64,
+}
+
+/// The outcome of distributing a payment across the reward pools.
+///
+/// # Fields
+/// - `construction_rewards`: The construction share.
+/// - `bonus_rewards`: The bonus share, equal to the construction share.
+/// - `lottery_rewards`: The lottery share.
+/// - `referral_rewards`: The referral share.
+/// - `insurance_rewards`: The insurance share, set aside to cover payout shortfalls.
+/// - `grand_prizes_rewards`: The grand prize share, the remainder of the cost once the other shares are taken.
+/// - `dust`: The part of `grand_prizes_rewards` above the configured grand prize share, left by rounding the other shares down.
+/// - `developer_rewards`: The developer share moved out of the consumption pool, `0` if the pool could not cover it.
+/// - `consumption_rewards`: The consumption rewards earned by the payer, `0` if the pool could not cover them.
+/// - `phase`: The phase of the round the payment was distributed in. In `RoundPhase::Bootstrapping` the
+///   construction and bonus shares went to the round's grand prize pool, as the round had no ORE to earn them.
+/// - `referral_credited`: Whether the referral share was credited to the referrer.
+#[derive(Debug, Default, PartialEq)]
+pub struct Distribution {
+    pub construction_rewards: u64,
+    pub bonus_rewards: u64,
+    pub lottery_rewards: u64,
+    pub referral_rewards: u64,
+    pub insurance_rewards: u64,
+    pub grand_prizes_rewards: u64,
+    pub dust: u64,
+    pub developer_rewards: u64,
+    pub consumption_rewards: u64,
+    pub phase: RoundPhase,
+    pub referral_credited: bool,
+}
+
+impl Distribution {
+    /// The tokens owed to the round vault: the grand prize share, plus the construction and bonus shares
+    /// when they went to the grand prize pool.
+    pub fn round_vault_amount(&self) -> Result<u64> {
+        if !self.phase.feeds_ore_holders() {
+            Ok(self
+                .grand_prizes_rewards
+                .safe_add(self.construction_rewards)?
+                .safe_add(self.bonus_rewards)?)
+        } else {
+            Ok(self.grand_prizes_rewards)
+        }
+    }
+
+    /// The tokens owed to the game vault: the lottery, referral and insurance shares, plus the construction and
+    /// bonus shares unless they went to the grand prize pool.
+    pub fn game_vault_amount(&self) -> Result<u64> {
+        let amount = self
+            .lottery_rewards
+            .safe_add(self.referral_rewards)?
+            .safe_add(self.insurance_rewards)?;
+        if !self.phase.feeds_ore_holders() {
+            Ok(amount)
+        } else {
+            Ok(amount
+                .safe_add(self.construction_rewards)?
+                .safe_add(self.bonus_rewards)?)
+        }
+    }
+
+    /// The sum of the construction, bonus, lottery, referral, insurance and grand prize shares, always equal to the cost.
+    pub fn total_allocated(&self) -> Result<u64> {
+        Ok(self
+            .construction_rewards
+            .safe_add(self.bonus_rewards)?
+            .safe_add(self.lottery_rewards)?
+            .safe_add(self.referral_rewards)?
+            .safe_add(self.insurance_rewards)?
+            .safe_add(self.grand_prizes_rewards)?)
+    }
+
+    /// The referral share to burn from the game vault when no referrer was credited.
+    pub fn burned_referral_rewards(&self) -> u64 {
+        if self.referral_credited {
+            0
+        } else {
+            self.referral_rewards
+        }
+    }
+}
+
+/// A purchase of ORE to be recorded on the round, the player, their team and the period leaderboards.
+///
+/// # Fields
+/// - `player`: The public key of the player.
+/// - `round`: The public key of the current round.
+/// - `period`: The public key of the current period.
+/// - `team`: The public key of the player's team.
+/// - `default_team`: The game's default team, which never enters the team leaderboard.
+/// - `purchased_ores`: The ORE purchased.
+/// - `total_cost`: The tokens and vouchers paid for the ORE, added to the player's purchase volume.
+/// - `timestamp`: A UNIX timestamp representing the current time.
+pub struct PurchaseRecord {
+    pub player: Pubkey,
+    pub round: Pubkey,
+    pub period: Pubkey,
+    pub team: Pubkey,
+    pub default_team: Pubkey,
+    pub purchased_ores: u32,
+    pub total_cost: u64,
+    pub timestamp: u64,
+}
+
+/// Splits a payment across the game and round reward pools.
+///
+/// The construction and bonus shares follow the `RoundPhase` of the round: they feed the game pools and
+/// raise the round's `earnings_per_ore` while ORE is held, and go to the round's grand prize pool
+/// during `RoundPhase::Bootstrapping`. The referral share is credited to
+/// `referrer_data` when `has_referrer` is set. The developer and consumption shares are only paid while
+/// the consumption pool can cover them; the caller credits the returned `consumption_rewards` to the payer.
+/// The consumption rewards rate is the one of the payer's `ConsumptionRewardTier`, see `resolve_consumption_rate`.
+///
+/// # Arguments
+/// - `input`: The payment to distribute.
+/// - `config`: The game config holding the pool shares.
+/// - `game`: The game holding the reward pools.
+/// - `round`: The current round.
+/// - `referrer_data`: The referrer credited with the referral share.
+pub fn distribute(
+    input: &DistributionInput,
+    config: &GameConfig,
+    game: &mut Game,
+    round: &mut Round,
+    referrer_data: &mut PlayerData,
+) -> Result<Distribution> {
+    let total_cost = input.total_cost;
+    let construction_rewards = calculate_proportion(total_cost, config.construction_pool_share)?;
+    let lottery_rewards = calculate_proportion(total_cost, config.lottery_pool_share)?;
+    let referral_rewards = calculate_proportion(total_cost, config.referral_pool_share)?;
+    let insurance_rewards = calculate_proportion(total_cost, config.insurance_pool_share)?;
+
+    // The grand prize share takes the remainder, sinking the rounding dust of the other shares
+    let grand_prizes_rewards = total_cost
+        .safe_sub(construction_rewards)?
+        .safe_sub(construction_rewards)?
+        .safe_sub(lottery_rewards)?
+        .safe_sub(referral_rewards)?
+        .safe_sub(insurance_rewards)?;
+    let dust = grand_prizes_rewards.safe_sub(calculate_proportion(
+        total_cost,
+        config.grand_prizes_pool_share(),
+    )?)?;
+
+    let mut distribution = Distribution {
+        construction_rewards,
+        bonus_rewards: construction_rewards,
+        lottery_rewards,
+        referral_rewards,
+        insurance_rewards,
+        grand_prizes_rewards,
+        dust,
+        phase: round.phase(input.timestamp),
+        referral_credited: input.has_referrer,
+        ..Default::default()
+    };
+
+    // Construction and bonus rewards are earned by the ORE already held, if any
+    if !distribution.phase.feeds_ore_holders() {
+        round.grand_prize_pool_balance = round
+            .grand_prize_pool_balance
+            .safe_add(distribution.construction_rewards)?
+            .safe_add(distribution.bonus_rewards)?;
+    } else {
+        game.construction_rewards_pool_balance = game
+            .construction_rewards_pool_balance
+            .safe_add(distribution.construction_rewards)?;
+        game.bonus_rewards_pool_balance = game
+            .bonus_rewards_pool_balance
+            .safe_add(distribution.bonus_rewards)?;
+
+        let earnings_per_ore_increment = calculate_earnings_per_ore_increment(
+            distribution.construction_rewards,
+            round.available_ores,
+        )?;
+        round.earnings_per_ore = round
+            .earnings_per_ore
+            .safe_add(earnings_per_ore_increment)?;
+    }
+
+    game.lottery_rewards_pool_balance = game
+        .lottery_rewards_pool_balance
+        .safe_add(distribution.lottery_rewards)?;
+    game.insurance_pool_balance = game
+        .insurance_pool_balance
+        .safe_add(distribution.insurance_rewards)?;
+
+    if distribution.referral_credited {
+        game.referral_rewards_pool_balance = game
+            .referral_rewards_pool_balance
+            .safe_add(distribution.referral_rewards)?;
+        referrer_data
+            .add_collectable_referral_rewards(distribution.referral_rewards, input.timestamp)?;
+    }
+
+    round.grand_prize_pool_balance = round
+        .grand_prize_pool_balance
+        .safe_add(distribution.grand_prizes_rewards)?;
+
+    // If the consumption pool, and its part still distributable, are large enough, move the developer share out of it
+    let developer_rewards =
+        calculate_proportion(input.consumption_base, config.consumption_pool_share)?;
+    if game.consumption_rewards_pool_balance >= developer_rewards
+        && game.distributable_consumption_rewards >= developer_rewards
+    {
+        game.consumption_rewards_pool_balance = game
+            .consumption_rewards_pool_balance
+            .safe_sub(developer_rewards)?;
+        game.distributable_consumption_rewards = game
+            .distributable_consumption_rewards
+            .safe_sub(developer_rewards)?;
+        game.developer_rewards_pool_balance = game
+            .developer_rewards_pool_balance
+            .safe_add(developer_rewards)?;
+        distribution.developer_rewards = developer_rewards;
+    }
+
+    // Reward the tokens spent with consumption rewards, at the payer's tier rate, while they last
+    let consumption_rate = resolve_consumption_rate(
+        &game.consumption_reward_tiers,
+        input.purchase_volume,
+        config.consumption_pool_share,
+    );
+    let consumption_rewards = calculate_proportion(input.consumption_base, consumption_rate)?;
+    if game.distributable_consumption_rewards >= consumption_rewards {
+        game.distributable_consumption_rewards = game
+            .distributable_consumption_rewards
+            .safe_sub(consumption_rewards)?;
+        distribution.consumption_rewards = consumption_rewards;
+    }
+
+    Ok(distribution)
+}
+
+/// Resolves the consumption rewards rate of a payer: the rate of the highest used tier whose minimum
+/// purchase volume `purchase_volume` reaches, or `default_rate` below every tier.
+///
+/// # Arguments
+/// - `tiers`: The consumption reward tiers, by ascending minimum purchase volume.
+/// - `purchase_volume`: The payer's purchase volume over the last 30 days.
+/// - `default_rate`: The rate applying below every tier, the configured consumption pool share.
+pub fn resolve_consumption_rate(
+    tiers: &[ConsumptionRewardTier],
+    purchase_volume: u64,
+    default_rate: u8,
+) -> u8 {
+    tiers
+        .iter()
+        .rfind(|tier| tier.is_used() && tier.min_purchase_volume <= purchase_volume)
+        .map_or(default_rate, |tier| tier.consumption_rate)
+}
+
+/// Records purchased ORE on the round, the player, their team and the period leaderboards.
+/// Must be called after `distribute`, so the player's pending construction rewards are settled
+/// at the updated `earnings_per_ore` before their holdings grow. The construction rewards settled
+/// are boosted by the player's stake, see `boost_construction_rewards`.
+///
+/// # Arguments
+/// - `record`: The purchase to record.
+/// - `game`: The global game account, paying the boost from its bonus pool.
+/// - `round`: The current round.
+/// - `period`: The current period.
+/// - `player_data`: The purchasing player's data.
+/// - `team`: The player's team, or the default team.
+///
+/// # Returns
+/// Returns the construction rewards settled by the purchase and the boost added to them.
+pub fn record_purchased_ores(
+    record: &PurchaseRecord,
+    game: &mut Game,
+    round: &mut Round,
+    period: &mut Period,
+    player_data: &mut PlayerData,
+    team: &mut Team,
+) -> Result<(u64, u64)> {
+    let purchased_ores = record.purchased_ores;
+
+    // Update the player to reflect they are now in the current round and period
+    player_data.current_round = record.round;
+    player_data.update_current_period(record.period);
+
+    // Update consecutive purchase days if needed, a streak shield covering one missed day
+    player_data.record_purchased_day(record.timestamp)?;
+    player_data.record_purchase_volume(record.total_cost, record.timestamp)?;
+
+    // Accrue exit rewards to the ORE held before the round's holdings grow
+    round.accrue_exit_rewards(record.timestamp)?;
+
+    // Update round state: sold ORE, participant list, end time
+    round.available_ores = round.available_ores.safe_add(purchased_ores)?;
+    round.sold_ores = round.sold_ores.safe_add(purchased_ores)?;
+    round.update_last_active_participant_list(record.player)?;
+    round.update_end_time(record.timestamp)?;
+
+    // Settle any pending construction rewards, boosted by the player's stake, before adding newly purchased ORE
+    let construction_rewards =
+        player_data.settle_collectable_construction_rewards(round.earnings_per_ore)?;
+    let boost =
+        boost_construction_rewards(game, player_data, construction_rewards, record.timestamp)?;
+    player_data.settle_collectable_exit_rewards(round.exit_rewards_per_ore)?;
+    player_data.available_ores = player_data.available_ores.safe_add(purchased_ores)?;
+    player_data.purchased_ores = player_data.purchased_ores.safe_add(purchased_ores)?;
+
+    team.update_current_period(record.period);
+    team.purchased_ores = team.purchased_ores.safe_add(purchased_ores)?;
+    team.last_updated_timestamp = record.timestamp;
+
+    // If the current period is ongoing, update leaderboards
+    if period.is_ongoing(record.timestamp) {
+        player_data.current_period_purchased_ores = player_data
+            .current_period_purchased_ores
+            .safe_add(purchased_ores)?;
+        period.update_top_player(record.player, player_data.current_period_purchased_ores)?;
+
+        team.current_period_purchased_ores = team
+            .current_period_purchased_ores
+            .safe_add(purchased_ores)?;
+        team.record_member_ores(
+            record.player,
+            purchased_ores,
+            player_data.current_period_purchased_ores,
+        )?;
+        if record.team != record.default_team && period.is_team_qualified(team) {
+            let team_score = team.period_score(period.team_scoring_mode())?;
+            period.update_top_team_list(record.team, team_score)?;
+        }
+    }
+
+    Ok((construction_rewards, boost))
+}
+
+/// Boosts the construction rewards settled by a purchase by the player's stake boost, refreshed at `timestamp`.
+/// The boost is paid from the bonus pool, capped at its balance, into the construction pool and
+/// the player's collectable construction rewards.
+///
+/// # Arguments
+/// - `game`: The global game account, holding the bonus and construction pools.
+/// - `player_data`: The purchasing player's data, holding their vote-escrowed stake.
+/// - `construction_rewards`: The construction rewards settled by the purchase.
+/// - `timestamp`: The UNIX timestamp of the purchase.
+///
+/// # Returns
+/// Returns the boost added to the player's construction rewards, `0` if none.
+pub fn boost_construction_rewards(
+    game: &mut Game,
+    player_data: &mut PlayerData,
+    construction_rewards: u64,
+    timestamp: u64,
+) -> Result<u64> {
+    let boost_factor_bps = player_data.refresh_stake_boost(timestamp)?;
+    let boost = calculate_boost(construction_rewards, boost_factor_bps)?
+        .min(game.bonus_rewards_pool_balance);
+
+    game.bonus_rewards_pool_balance = game.bonus_rewards_pool_balance.safe_sub(boost)?;
+    game.distributed_bonus_rewards = game.distributed_bonus_rewards.safe_add(boost)?;
+    game.construction_rewards_pool_balance =
+        game.construction_rewards_pool_balance.safe_add(boost)?;
+    player_data.collectable_construction_rewards = player_data
+        .collectable_construction_rewards
+        .safe_add(boost)?;
+
+    Ok(boost)
+}
+
+/// Settles a player's holdings in a round that is over, as `settle_previous_round` does.
+/// Pending construction rewards are settled at the round's final `earnings_per_ore` and moved from the
+/// construction pool to the player's collected rewards, the player's ORE leaves the round, and the player exits it.
+/// The caller transfers the returned construction rewards from the game vault to the player.
+///
+/// # Arguments
+/// - `game`: The global game account, holding the construction pool.
+/// - `round`: The round being settled.
+/// - `player_data`: The settling player's data.
+///
+/// # Returns
+/// Returns the ORE the player held in the round and the construction rewards due to them.
+pub fn settle_round(
+    game: &mut Game,
+    round: &mut Round,
+    player_data: &mut PlayerData,
+) -> Result<(u32, u64)> {
+    // Settle any pending construction rewards based on the round's final earnings_per_ore
+    player_data.settle_collectable_construction_rewards(round.earnings_per_ore)?;
+
+    let construction_rewards = player_data.collectable_construction_rewards;
+    player_data.collectable_construction_rewards = 0;
+    let available_ores = player_data.available_ores;
+
+    // Deduct construction rewards from the game's construction pool and update distributed metrics
+    game.construction_rewards_pool_balance = game
+        .construction_rewards_pool_balance
+        .safe_sub(construction_rewards)?;
+    game.distributed_construction_rewards = game
+        .distributed_construction_rewards
+        .safe_add(construction_rewards)?;
+
+    // Ensure the round has enough ORE to cover the player's holdings and reduce it accordingly
+    require!(
+        round.available_ores >= available_ores,
+        RoundError::InsufficientOres
+    );
+    round.available_ores = round.available_ores.safe_sub(available_ores)?;
+
+    // Update the player's collected construction rewards and mark them as exited from the round
+    player_data.collected_construction_rewards = player_data
+        .collected_construction_rewards
+        .safe_add(construction_rewards)?;
+    player_data.exit_round()?;
+
+    Ok((available_ores, construction_rewards))
+}
+
+/// Carries construction rewards settled by `settle_round` over to the player's next round instead of paying them out.
+/// The rewards stay in the game vault, held by the carryover pool until the player's next purchase releases them.
+///
+/// # Arguments
+/// - `game`: The global game account, holding the carryover pool.
+/// - `player_data`: The settling player's data.
+/// - `construction_rewards`: The construction rewards returned by `settle_round`.
+pub fn carry_over_construction_rewards(
+    game: &mut Game,
+    player_data: &mut PlayerData,
+    construction_rewards: u64,
+) -> Result<()> {
+    game.carryover_pool_balance = game.carryover_pool_balance.safe_add(construction_rewards)?;
+    player_data.carryover_balance = player_data
+        .carryover_balance
+        .safe_add(construction_rewards)?;
+    Ok(())
+}
+
+/// Releases the player's carried over construction rewards at their first purchase of the next round.
+/// The caller transfers the returned amount from the game vault to the player's token account, where it pays
+/// for the purchase before any other funds of the player.
+///
+/// # Arguments
+/// - `game`: The global game account, holding the carryover pool.
+/// - `player_data`: The purchasing player's data.
+///
+/// # Returns
+/// Returns the carried over construction rewards released, `0` if there were none.
+pub fn release_carryover(game: &mut Game, player_data: &mut PlayerData) -> Result<u64> {
+    let carryover = player_data.carryover_balance;
+    game.carryover_pool_balance = game.carryover_pool_balance.safe_sub(carryover)?;
+    player_data.carryover_balance = 0;
+    Ok(carryover)
+}
+
+/// Covers a payout shortfall from the insurance pool, moving `amount` into the pool selected by `pool`.
+/// For `ShortfallPool::GrandPrize` the round's grand prize pool is credited, and the caller transfers the
+/// tokens from the game vault to the round vault; the other pools are held by the game vault already.
+///
+/// # Arguments
+/// - `game`: The game holding the insurance pool.
+/// - `round`: The round whose grand prize pool is covered.
+/// - `pool`: The pool short of funds.
+/// - `amount`: The shortfall to cover.
+pub fn cover_shortfall(
+    game: &mut Game,
+    round: &mut Round,
+    pool: ShortfallPool,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(
+        game.insurance_pool_balance >= amount,
+        ErrorCode::InsufficientInsuranceBalance
+    );
+    game.insurance_pool_balance = game.insurance_pool_balance.safe_sub(amount)?;
+
+    match pool {
+        ShortfallPool::GrandPrize => {
+            round.grand_prize_pool_balance = round.grand_prize_pool_balance.safe_add(amount)?;
+        }
+        ShortfallPool::Construction => {
+            game.construction_rewards_pool_balance =
+                game.construction_rewards_pool_balance.safe_add(amount)?;
+        }
+        ShortfallPool::Exit => {
+            game.exit_rewards_pool_balance = game.exit_rewards_pool_balance.safe_add(amount)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{
+        EARNINGS_PER_ORE_PRECISION, LOCK_DURATION, MAX_STAKE_BOOST_BPS,
+        REFERRAL_REWARDS_EXPIRY_SECONDS, ROUND_CLIMAX_SECONDS, SECONDS_PER_DAY,
+        STAKE_BOOST_FULL_BALANCE,
+    };
+    use crate::state::{GameConfigParams, StakeOrder};
+
+    const DAY: u64 = SECONDS_PER_DAY;
+
+    fn game_config() -> GameConfig {
+        let mut config = GameConfig::default();
+        config.initialize().unwrap();
+        config
+    }
+
+    fn funded_game() -> Game {
+        Game {
+            consumption_rewards_pool_balance: 10_000,
+            distributable_consumption_rewards: 10_000,
+            ..Default::default()
+        }
+    }
+
+    fn period(default_player: Pubkey, default_team: Pubkey) -> Period {
+        let mut period: Period = bytemuck::Zeroable::zeroed();
+        period
+            .initialize(
+                0,
+                Pubkey::new_unique(),
+                10 * DAY,
+                DAY,
+                1_000,
+                1_000,
+                default_player,
+                default_team,
+                255,
+            )
+            .unwrap();
+        period
+    }
+
+    fn record(team: Pubkey, default_team: Pubkey, timestamp: u64) -> PurchaseRecord {
+        PurchaseRecord {
+            player: Pubkey::new_unique(),
+            round: Pubkey::new_unique(),
+            period: Pubkey::new_unique(),
+            team,
+            default_team,
+            purchased_ores: 5,
+            total_cost: 500,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_distribute_with_held_ores_and_referrer() {
+        let mut game = funded_game();
+        let mut round = Round {
+            available_ores: 10,
+            end_time: DAY + ROUND_CLIMAX_SECONDS,
+            ..Default::default()
+        };
+        let mut referrer_data = PlayerData::default();
+        let input = DistributionInput {
+            total_cost: 1_000,
+            consumption_base: 1_000,
+            has_referrer: true,
+            purchase_volume: 0,
+            timestamp: 0,
+        };
+
+        let distribution = distribute(
+            &input,
+            &game_config(),
+            &mut game,
+            &mut round,
+            &mut referrer_data,
+        )
+        .unwrap();
+
+        assert_eq!(distribution.phase, RoundPhase::Active);
+        assert_eq!(game.construction_rewards_pool_balance, 250);
+        assert_eq!(game.bonus_rewards_pool_balance, 250);
+        assert_eq!(game.lottery_rewards_pool_balance, 100);
+        assert_eq!(game.referral_rewards_pool_balance, 100);
+        assert_eq!(referrer_data.collectable_referral_rewards, 100);
+        assert_eq!(round.earnings_per_ore, 25 * EARNINGS_PER_ORE_PRECISION);
+        assert_eq!(game.insurance_pool_balance, 10);
+        assert_eq!(round.grand_prize_pool_balance, 290);
+        assert_eq!(distribution.developer_rewards, 100);
+        assert_eq!(distribution.consumption_rewards, 100);
+        assert_eq!(game.consumption_rewards_pool_balance, 9_900);
+        assert_eq!(game.distributable_consumption_rewards, 9_800);
+        assert_eq!(game.developer_rewards_pool_balance, 100);
+        assert_eq!(distribution.burned_referral_rewards(), 0);
+        assert_eq!(distribution.round_vault_amount().unwrap(), 290);
+        assert_eq!(distribution.game_vault_amount().unwrap(), 710);
+    }
+
+    #[test]
+    fn test_distribute_without_held_ores_or_referrer() {
+        let mut game = funded_game();
+        let mut round = Round::default();
+        let mut referrer_data = PlayerData::default();
+        let input = DistributionInput {
+            total_cost: 1_000,
+            consumption_base: 400,
+            has_referrer: false,
+            purchase_volume: 0,
+            timestamp: 0,
+        };
+
+        let distribution = distribute(
+            &input,
+            &game_config(),
+            &mut game,
+            &mut round,
+            &mut referrer_data,
+        )
+        .unwrap();
+
+        assert_eq!(distribution.phase, RoundPhase::Bootstrapping);
+        assert_eq!(game.construction_rewards_pool_balance, 0);
+        assert_eq!(game.bonus_rewards_pool_balance, 0);
+        assert_eq!(round.earnings_per_ore, 0);
+        assert_eq!(round.grand_prize_pool_balance, 790);
+        assert_eq!(game.referral_rewards_pool_balance, 0);
+        assert_eq!(referrer_data.collectable_referral_rewards, 0);
+        assert_eq!(distribution.burned_referral_rewards(), 100);
+        assert_eq!(distribution.consumption_rewards, 40);
+        assert_eq!(distribution.round_vault_amount().unwrap(), 790);
+        assert_eq!(distribution.game_vault_amount().unwrap(), 210);
+    }
+
+    #[test]
+    fn test_distribute_credits_expiring_referral_rewards() {
+        let mut game = funded_game();
+        let mut round = Round::default();
+        let mut referrer_data = PlayerData::default();
+        for timestamp in [DAY, DAY + 60, 10 * DAY] {
+            let input = DistributionInput {
+                total_cost: 1_000,
+                consumption_base: 0,
+                has_referrer: true,
+                purchase_volume: 0,
+                timestamp,
+            };
+            distribute(
+                &input,
+                &game_config(),
+                &mut game,
+                &mut round,
+                &mut referrer_data,
+            )
+            .unwrap();
+        }
+        assert_eq!(referrer_data.collectable_referral_rewards, 300);
+
+        // The two credits of the first day share a slot, expiring from the later one
+        let first_expiry = DAY + 60 + REFERRAL_REWARDS_EXPIRY_SECONDS;
+        assert_eq!(
+            referrer_data
+                .expire_referral_rewards(first_expiry - 1)
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            referrer_data.expire_referral_rewards(first_expiry).unwrap(),
+            200
+        );
+        assert_eq!(referrer_data.collectable_referral_rewards, 100);
+        assert_eq!(
+            referrer_data
+                .expire_referral_rewards(10 * DAY + REFERRAL_REWARDS_EXPIRY_SECONDS)
+                .unwrap(),
+            100
+        );
+        assert_eq!(referrer_data.collectable_referral_rewards, 0);
+
+        // Collecting clears the credits, so nothing expires afterwards
+        referrer_data
+            .add_collectable_referral_rewards(100, 20 * DAY)
+            .unwrap();
+        referrer_data.collect_referral_rewards(100).unwrap();
+        assert_eq!(referrer_data.expire_referral_rewards(u64::MAX).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_distribute_follows_game_config() {
+        let mut config = game_config();
+        config
+            .update(&GameConfigParams {
+                construction_pool_share: 20,
+                lottery_pool_share: 5,
+                referral_pool_share: 5,
+                insurance_pool_share: 0,
+                consumption_pool_share: 20,
+                ..GameConfigParams::default()
+            })
+            .unwrap();
+        let mut game = funded_game();
+        let mut round = Round {
+            available_ores: 1,
+            ..Default::default()
+        };
+        let input = DistributionInput {
+            total_cost: 1_000,
+            consumption_base: 1_000,
+            has_referrer: true,
+            purchase_volume: 0,
+            timestamp: 0,
+        };
+
+        let distribution = distribute(
+            &input,
+            &config,
+            &mut game,
+            &mut round,
+            &mut PlayerData::default(),
+        )
+        .unwrap();
+
+        assert_eq!(config.grand_prizes_pool_share(), 50);
+        assert_eq!(distribution.construction_rewards, 200);
+        assert_eq!(distribution.lottery_rewards, 50);
+        assert_eq!(distribution.insurance_rewards, 0);
+        assert_eq!(round.grand_prize_pool_balance, 500);
+        assert_eq!(distribution.developer_rewards, 200);
+        assert_eq!(distribution.total_allocated().unwrap(), 1_000);
+
+        // Shares leaving nothing to the grand prizes are rejected
+        assert!(config
+            .update(&GameConfigParams {
+                construction_pool_share: 40,
+                ..GameConfigParams::default()
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_distribute_follows_round_phase() {
+        let input = DistributionInput {
+            total_cost: 1_000,
+            consumption_base: 1_000,
+            has_referrer: true,
+            purchase_volume: 0,
+            timestamp: DAY - ROUND_CLIMAX_SECONDS,
+        };
+
+        // In its climax, the round distributes like an active round
+        let mut game = funded_game();
+        let mut round = Round {
+            available_ores: 10,
+            end_time: DAY,
+            ..Default::default()
+        };
+        let distribution = distribute(
+            &input,
+            &game_config(),
+            &mut game,
+            &mut round,
+            &mut PlayerData::default(),
+        )
+        .unwrap();
+        assert_eq!(distribution.phase, RoundPhase::Climax);
+        assert_eq!(game.construction_rewards_pool_balance, 250);
+        assert_eq!(round.grand_prize_pool_balance, 290);
+
+        // Once every holder exited, the round is bootstrapping again
+        round.available_ores = 0;
+        let distribution = distribute(
+            &input,
+            &game_config(),
+            &mut game,
+            &mut round,
+            &mut PlayerData::default(),
+        )
+        .unwrap();
+        assert_eq!(distribution.phase, RoundPhase::Bootstrapping);
+        assert_eq!(game.construction_rewards_pool_balance, 250);
+        assert_eq!(round.grand_prize_pool_balance, 1_080);
+    }
+
+    #[test]
+    fn test_settle_round() {
+        let mut game = Game {
+            construction_rewards_pool_balance: 1_000,
+            ..Default::default()
+        };
+        let mut round = Round {
+            available_ores: 10,
+            earnings_per_ore: 30 * EARNINGS_PER_ORE_PRECISION,
+            is_over: true,
+            ..Default::default()
+        };
+        let mut player_data = PlayerData {
+            available_ores: 4,
+            earnings_per_ore: 10 * EARNINGS_PER_ORE_PRECISION,
+            collectable_construction_rewards: 20,
+            ..Default::default()
+        };
+
+        let (ores, construction_rewards) =
+            settle_round(&mut game, &mut round, &mut player_data).unwrap();
+
+        assert_eq!(ores, 4);
+        assert_eq!(construction_rewards, 100);
+        assert_eq!(game.construction_rewards_pool_balance, 900);
+        assert_eq!(game.distributed_construction_rewards, 100);
+        assert_eq!(round.available_ores, 6);
+        assert_eq!(player_data.collected_construction_rewards, 100);
+        assert_eq!(player_data.collectable_construction_rewards, 0);
+        assert_eq!(player_data.available_ores, 0);
+        assert!(player_data.is_exited);
+
+        // A player holding more ORE than the round cannot be settled
+        let mut player_data = PlayerData {
+            available_ores: 7,
+            ..Default::default()
+        };
+        assert!(settle_round(&mut game, &mut round, &mut player_data).is_err());
+    }
+
+    #[test]
+    fn test_boost_construction_rewards() {
+        let year = LOCK_DURATION;
+        let now = 1_700_000_000;
+        let mut game = Game {
+            construction_rewards_pool_balance: 1_000,
+            bonus_rewards_pool_balance: 300,
+            ..Default::default()
+        };
+        let mut player_data = PlayerData {
+            collectable_construction_rewards: 1_000,
+            ..Default::default()
+        };
+
+        // Without a stake, nothing is boosted
+        assert_eq!(
+            boost_construction_rewards(&mut game, &mut player_data, 1_000, now).unwrap(),
+            0
+        );
+
+        // Half the full balance locked for a year boosts by half the highest boost
+        let mut stake_order = StakeOrder::default();
+        stake_order
+            .initialize(
+                1,
+                STAKE_BOOST_FULL_BALANCE / 2,
+                100,
+                year,
+                0,
+                Pubkey::new_unique(),
+                now,
+                255,
+            )
+            .unwrap();
+        player_data.add_stake_boost(&stake_order, now).unwrap();
+        assert_eq!(player_data.boost_factor_bps, MAX_STAKE_BOOST_BPS / 2);
+        assert_eq!(
+            boost_construction_rewards(&mut game, &mut player_data, 1_000, now).unwrap(),
+            125
+        );
+        assert_eq!(game.bonus_rewards_pool_balance, 175);
+        assert_eq!(game.distributed_bonus_rewards, 125);
+        assert_eq!(game.construction_rewards_pool_balance, 1_125);
+        assert_eq!(player_data.collectable_construction_rewards, 1_125);
+
+        // The boost decays with the remaining lock, and is capped by the bonus pool
+        assert_eq!(
+            boost_construction_rewards(&mut game, &mut player_data, 4_000, now + year / 2).unwrap(),
+            175
+        );
+        assert_eq!(player_data.boost_factor_bps, MAX_STAKE_BOOST_BPS / 4);
+        assert_eq!(game.bonus_rewards_pool_balance, 0);
+
+        // Unstaking removes the boost, once
+        player_data
+            .remove_stake_boost(&mut stake_order, now + year)
+            .unwrap();
+        player_data
+            .remove_stake_boost(&mut stake_order, now + year)
+            .unwrap();
+        assert_eq!(player_data.boost_factor_bps, 0);
+        assert_eq!(stake_order.boost_lock_end, 0);
+        assert_eq!(player_data.stake_boost_amount, 0);
+        assert_eq!(player_data.stake_boost_lock_end_weight, 0);
+    }
+
+    #[test]
+    fn test_carryover_across_rounds() {
+        let mut game = Game {
+            construction_rewards_pool_balance: 1_000,
+            ..Default::default()
+        };
+        let mut player_data = PlayerData {
+            available_ores: 4,
+            ..Default::default()
+        };
+
+        // Round 1 ends before the player settles: the rewards are carried over at the frozen earnings_per_ore
+        let mut round_1 = Round {
+            available_ores: 10,
+            earnings_per_ore: 30 * EARNINGS_PER_ORE_PRECISION,
+            is_over: true,
+            ..Default::default()
+        };
+        let (_, construction_rewards) =
+            settle_round(&mut game, &mut round_1, &mut player_data).unwrap();
+        carry_over_construction_rewards(&mut game, &mut player_data, construction_rewards).unwrap();
+        assert_eq!(construction_rewards, 120);
+        assert_eq!(player_data.carryover_balance, 120);
+        assert_eq!(game.carryover_pool_balance, 120);
+        assert_eq!(game.construction_rewards_pool_balance, 880);
+
+        // Later growth of the round does not change the carried over entitlement
+        round_1.earnings_per_ore *= 2;
+        assert_eq!(player_data.carryover_balance, 120);
+
+        // The first purchase of round 2 releases the whole carryover, and the next releases nothing
+        assert_eq!(release_carryover(&mut game, &mut player_data).unwrap(), 120);
+        assert_eq!(release_carryover(&mut game, &mut player_data).unwrap(), 0);
+        assert_eq!(game.carryover_pool_balance, 0);
+
+        // Round 2 is carried over too, then round 3 while round 2 is still carried over
+        player_data.available_ores = 5;
+        player_data.earnings_per_ore = 10 * EARNINGS_PER_ORE_PRECISION;
+        let mut round_2 = Round {
+            available_ores: 5,
+            earnings_per_ore: 20 * EARNINGS_PER_ORE_PRECISION,
+            is_over: true,
+            ..Default::default()
+        };
+        let (_, construction_rewards) =
+            settle_round(&mut game, &mut round_2, &mut player_data).unwrap();
+        carry_over_construction_rewards(&mut game, &mut player_data, construction_rewards).unwrap();
+
+        player_data.available_ores = 1;
+        player_data.earnings_per_ore = 0;
+        let mut round_3 = Round {
+            available_ores: 1,
+            earnings_per_ore: 7 * EARNINGS_PER_ORE_PRECISION,
+            is_over: true,
+            ..Default::default()
+        };
+        let (_, construction_rewards) =
+            settle_round(&mut game, &mut round_3, &mut player_data).unwrap();
+        carry_over_construction_rewards(&mut game, &mut player_data, construction_rewards).unwrap();
+
+        assert_eq!(player_data.carryover_balance, 57);
+        assert_eq!(game.carryover_pool_balance, 57);
+        assert_eq!(game.construction_rewards_pool_balance, 823);
+        assert_eq!(player_data.collected_construction_rewards, 177);
+        assert_eq!(release_carryover(&mut game, &mut player_data).unwrap(), 57);
+        assert_eq!(game.carryover_pool_balance, 0);
+    }
+
+    #[test]
+    fn test_cover_shortfall() {
+        let mut game = Game {
+            insurance_pool_balance: 100,
+            ..Default::default()
+        };
+        let mut round = Round::default();
+
+        cover_shortfall(&mut game, &mut round, ShortfallPool::GrandPrize, 40).unwrap();
+        cover_shortfall(&mut game, &mut round, ShortfallPool::Construction, 30).unwrap();
+        cover_shortfall(&mut game, &mut round, ShortfallPool::Exit, 20).unwrap();
+        assert_eq!(round.grand_prize_pool_balance, 40);
+        assert_eq!(game.construction_rewards_pool_balance, 30);
+        assert_eq!(game.exit_rewards_pool_balance, 20);
+        assert_eq!(game.insurance_pool_balance, 10);
+
+        // The insurance pool never goes negative
+        assert!(cover_shortfall(&mut game, &mut round, ShortfallPool::Exit, 11).is_err());
+        assert!(cover_shortfall(&mut game, &mut round, ShortfallPool::Exit, 0).is_err());
+        assert_eq!(game.insurance_pool_balance, 10);
+    }
+
+    #[test]
+    fn test_distribute_with_exhausted_consumption_pool() {
+        let mut game = Game {
+            consumption_rewards_pool_balance: 50,
+            distributable_consumption_rewards: 50,
+            ..Default::default()
+        };
+        let mut round = Round {
+            available_ores: 1,
+            ..Default::default()
+        };
+        let mut referrer_data = PlayerData::default();
+        let input = DistributionInput {
+            total_cost: 1_000,
+            consumption_base: 1_000,
+            has_referrer: true,
+            purchase_volume: 0,
+            timestamp: 0,
+        };
+
+        let distribution = distribute(
+            &input,
+            &game_config(),
+            &mut game,
+            &mut round,
+            &mut referrer_data,
+        )
+        .unwrap();
+
+        assert_eq!(distribution.developer_rewards, 0);
+        assert_eq!(distribution.consumption_rewards, 0);
+        assert_eq!(game.consumption_rewards_pool_balance, 50);
+        assert_eq!(game.distributable_consumption_rewards, 50);
+        assert_eq!(game.developer_rewards_pool_balance, 0);
+    }
+
+    #[test]
+    fn test_distribute_with_exhausted_distributable_consumption_rewards() {
+        let mut game = Game {
+            consumption_rewards_pool_balance: 10_000,
+            distributable_consumption_rewards: 50,
+            ..Default::default()
+        };
+        let mut round = Round::default();
+        let mut referrer_data = PlayerData::default();
+        let input = DistributionInput {
+            total_cost: 1_000,
+            consumption_base: 1_000,
+            has_referrer: false,
+            purchase_volume: 0,
+            timestamp: 0,
+        };
+
+        let distribution = distribute(
+            &input,
+            &game_config(),
+            &mut game,
+            &mut round,
+            &mut referrer_data,
+        )
+        .unwrap();
+
+        assert_eq!(distribution.developer_rewards, 0);
+        assert_eq!(distribution.consumption_rewards, 0);
+        assert_eq!(game.consumption_rewards_pool_balance, 10_000);
+        assert_eq!(game.distributable_consumption_rewards, 50);
+    }
+
+    fn consumption_reward_tiers() -> Vec<ConsumptionRewardTier> {
+        vec![
+            ConsumptionRewardTier {
+                min_purchase_volume: 1_000,
+                consumption_rate: 12,
+            },
+            ConsumptionRewardTier {
+                min_purchase_volume: 10_000,
+                consumption_rate: 15,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_resolve_consumption_rate() {
+        let tiers = consumption_reward_tiers();
+
+        assert_eq!(resolve_consumption_rate(&tiers, 0, 10), 10);
+        assert_eq!(resolve_consumption_rate(&tiers, 999, 10), 10);
+        assert_eq!(resolve_consumption_rate(&tiers, 1_000, 10), 12);
+        assert_eq!(resolve_consumption_rate(&tiers, 9_999, 10), 12);
+        assert_eq!(resolve_consumption_rate(&tiers, u64::MAX, 10), 15);
+        assert_eq!(resolve_consumption_rate(&[], u64::MAX, 10), 10);
+    }
+
+    #[test]
+    fn test_distribute_with_consumption_reward_tier() {
+        let mut game = funded_game();
+        game.set_consumption_reward_tiers(&consumption_reward_tiers())
+            .unwrap();
+        let mut round = Round::default();
+        let mut referrer_data = PlayerData::default();
+        let input = DistributionInput {
+            total_cost: 1_000,
+            consumption_base: 1_000,
+            has_referrer: true,
+            purchase_volume: 10_000,
+            timestamp: 0,
+        };
+
+        let distribution = distribute(
+            &input,
+            &game_config(),
+            &mut game,
+            &mut round,
+            &mut referrer_data,
+        )
+        .unwrap();
+
+        // The tier raises the consumption rewards, leaving the developer share at the configured rate
+        assert_eq!(distribution.consumption_rewards, 150);
+        assert_eq!(distribution.developer_rewards, 100);
+        assert_eq!(game.distributable_consumption_rewards, 9_750);
+    }
+
+    #[test]
+    fn test_record_purchased_ores_during_period() {
+        let default_team = Pubkey::new_unique();
+        let team_key = Pubkey::new_unique();
+        let mut period = period(Pubkey::new_unique(), default_team);
+        let record = record(team_key, default_team, 10 * DAY);
+        let mut round = Round {
+            end_time: 20 * DAY,
+            ..Default::default()
+        };
+        let mut player_data = PlayerData {
+            current_period_purchased_ores: 7,
+            last_purchased_day: 9,
+            consecutive_purchased_days: 3,
+            ..Default::default()
+        };
+        let mut team = Team::default();
+
+        record_purchased_ores(
+            &record,
+            &mut Game::default(),
+            &mut round,
+            &mut period,
+            &mut player_data,
+            &mut team,
+        )
+        .unwrap();
+
+        assert_eq!(round.available_ores, 5);
+        assert_eq!(round.sold_ores, 5);
+        assert_eq!(round.last_active_participant_list[0], record.player);
+        assert_eq!(player_data.current_round, record.round);
+        assert_eq!(player_data.current_period, record.period);
+        assert_eq!(player_data.current_period_purchased_ores, 5);
+        assert_eq!(player_data.consecutive_purchased_days, 4);
+        assert_eq!(player_data.available_ores, 5);
+        assert_eq!(team.purchased_ores, 5);
+        assert_eq!(team.current_period_purchased_ores, 5);
+        assert_eq!(team.current_period_active_members, 1);
+        assert_eq!(period.top_player_list[0].player, record.player);
+        assert_eq!(period.top_player_list[0].purchased_ores, 5);
+        assert_eq!(period.top_team_list[0].team, team_key);
+    }
+
+    #[test]
+    fn test_record_purchased_ores_with_streak_shield() {
+        let default_team = Pubkey::new_unique();
+        let mut period = period(Pubkey::new_unique(), default_team);
+        let mut round = Round {
+            end_time: 20 * DAY,
+            ..Default::default()
+        };
+        let mut team = Team::default();
+
+        // A shield covers the missed day 10 once, and is consumed
+        let mut player_data = PlayerData {
+            last_purchased_day: 9,
+            consecutive_purchased_days: 3,
+            has_streak_shield: true,
+            streak_shield_expiry: 12 * DAY,
+            ..Default::default()
+        };
+        let record = record(default_team, default_team, 11 * DAY);
+        record_purchased_ores(
+            &record,
+            &mut Game::default(),
+            &mut round,
+            &mut period,
+            &mut player_data,
+            &mut team,
+        )
+        .unwrap();
+        assert_eq!(player_data.consecutive_purchased_days, 4);
+        assert!(!player_data.has_streak_shield);
+
+        // An expired shield does not
+        let mut player_data = PlayerData {
+            last_purchased_day: 9,
+            consecutive_purchased_days: 3,
+            has_streak_shield: true,
+            streak_shield_expiry: 11 * DAY,
+            ..Default::default()
+        };
+        record_purchased_ores(
+            &record,
+            &mut Game::default(),
+            &mut round,
+            &mut period,
+            &mut player_data,
+            &mut team,
+        )
+        .unwrap();
+        assert_eq!(player_data.consecutive_purchased_days, 1);
+        assert!(player_data.has_streak_shield);
+    }
+
+    #[test]
+    fn test_record_purchased_ores_outside_period() {
+        let default_team = Pubkey::new_unique();
+        let mut period = period(Pubkey::new_unique(), default_team);
+        let record = record(default_team, default_team, 12 * DAY);
+        let mut round = Round {
+            end_time: 20 * DAY,
+            ..Default::default()
+        };
+        let mut player_data = PlayerData {
+            last_purchased_day: 5,
+            consecutive_purchased_days: 3,
+            ..Default::default()
+        };
+        let mut team = Team::default();
+
+        record_purchased_ores(
+            &record,
+            &mut Game::default(),
+            &mut round,
+            &mut period,
+            &mut player_data,
+            &mut team,
+        )
+        .unwrap();
+
+        assert_eq!(player_data.consecutive_purchased_days, 1);
+        assert_eq!(player_data.current_period_purchased_ores, 0);
+        assert_eq!(team.purchased_ores, 5);
+        assert_eq!(team.current_period_purchased_ores, 0);
+        assert_ne!(period.top_player_list[0].player, record.player);
+    }
+
+    #[test]
+    fn test_record_purchased_ores_boosts_construction_rewards() {
+        let now = 1_700_000_000;
+        let default_team = Pubkey::new_unique();
+        let mut period = period(Pubkey::new_unique(), default_team);
+        let mut game = Game {
+            construction_rewards_pool_balance: 1_000,
+            bonus_rewards_pool_balance: 300,
+            ..Default::default()
+        };
+        let mut round = Round {
+            earnings_per_ore: 100 * EARNINGS_PER_ORE_PRECISION,
+            ..Default::default()
+        };
+        let mut player_data = PlayerData {
+            available_ores: 10,
+            ..Default::default()
+        };
+        let mut team = Team::default();
+
+        // Half the full balance locked for a year boosts the settled rewards by half the highest boost
+        let mut stake_order = StakeOrder::default();
+        stake_order
+            .initialize(
+                1,
+                STAKE_BOOST_FULL_BALANCE / 2,
+                100,
+                LOCK_DURATION,
+                0,
+                Pubkey::new_unique(),
+                now,
+                255,
+            )
+            .unwrap();
+        player_data.add_stake_boost(&stake_order, now).unwrap();
+
+        let (construction_rewards, boost) = record_purchased_ores(
+            &record(default_team, default_team, now),
+            &mut game,
+            &mut round,
+            &mut period,
+            &mut player_data,
+            &mut team,
+        )
+        .unwrap();
+
+        assert_eq!(construction_rewards, 1_000);
+        assert_eq!(boost, 125);
+        assert_eq!(player_data.collectable_construction_rewards, 1_125);
+        assert_eq!(game.bonus_rewards_pool_balance, 175);
+        assert_eq!(game.construction_rewards_pool_balance, 1_125);
+    }
+
+    #[test]
+    fn test_exit_rewards_accrue_pro_rata() {
+        let default_team = Pubkey::new_unique();
+        let mut period = period(Pubkey::new_unique(), default_team);
+        let mut round = Round {
+            end_time: 20 * DAY,
+            exit_rewards_per_second: 10,
+            ..Default::default()
+        };
+        let mut first_player_data = PlayerData::default();
+        let mut second_player_data = PlayerData::default();
+        let mut team = Team::default();
+
+        // The first player holds all the ORE for 100 seconds, then shares it with the second for 100 seconds
+        let start = 12 * DAY;
+        record_purchased_ores(
+            &record(default_team, default_team, start),
+            &mut Game::default(),
+            &mut round,
+            &mut period,
+            &mut first_player_data,
+            &mut team,
+        )
+        .unwrap();
+        record_purchased_ores(
+            &record(default_team, default_team, start + 100),
+            &mut Game::default(),
+            &mut round,
+            &mut period,
+            &mut second_player_data,
+            &mut team,
+        )
+        .unwrap();
+        assert_eq!(second_player_data.collectable_exit_rewards, 0);
+
+        round.accrue_exit_rewards(start + 200).unwrap();
+        first_player_data
+            .settle_collectable_exit_rewards(round.exit_rewards_per_ore)
+            .unwrap();
+        second_player_data
+            .settle_collectable_exit_rewards(round.exit_rewards_per_ore)
+            .unwrap();
+        assert_eq!(first_player_data.collectable_exit_rewards, 1_500);
+        assert_eq!(second_player_data.collectable_exit_rewards, 500);
+
+        // Leaving the round clears the player's exit reward accrual
+        first_player_data.exit_round().unwrap();
+        assert_eq!(first_player_data.collectable_exit_rewards, 0);
+        assert_eq!(first_player_data.exit_rewards_per_ore, 0);
+    }
+
+    #[test]
+    fn test_default_team_stays_off_team_leaderboard() {
+        let default_team = Pubkey::new_unique();
+        let mut period = period(Pubkey::new_unique(), default_team);
+        let record = record(default_team, default_team, 10 * DAY);
+        let mut round = Round::default();
+        let mut player_data = PlayerData::default();
+        let mut team = Team::default();
+
+        record_purchased_ores(
+            &record,
+            &mut Game::default(),
+            &mut round,
+            &mut period,
+            &mut player_data,
+            &mut team,
+        )
+        .unwrap();
+
+        assert_eq!(period.top_player_list[0].player, record.player);
+        assert!(period
+            .top_team_list
+            .iter()
+            .all(|entry| entry.team == default_team));
+    }
+}
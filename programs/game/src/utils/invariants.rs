@@ -0,0 +1,299 @@
+use crate::errors::ErrorCode;
+use crate::state::{Game, Round};
+use anchor_lang::prelude::*;
+
+/// The monotonic counters of the game and the current round, captured at the start of an instruction
+/// so `assert_invariants` can check they never decrease.
+///
+/// # Fields
+/// - `distributed_rewards`: The game's `distributed_*_rewards` counters.
+/// - `earnings_per_ore`: The round's cumulative construction earnings per ORE.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvariantSnapshot {
+    pub distributed_rewards: [u64; 13],
+    pub earnings_per_ore: u128,
+}
+
+impl InvariantSnapshot {
+    /// Captures the monotonic counters of `game` and `round`.
+    pub fn take(game: &Game, round: &Round) -> Self {
+        Self {
+            distributed_rewards: [
+                game.distributed_registration_rewards,
+                game.distributed_airdrop_rewards,
+                game.distributed_consumption_rewards,
+                game.distributed_exit_rewards,
+                game.distributed_stake_rewards,
+                game.distributed_construction_rewards,
+                game.distributed_bonus_rewards,
+                game.distributed_lottery_rewards,
+                game.distributed_developer_rewards,
+                game.distributed_referral_rewards,
+                game.distributed_grand_prizes,
+                game.distributed_individual_rewards,
+                game.distributed_team_rewards,
+            ],
+            earnings_per_ore: round.earnings_per_ore,
+        }
+    }
+}
+
+/// Checks the game-wide invariants at the end of a mutating instruction:
+/// - the game pools never exceed the game vault balance,
+/// - the round's grand prize pool never exceeds the round vault balance,
+/// - the distributed counters and the round's `earnings_per_ore` never decrease.
+///
+/// # Arguments
+/// - `before`: The snapshot taken at the start of the instruction.
+/// - `game`: The game after the instruction.
+/// - `round`: The current round after the instruction.
+/// - `game_vault_amount`: The game vault balance after the instruction.
+/// - `round_vault_amount`: The round vault balance after the instruction.
+pub fn assert_invariants(
+    before: &InvariantSnapshot,
+    game: &Game,
+    round: &Round,
+    game_vault_amount: u64,
+    round_vault_amount: u64,
+) -> Result<()> {
+    require!(
+        game.total_pool_balance()? <= game_vault_amount,
+        ErrorCode::InvariantViolation
+    );
+    require!(
+        round.grand_prize_pool_balance <= round_vault_amount,
+        ErrorCode::InvariantViolation
+    );
+
+    let after = InvariantSnapshot::take(game, round);
+    require!(
+        after
+            .distributed_rewards
+            .iter()
+            .zip(before.distributed_rewards.iter())
+            .all(|(after, before)| after >= before),
+        ErrorCode::InvariantViolation
+    );
+    require!(
+        after.earnings_per_ore >= before.earnings_per_ore,
+        ErrorCode::InvariantViolation
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{GameConfig, PlayerData};
+    use crate::utils::{distribute, DistributionInput};
+
+    /// A small xorshift generator, so the random instruction sequences are reproducible.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, bound: u64) -> u64 {
+            self.next() % bound
+        }
+    }
+
+    fn game_config() -> GameConfig {
+        let mut config = GameConfig::default();
+        config.initialize().unwrap();
+        config
+    }
+
+    /// The token balances of the vaults backing the game and the round.
+    struct Vaults {
+        game: u64,
+        round: u64,
+    }
+
+    fn purchase(rng: &mut Rng, game: &mut Game, round: &mut Round, vaults: &mut Vaults) {
+        let total_cost = rng.below(1_000_000_000) + 1;
+        let input = DistributionInput {
+            total_cost,
+            consumption_base: rng.below(total_cost + 1),
+            has_referrer: rng.below(2) == 0,
+            purchase_volume: 0,
+            timestamp: 0,
+        };
+        let distribution = distribute(
+            &input,
+            &game_config(),
+            game,
+            round,
+            &mut PlayerData::default(),
+        )
+        .unwrap();
+
+        vaults.game += distribution.game_vault_amount().unwrap();
+        vaults.game -= distribution.burned_referral_rewards();
+        vaults.round += distribution.round_vault_amount().unwrap();
+        round.available_ores += rng.below(100) as u32 + 1;
+    }
+
+    fn candy_tap(rng: &mut Rng, game: &mut Game, round: &mut Round, vaults: &mut Vaults) {
+        let total_cost = rng.below(game.sugar_rush_rewards_pool_balance + 1);
+        game.sugar_rush_rewards_pool_balance -= total_cost;
+        let input = DistributionInput {
+            total_cost,
+            consumption_base: total_cost,
+            has_referrer: true,
+            purchase_volume: 0,
+            timestamp: 0,
+        };
+        let distribution = distribute(
+            &input,
+            &game_config(),
+            game,
+            round,
+            &mut PlayerData::default(),
+        )
+        .unwrap();
+
+        let round_vault_amount = distribution.round_vault_amount().unwrap();
+        vaults.game -= round_vault_amount;
+        vaults.round += round_vault_amount;
+    }
+
+    fn reinvest(rng: &mut Rng, game: &mut Game, round: &mut Round, vaults: &mut Vaults) {
+        let half_cost = rng.below(
+            game.construction_rewards_pool_balance
+                .min(game.bonus_rewards_pool_balance)
+                + 1,
+        );
+        game.construction_rewards_pool_balance -= half_cost;
+        game.bonus_rewards_pool_balance -= half_cost;
+        game.distributed_construction_rewards += half_cost;
+        game.distributed_bonus_rewards += half_cost;
+
+        let input = DistributionInput {
+            total_cost: half_cost * 2,
+            consumption_base: half_cost * 2,
+            has_referrer: rng.below(2) == 0,
+            purchase_volume: 0,
+            timestamp: 0,
+        };
+        let distribution = distribute(
+            &input,
+            &game_config(),
+            game,
+            round,
+            &mut PlayerData::default(),
+        )
+        .unwrap();
+
+        let round_vault_amount = distribution.round_vault_amount().unwrap();
+        vaults.game -= round_vault_amount + distribution.burned_referral_rewards();
+        vaults.round += round_vault_amount;
+    }
+
+    #[test]
+    fn test_invariants_hold_over_random_instruction_sequences() {
+        for seed in 1..=200 {
+            let mut rng = Rng(seed);
+            let mut game = Game {
+                sugar_rush_rewards_pool_balance: 1_000_000_000,
+                consumption_rewards_pool_balance: 1_000_000_000,
+                distributable_consumption_rewards: 1_000_000_000,
+                ..Default::default()
+            };
+            let mut round = Round::default();
+            let mut vaults = Vaults {
+                game: game.total_pool_balance().unwrap(),
+                round: 0,
+            };
+
+            for _ in 0..50 {
+                let before = InvariantSnapshot::take(&game, &round);
+                match rng.below(3) {
+                    0 => purchase(&mut rng, &mut game, &mut round, &mut vaults),
+                    1 => candy_tap(&mut rng, &mut game, &mut round, &mut vaults),
+                    _ => reinvest(&mut rng, &mut game, &mut round, &mut vaults),
+                }
+                assert_invariants(&before, &game, &round, vaults.game, vaults.round).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_allocations_sum_to_cost() {
+        let mut rng = Rng(0x5eed);
+        for iteration in 0..10_000 {
+            // Cover small costs, where whole shares round down to zero, as well as the full range
+            let total_cost = match iteration % 3 {
+                0 => rng.below(1_000),
+                1 => rng.below(1_000_000_000_000),
+                _ => rng.next() / 2,
+            };
+            let mut round = Round {
+                available_ores: rng.below(2) as u32,
+                end_time: u64::MAX,
+                ..Default::default()
+            };
+            let input = DistributionInput {
+                total_cost,
+                consumption_base: total_cost,
+                has_referrer: rng.below(2) == 0,
+                purchase_volume: 0,
+                timestamp: 0,
+            };
+            let distribution = distribute(
+                &input,
+                &game_config(),
+                &mut Game::default(),
+                &mut round,
+                &mut PlayerData::default(),
+            )
+            .unwrap();
+
+            assert_eq!(distribution.total_allocated().unwrap(), total_cost);
+            assert_eq!(
+                distribution.round_vault_amount().unwrap()
+                    + distribution.game_vault_amount().unwrap(),
+                total_cost
+            );
+            assert!(distribution.dust < 100);
+        }
+    }
+
+    #[test]
+    fn test_assert_invariants_detects_violations() {
+        let game = Game {
+            lottery_rewards_pool_balance: 100,
+            distributed_lottery_rewards: 10,
+            ..Default::default()
+        };
+        let round = Round {
+            grand_prize_pool_balance: 50,
+            earnings_per_ore: 5,
+            ..Default::default()
+        };
+        let before = InvariantSnapshot::take(&game, &round);
+        assert!(assert_invariants(&before, &game, &round, 100, 50).is_ok());
+
+        // Pools exceeding the vaults
+        assert!(assert_invariants(&before, &game, &round, 99, 50).is_err());
+        assert!(assert_invariants(&before, &game, &round, 100, 49).is_err());
+
+        // Counters going backwards
+        let reverted_game = Game {
+            distributed_lottery_rewards: 9,
+            ..game
+        };
+        assert!(assert_invariants(&before, &reverted_game, &round, 100, 50).is_err());
+        let reverted_round = Round {
+            earnings_per_ore: 4,
+            ..Default::default()
+        };
+        assert!(assert_invariants(&before, &game, &reverted_round, 100, 50).is_err());
+    }
+}
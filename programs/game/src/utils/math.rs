@@ -1,10 +1,24 @@
-use crate::constants::SECONDS_PER_YEAR;
-use anchor_lang::prelude::*;
-use anchor_safe_math::SafeMath;
+//! The reward math of the game, kept free of Anchor types so it can be fuzzed and tested in isolation.
+//! Every function is deterministic and reports overflows, underflows and divisions by zero as `MathError`,
+//! which converts into the program's `ErrorCode::MathOverflow`.
+
+use crate::constants::{
+    EARNINGS_PER_ORE_PRECISION, LAMPORTS_PER_TOKEN, SECONDS_PER_MINUTE, SECONDS_PER_YEAR,
+};
 
 /// Basis points denominator (100%)
 const BASIS_POINTS_DENOMINATOR: u8 = 100;
 
+/// Denominator of boost factors, in hundredths of a percent (100%)
+const BOOST_BASIS_POINTS: u64 = 10_000;
+
+/// An overflow, underflow or division by zero in the reward math.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MathError;
+
+/// The result of the reward math.
+pub type MathResult<T> = core::result::Result<T, MathError>;
+
 /// Calculate interest based on duration and annual rate
 ///
 /// # Arguments
@@ -13,19 +27,19 @@ const BASIS_POINTS_DENOMINATOR: u8 = 100;
 /// * `annual_rate` - Annual interest rate in basis points
 ///
 /// # Returns
-/// * `Result<u64>` - Calculated interest amount
+/// * `MathResult<u64>` - Calculated interest amount
 pub fn calculate_prorated_interest(
     principal: u64,
     actual_duration: u64,
     annual_rate: u8,
-) -> Result<u64> {
-    let interest = principal
-        .safe_div(BASIS_POINTS_DENOMINATOR as u64)?
-        .safe_mul(annual_rate as u64)?
-        .safe_mul(actual_duration)?
-        .safe_div(SECONDS_PER_YEAR)?;
+) -> MathResult<u64> {
+    // Scale in u128, so only interest that does not fit in a u64 overflows
+    let interest = (principal / BASIS_POINTS_DENOMINATOR as u64) as u128
+        * annual_rate as u128
+        * actual_duration as u128
+        / SECONDS_PER_YEAR as u128;
 
-    Ok(interest)
+    u64::try_from(interest).map_err(|_| MathError)
 }
 
 /// Calculate proportional amount
@@ -35,63 +49,573 @@ pub fn calculate_prorated_interest(
 /// * `proportion` - The proportion in basis points
 ///
 /// # Returns
-/// * `Result<u64>` - Calculated proportional amount
-pub fn calculate_proportion(amount: u64, proportion: u8) -> Result<u64> {
-    let proportional_amount = amount
-        .safe_div(BASIS_POINTS_DENOMINATOR as u64)?
-        .safe_mul(proportion as u64)?;
+/// * `MathResult<u64>` - Calculated proportional amount
+pub fn calculate_proportion(amount: u64, proportion: u8) -> MathResult<u64> {
+    (amount / BASIS_POINTS_DENOMINATOR as u64)
+        .checked_mul(proportion as u64)
+        .ok_or(MathError)
+}
 
-    Ok(proportional_amount)
+/// Calculate the utilization of a reward pool
+///
+/// # Arguments
+/// * `committed` - The amount already committed (e.g. staked principal)
+/// * `available` - The amount still available to be committed
+///
+/// # Returns
+/// * `MathResult<u8>` - `committed / (committed + available)` in basis points, `0` when both are empty
+pub fn calculate_utilization(committed: u64, available: u64) -> MathResult<u8> {
+    // Neither the sum nor the scaled amount of two u64 can overflow a u128
+    let total = committed as u128 + available as u128;
+    if total == 0 {
+        return Ok(0);
+    }
+
+    let utilization = committed as u128 * BASIS_POINTS_DENOMINATOR as u128 / total;
+
+    Ok(utilization as u8)
 }
 
-pub fn calculate_multiplier(symbols: [u8; 3]) -> u16 {
-    let (s1, s2, s3) = (symbols[0], symbols[1], symbols[2]);
+/// Calculate an annual rate from a kinked utilization curve
+///
+/// The rate stays at `max_rate` while `utilization <= target_utilization`, then falls
+/// linearly to `min_rate` as utilization approaches 100%.
+///
+/// # Arguments
+/// * `utilization` - Current utilization in basis points
+/// * `target_utilization` - Utilization at which the rate starts decreasing
+/// * `min_rate` - Annual rate at full utilization
+/// * `max_rate` - Annual rate at or below the target utilization
+///
+/// # Returns
+/// * `MathResult<u8>` - Annual rate in basis points
+pub fn calculate_curve_rate(
+    utilization: u8,
+    target_utilization: u8,
+    min_rate: u8,
+    max_rate: u8,
+) -> MathResult<u8> {
+    if utilization <= target_utilization || target_utilization >= BASIS_POINTS_DENOMINATOR {
+        return Ok(max_rate);
+    }
+
+    let excess = utilization.min(BASIS_POINTS_DENOMINATOR) as u64 - target_utilization as u64;
+    let range = (BASIS_POINTS_DENOMINATOR - target_utilization) as u64;
+    let reduction = max_rate.checked_sub(min_rate).ok_or(MathError)? as u64 * excess / range;
+
+    max_rate.checked_sub(reduction as u8).ok_or(MathError)
+}
+
+/// Calculate the multiplier of a lottery spin from the multiplier rows of its table
+///
+/// Three of a kind pays the symbol's `triple_multipliers` entry. Otherwise the first symbol, in table
+/// order, whose count pays anything decides: its `pair_multipliers` entry when it shows twice, its
+/// `single_multipliers` entry when it shows once.
+///
+/// # Arguments
+/// * `symbols` - The symbols shown on the three reels
+/// * `triple_multipliers` - Multiplier of each symbol showing three times
+/// * `pair_multipliers` - Multiplier of each symbol showing exactly twice
+/// * `single_multipliers` - Multiplier of each symbol showing exactly once
+///
+/// # Returns
+/// * `u16` - The multiplier of the lottery draw cost won, `0` when the spin loses
+pub fn calculate_spin_multiplier(
+    symbols: [u8; 3],
+    triple_multipliers: &[u16],
+    pair_multipliers: &[u16],
+    single_multipliers: &[u16],
+) -> u16 {
+    let [s1, s2, s3] = symbols;
 
     if s1 == s2 && s2 == s3 {
-        return match s1 {
-            0 => 1000,
-            x if x == 1 || x == 2 => 100,
-            x if (3..=5).contains(&x) => 50,
-            x if (6..=9).contains(&x) => 20,
-            _ => 0,
-        };
+        return triple_multipliers.get(s1 as usize).copied().unwrap_or(0);
     }
 
-    let cherry_count = [s1, s2, s3].iter().filter(|&&x| x == 1 || x == 2).count();
-    if cherry_count > 0 && cherry_count < 3 {
-        return 3 * (cherry_count as u16);
-    }
+    let mut present = [s1, s2, s3];
+    present.sort_unstable();
 
-    let bell_count = [s1, s2, s3]
+    present
         .iter()
-        .filter(|&&x| (3..=5).contains(&x))
-        .count();
-    if bell_count == 2 {
-        return 6;
+        .filter_map(|&symbol| {
+            let multipliers = match symbols.iter().filter(|&&s| s == symbol).count() {
+                2 => pair_multipliers,
+                _ => single_multipliers,
+            };
+            multipliers.get(symbol as usize).copied()
+        })
+        .find(|&multiplier| multiplier > 0)
+        .unwrap_or(0)
+}
+
+/// Get the reel stop a random byte lands on, out of `reel_stops`
+pub fn get_reel_stop(random_byte: u8, reel_stops: usize) -> u8 {
+    (random_byte as usize % reel_stops) as u8
+}
+
+/// Calculate a percentage rate from an oracle price
+///
+/// # Arguments
+/// * `price` - The oracle price, scaled by `10^precision`
+/// * `std_dev` - The standard deviation of the oracle submissions, with the same scale
+/// * `precision` - The number of decimals of `price` and `std_dev`
+/// * `max_confidence_rate` - Maximum standard deviation as a percentage of the price
+///
+/// # Returns
+/// * `Option<u64>` - The price as a percentage, or `None` if it is not positive or not confident enough
+pub fn calculate_oracle_rate(
+    price: i128,
+    std_dev: i128,
+    precision: u32,
+    max_confidence_rate: u8,
+) -> Option<u64> {
+    if price <= 0 || std_dev < 0 {
+        return None;
     }
 
-    let lemon_count = [s1, s2, s3]
+    let deviation = std_dev.checked_mul(BASIS_POINTS_DENOMINATOR as i128)?;
+    if deviation > price.checked_mul(max_confidence_rate as i128)? {
+        return None;
+    }
+
+    let rate = price
+        .checked_mul(BASIS_POINTS_DENOMINATOR as i128)?
+        .checked_div(10i128.checked_pow(precision)?)?;
+
+    u64::try_from(rate).ok()
+}
+
+/// Split rewards across leaderboard ranks following a payout curve
+///
+/// # Arguments
+/// * `rewards` - The rewards to split
+/// * `payout_curve` - The percentage of the rewards paid to each rank
+///
+/// # Returns
+/// * `MathResult<Option<[u64; N]>>` - The rewards of each rank, with any rounding remainder given to the first rank,
+///   or `None` if the payout curve does not sum to 100%
+pub fn calculate_rank_rewards<const N: usize>(
+    rewards: u64,
+    payout_curve: &[u8; N],
+) -> MathResult<Option<[u64; N]>> {
+    let total_rate = payout_curve
         .iter()
-        .filter(|&&x| (6..=9).contains(&x))
-        .count();
-    if lemon_count == 2 {
-        return 3;
+        .try_fold(0u8, |total, &rate| total.checked_add(rate));
+    if N == 0 || total_rate != Some(BASIS_POINTS_DENOMINATOR) {
+        return Ok(None);
+    }
+
+    let mut rank_rewards = [0u64; N];
+    let mut distributed = 0u64;
+    for (rank_reward, &rate) in rank_rewards.iter_mut().zip(payout_curve) {
+        // Rates sum to 100%, so each share fits in a u64 and the shares never exceed the rewards
+        *rank_reward = (rewards as u128 * rate as u128 / BASIS_POINTS_DENOMINATOR as u128) as u64;
+        distributed += *rank_reward;
+    }
+    rank_rewards[0] += rewards - distributed;
+
+    Ok(Some(rank_rewards))
+}
+
+/// Split a grand prize pool between the last active participants
+///
+/// # Arguments
+/// * `prize_pool` - The grand prize pool
+/// * `winners` - The number of winners sharing half of the pool
+///
+/// # Returns
+/// * `MathResult<(u64, u64)>` - The prize of the first winner, who also takes the other half of the pool,
+///   and the prize of each other winner
+pub fn calculate_prize_split(prize_pool: u64, winners: u64) -> MathResult<(u64, u64)> {
+    let half_prize = prize_pool / 2;
+    let shared_prize = half_prize.checked_div(winners).ok_or(MathError)?;
+
+    Ok((half_prize + shared_prize, shared_prize))
+}
+
+/// Calculate the increase of the construction earnings per ORE
+///
+/// # Arguments
+/// * `construction_rewards` - The construction rewards shared by the ORE held
+/// * `available_ores` - The ORE held in the round
+///
+/// # Returns
+/// * `MathResult<u128>` - The earnings added to each ORE held, scaled by `EARNINGS_PER_ORE_PRECISION`
+pub fn calculate_earnings_per_ore_increment(
+    construction_rewards: u64,
+    available_ores: u32,
+) -> MathResult<u128> {
+    // A u64 scaled by the precision cannot overflow a u128
+    (construction_rewards as u128 * EARNINGS_PER_ORE_PRECISION)
+        .checked_div(available_ores as u128)
+        .ok_or(MathError)
+}
+
+/// Calculate the construction rewards accrued by ORE since the earnings per ORE last settled
+///
+/// # Arguments
+/// * `delta_earnings_per_ore` - The increase of the earnings per ORE, scaled by `EARNINGS_PER_ORE_PRECISION`
+/// * `available_ores` - The ORE held
+///
+/// # Returns
+/// * `MathResult<u64>` - The accrued rewards, rounded down
+pub fn calculate_accrued_earnings(
+    delta_earnings_per_ore: u128,
+    available_ores: u32,
+) -> MathResult<u64> {
+    let accrued_earnings = delta_earnings_per_ore
+        .checked_mul(available_ores as u128)
+        .ok_or(MathError)?
+        / EARNINGS_PER_ORE_PRECISION;
+
+    u64::try_from(accrued_earnings).map_err(|_| MathError)
+}
+
+/// Calculate the sugar rush multiplier, escalating the longer no one taps candy
+///
+/// The multiplier stays at 1x for `escalation_delay` seconds, then grows by `escalation_rate`
+/// basis points for every further full minute without a tap, up to `max_multiplier`.
+///
+/// # Arguments
+/// * `idle_seconds` - Time since the last candy tap of the round (in seconds)
+/// * `escalation_delay` - Idle time before the multiplier starts growing (in seconds)
+/// * `escalation_rate` - Growth of the multiplier per idle minute in basis points
+/// * `max_multiplier` - Highest multiplier in basis points
+///
+/// # Returns
+/// * `u16` - Multiplier in basis points, `100` being 1x
+pub fn calculate_sugar_rush_multiplier(
+    idle_seconds: u64,
+    escalation_delay: u64,
+    escalation_rate: u16,
+    max_multiplier: u16,
+) -> u16 {
+    let escalated_minutes = idle_seconds.saturating_sub(escalation_delay) / SECONDS_PER_MINUTE;
+
+    // Scale in u128, so the growth cannot overflow before being capped
+    let multiplier =
+        BASIS_POINTS_DENOMINATOR as u128 + escalated_minutes as u128 * escalation_rate as u128;
+
+    multiplier.min(max_multiplier as u128) as u16
+}
+
+/// Calculate the sugar rush rewards accrued since the last candy tap
+///
+/// # Arguments
+/// * `rewards_per_second` - Sugar rush rewards accruing each second
+/// * `idle_seconds` - Time since the last candy tap of the round (in seconds)
+/// * `multiplier` - Sugar rush multiplier in basis points
+///
+/// # Returns
+/// * `MathResult<u64>` - The escalated rewards, rounded down
+pub fn calculate_sugar_rush_rewards(
+    rewards_per_second: u64,
+    idle_seconds: u64,
+    multiplier: u16,
+) -> MathResult<u64> {
+    let rewards = (rewards_per_second as u128)
+        .checked_mul(idle_seconds as u128)
+        .and_then(|rewards| rewards.checked_mul(multiplier as u128))
+        .ok_or(MathError)?
+        / BASIS_POINTS_DENOMINATOR as u128;
+
+    u64::try_from(rewards).map_err(|_| MathError)
+}
+
+/// Calculate the sugar rush rewards per second emitting the remaining pool over a horizon
+///
+/// # Arguments
+/// * `pool_balance` - The remaining sugar rush pool
+/// * `horizon_seconds` - Time over which the remaining pool is emitted (in seconds)
+/// * `min_rewards_per_second` - The lowest rate
+/// * `max_rewards_per_second` - The highest rate
+///
+/// # Returns
+/// * `MathResult<u64>` - The rate, rounded down and clamped between the lowest and the highest rate
+pub fn calculate_sugar_rush_emission_rate(
+    pool_balance: u64,
+    horizon_seconds: u64,
+    min_rewards_per_second: u64,
+    max_rewards_per_second: u64,
+) -> MathResult<u64> {
+    let rate = pool_balance.checked_div(horizon_seconds).ok_or(MathError)?;
+
+    Ok(rate.max(min_rewards_per_second).min(max_rewards_per_second))
+}
+
+/// Check whether a new daily limit is looser than the current one
+///
+/// # Arguments
+/// * `current_limit` - The limit in force, zero for unlimited
+/// * `new_limit` - The requested limit, zero for unlimited
+///
+/// # Returns
+/// * `bool` - Whether the new limit allows more than the current one
+pub fn is_limit_loosened(current_limit: u32, new_limit: u32) -> bool {
+    current_limit != 0 && (new_limit == 0 || new_limit > current_limit)
+}
+
+/// Calculate the realized return to player of a lottery table
+///
+/// # Arguments
+/// * `total_bets` - Cumulative draw costs paid on the table
+/// * `total_payouts` - Cumulative lottery rewards paid out by the table
+///
+/// # Returns
+/// * `u64` - `total_payouts / total_bets` as a percentage, `0` before any bet
+pub fn calculate_return_to_player_rate(total_bets: u64, total_payouts: u64) -> u64 {
+    if total_bets == 0 {
+        return 0;
     }
 
-    0
+    // The scaled payouts of a u64 cannot overflow a u128, and the rate is at most 100 * u64::MAX / 1
+    (total_payouts as u128 * 100 / total_bets as u128).min(u64::MAX as u128) as u64
 }
 
-pub const REEL_SYMBOLS: [u8; 32] = [
-    0, // 0:7
-    1, 2, // 1,2
-    3, 4, 5, // 3,4,5
-    6, 7, 8, 9, // 6..9
-    10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31,
-];
+/// Check whether a lottery table pays out more than its return to player ceiling
+///
+/// The ceiling is only enforced once `min_bets` were placed, so a lucky early jackpot does not
+/// trip it before the realized return had a chance to converge.
+///
+/// # Arguments
+/// * `total_bets` - Cumulative draw costs paid on the table
+/// * `total_payouts` - Cumulative lottery rewards paid out by the table
+/// * `max_rate` - Return to player ceiling as a percentage
+/// * `min_bets` - Cumulative draw costs required before the ceiling is enforced
+///
+/// # Returns
+/// * `bool` - Whether the realized return exceeds the ceiling
+pub fn is_return_to_player_exceeded(
+    total_bets: u64,
+    total_payouts: u64,
+    max_rate: u16,
+    min_bets: u64,
+) -> bool {
+    total_bets >= min_bets && total_payouts as u128 * 100 > total_bets as u128 * max_rate as u128
+}
 
-pub fn get_symbol_id(random_byte: u8) -> u8 {
-    let idx = (random_byte % 32) as usize;
-    REEL_SYMBOLS[idx]
+/// Calculate the tip paid to the caller of a permissionless maintenance crank
+///
+/// # Arguments
+/// * `tip_per_task` - Tip for each unit of work the crank completed
+/// * `tasks` - Units of work the crank completed
+/// * `maintenance_pool_balance` - Balance of the maintenance pool funding the tips
+///
+/// # Returns
+/// * `u64` - The tip, capped at the maintenance pool balance
+pub fn calculate_crank_tip(tip_per_task: u64, tasks: u32, maintenance_pool_balance: u64) -> u64 {
+    tip_per_task
+        .saturating_mul(tasks as u64)
+        .min(maintenance_pool_balance)
+}
+
+/// Check whether the bot missed its heartbeat for longer than the timeout
+///
+/// # Arguments
+/// * `last_heartbeat` - Timestamp of the bot's last heartbeat, `0` if it never sent one
+/// * `timeout_seconds` - How long the bot may go without a heartbeat
+/// * `current_time` - Current timestamp
+///
+/// # Returns
+/// * `bool` - Whether the heartbeat is stale
+pub fn is_bot_heartbeat_stale(
+    last_heartbeat: u64,
+    timeout_seconds: u64,
+    current_time: u64,
+) -> bool {
+    current_time.saturating_sub(last_heartbeat) > timeout_seconds
+}
+
+/// Check whether a randomness seed can still be committed to
+///
+/// A seed stays committable from the slot after it was taken until `slot_window` slots later, so a draw
+/// landing a few slots late can be retried instead of being rejected.
+///
+/// # Arguments
+/// * `seed_slot` - Slot the randomness seed was taken in
+/// * `current_slot` - Current slot
+/// * `slot_window` - Number of slots after the seed slot the seed stays committable
+///
+/// # Returns
+/// * `bool` - Whether the current slot is within the window
+pub fn is_randomness_seed_in_window(seed_slot: u64, current_slot: u64, slot_window: u64) -> bool {
+    seed_slot < current_slot && current_slot - seed_slot <= slot_window
+}
+
+/// Calculate the daily airdrop rewards of a purchase streak
+///
+/// # Arguments
+/// * `consecutive_purchased_days` - Consecutive days the player purchased ORE, including today
+///
+/// # Returns
+/// * `u64` - 100 tokens per day of the streak for the first five days, 1,000 tokens otherwise
+pub fn calculate_airdrop_rewards(consecutive_purchased_days: u16) -> u64 {
+    match consecutive_purchased_days {
+        1..=5 => consecutive_purchased_days as u64 * 100 * LAMPORTS_PER_TOKEN,
+        _ => 1000 * LAMPORTS_PER_TOKEN,
+    }
+}
+
+/// Calculate the registration rewards of the next new player on a decaying schedule
+///
+/// The rewards start at `initial_rewards` and decrease by `decay_amount` every time another `decay_interval`
+/// of registration rewards has been distributed, down to `floor_rewards`.
+///
+/// # Arguments
+/// * `initial_rewards` - Registration rewards of the first players
+/// * `distributed_rewards` - Registration rewards distributed so far
+/// * `decay_interval` - Registration rewards distributed between two decreases, zero for fixed rewards
+/// * `decay_amount` - Decrease of the registration rewards at every interval
+/// * `floor_rewards` - Registration rewards the schedule never decays below
+///
+/// # Returns
+/// * `MathResult<u64>` - The registration rewards of the next new player
+pub fn calculate_registration_rewards(
+    initial_rewards: u64,
+    distributed_rewards: u64,
+    decay_interval: u64,
+    decay_amount: u64,
+    floor_rewards: u64,
+) -> MathResult<u64> {
+    if decay_interval == 0 {
+        return Ok(initial_rewards);
+    }
+
+    let decay = (distributed_rewards / decay_interval).saturating_mul(decay_amount);
+    Ok(initial_rewards.saturating_sub(decay).max(floor_rewards))
+}
+
+/// Calculate the part of a purchase paid with a voucher kind worth `value_rate` percent of its face value
+///
+/// The player burns as few vouchers as cover the cost, at most their balance. The value credited never
+/// exceeds the cost, and rounds down so that the vault backing the kind stays solvent.
+///
+/// # Arguments
+/// * `cost` - Cost left to pay, in token lamports
+/// * `voucher_balance` - Vouchers of the kind held by the player
+/// * `value_rate` - Value of one voucher of the kind, as a percentage of a token
+///
+/// # Returns
+/// * `MathResult<(u64, u64)>` - The vouchers burned and the tokens they are worth
+pub fn calculate_voucher_kind_payment(
+    cost: u64,
+    voucher_balance: u64,
+    value_rate: u16,
+) -> MathResult<(u64, u64)> {
+    if value_rate == 0 || cost == 0 {
+        return Ok((0, 0));
+    }
+
+    // Vouchers needed to cover the whole cost, rounded up
+    let needed_vouchers = (cost as u128 * BASIS_POINTS_DENOMINATOR as u128)
+        .div_ceil(value_rate as u128)
+        .min(u64::MAX as u128) as u64;
+    let burned_vouchers = needed_vouchers.min(voucher_balance);
+    let value = calculate_voucher_kind_value(burned_vouchers, value_rate)?.min(cost);
+
+    Ok((burned_vouchers, value))
+}
+
+/// Calculate the tokens backing vouchers of a kind worth `value_rate` percent of their face value
+///
+/// # Arguments
+/// * `amount` - Vouchers of the kind
+/// * `value_rate` - Value of one voucher of the kind, as a percentage of a token
+///
+/// # Returns
+/// * `MathResult<u64>` - The tokens the vouchers are worth, rounded down
+pub fn calculate_voucher_kind_value(amount: u64, value_rate: u16) -> MathResult<u64> {
+    u64::try_from(amount as u128 * value_rate as u128 / BASIS_POINTS_DENOMINATOR as u128)
+        .map_err(|_| MathError)
+}
+
+/// Calculate the payout of a correct round winner prediction, its stake's share of the prediction pool
+///
+/// # Arguments
+/// * `amount` - Vouchers staked on the prediction
+/// * `total_staked` - Vouchers staked on all predictions of the round
+/// * `winning_staked` - Vouchers staked on the round's winner
+///
+/// # Returns
+/// * `MathResult<u64>` - The vouchers paid out, rounded down
+pub fn calculate_prediction_payout(
+    amount: u64,
+    total_staked: u64,
+    winning_staked: u64,
+) -> MathResult<u64> {
+    let payout = (amount as u128 * total_staked as u128)
+        .checked_div(winning_staked as u128)
+        .ok_or(MathError)?;
+    u64::try_from(payout).map_err(|_| MathError)
+}
+
+/// Calculate the amount of a linearly streamed prize vested after `elapsed` of its `duration`
+///
+/// # Arguments
+/// * `total_amount` - The prize streamed
+/// * `elapsed` - Time since the stream started
+/// * `duration` - Time over which the whole prize vests
+///
+/// # Returns
+/// * `MathResult<u64>` - The amount vested, rounded down, the whole prize once `duration` has elapsed
+pub fn calculate_vested_amount(total_amount: u64, elapsed: u64, duration: u64) -> MathResult<u64> {
+    if elapsed >= duration {
+        return Ok(total_amount);
+    }
+
+    // elapsed < duration, so the vested amount is below `total_amount`
+    Ok((total_amount as u128 * elapsed as u128 / duration as u128) as u64)
+}
+
+/// Calculate the boost factor of a staker's vote-escrowed balance, veToken style
+///
+/// Each stake weighs its amount times its remaining lock over `max_lock_duration`, so the vote-escrowed balance
+/// is `(lock_end_weight - staked_amount * timestamp) / max_lock_duration`, where `lock_end_weight` sums
+/// each stake's amount times its lock end.
+///
+/// # Arguments
+/// * `staked_amount` - Total amount staked
+/// * `lock_end_weight` - Sum of each stake's amount times its lock end timestamp
+/// * `timestamp` - Current UNIX timestamp
+/// * `max_lock_duration` - Lock duration granting a stake its full amount as vote-escrowed balance
+/// * `full_boost_balance` - Vote-escrowed balance granting the highest boost
+/// * `max_boost_bps` - Highest boost factor, in basis points
+///
+/// # Returns
+/// * `MathResult<u16>` - The boost factor in basis points, growing linearly with the vote-escrowed balance up to `max_boost_bps`
+pub fn calculate_stake_boost(
+    staked_amount: u64,
+    lock_end_weight: u128,
+    timestamp: u64,
+    max_lock_duration: u64,
+    full_boost_balance: u64,
+    max_boost_bps: u16,
+) -> MathResult<u16> {
+    // Locks that already ended weigh nothing
+    let escrowed_weight = lock_end_weight.saturating_sub(staked_amount as u128 * timestamp as u128);
+    let escrowed_balance = escrowed_weight
+        .checked_div(max_lock_duration as u128)
+        .ok_or(MathError)?;
+
+    let boost_bps = escrowed_balance
+        .checked_mul(max_boost_bps as u128)
+        .ok_or(MathError)?
+        .checked_div(full_boost_balance as u128)
+        .ok_or(MathError)?;
+    Ok(boost_bps.min(max_boost_bps as u128) as u16)
+}
+
+/// Calculate the bonus a boost factor adds to an amount
+///
+/// # Arguments
+/// * `amount` - The amount boosted
+/// * `boost_bps` - Boost factor, in basis points
+///
+/// # Returns
+/// * `MathResult<u64>` - The bonus, rounded down
+pub fn calculate_boost(amount: u64, boost_bps: u16) -> MathResult<u64> {
+    u64::try_from(amount as u128 * boost_bps as u128 / BOOST_BASIS_POINTS as u128)
+        .map_err(|_| MathError)
 }
 
 #[cfg(test)]
@@ -100,13 +624,13 @@ mod tests {
 
     #[test]
     fn test_calculate_prorated_interest() {
-        // Test case: 1000 tokens, 30 days, 100% APR
+        // Test case: 1000 tokens, a twelfth of a year, 100% APR
         let principal = 1000;
-        let duration = 30 * 24 * 60 * 60; // 30 days in seconds
+        let duration = SECONDS_PER_YEAR / 12; // about 30 days in seconds
         let rate = 100; // 100% in basis points
 
         let interest = calculate_prorated_interest(principal, duration, rate).unwrap();
-        assert_eq!(interest, 82); // Approximately 8.2% for 30 days
+        assert_eq!(interest, 83); // Approximately 8.3% for a twelfth of a year
     }
 
     #[test]
@@ -118,4 +642,508 @@ mod tests {
         let amount = calculate_proportion(total, proportion).unwrap();
         assert_eq!(amount, 250);
     }
+
+    #[test]
+    fn test_calculate_curve_rate() {
+        // Test case: 80% target utilization, 20%..100% APR
+        assert_eq!(calculate_utilization(0, 0).unwrap(), 0);
+        assert_eq!(calculate_utilization(900, 100).unwrap(), 90);
+
+        assert_eq!(calculate_curve_rate(50, 80, 20, 100).unwrap(), 100);
+        assert_eq!(calculate_curve_rate(90, 80, 20, 100).unwrap(), 60);
+        assert_eq!(calculate_curve_rate(100, 80, 20, 100).unwrap(), 20);
+    }
+
+    #[test]
+    fn test_calculate_oracle_rate() {
+        // Test case: price 1.25 with 18 decimals, 5% confidence limit
+        let price = 1_250_000_000_000_000_000;
+        assert_eq!(calculate_oracle_rate(price, price / 100, 18, 5), Some(125));
+        assert_eq!(calculate_oracle_rate(price, price / 10, 18, 5), None);
+        assert_eq!(calculate_oracle_rate(0, 0, 18, 5), None);
+        assert_eq!(calculate_oracle_rate(-price, 0, 18, 5), None);
+    }
+
+    #[test]
+    fn test_calculate_rank_rewards() {
+        // Test case: 50/30/20 split with a rounding remainder
+        let payout_curve = [50, 30, 20];
+        assert_eq!(
+            calculate_rank_rewards(1001, &payout_curve).unwrap(),
+            Some([501, 300, 200])
+        );
+        assert_eq!(calculate_rank_rewards(1000, &[50, 30, 30]).unwrap(), None);
+        assert_eq!(calculate_rank_rewards(1000, &[200, 200, 0]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_calculate_prize_split() {
+        // Test case: 1000 tokens, half of them shared by 5 winners
+        assert_eq!(calculate_prize_split(1000, 5).unwrap(), (600, 100));
+        assert_eq!(calculate_prize_split(u64::MAX, 1).unwrap().0, u64::MAX - 1);
+        assert_eq!(calculate_prize_split(1000, 0), Err(MathError));
+    }
+
+    #[test]
+    fn test_calculate_earnings_per_ore_increment() {
+        assert_eq!(
+            calculate_earnings_per_ore_increment(250, 10).unwrap(),
+            25 * EARNINGS_PER_ORE_PRECISION
+        );
+        assert_eq!(
+            calculate_earnings_per_ore_increment(u64::MAX, 1).unwrap(),
+            u64::MAX as u128 * EARNINGS_PER_ORE_PRECISION
+        );
+        assert_eq!(calculate_earnings_per_ore_increment(250, 0), Err(MathError));
+
+        // Rewards smaller than the ORE held still raise the earnings per ORE
+        let increment = calculate_earnings_per_ore_increment(1, 3).unwrap();
+        assert!(increment > 0);
+        assert_eq!(calculate_accrued_earnings(increment, 3).unwrap(), 0);
+        assert_eq!(calculate_accrued_earnings(increment * 3, 3).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_calculate_accrued_earnings() {
+        assert_eq!(
+            calculate_accrued_earnings(25 * EARNINGS_PER_ORE_PRECISION, 10).unwrap(),
+            250
+        );
+        assert_eq!(
+            calculate_accrued_earnings(u64::MAX as u128 * EARNINGS_PER_ORE_PRECISION, 1).unwrap(),
+            u64::MAX
+        );
+        assert_eq!(
+            calculate_accrued_earnings(u64::MAX as u128 * EARNINGS_PER_ORE_PRECISION, 2),
+            Err(MathError)
+        );
+        assert_eq!(
+            calculate_accrued_earnings(u128::MAX, u32::MAX),
+            Err(MathError)
+        );
+    }
+
+    #[test]
+    fn test_calculate_sugar_rush_multiplier() {
+        // Test case: 1 minute delay, +10% per idle minute, capped at 3x
+        assert_eq!(calculate_sugar_rush_multiplier(0, 60, 10, 300), 100);
+        assert_eq!(calculate_sugar_rush_multiplier(119, 60, 10, 300), 100);
+        assert_eq!(calculate_sugar_rush_multiplier(120, 60, 10, 300), 110);
+        assert_eq!(calculate_sugar_rush_multiplier(660, 60, 10, 300), 200);
+        assert_eq!(calculate_sugar_rush_multiplier(u64::MAX, 60, 10, 300), 300);
+        assert_eq!(
+            calculate_sugar_rush_multiplier(u64::MAX, 0, u16::MAX, u16::MAX),
+            u16::MAX
+        );
+    }
+
+    #[test]
+    fn test_calculate_sugar_rush_rewards() {
+        // Test case: 10 tokens per second for 1 minute at 1x and 1.5x
+        assert_eq!(calculate_sugar_rush_rewards(10, 60, 100).unwrap(), 600);
+        assert_eq!(calculate_sugar_rush_rewards(10, 60, 150).unwrap(), 900);
+        assert_eq!(calculate_sugar_rush_rewards(10, 0, 300).unwrap(), 0);
+        assert_eq!(
+            calculate_sugar_rush_rewards(u64::MAX, 1, 100).unwrap(),
+            u64::MAX
+        );
+        assert_eq!(
+            calculate_sugar_rush_rewards(u64::MAX, 1, 101),
+            Err(MathError)
+        );
+    }
+
+    #[test]
+    fn test_calculate_proportion_overflow() {
+        // Proportions up to 100% never overflow, larger ones overflow for the largest amounts
+        for proportion in 0..=u8::MAX {
+            let result = calculate_proportion(u64::MAX, proportion);
+            if proportion <= BASIS_POINTS_DENOMINATOR {
+                assert!(result.is_ok());
+            } else {
+                assert_eq!(result, Err(MathError));
+            }
+            assert!(calculate_proportion(u32::MAX as u64, proportion).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_calculate_prorated_interest_overflow() {
+        // Only interest exceeding a u64 overflows, whatever the principal, rate or duration
+        for annual_rate in 0..=BASIS_POINTS_DENOMINATOR {
+            assert!(calculate_prorated_interest(u64::MAX, SECONDS_PER_YEAR, annual_rate).is_ok());
+        }
+        assert!(calculate_prorated_interest(u64::MAX / 3, SECONDS_PER_YEAR, u8::MAX).is_ok());
+        assert_eq!(
+            calculate_prorated_interest(u64::MAX, SECONDS_PER_YEAR, u8::MAX),
+            Err(MathError)
+        );
+        assert_eq!(
+            calculate_prorated_interest(u64::MAX, u64::MAX, 1),
+            Err(MathError)
+        );
+        assert_eq!(calculate_prorated_interest(u64::MAX, 0, u8::MAX), Ok(0));
+    }
+
+    #[test]
+    fn test_calculate_curve_rate_exhaustive() {
+        // The rate always stays within [min_rate, max_rate]
+        for utilization in 0..=u8::MAX {
+            for target_utilization in 0..=u8::MAX {
+                let rate = calculate_curve_rate(utilization, target_utilization, 20, 100).unwrap();
+                assert!((20..=100).contains(&rate));
+            }
+        }
+        assert_eq!(calculate_curve_rate(90, 80, 100, 20), Err(MathError));
+    }
+
+    #[test]
+    fn test_calculate_rank_rewards_overflow() {
+        // The shares of any rewards add back up to the rewards
+        let payout_curve = [25, 20, 15, 10, 10, 5, 5, 5, 3, 2];
+        for rewards in [0, 1, 99, u32::MAX as u64, u64::MAX - 1, u64::MAX] {
+            let rank_rewards = calculate_rank_rewards(rewards, &payout_curve)
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                rank_rewards.iter().map(|&r| r as u128).sum::<u128>(),
+                rewards as u128
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_randomness_seed_in_window() {
+        // Retries landing anywhere within the window succeed, whatever slot they land in
+        let seed_slot = 1_000;
+        for slot_window in 1..=10 {
+            for current_slot in 0..seed_slot + 20 {
+                let expected = current_slot > seed_slot && current_slot <= seed_slot + slot_window;
+                assert_eq!(
+                    is_randomness_seed_in_window(seed_slot, current_slot, slot_window),
+                    expected
+                );
+            }
+        }
+
+        // A window of one slot only accepts the slot right after the seed
+        assert!(is_randomness_seed_in_window(seed_slot, seed_slot + 1, 1));
+        assert!(!is_randomness_seed_in_window(seed_slot, seed_slot + 2, 1));
+        assert!(!is_randomness_seed_in_window(u64::MAX, u64::MAX, u64::MAX));
+    }
+
+    #[test]
+    fn test_calculate_spin_multiplier() {
+        let triple = [1000, 100, 50, 20, 0, 0, 0, 0];
+        let pair = [0, 6, 6, 3, 0, 0, 0, 0];
+        let single = [0, 3, 0, 0, 0, 0, 0, 0];
+        let multiplier = |symbols| calculate_spin_multiplier(symbols, &triple, &pair, &single);
+
+        // Three of a kind
+        assert_eq!(multiplier([0, 0, 0]), 1000);
+        assert_eq!(multiplier([1, 1, 1]), 100);
+        assert_eq!(multiplier([3, 3, 3]), 20);
+        assert_eq!(multiplier([4, 4, 4]), 0);
+
+        // Pairs and singles, the first paying symbol in table order deciding
+        assert_eq!(multiplier([1, 4, 1]), 6);
+        assert_eq!(multiplier([4, 1, 4]), 3);
+        assert_eq!(multiplier([2, 1, 2]), 3);
+        assert_eq!(multiplier([3, 2, 3]), 3);
+        assert_eq!(multiplier([0, 0, 4]), 0);
+        assert_eq!(multiplier([0, 2, 3]), 0);
+
+        // Symbols without multipliers never pay
+        assert_eq!(multiplier([9, 9, 9]), 0);
+        assert_eq!(multiplier([9, 1, 9]), 3);
+    }
+
+    #[test]
+    fn test_get_reel_stop() {
+        // Every reel stop is landed on by the same number of random bytes
+        let mut landings = [0u32; 32];
+        for random_byte in 0..=u8::MAX {
+            landings[get_reel_stop(random_byte, 32) as usize] += 1;
+        }
+        assert!(landings.iter().all(|&count| count == 8));
+    }
+
+    #[test]
+    fn test_calculate_return_to_player_rate() {
+        assert_eq!(calculate_return_to_player_rate(0, 0), 0);
+        assert_eq!(calculate_return_to_player_rate(0, 1_000), 0);
+        assert_eq!(calculate_return_to_player_rate(1_000, 943), 94);
+        assert_eq!(calculate_return_to_player_rate(1_000, 2_500), 250);
+        assert_eq!(calculate_return_to_player_rate(1, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_is_return_to_player_exceeded() {
+        // Not enforced before the minimum bets, however much was paid out
+        assert!(!is_return_to_player_exceeded(999, u64::MAX, 120, 1_000));
+
+        // Enforced strictly above the ceiling
+        assert!(!is_return_to_player_exceeded(1_000, 1_200, 120, 1_000));
+        assert!(is_return_to_player_exceeded(1_000, 1_201, 120, 1_000));
+        assert!(is_return_to_player_exceeded(0, 1, 120, 0));
+        assert!(!is_return_to_player_exceeded(u64::MAX, u64::MAX, 100, 0));
+        assert!(is_return_to_player_exceeded(u64::MAX, u64::MAX, 99, 0));
+    }
+
+    #[test]
+    fn test_calculate_crank_tip() {
+        // One tip per task
+        assert_eq!(calculate_crank_tip(10, 1, 1_000), 10);
+        assert_eq!(calculate_crank_tip(10, 7, 1_000), 70);
+        assert_eq!(calculate_crank_tip(10, 0, 1_000), 0);
+
+        // Capped at the pool balance, without overflowing
+        assert_eq!(calculate_crank_tip(10, 7, 65), 65);
+        assert_eq!(calculate_crank_tip(10, 1, 0), 0);
+        assert_eq!(calculate_crank_tip(u64::MAX, u32::MAX, 1_000), 1_000);
+    }
+
+    #[test]
+    fn test_is_bot_heartbeat_stale() {
+        // Stale strictly after the timeout
+        assert!(!is_bot_heartbeat_stale(1_000, 60, 1_060));
+        assert!(is_bot_heartbeat_stale(1_000, 60, 1_061));
+
+        // A heartbeat ahead of the clock is never stale
+        assert!(!is_bot_heartbeat_stale(2_000, 60, 1_000));
+
+        // A bot that never sent a heartbeat is stale once the timeout elapsed since the epoch
+        assert!(is_bot_heartbeat_stale(0, 60, 1_000));
+    }
+
+    #[test]
+    fn test_calculate_voucher_kind_payment() {
+        // A standard credit covers the cost one for one
+        assert_eq!(
+            calculate_voucher_kind_payment(1_000, 5_000, 100),
+            Ok((1_000, 1_000))
+        );
+
+        // A premium credit worth 125% burns fewer vouchers, rounding up to cover the cost
+        assert_eq!(
+            calculate_voucher_kind_payment(1_000, 5_000, 125),
+            Ok((800, 1_000))
+        );
+        assert_eq!(
+            calculate_voucher_kind_payment(1_001, 5_000, 125),
+            Ok((801, 1_001))
+        );
+
+        // A discounted credit worth 80% burns more vouchers
+        assert_eq!(
+            calculate_voucher_kind_payment(1_000, 5_000, 80),
+            Ok((1_250, 1_000))
+        );
+
+        // A short balance is burned whole, covering part of the cost
+        assert_eq!(
+            calculate_voucher_kind_payment(1_000, 400, 125),
+            Ok((400, 500))
+        );
+
+        // A worthless kind or nothing to pay burns nothing
+        assert_eq!(calculate_voucher_kind_payment(1_000, 400, 0), Ok((0, 0)));
+        assert_eq!(calculate_voucher_kind_payment(0, 400, 125), Ok((0, 0)));
+
+        // The value never exceeds the backing of the burned vouchers
+        for cost in 1..500u64 {
+            let (burned, value) = calculate_voucher_kind_payment(cost, 10_000, 137).unwrap();
+            assert!(value <= cost);
+            assert!(value <= calculate_voucher_kind_value(burned, 137).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_calculate_airdrop_rewards() {
+        // 100 tokens per day of the streak, up to five days
+        assert_eq!(calculate_airdrop_rewards(1), 100 * LAMPORTS_PER_TOKEN);
+        assert_eq!(calculate_airdrop_rewards(5), 500 * LAMPORTS_PER_TOKEN);
+
+        // 1,000 tokens from the sixth day on
+        assert_eq!(calculate_airdrop_rewards(6), 1000 * LAMPORTS_PER_TOKEN);
+        assert_eq!(
+            calculate_airdrop_rewards(u16::MAX),
+            1000 * LAMPORTS_PER_TOKEN
+        );
+    }
+
+    #[test]
+    fn test_calculate_registration_rewards() {
+        let initial = 1_500 * LAMPORTS_PER_TOKEN;
+        let interval = 1_000_000 * LAMPORTS_PER_TOKEN;
+        let decay = 100 * LAMPORTS_PER_TOKEN;
+        let floor = 500 * LAMPORTS_PER_TOKEN;
+
+        // Fixed rewards without a decay interval
+        assert_eq!(
+            calculate_registration_rewards(initial, u64::MAX, 0, decay, floor).unwrap(),
+            initial
+        );
+
+        // Initial rewards until the first interval has been distributed
+        assert_eq!(
+            calculate_registration_rewards(initial, interval - 1, interval, decay, floor).unwrap(),
+            initial
+        );
+
+        // One decrease per interval
+        assert_eq!(
+            calculate_registration_rewards(initial, interval, interval, decay, floor).unwrap(),
+            1_400 * LAMPORTS_PER_TOKEN
+        );
+        assert_eq!(
+            calculate_registration_rewards(initial, 7 * interval, interval, decay, floor).unwrap(),
+            800 * LAMPORTS_PER_TOKEN
+        );
+
+        // Never below the floor, however much was distributed
+        assert_eq!(
+            calculate_registration_rewards(initial, 10 * interval, interval, decay, floor).unwrap(),
+            floor
+        );
+        assert_eq!(
+            calculate_registration_rewards(initial, u64::MAX, 1, u64::MAX, floor).unwrap(),
+            floor
+        );
+    }
+
+    #[test]
+    fn test_is_limit_loosened() {
+        // Unlimited can only be tightened
+        assert!(!is_limit_loosened(0, 0));
+        assert!(!is_limit_loosened(0, 1));
+
+        assert!(is_limit_loosened(10, 0));
+        assert!(is_limit_loosened(10, 11));
+        assert!(!is_limit_loosened(10, 10));
+        assert!(!is_limit_loosened(10, 1));
+    }
+
+    #[test]
+    fn test_calculate_prediction_payout() {
+        // Test case: 300 of a 1000 pool staked on the winner, split 200 and 100
+        assert_eq!(calculate_prediction_payout(200, 1000, 300).unwrap(), 666);
+        assert_eq!(calculate_prediction_payout(100, 1000, 300).unwrap(), 333);
+        assert_eq!(calculate_prediction_payout(300, 300, 300).unwrap(), 300);
+        assert_eq!(
+            calculate_prediction_payout(u64::MAX, u64::MAX, u64::MAX).unwrap(),
+            u64::MAX
+        );
+        assert_eq!(calculate_prediction_payout(100, 1000, 0), Err(MathError));
+    }
+
+    #[test]
+    fn test_calculate_vested_amount() {
+        // Test case: 1000 tokens streamed over 10 days
+        let duration = 10 * 86_400;
+        assert_eq!(calculate_vested_amount(1000, 0, duration).unwrap(), 0);
+        assert_eq!(
+            calculate_vested_amount(1000, 86_400, duration).unwrap(),
+            100
+        );
+        assert_eq!(
+            calculate_vested_amount(1000, duration - 1, duration).unwrap(),
+            999
+        );
+        assert_eq!(
+            calculate_vested_amount(1000, duration, duration).unwrap(),
+            1000
+        );
+        assert_eq!(
+            calculate_vested_amount(1000, u64::MAX, duration).unwrap(),
+            1000
+        );
+        assert_eq!(
+            calculate_vested_amount(u64::MAX, duration / 2, duration).unwrap(),
+            u64::MAX / 2
+        );
+        assert_eq!(calculate_vested_amount(1000, 0, 0).unwrap(), 1000);
+    }
+
+    #[test]
+    fn test_calculate_sugar_rush_emission_rate() {
+        // Test case: 1,000,000 tokens emitted over 10 days, between 1 and 10 tokens per second
+        let horizon = 10 * 86_400;
+        let min_rate = LAMPORTS_PER_TOKEN;
+        let max_rate = 10 * LAMPORTS_PER_TOKEN;
+        assert_eq!(
+            calculate_sugar_rush_emission_rate(
+                1_000_000 * LAMPORTS_PER_TOKEN,
+                horizon,
+                min_rate,
+                max_rate
+            )
+            .unwrap(),
+            1_000_000 * LAMPORTS_PER_TOKEN / horizon
+        );
+        // A full pool is capped at the highest rate, a drained one floored at the lowest
+        assert_eq!(
+            calculate_sugar_rush_emission_rate(u64::MAX, horizon, min_rate, max_rate).unwrap(),
+            max_rate
+        );
+        assert_eq!(
+            calculate_sugar_rush_emission_rate(0, horizon, min_rate, max_rate).unwrap(),
+            min_rate
+        );
+        assert_eq!(
+            calculate_sugar_rush_emission_rate(1000, 0, min_rate, max_rate),
+            Err(MathError)
+        );
+    }
+
+    #[test]
+    fn test_calculate_stake_boost() {
+        // Test case: 25% highest boost, reached by 100,000 tokens locked for a year
+        let year = SECONDS_PER_YEAR;
+        let full = 100_000 * LAMPORTS_PER_TOKEN;
+        let now = 1_700_000_000;
+        let weight = |amount: u64, lock_end: u64| amount as u128 * lock_end as u128;
+
+        // A full year lock of the full balance grants the highest boost, half of it half the boost
+        assert_eq!(
+            calculate_stake_boost(full, weight(full, now + year), now, year, full, 2_500).unwrap(),
+            2_500
+        );
+        assert_eq!(
+            calculate_stake_boost(full, weight(full, now + year / 2), now, year, full, 2_500)
+                .unwrap(),
+            1_250
+        );
+        // The boost is capped, and an ended lock grants none
+        assert_eq!(
+            calculate_stake_boost(
+                4 * full,
+                weight(4 * full, now + year),
+                now,
+                year,
+                full,
+                2_500
+            )
+            .unwrap(),
+            2_500
+        );
+        assert_eq!(
+            calculate_stake_boost(full, weight(full, now), now + 1, year, full, 2_500).unwrap(),
+            0
+        );
+        // Stakes add up
+        let lock_end_weight = weight(full / 2, now + year) + weight(full / 2, now + year / 2);
+        assert_eq!(
+            calculate_stake_boost(full, lock_end_weight, now, year, full, 2_500).unwrap(),
+            1_875
+        );
+        assert_eq!(
+            calculate_stake_boost(0, 0, now, year, 0, 2_500),
+            Err(MathError)
+        );
+
+        assert_eq!(calculate_boost(1_000, 2_500).unwrap(), 250);
+        assert_eq!(calculate_boost(1_000, 0).unwrap(), 0);
+        assert_eq!(calculate_boost(u64::MAX, 10_000).unwrap(), u64::MAX);
+    }
 }
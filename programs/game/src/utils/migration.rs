@@ -0,0 +1,146 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::{Discriminator, ZeroCopy};
+
+/// Resizes a program-owned account to `new_len`, funding any missing rent from `payer`.
+/// Used by the migrations that move legacy accounts to their current layout.
+///
+/// # Arguments
+/// - `account`: The account to resize.
+/// - `new_len`: The size of the account on its current layout.
+/// - `payer`: The signer paying the extra rent.
+/// - `system_program`: The system program, required to fund the account.
+pub fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    new_len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let missing_lamports = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if missing_lamports > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            missing_lamports,
+        )?;
+    }
+
+    account.realloc(new_len, false)?;
+    Ok(())
+}
+
+/// Writes `value` with its discriminator over the data of `account`, which must already have the value's size.
+pub fn write_account<T: AccountSerialize>(account: &AccountInfo, value: &T) -> Result<()> {
+    let mut data = account.try_borrow_mut_data()?;
+    value.try_serialize(&mut &mut data[..])
+}
+
+/// Reads `account` on the frozen legacy layout `L` if it still has the legacy size `legacy_len`.
+/// Accounts at least as large as the current layout `T`, including those grown past it, are already migrated.
+///
+/// # Arguments
+/// - `account`: The account to read, which must be a `T` owned by the program.
+/// - `program_id`: The program ID.
+/// - `legacy_len`: The size of the account on the legacy layout, discriminator included.
+///
+/// # Returns
+/// The legacy account, or `None` if it already is on the current layout. Fails on any other size.
+pub fn load_legacy_account<L, T>(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+    legacy_len: usize,
+) -> Result<Option<L>>
+where
+    L: AnchorDeserialize,
+    T: Discriminator + Space,
+{
+    read_legacy_account(
+        account,
+        program_id,
+        &T::DISCRIMINATOR,
+        8 + T::INIT_SPACE,
+        legacy_len,
+    )
+}
+
+/// Reads `account` on the frozen legacy layout `L` like `load_legacy_account`, for a current layout `T` that is zero-copy.
+pub fn load_legacy_zero_copy_account<L, T>(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+    legacy_len: usize,
+) -> Result<Option<L>>
+where
+    L: AnchorDeserialize,
+    T: ZeroCopy,
+{
+    read_legacy_account(
+        account,
+        program_id,
+        &T::DISCRIMINATOR,
+        8 + std::mem::size_of::<T>(),
+        legacy_len,
+    )
+}
+
+fn read_legacy_account<L: AnchorDeserialize>(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+    discriminator: &[u8],
+    current_len: usize,
+    legacy_len: usize,
+) -> Result<Option<L>> {
+    require_keys_eq!(
+        *account.owner,
+        *program_id,
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    let data = account.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == *discriminator,
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    match data.len() {
+        len if len >= current_len => Ok(None),
+        len if len == legacy_len => Ok(Some(L::deserialize(&mut &data[8..])?)),
+        _ => err!(ErrorCode::UnknownAccountLayout),
+    }
+}
+
+/// Grows `account` to the size of the current layout `T`, funded by `payer`, and writes `value` into it.
+pub fn store_migrated_account<'info, T>(
+    account: &AccountInfo<'info>,
+    value: &T,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()>
+where
+    T: AccountSerialize + Space,
+{
+    resize_account(account, 8 + T::INIT_SPACE, payer, system_program)?;
+    write_account(account, value)
+}
+
+/// Resizes `account` to the size of the current zero-copy layout `T`, funded by `payer`, and writes `value` into it.
+pub fn store_migrated_zero_copy_account<'info, T: ZeroCopy>(
+    account: &AccountInfo<'info>,
+    value: &T,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    resize_account(account, 8 + std::mem::size_of::<T>(), payer, system_program)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    data[..8].copy_from_slice(&T::DISCRIMINATOR);
+    data[8..].copy_from_slice(bytemuck::bytes_of(value));
+    Ok(())
+}
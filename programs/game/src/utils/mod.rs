@@ -1,6 +1,16 @@
+pub mod badge;
+pub mod distribution;
+pub mod invariants;
 pub mod math;
+pub mod migration;
+pub mod randomness;
 pub mod token;
 pub mod util;
+pub use badge::*;
+pub use distribution::*;
+pub use invariants::*;
 pub use math::*;
+pub use migration::*;
+pub use randomness::*;
 pub use token::*;
 pub use util::*;
@@ -0,0 +1,168 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+use switchboard_on_demand::accounts::RandomnessAccountData;
+
+/// The slots and value of the randomness a lottery draw commits to and reveals against.
+///
+/// # Fields
+/// - `seed_slot`: The slot the randomness was seeded at.
+/// - `reveal_slot`: The slot the randomness was revealed at, before `seed_slot` while unrevealed.
+/// - `value`: The revealed random value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Randomness {
+    pub seed_slot: u64,
+    pub reveal_slot: u64,
+    pub value: [u8; 32],
+}
+
+impl Randomness {
+    /// Reads the randomness held by `provider`, a Switchboard randomness account, or with the `mock-randomness`
+    /// feature a `MockRandomness` account owned by this program, so that the lottery runs without Switchboard in
+    /// local tests and CI.
+    ///
+    /// # Returns
+    /// Returns `None` if `provider` holds no randomness.
+    pub fn parse(provider: &AccountInfo) -> Option<Self> {
+        #[cfg(any(test, feature = "mock-randomness"))]
+        if provider.owner == &crate::ID {
+            if let Some(randomness) = MockRandomness::parse(&provider.data.borrow()) {
+                return Some(randomness);
+            }
+        }
+
+        let data = RandomnessAccountData::parse(provider.data.borrow()).ok()?;
+        Some(Randomness {
+            seed_slot: data.seed_slot,
+            reveal_slot: data.reveal_slot,
+            value: data.value,
+        })
+    }
+
+    /// Returns the revealed random value, only readable at the slot it was revealed at, as Switchboard's `get_value`.
+    pub fn value(&self, clock: &Clock) -> Result<[u8; 32]> {
+        require!(
+            clock.slot == self.reveal_slot,
+            ErrorCode::RandomnessNotResolved
+        );
+        Ok(self.value)
+    }
+}
+
+/// A randomness provider standing in for Switchboard, whose account data a test writes directly:
+/// the `MOCK_RANDOMNESS_DISCRIMINATOR`, then the seed slot, the reveal slot and the value.
+/// Only accepted by `draw_lottery` and `reveal_draw_lottery_result` with the `mock-randomness` feature, and only
+/// from accounts owned by this program, which a test loads directly. On-chain builds refuse the feature unless
+/// `localnet-defaults` is enabled too.
+#[cfg(any(test, feature = "mock-randomness"))]
+pub struct MockRandomness;
+
+/// Discriminator of the `MockRandomness` accounts.
+#[cfg(any(test, feature = "mock-randomness"))]
+pub const MOCK_RANDOMNESS_DISCRIMINATOR: [u8; 8] = *b"mockrand";
+
+#[cfg(any(test, feature = "mock-randomness"))]
+impl MockRandomness {
+    /// Serializes `randomness` into the data of a mock randomness account.
+    pub fn account_data(randomness: &Randomness) -> Vec<u8> {
+        let mut data = MOCK_RANDOMNESS_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&randomness.seed_slot.to_le_bytes());
+        data.extend_from_slice(&randomness.reveal_slot.to_le_bytes());
+        data.extend_from_slice(&randomness.value);
+        data
+    }
+
+    /// Reads the randomness of a mock randomness account, `None` for any other account.
+    pub fn parse(data: &[u8]) -> Option<Randomness> {
+        let data = data.strip_prefix(&MOCK_RANDOMNESS_DISCRIMINATOR)?;
+        if data.len() < 48 {
+            return None;
+        }
+        Some(Randomness {
+            seed_slot: u64::from_le_bytes(data[0..8].try_into().ok()?),
+            reveal_slot: u64::from_le_bytes(data[8..16].try_into().ok()?),
+            value: data[16..48].try_into().ok()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_randomness_round_trips() {
+        let randomness = Randomness {
+            seed_slot: 100,
+            reveal_slot: 105,
+            value: [7; 32],
+        };
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = MockRandomness::account_data(&randomness);
+        let provider = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+
+        assert_eq!(Randomness::parse(&provider), Some(randomness));
+
+        let mut clock = Clock {
+            slot: 105,
+            ..Default::default()
+        };
+        assert_eq!(randomness.value(&clock).unwrap(), [7; 32]);
+        clock.slot = 106;
+        assert!(randomness.value(&clock).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_accounts_without_randomness() {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![0; 64];
+        let provider = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        assert_eq!(Randomness::parse(&provider), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_mock_randomness_not_owned_by_the_program() {
+        let randomness = Randomness {
+            seed_slot: 100,
+            reveal_slot: 105,
+            value: [7; 32],
+        };
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = MockRandomness::account_data(&randomness);
+        let provider = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        assert_eq!(Randomness::parse(&provider), None);
+    }
+}
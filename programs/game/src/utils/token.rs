@@ -1,69 +1,282 @@
+use crate::errors::ErrorCode;
+use crate::state::SessionKey;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+};
+use anchor_spl::token_interface::{
+    freeze_account, thaw_account, transfer_checked, FreezeAccount, Mint, ThawAccount, TokenAccount,
+    TokenInterface, TransferChecked,
+};
+
+/// Rejects a Token-2022 mint carrying the transfer fee extension. Every transfer helper credits the pools with
+/// the amount sent, which a mint charging a fee would not deliver in full to the vaults.
+pub fn require_no_transfer_fee(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    require!(
+        mint_state.get_extension::<TransferFeeConfig>().is_err(),
+        ErrorCode::MintHasTransferFee
+    );
+
+    Ok(())
+}
 
 pub fn transfer_from_player_to_vault<'info>(
     authority: &Signer<'info>,
-    token_account: &Account<'info, TokenAccount>,
-    token_vault: &Account<'info, TokenAccount>,
-    token_program: &Program<'info, Token>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
     amount: u64,
 ) -> Result<()> {
-    transfer(
+    transfer_checked(
         CpiContext::new(
             token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: token_account.to_account_info(),
+                mint: mint.to_account_info(),
                 to: token_vault.to_account_info(),
                 authority: authority.to_account_info(),
             },
         ),
         amount,
+        mint.decimals,
     )
 }
 
-pub fn redeem_vouchers<'info, T: AccountSerialize + AccountDeserialize + Clone>(
+/// Transfers tokens from a player's token account to a vault, signed by the player or, when the player acts
+/// through a session key, by the session key as delegate of the token account.
+pub fn transfer_from_player_or_session_to_vault<'info>(
+    signer: &Signer<'info>,
+    session_key: Option<&Account<'info, SessionKey>>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    match session_key {
+        None => transfer_from_player_to_vault(
+            signer,
+            token_account,
+            token_vault,
+            mint,
+            token_program,
+            amount,
+        ),
+        Some(session_key) => transfer_from_vault(
+            session_key,
+            session_key.bump,
+            token_account,
+            token_vault,
+            mint,
+            token_program,
+            amount,
+        ),
+    }
+}
+
+/// A PDA account owning token vaults, which knows the seeds it signs vault transfers with.
+///
+/// Implemented by the accounts holding vaults, so that instructions moving tokens out of a vault pass the
+/// owning account and its bump rather than assembling its seeds by hand. Singleton PDAs such as `Game` do
+/// not store their bump, which is why it is passed in rather than read from the account.
+pub trait VaultAuthority {
+    /// Calls `f` with the signer seeds of the account's PDA, ending with `bump`.
+    fn with_signer_seeds<R>(&self, bump: u8, f: impl FnOnce(&[&[u8]]) -> R) -> R;
+}
+
+/// Transfers tokens out of a vault owned by a `VaultAuthority` account, signed with the account's seeds.
+pub fn transfer_from_vault<'info, T>(
     authority: &Account<'info, T>,
-    voucher_vault: &Account<'info, TokenAccount>,
-    token_account: &Account<'info, TokenAccount>,
-    token_program: &Program<'info, Token>,
+    bump: u8,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()>
+where
+    T: VaultAuthority + AccountSerialize + AccountDeserialize + Clone,
+{
+    authority.with_signer_seeds(bump, |seeds| {
+        transfer_from_token_vault_to_token_account(
+            authority,
+            token_vault,
+            token_account,
+            mint,
+            token_program,
+            amount,
+            seeds,
+        )
+    })
+}
+
+/// Transfers tokens out of a vault owned by a zero-copy `VaultAuthority` account, such as a `Period`.
+/// The seeds are read from `authority_data`, a copy of the account's data taken before the transfer,
+/// since the account cannot stay loaded across the CPI.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_from_zero_copy_vault<'info, T>(
+    authority: &AccountLoader<'info, T>,
+    authority_data: &T,
+    bump: u8,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()>
+where
+    T: VaultAuthority + anchor_lang::ZeroCopy + Owner,
+{
+    authority_data.with_signer_seeds(bump, |seeds| {
+        transfer_from_zero_copy_vault_to_token_account(
+            authority,
+            token_vault,
+            token_account,
+            mint,
+            token_program,
+            amount,
+            seeds,
+        )
+    })
+}
+
+/// Redeems vouchers for the tokens backing them, transferring the tokens out of the vault of a `VaultAuthority`.
+pub fn redeem_vouchers<'info, T>(
+    authority: &Account<'info, T>,
+    bump: u8,
+    voucher_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()>
+where
+    T: VaultAuthority + AccountSerialize + AccountDeserialize + Clone,
+{
+    transfer_from_vault(
+        authority,
+        bump,
+        voucher_vault,
+        token_account,
+        mint,
+        token_program,
+        amount,
+    )
+}
+
+/// Transfers tokens out of a vault signed with raw `seeds`. Prefer `transfer_from_vault`, which derives the
+/// seeds from the owning account; this remains for PDAs that cannot, such as stake orders seeded by an owner
+/// they do not store.
+pub fn transfer_from_token_vault_to_token_account<
+    'info,
+    T: AccountSerialize + AccountDeserialize + Clone,
+>(
+    authority: &Account<'info, T>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
     amount: u64,
     seeds: &[&[u8]],
 ) -> Result<()> {
-    transfer(
+    transfer_checked(
         CpiContext::new_with_signer(
             token_program.to_account_info(),
-            Transfer {
-                from: voucher_vault.to_account_info(),
+            TransferChecked {
+                from: token_vault.to_account_info(),
+                mint: mint.to_account_info(),
                 to: token_account.to_account_info(),
                 authority: authority.to_account_info(),
             },
             &[seeds],
         ),
         amount,
+        mint.decimals,
     )
 }
 
-pub fn transfer_from_token_vault_to_token_account<
-    'info,
-    T: AccountSerialize + AccountDeserialize + Clone,
->(
-    authority: &Account<'info, T>,
-    token_vault: &Account<'info, TokenAccount>,
-    token_account: &Account<'info, TokenAccount>,
-    token_program: &Program<'info, Token>,
+/// Transfers tokens out of a vault owned by a zero-copy PDA account signed with raw `seeds`.
+/// Prefer `transfer_from_zero_copy_vault`, which derives the seeds from the owning account.
+pub fn transfer_from_zero_copy_vault_to_token_account<'info, T: anchor_lang::ZeroCopy + Owner>(
+    authority: &AccountLoader<'info, T>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
     amount: u64,
     seeds: &[&[u8]],
 ) -> Result<()> {
-    transfer(
+    transfer_checked(
         CpiContext::new_with_signer(
             token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: token_vault.to_account_info(),
+                mint: mint.to_account_info(),
                 to: token_account.to_account_info(),
                 authority: authority.to_account_info(),
             },
             &[seeds],
         ),
         amount,
+        mint.decimals,
     )
 }
+
+/// Thaws a frozen voucher account so the game can mint, burn or transfer vouchers through it.
+/// Returns whether the account was frozen, to be handed to `refreeze_voucher_account` afterwards.
+pub fn thaw_voucher_account_if_frozen<'info, T: AccountSerialize + AccountDeserialize + Clone>(
+    authority: &Account<'info, T>,
+    voucher_mint: &InterfaceAccount<'info, Mint>,
+    voucher_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    seeds: &[&[u8]],
+) -> Result<bool> {
+    if !voucher_account.is_frozen() {
+        return Ok(false);
+    }
+
+    thaw_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        ThawAccount {
+            account: voucher_account.to_account_info(),
+            mint: voucher_mint.to_account_info(),
+            authority: authority.to_account_info(),
+        },
+        &[seeds],
+    ))?;
+
+    Ok(true)
+}
+
+/// Freezes a voucher account again if it was thawed by `thaw_voucher_account_if_frozen`.
+pub fn refreeze_voucher_account<'info, T: AccountSerialize + AccountDeserialize + Clone>(
+    authority: &Account<'info, T>,
+    voucher_mint: &InterfaceAccount<'info, Mint>,
+    voucher_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    was_frozen: bool,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    if !was_frozen {
+        return Ok(());
+    }
+
+    freeze_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        FreezeAccount {
+            account: voucher_account.to_account_info(),
+            mint: voucher_mint.to_account_info(),
+            authority: authority.to_account_info(),
+        },
+        &[seeds],
+    ))
+}
@@ -1,15 +1,171 @@
 use crate::constants::config::SECONDS_PER_DAY;
 use crate::errors::ErrorCode;
+use crate::state::SessionKey;
 use anchor_lang::prelude::*;
 use anchor_safe_math::SafeMath;
+use solana_program::ed25519_program;
+use solana_program::hash::hashv;
+use solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
 
 pub fn to_timestamp_u64(t: i64) -> Result<u64> {
     u64::try_from(t).or(Err(ErrorCode::InvalidTimestampConversion.into()))
 }
 
+/// Checks that a start time provided as input lies within the start time window relative to now,
+/// rather than trusting it as is.
+///
+/// # Arguments
+/// - `start_time`: The requested start time.
+/// - `timestamp`: The current UNIX timestamp.
+/// - `min_start_lead_seconds`: The minimum delay between now and `start_time`.
+/// - `max_future_start_seconds`: The maximum delay between now and `start_time`.
+pub fn validate_start_time(
+    start_time: u64,
+    timestamp: u64,
+    min_start_lead_seconds: u64,
+    max_future_start_seconds: u64,
+) -> Result<()> {
+    let earliest_start_time = timestamp.saturating_add(min_start_lead_seconds);
+    let latest_start_time = timestamp.saturating_add(max_future_start_seconds);
+    require!(
+        (earliest_start_time..=latest_start_time).contains(&start_time),
+        ErrorCode::StartTimeOutOfWindow
+    );
+    Ok(())
+}
+
+/// Checks that the transaction signer may act for the player: either the player signed themselves, or the
+/// player's session key authorizes the signer for the instruction's scope.
+///
+/// # Arguments
+/// - `player`: The player the instruction acts for.
+/// - `signer`: The key that signed the transaction.
+/// - `session_key`: The player's session key, if provided.
+/// - `scope`: The `SESSION_SCOPE_*` of the instruction.
+/// - `timestamp`: The current UNIX timestamp.
+///
+/// # Returns
+/// Returns whether the signer acts through the session key.
+pub fn authorize_player_signer(
+    player: Pubkey,
+    signer: Pubkey,
+    session_key: Option<&SessionKey>,
+    scope: u8,
+    timestamp: u64,
+) -> Result<bool> {
+    if signer == player {
+        return Ok(false);
+    }
+
+    let session_key = session_key.ok_or(ErrorCode::SessionSignerMismatch)?;
+    session_key.authorize(signer, scope, timestamp)?;
+    Ok(true)
+}
+
 pub fn timestamp_to_days(timestamp: u64) -> Result<u32> {
     timestamp
         .safe_div(SECONDS_PER_DAY)
         .or(Err(ErrorCode::InvalidTimestampConversion.into()))
         .map(|days| days as u32)
 }
+
+/// The size of the offsets of one signature in an ed25519 program instruction.
+const ED25519_SIGNATURE_OFFSETS_SIZE: usize = 14;
+
+/// Checks that the instruction preceding the current one in the transaction is an ed25519 program instruction
+/// verifying a single signature of `attestor` over `message`. The ed25519 program fails the transaction if the
+/// signature is invalid, so a matching instruction proves the attestor signed the message.
+///
+/// # Arguments
+/// - `instructions_sysvar`: The instructions sysvar account.
+/// - `attestor`: The key expected to have signed `message`.
+/// - `message`: The attested message.
+///
+/// # Returns
+/// Returns whether the preceding instruction verifies the attestation.
+pub fn is_ed25519_attested(
+    instructions_sysvar: &AccountInfo,
+    attestor: &Pubkey,
+    message: &[u8],
+) -> Result<bool> {
+    Ok(ed25519_attested_message(instructions_sysvar, attestor)?.as_deref() == Some(message))
+}
+
+/// Reads the message `attestor` signed in the ed25519 program instruction preceding the current one in the
+/// transaction, for attestations whose message carries data to check, such as when they were issued.
+///
+/// # Arguments
+/// - `instructions_sysvar`: The instructions sysvar account.
+/// - `attestor`: The key expected to have signed the message.
+///
+/// # Returns
+/// Returns the signed message, `None` if the preceding instruction does not verify a single signature of `attestor`.
+pub fn ed25519_attested_message(
+    instructions_sysvar: &AccountInfo,
+    attestor: &Pubkey,
+) -> Result<Option<Vec<u8>>> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    if current_index == 0 {
+        return Ok(None);
+    }
+    let instruction = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    if instruction.program_id != ed25519_program::ID {
+        return Ok(None);
+    }
+
+    // A single signature, whose offsets follow the signature count and a padding byte
+    let data = &instruction.data;
+    if data.len() < 2 + ED25519_SIGNATURE_OFFSETS_SIZE || data[0] != 1 {
+        return Ok(None);
+    }
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+
+    // The signature, public key and message must all be read from the ed25519 instruction itself
+    if [read_u16(4), read_u16(8), read_u16(14)]
+        .iter()
+        .any(|&instruction_index| instruction_index != u16::MAX)
+    {
+        return Ok(None);
+    }
+
+    let signed_public_key = data.get(public_key_offset..public_key_offset + 32);
+    let signed_message = data.get(message_offset..message_offset + message_size);
+
+    if signed_public_key != Some(attestor.as_ref()) {
+        return Ok(None);
+    }
+    Ok(signed_message.map(<[u8]>::to_vec))
+}
+
+/// Computes the merkle leaf of a `MerkleAirdrop` claim: the SHA-256 hash of `0x00 || index || player || amount`,
+/// integers in little-endian.
+pub fn merkle_airdrop_leaf(index: u32, player: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        &[0],
+        &index.to_le_bytes(),
+        player.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Checks that `leaf` belongs to the merkle tree of root `root`.
+///
+/// Each node is the SHA-256 hash of `0x01` followed by its two children in ascending byte order,
+/// so the proof only lists the sibling hashes from the leaf up to the root.
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed_root = proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling {
+            (&node, sibling)
+        } else {
+            (sibling, &node)
+        };
+        hashv(&[&[1], left, right]).to_bytes()
+    });
+    computed_root == *root
+}
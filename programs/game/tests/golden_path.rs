@@ -0,0 +1,280 @@
+//! Drives a full round lifecycle through the instruction handlers: initialize, create the round and the period,
+//! register and purchase, tap the candy machine, end the round, pay the grand prizes and the leaderboard, and
+//! settle the players, reconciling every pool and counter against the vaults along the way.
+
+use game::constants::{DEFAULT_PLAYER, LAMPORTS_PER_ORE, LAMPORTS_PER_TOKEN, SECONDS_PER_DAY};
+use game::state::{
+    Game, PayoutAsset, Period, PeriodConfig, PlayerData, Round, TeamScoringMode, Voucher,
+    PLAYER_WINNERS_COUNT, TEAM_WINNERS_COUNT,
+};
+use game::testing::harness::GameHarness;
+use game::utils::{assert_invariants, InvariantSnapshot};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const POOL_BALANCE: u64 = 1_000_000 * LAMPORTS_PER_TOKEN;
+const GRAND_PRIZES: u64 = 10_000 * LAMPORTS_PER_TOKEN;
+const TEAM_REWARDS: u64 = 5_000 * LAMPORTS_PER_TOKEN;
+const INDIVIDUAL_REWARDS: u64 = 5_000 * LAMPORTS_PER_TOKEN;
+const PLAYER_TOKENS: u64 = 100 * LAMPORTS_PER_ORE;
+const PLAYERS: usize = 4;
+
+/// The number of counted calls after which a round whose countdown ran out is over.
+const ROUND_END_CALLS: u8 = 10;
+
+/// Seconds between two round end calls, covering the 150 slots they must be apart.
+const ROUND_END_CALL_INTERVAL: u64 = 60;
+
+fn period_config() -> PeriodConfig {
+    PeriodConfig {
+        team_payout_curve: [10; TEAM_WINNERS_COUNT],
+        player_payout_curve: [10; PLAYER_WINNERS_COUNT],
+        ends_with_round: false,
+        team_scoring_mode: TeamScoringMode::TotalOres,
+        min_team_active_members: 0,
+        min_team_purchased_ores: 0,
+        team_payout_asset: PayoutAsset::Token,
+        player_payout_asset: PayoutAsset::Token,
+    }
+}
+
+/// Checks the game invariants since `before`, that every voucher is backed by the voucher vault,
+/// and that every game token in circulation is held by a player or a vault of the game.
+async fn reconcile(
+    harness: &mut GameHarness,
+    before: &InvariantSnapshot,
+    round: Pubkey,
+    period: Pubkey,
+    players: &[Keypair],
+) -> InvariantSnapshot {
+    let game: Game = harness.account(harness.addresses.game).await;
+    let round_data: Round = harness.account(round).await;
+    let game_vault_amount = harness.token_balance(harness.game_vault()).await;
+    let round_vault_amount = harness.token_balance(round_data.round_vault).await;
+    assert_invariants(
+        before,
+        &game,
+        &round_data,
+        game_vault_amount,
+        round_vault_amount,
+    )
+    .unwrap();
+
+    let period_data: Period = harness.zero_copy_account(period).await;
+    let period_vault_amount = harness.token_balance(period_data.period_vault).await;
+    assert_eq!(
+        period_vault_amount,
+        period_data.unclaimed_rewards().unwrap()
+    );
+
+    let voucher: Voucher = harness.account(harness.addresses.voucher).await;
+    let voucher_vault_amount = harness.token_balance(harness.voucher_vault()).await;
+    assert_eq!(voucher_vault_amount, voucher.total_supply);
+    assert_eq!(
+        harness.mint_supply(harness.addresses.voucher_mint).await,
+        voucher.total_supply
+    );
+
+    let mut held =
+        game_vault_amount + round_vault_amount + period_vault_amount + voucher_vault_amount;
+    for owner in [
+        harness.authority.pubkey(),
+        DEFAULT_PLAYER,
+        harness.addresses.default_team,
+    ] {
+        held += harness.token_balance(harness.token_account(&owner)).await;
+    }
+    for player in players {
+        held += harness
+            .token_balance(harness.token_account(&player.pubkey()))
+            .await;
+    }
+    assert_eq!(held, harness.mint_supply(harness.token_mint).await);
+
+    InvariantSnapshot::take(&game, &round_data)
+}
+
+#[tokio::test]
+async fn test_golden_path_round_lifecycle_reconciles() {
+    let mut harness = GameHarness::start(1_700_000_000).await;
+    harness.initialize(POOL_BALANCE).await;
+
+    // Create the round and the period, funded out of the game vault
+    let round = harness
+        .create_round(SECONDS_PER_DAY, GRAND_PRIZES)
+        .await
+        .unwrap();
+    let period = harness
+        .create_period(
+            SECONDS_PER_DAY,
+            TEAM_REWARDS,
+            INDIVIDUAL_REWARDS,
+            period_config(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(round, harness.addresses.round(1));
+    assert_eq!(period, harness.addresses.period(1));
+    assert_eq!(
+        harness.token_balance(harness.token_account(&round)).await,
+        GRAND_PRIZES
+    );
+
+    let game: Game = harness.account(harness.addresses.game).await;
+    let round_data: Round = harness.account(round).await;
+    let mut snapshot = InvariantSnapshot::take(&game, &round_data);
+
+    // Register the players, each receiving the registration rewards in vouchers
+    let mut players = Vec::new();
+    for _ in 0..PLAYERS {
+        let player = harness.create_player(PLAYER_TOKENS).await;
+        harness.register(&player, DEFAULT_PLAYER).await.unwrap();
+        players.push(player);
+    }
+    snapshot = reconcile(&mut harness, &snapshot, round, period, &players).await;
+    let game: Game = harness.account(harness.addresses.game).await;
+    assert!(game.distributed_registration_rewards > 0);
+
+    // Each player purchases a few times, the first ones bootstrapping the round
+    for purchase_quantity in 1..=3 {
+        for player in &players {
+            harness.advance(60).await;
+            harness.purchase(player, purchase_quantity).await.unwrap();
+            snapshot = reconcile(&mut harness, &snapshot, round, period, &players).await;
+        }
+    }
+    let round_data: Round = harness.account(round).await;
+    assert_eq!(round_data.sold_ores, PLAYERS as u32 * (1 + 2 + 3));
+    assert_eq!(round_data.available_ores, round_data.sold_ores);
+
+    // Candy taps move the sugar rush rewards into the pools, the round's share into the round vault
+    for _ in 0..3 {
+        let game: Game = harness.account(harness.addresses.game).await;
+        harness
+            .advance(
+                game.candy_tap_min_interval_seconds
+                    .max(game.candy_tap_cooldown_seconds)
+                    .max(1),
+            )
+            .await;
+        harness.candy_tap(&players[0]).await.unwrap();
+        snapshot = reconcile(&mut harness, &snapshot, round, period, &players).await;
+    }
+
+    // Once the countdown runs out, the round ends after enough spaced out calls
+    let round_data: Round = harness.account(round).await;
+    harness.advance_to(round_data.end_time + 1).await;
+    for _ in 0..ROUND_END_CALLS {
+        harness.purchase(&players[0], 0).await.unwrap();
+        harness.advance(ROUND_END_CALL_INTERVAL).await;
+    }
+    let round_data: Round = harness.account(round).await;
+    assert!(round_data.is_over);
+    snapshot = reconcile(&mut harness, &snapshot, round, period, &players).await;
+
+    // The grand prizes are paid down the last active participants,
+    // the default player's prizes going to the pool picked by the default winner policy, or burned
+    loop {
+        let round_data: Round = harness.account(round).await;
+        if round_data.is_grand_prize_distribution_completed {
+            break;
+        }
+        let game_before: Game = harness.account(harness.addresses.game).await;
+        let index = round_data.grand_prize_distribution_index as usize;
+        let winner = round_data.last_active_participant_list[index];
+        let winner_balance = harness.token_balance(harness.token_account(&winner)).await;
+
+        assert_eq!(
+            harness.distribute_grand_prizes(round).await.unwrap(),
+            winner
+        );
+
+        let round_after: Round = harness.account(round).await;
+        let grand_prizes =
+            round_data.grand_prize_pool_balance - round_after.grand_prize_pool_balance;
+        if winner == DEFAULT_PLAYER {
+            let mut expected = game_before.clone();
+            expected
+                .credit_default_winner_rewards(expected.default_winner_policy(), grand_prizes)
+                .unwrap();
+            let game: Game = harness.account(harness.addresses.game).await;
+            assert_eq!(
+                game.total_pool_balance().unwrap(),
+                expected.total_pool_balance().unwrap()
+            );
+        } else {
+            assert_eq!(
+                harness.token_balance(harness.token_account(&winner)).await,
+                winner_balance + grand_prizes
+            );
+        }
+        snapshot = reconcile(&mut harness, &snapshot, round, period, &players).await;
+    }
+    let round_data: Round = harness.account(round).await;
+    assert_eq!(
+        harness.token_balance(round_data.round_vault).await,
+        round_data.grand_prize_pool_balance
+    );
+
+    // Once the period is over, the ranked players claim their rewards
+    let period_data: Period = harness.zero_copy_account(period).await;
+    if harness.clock.now() < period_data.end_time {
+        harness.advance_to(period_data.end_time).await;
+    }
+    let mut claimed_ranks = 0;
+    for rank in 0..PLAYER_WINNERS_COUNT {
+        let holder = period_data.top_player_list[rank].player;
+        let Some(player) = players.iter().find(|player| player.pubkey() == holder) else {
+            continue;
+        };
+        let balance = harness.token_balance(harness.token_account(&holder)).await;
+        harness
+            .claim_leaderboard_reward(player, period, rank as u8)
+            .await
+            .unwrap();
+        assert_eq!(
+            harness.token_balance(harness.token_account(&holder)).await,
+            balance + period_data.player_rank_rewards[rank]
+        );
+        claimed_ranks += 1;
+        snapshot = reconcile(&mut harness, &snapshot, round, period, &players).await;
+    }
+    assert_eq!(claimed_ranks, PLAYERS.min(PLAYER_WINNERS_COUNT));
+
+    // Every player settles their holdings in the round, collecting their construction rewards
+    for player in &players {
+        let address = harness.player(&player.pubkey()).player_data;
+        let before: PlayerData = harness.account(address).await;
+        let balance = harness.token_balance(before.token_account).await;
+        harness.settle_previous_round(player, false).await.unwrap();
+
+        let after: PlayerData = harness.account(address).await;
+        assert_eq!(
+            harness.token_balance(after.token_account).await,
+            balance + after.collected_construction_rewards - before.collected_construction_rewards
+        );
+        assert_eq!(after.available_ores, 0);
+        snapshot = reconcile(&mut harness, &snapshot, round, period, &players).await;
+    }
+    let round_data: Round = harness.account(round).await;
+    assert_eq!(round_data.available_ores, 0);
+
+    // Every token paid out by the game is accounted for by a player
+    let game: Game = harness.account(harness.addresses.game).await;
+    let mut collected = 0;
+    for player in &players {
+        let player_data: PlayerData = harness
+            .account(harness.player(&player.pubkey()).player_data)
+            .await;
+        collected += player_data.collected_grand_prizes
+            + player_data.collected_individual_rewards
+            + player_data.collected_construction_rewards;
+    }
+    assert!(game.distributed_grand_prizes > 0 && game.distributed_individual_rewards > 0);
+    assert_eq!(
+        collected,
+        game.distributed_grand_prizes
+            + game.distributed_individual_rewards
+            + game.distributed_construction_rewards
+    );
+}